@@ -0,0 +1,76 @@
+#!/usr/bin/env python3
+"""
+Refresh the end-of-life dates in data/advisories.json from endoflife.date.
+
+Usage:
+    python scripts/sync_eol.py
+
+Every product with an "endoflife" slug gets its "eol" list rebuilt from
+https://endoflife.date/api/<slug>.json, keeping only cycles whose EOL date
+has been announced. CVE entries ("vulns") are curated by hand and left
+untouched.
+
+After running, rebuild psnet: cargo build
+"""
+
+import json
+import os
+import sys
+import urllib.request
+
+API_BASE = "https://endoflife.date/api"
+
+def fetch_json(url):
+    """Fetch JSON from a URL."""
+    try:
+        req = urllib.request.Request(url, headers={"User-Agent": "psnet-sync/1.0"})
+        with urllib.request.urlopen(req, timeout=15) as resp:
+            return json.loads(resp.read().decode())
+    except Exception as e:
+        print(f"  Warning: Could not fetch {url}: {e}", file=sys.stderr)
+        return None
+
+def convert_cycles(cycles):
+    """
+    endoflife.date reports "eol" as a date string, or a boolean when no date
+    is known. Only dated cycles are kept; psnet compares them to today.
+    """
+    out = []
+    for c in cycles:
+        eol = c.get("eol")
+        cycle = str(c.get("cycle", "")).strip()
+        if not cycle or not isinstance(eol, str):
+            continue
+        out.append({"cycle": cycle, "eol": eol})
+    out.sort(key=lambda c: [int(p) if p.isdigit() else 0 for p in c["cycle"].split(".")])
+    return out
+
+def main():
+    script_dir = os.path.dirname(os.path.abspath(__file__))
+    data_path = os.path.join(script_dir, "..", "data", "advisories.json")
+
+    with open(data_path, encoding="utf-8") as f:
+        products = json.load(f)
+
+    updated = 0
+    for product in products:
+        slug = product.get("endoflife")
+        if not slug:
+            continue
+        print(f"Fetching {slug}...")
+        cycles = fetch_json(f"{API_BASE}/{slug}.json")
+        if not isinstance(cycles, list):
+            continue
+        eol = convert_cycles(cycles)
+        if eol != product.get("eol"):
+            product["eol"] = eol
+            updated += 1
+
+    with open(data_path, "w", encoding="utf-8") as f:
+        json.dump(products, f, indent=2, ensure_ascii=False)
+        f.write("\n")
+
+    print(f"\nUpdated EOL dates for {updated} product(s) in {data_path}")
+
+if __name__ == "__main__":
+    main()
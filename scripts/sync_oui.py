@@ -0,0 +1,78 @@
+#!/usr/bin/env python3
+"""
+Rebuild data/oui.txt from the four IEEE registries.
+
+Usage:
+    python scripts/sync_oui.py
+
+Downloads MA-L (oui.csv), MA-M (mam.csv), MA-S (oui36.csv) and CID
+(cid.csv) and writes one "HEX<TAB>Vendor" line per assignment, where HEX is
+6, 7 or 9 digits. The "IEEE Registration Authority" placeholders for the
+MA-L blocks that are split into MA-M / MA-S assignments are left out; psnet
+would only ever show that name for them.
+
+If any registry cannot be fetched, data/oui.txt is left unchanged.
+
+After running, rebuild psnet: cargo build
+"""
+
+import csv
+import io
+import os
+import sys
+import urllib.request
+
+REGISTRIES = [
+    ("MA-L", "https://standards-oui.ieee.org/oui/oui.csv"),
+    ("MA-M", "https://standards-oui.ieee.org/oui28/mam.csv"),
+    ("MA-S", "https://standards-oui.ieee.org/oui36/oui36.csv"),
+    ("CID", "https://standards-oui.ieee.org/cid/cid.csv"),
+]
+
+PLACEHOLDER = "IEEE Registration Authority"
+
+def fetch_text(url):
+    """Fetch a text file from a URL."""
+    try:
+        req = urllib.request.Request(url, headers={"User-Agent": "psnet-sync/1.0"})
+        with urllib.request.urlopen(req, timeout=60) as resp:
+            return resp.read().decode("utf-8", errors="replace")
+    except Exception as e:
+        print(f"  Warning: Could not fetch {url}: {e}", file=sys.stderr)
+        return None
+
+def convert_registry(text):
+    """(hex, vendor) pairs from one registry CSV."""
+    out = []
+    for row in csv.DictReader(io.StringIO(text)):
+        hex_ = (row.get("Assignment") or "").strip().upper()
+        vendor = " ".join((row.get("Organization Name") or "").split())
+        if len(hex_) not in (6, 7, 9) or not all(c in "0123456789ABCDEF" for c in hex_):
+            continue
+        if not vendor or vendor == PLACEHOLDER:
+            continue
+        out.append((hex_, vendor))
+    return out
+
+def main():
+    script_dir = os.path.dirname(os.path.abspath(__file__))
+    data_path = os.path.join(script_dir, "..", "data", "oui.txt")
+
+    entries = {}
+    for name, url in REGISTRIES:
+        print(f"Fetching {name}...")
+        text = fetch_text(url)
+        if text is None:
+            sys.exit(f"Could not fetch the {name} registry; {data_path} left unchanged")
+        converted = convert_registry(text)
+        print(f"  {len(converted)} assignments")
+        entries.update(converted)
+
+    with open(data_path, "w", encoding="utf-8", newline="\n") as f:
+        for hex_, vendor in sorted(entries.items(), key=lambda e: (len(e[0]), e[0])):
+            f.write(f"{hex_}\t{vendor}\n")
+
+    print(f"\nWrote {len(entries)} prefixes to {data_path}")
+
+if __name__ == "__main__":
+    main()
@@ -14,6 +14,7 @@ After running, rebuild psnet: cargo build
 
 import json
 import os
+import re
 import sys
 import urllib.request
 
@@ -35,10 +36,34 @@ def fetch_json(url):
         print(f"  Warning: Could not fetch {url}: {e}", file=sys.stderr)
         return None
 
-def extract_header_sigs(tech_name, tech_data, categories):
-    """Extract header-based signatures from a Wappalyzer technology entry."""
-    sigs = []
+# Fields psnet evaluates. "js" and "dom" need a browser and are dropped.
+PATTERN_MAPS = ("headers", "cookies", "meta")
+PATTERN_LISTS = ("html", "scriptSrc")
+RELATIONS = ("implies", "excludes")
 
+# Constructs Rust's regex crate rejects: lookaround and backreferences.
+UNSUPPORTED = re.compile(r"\(\?<?[=!]|\\[1-9]")
+
+def supported(pattern):
+    """Whether psnet can compile a pattern (tags after `\\;` are not regex)."""
+    return not UNSUPPORTED.search(pattern.split("\\;")[0])
+
+def supported_patterns(value):
+    """A pattern or list of patterns without the ones psnet cannot compile."""
+    patterns = [value] if isinstance(value, str) else value
+    kept = [p for p in patterns if isinstance(p, str) and supported(p)]
+    if not kept:
+        return None
+    return kept[0] if isinstance(value, str) else kept
+
+def convert_technology(tech_name, tech_data, categories, keep_empty=False):
+    """
+    Convert a Wappalyzer technology to a psnet entry, keeping the regex
+    patterns (with their \\;version: tags) and implies/excludes as-is.
+    Patterns psnet cannot compile are dropped.
+    Returns None if the technology has nothing psnet can evaluate, unless
+    `keep_empty` asks for a name + category stub.
+    """
     # Map category IDs to labels
     cats = tech_data.get("cats", [])
     category = "Technology"
@@ -48,64 +73,123 @@ def extract_header_sigs(tech_name, tech_data, categories):
             category = categories[cat_id_str].get("name", "Technology")
             break
 
-    # Extract from "headers" field: {header_name: pattern}
-    headers = tech_data.get("headers", {})
-    for header_name, pattern in headers.items():
-        if isinstance(pattern, str):
-            # Remove regex anchors, version groups, etc. for simple substring match
-            clean_pattern = clean_wappalyzer_pattern(pattern)
-            if clean_pattern is not None:
-                sig = {
-                    "name": tech_name,
-                    "category": category,
-                    "header": header_name.lower(),
-                }
-                if clean_pattern:
-                    sig["pattern"] = clean_pattern
-                sigs.append(sig)
-
-    # Extract from "meta" field (maps to HTML meta headers)
-    meta = tech_data.get("meta", {})
-    for meta_name, pattern in meta.items():
-        if isinstance(pattern, str):
-            clean_pattern = clean_wappalyzer_pattern(pattern)
-            if clean_pattern is not None:
-                sig = {
-                    "name": tech_name,
-                    "category": category,
-                    "header": f"x-meta-{meta_name.lower()}",
-                }
-                if clean_pattern:
-                    sig["pattern"] = clean_pattern
-                sigs.append(sig)
-
-    return sigs
-
-def clean_wappalyzer_pattern(pattern):
+    entry = {"name": tech_name, "category": category}
+    for field in PATTERN_MAPS:
+        value = tech_data.get(field)
+        if isinstance(value, dict) and value:
+            patterns = {k.lower(): supported_patterns(v) for k, v in value.items() if isinstance(v, (str, list))}
+            patterns = {k: v for k, v in patterns.items() if v is not None}
+            if patterns:
+                entry[field] = patterns
+    for field in PATTERN_LISTS + RELATIONS:
+        value = tech_data.get(field)
+        if isinstance(value, str):
+            value = [value]
+        if isinstance(value, list):
+            value = [v for v in value if isinstance(v, str) and (field in RELATIONS or supported(v))]
+            if value:
+                entry[field] = value
+
+    if len(entry) == 2 and not keep_empty:
+        return None
+    return entry
+
+VERSION_CHARS = r"[\d.-]"
+
+def escape(text):
+    """Escape regex metacharacters (only those, so Rust's regex crate accepts every escape)."""
+    return re.sub(r"([\\.+*?()|\[\]{}^$])", r"\\\1", text)
+
+def flat_to_regex(pattern, version_prefix):
     """
-    Convert a Wappalyzer regex pattern to a simple substring match.
-    Returns None if the pattern is too complex for substring matching.
-    Returns empty string if pattern matches any value (header existence check).
+    Regex equivalent of a flat header signature: the lowercase `pattern`
+    substring, with the version read after `<version_prefix>/`.
     """
-    if not pattern:
-        return ""
+    pattern, prefix = pattern.lower(), version_prefix.lower()
+    if not prefix:
+        return escape(pattern)
+    if pattern == prefix:
+        return f"{escape(prefix)}(?:/({VERSION_CHARS}+))?\\;version:\\1"
+    if pattern.startswith(prefix + "/"):
+        # "squid/" or "caddy/2": the rest of the pattern is part of the version
+        rest = escape(pattern[len(prefix) + 1:])
+        return f"{escape(prefix)}/({rest}{VERSION_CHARS}*)\\;version:\\1"
+    if prefix.startswith(pattern):
+        # "imunify360" versioned as "imunify360-webshield/1.2"
+        return f"{escape(prefix)}/({VERSION_CHARS}+)|{escape(pattern)}\\;version:\\1"
+    raise ValueError(f"cannot convert pattern {pattern!r} with version prefix {version_prefix!r}")
 
-    # Remove version extraction groups: \\;version:\\1
-    if "\\;" in pattern:
-        pattern = pattern.split("\\;")[0]
-
-    # Remove confidence: \\;confidence:50
-    pattern = pattern.strip()
+def normalize(entries):
+    """
+    Merge entries into one Wappalyzer-format entry per technology. Flat
+    header signatures from older files become `headers` (or `meta`, for
+    `x-meta-<name>`) patterns; the first entry's category wins.
+    """
+    merged = {}
+    for entry in entries:
+        name = entry["name"]
+        tech = merged.setdefault(name, {"name": name, "category": entry["category"]})
+        if "header" in entry:
+            header = entry["header"].lower()
+            field, key = ("meta", header[len("x-meta-"):]) if header.startswith("x-meta-") else ("headers", header)
+            regex = flat_to_regex(entry.get("pattern", ""), entry.get("version_prefix", ""))
+            patterns = tech.setdefault(field, {}).setdefault(key, [])
+            if regex not in patterns:
+                patterns.append(regex)
+            continue
+        for field in PATTERN_MAPS:
+            for key, value in entry.get(field, {}).items():
+                patterns = tech.setdefault(field, {}).setdefault(key, [])
+                for regex in [value] if isinstance(value, str) else value:
+                    if regex not in patterns:
+                        patterns.append(regex)
+        for field in PATTERN_LISTS + RELATIONS:
+            values = tech.setdefault(field, [])
+            values.extend(v for v in entry.get(field, []) if v not in values)
+    # Single patterns are written as strings, like upstream
+    for tech in merged.values():
+        for field in PATTERN_MAPS:
+            for key, patterns in tech.get(field, {}).items():
+                if len(patterns) == 1:
+                    tech[field][key] = patterns[0]
+        for field in PATTERN_LISTS + RELATIONS:
+            if not tech.get(field):
+                tech.pop(field, None)
+    return list(merged.values())
 
-    # Skip patterns with complex regex
-    if any(c in pattern for c in ['(', ')', '|', '[', '+', '*', '{', '?', '^', '$']):
-        # Try to extract a simple literal if the regex is just "^literal"
-        if pattern.startswith('^') and not any(c in pattern[1:] for c in ['(', ')', '|', '[', '+', '*', '{', '?']):
-            return pattern[1:].rstrip('$').lower()
-        return None
+def relation_target(value):
+    """An implies/excludes value without its `\\;confidence:` tag."""
+    return value.split("\\;")[0].strip()
 
-    # Simple literal pattern
-    return pattern.lower()
+def resolve_relations(entries, upstream):
+    """
+    Make every implies/excludes target an entry: technologies upstream only
+    detects in the browser are added as name + category stubs (so implied
+    ones get their category), anything else is dropped with a warning.
+    """
+    by_name = {e["name"]: e for e in entries}
+    pending = list(entries)
+    while pending:
+        entry = pending.pop()
+        for field in RELATIONS:
+            kept = []
+            for value in entry.get(field, []):
+                target = relation_target(value)
+                if target not in by_name and target in upstream:
+                    tech_data, categories = upstream[target]
+                    stub = convert_technology(target, tech_data, categories, keep_empty=True)
+                    by_name[target] = stub
+                    entries.append(stub)
+                    pending.append(stub)
+                if target in by_name:
+                    kept.append(value)
+                else:
+                    print(f"  Warning: {entry['name']} {field} unknown technology {target!r}, dropped", file=sys.stderr)
+            if kept:
+                entry[field] = kept
+            else:
+                entry.pop(field, None)
+    return entries
 
 def main():
     output_path = os.path.join(os.path.dirname(__file__), "..", "data", "wappalyzer.json")
@@ -115,15 +199,16 @@ def main():
     if os.path.exists(output_path):
         with open(output_path, "r") as f:
             existing = json.load(f)
-        print(f"Loaded {len(existing)} existing signatures")
+        print(f"Loaded {len(existing)} existing entries")
 
     # Fetch categories
     print("Fetching Wappalyzer categories...")
     categories = fetch_json(CATEGORIES_URL) or {}
 
     # Fetch all technology files
-    all_sigs = []
-    seen = set()
+    all_entries = []
+    upstream_names = set()
+    upstream = {}
 
     for tech_file in TECH_FILES:
         url = f"{UPSTREAM_BASE}/{tech_file}"
@@ -135,47 +220,36 @@ def main():
 
         count = 0
         for tech_name, tech_data in data.items():
-            if not isinstance(tech_data, dict):
+            if not isinstance(tech_data, dict) or tech_name in upstream_names:
                 continue
-            sigs = extract_header_sigs(tech_name, tech_data, categories)
-            for sig in sigs:
-                # Dedup by (name, header, pattern)
-                key = (sig["name"], sig["header"], sig.get("pattern", ""))
-                if key not in seen:
-                    seen.add(key)
-                    all_sigs.append(sig)
-                    count += 1
-
-        print(f"{count} sigs")
-
-    # Also keep any existing signatures that aren't in upstream
-    # (custom/manual additions)
-    existing_keys = set()
-    for sig in existing:
-        key = (sig["name"], sig["header"], sig.get("pattern", ""))
-        existing_keys.add(key)
-
-    upstream_keys = set()
-    for sig in all_sigs:
-        key = (sig["name"], sig["header"], sig.get("pattern", ""))
-        upstream_keys.add(key)
-
-    custom_kept = 0
-    for sig in existing:
-        key = (sig["name"], sig["header"], sig.get("pattern", ""))
-        if key not in upstream_keys:
-            all_sigs.append(sig)
-            custom_kept += 1
+            upstream.setdefault(tech_name, (tech_data, categories))
+            entry = convert_technology(tech_name, tech_data, categories)
+            if entry:
+                upstream_names.add(tech_name)
+                all_entries.append(entry)
+                count += 1
+
+        print(f"{count} technologies")
+
+    if not upstream:
+        sys.exit(f"Could not fetch any upstream technologies; {output_path} left unchanged")
+
+    # Also keep any existing entries for technologies upstream doesn't have
+    # (custom/manual additions); flat header signatures are converted
+    kept = normalize([e for e in existing if e["name"] not in upstream_names])
+    custom_kept = len(kept)
+    all_entries.extend(kept)
+    all_entries = resolve_relations(all_entries, upstream)
 
     # Sort by category then name for readability
-    all_sigs.sort(key=lambda s: (s["category"], s["name"], s["header"]))
+    all_entries.sort(key=lambda e: (e["category"], e["name"]))
 
     # Write output
     with open(output_path, "w") as f:
-        json.dump(all_sigs, f, indent=2)
+        json.dump(all_entries, f, indent=2)
 
-    print(f"\nWrote {len(all_sigs)} signatures to {output_path}")
-    print(f"  From upstream: {len(all_sigs) - custom_kept}")
+    print(f"\nWrote {len(all_entries)} entries to {output_path}")
+    print(f"  From upstream: {len(all_entries) - custom_kept}")
     print(f"  Custom kept: {custom_kept}")
     print(f"\nRebuild psnet: cargo build")
 
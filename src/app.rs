@@ -4,18 +4,21 @@ use std::sync::{Arc, Mutex};
 use std::time::Instant;
 use std::process::Command;
 
-use crossterm::event::{KeyCode, MouseEventKind};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEventKind};
 use ratatui::layout::Rect;
 use sysinfo::Networks;
 
+use crate::keymap::{Action, Context, KeyChord, Keymap};
 use crate::network::alerts::AlertEngine;
 use crate::network::bandwidth::BandwidthTracker;
 use crate::network::capture::TrafficTracker;
 use crate::network::connections::fetch_connections;
 use crate::network::dns;
 use crate::network::firewall::FirewallManager;
+use crate::network::upnp::UpnpMonitor;
 use crate::network::geoip::GeoIpResolver;
 use crate::network::networks::NetworksScanner;
+use crate::network::os_fingerprint::OsFingerprinter;
 use crate::network::protocols::ProtocolTracker;
 use crate::network::scanner::NetworkScanner;
 use crate::network::servers::ServersScanner;
@@ -23,6 +26,7 @@ use crate::network::sniffer::PacketSniffer;
 use crate::network::speed::get_network_bytes;
 use crate::network::system_monitor::SystemMonitor;
 use crate::network::threats::ThreatDetector;
+use crate::network::traceroute::{TraceMode, Tracer};
 use crate::network::usage::UsageTracker;
 use crate::types::*;
 
@@ -79,12 +83,16 @@ pub struct App {
     pub network_scanner: NetworkScanner,
     /// Windows Firewall manager
     pub firewall_manager: FirewallManager,
+    /// Gateway UPnP port mappings (for Servers exposure)
+    pub upnp_monitor: UpnpMonitor,
     /// Threat intelligence detector
     pub threat_detector: ThreatDetector,
     /// Data plan + usage persistence
     pub usage_tracker: UsageTracker,
     /// GeoIP country resolver
     pub geoip: GeoIpResolver,
+    /// Traceroutes started from Connections or Topology
+    pub tracer: Tracer,
     /// System monitor (hosts file, proxy, WiFi, app hash)
     pub system_monitor: SystemMonitor,
     /// Non-primary networks scanner (VPN, Docker, WSL, secondary adapters)
@@ -101,6 +109,9 @@ pub struct App {
     /// Protocol activity tracker for tag cloud widget.
     pub protocol_tracker: ProtocolTracker,
 
+    /// Passive OS guesses per host from captured SYN / SYN-ACK packets.
+    pub os_fingerprints: OsFingerprinter,
+
     // Packets tab state
     pub packets_scroll: usize,
     pub packets_filter: String,
@@ -109,6 +120,14 @@ pub struct App {
 
     // Topology tab state
     pub topology_scroll: usize,
+    /// Topology shows the traced paths instead of the hub diagram.
+    pub path_view: bool,
+    /// Trace whose hops the path view lists.
+    pub trace_selected: usize,
+    /// Probe mode for new traces.
+    pub trace_mode: TraceMode,
+    /// Path typed into the topology export prompt (`e` on the Topology tab).
+    pub topology_export_path: Option<String>,
 
     // Sort state for Devices tab
     pub device_sort_column: usize,
@@ -132,8 +151,30 @@ pub struct App {
     pub incognito: bool,
     /// Hide offline devices in the Devices tab.
     pub hide_offline_devices: bool,
+    /// Show only LAN devices of this type (Devices and Topology tabs).
+    pub device_type_filter: Option<crate::types::DeviceType>,
     /// Device rename state — Some(device_index) when renaming.
     pub renaming_device: Option<usize>,
+    /// SecureOn password entry — Some(device_index) while typing.
+    pub wol_password_device: Option<usize>,
+    pub wol_password_text: String,
+    /// Path typed into the OUI import prompt (`v` on the Devices tab).
+    pub oui_import_path: Option<String>,
+    /// Path typed into the allowlist export (`e`) / import (`E`) prompts.
+    pub allowlist_export_path: Option<String>,
+    pub allowlist_import_path: Option<String>,
+    /// Devices tab shows only devices not on the allowlist.
+    pub quarantine_view: bool,
+    /// Devices tab shows the DNS-SD services sub-view instead of devices.
+    pub dnssd_view: bool,
+    /// Devices tab shows the network's DHCP servers instead of devices.
+    pub dhcp_view: bool,
+    /// Devices tab shows saved range scans instead of devices.
+    pub range_scan_view: bool,
+    /// Index into the saved range scans shown by the range scan view.
+    pub range_scan_selected: usize,
+    /// Targets typed into the range scan prompt (`n` on the Devices tab).
+    pub range_scan_input: Option<String>,
     /// Text buffer for device rename.
     pub device_rename_text: String,
 
@@ -154,6 +195,12 @@ pub struct App {
     /// Transient status message shown briefly (text, when).
     pub status_message: Option<(String, Instant)>,
 
+    /// Key bindings (`keys.toml`).
+    pub keymap: Keymap,
+    /// Whether the key bindings overlay (`?`) is open.
+    pub show_help: bool,
+    pub help_scroll: usize,
+
     // Internal
     pid_cache: PidCache,
     pub dns_cache: DnsCache,
@@ -211,15 +258,18 @@ impl App {
             networks_scanner: NetworksScanner::new(None), // primary_ip set after first scan
             servers_scanner: ServersScanner::new(),
             firewall_manager: FirewallManager::new(),
+            upnp_monitor: UpnpMonitor::new(),
             threat_detector: ThreatDetector::new(),
             usage_tracker: UsageTracker::new(),
             geoip: GeoIpResolver::new(),
+            tracer: Tracer::new(),
             system_monitor: SystemMonitor::new(),
 
             detail_popup: None,
             tick_count: 0,
 
             protocol_tracker: ProtocolTracker::new(),
+            os_fingerprints: OsFingerprinter::new(),
 
             packets_scroll: 0,
             packets_filter: String::new(),
@@ -227,6 +277,10 @@ impl App {
             packets_detail_open: false,
 
             topology_scroll: 0,
+            path_view: false,
+            trace_selected: 0,
+            trace_mode: TraceMode::Icmp,
+            topology_export_path: None,
 
             device_sort_column: 0,
             device_sort_ascending: false,
@@ -242,7 +296,19 @@ impl App {
 
             incognito: false,
             hide_offline_devices: true,
+            device_type_filter: None,
             renaming_device: None,
+            wol_password_device: None,
+            wol_password_text: String::new(),
+            oui_import_path: None,
+            allowlist_export_path: None,
+            allowlist_import_path: None,
+            quarantine_view: false,
+            dnssd_view: false,
+            dhcp_view: false,
+            range_scan_view: false,
+            range_scan_selected: 0,
+            range_scan_input: None,
             device_rename_text: String::new(),
 
             map_fading_dots: Vec::new(),
@@ -259,6 +325,10 @@ impl App {
             bg_dns_servers: Arc::new(Mutex::new(None)),
             bg_dns_ipconfig: Arc::new(Mutex::new(None)),
             status_message: None,
+
+            keymap: Keymap::default(),
+            show_help: false,
+            help_scroll: 0,
         }
     }
 
@@ -270,9 +340,39 @@ impl App {
         // Poll devices scanner streaming buffer (always — results arrive from bg thread)
         if let Some(prev_devices) = self.network_scanner.poll_results() {
             self.alert_engine.check_arp_anomalies(&self.network_scanner.devices);
-            self.alert_engine.check_device_changes(&self.network_scanner.devices, &prev_devices);
+            let scanner = &self.network_scanner;
+            let gap = scanner.schedule.schedule.presence_gap();
+            self.alert_engine.check_device_changes(&scanner.devices, &prev_devices, |mac| scanner.is_trusted(mac), gap);
+            let offline = std::mem::take(&mut self.network_scanner.new_offline);
+            if !offline.is_empty() {
+                self.alert_engine.check_devices_offline(&offline);
+            }
+            changed = true;
+        }
+        for outcome in self.network_scanner.wake_watch.check(&self.network_scanner.devices, chrono::Local::now()) {
+            let msg = match outcome {
+                crate::network::wol::WakeOutcome::Online { name, after_secs } => {
+                    format!("{} is awake ({}s after Wake-on-LAN)", name, after_secs)
+                }
+                crate::network::wol::WakeOutcome::TimedOut { name } => {
+                    format!("{} did not come online after Wake-on-LAN", name)
+                }
+            };
+            self.status_message = Some((msg, Instant::now()));
             changed = true;
         }
+        if let Some(msg) = self.network_scanner.range_scans.poll() {
+            self.range_scan_selected = 0;
+            self.status_message = Some((msg, Instant::now()));
+            changed = true;
+        }
+        // Write the inventory between scans rather than on every streamed batch
+        if !self.incognito && !self.network_scanner.is_scanning() {
+            self.network_scanner.inventory.save_if_dirty();
+            self.network_scanner.allowlist.save_if_dirty();
+            self.network_scanner.dhcp_servers.save_if_dirty();
+            self.network_scanner.range_scans.save_if_dirty();
+        }
 
         // Poll networks scanner streaming buffer — only when on Networks tab
         if self.bottom_tab == BottomTab::Networks {
@@ -391,14 +491,26 @@ impl App {
                 let is_udp = pkt.protocol == ConnProto::Udp;
                 self.protocol_tracker.record(pkt.src_port, pkt.dst_port, is_udp, self.tick_count);
             }
+            self.os_fingerprints.ingest(&new_packets);
 
-            // Extract DHCP hostnames from captured packets (option 12)
+            // Extract DHCP hostnames (option 12), client fingerprints
+            // (options 55, 57, 60) and server offers from captured packets
             for pkt in &new_packets {
                 if pkt.protocol == ConnProto::Udp
                     && (pkt.src_port == 67 || pkt.src_port == 68
                         || pkt.dst_port == 67 || pkt.dst_port == 68)
                     && !pkt.raw_payload.is_empty()
                 {
+                    if let Some(req) = crate::network::dhcp_fingerprint::parse_request(&pkt.raw_payload) {
+                        if let Some(fp) = crate::network::dhcp_fingerprint::fingerprint(&req) {
+                            self.network_scanner.record_dhcp(&req.mac, fp);
+                        }
+                    }
+                    if let (Some(reply), IpAddr::V4(source)) =
+                        (crate::network::dhcp_servers::parse_reply(&pkt.raw_payload), pkt.src_ip)
+                    {
+                        self.network_scanner.record_dhcp_reply(&reply, source);
+                    }
                     if let Some((_mac, hostname)) = crate::network::hostnames::parse_dhcp_hostname(&pkt.raw_payload) {
                         // Try to get client IP from DHCP packet fields, fall back to source IP
                         let client_ip = crate::network::hostnames::dhcp_client_ip(&pkt.raw_payload)
@@ -415,11 +527,18 @@ impl App {
                 }
             }
 
+            let rogue_dhcp = std::mem::take(&mut self.network_scanner.dhcp_servers.new_rogue);
+            if !rogue_dhcp.is_empty() {
+                self.alert_engine.check_rogue_dhcp(&rogue_dhcp);
+            }
+
             // Per-device bandwidth: correlate packets with LAN device IPs
-            // Build IP→index HashMap for O(1) lookups instead of O(n) per packet
+            // Build IP→index HashMap for O(1) lookups instead of O(n) per packet.
+            // Offline history devices keep their last IP, which DHCP may have reassigned.
             let device_ip_index: HashMap<IpAddr, usize> = self.network_scanner.devices
                 .iter()
                 .enumerate()
+                .filter(|(_, d)| d.is_online)
                 .map(|(i, d)| (d.ip, i))
                 .collect();
 
@@ -514,15 +633,31 @@ impl App {
             });
         }
 
-        // Network scanner tick — full-speed when on Devices tab, slow otherwise
-        // (still needed for alert engine: new device, device left, ARP anomaly)
+        // Network scanner tick — every 15s on the Devices tab, otherwise on the
+        // devices.json schedule (feeds presence history and device alerts)
         let on_devices_tab = self.bottom_tab == BottomTab::Devices;
-        if on_devices_tab || self.tick_count % 30 == 0 {
-            self.network_scanner.tick();
+        self.network_scanner.tick(on_devices_tab);
+        if let Some(err) = self.network_scanner.schedule.error.take() {
+            self.status_message = Some((err, Instant::now()));
         }
 
         // Servers scanner tick — always tick to collect results, scans internally throttled
         self.servers_scanner.tick();
+        if let Some(notice) = self.servers_scanner.db_notice.take() {
+            self.status_message = Some((notice, Instant::now()));
+        }
+        let listener_changes = std::mem::take(&mut self.servers_scanner.new_changes);
+        if !listener_changes.is_empty() {
+            self.alert_engine.check_listener_changes(&listener_changes);
+        }
+        let health_failures = std::mem::take(&mut self.servers_scanner.health.new_failures);
+        if !health_failures.is_empty() {
+            self.alert_engine.check_health_failures(&health_failures);
+        }
+        if !self.incognito {
+            self.servers_scanner.baseline.save_if_dirty();
+            self.servers_scanner.health.save_if_dirty();
+        }
 
         // Networks scanner tick — only when on Networks tab
         if self.bottom_tab == BottomTab::Networks {
@@ -541,6 +676,14 @@ impl App {
         // Firewall manager tick (periodic rule refresh)
         self.firewall_manager.tick();
 
+        // Exposure: bind address + firewall verdict + UPnP mapping per listener
+        self.upnp_monitor.tick();
+        self.servers_scanner.update_exposure(&self.firewall_manager, &self.upnp_monitor);
+        let new_mappings = std::mem::take(&mut self.upnp_monitor.new_mappings);
+        if !new_mappings.is_empty() {
+            self.alert_engine.check_port_mappings(&new_mappings);
+        }
+
         // Ask-to-connect mode: check new processes
         if self.firewall_manager.mode == FirewallMode::AskToConnect {
             for conn in &self.connections {
@@ -830,17 +973,32 @@ impl App {
     // ─── Input handling ──────────────────────────────────────────────────
 
     /// Handle a key press. Returns true if the app should quit.
-    pub fn handle_key(&mut self, code: KeyCode) -> bool {
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
         // Notify idle tracker of user input
         self.alert_engine.idle_tracker.on_input();
+        let chord = KeyChord::from(key);
+
+        // Help overlay: scroll keys scroll it, anything else closes it
+        if self.show_help {
+            match self.keymap.action(Context::Global, chord).map(|(_, a)| a) {
+                Some(Action::ScrollUp) => self.help_scroll = self.help_scroll.saturating_sub(1),
+                Some(Action::ScrollDown) => self.help_scroll += 1,
+                Some(Action::PageUp) => self.help_scroll = self.help_scroll.saturating_sub(20),
+                Some(Action::PageDown) => self.help_scroll += 20,
+                Some(Action::Home) => self.help_scroll = 0,
+                _ => self.show_help = false,
+            }
+            return false;
+        }
 
         // If detail popup is open, handle navigation for FirewallApp or dismiss
         if self.detail_popup.is_some() {
+            let action = self.keymap.action(Context::Popup, chord).map(|(_, a)| a);
             if let Some(DetailKind::FirewallApp(ref mut detail)) = self.detail_popup {
-                match code {
-                    KeyCode::Up => { detail.selected_action = detail.selected_action.saturating_sub(1); }
-                    KeyCode::Down => { if detail.selected_action < 3 { detail.selected_action += 1; } }
-                    KeyCode::Enter => {
+                match action {
+                    Some(Action::ScrollUp) => { detail.selected_action = detail.selected_action.saturating_sub(1); }
+                    Some(Action::ScrollDown) => { if detail.selected_action < 3 { detail.selected_action += 1; } }
+                    Some(Action::Confirm) => {
                         if detail.selected_action < 3 {
                             let name = detail.app_name.clone();
                             let path = detail.app_path.clone();
@@ -855,35 +1013,35 @@ impl App {
                             self.detail_popup = None;
                         }
                     }
-                    KeyCode::Esc | KeyCode::Char('q') => { self.detail_popup = None; }
+                    Some(Action::Close) => { self.detail_popup = None; }
                     _ => {}
                 }
             } else {
                 // Server detail popup: o/y/p shortcuts for exe path
-                let server_exe_path = if let Some(DetailKind::Server { ref exe_path, .. }) = self.detail_popup {
-                    if !exe_path.is_empty() { Some(exe_path.clone()) } else { None }
+                let server_exe_path = if let Some(DetailKind::Server(ref d)) = self.detail_popup {
+                    if !d.exe_path.is_empty() { Some(d.exe_path.clone()) } else { None }
                 } else {
                     None
                 };
 
-                match code {
-                    KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => {
+                match action {
+                    Some(Action::Close) | Some(Action::Confirm) => {
                         self.detail_popup = None;
                     }
-                    KeyCode::Char('o') | KeyCode::Char('O') if server_exe_path.is_some() => {
+                    Some(Action::OpenFolder) if server_exe_path.is_some() => {
                         let path = server_exe_path.unwrap();
                         let _ = Command::new("explorer.exe")
                             .arg(format!("/select,{}", path))
                             .spawn();
                         self.status_message = Some((format!("Opened folder: {}", path), Instant::now()));
                     }
-                    KeyCode::Char('y') | KeyCode::Char('Y') if server_exe_path.is_some() => {
+                    Some(Action::CopyPath) if server_exe_path.is_some() => {
                         let path = server_exe_path.unwrap();
                         if copy_to_clipboard(&path) {
                             self.status_message = Some((format!("Copied: {}", path), Instant::now()));
                         }
                     }
-                    KeyCode::Char('p') | KeyCode::Char('P') if server_exe_path.is_some() => {
+                    Some(Action::CopyFolder) if server_exe_path.is_some() => {
                         let path = server_exe_path.unwrap();
                         let folder = std::path::Path::new(&path)
                             .parent()
@@ -899,8 +1057,47 @@ impl App {
             return false;
         }
 
-        match code {
-            KeyCode::Char('q') | KeyCode::Char('Q') => {
+        // Text prompts on the Devices tab take every key, 'q' included
+        if self.bottom_tab == BottomTab::Devices && self.devices_text_input() {
+            self.handle_devices_prompt_key(key.code);
+            return false;
+        }
+        if self.bottom_tab == BottomTab::Topology && self.topology_export_path.is_some() {
+            self.handle_topology_export_key(key.code);
+            return false;
+        }
+
+        // The tab's own bindings win over global ones
+        let action = match self.keymap.action(Context::Tab(self.bottom_tab), chord) {
+            Some((Context::Global, action)) => return self.handle_global_action(action),
+            Some((_, action)) => Some(action),
+            // Unbound Ctrl / Alt chords are not typed into filters
+            None if key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => return false,
+            None => None,
+        };
+        match self.bottom_tab {
+            BottomTab::Connections => self.handle_connections_key(action, key.code),
+            BottomTab::Servers => self.handle_servers_key(action, key.code),
+            BottomTab::Packets => self.handle_packets_key(action, key.code),
+            BottomTab::Firewall => self.handle_firewall_key(action, key.code),
+            BottomTab::Alerts => self.handle_alerts_key(action),
+            tab => {
+                let Some(action) = action else { return false };
+                match tab {
+                    BottomTab::Dashboard => self.handle_dashboard_key(action),
+                    BottomTab::Topology => self.handle_topology_key(action),
+                    BottomTab::Devices => self.handle_devices_key(action),
+                    _ => self.handle_networks_key(action),
+                }
+            }
+        }
+        false
+    }
+
+    /// Run a `[global]` action. Returns true if the app should quit.
+    fn handle_global_action(&mut self, action: Action) -> bool {
+        match action {
+            Action::Quit => {
                 if !self.incognito {
                     self.alert_engine.save_alerts();
                     self.alert_engine.save_known_state(
@@ -910,40 +1107,36 @@ impl App {
                         self.network_scanner.devices.len(),
                     );
                     self.usage_tracker.save();
+                    self.network_scanner.inventory.save_if_dirty();
+                    self.network_scanner.allowlist.save_if_dirty();
+                    self.network_scanner.dhcp_servers.save_if_dirty();
+                    self.network_scanner.range_scans.save_if_dirty();
                 }
                 return true;
             }
-            KeyCode::Tab => {
+            Action::NextTab => {
                 self.bottom_tab = self.bottom_tab.next();
             }
-            KeyCode::BackTab => {
+            Action::PrevTab => {
                 self.bottom_tab = self.bottom_tab.prev();
             }
-            KeyCode::Char('i') | KeyCode::Char('I') => {
+            Action::Incognito => {
                 self.incognito = !self.incognito;
             }
-            KeyCode::Enter => {
+            Action::Detail => {
                 self.open_detail_popup();
             }
-            KeyCode::Up => self.scroll_up(1),
-            KeyCode::Down => self.scroll_down(1),
-            KeyCode::PageUp => self.scroll_up(20),
-            KeyCode::PageDown => self.scroll_down(20),
-            KeyCode::Home => self.scroll_home(),
-            KeyCode::End => self.scroll_end(),
-            _ => {
-                match self.bottom_tab {
-                    BottomTab::Dashboard => self.handle_dashboard_key(code),
-                    BottomTab::Connections => self.handle_connections_key(code),
-                    BottomTab::Servers => self.handle_servers_key(code),
-                    BottomTab::Packets => self.handle_packets_key(code),
-                    BottomTab::Topology => self.handle_topology_key(code),
-                    BottomTab::Alerts => self.handle_alerts_key(code),
-                    BottomTab::Firewall => self.handle_firewall_key(code),
-                    BottomTab::Devices => self.handle_devices_key(code),
-                    BottomTab::Networks => self.handle_networks_key(code),
-                }
+            Action::ScrollUp => self.scroll_up(1),
+            Action::ScrollDown => self.scroll_down(1),
+            Action::PageUp => self.scroll_up(20),
+            Action::PageDown => self.scroll_down(20),
+            Action::Home => self.scroll_home(),
+            Action::End => self.scroll_end(),
+            Action::Help => {
+                self.show_help = true;
+                self.help_scroll = 0;
             }
+            _ => {}
         }
         false
     }
@@ -959,6 +1152,7 @@ impl App {
                 filtered.get(selected).map(|c| DetailKind::Connection((*c).clone()))
             }
             BottomTab::Servers => {
+                if self.servers_scanner.show_changes || self.servers_scanner.show_upnp { return; }
                 let visible = self.servers_scanner.filtered_servers();
                 if visible.is_empty() { return; }
                 let selected = self.servers_scanner.scroll_offset.min(visible.len() - 1);
@@ -967,7 +1161,7 @@ impl App {
                         .filter(|c| c.local_port == s.port && !matches!(c.state.as_ref(), Some(TcpState::Listen)))
                         .count() as u32;
                     let has_tls = s.details.contains("TLS: yes");
-                    Some(DetailKind::Server {
+                    Some(DetailKind::Server(Box::new(ServerDetail {
                         kind_label: s.display_name(),
                         kind_icon: s.server_kind.icon().to_string(),
                         category: s.server_kind.category().label().to_string(),
@@ -990,7 +1184,21 @@ impl App {
                         tls_detected: has_tls,
                         category_color: s.server_kind.category().color(),
                         detected_techs: s.detected_techs.iter().map(|t| (t.name.clone(), t.category.clone(), t.version.clone())).collect(),
-                    })
+                        protocol_details: s.protocol_details.clone(),
+                        exposure: s.exposure.as_ref().map(|e| ServerExposureDetail {
+                            level: e.level.label().to_string(),
+                            color: e.level.color(),
+                            reasons: e.reasons.clone(),
+                            risks: e.risks.iter().map(|r| (r.severity, r.message.clone())).collect(),
+                        }),
+                        advisories: s.advisories.iter().map(|a| (a.severity, a.title(), a.detail())).collect(),
+                        health: self.servers_scanner.health.for_port(s.port).map(|h| ServerHealthDetail {
+                            check: h.check.describe(),
+                            interval_secs: h.check.interval_secs,
+                            history: h.history.iter().copied().collect(),
+                            last_error: h.last_error.clone(),
+                        }),
+                    })))
                 } else {
                     None
                 }
@@ -1012,12 +1220,12 @@ impl App {
             }
             BottomTab::Devices => {
                 let devices: Vec<&crate::types::LanDevice> = self.network_scanner.devices.iter()
-                    .filter(|d| !self.hide_offline_devices || d.is_online)
+                    .filter(|d| self.device_visible(d))
                     .collect();
                 let total = devices.len();
                 if total == 0 { return; }
                 let selected = self.device_scroll.min(total - 1);
-                devices.get(selected).map(|d| DetailKind::Device((*d).clone()))
+                devices.get(selected).map(|d| DetailKind::Device(Box::new((*d).clone())))
             }
             BottomTab::Networks => {
                 let display_rows = crate::ui::networks::build_display_rows(self);
@@ -1031,7 +1239,7 @@ impl App {
                         None
                     }
                     crate::ui::networks::NetworksRow::Device { device, .. } => {
-                        Some(DetailKind::Device((*device).clone()))
+                        Some(DetailKind::Device(Box::new((*device).clone())))
                     }
                 }
             }
@@ -1098,23 +1306,23 @@ impl App {
         };
     }
 
-    fn handle_connections_key(&mut self, code: KeyCode) {
-        match code {
-            KeyCode::Char('l') | KeyCode::Char('L') => {
+    fn handle_connections_key(&mut self, action: Option<Action>, code: KeyCode) {
+        match action {
+            Some(Action::ToggleListen) => {
                 self.show_listen = !self.show_listen;
             }
-            KeyCode::Char('x') | KeyCode::Char('X') => {
+            Some(Action::ToggleLocalhost) => {
                 self.hide_localhost_conn = !self.hide_localhost_conn;
             }
             // Sort keys mapped to displayed column order:
             // 1=Process, 2=Remote Host, 3=Service, 4=State, 5=Local
-            KeyCode::Char('1') => self.toggle_sort(6),
-            KeyCode::Char('2') => self.toggle_sort(3),
-            KeyCode::Char('3') => self.toggle_sort(4),
-            KeyCode::Char('4') => self.toggle_sort(5),
-            KeyCode::Char('5') => self.toggle_sort(2),
+            Some(Action::Sort1) => self.toggle_sort(6),
+            Some(Action::Sort2) => self.toggle_sort(3),
+            Some(Action::Sort3) => self.toggle_sort(4),
+            Some(Action::Sort4) => self.toggle_sort(5),
+            Some(Action::Sort5) => self.toggle_sort(2),
             // Block selected connection's process via firewall
-            KeyCode::Char('b') | KeyCode::Char('B') => {
+            Some(Action::Block) => {
                 let filtered = self.filtered_connections();
                 if let Some(conn) = filtered.get(self.conn_scroll) {
                     if !conn.process_name.is_empty() && !conn.process_name.starts_with("PID:") {
@@ -1127,16 +1335,30 @@ impl App {
                     }
                 }
             }
-            KeyCode::Backspace => { self.filter_text.pop(); }
-            KeyCode::Esc => { self.filter_text.clear(); }
-            KeyCode::Char(c) => {
-                if c == 'f' || c == 'F' {
-                    // 'f' starts filter mode
-                } else {
-                    self.filter_text.push(c);
+            // Traceroute to the selected connection's remote host
+            Some(Action::Trace) => {
+                let target = self.filtered_connections().get(self.conn_scroll).and_then(|conn| {
+                    let ip = conn.remote_addr?;
+                    let label = conn.dns_hostname.clone().unwrap_or_else(|| ip.to_string());
+                    Some((ip, label, conn.remote_port))
+                });
+                if let Some((ip, label, port)) = target {
+                    self.start_trace(ip, label, port);
                 }
             }
-            _ => {}
+            Some(Action::ClearFilter) => { self.filter_text.clear(); }
+            Some(_) => {}
+            None => match code {
+                KeyCode::Backspace => { self.filter_text.pop(); }
+                KeyCode::Char(c) => {
+                    if c == 'f' || c == 'F' {
+                        // 'f' starts filter mode
+                    } else {
+                        self.filter_text.push(c);
+                    }
+                }
+                _ => {}
+            },
         }
     }
 
@@ -1150,13 +1372,58 @@ impl App {
         if path.is_empty() { None } else { Some(path.clone()) }
     }
 
-    fn handle_servers_key(&mut self, code: KeyCode) {
-        match code {
-            KeyCode::Char('s') | KeyCode::Char('S') => {
+    fn handle_servers_key(&mut self, action: Option<Action>, code: KeyCode) {
+        match action {
+            Some(Action::Scan) => {
                 self.servers_scanner.start_scan();
             }
+            // Reload fingerprint / Wappalyzer override files
+            Some(Action::ReloadDatabases) => {
+                let summary = self.servers_scanner.reload_databases();
+                self.status_message = Some((summary, Instant::now()));
+            }
+            // Toggle the "changes since baseline" view
+            Some(Action::ToggleChanges) => {
+                self.servers_scanner.show_changes = !self.servers_scanner.show_changes;
+                self.servers_scanner.show_upnp = false;
+                self.servers_scanner.scroll_offset = 0;
+            }
+            // Toggle the router's UPnP port mapping table (refreshed on open)
+            Some(Action::ToggleUpnp) => {
+                self.servers_scanner.show_upnp = !self.servers_scanner.show_upnp;
+                self.servers_scanner.show_changes = false;
+                self.servers_scanner.scroll_offset = 0;
+                if self.servers_scanner.show_upnp {
+                    self.upnp_monitor.refresh();
+                }
+            }
+            // Accept the current listeners as the new baseline
+            Some(Action::AcceptBaseline) => {
+                self.servers_scanner.accept_baseline();
+                self.status_message = Some((
+                    format!("Baseline updated: {} listeners", self.servers_scanner.baseline.entries.len()),
+                    Instant::now(),
+                ));
+            }
+            // Add or remove a health check for the selected server
+            Some(Action::HealthCheck) => {
+                let visible = self.servers_scanner.filtered_servers();
+                if visible.is_empty() || self.servers_scanner.show_changes || self.servers_scanner.show_upnp {
+                    return;
+                }
+                let server = visible[self.servers_scanner.scroll_offset.min(visible.len() - 1)].clone();
+                let msg = if self.servers_scanner.health.toggle(&server) {
+                    let check = self.servers_scanner.health.for_port(server.port)
+                        .map(|c| c.check.describe())
+                        .unwrap_or_default();
+                    format!("Health check added for {} :{} ({})", server.display_name(), server.port, check)
+                } else {
+                    format!("Health check removed for {} :{}", server.display_name(), server.port)
+                };
+                self.status_message = Some((msg, Instant::now()));
+            }
             // Open containing folder in Explorer
-            KeyCode::Char('o') | KeyCode::Char('O') => {
+            Some(Action::OpenFolder) => {
                 if let Some(path) = self.selected_server_exe_path() {
                     let _ = Command::new("explorer.exe")
                         .arg(format!("/select,{}", path))
@@ -1167,7 +1434,7 @@ impl App {
                 }
             }
             // Copy full executable path to clipboard
-            KeyCode::Char('y') | KeyCode::Char('Y') => {
+            Some(Action::CopyPath) => {
                 if let Some(path) = self.selected_server_exe_path() {
                     let copied = copy_to_clipboard(&path);
                     if copied {
@@ -1180,7 +1447,7 @@ impl App {
                 }
             }
             // Copy containing folder path to clipboard
-            KeyCode::Char('p') | KeyCode::Char('P') => {
+            Some(Action::CopyFolder) => {
                 if let Some(path) = self.selected_server_exe_path() {
                     let folder = std::path::Path::new(&path)
                         .parent()
@@ -1196,28 +1463,31 @@ impl App {
                     self.status_message = Some(("No executable path available".into(), Instant::now()));
                 }
             }
-            KeyCode::Char('1') => {
+            Some(Action::Sort1) => {
                 self.servers_scanner.sort_column = 0;
                 self.servers_scanner.sort_ascending = !self.servers_scanner.sort_ascending;
             }
-            KeyCode::Char('2') => {
+            Some(Action::Sort2) => {
                 self.servers_scanner.sort_column = 1;
                 self.servers_scanner.sort_ascending = !self.servers_scanner.sort_ascending;
             }
-            KeyCode::Char('3') => {
+            Some(Action::Sort3) => {
                 self.servers_scanner.sort_column = 3;
                 self.servers_scanner.sort_ascending = !self.servers_scanner.sort_ascending;
             }
-            KeyCode::Backspace => { self.servers_scanner.filter_text.pop(); }
-            KeyCode::Esc => { self.servers_scanner.filter_text.clear(); }
-            KeyCode::Char(c) if !c.is_ascii_digit() => {
-                self.servers_scanner.filter_text.push(c);
-            }
-            _ => {}
+            Some(Action::ClearFilter) => { self.servers_scanner.filter_text.clear(); }
+            Some(_) => {}
+            None => match code {
+                KeyCode::Backspace => { self.servers_scanner.filter_text.pop(); }
+                KeyCode::Char(c) if !c.is_ascii_digit() => {
+                    self.servers_scanner.filter_text.push(c);
+                }
+                _ => {}
+            },
         }
     }
 
-    fn handle_alerts_key(&mut self, code: KeyCode) {
+    fn handle_alerts_key(&mut self, action: Option<Action>) {
         // Dismiss banners on any key press
         if self.alert_engine.last_visit_summary.is_some() {
             self.alert_engine.last_visit_summary = None;
@@ -1231,16 +1501,16 @@ impl App {
         let active_cats = crate::ui::alerts::active_categories(&self.alert_engine.alerts);
         let num_panes = active_cats.len();
 
-        match code {
-            KeyCode::Char('r') | KeyCode::Char('R') => {
+        match action {
+            Some(Action::MarkRead) => {
                 self.alert_engine.mark_all_read();
             }
-            KeyCode::Char('c') | KeyCode::Char('C') => {
+            Some(Action::Clear) => {
                 self.alert_engine.alerts.clear();
                 self.alert_engine.unread_count = 0;
                 self.alert_pane_scrolls = [0; 6];
             }
-            KeyCode::Char('z') | KeyCode::Char('Z') => {
+            Some(Action::Snooze) => {
                 if self.alert_engine.is_snoozed() {
                     self.alert_engine.unsnooze();
                 } else {
@@ -1248,7 +1518,7 @@ impl App {
                 }
             }
             // Navigate between panes
-            KeyCode::Left => {
+            Some(Action::PrevPane) => {
                 if num_panes > 0 {
                     if self.alert_focused_pane == 0 {
                         self.alert_focused_pane = num_panes - 1;
@@ -1257,7 +1527,7 @@ impl App {
                     }
                 }
             }
-            KeyCode::Right => {
+            Some(Action::NextPane) => {
                 if num_panes > 0 {
                     self.alert_focused_pane = (self.alert_focused_pane + 1) % num_panes;
                 }
@@ -1316,54 +1586,66 @@ impl App {
         })
     }
 
-    fn handle_firewall_key(&mut self, code: KeyCode) {
-        match code {
-            KeyCode::Char('r') | KeyCode::Char('R') => {
+    fn handle_firewall_key(&mut self, action: Option<Action>, code: KeyCode) {
+        match action {
+            Some(Action::Refresh) => {
                 self.firewall_manager.refresh_rules();
             }
-            KeyCode::Char('a') | KeyCode::Char('A') => {
+            Some(Action::AskToConnect) => {
                 self.firewall_manager.toggle_ask_to_connect();
             }
-            KeyCode::Char('d') | KeyCode::Char('D') => {
+            Some(Action::DefaultPolicy) => {
                 self.firewall_manager.toggle_default_policy();
             }
-            KeyCode::Char('x') | KeyCode::Char('X') => {
+            Some(Action::ResetRules) => {
                 self.firewall_manager.reset_all_psnet_rules();
             }
             // Sort keys (merged from Usage tab)
-            KeyCode::Char('1') => {
+            Some(Action::Sort1) => {
                 self.bandwidth_tracker.sort_column = 0;
                 self.bandwidth_tracker.sort_ascending = !self.bandwidth_tracker.sort_ascending;
             }
-            KeyCode::Char('2') => {
+            Some(Action::Sort2) => {
                 self.bandwidth_tracker.sort_column = 1;
                 self.bandwidth_tracker.sort_ascending = !self.bandwidth_tracker.sort_ascending;
             }
-            KeyCode::Char('3') => {
+            Some(Action::Sort3) => {
                 self.bandwidth_tracker.sort_column = 2;
                 self.bandwidth_tracker.sort_ascending = !self.bandwidth_tracker.sort_ascending;
             }
-            KeyCode::Char('4') => {
+            Some(Action::Sort4) => {
                 self.bandwidth_tracker.sort_column = 4;
                 self.bandwidth_tracker.sort_ascending = !self.bandwidth_tracker.sort_ascending;
             }
             // Export CSV
-            KeyCode::Char('e') | KeyCode::Char('E') => {
+            Some(Action::Export) => {
                 if let Some(data_dir) = dirs::data_dir() {
                     let path = data_dir.join("psnet").join("usage_export.csv");
                     let _ = self.usage_tracker.export_csv(&path.to_string_lossy());
                 }
             }
-            KeyCode::Backspace => { self.firewall_manager.filter_text.pop(); }
-            KeyCode::Esc => { self.firewall_manager.filter_text.clear(); }
-            KeyCode::Char(c) => {
-                self.firewall_manager.filter_text.push(c);
-            }
-            _ => {}
+            Some(Action::ClearFilter) => { self.firewall_manager.filter_text.clear(); }
+            Some(_) => {}
+            None => match code {
+                KeyCode::Backspace => { self.firewall_manager.filter_text.pop(); }
+                KeyCode::Char(c) => {
+                    self.firewall_manager.filter_text.push(c);
+                }
+                _ => {}
+            },
         }
     }
 
-    fn handle_devices_key(&mut self, code: KeyCode) {
+    /// Is a rename, password or import prompt open on the Devices tab?
+    fn devices_text_input(&self) -> bool {
+        self.renaming_device.is_some() || self.wol_password_device.is_some() || self.oui_import_path.is_some()
+            || self.allowlist_export_path.is_some() || self.allowlist_import_path.is_some()
+            || self.range_scan_input.is_some()
+    }
+
+    /// Keys typed into an open Devices prompt (rename, SecureOn password,
+    /// import / export path, range scan targets).
+    fn handle_devices_prompt_key(&mut self, code: KeyCode) {
         // Rename mode intercepts all input
         if let Some(idx) = self.renaming_device {
             match code {
@@ -1386,25 +1668,217 @@ impl App {
             }
             return;
         }
-        match code {
-            KeyCode::Char('s') | KeyCode::Char('S') => {
+        if let Some(ref mut path) = self.oui_import_path {
+            match code {
+                KeyCode::Enter => {
+                    let path = path.trim().trim_matches('"').to_string();
+                    let msg = match crate::network::oui::import(std::path::Path::new(&path)) {
+                        Ok(summary) => {
+                            self.network_scanner.refresh_vendors();
+                            format!("Imported OUI registry: {}", summary)
+                        }
+                        Err(e) => format!("OUI import failed: {}", e),
+                    };
+                    self.status_message = Some((msg, Instant::now()));
+                    self.oui_import_path = None;
+                }
+                KeyCode::Esc => self.oui_import_path = None,
+                KeyCode::Backspace => { path.pop(); }
+                KeyCode::Char(c) => path.push(c),
+                _ => {}
+            }
+            return;
+        }
+        if let Some(ref mut path) = self.allowlist_export_path {
+            match code {
+                KeyCode::Enter => {
+                    let path = path.trim().trim_matches('"').to_string();
+                    let network = self.network_scanner.network_id();
+                    let msg = match self.network_scanner.allowlist.export(&network, std::path::Path::new(&path)) {
+                        Ok(summary) => format!("Exported {}", summary),
+                        Err(e) => format!("Allowlist export failed: {}", e),
+                    };
+                    self.status_message = Some((msg, Instant::now()));
+                    self.allowlist_export_path = None;
+                }
+                KeyCode::Esc => self.allowlist_export_path = None,
+                KeyCode::Backspace => { path.pop(); }
+                KeyCode::Char(c) => path.push(c),
+                _ => {}
+            }
+            return;
+        }
+        if let Some(ref mut path) = self.allowlist_import_path {
+            match code {
+                KeyCode::Enter => {
+                    let path = path.trim().trim_matches('"').to_string();
+                    let network = self.network_scanner.network_id();
+                    let msg = match self.network_scanner.allowlist.import(&network, std::path::Path::new(&path)) {
+                        Ok(summary) => format!("Imported allowlist: {}", summary),
+                        Err(e) => format!("Allowlist import failed: {}", e),
+                    };
+                    self.status_message = Some((msg, Instant::now()));
+                    self.allowlist_import_path = None;
+                }
+                KeyCode::Esc => self.allowlist_import_path = None,
+                KeyCode::Backspace => { path.pop(); }
+                KeyCode::Char(c) => path.push(c),
+                _ => {}
+            }
+            return;
+        }
+        if let Some(ref mut input) = self.range_scan_input {
+            match code {
+                KeyCode::Enter => {
+                    let input = input.trim().to_string();
+                    let msg = match self.network_scanner.start_range_scan(&input) {
+                        Ok(summary) => format!("Range scan started: {}", summary),
+                        Err(e) => format!("Range scan not started: {}", e),
+                    };
+                    self.status_message = Some((msg, Instant::now()));
+                    self.range_scan_input = None;
+                }
+                KeyCode::Esc => self.range_scan_input = None,
+                KeyCode::Backspace => { input.pop(); }
+                KeyCode::Char(c) => input.push(c),
+                _ => {}
+            }
+            return;
+        }
+        if let Some(idx) = self.wol_password_device {
+            match code {
+                KeyCode::Enter => {
+                    let text = self.wol_password_text.trim().to_string();
+                    if !text.is_empty() && crate::network::wol::parse_mac(&text).is_none() {
+                        self.status_message = Some(("SecureOn password must be 6 hex bytes, e.g. 01:23:45:67:89:AB".to_string(), Instant::now()));
+                        return;
+                    }
+                    let mac = self.network_scanner.devices.get(idx).map(|d| d.mac.clone());
+                    if let Some(mac) = mac {
+                        self.network_scanner.inventory.set_wol_password(&mac, &text);
+                    }
+                    self.wol_password_device = None;
+                    self.wol_password_text.clear();
+                }
+                KeyCode::Esc => {
+                    self.wol_password_device = None;
+                    self.wol_password_text.clear();
+                }
+                KeyCode::Backspace => { self.wol_password_text.pop(); }
+                KeyCode::Char(c) => { self.wol_password_text.push(c); }
+                _ => {}
+            }
+        }
+    }
+
+    fn handle_devices_key(&mut self, action: Action) {
+        match action {
+            Action::Scan => {
                 self.network_scanner.start_scan();
             }
-            KeyCode::Char('o') | KeyCode::Char('O') => {
+            Action::Wake => {
+                if let Some(idx) = self.selected_device_index() {
+                    let d = &self.network_scanner.devices[idx];
+                    let mac = d.mac.clone();
+                    let name = d.custom_name.clone()
+                        .or_else(|| d.hostname.clone())
+                        .unwrap_or_else(|| d.ip.to_string());
+                    let msg = match self.network_scanner.wake(&mac, &name) {
+                        Ok(()) => format!("Wake-on-LAN sent to {} — watching for it", name),
+                        Err(e) => format!("Wake-on-LAN failed: {}", e),
+                    };
+                    self.status_message = Some((msg, Instant::now()));
+                }
+            }
+            Action::SecureOn => {
+                if let Some(idx) = self.selected_device_index() {
+                    let mac = &self.network_scanner.devices[idx].mac;
+                    self.wol_password_text = self.network_scanner.inventory.get(mac)
+                        .and_then(|r| r.wol_password.clone())
+                        .unwrap_or_default();
+                    self.wol_password_device = Some(idx);
+                }
+            }
+            Action::ImportOui => {
+                self.oui_import_path = Some(String::new());
+            }
+            Action::ToggleOffline => {
                 self.hide_offline_devices = !self.hide_offline_devices;
                 self.device_scroll = 0;
             }
-            KeyCode::Char('r') | KeyCode::Char('R') => {
-                // Map filtered index back to real device index
-                let filtered: Vec<usize> = self.network_scanner.devices.iter()
-                    .enumerate()
-                    .filter(|(_, d)| !self.hide_offline_devices || d.is_online)
-                    .map(|(i, _)| i)
-                    .collect();
-                let total = filtered.len();
-                if total > 0 {
-                    let sel = self.device_scroll.min(total - 1);
-                    let real_idx = filtered[sel];
+            Action::Trust => {
+                if let Some(idx) = self.selected_device_index() {
+                    let d = &self.network_scanner.devices[idx];
+                    if d.mac.is_empty() {
+                        self.status_message = Some(("No MAC address yet — cannot trust this device".to_string(), Instant::now()));
+                        return;
+                    }
+                    let mac = d.mac.clone();
+                    let name = d.custom_name.clone().or_else(|| d.hostname.clone());
+                    let label = name.clone().unwrap_or_else(|| mac.clone());
+                    let msg = if self.network_scanner.toggle_trusted(&mac, name.as_deref()) {
+                        format!("{} is now trusted on this network", label)
+                    } else {
+                        format!("{} removed from the allowlist", label)
+                    };
+                    self.status_message = Some((msg, Instant::now()));
+                }
+            }
+            Action::ToggleServices => {
+                self.dnssd_view = !self.dnssd_view;
+                self.dhcp_view = false;
+                self.range_scan_view = false;
+                self.device_scroll = 0;
+            }
+            Action::ToggleDhcp => {
+                self.dhcp_view = !self.dhcp_view;
+                self.dnssd_view = false;
+                self.range_scan_view = false;
+                self.device_scroll = 0;
+            }
+            Action::RangeScan => {
+                // Start from the targets of the scan on screen, to re-run or tweak it
+                let scans = &self.network_scanner.range_scans.scans;
+                let prefill = scans.get(self.range_scan_selected)
+                    .filter(|_| self.range_scan_view)
+                    .map(|s| if s.name == s.targets { s.targets.clone() } else { format!("{}: {}", s.name, s.targets) })
+                    .unwrap_or_default();
+                self.range_scan_input = Some(prefill);
+                self.range_scan_view = true;
+                self.dnssd_view = false;
+                self.dhcp_view = false;
+            }
+            Action::ToggleRangeScans => {
+                self.range_scan_view = !self.range_scan_view;
+                self.dnssd_view = false;
+                self.dhcp_view = false;
+                self.device_scroll = 0;
+            }
+            Action::Prev if self.range_scan_view => {
+                self.range_scan_selected = self.range_scan_selected.saturating_sub(1);
+                self.device_scroll = 0;
+            }
+            Action::Next if self.range_scan_view => {
+                let count = self.network_scanner.range_scans.scans.len();
+                self.range_scan_selected = (self.range_scan_selected + 1).min(count.saturating_sub(1));
+                self.device_scroll = 0;
+            }
+            Action::Cancel if self.range_scan_view && self.network_scanner.range_scans.cancel() => {
+                self.status_message = Some(("Stopping range scan — resolving the hosts found so far".to_string(), Instant::now()));
+            }
+            Action::ToggleQuarantine => {
+                self.quarantine_view = !self.quarantine_view;
+                self.device_scroll = 0;
+            }
+            Action::Export => {
+                self.allowlist_export_path = Some(String::new());
+            }
+            Action::Import => {
+                self.allowlist_import_path = Some(String::new());
+            }
+            Action::TypeFilter => self.cycle_device_type_filter(),
+            Action::Rename => {
+                if let Some(real_idx) = self.selected_device_index() {
                     let current = self.network_scanner.devices.get(real_idx)
                         .map(|d| {
                             d.custom_name.as_deref()
@@ -1435,57 +1909,166 @@ impl App {
         non_bt + if bt > 0 { 1 } else { 0 } + if self.bluetooth_expanded { bt } else { 0 }
     }
 
-    fn handle_networks_key(&mut self, code: KeyCode) {
-        match code {
-            KeyCode::Char('s') | KeyCode::Char('S') => {
+    fn handle_networks_key(&mut self, action: Action) {
+        match action {
+            Action::Scan => {
                 self.networks_scanner.start_scan();
             }
-            KeyCode::Char('b') | KeyCode::Char('B') => {
+            Action::ToggleBluetooth => {
                 self.bluetooth_expanded = !self.bluetooth_expanded;
             }
             _ => {}
         }
     }
 
-    fn handle_packets_key(&mut self, code: KeyCode) {
-        match code {
-            KeyCode::Char(' ') => {
+    fn handle_packets_key(&mut self, action: Option<Action>, code: KeyCode) {
+        match action {
+            Some(Action::Pause) => {
                 self.packets_paused = !self.packets_paused;
             }
-            KeyCode::Char('d') | KeyCode::Char('D') => {
+            Some(Action::ToggleDetail) => {
                 self.packets_detail_open = !self.packets_detail_open;
             }
-            KeyCode::Char('c') | KeyCode::Char('C') => {
+            Some(Action::Clear) => {
                 if let Ok(mut lock) = self.sniffer.snippets.lock() {
                     lock.clear();
                 }
             }
-            KeyCode::Backspace => { self.packets_filter.pop(); }
-            KeyCode::Esc => {
+            Some(Action::ClearFilter) => {
                 if !self.packets_filter.is_empty() {
                     self.packets_filter.clear();
                 } else {
                     self.packets_detail_open = false;
                 }
             }
-            KeyCode::Char(c) => {
-                self.packets_filter.push(c);
+            Some(_) => {}
+            None => match code {
+                KeyCode::Backspace => { self.packets_filter.pop(); }
+                KeyCode::Char(c) => {
+                    self.packets_filter.push(c);
+                }
+                _ => {}
+            },
+        }
+    }
+
+    /// Keys typed into the topology export path prompt.
+    fn handle_topology_export_key(&mut self, code: KeyCode) {
+        if let Some(ref mut path) = self.topology_export_path {
+            match code {
+                KeyCode::Enter => {
+                    let path = path.trim().trim_matches('"').to_string();
+                    let graph = crate::ui::topology::graph(self);
+                    let msg = match crate::network::topology::export(&graph, std::path::Path::new(&path)) {
+                        Ok(summary) => format!("Exported {}", summary),
+                        Err(e) => format!("Topology export failed: {}", e),
+                    };
+                    self.status_message = Some((msg, Instant::now()));
+                    self.topology_export_path = None;
+                }
+                KeyCode::Esc => self.topology_export_path = None,
+                KeyCode::Backspace => { path.pop(); }
+                KeyCode::Char(c) => path.push(c),
+                _ => {}
+            }
+        }
+    }
+
+    fn handle_topology_key(&mut self, action: Action) {
+        // Scrolling is handled by scroll_up/scroll_down
+        match action {
+            Action::Export => {
+                self.topology_export_path = Some(String::new());
+            }
+            Action::TypeFilter => self.cycle_device_type_filter(),
+            Action::Trace if !self.path_view => {
+                if let Some((ip, label)) = crate::ui::topology::selected_remote(self) {
+                    self.start_trace(ip, label, None);
+                }
+            }
+            Action::TogglePaths => {
+                self.path_view = !self.path_view;
+                self.topology_scroll = 0;
+            }
+            Action::TraceMode => {
+                self.trace_mode = self.trace_mode.next();
+                let msg = format!("Traceroute mode: {}", self.trace_mode.label());
+                self.status_message = Some((msg, Instant::now()));
+            }
+            Action::Prev if self.path_view => {
+                self.trace_selected = self.trace_selected.saturating_sub(1);
+            }
+            Action::Next if self.path_view => {
+                let count = self.tracer.snapshot().len();
+                self.trace_selected = (self.trace_selected + 1).min(count.saturating_sub(1));
             }
             _ => {}
         }
     }
 
-    fn handle_topology_key(&mut self, _code: KeyCode) {
-        // Topology tab currently only uses scroll (handled by scroll_up/scroll_down)
+    /// Trace the path to `ip` in the current mode and show it in the
+    /// Topology path view. `port` is the TCP-SYN destination.
+    fn start_trace(&mut self, ip: IpAddr, label: String, port: Option<u16>) {
+        let msg = match self.tracer.start(ip, label.clone(), self.trace_mode, port) {
+            Ok(index) => {
+                self.trace_selected = index;
+                self.path_view = true;
+                self.bottom_tab = BottomTab::Topology;
+                format!("Tracing {} ({})", label, self.trace_mode.label())
+            }
+            Err(e) => format!("Traceroute not started: {}", e),
+        };
+        self.status_message = Some((msg, Instant::now()));
+    }
+
+    /// Index into `network_scanner.devices` of the selected Devices row.
+    fn selected_device_index(&self) -> Option<usize> {
+        // Map filtered index back to real device index
+        let filtered: Vec<usize> = self.network_scanner.devices.iter()
+            .enumerate()
+            .filter(|(_, d)| self.device_visible(d))
+            .map(|(i, _)| i)
+            .collect();
+        let total = filtered.len();
+        if total == 0 {
+            return None;
+        }
+        Some(filtered[self.device_scroll.min(total - 1)])
+    }
+
+    /// Whether a LAN device passes the offline, device-type and quarantine filters.
+    pub fn device_visible(&self, d: &crate::types::LanDevice) -> bool {
+        (!self.hide_offline_devices || d.is_online)
+            && self.device_type_filter.is_none_or(|t| d.device_type == t)
+            && (!self.quarantine_view || !self.network_scanner.is_trusted(&d.mac))
+    }
+
+    /// Step the device-type filter through the types currently on the LAN,
+    /// then back to showing everything.
+    fn cycle_device_type_filter(&mut self) {
+        let present: Vec<crate::types::DeviceType> = crate::types::DeviceType::all()
+            .iter()
+            .copied()
+            .filter(|t| self.network_scanner.devices.iter().any(|d| d.device_type == *t))
+            .collect();
+        let next = match self.device_type_filter {
+            None => 0,
+            Some(cur) => present.iter().position(|t| *t == cur).map_or(0, |i| i + 1),
+        };
+        self.device_type_filter = present.get(next).copied();
+        self.device_scroll = 0;
+        self.topology_scroll = 0;
+        let label = self.device_type_filter.map_or("All", |t| t.label());
+        self.status_message = Some((format!("Device type: {}", label), Instant::now()));
     }
 
-    fn handle_dashboard_key(&mut self, code: KeyCode) {
-        match code {
-            KeyCode::Char('1') => self.dashboard_time_range = DashboardTimeRange::Minutes5,
-            KeyCode::Char('2') => self.dashboard_time_range = DashboardTimeRange::Minutes15,
-            KeyCode::Char('3') => self.dashboard_time_range = DashboardTimeRange::Hour1,
-            KeyCode::Char('4') => self.dashboard_time_range = DashboardTimeRange::Hours24,
-            KeyCode::Char('m') | KeyCode::Char('M') => self.map_fullscreen = !self.map_fullscreen,
+    fn handle_dashboard_key(&mut self, action: Action) {
+        match action {
+            Action::Range5m => self.dashboard_time_range = DashboardTimeRange::Minutes5,
+            Action::Range15m => self.dashboard_time_range = DashboardTimeRange::Minutes15,
+            Action::Range1h => self.dashboard_time_range = DashboardTimeRange::Hour1,
+            Action::Range24h => self.dashboard_time_range = DashboardTimeRange::Hours24,
+            Action::ToggleMap => self.map_fullscreen = !self.map_fullscreen,
             _ => {}
         }
     }
@@ -1821,8 +2404,8 @@ impl App {
                 }
             }
             BottomTab::Devices => {
-                // Columns: Status(10), IP(22), Hostname(14), MAC(18), Vendor(20), Ports(22), First(9), Last(9), Recv(18), Sent(18), Details(Min)
-                let col = column_from_x(x, &[10, 22, 14, 18, 20, 22, 9, 9, 18, 18, 0], frame_w);
+                // Columns: Status(10), Type(14), IP(22), Hostname(14), MAC(18), Vendor(20), Ports(22), First(11), Last(11), Recv(18), Sent(18), Details(Min)
+                let col = column_from_x(x, &[10, 14, 16, 22, 14, 18, 20, 22, 34, 11, 11, 18, 18, 0], frame_w);
                 if let Some(col) = col {
                     if self.device_sort_column == col {
                         self.device_sort_ascending = !self.device_sort_ascending;
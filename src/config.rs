@@ -0,0 +1,22 @@
+//! Where psnet keeps user configuration: `%APPDATA%\psnet\` on Windows.
+//!
+//! Theme, key bindings, health checks, the scan schedule and the detection
+//! database overrides all live there; files that are re-read on change use
+//! [`modified`] as their stamp.
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Path of a config file: %APPDATA%/psnet/<file_name>
+pub fn path(file_name: &str) -> PathBuf {
+    if let Some(config_dir) = dirs::config_dir() {
+        config_dir.join("psnet").join(file_name)
+    } else {
+        PathBuf::from(format!("psnet_{}", file_name))
+    }
+}
+
+/// Modification time, used to detect edits. None when the file does not exist.
+pub fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
@@ -0,0 +1,641 @@
+//! Key bindings.
+//!
+//! Key presses are looked up as chords (a key plus Ctrl / Alt) in the
+//! active [`Keymap`] and turned into named [`Action`]s. Bindings live in
+//! sections: `global`, `popup` (while a detail popup is open) and one per
+//! tab, which wins over `global` on that tab. The built-in bindings are
+//! `data/keys.toml`; the `vim` preset adds `data/keys_vim.toml` on top.
+//! `%APPDATA%\psnet\keys.toml` picks a preset and rebinds actions:
+//!
+//! ```toml
+//! preset = "vim"
+//!
+//! [servers]
+//! copy-path = ["y", "ctrl+c"]   # replaces the action's default keys
+//! health-check = []             # unbinds it
+//! ```
+//!
+//! A key rebound to another action in the same section is taken from the
+//! action that had it. That, and tab keys that hide a global binding, are
+//! reported on the status line.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+
+use crate::config;
+use crate::types::BottomTab;
+
+const CONFIG_FILE: &str = "keys.toml";
+static DEFAULT_TOML: &str = include_str!("../data/keys.toml");
+static VIM_TOML: &str = include_str!("../data/keys_vim.toml");
+
+// ─── Actions ─────────────────────────────────────────────────────────────────
+
+macro_rules! actions {
+    ($($variant:ident => $name:literal, $desc:literal;)*) => {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub enum Action {
+            $($variant,)*
+        }
+
+        impl Action {
+            /// Name used in `keys.toml`.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $name,)*
+                }
+            }
+
+            /// One-line description for the help overlay.
+            pub fn description(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $desc,)*
+                }
+            }
+
+            pub fn parse(name: &str) -> Option<Self> {
+                match name {
+                    $($name => Some(Self::$variant),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+actions! {
+    // Global
+    Quit => "quit", "Quit";
+    NextTab => "next-tab", "Next tab";
+    PrevTab => "prev-tab", "Previous tab";
+    Incognito => "incognito", "Toggle incognito (nothing is saved)";
+    Detail => "detail", "Open the detail popup";
+    ScrollUp => "scroll-up", "Move up";
+    ScrollDown => "scroll-down", "Move down";
+    PageUp => "page-up", "Page up";
+    PageDown => "page-down", "Page down";
+    Home => "home", "Jump to the top";
+    End => "end", "Jump to the bottom";
+    Help => "help", "Show these key bindings";
+    // Popup
+    Close => "close", "Close the popup";
+    Confirm => "confirm", "Apply the selected action, or close";
+    OpenFolder => "open-folder", "Open the executable's folder in Explorer";
+    CopyPath => "copy-path", "Copy the executable path";
+    CopyFolder => "copy-folder", "Copy the executable's folder";
+    // Shared by several tabs
+    Sort1 => "sort-1", "Sort by column 1";
+    Sort2 => "sort-2", "Sort by column 2";
+    Sort3 => "sort-3", "Sort by column 3";
+    Sort4 => "sort-4", "Sort by column 4";
+    Sort5 => "sort-5", "Sort by column 5";
+    ClearFilter => "clear-filter", "Clear the filter";
+    Scan => "scan", "Scan now";
+    Clear => "clear", "Clear";
+    Export => "export", "Export";
+    Import => "import", "Import";
+    TypeFilter => "type-filter", "Cycle the device-type filter";
+    Trace => "trace", "Traceroute to the selected host";
+    Prev => "prev", "Previous item";
+    Next => "next", "Next item";
+    Cancel => "cancel", "Cancel";
+    // Dashboard
+    Range5m => "range-5m", "Traffic graph: last 5 minutes";
+    Range15m => "range-15m", "Traffic graph: last 15 minutes";
+    Range1h => "range-1h", "Traffic graph: last hour";
+    Range24h => "range-24h", "Traffic graph: last 24 hours";
+    ToggleMap => "toggle-map", "Toggle the full-screen world map";
+    // Connections
+    ToggleListen => "toggle-listen", "Show / hide LISTEN connections";
+    ToggleLocalhost => "toggle-localhost", "Show / hide localhost connections";
+    Block => "block", "Block the selected connection's app";
+    // Servers
+    ReloadDatabases => "reload-databases", "Reload fingerprint / Wappalyzer overrides";
+    ToggleChanges => "toggle-changes", "Toggle the changes-since-baseline view";
+    ToggleUpnp => "toggle-upnp", "Toggle the UPnP port mapping view";
+    AcceptBaseline => "accept-baseline", "Accept the current listeners as the baseline";
+    HealthCheck => "health-check", "Add / remove a health check";
+    // Packets
+    Pause => "pause", "Pause / resume capture";
+    ToggleDetail => "toggle-detail", "Toggle the packet detail pane";
+    // Topology
+    TogglePaths => "toggle-paths", "Toggle the traceroute path view";
+    TraceMode => "trace-mode", "Cycle traceroute mode (ICMP, UDP, TCP-SYN)";
+    // Alerts
+    MarkRead => "mark-read", "Mark all alerts read";
+    Snooze => "snooze", "Snooze / unsnooze alerts for 5 minutes";
+    PrevPane => "prev-pane", "Previous pane";
+    NextPane => "next-pane", "Next pane";
+    // Firewall
+    Refresh => "refresh", "Refresh firewall rules";
+    AskToConnect => "ask-to-connect", "Toggle ask-to-connect";
+    DefaultPolicy => "default-policy", "Toggle the default policy (allow / deny all)";
+    ResetRules => "reset-rules", "Remove all psnet firewall rules";
+    // Devices
+    Rename => "rename", "Rename the selected device";
+    Wake => "wake", "Wake the selected device (Wake-on-LAN)";
+    SecureOn => "secureon", "Set the selected device's SecureOn password";
+    ImportOui => "import-oui", "Import IEEE OUI registry CSVs";
+    ToggleOffline => "toggle-offline", "Show / hide offline devices";
+    Trust => "trust", "Trust / untrust the selected device";
+    ToggleServices => "toggle-services", "Toggle the DNS-SD services view";
+    ToggleDhcp => "toggle-dhcp", "Toggle the DHCP servers view";
+    RangeScan => "range-scan", "Scan a target range";
+    ToggleRangeScans => "toggle-range-scans", "Toggle the saved range scans view";
+    ToggleQuarantine => "toggle-quarantine", "Toggle the untrusted-devices view";
+    // Networks
+    ToggleBluetooth => "toggle-bluetooth", "Expand / collapse Bluetooth devices";
+}
+
+impl Action {
+    /// Description where an action means something more specific than its
+    /// generic [`Action::description`].
+    pub fn describe(&self, ctx: Context) -> &'static str {
+        use BottomTab::*;
+        match (self, ctx) {
+            (Self::Sort1, Context::Tab(Connections)) => "Sort by process",
+            (Self::Sort2, Context::Tab(Connections)) => "Sort by remote host",
+            (Self::Sort3, Context::Tab(Connections)) => "Sort by service",
+            (Self::Sort4, Context::Tab(Connections)) => "Sort by state",
+            (Self::Sort5, Context::Tab(Connections)) => "Sort by local port",
+            (Self::Sort1, Context::Tab(Servers)) => "Sort by port",
+            (Self::Sort2, Context::Tab(Servers)) => "Sort by service",
+            (Self::Sort3, Context::Tab(Servers)) => "Sort by process",
+            (Self::Sort1, Context::Tab(Firewall)) => "Sort by app",
+            (Self::Sort2, Context::Tab(Firewall)) => "Sort by download",
+            (Self::Sort3, Context::Tab(Firewall)) => "Sort by upload",
+            (Self::Sort4, Context::Tab(Firewall)) => "Sort by connections",
+            (Self::Clear, Context::Tab(Packets)) => "Clear captured packets",
+            (Self::Clear, Context::Tab(Alerts)) => "Clear all alerts",
+            (Self::Export, Context::Tab(Topology)) => "Export the diagram (DOT, Mermaid or JSON)",
+            (Self::Export, Context::Tab(Firewall)) => "Export usage as CSV",
+            (Self::Export, Context::Tab(Devices)) => "Export the allowlist (JSON or CSV)",
+            (Self::Import, Context::Tab(Devices)) => "Import an allowlist (JSON or CSV)",
+            (Self::Prev, Context::Tab(Topology)) => "Previous traceroute",
+            (Self::Next, Context::Tab(Topology)) => "Next traceroute",
+            (Self::Prev, Context::Tab(Devices)) => "Previous range scan",
+            (Self::Next, Context::Tab(Devices)) => "Next range scan",
+            (Self::Cancel, Context::Tab(Devices)) => "Stop the running range scan",
+            (Self::ScrollUp, Context::Popup) | (Self::ScrollDown, Context::Popup) => "Select a firewall action",
+            _ => self.description(),
+        }
+    }
+}
+
+// ─── Contexts ────────────────────────────────────────────────────────────────
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Context {
+    Global,
+    Popup,
+    Tab(BottomTab),
+}
+
+impl Context {
+    /// Section name in `keys.toml`.
+    pub fn section(&self) -> String {
+        match self {
+            Self::Global => "global".to_string(),
+            Self::Popup => "popup".to_string(),
+            Self::Tab(tab) => tab.label().to_lowercase(),
+        }
+    }
+
+    pub fn parse(section: &str) -> Option<Self> {
+        match section {
+            "global" => Some(Self::Global),
+            "popup" => Some(Self::Popup),
+            _ => BottomTab::ALL.iter().find(|t| t.label().eq_ignore_ascii_case(section)).map(|t| Self::Tab(*t)),
+        }
+    }
+}
+
+// ─── Chords ──────────────────────────────────────────────────────────────────
+
+/// A key plus its Ctrl / Alt modifiers. Shift is folded into the key
+/// (`G`, `Shift+Tab`) so it matches however the terminal reports it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let code = match code {
+            KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::Char(c.to_ascii_uppercase()),
+            other => other,
+        };
+        let modifiers = match code {
+            KeyCode::Char(_) | KeyCode::BackTab => modifiers.difference(KeyModifiers::SHIFT),
+            _ => modifiers,
+        };
+        Self { code, modifiers: modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT) }
+    }
+
+    /// Parse `q`, `G`, `ctrl+d`, `shift+tab`, `pagedown`, `f1`, `space`, ...
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        // A lone '+' or a chord ending in '+' ("ctrl++") binds the plus key
+        let (mods_part, key) = match s.strip_suffix("++") {
+            Some(rest) => (Some(rest), "+"),
+            None if s == "+" => (None, "+"),
+            None => match s.rsplit_once('+') {
+                Some((m, k)) => (Some(m), k),
+                None => (None, s),
+            },
+        };
+        let mut modifiers = KeyModifiers::NONE;
+        for m in mods_part.into_iter().flat_map(|m| m.split('+')) {
+            modifiers |= match m.trim().to_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "meta" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return None,
+            };
+        }
+        let code = if key.chars().count() == 1 {
+            KeyCode::Char(key.chars().next()?)
+        } else {
+            let lower = key.to_lowercase();
+            match lower.as_str() {
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "backspace" | "bs" => KeyCode::Backspace,
+                "space" => KeyCode::Char(' '),
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "pageup" | "pgup" => KeyCode::PageUp,
+                "pagedown" | "pgdn" => KeyCode::PageDown,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "insert" | "ins" => KeyCode::Insert,
+                "delete" | "del" => KeyCode::Delete,
+                _ => {
+                    let n: u8 = lower.strip_prefix('f')?.parse().ok()?;
+                    if !(1..=24).contains(&n) {
+                        return None;
+                    }
+                    KeyCode::F(n)
+                }
+            }
+        };
+        Some(Self::new(code, modifiers))
+    }
+
+    /// Short label for hints and the help overlay.
+    pub fn label(&self) -> String {
+        let key = match self.code {
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::Enter => "Enter".to_string(),
+            KeyCode::Esc => "Esc".to_string(),
+            KeyCode::Tab => "Tab".to_string(),
+            KeyCode::BackTab => "Shift+Tab".to_string(),
+            KeyCode::Backspace => "Backspace".to_string(),
+            KeyCode::Up => "\u{2191}".to_string(),
+            KeyCode::Down => "\u{2193}".to_string(),
+            KeyCode::Left => "\u{2190}".to_string(),
+            KeyCode::Right => "\u{2192}".to_string(),
+            KeyCode::PageUp => "PgUp".to_string(),
+            KeyCode::PageDown => "PgDn".to_string(),
+            KeyCode::Home => "Home".to_string(),
+            KeyCode::End => "End".to_string(),
+            KeyCode::Insert => "Ins".to_string(),
+            KeyCode::Delete => "Del".to_string(),
+            KeyCode::F(n) => format!("F{}", n),
+            other => format!("{:?}", other),
+        };
+        let mut label = String::new();
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            label.push_str("Ctrl+");
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            label.push_str("Alt+");
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            label.push_str("Shift+");
+        }
+        label.push_str(&key);
+        label
+    }
+}
+
+impl From<KeyEvent> for KeyChord {
+    fn from(key: KeyEvent) -> Self {
+        Self::new(key.code, key.modifiers)
+    }
+}
+
+// ─── Keymap ──────────────────────────────────────────────────────────────────
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Preset {
+    Default,
+    Vim,
+}
+
+impl Preset {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::Vim => "vim",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "default" => Some(Self::Default),
+            "vim" => Some(Self::Vim),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Keys {
+    One(String),
+    Many(Vec<String>),
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct KeysFile {
+    preset: Option<String>,
+    #[serde(flatten)]
+    sections: BTreeMap<String, BTreeMap<String, Keys>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Keymap {
+    pub preset: Preset,
+    /// Chord → action, per section.
+    bindings: HashMap<(Context, KeyChord), Action>,
+    /// Action → chords, per section, in binding order.
+    keys: HashMap<(Context, Action), Vec<KeyChord>>,
+    /// Actions each section offers, in the order of `data/keys.toml`.
+    actions: HashMap<Context, Vec<Action>>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::preset(Preset::Default)
+    }
+}
+
+impl Keymap {
+    /// Built-in bindings for a preset.
+    pub fn preset(preset: Preset) -> Self {
+        let mut map = Keymap { preset, bindings: HashMap::new(), keys: HashMap::new(), actions: HashMap::new() };
+        let mut warnings = Vec::new();
+        map.apply(DEFAULT_TOML, true, &mut warnings);
+        if preset == Preset::Vim {
+            map.apply(VIM_TOML, false, &mut warnings);
+        }
+        debug_assert!(warnings.is_empty(), "{:?}", warnings);
+        map
+    }
+
+    /// Action bound to a key: the context's own binding, then `[global]`
+    /// (the popup context does not fall back). Returns where it was found.
+    pub fn action(&self, ctx: Context, chord: KeyChord) -> Option<(Context, Action)> {
+        if let Some(a) = self.bindings.get(&(ctx, chord)) {
+            return Some((ctx, *a));
+        }
+        if ctx == Context::Popup {
+            return None;
+        }
+        self.bindings.get(&(Context::Global, chord)).map(|a| (Context::Global, *a))
+    }
+
+    pub fn keys(&self, ctx: Context, action: Action) -> &[KeyChord] {
+        self.keys.get(&(ctx, action)).map_or(&[], |v| v.as_slice())
+    }
+
+    /// Label of the first key bound to an action, for hint bars.
+    pub fn label(&self, ctx: Context, action: Action) -> Option<String> {
+        self.keys(ctx, action).first().map(|c| c.label())
+    }
+
+    /// Every key of an action joined with " / ", for the help overlay.
+    pub fn labels(&self, ctx: Context, action: Action) -> String {
+        self.keys(ctx, action).iter().map(|c| c.label()).collect::<Vec<_>>().join(" / ")
+    }
+
+    /// Actions available in a section, in display order.
+    pub fn actions(&self, ctx: Context) -> &[Action] {
+        self.actions.get(&ctx).map_or(&[], |v| v.as_slice())
+    }
+
+    /// Apply one bindings file. `defining` is set for `data/keys.toml`,
+    /// which declares the actions each section offers; later files may
+    /// only rebind those.
+    fn apply(&mut self, content: &str, defining: bool, warnings: &mut Vec<String>) {
+        let file: KeysFile = match toml::from_str(content) {
+            Ok(f) => f,
+            Err(e) => {
+                warnings.push(format!("invalid TOML ({})", e.message().trim()));
+                return;
+            }
+        };
+        for (section, entries) in &file.sections {
+            let Some(ctx) = Context::parse(section) else {
+                warnings.push(format!("unknown section [{}]", section));
+                continue;
+            };
+            // Sections list their actions in declaration order of `Action`
+            let mut ordered: Vec<(&String, &Keys)> = entries.iter().collect();
+            if defining {
+                ordered.sort_by_key(|(name, _)| Action::parse(name));
+            }
+            for (name, keys) in ordered {
+                let Some(action) = Action::parse(name) else {
+                    warnings.push(format!("unknown action '{}' in [{}]", name, section));
+                    continue;
+                };
+                if defining {
+                    self.actions.entry(ctx).or_default().push(action);
+                } else if !self.actions(ctx).contains(&action) {
+                    warnings.push(format!("'{}' is not available in [{}]", name, section));
+                    continue;
+                }
+                let list: Vec<&String> = match keys {
+                    Keys::One(k) => vec![k],
+                    Keys::Many(ks) => ks.iter().collect(),
+                };
+                let mut chords = Vec::new();
+                for k in list {
+                    match KeyChord::parse(k) {
+                        Some(c) if !chords.contains(&c) => chords.push(c),
+                        Some(_) => {}
+                        None => warnings.push(format!("bad key '{}' for {} in [{}]", k, name, section)),
+                    }
+                }
+                self.bind(ctx, action, chords, warnings);
+            }
+        }
+    }
+
+    /// Replace an action's keys in a section. A key that belonged to another
+    /// action there is taken from it.
+    fn bind(&mut self, ctx: Context, action: Action, chords: Vec<KeyChord>, warnings: &mut Vec<String>) {
+        for old in self.keys.remove(&(ctx, action)).unwrap_or_default() {
+            self.bindings.remove(&(ctx, old));
+        }
+        for chord in &chords {
+            if let Some(prev) = self.bindings.insert((ctx, *chord), action) {
+                if let Some(list) = self.keys.get_mut(&(ctx, prev)) {
+                    list.retain(|c| c != chord);
+                }
+                warnings.push(format!(
+                    "[{}] {} moved from {} to {}",
+                    ctx.section(), chord.label(), prev.name(), action.name()
+                ));
+            }
+        }
+        self.keys.insert((ctx, action), chords);
+    }
+
+    /// Keymap from a user bindings file, plus warnings for everything that
+    /// could not be applied.
+    pub fn parse(content: &str) -> (Self, Vec<String>) {
+        let mut warnings = Vec::new();
+        let preset = match toml::from_str::<KeysFile>(content) {
+            Ok(f) => f.preset,
+            Err(e) => {
+                warnings.push(format!("invalid TOML ({})", e.message().trim()));
+                return (Self::default(), warnings);
+            }
+        };
+        let preset = match preset.as_deref() {
+            None => Preset::Default,
+            Some(name) => Preset::parse(name).unwrap_or_else(|| {
+                warnings.push(format!("unknown preset '{}' (use default or vim)", name));
+                Preset::Default
+            }),
+        };
+        let mut map = Self::preset(preset);
+        map.apply(content, false, &mut warnings);
+        for (ctx, chord, global) in map.shadowed() {
+            warnings.push(format!("[{}] {} hides global {}", ctx.section(), chord.label(), global.name()));
+        }
+        (map, warnings)
+    }
+
+    /// Chords a tab binds that `[global]` also binds — the tab's binding
+    /// wins there, so the global action is unreachable on that tab.
+    pub fn shadowed(&self) -> Vec<(Context, KeyChord, Action)> {
+        let mut out: Vec<(Context, KeyChord, Action)> = self.bindings.iter()
+            .filter(|((ctx, _), _)| matches!(ctx, Context::Tab(_)))
+            .filter_map(|((ctx, chord), _)| {
+                self.bindings.get(&(Context::Global, *chord)).map(|g| (*ctx, *chord, *g))
+            })
+            .collect();
+        out.sort_by_key(|(ctx, chord, _)| (ctx.section(), chord.label()));
+        out
+    }
+}
+
+/// Load `keys.toml` from the config dir. Returns the keymap and a status
+/// line message when the file had problems.
+pub fn load() -> (Keymap, Option<String>) {
+    load_from(&config::path(CONFIG_FILE))
+}
+
+fn load_from(path: &Path) -> (Keymap, Option<String>) {
+    match std::fs::read_to_string(path) {
+        Ok(content) => {
+            let (map, warnings) = Keymap::parse(&content);
+            let msg = (!warnings.is_empty()).then(|| format!("{}: {}", CONFIG_FILE, warnings.join("; ")));
+            (map, msg)
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => (Keymap::default(), None),
+        Err(e) => (Keymap::default(), Some(format!("{}: {}", CONFIG_FILE, e))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chord(s: &str) -> KeyChord {
+        KeyChord::parse(s).unwrap()
+    }
+
+    #[test]
+    fn test_parse_chords() {
+        assert_eq!(chord("q"), KeyChord::new(KeyCode::Char('q'), KeyModifiers::NONE));
+        assert_eq!(chord("ctrl+d"), KeyChord::new(KeyCode::Char('d'), KeyModifiers::CONTROL));
+        assert_eq!(chord("shift+tab"), KeyChord::new(KeyCode::BackTab, KeyModifiers::NONE));
+        assert_eq!(chord("shift+g"), chord("G"));
+        assert_eq!(chord("PgDn").code, KeyCode::PageDown);
+        assert_eq!(chord("f5").code, KeyCode::F(5));
+        assert_eq!(chord("+").code, KeyCode::Char('+'));
+        assert_eq!(chord("ctrl++"), KeyChord::new(KeyCode::Char('+'), KeyModifiers::CONTROL));
+        assert!(KeyChord::parse("hyper+x").is_none());
+        assert!(KeyChord::parse("f99").is_none());
+        // Terminals report 'G' with or without SHIFT
+        assert_eq!(KeyChord::new(KeyCode::Char('G'), KeyModifiers::SHIFT), chord("G"));
+        assert_eq!(chord("ctrl+d").label(), "Ctrl+d");
+    }
+
+    #[test]
+    fn test_presets_have_no_collisions() {
+        for preset in [Preset::Default, Preset::Vim] {
+            let map = Keymap::preset(preset);
+            assert!(map.shadowed().is_empty(), "{:?}: {:?}", preset, map.shadowed());
+        }
+        let vim = Keymap::preset(Preset::Vim);
+        let servers = Context::Tab(BottomTab::Servers);
+        assert_eq!(vim.action(servers, chord("j")), Some((Context::Global, Action::ScrollDown)));
+        assert_eq!(vim.action(servers, chord("o")), Some((servers, Action::OpenFolder)));
+        assert_eq!(Keymap::default().action(servers, chord("j")), None);
+    }
+
+    #[test]
+    fn test_contexts_keep_popup_keys_apart() {
+        let map = Keymap::default();
+        let topology = Context::Tab(BottomTab::Topology);
+        let devices = Context::Tab(BottomTab::Devices);
+        assert_eq!(map.action(Context::Popup, chord("p")), Some((Context::Popup, Action::CopyFolder)));
+        assert_eq!(map.action(topology, chord("p")), Some((topology, Action::TogglePaths)));
+        assert_eq!(map.action(devices, chord("o")), Some((devices, Action::ToggleOffline)));
+        // The popup does not fall back to global bindings
+        assert_eq!(map.action(Context::Popup, chord("tab")), None);
+        assert_eq!(map.action(devices, chord("q")), Some((Context::Global, Action::Quit)));
+    }
+
+    #[test]
+    fn test_user_file() {
+        let (map, warnings) = Keymap::parse(
+            "preset = \"vim\"\n[servers]\ncopy-path = [\"c\", \"ctrl+y\"]\nhealth-check = []\n[devices]\nquit = \"x\"\n[nowhere]\nx = \"y\"\n",
+        );
+        let servers = Context::Tab(BottomTab::Servers);
+        assert_eq!(map.preset, Preset::Vim);
+        assert_eq!(map.action(servers, chord("c")), Some((servers, Action::CopyPath)));
+        assert_eq!(map.action(servers, chord("ctrl+y")), Some((servers, Action::CopyPath)));
+        assert_eq!(map.action(servers, chord("y")), None);
+        assert_eq!(map.action(servers, chord("h")), None);
+        // 'c' was taken from toggle-changes, which keeps 'C'
+        assert_eq!(map.label(servers, Action::ToggleChanges).as_deref(), Some("C"));
+        assert_eq!(warnings.len(), 3, "{:?}", warnings);
+        assert!(warnings.iter().any(|w| w.contains("c moved from toggle-changes to copy-path")));
+        assert!(warnings.iter().any(|w| w.contains("'quit' is not available in [devices]")));
+        assert!(warnings.iter().any(|w| w.contains("unknown section [nowhere]")));
+
+        let (_, warnings) = Keymap::parse("[packets]\nclear = \"q\"\n");
+        assert_eq!(warnings, vec!["[packets] q hides global quit".to_string()]);
+
+        let (map, warnings) = Keymap::parse("[global\n");
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(map.preset, Preset::Default);
+    }
+}
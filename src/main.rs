@@ -1,4 +1,6 @@
 mod app;
+mod config;
+mod keymap;
 mod network;
 mod types;
 mod ui;
@@ -29,9 +31,18 @@ fn main() -> io::Result<()> {
     std::thread::spawn(|| { crate::network::oui::warm(); });
     std::thread::spawn(|| { crate::network::geoip::warm(); });
 
+    // Load the colour theme and key bindings before anything is drawn
+    let theme_message = ui::theme::init();
+    let (keymap, keymap_message) = keymap::load();
+
     // Init sysinfo Networks (fast — just enumerates adapters)
     let mut networks = sysinfo::Networks::new_with_refreshed_list();
     let mut app = App::new(&networks);
+    app.keymap = keymap;
+    let messages: Vec<String> = theme_message.into_iter().chain(keymap_message).collect();
+    if !messages.is_empty() {
+        app.status_message = Some((messages.join(" | "), Instant::now()));
+    }
 
     // Draw FIRST frame immediately — before any heavy update()
     terminal.draw(|f| {
@@ -77,7 +88,7 @@ fn main() -> io::Result<()> {
                         {
                             break;
                         }
-                        if app.handle_key(key.code) {
+                        if app.handle_key(key) {
                             break;
                         }
                         needs_redraw = true;
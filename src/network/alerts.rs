@@ -1,8 +1,9 @@
 //! Alert engine — GlassWire-style security & network alerts.
 //!
 //! Monitors for: new app connections, DNS config changes, suspicious hosts,
-//! RDP connections, bandwidth spikes, ARP anomalies, data plan overages,
-//! anomaly detection, idle summary, system monitor events.
+//! RDP connections, bandwidth spikes, ARP anomalies, unknown devices, data
+//! plan overages, anomaly detection, idle summary, system monitor events,
+//! new UPnP port mappings on the router, rogue DHCP servers.
 
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::net::IpAddr;
@@ -27,6 +28,11 @@ pub struct AlertConfig {
     pub enable_spike: bool,
     pub enable_device: bool,
     pub enable_arp: bool,
+    pub enable_listener: bool,
+    pub enable_health: bool,
+    pub enable_offline: bool,
+    pub enable_upnp: bool,
+    pub enable_dhcp: bool,
 }
 
 impl Default for AlertConfig {
@@ -41,6 +47,11 @@ impl Default for AlertConfig {
             enable_spike: true,
             enable_device: true,
             enable_arp: true,
+            enable_listener: true,
+            enable_health: true,
+            enable_offline: true,
+            enable_upnp: true,
+            enable_dhcp: true,
         }
     }
 }
@@ -184,6 +195,8 @@ pub struct AlertEngine {
     prev_dns_servers: Vec<IpAddr>,
     /// Known device MACs for ARP anomaly detection.
     known_device_macs: HashMap<IpAddr, String>,
+    /// MACs seen online this session, for device join alerts.
+    session_device_macs: HashSet<String>,
     /// Config
     pub config: AlertConfig,
     /// Unread count
@@ -227,6 +240,7 @@ impl AlertEngine {
             known_apps: HashSet::new(),
             prev_dns_servers: Vec::new(),
             known_device_macs: HashMap::new(),
+            session_device_macs: HashSet::new(),
             config: AlertConfig::default(),
             unread_count: 0,
             snoozed_until: None,
@@ -440,7 +454,8 @@ impl AlertEngine {
         }
         for device in devices {
             // Skip devices with empty MAC (discovered by ICMP/TCP/LLMNR — no MAC yet)
-            if device.mac.is_empty() {
+            // and offline history entries, whose IP may since have been reassigned
+            if device.mac.is_empty() || !device.is_online {
                 continue;
             }
             if let Some(expected_mac) = self.known_device_macs.get(&device.ip) {
@@ -460,19 +475,25 @@ impl AlertEngine {
         }
     }
 
-    /// Check for new/departed devices.
+    /// Check for devices that came online or departed. Devices joining are
+    /// checked against the allowlist: unknown ones raise a Critical alert,
+    /// trusted ones an informational one. A device only counts as joining
+    /// the first time this session it is seen, or after being gone longer
+    /// than `presence_gap`, so one missed scan does not alert again.
     pub fn check_device_changes(
         &mut self,
         current_devices: &[LanDevice],
         prev_devices: &[LanDevice],
+        is_trusted: impl Fn(&str) -> bool,
+        presence_gap: chrono::Duration,
     ) {
         if !self.config.enable_device {
             return;
         }
         // Skip devices with empty MACs — streaming discovery finds IPs first,
         // MACs arrive later. Comparing empty MACs causes false churn alerts.
-        let prev_macs: HashSet<&str> = prev_devices.iter()
-            .filter(|d| !d.mac.is_empty())
+        let prev_online: HashSet<&str> = prev_devices.iter()
+            .filter(|d| !d.mac.is_empty() && d.is_online)
             .map(|d| d.mac.as_str())
             .collect();
         let curr_macs: HashSet<&str> = current_devices.iter()
@@ -480,11 +501,27 @@ impl AlertEngine {
             .map(|d| d.mac.as_str())
             .collect();
 
-        // New devices (only if they have a real MAC)
+        // Devices that came online (only if they have a real MAC), including
+        // offline inventory entries a scan has just found again
         for device in current_devices {
-            if device.mac.is_empty() { continue; }
-            if !prev_macs.contains(device.mac.as_str()) {
-                self.push_alert(AlertKind::NewDevice {
+            if device.mac.is_empty() || !device.is_online { continue; }
+            if prev_online.contains(device.mac.as_str()) { continue; }
+            let first_sighting = self.session_device_macs.insert(device.mac.clone());
+            let long_absence = prev_devices.iter()
+                .find(|d| d.mac == device.mac)
+                .is_none_or(|d| device.last_seen - d.last_seen > presence_gap);
+            if !first_sighting && !long_absence { continue; }
+            if is_trusted(&device.mac) {
+                let name = device.custom_name.clone()
+                    .or_else(|| device.hostname.clone())
+                    .unwrap_or_else(|| device.mac.clone());
+                self.push_alert(AlertKind::TrustedDeviceReturned {
+                    ip: device.ip,
+                    mac: device.mac.clone(),
+                    name,
+                });
+            } else {
+                self.push_alert(AlertKind::UnknownDevice {
                     ip: device.ip,
                     mac: device.mac.clone(),
                     hostname: device.hostname.clone(),
@@ -635,6 +672,97 @@ impl AlertEngine {
         }
     }
 
+    // ─── Listener baseline changes ───────────────────────────────────
+
+    /// Raise alerts for listeners that differ from the baseline.
+    pub fn check_listener_changes(&mut self, changes: &[crate::network::servers::baseline::ListenerChange]) {
+        use crate::network::servers::baseline::ChangeKind;
+        if !self.config.enable_listener {
+            return;
+        }
+        for c in changes {
+            let (name, port, proto) = (c.name.clone(), c.port, c.proto.clone());
+            let kind = match &c.kind {
+                ChangeKind::Added => AlertKind::NewListener { name, port, proto, exe: c.exe.clone() },
+                ChangeKind::Removed => AlertKind::ListenerGone { name, port, proto },
+                ChangeKind::VersionChanged { old, new } => AlertKind::ListenerChanged {
+                    name, port, proto, what: "version".into(), old: old.clone(), new: new.clone(),
+                },
+                ChangeKind::ExeChanged { old, new } => AlertKind::ListenerChanged {
+                    name, port, proto, what: "executable".into(), old: old.clone(), new: new.clone(),
+                },
+            };
+            self.push_alert(kind);
+        }
+    }
+
+    // ─── Service health checks ───────────────────────────────────────
+
+    /// Raise alerts for health checks that just started failing.
+    pub fn check_health_failures(&mut self, failures: &[crate::network::servers::health::HealthFailure]) {
+        if !self.config.enable_health {
+            return;
+        }
+        for f in failures {
+            self.push_alert(AlertKind::HealthCheckFailed {
+                name: f.name.clone(),
+                port: f.port,
+                check: f.check.clone(),
+                error: f.error.clone(),
+            });
+        }
+    }
+
+    /// Alert on devices that have stayed offline past the configured threshold.
+    pub fn check_devices_offline(&mut self, offline: &[crate::network::presence::DeviceOffline]) {
+        if !self.config.enable_offline {
+            return;
+        }
+        for d in offline {
+            self.push_alert(AlertKind::DeviceOffline {
+                name: d.name.clone(),
+                ip: d.ip,
+                mac: d.mac.clone(),
+                minutes: d.minutes,
+            });
+        }
+    }
+
+    // ─── UPnP port mappings ──────────────────────────────────────────
+
+    /// Alert on port mappings the gateway gained since the last refresh.
+    pub fn check_port_mappings(&mut self, added: &[crate::network::upnp::PortMapping]) {
+        if !self.config.enable_upnp {
+            return;
+        }
+        for m in added {
+            self.push_alert(AlertKind::NewPortMapping {
+                external_port: m.external_port,
+                protocol: m.protocol.clone(),
+                internal_client: IpAddr::V4(m.internal_client),
+                internal_port: m.internal_port,
+                description: m.description.clone(),
+            });
+        }
+    }
+
+    // ─── Rogue DHCP servers ──────────────────────────────────────────
+
+    /// Alert on DHCP servers that are neither the gateway nor configured.
+    pub fn check_rogue_dhcp(&mut self, rogue: &[crate::network::dhcp_servers::RogueDhcp]) {
+        if !self.config.enable_dhcp {
+            return;
+        }
+        for r in rogue {
+            self.push_alert(AlertKind::RogueDhcpServer {
+                server: IpAddr::V4(r.server_id),
+                mac: r.mac.clone(),
+                routers: r.routers.iter().map(|&a| IpAddr::V4(a)).collect(),
+                dns: r.dns.iter().map(|&a| IpAddr::V4(a)).collect(),
+            });
+        }
+    }
+
     // ─── Persistence ─────────────────────────────────────────────────
 
     /// Save current alerts to disk (JSON).
@@ -0,0 +1,214 @@
+//! Known-device allowlist.
+//!
+//! MACs the user has marked as trusted, kept per network (subnet in CIDR
+//! form, as in the inventory) in `%APPDATA%\psnet\device_allowlist.json`.
+//! A device joining that is not on its network's list raises a Critical
+//! "Unknown Device" alert and shows in the Devices tab's quarantine view.
+//! Lists can be exported to and imported from JSON or CSV (`mac,name,added`).
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TrustedDevice {
+    pub mac: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(with = "super::inventory::rfc3339")]
+    pub added: DateTime<Local>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct AllowlistFile {
+    networks: BTreeMap<String, Vec<TrustedDevice>>,
+}
+
+pub struct Allowlist {
+    networks: BTreeMap<String, Vec<TrustedDevice>>,
+    /// Set when the lists changed and have not been written yet.
+    dirty: bool,
+    path: PathBuf,
+}
+
+impl Allowlist {
+    pub fn load() -> Self {
+        let path = match dirs::data_dir() {
+            Some(data_dir) => {
+                let dir = data_dir.join("psnet");
+                let _ = std::fs::create_dir_all(&dir);
+                dir.join("device_allowlist.json")
+            }
+            None => PathBuf::from("psnet_device_allowlist.json"),
+        };
+        let file: AllowlistFile = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default();
+        Self { networks: file.networks, dirty: false, path }
+    }
+
+    pub fn is_trusted(&self, network: &str, mac: &str) -> bool {
+        self.networks
+            .get(network)
+            .is_some_and(|list| list.iter().any(|t| t.mac.eq_ignore_ascii_case(mac)))
+    }
+
+    /// Number of trusted MACs on a network.
+    pub fn count(&self, network: &str) -> usize {
+        self.networks.get(network).map_or(0, Vec::len)
+    }
+
+    /// Trust a device, or stop trusting it if it already is. Returns whether
+    /// the device is trusted afterwards.
+    pub fn toggle(&mut self, network: &str, mac: &str, name: Option<&str>) -> bool {
+        let list = self.networks.entry(network.to_string()).or_default();
+        let before = list.len();
+        list.retain(|t| !t.mac.eq_ignore_ascii_case(mac));
+        let trusted = list.len() == before;
+        if trusted {
+            list.push(TrustedDevice {
+                mac: mac.to_string(),
+                name: name.map(str::to_string),
+                added: Local::now(),
+            });
+        }
+        self.dirty = true;
+        trusted
+    }
+
+    /// Write a network's list to `path`, as CSV when it ends in `.csv` and
+    /// JSON otherwise.
+    pub fn export(&self, network: &str, path: &Path) -> Result<String, String> {
+        let list = self.networks.get(network).map(Vec::as_slice).unwrap_or_default();
+        let text = if is_csv(path) {
+            to_csv(list)
+        } else {
+            serde_json::to_string_pretty(list).map_err(|e| e.to_string())?
+        };
+        std::fs::write(path, text).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Ok(format!("{} trusted devices to {}", list.len(), path.display()))
+    }
+
+    /// Add the MACs from a JSON or CSV file to a network's list. MACs already
+    /// trusted keep their name and date.
+    pub fn import(&mut self, network: &str, path: &Path) -> Result<String, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let entries = if is_csv(path) {
+            parse_csv(&text)
+        } else {
+            serde_json::from_str::<Vec<TrustedDevice>>(&text).map_err(|e| format!("{}: {}", path.display(), e))?
+        };
+        let list = self.networks.entry(network.to_string()).or_default();
+        let mut seen: HashSet<String> = list.iter().map(|t| t.mac.to_ascii_uppercase()).collect();
+        let mut added = 0;
+        for mut entry in entries {
+            let Some(mac) = normalize_mac(&entry.mac) else {
+                continue;
+            };
+            if seen.insert(mac.clone()) {
+                entry.mac = mac;
+                list.push(entry);
+                added += 1;
+            }
+        }
+        if added == 0 {
+            return Err(format!("no new MAC addresses in {}", path.display()));
+        }
+        self.dirty = true;
+        Ok(format!("{} trusted devices added", added))
+    }
+
+    /// Write the allowlist if it changed since the last save.
+    pub fn save_if_dirty(&mut self) {
+        if !self.dirty {
+            return;
+        }
+        let file = AllowlistFile { networks: self.networks.clone() };
+        if let Ok(json) = serde_json::to_string_pretty(&file) {
+            let _ = std::fs::write(&self.path, json);
+        }
+        self.dirty = false;
+    }
+}
+
+fn is_csv(path: &Path) -> bool {
+    path.extension().is_some_and(|e| e.eq_ignore_ascii_case("csv"))
+}
+
+/// MACs are kept the way the scanner writes them: `AA:BB:CC:DD:EE:FF`.
+fn normalize_mac(mac: &str) -> Option<String> {
+    let b = super::wol::parse_mac(mac)?;
+    Some(format!("{:02X}:{:02X}:{:02X}:{:02X}:{:02X}:{:02X}", b[0], b[1], b[2], b[3], b[4], b[5]))
+}
+
+fn to_csv(list: &[TrustedDevice]) -> String {
+    let mut out = String::from("mac,name,added\n");
+    for t in list {
+        let name = t.name.as_deref().unwrap_or("").replace('"', "\"\"");
+        out.push_str(&format!("{},\"{}\",{}\n", t.mac, name, t.added.to_rfc3339()));
+    }
+    out
+}
+
+/// Rows of `mac[,name[,added]]`. A header row and lines without a MAC are
+/// skipped; a missing or unreadable date becomes now.
+fn parse_csv(text: &str) -> Vec<TrustedDevice> {
+    text.lines()
+        .filter_map(|line| {
+            let fields = super::oui::split_csv_line(line);
+            let mac = fields.first()?.trim();
+            normalize_mac(mac)?;
+            let name = fields.get(1).map(|n| n.trim()).filter(|n| !n.is_empty());
+            let added = fields
+                .get(2)
+                .and_then(|d| DateTime::parse_from_rfc3339(d.trim()).ok())
+                .map_or_else(Local::now, |d| d.with_timezone(&Local));
+            Some(TrustedDevice { mac: mac.to_string(), name: name.map(str::to_string), added })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty() -> Allowlist {
+        Allowlist { networks: BTreeMap::new(), dirty: false, path: PathBuf::new() }
+    }
+
+    #[test]
+    fn test_toggle_is_per_network() {
+        let mut list = empty();
+        assert!(list.toggle("192.168.1.0/24", "B8:27:EB:01:02:03", Some("pi")));
+        assert!(list.is_trusted("192.168.1.0/24", "b8:27:eb:01:02:03"));
+        assert!(!list.is_trusted("10.0.0.0/24", "B8:27:EB:01:02:03"));
+        assert!(!list.toggle("192.168.1.0/24", "B8:27:EB:01:02:03", None));
+        assert!(!list.is_trusted("192.168.1.0/24", "B8:27:EB:01:02:03"));
+        assert_eq!(list.count("192.168.1.0/24"), 0);
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let mut list = empty();
+        list.toggle("lan", "AA:BB:CC:00:00:01", Some("Printer, 2nd floor"));
+        list.toggle("lan", "AA:BB:CC:00:00:02", None);
+        let csv = to_csv(&list.networks["lan"]);
+        let back = parse_csv(&csv);
+        assert_eq!(back.len(), 2);
+        assert_eq!(back[0].mac, "AA:BB:CC:00:00:01");
+        assert_eq!(back[0].name.as_deref(), Some("Printer, 2nd floor"));
+        assert_eq!(back[0].added.timestamp(), list.networks["lan"][0].added.timestamp());
+        assert_eq!(back[1].name, None);
+    }
+
+    #[test]
+    fn test_csv_accepts_bare_macs() {
+        let entries = parse_csv("MAC\naa-bb-cc-00-00-01\nnot a mac\n\naa:bb:cc:00:00:02,NAS\n");
+        let macs: Vec<&str> = entries.iter().map(|e| e.mac.as_str()).collect();
+        assert_eq!(macs, vec!["aa-bb-cc-00-00-01", "aa:bb:cc:00:00:02"]);
+        assert_eq!(normalize_mac(macs[0]).as_deref(), Some("AA:BB:CC:00:00:01"));
+    }
+}
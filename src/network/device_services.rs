@@ -0,0 +1,251 @@
+//! Service detection on LAN devices.
+//!
+//! The port scan only says which ports accept a connection. This runs the
+//! Servers tab's probe → classify pipeline against those ports on the remote
+//! device, so a NAS reads "Synology DSM 7.2 on 5000, OpenSSH 8.4p1 on 22"
+//! instead of "22:ssh 5000:upnp". There is no process to look at remotely,
+//! so classification rests on banners, protocol handshakes and HTTP evidence.
+
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr};
+use std::thread;
+
+use crate::types::DeviceService;
+
+use super::servers::fingerprint::{self, ProbeResult};
+use super::servers::types::{DetectedTech, ServerKind};
+use super::servers::{classify, wappalyzer_db};
+
+/// Longest HTTP title or banner used as a product name.
+const MAX_NAME_LEN: usize = 40;
+
+/// Fingerprint the open ports of every device in parallel.
+pub fn identify_batch(open: &[(Ipv4Addr, Vec<u16>)]) -> HashMap<Ipv4Addr, Vec<DeviceService>> {
+    let results: std::sync::Mutex<HashMap<Ipv4Addr, Vec<DeviceService>>> = Default::default();
+    thread::scope(|s| {
+        for (ip, ports) in open {
+            let results = &results;
+            s.spawn(move || {
+                let services = identify(IpAddr::V4(*ip), ports);
+                if !services.is_empty() {
+                    results.lock().unwrap().insert(*ip, services);
+                }
+            });
+        }
+    });
+    results.into_inner().unwrap()
+}
+
+/// Probe `ports` on `ip` and name what answers. Ports that could not be
+/// identified are left out; they still show in the open-port list.
+pub fn identify(ip: IpAddr, ports: &[u16]) -> Vec<DeviceService> {
+    let targets: Vec<(u16, IpAddr)> = ports.iter().map(|&p| (p, ip)).collect();
+    let probes = fingerprint::probe_ports(&targets);
+    let mut services: Vec<DeviceService> = probes
+        .iter()
+        .filter_map(|(&port, probe)| describe(port, probe))
+        .collect();
+    services.sort_by_key(|s| s.port);
+    services
+}
+
+/// Name the product behind one probed port.
+pub fn describe(port: u16, probe: &ProbeResult) -> Option<DeviceService> {
+    // An HTTP status line as the banner matches fingerprints meant for a
+    // known local process (WinRM under System); remotely it says nothing.
+    let stripped;
+    let evidence = if probe.banner.as_deref().is_some_and(|b| b.starts_with("HTTP/")) {
+        stripped = ProbeResult { banner: None, ..probe.clone() };
+        &stripped
+    } else {
+        probe
+    };
+    let (kind, version) = classify::classify("", "", "", port, Some(evidence));
+    let techs = wappalyzer_db::detect(&wappalyzer_db::HttpEvidence {
+        headers: &probe.http_headers,
+        cookies: &probe.http_cookies,
+        html: probe.http_body.as_deref().unwrap_or(""),
+    });
+    let (kind, version) = match super::servers::upgrade_kind_from_techs(&kind, &techs) {
+        Some((upgraded, ver)) => (upgraded, ver.or(version)),
+        None => (kind, version),
+    };
+
+    // A completed handshake names the exact product (dropbear rather than
+    // the OpenSSH kind every SSH server maps to).
+    if let Some(ref hs) = probe.handshake {
+        return Some(service(port, &hs.product, hs.version.clone().or(version)));
+    }
+    if !is_generic(&kind) {
+        return Some(service(port, kind.label(), version));
+    }
+    generic_name(evidence, &techs).map(|(product, version)| service(port, &product, version))
+}
+
+fn service(port: u16, product: &str, version: Option<String>) -> DeviceService {
+    DeviceService {
+        port,
+        product: product.to_string(),
+        version: version.filter(|v| !v.is_empty()),
+    }
+}
+
+fn is_generic(kind: &ServerKind) -> bool {
+    matches!(
+        kind,
+        ServerKind::Unknown | ServerKind::GenericTcp | ServerKind::GenericUdp | ServerKind::CustomHttp
+    )
+}
+
+/// Best name for a service the fingerprint database does not know: the first
+/// web technology detected, then the Server header, the page title, and
+/// finally the raw banner.
+fn generic_name(probe: &ProbeResult, techs: &[DetectedTech]) -> Option<(String, Option<String>)> {
+    if let Some(tech) = techs.first() {
+        return Some((tech.name.clone(), Some(tech.version.clone())));
+    }
+    if let Some(ref server) = probe.http_server {
+        let (name, version) = match server.split_once('/') {
+            Some((name, rest)) => (name, rest.split_whitespace().next().map(str::to_string)),
+            None => (server.as_str(), None),
+        };
+        return Some((name.trim().to_string(), version));
+    }
+    probe
+        .http_title
+        .as_deref()
+        .or(probe.banner.as_deref())
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| (truncate(s), None))
+}
+
+fn truncate(s: &str) -> String {
+    if s.chars().count() <= MAX_NAME_LEN {
+        s.to_string()
+    } else {
+        let cut: String = s.chars().take(MAX_NAME_LEN - 1).collect();
+        format!("{}…", cut)
+    }
+}
+
+/// Fold newly identified services into a device's list; a port's latest
+/// identification replaces the earlier one.
+pub fn merge(services: &mut Vec<DeviceService>, incoming: &[DeviceService]) {
+    for svc in incoming {
+        match services.iter_mut().find(|s| s.port == svc.port) {
+            Some(existing) => *existing = svc.clone(),
+            None => services.push(svc.clone()),
+        }
+    }
+    services.sort_by_key(|s| s.port);
+}
+
+/// "Synology DSM 7.2 on 5000, OpenSSH 8.4p1 on 22" for the Devices tab.
+pub fn summary(services: &[DeviceService]) -> String {
+    services.iter().map(DeviceService::label).collect::<Vec<_>>().join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    /// A loopback listener below the ephemeral range, which the prober skips.
+    fn listen() -> (TcpListener, u16) {
+        (40000..41000)
+            .find_map(|port| TcpListener::bind((Ipv4Addr::LOCALHOST, port)).ok().map(|l| (l, port)))
+            .expect("no free loopback port")
+    }
+
+    /// Serve `reply` on a loopback port. `wait_for_request` holds the reply
+    /// until the client has written something, like an HTTP server; the
+    /// prober reconnects for its HTTP request, so a few connections are served.
+    fn stand_in(reply: &'static [u8], wait_for_request: bool) -> u16 {
+        let (listener, port) = listen();
+        thread::spawn(move || {
+            for mut conn in listener.incoming().take(4).flatten() {
+                thread::spawn(move || {
+                    if wait_for_request {
+                        let mut buf = [0u8; 1024];
+                        if !matches!(conn.read(&mut buf), Ok(n) if n > 0) {
+                            return;
+                        }
+                    }
+                    let _ = conn.write_all(reply);
+                    thread::sleep(std::time::Duration::from_millis(200));
+                });
+            }
+        });
+        port
+    }
+
+    #[test]
+    fn test_identifies_ssh_banner() {
+        let port = stand_in(b"SSH-2.0-OpenSSH_8.4p1 Debian-5\r\n", false);
+        let services = identify(IpAddr::V4(Ipv4Addr::LOCALHOST), &[port]);
+        assert_eq!(services.len(), 1);
+        assert_eq!(services[0].product, "OpenSSH");
+        assert_eq!(services[0].version.as_deref(), Some("8.4p1"));
+        assert_eq!(services[0].label(), format!("OpenSSH 8.4p1 on {}", port));
+    }
+
+    #[test]
+    fn test_identifies_http_server_header() {
+        let port = stand_in(
+            b"HTTP/1.1 200 OK\r\nServer: nginx/1.24.0\r\nContent-Type: text/html\r\nContent-Length: 39\r\n\r\n<html><title>NAS Login</title></html>\r\n",
+            true,
+        );
+        let services = identify(IpAddr::V4(Ipv4Addr::LOCALHOST), &[port]);
+        assert_eq!(services.len(), 1, "{:?}", services);
+        assert_eq!(services[0].product, "Nginx");
+        assert_eq!(services[0].version.as_deref(), Some("1.24.0"));
+    }
+
+    #[test]
+    fn test_unknown_product_falls_back_to_title() {
+        let probe = ProbeResult {
+            banner: Some("HTTP/1.1 200 OK".into()),
+            http_server: None,
+            http_powered_by: None,
+            http_title: Some("DiskStation Manager".into()),
+            http_headers: Vec::new(),
+            http_cookies: Vec::new(),
+            http_body: None,
+            tls_detected: false,
+            is_responsive: true,
+            handshake: None,
+        };
+        let svc = describe(5000, &probe).unwrap();
+        assert_eq!(svc.label(), "DiskStation Manager on 5000");
+    }
+
+    #[test]
+    fn test_closed_port_is_skipped() {
+        let port = listen().1;
+        assert!(identify(IpAddr::V4(Ipv4Addr::LOCALHOST), &[port]).is_empty());
+    }
+
+    #[test]
+    fn test_merge_replaces_by_port() {
+        let mut services = vec![DeviceService { port: 80, product: "nginx".into(), version: None }];
+        merge(&mut services, &[
+            DeviceService { port: 22, product: "OpenSSH".into(), version: None },
+            DeviceService { port: 80, product: "nginx".into(), version: Some("1.24.0".into()) },
+        ]);
+        assert_eq!(services.len(), 2);
+        assert_eq!(services[0].port, 22);
+        assert_eq!(services[1].version.as_deref(), Some("1.24.0"));
+    }
+
+    #[test]
+    fn test_summary() {
+        let services = vec![
+            DeviceService { port: 5000, product: "Synology DSM".into(), version: Some("7.2".into()) },
+            DeviceService { port: 22, product: "OpenSSH".into(), version: Some("8.4".into()) },
+            DeviceService { port: 554, product: "RTSP".into(), version: None },
+        ];
+        assert_eq!(summary(&services), "Synology DSM 7.2 on 5000, OpenSSH 8.4 on 22, RTSP on 554");
+    }
+}
@@ -0,0 +1,377 @@
+//! Device type classification for LAN hosts.
+//!
+//! Every piece of evidence the scanner collects — OUI vendor, mDNS service
+//! types, UPnP device descriptions, DHCP fingerprints, open ports, NetBIOS
+//! replies and hostname patterns — adds weight to one or more [`DeviceType`]s. The heaviest type
+//! wins; its confidence drops when a runner-up is close behind, so a Samsung
+//! MAC alone ("phone or TV?") stays low while `_googlecast` plus port 8009
+//! lands near the top.
+
+use std::collections::HashMap;
+
+use crate::types::{DeviceType, LanDevice};
+use DeviceType::*;
+
+/// Below this total weight the device stays `Unknown`.
+const MIN_SCORE: u32 = 20;
+
+/// The classifier's verdict for one device.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Classification {
+    pub kind: DeviceType,
+    /// 0-99.
+    pub confidence: u8,
+    /// Evidence that supported `kind`, strongest first.
+    pub reasons: Vec<String>,
+}
+
+/// Total weight for a type plus each (weight, reason) that contributed.
+type Tally = (u32, Vec<(u32, String)>);
+
+#[derive(Default)]
+struct Scores {
+    by_type: HashMap<DeviceType, Tally>,
+}
+
+impl Scores {
+    fn add(&mut self, kind: DeviceType, weight: u32, reason: impl Into<String>) {
+        let entry = self.by_type.entry(kind).or_default();
+        entry.0 += weight;
+        entry.1.push((weight, reason.into()));
+    }
+
+    fn verdict(self) -> Classification {
+        let mut ranked: Vec<(DeviceType, Tally)> = self.by_type.into_iter().collect();
+        // Ties go to the type listed first in DeviceType::all() for a stable result
+        let order = |t: &DeviceType| DeviceType::all().iter().position(|x| x == t).unwrap_or(usize::MAX);
+        ranked.sort_by(|a, b| b.1 .0.cmp(&a.1 .0).then(order(&a.0).cmp(&order(&b.0))));
+
+        let mut iter = ranked.into_iter();
+        let Some((kind, (best, mut reasons))) = iter.next() else {
+            return Classification { kind: DeviceType::Unknown, confidence: 0, reasons: Vec::new() };
+        };
+        if best < MIN_SCORE {
+            return Classification { kind: DeviceType::Unknown, confidence: 0, reasons: Vec::new() };
+        }
+        let second = iter.next().map(|(_, (s, _))| s).unwrap_or(0);
+        let confidence = best.min(99) * (2 * best - second) / (2 * best);
+
+        reasons.sort_by_key(|(w, _)| std::cmp::Reverse(*w));
+        Classification {
+            kind,
+            confidence: confidence as u8,
+            reasons: reasons.into_iter().map(|(_, r)| r).collect(),
+        }
+    }
+}
+
+/// Classify a device from everything the scanner knows about it.
+pub fn classify(device: &LanDevice, is_gateway: bool) -> Classification {
+    let mut scores = Scores::default();
+
+    if is_gateway {
+        scores.add(DeviceType::Router, 60, "default gateway");
+    }
+    if let Some(ref vendor) = device.vendor {
+        score_vendor(&mut scores, vendor);
+    }
+    for hint in &device.hints {
+        if let Some(svc) = hint.strip_prefix("mdns:") {
+            score_mdns(&mut scores, svc);
+        } else if let Some(kind) = hint.strip_prefix("upnp:") {
+            score_upnp(&mut scores, kind);
+        } else if let Some(model) = hint.strip_prefix("model:") {
+            score_name(&mut scores, model, "model");
+        }
+    }
+    if let Some(family) = device.dhcp.as_ref().and_then(|d| d.family.as_deref()) {
+        score_dhcp(&mut scores, family);
+    }
+    score_ports(&mut scores, &parse_ports(&device.open_ports));
+    for name in device.hostname.iter().chain(device.custom_name.iter()) {
+        score_name(&mut scores, name, "name");
+    }
+    if device.discovery_info.contains("NBNS:") {
+        scores.add(DeviceType::Desktop, 15, "answers NetBIOS");
+        scores.add(DeviceType::Laptop, 10, "answers NetBIOS");
+    }
+
+    scores.verdict()
+}
+
+/// Classify a device in place.
+pub fn apply(device: &mut LanDevice, is_gateway: bool) {
+    let c = classify(device, is_gateway);
+    device.device_type = c.kind;
+    device.type_confidence = c.confidence;
+}
+
+/// Port numbers from `LanDevice::open_ports` ("22:ssh 80:http 9100").
+pub fn parse_ports(open_ports: &str) -> Vec<u16> {
+    open_ports
+        .split_whitespace()
+        .filter_map(|p| p.split(':').next()?.parse().ok())
+        .collect()
+}
+
+// ─── Evidence ────────────────────────────────────────────────────────────────
+
+type Rule = (&'static [&'static str], &'static [(DeviceType, u32)]);
+
+const VENDOR_RULES: &[Rule] = &[
+    (&["vmware", "virtualbox", "pcs systemtechnik", "hyper-v", "kvm/qemu", "xen vm", "libvirt", "parallels", "docker"], &[(VirtualMachine, 60)]),
+    (&["synology", "qnap", "asustor", "western digital", "drobo", "buffalo"], &[(Nas, 50)]),
+    (&["hikvision", "dahua", "axis communications", "reolink", "amcrest", "foscam", "wyze", "arlo", "hanwha"], &[(Camera, 50)]),
+    (&["brother", "canon", "epson", "seiko epson", "lexmark", "xerox", "kyocera", "ricoh", "konica"], &[(Printer, 40)]),
+    (&["hewlett packard", "hp inc"], &[(Printer, 25), (Laptop, 15)]),
+    (&["sonos", "bose", "harman", "bang & olufsen"], &[(Speaker, 50)]),
+    (&["roku"], &[(MediaPlayer, 50)]),
+    (&["nintendo", "sony interactive"], &[(GameConsole, 60)]),
+    (&["vizio", "tcl", "hisense", "lg electronics", "funai", "sharp"], &[(Tv, 35)]),
+    (&["samsung"], &[(Phone, 20), (Tv, 15)]),
+    (&["sony"], &[(Tv, 20), (Phone, 10)]),
+    (&["apple"], &[(Phone, 15), (Laptop, 12)]),
+    (&["google"], &[(MediaPlayer, 15), (Speaker, 15), (Phone, 10)]),
+    (&["amazon"], &[(Speaker, 20), (MediaPlayer, 15)]),
+    (&["xiaomi", "oneplus", "oppo", "vivo mobile", "motorola", "huawei device", "realme", "honor device", "nothing technology", "fairphone"], &[(Phone, 35)]),
+    (&["netgear", "tp-link", "d-link", "linksys", "mikrotik", "ubiquiti", "cisco", "juniper", "arris", "technicolor", "sagemcom", "zyxel", "aruba", "ruckus", "eero", "avm gmbh", "cradlepoint", "fortinet"], &[(Router, 30)]),
+    (&["espressif", "tuya", "shelly", "itead", "signify", "philips lighting", "nest labs", "ecobee", "ring llc", "lifx", "wiz connected"], &[(Iot, 45)]),
+    (&["raspberry pi"], &[(Iot, 25), (Server, 20)]),
+    (&["micro-star", "gigabyte", "asrock"], &[(Desktop, 35)]),
+    (&["dell", "lenovo"], &[(Laptop, 20), (Desktop, 15)]),
+    (&["intel", "azurewave", "liteon", "lite-on", "rivet networks", "killer"], &[(Laptop, 25)]),
+    (&["realtek"], &[(Desktop, 15)]),
+    (&["private mac"], &[(Phone, 20), (Laptop, 10)]),
+];
+
+const MDNS_RULES: &[Rule] = &[
+    (&["_ipp", "_ipps", "_printer", "_pdl-datastream", "_scanner", "_uscan", "_uscans"], &[(Printer, 50)]),
+    (&["_googlecast"], &[(MediaPlayer, 40), (Tv, 20)]),
+    (&["_androidtvremote", "_androidtvremote2"], &[(Tv, 50)]),
+    (&["_amzn-wplay"], &[(MediaPlayer, 40)]),
+    (&["_airplay"], &[(Tv, 25), (MediaPlayer, 20)]),
+    (&["_raop"], &[(Speaker, 30)]),
+    (&["_sonos", "_spotify-connect"], &[(Speaker, 40)]),
+    (&["_apple-mobdev2", "_companion-link"], &[(Phone, 30), (Tablet, 15)]),
+    (&["_adisk"], &[(Nas, 40)]),
+    (&["_smb", "_afpovertcp", "_nfs"], &[(Nas, 20), (Desktop, 10)]),
+    (&["_hap", "_matter", "_matterc", "_hue", "_esphomelib", "_home-assistant", "_miio", "_shelly"], &[(Iot, 45)]),
+    (&["_axis-video", "_rtsp"], &[(Camera, 40)]),
+    (&["_workstation"], &[(Desktop, 15), (Server, 10)]),
+    (&["_ssh", "_sftp-ssh"], &[(Server, 15)]),
+    (&["_rfb"], &[(Desktop, 10)]),
+];
+
+const UPNP_RULES: &[Rule] = &[
+    (&["internetgatewaydevice", "wandevice", "wanconnectiondevice"], &[(Router, 60)]),
+    (&["printer"], &[(Printer, 50)]),
+    (&["zoneplayer"], &[(Speaker, 50)]),
+    (&["mediarenderer"], &[(Tv, 25), (Speaker, 20)]),
+    (&["mediaserver"], &[(Nas, 30)]),
+    (&["digitalsecuritycamera"], &[(Camera, 50)]),
+    (&["dial"], &[(MediaPlayer, 30)]),
+];
+
+/// Device families from `data/dhcp_fingerprints.json`.
+const DHCP_RULES: &[Rule] = &[
+    (&["windows"], &[(Desktop, 25), (Laptop, 20)]),
+    (&["apple mac"], &[(Laptop, 35), (Desktop, 20)]),
+    (&["apple ios"], &[(Phone, 40), (Tablet, 20)]),
+    (&["android"], &[(Phone, 40), (Tablet, 10), (Tv, 10)]),
+    (&["linux"], &[(Server, 20), (Desktop, 10)]),
+    (&["embedded"], &[(Iot, 35), (Router, 10)]),
+    (&["printer"], &[(Printer, 50)]),
+    (&["game console"], &[(GameConsole, 60)]),
+];
+
+/// Hostname and model-name keywords. Matched as substrings of the
+/// lowercased name, so keep them specific.
+const NAME_RULES: &[Rule] = &[
+    (&["iphone", "android", "galaxy-s", "galaxy-a", "pixel", "oneplus", "redmi", "sm-"], &[(Phone, 45)]),
+    (&["ipad", "galaxy-tab", "tablet", "kindle"], &[(Tablet, 45)]),
+    (&["macbook", "laptop", "notebook", "thinkpad", "xps", "surface"], &[(Laptop, 45)]),
+    (&["imac", "mac-mini", "macmini", "desktop", "workstation", "-pc"], &[(Desktop, 40)]),
+    (&["printer", "laserjet", "officejet", "deskjet", "pixma", "npi", "brw", "brn", "epson"], &[(Printer, 45)]),
+    (&["bravia", "smarttv", "smart-tv", "-tv", "tv-", "webos", "tizen"], &[(Tv, 40)]),
+    (&["roku", "chromecast", "appletv", "apple-tv", "firetv", "fire-tv", "shield"], &[(MediaPlayer, 45)]),
+    (&["sonos", "homepod", "echo", "speaker", "nest-audio", "nest-mini"], &[(Speaker, 40)]),
+    (&["ipcam", "camera", "doorbell", "-cam"], &[(Camera, 40)]),
+    (&["diskstation", "synology", "qnap", "truenas", "unraid", "nas"], &[(Nas, 45)]),
+    (&["router", "gateway", "openwrt", "unifi", "fritz", "archer", "nighthawk", "mikrotik"], &[(Router, 40)]),
+    (&["xbox", "playstation", "ps4", "ps5", "nintendo"], &[(GameConsole, 50)]),
+    (&["esp_", "esp-", "esp32", "esp8266", "tasmota", "shelly", "sonoff", "tuya", "wled", "plug", "thermostat", "wemo", "hue-bridge"], &[(Iot, 40)]),
+    (&["server", "srv", "proxmox", "pve", "k8s", "docker"], &[(Server, 35)]),
+];
+
+fn apply_rules(scores: &mut Scores, rules: &[Rule], text: &str, source: &str, matches: impl Fn(&str, &str) -> bool) {
+    for (keywords, weights) in rules {
+        if let Some(k) = keywords.iter().find(|k| matches(text, k)) {
+            for &(kind, weight) in weights.iter() {
+                scores.add(kind, weight, format!("{} {}", source, k));
+            }
+        }
+    }
+}
+
+fn score_vendor(scores: &mut Scores, vendor: &str) {
+    let lower = vendor.to_lowercase();
+    // First match only: "Sony Interactive" must not also count as a Sony TV
+    if let Some((_, weights)) = VENDOR_RULES.iter().find(|(ks, _)| ks.iter().any(|k| lower.contains(k))) {
+        for &(kind, weight) in weights.iter() {
+            scores.add(kind, weight, format!("vendor {}", vendor));
+        }
+    }
+}
+
+fn score_mdns(scores: &mut Scores, service: &str) {
+    // "_ipp._tcp" → "_ipp"
+    let name = service.split('.').next().unwrap_or(service).to_lowercase();
+    apply_rules(scores, MDNS_RULES, &name, "mDNS", |text, k| text == k);
+}
+
+fn score_upnp(scores: &mut Scores, kind: &str) {
+    let lower = kind.to_lowercase();
+    apply_rules(scores, UPNP_RULES, &lower, "UPnP", |text, k| text == k);
+}
+
+fn score_dhcp(scores: &mut Scores, family: &str) {
+    let lower = family.to_lowercase();
+    apply_rules(scores, DHCP_RULES, &lower, "DHCP", |text, k| text == k);
+}
+
+fn score_name(scores: &mut Scores, name: &str, source: &str) {
+    let lower = name.to_lowercase();
+    apply_rules(scores, NAME_RULES, &lower, source, |text, k| text.contains(k));
+    // Windows default names: DESKTOP-XXXXXXX / LAPTOP-XXXXXXX
+    if lower.starts_with("laptop-") {
+        scores.add(Laptop, 20, "Windows laptop name");
+    }
+}
+
+fn score_ports(scores: &mut Scores, ports: &[u16]) {
+    let has = |p: u16| ports.contains(&p);
+    if has(9100) || has(515) {
+        scores.add(Printer, 35, "raw print port");
+    }
+    if has(631) {
+        scores.add(Printer, 30, "port 631 (IPP)");
+    }
+    if has(554) {
+        scores.add(Camera, 30, "port 554 (RTSP)");
+    }
+    if has(8008) || has(8009) {
+        scores.add(MediaPlayer, 30, "Cast port");
+    }
+    if has(62078) {
+        scores.add(Phone, 40, "port 62078 (iOS sync)");
+    }
+    if has(5555) {
+        scores.add(Phone, 15, "port 5555 (ADB)");
+        scores.add(Tv, 15, "port 5555 (ADB)");
+    }
+    if has(3389) {
+        scores.add(Desktop, 25, "port 3389 (RDP)");
+    }
+    if has(135) && has(445) {
+        scores.add(Desktop, 15, "Windows RPC + SMB");
+        scores.add(Laptop, 10, "Windows RPC + SMB");
+    }
+    if has(445) && (has(5000) || has(5001)) {
+        scores.add(Nas, 25, "SMB + port 5000 (NAS admin)");
+    }
+    if has(1883) {
+        scores.add(Iot, 25, "port 1883 (MQTT)");
+    }
+    if has(53) {
+        scores.add(Router, 30, "port 53 (DNS)");
+        if has(80) || has(443) {
+            scores.add(Router, 10, "DNS + web admin");
+        }
+    }
+    if has(22) {
+        scores.add(Server, 10, "port 22 (SSH)");
+    }
+    if has(3306) {
+        scores.add(Server, 20, "port 3306 (MySQL)");
+    }
+    if has(23) {
+        scores.add(Router, 10, "port 23 (Telnet)");
+        scores.add(Iot, 10, "port 23 (Telnet)");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    use chrono::Local;
+
+    fn device(vendor: Option<&str>, hostname: Option<&str>, ports: &str, hints: &[&str]) -> LanDevice {
+        LanDevice {
+            hostname: hostname.map(String::from),
+            vendor: vendor.map(String::from),
+            first_seen: Local::now(),
+            last_seen: Local::now(),
+            open_ports: ports.into(),
+            hints: hints.iter().map(|h| h.to_string()).collect(),
+            ..LanDevice::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 20)), "AA:BB:CC:DD:EE:FF".into())
+        }
+    }
+
+    #[test]
+    fn test_no_evidence_is_unknown() {
+        let c = classify(&device(None, None, "", &[]), false);
+        assert_eq!(c.kind, DeviceType::Unknown);
+        assert_eq!(c.confidence, 0);
+    }
+
+    #[test]
+    fn test_printer_from_mdns_and_ports() {
+        let d = device(Some("Brother Industries, Ltd."), Some("BRW1234"), "80:http 631:ipp 9100", &["mdns:_ipp._tcp"]);
+        let c = classify(&d, false);
+        assert_eq!(c.kind, DeviceType::Printer);
+        assert!(c.confidence >= 90, "{:?}", c);
+        assert!(c.reasons.iter().any(|r| r.contains("_ipp")));
+    }
+
+    #[test]
+    fn test_gateway_is_router() {
+        let d = device(Some("TP-LINK TECHNOLOGIES CO.,LTD."), None, "53:dns 80:http", &["upnp:InternetGatewayDevice"]);
+        assert_eq!(classify(&d, true).kind, DeviceType::Router);
+    }
+
+    #[test]
+    fn test_ambiguous_vendor_has_low_confidence() {
+        let samsung = classify(&device(Some("Samsung Electronics Co.,Ltd"), None, "", &[]), false);
+        assert_eq!(samsung.kind, DeviceType::Phone);
+        assert!(samsung.confidence < 20, "{:?}", samsung);
+
+        let tv = classify(&device(Some("Samsung Electronics Co.,Ltd"), Some("Samsung-TV"), "8009", &["upnp:MediaRenderer"]), false);
+        assert_eq!(tv.kind, DeviceType::Tv);
+        assert!(tv.confidence > samsung.confidence);
+    }
+
+    #[test]
+    fn test_dhcp_family_settles_ambiguous_vendor() {
+        let mut d = device(Some("Samsung Electronics Co.,Ltd"), None, "", &[]);
+        let vendor_only = classify(&d, false);
+        d.dhcp = Some(crate::types::DhcpFingerprint {
+            params: "1,3,6,15,26,28,51,58,59,43,114".into(),
+            vendor_class: Some("android-dhcp-14".into()),
+            max_size: Some(1500),
+            os: Some("Android 14".into()),
+            family: Some("Android".into()),
+        });
+        let c = classify(&d, false);
+        assert_eq!(c.kind, DeviceType::Phone);
+        assert!(c.confidence > 2 * vendor_only.confidence, "{:?} vs {:?}", c, vendor_only);
+        assert!(c.reasons.iter().any(|r| r == "DHCP android"));
+    }
+
+    #[test]
+    fn test_parse_ports() {
+        assert_eq!(parse_ports("22:ssh 80:http 9100"), vec![22, 80, 9100]);
+        assert!(parse_ports("").is_empty());
+    }
+}
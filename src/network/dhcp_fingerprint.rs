@@ -0,0 +1,225 @@
+//! Passive DHCP fingerprinting.
+//!
+//! Every DHCP client asks for its own set of options (option 55) in its own
+//! order, and many announce a vendor class (option 60). Together they name
+//! the DHCP stack, and so the OS, about as reliably as a User-Agent: Windows
+//! sends "MSFT 5.0", Android "android-dhcp-14", and Apple devices a request
+//! list no one else uses. The table in `data/dhcp_fingerprints.json` follows
+//! Fingerbank's approach of matching those values exactly.
+
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+use crate::types::DhcpFingerprint;
+
+static TABLE_JSON: &str = include_str!("../../data/dhcp_fingerprints.json");
+
+/// One known DHCP client. Every field it sets must match.
+#[derive(Debug, Deserialize)]
+struct Entry {
+    os: String,
+    family: String,
+    /// Exact option 55 list.
+    #[serde(default)]
+    params: Option<String>,
+    /// Case-insensitive prefix of option 60.
+    #[serde(default)]
+    vendor_class: Option<String>,
+    /// Append what follows the vendor prefix to `os` ("android-dhcp-14" → "Android 14").
+    #[serde(default)]
+    vendor_version: bool,
+}
+
+fn table() -> &'static [Entry] {
+    static TABLE: OnceLock<Vec<Entry>> = OnceLock::new();
+    TABLE.get_or_init(|| serde_json::from_str(TABLE_JSON).unwrap_or_default())
+}
+
+/// The options of a client DHCP message that identify the sender.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DhcpRequest {
+    /// Client hardware address (chaddr) as "AA:BB:CC:DD:EE:FF".
+    pub mac: String,
+    /// Option 53: 1 = DISCOVER, 3 = REQUEST, 8 = INFORM, ...
+    pub message_type: Option<u8>,
+    pub params: Vec<u8>,
+    pub vendor_class: Option<String>,
+    pub max_size: Option<u16>,
+}
+
+/// Parse a client → server DHCP message (BOOTREQUEST). Server replies carry
+/// the server's options, not the client's, and are ignored.
+pub fn parse_request(payload: &[u8]) -> Option<DhcpRequest> {
+    if payload.len() < 244 || payload[0] != 1 || payload[236..240] != [99, 130, 83, 99] {
+        return None;
+    }
+    let chaddr = &payload[28..34];
+    let mut req = DhcpRequest {
+        mac: chaddr.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(":"),
+        message_type: None,
+        params: Vec::new(),
+        vendor_class: None,
+        max_size: None,
+    };
+
+    let mut pos = 240;
+    while pos < payload.len() {
+        let tag = payload[pos];
+        if tag == 255 { break; }
+        if tag == 0 { pos += 1; continue; }
+        if pos + 1 >= payload.len() { break; }
+        let len = payload[pos + 1] as usize;
+        pos += 2;
+        if pos + len > payload.len() { break; }
+        let value = &payload[pos..pos + len];
+        match tag {
+            53 if len == 1 => req.message_type = Some(value[0]),
+            55 => req.params = value.to_vec(),
+            57 if len == 2 => req.max_size = Some(u16::from_be_bytes([value[0], value[1]])),
+            60 => {
+                let class = String::from_utf8_lossy(value).trim().to_string();
+                if !class.is_empty() {
+                    req.vendor_class = Some(class);
+                }
+            }
+            _ => {}
+        }
+        pos += len;
+    }
+    Some(req)
+}
+
+/// Fingerprint a request: its raw options plus the best table match.
+/// Requests with neither option 55 nor 60 say nothing and give `None`.
+pub fn fingerprint(req: &DhcpRequest) -> Option<DhcpFingerprint> {
+    if req.params.is_empty() && req.vendor_class.is_none() {
+        return None;
+    }
+    let params = req.params.iter().map(u8::to_string).collect::<Vec<_>>().join(",");
+    let (os, family) = match best_match(&params, req.vendor_class.as_deref()) {
+        Some((entry, suffix)) => {
+            let os = match suffix {
+                Some(v) => format!("{} {}", entry.os, v),
+                None => entry.os.clone(),
+            };
+            (Some(os), Some(entry.family.clone()))
+        }
+        None => (None, None),
+    };
+    Some(DhcpFingerprint {
+        params,
+        vendor_class: req.vendor_class.clone(),
+        max_size: req.max_size,
+        os,
+        family,
+    })
+}
+
+/// The most specific matching entry: most fields matched, then the longest
+/// vendor prefix ("MSFT 5.0 XBOX" over "MSFT 5.0"). Also returns the version
+/// suffix for `vendor_version` entries.
+fn best_match<'a>(params: &str, vendor_class: Option<&'a str>) -> Option<(&'static Entry, Option<&'a str>)> {
+    let mut best: Option<((usize, usize), &'static Entry, Option<&'a str>)> = None;
+    for entry in table() {
+        if entry.params.as_deref().is_some_and(|p| p != params) {
+            continue;
+        }
+        let mut suffix = None;
+        if let Some(ref prefix) = entry.vendor_class {
+            let Some(class) = vendor_class else { continue };
+            let Some(head) = class.get(..prefix.len()) else { continue };
+            if !head.eq_ignore_ascii_case(prefix) {
+                continue;
+            }
+            let rest = class[prefix.len()..].trim();
+            if entry.vendor_version && !rest.is_empty() {
+                suffix = Some(rest);
+            }
+        }
+        let fields = entry.params.is_some() as usize + entry.vendor_class.is_some() as usize;
+        if fields == 0 {
+            continue;
+        }
+        let rank = (fields, entry.vendor_class.as_ref().map_or(0, String::len));
+        if best.as_ref().is_none_or(|(r, _, _)| rank > *r) {
+            best = Some((rank, entry, suffix));
+        }
+    }
+    best.map(|(_, entry, suffix)| (entry, suffix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A minimal BOOTREQUEST carrying `options` (without the end marker).
+    fn request(options: &[u8]) -> Vec<u8> {
+        let mut p = vec![0u8; 240];
+        p[0] = 1;
+        p[28..34].copy_from_slice(&[0x3C, 0x22, 0xFB, 0x01, 0x02, 0x03]);
+        p[236..240].copy_from_slice(&[99, 130, 83, 99]);
+        p.extend_from_slice(options);
+        p.push(255);
+        p
+    }
+
+    fn with_params(params: &[u8], vendor: &str) -> Vec<u8> {
+        let mut opts = vec![53, 1, 1, 57, 2, 0x05, 0xDC, 55, params.len() as u8];
+        opts.extend_from_slice(params);
+        if !vendor.is_empty() {
+            opts.extend_from_slice(&[60, vendor.len() as u8]);
+            opts.extend_from_slice(vendor.as_bytes());
+        }
+        request(&opts)
+    }
+
+    #[test]
+    fn test_parse_request_options() {
+        let req = parse_request(&with_params(&[1, 3, 6, 15], "MSFT 5.0")).unwrap();
+        assert_eq!(req.mac, "3C:22:FB:01:02:03");
+        assert_eq!(req.message_type, Some(1));
+        assert_eq!(req.params, vec![1, 3, 6, 15]);
+        assert_eq!(req.vendor_class.as_deref(), Some("MSFT 5.0"));
+        assert_eq!(req.max_size, Some(1500));
+
+        // Server replies (BOOTREPLY) are not fingerprints
+        let mut reply = with_params(&[1, 3], "");
+        reply[0] = 2;
+        assert!(parse_request(&reply).is_none());
+    }
+
+    #[test]
+    fn test_windows_params_and_vendor() {
+        let params = [1, 3, 6, 15, 31, 33, 43, 44, 46, 47, 119, 121, 249, 252];
+        let fp = fingerprint(&parse_request(&with_params(&params, "MSFT 5.0")).unwrap()).unwrap();
+        assert_eq!(fp.os.as_deref(), Some("Windows 10/11"));
+        assert_eq!(fp.family.as_deref(), Some("Windows"));
+        assert_eq!(fp.params, "1,3,6,15,31,33,43,44,46,47,119,121,249,252");
+
+        // Unknown list, same vendor class: the vendor-only entry
+        let fp = fingerprint(&parse_request(&with_params(&[1, 3, 6], "MSFT 5.0")).unwrap()).unwrap();
+        assert_eq!(fp.os.as_deref(), Some("Windows"));
+    }
+
+    #[test]
+    fn test_vendor_version_and_prefix_length() {
+        let req = parse_request(&with_params(&[1, 3, 6, 15, 26, 28, 51, 58, 59, 43, 114], "android-dhcp-14")).unwrap();
+        let fp = fingerprint(&req).unwrap();
+        assert_eq!(fp.os.as_deref(), Some("Android 14"));
+        assert_eq!(fp.family.as_deref(), Some("Android"));
+
+        let fp = fingerprint(&parse_request(&with_params(&[1, 3, 6], "MSFT 5.0 XBOX")).unwrap()).unwrap();
+        assert_eq!(fp.family.as_deref(), Some("Game Console"));
+    }
+
+    #[test]
+    fn test_apple_by_params_and_unknown() {
+        let fp = fingerprint(&parse_request(&with_params(&[1, 121, 3, 6, 15, 119, 252], "")).unwrap()).unwrap();
+        assert_eq!(fp.family.as_deref(), Some("Apple iOS"));
+
+        let fp = fingerprint(&parse_request(&with_params(&[1, 2, 3], "")).unwrap()).unwrap();
+        assert_eq!(fp.os, None);
+        assert_eq!(fp.params, "1,2,3");
+    }
+}
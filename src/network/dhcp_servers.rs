@@ -0,0 +1,395 @@
+//! Rogue DHCP server detection.
+//!
+//! The sniffer sees the OFFER and ACK replies DHCP servers broadcast. They
+//! are grouped per network (subnet in CIDR form, as in the inventory) by
+//! server identifier (option 54). The capture has no link layer, so a
+//! server's MAC is whatever the device list holds for the reply's source
+//! address. A reply whose source is neither the network's gateway nor listed
+//! in `dhcp_servers` in `devices.json`, or comes from a different MAC than
+//! that server had before, raises a Critical "Rogue DHCP" alert, once per
+//! session. The last lease terms each server handed out (lease
+//! time, router, DNS servers, domain) are kept in
+//! `%APPDATA%\psnet\dhcp_servers.json` as the network's DHCP summary.
+
+use std::collections::{BTreeMap, HashSet};
+use std::net::Ipv4Addr;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+pub const DHCPOFFER: u8 = 2;
+pub const DHCPACK: u8 = 5;
+
+/// The options of a server → client OFFER or ACK that describe the lease.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DhcpReply {
+    /// Option 53: DHCPOFFER or DHCPACK.
+    pub message_type: u8,
+    /// Option 54; relayed and some embedded servers leave it out.
+    pub server_id: Option<Ipv4Addr>,
+    /// Address offered to the client (yiaddr).
+    pub your_ip: Ipv4Addr,
+    /// Client hardware address (chaddr) as "AA:BB:CC:DD:EE:FF".
+    pub client_mac: String,
+    pub lease_secs: Option<u32>,
+    pub subnet_mask: Option<Ipv4Addr>,
+    pub routers: Vec<Ipv4Addr>,
+    pub dns: Vec<Ipv4Addr>,
+    pub domain: Option<String>,
+}
+
+/// Parse a server → client DHCP message (BOOTREPLY). Only OFFER and ACK
+/// carry lease terms; NAKs and client messages give `None`.
+pub fn parse_reply(payload: &[u8]) -> Option<DhcpReply> {
+    if payload.len() < 244 || payload[0] != 2 || payload[236..240] != [99, 130, 83, 99] {
+        return None;
+    }
+    let mut reply = DhcpReply {
+        message_type: 0,
+        server_id: None,
+        your_ip: Ipv4Addr::new(payload[16], payload[17], payload[18], payload[19]),
+        client_mac: payload[28..34].iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(":"),
+        lease_secs: None,
+        subnet_mask: None,
+        routers: Vec::new(),
+        dns: Vec::new(),
+        domain: None,
+    };
+
+    let mut pos = 240;
+    while pos < payload.len() {
+        let tag = payload[pos];
+        if tag == 255 { break; }
+        if tag == 0 { pos += 1; continue; }
+        if pos + 1 >= payload.len() { break; }
+        let len = payload[pos + 1] as usize;
+        pos += 2;
+        if pos + len > payload.len() { break; }
+        let value = &payload[pos..pos + len];
+        match tag {
+            53 if len == 1 => reply.message_type = value[0],
+            54 if len == 4 => reply.server_id = Some(Ipv4Addr::new(value[0], value[1], value[2], value[3])),
+            51 if len == 4 => reply.lease_secs = Some(u32::from_be_bytes([value[0], value[1], value[2], value[3]])),
+            1 if len == 4 => reply.subnet_mask = Some(Ipv4Addr::new(value[0], value[1], value[2], value[3])),
+            3 => reply.routers = addrs(value),
+            6 => reply.dns = addrs(value),
+            15 => {
+                let domain = String::from_utf8_lossy(value).trim_end_matches('\0').trim().to_string();
+                if !domain.is_empty() {
+                    reply.domain = Some(domain);
+                }
+            }
+            _ => {}
+        }
+        pos += len;
+    }
+    matches!(reply.message_type, DHCPOFFER | DHCPACK).then_some(reply)
+}
+
+fn addrs(value: &[u8]) -> Vec<Ipv4Addr> {
+    value.chunks_exact(4).map(|c| Ipv4Addr::new(c[0], c[1], c[2], c[3])).collect()
+}
+
+/// A DHCP server seen on a network and the lease terms it last handed out.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DhcpServer {
+    /// Server identifier (option 54), else the reply's source address.
+    pub server_id: Ipv4Addr,
+    /// MAC the device list held for the reply's source address.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mac: Option<String>,
+    /// Neither the gateway nor a configured DHCP server when last seen.
+    pub rogue: bool,
+    pub offers: u32,
+    pub acks: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lease_secs: Option<u32>,
+    #[serde(default)]
+    pub routers: Vec<Ipv4Addr>,
+    #[serde(default)]
+    pub dns: Vec<Ipv4Addr>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+    #[serde(with = "super::inventory::rfc3339")]
+    pub first_seen: DateTime<Local>,
+    #[serde(with = "super::inventory::rfc3339")]
+    pub last_seen: DateTime<Local>,
+}
+
+/// An unexpected server heard for the first time this session.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RogueDhcp {
+    pub server_id: Ipv4Addr,
+    pub mac: Option<String>,
+    pub routers: Vec<Ipv4Addr>,
+    pub dns: Vec<Ipv4Addr>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DhcpServersFile {
+    networks: BTreeMap<String, Vec<DhcpServer>>,
+}
+
+pub struct DhcpServers {
+    networks: BTreeMap<String, Vec<DhcpServer>>,
+    /// Rogue servers not yet raised as alerts (drained by the app).
+    pub new_rogue: Vec<RogueDhcp>,
+    /// (network, server) pairs already alerted on this session.
+    alerted: HashSet<(String, Ipv4Addr)>,
+    /// Set when the summaries changed and have not been written yet.
+    dirty: bool,
+    path: PathBuf,
+}
+
+impl DhcpServers {
+    pub fn load() -> Self {
+        let path = match dirs::data_dir() {
+            Some(data_dir) => {
+                let dir = data_dir.join("psnet");
+                let _ = std::fs::create_dir_all(&dir);
+                dir.join("dhcp_servers.json")
+            }
+            None => PathBuf::from("psnet_dhcp_servers.json"),
+        };
+        let file: DhcpServersFile = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default();
+        Self {
+            networks: file.networks,
+            new_rogue: Vec::new(),
+            alerted: HashSet::new(),
+            dirty: false,
+            path,
+        }
+    }
+
+    /// Servers seen on a network, most recently heard first.
+    pub fn servers(&self, network: &str) -> Vec<&DhcpServer> {
+        let mut list: Vec<&DhcpServer> = self.networks.get(network).map(|l| l.iter().collect()).unwrap_or_default();
+        list.sort_by_key(|s| std::cmp::Reverse(s.last_seen));
+        list
+    }
+
+    /// Record a reply heard from `source` on `network`. `source_device_mac` is
+    /// the MAC the device list holds for `source`, if any. `expected` holds
+    /// the gateway and the configured servers; when it is empty nothing can
+    /// be judged and no server is flagged.
+    ///
+    /// Any server can put the gateway's address in option 54, so only the
+    /// source counts: it must be an expected server and, when both are known,
+    /// have the MAC that server was seen with before. Rogue replies are filed
+    /// under their source address.
+    pub fn record(
+        &mut self,
+        network: &str,
+        reply: &DhcpReply,
+        source: Ipv4Addr,
+        source_device_mac: Option<&str>,
+        expected: &[Ipv4Addr],
+        now: DateTime<Local>,
+    ) {
+        let source_device_mac = source_device_mac.filter(|m| !m.is_empty());
+        let same_mac = |known: Option<&str>| match (known, source_device_mac) {
+            (Some(known), Some(mac)) => known.eq_ignore_ascii_case(mac),
+            _ => true,
+        };
+        let list = self.networks.entry(network.to_string()).or_default();
+        let source_known = list
+            .iter()
+            .filter(|s| s.server_id == source && !s.rogue)
+            .all(|s| same_mac(s.mac.as_deref()));
+        let expected_source = expected.contains(&source) && source_known;
+        let rogue = !expected.is_empty() && !expected_source;
+        let server_id = if rogue { source } else { reply.server_id.unwrap_or(source) };
+        let idx = match list.iter().position(|s| s.server_id == server_id && same_mac(s.mac.as_deref())) {
+            Some(i) => i,
+            None => {
+                list.push(DhcpServer {
+                    server_id,
+                    mac: None,
+                    rogue,
+                    offers: 0,
+                    acks: 0,
+                    lease_secs: None,
+                    routers: Vec::new(),
+                    dns: Vec::new(),
+                    domain: None,
+                    first_seen: now,
+                    last_seen: now,
+                });
+                list.len() - 1
+            }
+        };
+        let server = &mut list[idx];
+        server.rogue = rogue;
+        server.last_seen = now;
+        if let Some(mac) = source_device_mac {
+            server.mac = Some(mac.to_string());
+        }
+        match reply.message_type {
+            DHCPOFFER => server.offers += 1,
+            _ => server.acks += 1,
+        }
+        if reply.lease_secs.is_some() {
+            server.lease_secs = reply.lease_secs;
+        }
+        if !reply.routers.is_empty() {
+            server.routers = reply.routers.clone();
+        }
+        if !reply.dns.is_empty() {
+            server.dns = reply.dns.clone();
+        }
+        if reply.domain.is_some() {
+            server.domain = reply.domain.clone();
+        }
+        self.dirty = true;
+
+        if rogue && self.alerted.insert((network.to_string(), server_id)) {
+            let server = &list[idx];
+            self.new_rogue.push(RogueDhcp {
+                server_id,
+                mac: server.mac.clone(),
+                routers: server.routers.clone(),
+                dns: server.dns.clone(),
+            });
+        }
+    }
+
+    /// Write the summaries if they changed since the last save.
+    pub fn save_if_dirty(&mut self) {
+        if !self.dirty {
+            return;
+        }
+        let file = DhcpServersFile { networks: self.networks.clone() };
+        if let Ok(json) = serde_json::to_string_pretty(&file) {
+            let _ = std::fs::write(&self.path, json);
+        }
+        self.dirty = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty() -> DhcpServers {
+        DhcpServers {
+            networks: BTreeMap::new(),
+            new_rogue: Vec::new(),
+            alerted: HashSet::new(),
+            dirty: false,
+            path: PathBuf::new(),
+        }
+    }
+
+    /// A minimal BOOTREPLY offering 192.168.1.50 with `options` (without the end marker).
+    fn reply(options: &[u8]) -> Vec<u8> {
+        let mut p = vec![0u8; 240];
+        p[0] = 2;
+        p[16..20].copy_from_slice(&[192, 168, 1, 50]);
+        p[28..34].copy_from_slice(&[0x3C, 0x22, 0xFB, 0x01, 0x02, 0x03]);
+        p[236..240].copy_from_slice(&[99, 130, 83, 99]);
+        p.extend_from_slice(options);
+        p.push(255);
+        p
+    }
+
+    fn offer(server: [u8; 4]) -> DhcpReply {
+        let mut opts = vec![53, 1, DHCPOFFER, 54, 4];
+        opts.extend_from_slice(&server);
+        opts.extend_from_slice(&[3, 4, server[0], server[1], server[2], server[3]]);
+        parse_reply(&reply(&opts)).unwrap()
+    }
+
+    #[test]
+    fn test_parse_offer_options() {
+        let r = parse_reply(&reply(&[
+            53, 1, 2,
+            54, 4, 192, 168, 1, 1,
+            51, 4, 0, 1, 81, 128,
+            1, 4, 255, 255, 255, 0,
+            3, 4, 192, 168, 1, 1,
+            6, 8, 1, 1, 1, 1, 8, 8, 8, 8,
+            15, 4, b'h', b'o', b'm', b'e',
+        ]))
+        .unwrap();
+        assert_eq!(r.message_type, DHCPOFFER);
+        assert_eq!(r.server_id, Some(Ipv4Addr::new(192, 168, 1, 1)));
+        assert_eq!(r.your_ip, Ipv4Addr::new(192, 168, 1, 50));
+        assert_eq!(r.client_mac, "3C:22:FB:01:02:03");
+        assert_eq!(r.lease_secs, Some(86400));
+        assert_eq!(r.subnet_mask, Some(Ipv4Addr::new(255, 255, 255, 0)));
+        assert_eq!(r.routers, vec![Ipv4Addr::new(192, 168, 1, 1)]);
+        assert_eq!(r.dns, vec![Ipv4Addr::new(1, 1, 1, 1), Ipv4Addr::new(8, 8, 8, 8)]);
+        assert_eq!(r.domain.as_deref(), Some("home"));
+
+        // NAKs and client requests carry no lease
+        assert!(parse_reply(&reply(&[53, 1, 6])).is_none());
+        let mut request = reply(&[53, 1, 1]);
+        request[0] = 1;
+        assert!(parse_reply(&request).is_none());
+    }
+
+    #[test]
+    fn test_rogue_server_alerts_once() {
+        let mut dhcp = empty();
+        let gw = Ipv4Addr::new(192, 168, 1, 1);
+        let now = Local::now();
+        dhcp.record("192.168.1.0/24", &offer([192, 168, 1, 1]), gw, None, &[gw], now);
+        assert!(dhcp.new_rogue.is_empty());
+
+        let rogue = Ipv4Addr::new(192, 168, 1, 66);
+        dhcp.record("192.168.1.0/24", &offer([192, 168, 1, 66]), rogue, Some("DE:AD:BE:EF:00:01"), &[gw], now);
+        dhcp.record("192.168.1.0/24", &offer([192, 168, 1, 66]), rogue, None, &[gw], now);
+        assert_eq!(dhcp.new_rogue.len(), 1);
+        assert_eq!(dhcp.new_rogue[0].server_id, rogue);
+        assert_eq!(dhcp.new_rogue[0].mac.as_deref(), Some("DE:AD:BE:EF:00:01"));
+        assert_eq!(dhcp.new_rogue[0].routers, vec![rogue]);
+
+        let servers = dhcp.servers("192.168.1.0/24");
+        assert_eq!(servers.len(), 2);
+        let r = servers.iter().find(|s| s.server_id == rogue).unwrap();
+        assert!(r.rogue);
+        assert_eq!(r.offers, 2);
+        assert_eq!(r.mac.as_deref(), Some("DE:AD:BE:EF:00:01"));
+    }
+
+    #[test]
+    fn test_configured_server_and_unknown_gateway() {
+        let mut dhcp = empty();
+        let gw = Ipv4Addr::new(10, 0, 0, 1);
+        let pihole = Ipv4Addr::new(10, 0, 0, 53);
+        let now = Local::now();
+        dhcp.record("10.0.0.0/24", &offer([10, 0, 0, 53]), pihole, None, &[gw, pihole], now);
+        // Without a gateway or configured servers nothing is judged
+        dhcp.record("172.16.0.0/24", &offer([172, 16, 0, 9]), Ipv4Addr::new(172, 16, 0, 9), None, &[], now);
+        assert!(dhcp.new_rogue.is_empty());
+        assert!(!dhcp.servers("10.0.0.0/24")[0].rogue);
+    }
+
+    #[test]
+    fn test_rogue_claiming_the_gateway_id() {
+        let mut dhcp = empty();
+        let gw = Ipv4Addr::new(192, 168, 1, 1);
+        let now = Local::now();
+        dhcp.record("192.168.1.0/24", &offer([192, 168, 1, 1]), gw, Some("AA:AA:AA:AA:AA:01"), &[gw], now);
+
+        // Option 54 names the gateway but the reply came from elsewhere
+        let rogue = Ipv4Addr::new(192, 168, 1, 66);
+        dhcp.record("192.168.1.0/24", &offer([192, 168, 1, 1]), rogue, None, &[gw], now);
+        assert_eq!(dhcp.new_rogue.len(), 1);
+        assert_eq!(dhcp.new_rogue[0].server_id, rogue);
+
+        // The gateway's address from another MAC
+        dhcp.record("192.168.1.0/24", &offer([192, 168, 1, 1]), gw, Some("DE:AD:BE:EF:00:02"), &[gw], now);
+        assert_eq!(dhcp.new_rogue.len(), 2);
+        assert_eq!(dhcp.new_rogue[1].mac.as_deref(), Some("DE:AD:BE:EF:00:02"));
+
+        let servers = dhcp.servers("192.168.1.0/24");
+        assert_eq!(servers.len(), 3);
+        let real = servers.iter().find(|s| !s.rogue).unwrap();
+        assert_eq!((real.server_id, real.mac.as_deref()), (gw, Some("AA:AA:AA:AA:AA:01")));
+    }
+}
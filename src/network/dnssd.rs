@@ -0,0 +1,540 @@
+//! mDNS / DNS-SD service browser.
+//!
+//! Enumerates the service types advertised on the LAN through the
+//! `_services._dns-sd._udp.local` meta-query (plus a list of well-known
+//! types for responders that skip it), browses each type for instances, and
+//! resolves every instance's SRV (host and port), TXT and the host's A/AAAA
+//! records. Records live in a [`DnsSdCache`] that each scan's browse
+//! refreshes: they expire with their TTL, and goodbye packets (TTL 0) remove
+//! them at once, so the Devices tab's services view stays current.
+
+use std::collections::{HashMap, HashSet};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, UdpSocket};
+use std::time::{Duration, Instant};
+
+use super::hostnames::{build_dns_query, read_dns_name, skip_dns_name};
+
+const META_QUERY: &str = "_services._dns-sd._udp.local";
+
+const TYPE_PTR: u16 = 12;
+const TYPE_TXT: u16 = 16;
+const TYPE_A: u16 = 1;
+const TYPE_AAAA: u16 = 28;
+const TYPE_SRV: u16 = 33;
+
+/// Browsed even when nobody answers the meta-query.
+const WELL_KNOWN: &[&str] = &[
+    "_http._tcp.local",
+    "_workstation._tcp.local",
+    "_smb._tcp.local",
+    "_device-info._tcp.local",
+    "_companion-link._tcp.local",
+    "_homekit._tcp.local",
+    "_hap._tcp.local",
+    "_airplay._tcp.local",
+    "_raop._tcp.local",
+    "_googlecast._tcp.local",
+    "_spotify-connect._tcp.local",
+    "_printer._tcp.local",
+    "_ipp._tcp.local",
+    "_ipps._tcp.local",
+    "_pdl-datastream._tcp.local",
+    "_scanner._tcp.local",
+    "_ssh._tcp.local",
+    "_sftp-ssh._tcp.local",
+    "_sleep-proxy._udp.local",
+    "_apple-mobdev2._tcp.local",
+];
+
+// ─── Records ─────────────────────────────────────────────────────────────────
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RData {
+    Ptr(String),
+    Srv { port: u16, target: String },
+    Txt(Vec<String>),
+    A(Ipv4Addr),
+    Aaaa(Ipv6Addr),
+}
+
+/// One answer, authority or additional record of an mDNS response.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    pub name: String,
+    pub ttl: u32,
+    pub data: RData,
+}
+
+/// Every PTR, SRV, TXT, A and AAAA record in an mDNS response.
+pub fn parse_records(buf: &[u8]) -> Vec<Record> {
+    let mut records = Vec::new();
+    if buf.len() < 12 { return records; }
+    let flags = ((buf[2] as u16) << 8) | buf[3] as u16;
+    if flags & 0x8000 == 0 { return records; }
+
+    let qdcount = ((buf[4] as u16) << 8) | buf[5] as u16;
+    let count = |i: usize| (((buf[i] as u16) << 8) | buf[i + 1] as u16) as usize;
+    let total_rr = count(6) + count(8) + count(10);
+
+    let mut pos = 12;
+    for _ in 0..qdcount {
+        match skip_dns_name(buf, pos) {
+            Some(p) => pos = p + 4,
+            None => return records,
+        }
+    }
+    // A record takes at least 11 bytes (root name + fixed fields); larger
+    // counts are lies
+    let total_rr = total_rr.min(buf.len().saturating_sub(pos) / 11);
+
+    for _ in 0..total_rr {
+        if pos >= buf.len() { break; }
+        let name = read_dns_name(buf, pos);
+        pos = match skip_dns_name(buf, pos) {
+            Some(p) => p,
+            None => break,
+        };
+        if pos + 10 > buf.len() { break; }
+        let rtype = ((buf[pos] as u16) << 8) | buf[pos + 1] as u16;
+        let ttl = u32::from_be_bytes([buf[pos + 4], buf[pos + 5], buf[pos + 6], buf[pos + 7]]);
+        let rdlength = (((buf[pos + 8] as u16) << 8) | buf[pos + 9] as u16) as usize;
+        pos += 10;
+        if pos + rdlength > buf.len() { break; }
+        let rdata = &buf[pos..pos + rdlength];
+
+        let data = match rtype {
+            TYPE_PTR => read_dns_name(buf, pos).map(RData::Ptr),
+            TYPE_SRV if rdlength > 6 => read_dns_name(buf, pos + 6).map(|target| RData::Srv {
+                port: u16::from_be_bytes([rdata[4], rdata[5]]),
+                target,
+            }),
+            TYPE_TXT => Some(RData::Txt(parse_txt(rdata))),
+            TYPE_A if rdlength == 4 => Some(RData::A(Ipv4Addr::new(rdata[0], rdata[1], rdata[2], rdata[3]))),
+            TYPE_AAAA if rdlength == 16 => {
+                let mut octets = [0u8; 16];
+                octets.copy_from_slice(rdata);
+                Some(RData::Aaaa(Ipv6Addr::from(octets)))
+            }
+            _ => None,
+        };
+        if let (Some(name), Some(data)) = (name, data) {
+            records.push(Record { name, ttl, data });
+        }
+        pos += rdlength;
+    }
+    records
+}
+
+/// TXT strings ("model=AppleTV6,2", "rp=ipp/print"); empty strings dropped.
+fn parse_txt(rdata: &[u8]) -> Vec<String> {
+    let mut entries = Vec::new();
+    let mut pos = 0;
+    while pos < rdata.len() {
+        let len = rdata[pos] as usize;
+        pos += 1;
+        if pos + len > rdata.len() { break; }
+        let entry = String::from_utf8_lossy(&rdata[pos..pos + len]).into_owned();
+        if !entry.is_empty() {
+            entries.push(entry);
+        }
+        pos += len;
+    }
+    entries
+}
+
+/// "Living Room._airplay._tcp.local" → "_airplay._tcp". The DNS-SD
+/// meta-service itself is not a service type.
+pub fn service_type(name: &str) -> Option<String> {
+    let labels: Vec<&str> = name.trim_end_matches('.').split('.').collect();
+    let i = labels.iter().rposition(|l| *l == "_tcp" || *l == "_udp")?;
+    let svc = labels.get(i.checked_sub(1)?)?;
+    if !svc.starts_with('_') || *svc == "_dns-sd" || *svc == "_services" {
+        return None;
+    }
+    Some(format!("{}.{}", svc, labels[i]))
+}
+
+/// Friendly name for a service type, e.g. "_googlecast._tcp" → "Chromecast".
+pub fn service_label(service_type: &str) -> &str {
+    match service_type {
+        "_airplay._tcp" => "AirPlay",
+        "_raop._tcp" => "AirPlay Audio",
+        "_companion-link._tcp" => "Apple Companion",
+        "_apple-mobdev2._tcp" => "Apple Device Sync",
+        "_sleep-proxy._udp" => "Bonjour Sleep Proxy",
+        "_device-info._tcp" => "Device Info",
+        "_googlecast._tcp" => "Chromecast",
+        "_spotify-connect._tcp" => "Spotify Connect",
+        "_homekit._tcp" | "_hap._tcp" => "HomeKit",
+        "_ipp._tcp" => "Printer (IPP)",
+        "_ipps._tcp" => "Printer (IPPS)",
+        "_printer._tcp" => "Printer (LPD)",
+        "_pdl-datastream._tcp" => "Printer (raw)",
+        "_scanner._tcp" | "_uscan._tcp" => "Scanner",
+        "_smb._tcp" => "SMB",
+        "_afpovertcp._tcp" => "AFP",
+        "_nfs._tcp" => "NFS",
+        "_ssh._tcp" => "SSH",
+        "_sftp-ssh._tcp" => "SFTP",
+        "_rfb._tcp" => "VNC",
+        "_rdp._tcp" => "RDP",
+        "_http._tcp" => "Web",
+        "_https._tcp" => "Web (HTTPS)",
+        "_workstation._tcp" => "Workstation",
+        "_matter._tcp" | "_matterc._udp" => "Matter",
+        "_hue._tcp" => "Philips Hue",
+        "_sonos._tcp" => "Sonos",
+        "_amzn-wplay._tcp" => "Amazon Fire TV",
+        "_mqtt._tcp" => "MQTT",
+        "_home-assistant._tcp" => "Home Assistant",
+        "_daap._tcp" => "iTunes Sharing",
+        "_adisk._tcp" => "Time Machine",
+        _ => service_type,
+    }
+}
+
+// ─── Cache ───────────────────────────────────────────────────────────────────
+
+/// A resolved service instance, as shown on the Devices tab.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DnsSdService {
+    /// Instance name, e.g. "Living Room".
+    pub instance: String,
+    /// e.g. "_airplay._tcp".
+    pub service_type: String,
+    /// SRV target, e.g. "Living-Room.local".
+    pub host: String,
+    pub port: u16,
+    /// TXT entries, e.g. "model=AppleTV6,2".
+    pub txt: Vec<String>,
+    /// The host's addresses, or the responder's address when it sent none.
+    pub addrs: Vec<IpAddr>,
+}
+
+impl DnsSdService {
+    pub fn label(&self) -> &str {
+        service_label(&self.service_type)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Instance {
+    /// Full name, e.g. "Living Room._airplay._tcp.local".
+    name: String,
+    expires: Instant,
+    srv: Option<(String, u16)>,
+    txt: Vec<String>,
+    /// Address the instance's records came from.
+    source: IpAddr,
+}
+
+#[derive(Clone, Default)]
+pub struct DnsSdCache {
+    /// Service types ("_ipp._tcp.local") → expiry.
+    types: HashMap<String, Instant>,
+    /// Instances keyed by lowercased full name.
+    instances: HashMap<String, Instance>,
+    /// Host names (lowercased) → addresses with their expiry.
+    hosts: HashMap<String, Vec<(IpAddr, Instant)>>,
+}
+
+impl DnsSdCache {
+    /// Fold a response from `source` into the cache.
+    pub fn ingest(&mut self, records: &[Record], source: IpAddr, now: Instant) {
+        for r in records {
+            let expires = now + Duration::from_secs(r.ttl as u64);
+            let key = r.name.trim_end_matches('.').to_lowercase();
+            match &r.data {
+                RData::Ptr(target) if key == META_QUERY => {
+                    let target = target.trim_end_matches('.').to_lowercase();
+                    if r.ttl == 0 {
+                        self.types.remove(&target);
+                    } else if service_type(&target).is_some() {
+                        self.types.insert(target, expires);
+                    }
+                }
+                RData::Ptr(target) => {
+                    // Only browse answers (type → instance), not reverse lookups
+                    if service_type(&key).is_none() || service_type(target).is_none() {
+                        continue;
+                    }
+                    let instance_key = target.trim_end_matches('.').to_lowercase();
+                    if r.ttl == 0 {
+                        self.instances.remove(&instance_key);
+                        continue;
+                    }
+                    self.types.entry(key).or_insert(expires);
+                    self.instance(&instance_key, target, source, expires);
+                }
+                RData::Srv { port, target } => {
+                    if service_type(&key).is_none() {
+                        continue;
+                    }
+                    if r.ttl == 0 {
+                        if let Some(i) = self.instances.get_mut(&key) {
+                            i.srv = None;
+                        }
+                        continue;
+                    }
+                    let host = target.trim_end_matches('.').to_string();
+                    self.instance(&key, &r.name, source, expires).srv = Some((host, *port));
+                }
+                RData::Txt(entries) => {
+                    if service_type(&key).is_none() || r.ttl == 0 {
+                        continue;
+                    }
+                    self.instance(&key, &r.name, source, expires).txt = entries.clone();
+                }
+                RData::A(ip) => self.address(&key, IpAddr::V4(*ip), r.ttl, expires),
+                RData::Aaaa(ip) => self.address(&key, IpAddr::V6(*ip), r.ttl, expires),
+            }
+        }
+    }
+
+    fn instance(&mut self, key: &str, name: &str, source: IpAddr, expires: Instant) -> &mut Instance {
+        let instance = self.instances.entry(key.to_string()).or_insert_with(|| Instance {
+            name: name.trim_end_matches('.').to_string(),
+            expires,
+            srv: None,
+            txt: Vec::new(),
+            source,
+        });
+        instance.expires = instance.expires.max(expires);
+        instance.source = source;
+        instance
+    }
+
+    fn address(&mut self, host: &str, ip: IpAddr, ttl: u32, expires: Instant) {
+        let addrs = self.hosts.entry(host.to_string()).or_default();
+        addrs.retain(|(a, _)| *a != ip);
+        if ttl > 0 {
+            addrs.push((ip, expires));
+        }
+    }
+
+    /// Drop everything whose TTL has run out.
+    pub fn prune(&mut self, now: Instant) {
+        self.types.retain(|_, exp| *exp > now);
+        self.instances.retain(|_, i| i.expires > now);
+        for addrs in self.hosts.values_mut() {
+            addrs.retain(|(_, exp)| *exp > now);
+        }
+        self.hosts.retain(|_, addrs| !addrs.is_empty());
+    }
+
+    /// Questions still worth asking: a browse for every known type, SRV and
+    /// TXT for instances missing them, and A/AAAA for hosts without addresses.
+    fn questions(&self) -> Vec<(String, u16)> {
+        let mut out: Vec<(String, u16)> = self.types.keys().map(|t| (t.clone(), TYPE_PTR)).collect();
+        for i in self.instances.values() {
+            match &i.srv {
+                None => out.push((i.name.clone(), TYPE_SRV)),
+                Some((host, _)) if !self.hosts.contains_key(&host.to_lowercase()) => {
+                    out.push((host.clone(), TYPE_A));
+                    out.push((host.clone(), TYPE_AAAA));
+                }
+                Some(_) => {}
+            }
+            if i.txt.is_empty() {
+                out.push((i.name.clone(), TYPE_TXT));
+            }
+        }
+        out
+    }
+
+    /// Every instance with a resolved SRV record.
+    pub fn services(&self) -> Vec<DnsSdService> {
+        let mut services: Vec<DnsSdService> = self
+            .instances
+            .values()
+            .filter_map(|i| {
+                let (host, port) = i.srv.clone()?;
+                let service_type = service_type(&i.name)?;
+                let suffix = format!(".{}", service_type);
+                let instance = match i.name.to_ascii_lowercase().rfind(&suffix.to_ascii_lowercase()) {
+                    Some(end) => i.name[..end].to_string(),
+                    None => i.name.clone(),
+                };
+                let mut addrs: Vec<IpAddr> = self
+                    .hosts
+                    .get(&host.to_lowercase())
+                    .map(|a| a.iter().map(|(ip, _)| *ip).collect())
+                    .unwrap_or_default();
+                if addrs.is_empty() {
+                    addrs.push(i.source);
+                }
+                Some(DnsSdService { instance, service_type, host, port, txt: i.txt.clone(), addrs })
+            })
+            .collect();
+        services.sort_by(|a, b| a.service_type.cmp(&b.service_type).then_with(|| a.instance.cmp(&b.instance)));
+        services
+    }
+
+    /// Host names with their addresses, for hostname resolution.
+    pub fn host_names(&self) -> Vec<(String, IpAddr)> {
+        self.hosts
+            .iter()
+            .flat_map(|(host, addrs)| addrs.iter().map(move |(ip, _)| (host.clone(), *ip)))
+            .collect()
+    }
+}
+
+/// Services advertised by one address, in display order.
+pub fn services_for(services: &[DnsSdService], ip: IpAddr) -> Vec<&DnsSdService> {
+    services.iter().filter(|s| s.addrs.contains(&ip)).collect()
+}
+
+// ─── Browsing ────────────────────────────────────────────────────────────────
+
+/// Browse for `timeout`, asking follow-up questions as answers come in,
+/// then expire stale records. `sock` must be joined to 224.0.0.251.
+pub fn browse(sock: &UdpSocket, cache: &mut DnsSdCache, timeout: Duration) {
+    let mut asked: HashSet<(String, u16)> = HashSet::new();
+    let mut ask = |name: &str, qtype: u16| {
+        if asked.insert((name.to_lowercase(), qtype)) {
+            let _ = sock.send_to(&build_dns_query(name, qtype), "224.0.0.251:5353");
+        }
+    };
+    ask(META_QUERY, TYPE_PTR);
+    for svc in WELL_KNOWN {
+        ask(svc, TYPE_PTR);
+    }
+    for (name, qtype) in cache.questions() {
+        ask(&name, qtype);
+    }
+
+    let deadline = Instant::now() + timeout;
+    let mut buf = [0u8; 9000];
+    while Instant::now() < deadline {
+        let _ = sock.set_read_timeout(Some(
+            deadline.saturating_duration_since(Instant::now()).max(Duration::from_millis(10)),
+        ));
+        match sock.recv_from(&mut buf) {
+            Ok((len, src)) => {
+                let records = parse_records(&buf[..len]);
+                if records.is_empty() {
+                    continue;
+                }
+                cache.ingest(&records, src.ip(), Instant::now());
+                for (name, qtype) in cache.questions() {
+                    ask(&name, qtype);
+                }
+            }
+            Err(_) => break,
+        }
+    }
+    cache.prune(Instant::now());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Response with no questions and the given records as answers.
+    fn response(records: &[(&str, u16, u32, Vec<u8>)]) -> Vec<u8> {
+        let mut pkt = vec![0, 0, 0x84, 0, 0, 0, 0, records.len() as u8, 0, 0, 0, 0];
+        for (name, rtype, ttl, rdata) in records {
+            super::super::hostnames::dns_encode_name(&mut pkt, name);
+            pkt.extend_from_slice(&rtype.to_be_bytes());
+            pkt.extend_from_slice(&[0x80, 0x01]);
+            pkt.extend_from_slice(&ttl.to_be_bytes());
+            pkt.extend_from_slice(&(rdata.len() as u16).to_be_bytes());
+            pkt.extend_from_slice(rdata);
+        }
+        pkt
+    }
+
+    fn name(n: &str) -> Vec<u8> {
+        let mut v = Vec::new();
+        super::super::hostnames::dns_encode_name(&mut v, n);
+        v
+    }
+
+    fn srv(port: u16, target: &str) -> Vec<u8> {
+        let mut v = vec![0, 0, 0, 0];
+        v.extend_from_slice(&port.to_be_bytes());
+        v.extend(name(target));
+        v
+    }
+
+    const SRC: IpAddr = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 40));
+
+    fn airplay() -> Vec<u8> {
+        response(&[
+            (META_QUERY, TYPE_PTR, 4500, name("_airplay._tcp.local")),
+            ("_airplay._tcp.local", TYPE_PTR, 4500, name("Living Room._airplay._tcp.local")),
+            ("Living Room._airplay._tcp.local", TYPE_SRV, 120, srv(7000, "Living-Room.local")),
+            ("Living Room._airplay._tcp.local", TYPE_TXT, 4500, b"\x10model=AppleTV6,2\x00".to_vec()),
+            ("Living-Room.local", TYPE_A, 120, vec![192, 168, 1, 40]),
+        ])
+    }
+
+    #[test]
+    fn test_parse_records() {
+        let records = parse_records(&airplay());
+        assert_eq!(records.len(), 5);
+        assert_eq!(records[2].data, RData::Srv { port: 7000, target: "Living-Room.local".into() });
+        assert_eq!(records[3].data, RData::Txt(vec!["model=AppleTV6,2".into()]));
+        assert_eq!(records[4].data, RData::A(Ipv4Addr::new(192, 168, 1, 40)));
+        assert_eq!(records[4].ttl, 120);
+    }
+
+    #[test]
+    fn test_parse_records_with_oversized_counts() {
+        let mut pkt = airplay();
+        pkt[6..12].copy_from_slice(&[0xFF; 6]);
+        assert_eq!(parse_records(&pkt).len(), 5);
+    }
+
+    #[test]
+    fn test_cache_resolves_instances() {
+        let mut cache = DnsSdCache::default();
+        let now = Instant::now();
+        cache.ingest(&parse_records(&airplay()), SRC, now);
+        let services = cache.services();
+        assert_eq!(services.len(), 1);
+        let s = &services[0];
+        assert_eq!(s.instance, "Living Room");
+        assert_eq!(s.service_type, "_airplay._tcp");
+        assert_eq!(s.label(), "AirPlay");
+        assert_eq!((s.host.as_str(), s.port), ("Living-Room.local", 7000));
+        assert_eq!(s.txt, vec!["model=AppleTV6,2"]);
+        assert_eq!(s.addrs, vec![SRC]);
+        assert!(cache.questions().iter().all(|(_, t)| *t == TYPE_PTR));
+    }
+
+    #[test]
+    fn test_questions_follow_up_missing_records() {
+        let mut cache = DnsSdCache::default();
+        let pkt = response(&[("_ipp._tcp.local", TYPE_PTR, 4500, name("Office._ipp._tcp.local"))]);
+        cache.ingest(&parse_records(&pkt), SRC, Instant::now());
+        let q = cache.questions();
+        assert!(q.contains(&("Office._ipp._tcp.local".into(), TYPE_SRV)));
+        assert!(q.contains(&("Office._ipp._tcp.local".into(), TYPE_TXT)));
+        // No SRV yet, so nothing to show
+        assert!(cache.services().is_empty());
+    }
+
+    #[test]
+    fn test_goodbye_and_expiry() {
+        let mut cache = DnsSdCache::default();
+        let now = Instant::now();
+        cache.ingest(&parse_records(&airplay()), SRC, now);
+        let bye = response(&[("_airplay._tcp.local", TYPE_PTR, 0, name("Living Room._airplay._tcp.local"))]);
+        cache.ingest(&parse_records(&bye), SRC, now);
+        assert!(cache.services().is_empty());
+
+        cache.ingest(&parse_records(&airplay()), SRC, now);
+        cache.prune(now + Duration::from_secs(5000));
+        assert!(cache.services().is_empty());
+        assert!(cache.host_names().is_empty());
+    }
+
+    #[test]
+    fn test_service_type() {
+        assert_eq!(service_type("Living Room._airplay._tcp.local.").as_deref(), Some("_airplay._tcp"));
+        assert_eq!(service_type("My.Printer._ipp._tcp.local").as_deref(), Some("_ipp._tcp"));
+        assert_eq!(service_type("_services._dns-sd._udp.local"), None);
+        assert_eq!(service_type("40.1.168.192.in-addr.arpa"), None);
+    }
+}
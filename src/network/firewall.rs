@@ -350,37 +350,214 @@ fn fetch_firewall_rules() -> Vec<FirewallRule> {
 
 fn parse_firewall_rules(text: &str) -> Vec<FirewallRule> {
     let mut rules = Vec::new();
-    let mut current_name = String::new();
-    let mut current_enabled = true;
-    let mut in_rule = false;
+    let mut current: Option<FirewallRule> = None;
 
     for line in text.lines() {
         let trimmed = line.trim();
-
-        if trimmed.starts_with("Rule Name:") {
-            // Save previous rule
-            if in_rule && !current_name.is_empty() {
-                rules.push(FirewallRule {
-                    name: current_name.clone(),
-                    enabled: current_enabled,
+        let Some((key, value)) = trimmed.split_once(':') else { continue };
+        let value = value.trim();
+
+        match key.trim() {
+            "Rule Name" => {
+                // Save previous rule
+                if let Some(rule) = current.take().filter(|r| !r.name.is_empty()) {
+                    rules.push(rule);
+                }
+                current = Some(FirewallRule {
+                    name: value.to_string(),
+                    enabled: true,
+                    program: "Any".to_string(),
+                    local_ports: "Any".to_string(),
+                    protocol: "Any".to_string(),
+                    ..Default::default()
                 });
             }
-            current_name = trimmed.splitn(2, ':').nth(1).unwrap_or("").trim().to_string();
-            current_enabled = true;
-            in_rule = true;
-        } else if trimmed.starts_with("Enabled:") {
-            let val = trimmed.splitn(2, ':').nth(1).unwrap_or("").trim();
-            current_enabled = val.eq_ignore_ascii_case("yes");
+            field => {
+                let Some(rule) = current.as_mut() else { continue };
+                match field {
+                    "Enabled" => rule.enabled = value.eq_ignore_ascii_case("yes"),
+                    "Direction" => rule.direction = value.to_string(),
+                    "Action" => rule.action = value.to_string(),
+                    // split_once keeps the drive-letter colon in the value
+                    "Program" => {
+                        rule.program = value.to_string();
+                        rule.program_path = expand_env_vars(value);
+                    }
+                    "LocalPort" => rule.local_ports = value.to_string(),
+                    "Protocol" => rule.protocol = value.to_string(),
+                    _ => {}
+                }
+            }
         }
     }
 
     // Push the last rule
-    if in_rule && !current_name.is_empty() {
-        rules.push(FirewallRule {
-            name: current_name,
-            enabled: current_enabled,
-        });
+    if let Some(rule) = current.filter(|r| !r.name.is_empty()) {
+        rules.push(rule);
     }
 
     rules
 }
+
+// ─── Inbound reachability ────────────────────────────────────────────────────
+
+/// Whether unsolicited inbound traffic can reach a listening program/port.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InboundAccess {
+    /// Windows Firewall is off.
+    FirewallOff,
+    /// Allowed by the named rule.
+    Allowed(String),
+    /// Blocked by the named rule, or by the default inbound policy (None).
+    Blocked(Option<String>),
+}
+
+impl InboundAccess {
+    pub fn is_allowed(&self) -> bool {
+        !matches!(self, InboundAccess::Blocked(_))
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            InboundAccess::FirewallOff => "firewall is off".to_string(),
+            InboundAccess::Allowed(rule) => format!("inbound allowed by rule \"{}\"", rule),
+            InboundAccess::Blocked(Some(rule)) => format!("inbound blocked by rule \"{}\"", rule),
+            InboundAccess::Blocked(None) => "no inbound allow rule (default block)".to_string(),
+        }
+    }
+}
+
+impl FirewallManager {
+    /// Inbound verdict for a listener, from the last rule refresh.
+    pub fn inbound_access(&self, program: &str, port: u16, protocol: &str) -> InboundAccess {
+        inbound_access(&self.rules, self.enabled, program, port, protocol)
+    }
+}
+
+/// Evaluate inbound rules the way Windows Firewall does: a matching block
+/// rule wins over any allow rule, and with no match the default inbound
+/// policy blocks. Profiles, remote addresses and services are not considered.
+pub fn inbound_access(rules: &[FirewallRule], enabled: bool, program: &str, port: u16, protocol: &str) -> InboundAccess {
+    if !enabled {
+        return InboundAccess::FirewallOff;
+    }
+    let matching = |action: &str| {
+        rules.iter().find(|r| {
+            r.enabled
+                && r.direction.eq_ignore_ascii_case("in")
+                && r.action.eq_ignore_ascii_case(action)
+                && (r.protocol.eq_ignore_ascii_case("any") || r.protocol.eq_ignore_ascii_case(protocol))
+                && program_matches(r, program)
+                && port_matches(&r.local_ports, port)
+        })
+    };
+    if let Some(rule) = matching("block") {
+        return InboundAccess::Blocked(Some(rule.name.clone()));
+    }
+    match matching("allow") {
+        Some(rule) => InboundAccess::Allowed(rule.name.clone()),
+        None => InboundAccess::Blocked(None),
+    }
+}
+
+fn program_matches(rule: &FirewallRule, program: &str) -> bool {
+    if rule.program.is_empty() || rule.program.eq_ignore_ascii_case("any") {
+        return true;
+    }
+    !program.is_empty() && rule.program_path.eq_ignore_ascii_case(program)
+}
+
+/// "Any", or a comma list of ports and ranges. Keywords such as "RPC" or
+/// "IPHTTPS" never match an ordinary listener.
+fn port_matches(spec: &str, port: u16) -> bool {
+    if spec.is_empty() || spec.eq_ignore_ascii_case("any") {
+        return true;
+    }
+    spec.split(',').map(str::trim).any(|part| match part.split_once('-') {
+        Some((lo, hi)) => match (lo.trim().parse::<u16>(), hi.trim().parse::<u16>()) {
+            (Ok(lo), Ok(hi)) => (lo..=hi).contains(&port),
+            _ => false,
+        },
+        None => part.parse::<u16>() == Ok(port),
+    })
+}
+
+/// Rules often store paths like `%ProgramFiles%\app\app.exe`; expanded once
+/// when the rules are read, not on every exposure check.
+fn expand_env_vars(path: &str) -> String {
+    let mut out = String::new();
+    let mut rest = path;
+    while let Some(start) = rest.find('%') {
+        let Some(len) = rest[start + 1..].find('%') else { break };
+        let name = &rest[start + 1..start + 1 + len];
+        out.push_str(&rest[..start]);
+        match std::env::var(name) {
+            Ok(value) => out.push_str(&value),
+            Err(_) => out.push_str(&rest[start..start + len + 2]),
+        }
+        rest = &rest[start + len + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VERBOSE: &str = "
+Rule Name:                            Redis
+----------------------------------------------------------------------
+Enabled:                              Yes
+Direction:                            In
+Profiles:                             Domain,Private
+Grouping:
+LocalIP:                              Any
+RemoteIP:                             Any
+Protocol:                             TCP
+LocalPort:                            6379,7000-7010
+RemotePort:                           Any
+Edge traversal:                       No
+Program:                              C:\\Redis\\redis-server.exe
+Action:                               Allow
+
+Rule Name:                            Block Telnet
+----------------------------------------------------------------------
+Enabled:                              Yes
+Direction:                            In
+Protocol:                             TCP
+LocalPort:                            23
+Action:                               Block
+";
+
+    #[test]
+    fn test_parse_verbose_rules() {
+        let rules = parse_firewall_rules(VERBOSE);
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].program, "C:\\Redis\\redis-server.exe");
+        assert_eq!(rules[0].program_path, rules[0].program);
+        assert_eq!(rules[0].local_ports, "6379,7000-7010");
+        assert_eq!(rules[1].action, "Block");
+        assert_eq!(rules[1].program, "Any");
+    }
+
+    #[test]
+    fn test_inbound_access() {
+        let rules = parse_firewall_rules(VERBOSE);
+        let redis = "c:\\redis\\redis-server.exe";
+        assert_eq!(inbound_access(&rules, true, redis, 6379, "TCP"), InboundAccess::Allowed("Redis".into()));
+        assert_eq!(inbound_access(&rules, true, redis, 7005, "TCP"), InboundAccess::Allowed("Redis".into()));
+        assert_eq!(inbound_access(&rules, true, redis, 6379, "UDP"), InboundAccess::Blocked(None));
+        assert_eq!(inbound_access(&rules, true, "C:\\other.exe", 6379, "TCP"), InboundAccess::Blocked(None));
+        assert_eq!(inbound_access(&rules, true, "C:\\telnetd.exe", 23, "TCP"), InboundAccess::Blocked(Some("Block Telnet".into())));
+        assert_eq!(inbound_access(&rules, false, "C:\\other.exe", 6379, "TCP"), InboundAccess::FirewallOff);
+    }
+
+    #[test]
+    fn test_port_spec() {
+        assert!(port_matches("Any", 1));
+        assert!(port_matches("80, 443", 443));
+        assert!(!port_matches("RPC", 135));
+        assert!(!port_matches("5000-5010", 5011));
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! Runs 13 methods in parallel to discover device hostnames:
 //! 1. NBNS (NetBIOS Name Service) — Windows/Samba devices
-//! 2. mDNS service browsing on port 5353 — Apple/IoT/Linux
+//! 2. mDNS / DNS-SD service browsing on port 5353 — Apple/IoT/Linux
 //! 3. mDNS per-IP unicast reverse PTR — any mDNS responder (Apple, Avahi, IoT)
 //! 4. Windows DNS cache (DnsGetCacheDataTable) — instant, no network traffic
 //! 5. SSDP/UPnP + XML friendlyName — routers, smart TVs, media devices
@@ -24,6 +24,11 @@ use std::time::Duration;
 
 use dns_lookup::lookup_addr;
 
+use crate::types::DeviceService;
+
+use super::dnssd::DnsSdCache;
+use super::snmp::SnmpInfo;
+
 /// Result of hostname resolution: best hostname + all discovered details.
 pub struct ResolvedDevice {
     /// Best hostname chosen by priority ranking.
@@ -32,32 +37,50 @@ pub struct ResolvedDevice {
     pub details: String,
     /// Open ports discovered via TCP connect scan.
     pub open_ports: Vec<u16>,
+    /// Device-type evidence that is not a name: "mdns:_ipp._tcp",
+    /// "upnp:MediaRenderer", "model:Sonos One".
+    pub hints: Vec<String>,
+    /// Products identified behind the open ports.
+    pub services: Vec<DeviceService>,
 }
 
 /// Resolve hostnames for a list of IPs using all available methods in parallel.
 /// `gateway` is used to query the router's DNS directly for PTR records.
 /// `dhcp_hostnames` contains hostnames extracted from captured DHCP packets.
+/// `ports` are connect-scanned and whatever answers is fingerprinted, except
+/// the ports in `fingerprinted`, which an earlier scan already identified.
+/// `snmp` is what `snmp::poll_batch` returned for these IPs; its sysNames
+/// rank as the SNMP method.
+/// `dnssd` is the DNS-SD cache the mDNS browse refreshes.
 /// Returns a map of IP -> ResolvedDevice (best hostname + aggregated details).
 /// Total wall-clock time: ~4 seconds (all methods run concurrently).
 pub fn resolve_all(
     ips: &[Ipv4Addr],
     gateway: Option<Ipv4Addr>,
     dhcp_hostnames: &HashMap<Ipv4Addr, String>,
+    ports: &[u16],
+    fingerprinted: &HashMap<Ipv4Addr, Vec<u16>>,
+    snmp: &HashMap<Ipv4Addr, SnmpInfo>,
+    dnssd: &Mutex<DnsSdCache>,
 ) -> HashMap<Ipv4Addr, ResolvedDevice> {
     // Collect ALL tagged results from every method: (ip, source_tag, name)
     let tagged: Mutex<Vec<(Ipv4Addr, &str, String)>> = Mutex::new(Vec::new());
     // Port scan results: ip -> sorted open ports
     let port_results: Mutex<HashMap<Ipv4Addr, Vec<u16>>> = Mutex::new(HashMap::new());
+    // Service fingerprints for the open ports: ip -> services
+    let service_results: Mutex<HashMap<Ipv4Addr, Vec<DeviceService>>> = Mutex::new(HashMap::new());
+    // Device-type hints (mDNS service types, UPnP device descriptions)
+    let hint_results: Mutex<Vec<(Ipv4Addr, String)>> = Mutex::new(Vec::new());
 
     thread::scope(|s| {
-        // Port scanning — runs in parallel with hostname methods
+        // Port scanning, then service detection on what answered — runs in
+        // parallel with hostname methods
         let port_results_ref = &port_results;
+        let service_results_ref = &service_results;
         s.spawn(move || {
-            let scanned = scan_ports_batch(ips, 400);
-            let mut pr = port_results_ref.lock().unwrap();
-            for (ip, ports) in scanned {
-                pr.insert(ip, ports);
-            }
+            let (scanned, services) = scan_and_identify(ips, ports, fingerprinted);
+            *service_results_ref.lock().unwrap() = services;
+            *port_results_ref.lock().unwrap() = scanned;
         });
 
         let tagged_ref = &tagged;
@@ -68,10 +91,13 @@ pub fn resolve_all(
         });
 
         let tagged_ref = &tagged;
+        let hints_ref = &hint_results;
         s.spawn(move || {
-            for (ip, name) in resolve_mdns_browse(3000) {
+            let (names, services) = resolve_mdns_browse(dnssd, 3000);
+            for (ip, name) in names {
                 tagged_ref.lock().unwrap().push((ip, "mDNS", name));
             }
+            hints_ref.lock().unwrap().extend(services.into_iter().map(|(ip, svc)| (ip, format!("mdns:{}", svc))));
         });
 
         let tagged_ref = &tagged;
@@ -89,10 +115,13 @@ pub fn resolve_all(
         });
 
         let tagged_ref = &tagged;
+        let hints_ref = &hint_results;
         s.spawn(move || {
-            for (ip, name) in resolve_ssdp(3000) {
+            let (names, hints) = resolve_ssdp(3000);
+            for (ip, name) in names {
                 tagged_ref.lock().unwrap().push((ip, "UPnP", name));
             }
+            hints_ref.lock().unwrap().extend(hints);
         });
 
         let tagged_ref = &tagged;
@@ -116,13 +145,6 @@ pub fn resolve_all(
             }
         });
 
-        let tagged_ref = &tagged;
-        s.spawn(move || {
-            for (ip, name) in resolve_snmp_batch(ips, 1500) {
-                tagged_ref.lock().unwrap().push((ip, "SNMP", name));
-            }
-        });
-
         let tagged_ref = &tagged;
         s.spawn(move || {
             for (ip, name) in resolve_telnet_banner(ips, 1500) {
@@ -156,11 +178,90 @@ pub fn resolve_all(
                 }
             }
         }
+
+        // Method 9: SNMP sysName (from the polled inventory)
+        {
+            let tagged_ref = &tagged;
+            for &ip in ips {
+                if let Some(name) = snmp.get(&ip).and_then(SnmpInfo::hostname) {
+                    tagged_ref.lock().unwrap().push((ip, "SNMP", name.to_string()));
+                }
+            }
+        }
     });
 
-    // Now aggregate: pick best hostname + build details string per IP
-    let all = tagged.into_inner().unwrap();
-    let ports_map = port_results.into_inner().unwrap();
+    aggregate(
+        ips,
+        tagged.into_inner().unwrap(),
+        port_results.into_inner().unwrap(),
+        service_results.into_inner().unwrap(),
+        hint_results.into_inner().unwrap(),
+    )
+}
+
+/// Resolve hosts outside the local subnet with the methods that ask each
+/// host directly: NetBIOS node status, reverse DNS and the SNMP sysNames in
+/// `snmp`, plus the port scan and service detection. Multicast and
+/// LAN-wide methods would only find local devices.
+pub fn resolve_unicast(
+    ips: &[Ipv4Addr],
+    ports: &[u16],
+    snmp: &HashMap<Ipv4Addr, SnmpInfo>,
+) -> HashMap<Ipv4Addr, ResolvedDevice> {
+    let (nbns, dns, (scanned, services)) = thread::scope(|s| {
+        let nbns = s.spawn(|| resolve_nbns_batch(ips, 1200));
+        let dns = s.spawn(|| resolve_dns_batch(ips, 3500));
+        let ported = scan_and_identify(ips, ports, &HashMap::new());
+        (nbns.join().unwrap_or_default(), dns.join().unwrap_or_default(), ported)
+    });
+    let mut tagged: Vec<(Ipv4Addr, &str, String)> = Vec::new();
+    tagged.extend(nbns.into_iter().map(|(ip, name)| (ip, "NBNS", name)));
+    tagged.extend(dns.into_iter().map(|(ip, name)| (ip, "DNS", name)));
+    for &ip in ips {
+        if let Some(name) = snmp.get(&ip).and_then(SnmpInfo::hostname) {
+            tagged.push((ip, "SNMP", name.to_string()));
+        }
+    }
+    aggregate(ips, tagged, scanned, services, Vec::new())
+}
+
+/// Connect-scan `ports` and fingerprint what answers, skipping the ports in
+/// `fingerprinted`. Returns the open ports and the identified services.
+fn scan_and_identify(
+    ips: &[Ipv4Addr],
+    ports: &[u16],
+    fingerprinted: &HashMap<Ipv4Addr, Vec<u16>>,
+) -> (HashMap<Ipv4Addr, Vec<u16>>, HashMap<Ipv4Addr, Vec<DeviceService>>) {
+    let scanned = scan_ports_batch(ips, ports, 400);
+    let fresh: Vec<(Ipv4Addr, Vec<u16>)> = scanned
+        .iter()
+        .map(|(ip, open)| {
+            let known = fingerprinted.get(ip);
+            let new: Vec<u16> = open.iter().copied().filter(|p| !known.is_some_and(|k| k.contains(p))).collect();
+            (*ip, new)
+        })
+        .filter(|(_, new)| !new.is_empty())
+        .collect();
+    let services = super::device_services::identify_batch(&fresh);
+    (scanned.into_iter().collect(), services)
+}
+
+/// Pick the best hostname and build the details string per IP from every
+/// method's (ip, source tag, name) results.
+fn aggregate(
+    ips: &[Ipv4Addr],
+    all: Vec<(Ipv4Addr, &str, String)>,
+    ports_map: HashMap<Ipv4Addr, Vec<u16>>,
+    mut services_map: HashMap<Ipv4Addr, Vec<DeviceService>>,
+    hint_results: Vec<(Ipv4Addr, String)>,
+) -> HashMap<Ipv4Addr, ResolvedDevice> {
+    let mut hints_map: HashMap<Ipv4Addr, Vec<String>> = HashMap::new();
+    for (ip, hint) in hint_results {
+        let list = hints_map.entry(ip).or_default();
+        if !list.contains(&hint) {
+            list.push(hint);
+        }
+    }
     let mut per_ip: HashMap<Ipv4Addr, Vec<(&str, String)>> = HashMap::new();
     for (ip, tag, name) in &all {
         per_ip.entry(*ip).or_default().push((tag, name.clone()));
@@ -216,7 +317,9 @@ pub fn resolve_all(
         let details = details_parts.join("  ");
 
         let open_ports = ports_map.get(&ip).cloned().unwrap_or_default();
-        result.insert(ip, ResolvedDevice { hostname, details, open_ports });
+        let hints = hints_map.remove(&ip).unwrap_or_default();
+        let services = services_map.remove(&ip).unwrap_or_default();
+        result.insert(ip, ResolvedDevice { hostname, details, open_ports, hints, services });
     }
 
     // For IPs that only have port scan results (no hostname found)
@@ -226,6 +329,21 @@ pub fn resolve_all(
                 hostname: String::new(),
                 details: String::new(),
                 open_ports: ports.clone(),
+                hints: hints_map.remove(ip).unwrap_or_default(),
+                services: services_map.remove(ip).unwrap_or_default(),
+            });
+        }
+    }
+
+    // Hints alone (e.g. an mDNS service from an IP nothing else resolved)
+    for (ip, hints) in hints_map {
+        if ips.contains(&ip) {
+            result.insert(ip, ResolvedDevice {
+                hostname: String::new(),
+                details: String::new(),
+                open_ports: Vec::new(),
+                hints,
+                services: Vec::new(),
             });
         }
     }
@@ -327,8 +445,14 @@ fn parse_nbns_response(buf: &[u8]) -> Option<String> {
 
 /// Bind to port 5353 with SO_REUSEADDR so we receive multicast responses
 /// that are sent to the standard mDNS port (not back to an ephemeral port).
-fn resolve_mdns_browse(timeout_ms: u64) -> Vec<(Ipv4Addr, String)> {
+/// (ip, name) pairs, and (ip, hint) pairs for device classification.
+type NamesAndHints = (Vec<(Ipv4Addr, String)>, Vec<(Ipv4Addr, String)>);
+
+/// Browse DNS-SD into `cache`, then return (ip, name) pairs — instance and
+/// host names — plus (ip, service type) pairs such as "_ipp._tcp".
+fn resolve_mdns_browse(cache: &Mutex<DnsSdCache>, timeout_ms: u64) -> NamesAndHints {
     let mut results = Vec::new();
+    let mut services = Vec::new();
 
     // Try binding to port 5353 first (with reuse), fall back to ephemeral
     let sock = bind_mdns_socket().unwrap_or_else(|| {
@@ -336,64 +460,43 @@ fn resolve_mdns_browse(timeout_ms: u64) -> Vec<(Ipv4Addr, String)> {
     });
     let sock = match sock {
         Some(s) => s,
-        None => return results,
+        None => return (results, services),
     };
-    let _ = sock.set_read_timeout(Some(Duration::from_millis(200)));
     let _ = sock.set_nonblocking(false);
     let mdns_addr = Ipv4Addr::new(224, 0, 0, 251);
     let _ = sock.join_multicast_v4(&mdns_addr, &Ipv4Addr::UNSPECIFIED);
 
-    // Browse many service types to maximize coverage
-    for svc in &[
-        "_services._dns-sd._udp.local",
-        "_http._tcp.local",
-        "_workstation._tcp.local",
-        "_smb._tcp.local",
-        "_device-info._tcp.local",
-        "_companion-link._tcp.local",
-        "_homekit._tcp.local",
-        "_airplay._tcp.local",
-        "_raop._tcp.local",
-        "_googlecast._tcp.local",
-        "_spotify-connect._tcp.local",
-        "_printer._tcp.local",
-        "_ipp._tcp.local",
-        "_pdl-datastream._tcp.local",
-        "_ssh._tcp.local",
-        "_sftp-ssh._tcp.local",
-        "_sleep-proxy._udp.local",
-        "_apple-mobdev2._tcp.local",
-    ] {
-        let pkt = build_dns_query(svc, 12); // PTR query
-        let _ = sock.send_to(&pkt, "224.0.0.251:5353");
-    }
-
-    let deadline = std::time::Instant::now() + Duration::from_millis(timeout_ms);
-    let mut buf = [0u8; 2048];
+    // Browse on a copy so the UI is not blocked on the cache meanwhile
+    let Ok(mut browsed) = cache.lock().map(|c| c.clone()) else {
+        return (results, services);
+    };
+    super::dnssd::browse(&sock, &mut browsed, Duration::from_millis(timeout_ms));
+    let _ = sock.leave_multicast_v4(&mdns_addr, &Ipv4Addr::UNSPECIFIED);
+    let Ok(mut cache) = cache.lock() else {
+        return (results, services);
+    };
+    *cache = browsed;
 
-    while std::time::Instant::now() < deadline {
-        let _ = sock.set_read_timeout(Some(
-            deadline.saturating_duration_since(std::time::Instant::now())
-                .max(Duration::from_millis(10))
-        ));
-        match sock.recv_from(&mut buf) {
-            Ok((len, src)) => {
-                if let Some(names) = parse_mdns_response(&buf[..len]) {
-                    let ip = match src.ip() {
-                        IpAddr::V4(v4) => v4,
-                        _ => continue,
-                    };
-                    for name in names {
-                        results.push((ip, name));
-                    }
-                }
+    for (host, ip) in cache.host_names() {
+        if let IpAddr::V4(v4) = ip {
+            let clean = clean_mdns_name(&host);
+            if !clean.is_empty() && !results.contains(&(v4, clean.clone())) {
+                results.push((v4, clean));
             }
-            Err(_) => break,
         }
     }
-
-    let _ = sock.leave_multicast_v4(&mdns_addr, &Ipv4Addr::UNSPECIFIED);
-    results
+    for svc in cache.services() {
+        for ip in &svc.addrs {
+            let IpAddr::V4(v4) = *ip else { continue };
+            if !svc.instance.is_empty() && !results.contains(&(v4, svc.instance.clone())) {
+                results.push((v4, svc.instance.clone()));
+            }
+            if !services.contains(&(v4, svc.service_type.clone())) {
+                services.push((v4, svc.service_type.clone()));
+            }
+        }
+    }
+    (results, services)
 }
 
 /// Try to bind a UDP socket to port 5353 with SO_REUSEADDR.
@@ -655,13 +758,16 @@ extern "system" {
 
 // ─── Method 5: SSDP/UPnP + XML fetch ────────────────────────────────────────
 
-fn resolve_ssdp(timeout_ms: u64) -> Vec<(Ipv4Addr, String)> {
+/// Returns (ip, name) pairs plus device-type hints from the SSDP search
+/// target and the device description ("upnp:MediaRenderer", "model:...").
+fn resolve_ssdp(timeout_ms: u64) -> NamesAndHints {
     let mut results = Vec::new();
+    let mut hints: Vec<(Ipv4Addr, String)> = Vec::new();
     let mut location_urls: Vec<(Ipv4Addr, String)> = Vec::new();
 
     let sock = match UdpSocket::bind("0.0.0.0:0") {
         Ok(s) => s,
-        Err(_) => return results,
+        Err(_) => return (results, hints),
     };
     let _ = sock.set_read_timeout(Some(Duration::from_millis(200)));
 
@@ -691,6 +797,13 @@ fn resolve_ssdp(timeout_ms: u64) -> Vec<(Ipv4Addr, String)> {
 
                 let text = String::from_utf8_lossy(&buf[..len]);
 
+                if let Some(kind) = extract_header(&text, "ST").as_deref().and_then(upnp_device_kind) {
+                    let hint = format!("upnp:{}", kind);
+                    if !hints.contains(&(ip, hint.clone())) {
+                        hints.push((ip, hint));
+                    }
+                }
+
                 // Collect LOCATION URLs for XML fetch (even for seen IPs,
                 // different URLs might give us friendlyName)
                 if !seen_ips.contains(&ip) {
@@ -715,7 +828,7 @@ fn resolve_ssdp(timeout_ms: u64) -> Vec<(Ipv4Addr, String)> {
 
     // Phase 2: Fetch UPnP XML descriptions in parallel for friendlyName
     // Always try XML fetch — friendlyName is more descriptive than SERVER header
-    let xml_found: Mutex<Vec<(Ipv4Addr, String)>> = Mutex::new(Vec::new());
+    let xml_found: Mutex<Vec<(Ipv4Addr, UpnpDescription)>> = Mutex::new(Vec::new());
     let mut fetched_ips = std::collections::HashSet::new();
     thread::scope(|s| {
         for (ip, url) in &location_urls {
@@ -724,22 +837,86 @@ fn resolve_ssdp(timeout_ms: u64) -> Vec<(Ipv4Addr, String)> {
             let xml_found = &xml_found;
             let ip = *ip;
             s.spawn(move || {
-                if let Some(name) = fetch_upnp_friendly_name(url) {
-                    xml_found.lock().unwrap().push((ip, name));
+                if let Some(desc) = fetch_upnp_description(url) {
+                    xml_found.lock().unwrap().push((ip, desc));
                 }
             });
         }
     });
     // XML friendlyName takes priority over SERVER header
     let xml_results = xml_found.into_inner().unwrap();
-    let xml_ips: std::collections::HashSet<Ipv4Addr> = xml_results.iter().map(|(ip, _)| *ip).collect();
+    for (ip, desc) in &xml_results {
+        if let Some(kind) = desc.device_type.as_deref().and_then(upnp_device_kind) {
+            let hint = format!("upnp:{}", kind);
+            if !hints.contains(&(*ip, hint.clone())) {
+                hints.push((*ip, hint));
+            }
+        }
+        if let Some(ref model) = desc.model {
+            hints.push((*ip, format!("model:{}", model)));
+        }
+    }
+    let xml_names: Vec<(Ipv4Addr, String)> = xml_results
+        .into_iter()
+        .filter_map(|(ip, desc)| desc.friendly_name.map(|n| (ip, n)))
+        .collect();
+    let xml_ips: std::collections::HashSet<Ipv4Addr> = xml_names.iter().map(|(ip, _)| *ip).collect();
     results.retain(|(ip, _)| !xml_ips.contains(ip));
-    results.extend(xml_results);
+    results.extend(xml_names);
 
-    results
+    (results, hints)
+}
+
+/// The parts of a UPnP device description that identify the device.
+#[derive(Debug, Default, PartialEq)]
+struct UpnpDescription {
+    friendly_name: Option<String>,
+    /// e.g. "urn:schemas-upnp-org:device:MediaRenderer:1"
+    device_type: Option<String>,
+    /// Manufacturer and model name, e.g. "Sonos, Inc. Sonos One"
+    model: Option<String>,
+}
+
+/// "urn:schemas-upnp-org:device:MediaRenderer:1" → "MediaRenderer".
+/// Service and root-device targets carry no device kind.
+fn upnp_device_kind(urn: &str) -> Option<String> {
+    let mut parts = urn.split(':');
+    parts.position(|p| p.eq_ignore_ascii_case("device"))?;
+    let kind = parts.next()?.trim();
+    if kind.is_empty() { None } else { Some(kind.to_string()) }
+}
+
+/// Byte offset of an ASCII `needle` in `haystack`, ignoring ASCII case. The
+/// offset indexes `haystack` itself; one found in a `to_lowercase()` copy may
+/// not once non-ASCII text changes length.
+fn find_ignore_ascii_case(haystack: &str, needle: &str) -> Option<usize> {
+    haystack
+        .as_bytes()
+        .windows(needle.len())
+        .position(|w| w.eq_ignore_ascii_case(needle.as_bytes()))
+}
+
+fn parse_upnp_description(xml: &str) -> UpnpDescription {
+    let tag = |name: &str| -> Option<String> {
+        let open = format!("<{}>", name);
+        let start = find_ignore_ascii_case(xml, &open)? + open.len();
+        let end = find_ignore_ascii_case(&xml[start..], &format!("</{}>", name))?;
+        let value = xml[start..start + end].trim();
+        if value.is_empty() { None } else { Some(value.to_string()) }
+    };
+    let model = match (tag("manufacturer"), tag("modelname")) {
+        (Some(m), Some(n)) if n.to_lowercase().starts_with(&m.to_lowercase()) => Some(n),
+        (Some(m), Some(n)) => Some(format!("{} {}", m, n)),
+        (m, n) => n.or(m),
+    };
+    UpnpDescription {
+        friendly_name: tag("friendlyname"),
+        device_type: tag("devicetype"),
+        model,
+    }
 }
 
-fn fetch_upnp_friendly_name(url: &str) -> Option<String> {
+fn fetch_upnp_description(url: &str) -> Option<UpnpDescription> {
     let url = url.strip_prefix("http://")
         .or_else(|| url.strip_prefix("HTTP://"))?;
     let (host_port, path) = match url.find('/') {
@@ -778,11 +955,8 @@ fn fetch_upnp_friendly_name(url: &str) -> Option<String> {
     }
 
     let text = String::from_utf8_lossy(&body[..total]);
-    let lower = text.to_lowercase();
-    let start = lower.find("<friendlyname>")? + 14;
-    let end = lower[start..].find("</friendlyname>")?;
-    let name = text[start..start + end].trim().to_string();
-    if name.is_empty() { None } else { Some(name) }
+    let desc = parse_upnp_description(&text);
+    if desc == UpnpDescription::default() { None } else { Some(desc) }
 }
 
 // ─── Method 6: HTTP banner grabbing ──────────────────────────────────────────
@@ -861,9 +1035,8 @@ fn http_banner_grab_port(ip: Ipv4Addr, port: u16, timeout_ms: u64) -> Option<Str
 }
 
 fn extract_html_title(html: &str) -> Option<String> {
-    let lower = html.to_lowercase();
-    let start = lower.find("<title>")? + 7;
-    let end = lower[start..].find("</title>")?;
+    let start = find_ignore_ascii_case(html, "<title>")? + 7;
+    let end = find_ignore_ascii_case(&html[start..], "</title>")?;
     let title = html[start..start + end].trim().to_string();
     if title.is_empty()
         || title.len() > 60
@@ -992,7 +1165,7 @@ fn resolve_mdns_multicast_reverse(ips: &[Ipv4Addr], timeout_ms: u64) -> Vec<(Ipv
 
 // ─── Shared DNS packet helpers ───────────────────────────────────────────────
 
-fn build_dns_query(name: &str, qtype: u16) -> Vec<u8> {
+pub(super) fn build_dns_query(name: &str, qtype: u16) -> Vec<u8> {
     build_dns_query_class(name, qtype, 0x0001)
 }
 
@@ -1007,7 +1180,7 @@ fn build_dns_query_class(name: &str, qtype: u16, qclass: u16) -> Vec<u8> {
     pkt
 }
 
-fn dns_encode_name(buf: &mut Vec<u8>, name: &str) {
+pub(super) fn dns_encode_name(buf: &mut Vec<u8>, name: &str) {
     for label in name.split('.') {
         buf.push(label.len() as u8);
         buf.extend_from_slice(label.as_bytes());
@@ -1124,7 +1297,7 @@ fn clean_mdns_name(name: &str) -> String {
     }
 }
 
-fn skip_dns_name(buf: &[u8], mut pos: usize) -> Option<usize> {
+pub(super) fn skip_dns_name(buf: &[u8], mut pos: usize) -> Option<usize> {
     if pos >= buf.len() { return None; }
     loop {
         if pos >= buf.len() { return None; }
@@ -1140,7 +1313,7 @@ fn skip_dns_name(buf: &[u8], mut pos: usize) -> Option<usize> {
     }
 }
 
-fn read_dns_name(buf: &[u8], mut pos: usize) -> Option<String> {
+pub(super) fn read_dns_name(buf: &[u8], mut pos: usize) -> Option<String> {
     let mut parts = Vec::new();
     let mut jumps = 0;
     loop {
@@ -1163,110 +1336,6 @@ fn read_dns_name(buf: &[u8], mut pos: usize) -> Option<String> {
     if parts.is_empty() { None } else { Some(parts.join(".")) }
 }
 
-// ─── Method 9: SNMP sysName query ────────────────────────────────────────────
-
-/// Send SNMPv1 GET for OID 1.3.6.1.2.1.1.5.0 (sysName) with community "public".
-/// Catches routers, managed switches, network printers, NAS, access points.
-fn resolve_snmp_batch(ips: &[Ipv4Addr], timeout_ms: u64) -> Vec<(Ipv4Addr, String)> {
-    let found: Mutex<Vec<(Ipv4Addr, String)>> = Mutex::new(Vec::new());
-    thread::scope(|s| {
-        for &ip in ips {
-            let found = &found;
-            s.spawn(move || {
-                if let Some(name) = snmp_get_sysname(ip, timeout_ms) {
-                    found.lock().unwrap().push((ip, name));
-                }
-            });
-        }
-    });
-    found.into_inner().unwrap()
-}
-
-fn snmp_get_sysname(ip: Ipv4Addr, timeout_ms: u64) -> Option<String> {
-    // SNMPv1 GET-request for OID 1.3.6.1.2.1.1.5.0 (sysName), community "public"
-    // Total = 2+3+8+2+6+3+3+2+2+10+2 = 43 bytes
-    // Outer SEQUENCE wraps 41 bytes of content
-    const SNMP_GET_SYSNAME: [u8; 43] = [
-        0x30, 0x29,                                     // SEQUENCE, len 41
-        0x02, 0x01, 0x00,                               // INTEGER: version = 0 (SNMPv1)
-        0x04, 0x06, 0x70, 0x75, 0x62, 0x6c, 0x69, 0x63, // OCTET STRING: "public"
-        0xa0, 0x1c,                                     // GET-request PDU, len 28
-        0x02, 0x04, 0x00, 0x00, 0x00, 0x01,             // INTEGER: request-id = 1
-        0x02, 0x01, 0x00,                               // INTEGER: error-status = 0
-        0x02, 0x01, 0x00,                               // INTEGER: error-index = 0
-        0x30, 0x0e,                                     // SEQUENCE (varbind list), len 14
-        0x30, 0x0c,                                     // SEQUENCE (single varbind), len 12
-        0x06, 0x08, 0x2b, 0x06, 0x01, 0x02, 0x01, 0x01, 0x05, 0x00, // OID: 1.3.6.1.2.1.1.5.0
-        0x05, 0x00,                                     // NULL (value)
-    ];
-
-    let sock = UdpSocket::bind("0.0.0.0:0").ok()?;
-    sock.set_read_timeout(Some(Duration::from_millis(timeout_ms))).ok()?;
-    sock.send_to(&SNMP_GET_SYSNAME, SocketAddr::from((ip, 161u16))).ok()?;
-
-    let mut buf = [0u8; 1024];
-    let (len, _) = sock.recv_from(&mut buf).ok()?;
-    parse_snmp_sysname_response(&buf[..len])
-}
-
-/// Parse SNMPv1 GET-response to extract sysName value (OCTET STRING).
-fn parse_snmp_sysname_response(buf: &[u8]) -> Option<String> {
-    if buf.len() < 20 { return None; }
-
-    // Walk BER-TLV to find the OCTET STRING (tag 0x04) value in the varbind
-    // The response structure is:
-    // SEQUENCE { version, community, GET-RESPONSE { reqid, err, erridx,
-    //   SEQUENCE { SEQUENCE { OID, VALUE } } } }
-    // We need the VALUE which is an OCTET STRING (tag 0x04)
-
-    // Find the last OCTET STRING (0x04) in the packet — that's the sysName value
-    let mut pos = 0;
-    let mut last_octet_string: Option<String> = None;
-
-    while pos < buf.len() - 2 {
-        if buf[pos] == 0x04 { // OCTET STRING tag
-            let (value_len, header_len) = parse_ber_length(&buf[pos + 1..])?;
-            let start = pos + 1 + header_len;
-            let end = start + value_len;
-            if end <= buf.len() {
-                if let Ok(s) = std::str::from_utf8(&buf[start..end]) {
-                    let name = s.trim().to_string();
-                    // Skip the community string "public"
-                    if !name.is_empty() && name != "public" {
-                        last_octet_string = Some(name);
-                    }
-                }
-            }
-        }
-        pos += 1;
-    }
-
-    last_octet_string.filter(|n| {
-        !n.is_empty()
-            && n.len() <= 255
-            && n.chars().all(|c| c.is_ascii_graphic() || c == ' ')
-    })
-}
-
-/// Parse BER length encoding. Returns (value_length, header_bytes_consumed).
-fn parse_ber_length(buf: &[u8]) -> Option<(usize, usize)> {
-    if buf.is_empty() { return None; }
-    if buf[0] & 0x80 == 0 {
-        // Short form: single byte
-        Some((buf[0] as usize, 1))
-    } else {
-        let num_bytes = (buf[0] & 0x7F) as usize;
-        if num_bytes == 0 || num_bytes > 4 || buf.len() < 1 + num_bytes {
-            return None;
-        }
-        let mut len = 0usize;
-        for i in 0..num_bytes {
-            len = (len << 8) | buf[1 + i] as usize;
-        }
-        Some((len, 1 + num_bytes))
-    }
-}
-
 // ─── Method 10: Telnet banner grabbing ───────────────────────────────────────
 
 /// Connect to port 23 and read the initial banner/login prompt.
@@ -1418,7 +1487,7 @@ fn resolve_dns_via_gateway(ips: &[Ipv4Addr], gateway: Ipv4Addr, timeout_ms: u64)
 }
 
 /// Build a DNS query packet with a specific transaction ID.
-fn build_dns_query_with_id(name: &str, qtype: u16, tid: u16) -> Vec<u8> {
+pub(super) fn build_dns_query_with_id(name: &str, qtype: u16, tid: u16) -> Vec<u8> {
     let mut pkt = Vec::with_capacity(64);
     // Header: ID, flags=0x0100 (RD=1, standard recursive query), QDCOUNT=1
     pkt.push((tid >> 8) as u8);
@@ -1589,8 +1658,8 @@ pub fn dhcp_client_ip(payload: &[u8]) -> Option<Ipv4Addr> {
 
 // ─── Port scanning ───────────────────────────────────────────────────────────
 
-/// Common ports to scan on LAN devices.
-const SCAN_PORTS: &[u16] = &[
+/// Ports scanned on LAN devices unless `devices.json` sets `scan_ports`.
+pub const SCAN_PORTS: &[u16] = &[
     21,   // FTP
     22,   // SSH
     23,   // Telnet
@@ -1613,8 +1682,8 @@ const SCAN_PORTS: &[u16] = &[
     9090, // Web admin
 ];
 
-/// Scan common ports on all IPs in parallel. Returns (ip, sorted open ports).
-fn scan_ports_batch(ips: &[Ipv4Addr], timeout_ms: u64) -> Vec<(Ipv4Addr, Vec<u16>)> {
+/// Scan `ports` on all IPs in parallel. Returns (ip, sorted open ports).
+fn scan_ports_batch(ips: &[Ipv4Addr], ports: &[u16], timeout_ms: u64) -> Vec<(Ipv4Addr, Vec<u16>)> {
     let results: Mutex<HashMap<Ipv4Addr, Vec<u16>>> = Mutex::new(HashMap::new());
     let timeout = Duration::from_millis(timeout_ms);
 
@@ -1625,7 +1694,7 @@ fn scan_ports_batch(ips: &[Ipv4Addr], timeout_ms: u64) -> Vec<(Ipv4Addr, Vec<u16
                 // Scan all ports for this IP in parallel (scoped threads)
                 let port_open: Mutex<Vec<u16>> = Mutex::new(Vec::new());
                 thread::scope(|s2| {
-                    for &port in SCAN_PORTS {
+                    for &port in ports {
                         let port_open = &port_open;
                         s2.spawn(move || {
                             let addr = SocketAddr::new(IpAddr::V4(ip), port);
@@ -1686,3 +1755,21 @@ pub fn format_ports(ports: &[u16]) -> String {
         .collect::<Vec<_>>()
         .join(" ")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_upnp_description_with_non_ascii_text() {
+        // 'İ' lowercases to two chars, which shifted offsets taken from a lowercased copy
+        let xml = "<root><device><deviceType>urn:schemas-upnp-org:device:MediaRenderer:1</deviceType>\
+                   <friendlyName>İİİ Salon TV</friendlyName><Manufacturer>Vestel</Manufacturer>\
+                   <modelName>Vestel 4K</modelName></device></root>";
+        let desc = parse_upnp_description(xml);
+        assert_eq!(desc.friendly_name.as_deref(), Some("İİİ Salon TV"));
+        assert_eq!(desc.device_type.as_deref(), Some("urn:schemas-upnp-org:device:MediaRenderer:1"));
+        assert_eq!(desc.model.as_deref(), Some("Vestel 4K"));
+        assert_eq!(extract_html_title("<TITLE>Ünïcode Router</TITLE>").as_deref(), Some("Ünïcode Router"));
+    }
+}
@@ -0,0 +1,396 @@
+//! Persistent LAN device inventory.
+//!
+//! Every device a scan has seen with a MAC address is recorded in
+//! `%APPDATA%\psnet\device_inventory.json`, keyed by MAC: when it was first
+//! and last seen, its custom name, vendor, every IP and hostname it has used,
+//! the discovery results from each resolution method, its open ports, and the
+//! networks it was seen on. The scanner merges these records with live
+//! results, so devices from earlier sessions show up (offline) on the Devices
+//! tab of the network they were seen on.
+
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::types::{DeviceService, DhcpFingerprint, LanDevice};
+
+use super::presence::PresenceSpan;
+
+/// Everything known about one device across sessions.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct InventoryRecord {
+    pub mac: String,
+    #[serde(with = "rfc3339")]
+    pub first_seen: DateTime<Local>,
+    #[serde(with = "rfc3339")]
+    pub last_seen: DateTime<Local>,
+    #[serde(default)]
+    pub custom_name: Option<String>,
+    #[serde(default)]
+    pub vendor: Option<String>,
+    /// Addresses the device has used, most recent last.
+    #[serde(default)]
+    pub ips: Vec<IpAddr>,
+    #[serde(default)]
+    pub hostnames: Vec<String>,
+    /// Discovery results as "method:value" (e.g. "NBNS:DESKTOP-4F2").
+    #[serde(default)]
+    pub discovery: Vec<String>,
+    /// Open ports as shown on the Devices tab (e.g. "22/ssh").
+    #[serde(default)]
+    pub open_ports: Vec<String>,
+    /// Products last identified behind those ports.
+    #[serde(default)]
+    pub services: Vec<DeviceService>,
+    /// Last DHCP fingerprint; requests are rare, so it is worth keeping.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dhcp: Option<DhcpFingerprint>,
+    /// Subnets (CIDR) the device was seen on.
+    #[serde(default)]
+    pub networks: Vec<String>,
+    /// Device-type hints (mDNS services, UPnP device types, model names).
+    #[serde(default)]
+    pub hints: Vec<String>,
+    /// When the device was online over the last week.
+    #[serde(default)]
+    pub presence: Vec<PresenceSpan>,
+    /// SecureOn password for Wake-on-LAN, written like a MAC.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wol_password: Option<String>,
+}
+
+impl InventoryRecord {
+    fn new(mac: &str, seen: DateTime<Local>) -> Self {
+        Self {
+            mac: mac.to_string(),
+            first_seen: seen,
+            last_seen: seen,
+            custom_name: None,
+            vendor: None,
+            ips: Vec::new(),
+            hostnames: Vec::new(),
+            discovery: Vec::new(),
+            open_ports: Vec::new(),
+            services: Vec::new(),
+            dhcp: None,
+            networks: Vec::new(),
+            hints: Vec::new(),
+            presence: Vec::new(),
+            wol_password: None,
+        }
+    }
+
+    /// The record as an offline device for the Devices tab.
+    fn to_device(&self) -> LanDevice {
+        let ip = self.ips.last().copied().unwrap_or(IpAddr::V4(std::net::Ipv4Addr::UNSPECIFIED));
+        LanDevice {
+            hostname: if self.hostnames.is_empty() { None } else { Some(self.hostnames.join(", ")) },
+            vendor: self.vendor.clone(),
+            first_seen: self.first_seen,
+            last_seen: self.last_seen,
+            is_online: false,
+            custom_name: self.custom_name.clone(),
+            discovery_info: self.discovery.join("  "),
+            open_ports: self.open_ports.join(" "),
+            hints: self.hints.clone(),
+            services: self.services.clone(),
+            dhcp: self.dhcp.clone(),
+            ..LanDevice::new(ip, self.mac.clone())
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct InventoryFile {
+    devices: Vec<InventoryRecord>,
+}
+
+pub struct DeviceInventory {
+    records: HashMap<String, InventoryRecord>,
+    /// Set when records changed and have not been written yet.
+    dirty: bool,
+    path: PathBuf,
+}
+
+impl DeviceInventory {
+    /// Load the saved inventory. Labels from the older `device_labels.json`
+    /// are carried over the first time.
+    pub fn load() -> Self {
+        let path = Self::default_path("device_inventory.json");
+        let file: Option<InventoryFile> = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok());
+        let mut inventory = Self {
+            records: HashMap::new(),
+            dirty: false,
+            path,
+        };
+        match file {
+            Some(file) => {
+                for r in file.devices {
+                    inventory.records.insert(r.mac.clone(), r);
+                }
+            }
+            None => inventory.import_labels(&Self::default_path("device_labels.json")),
+        }
+        inventory
+    }
+
+    fn default_path(file_name: &str) -> PathBuf {
+        if let Some(data_dir) = dirs::data_dir() {
+            let dir = data_dir.join("psnet");
+            let _ = std::fs::create_dir_all(&dir);
+            dir.join(file_name)
+        } else {
+            PathBuf::from(format!("psnet_{}", file_name))
+        }
+    }
+
+    fn import_labels(&mut self, path: &Path) {
+        let labels: HashMap<String, String> = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default();
+        let now = Local::now();
+        for (mac, label) in labels {
+            self.records
+                .entry(mac.clone())
+                .or_insert_with(|| InventoryRecord::new(&mac, now))
+                .custom_name = Some(label);
+            self.dirty = true;
+        }
+    }
+
+    pub fn get(&self, mac: &str) -> Option<&InventoryRecord> {
+        self.records.get(mac)
+    }
+
+    /// Restore what earlier sessions knew about a freshly discovered device:
+    /// its real first-seen time, custom name, hostnames, type hints and the
+    /// services and DHCP fingerprint seen last time.
+    pub fn apply(&self, device: &mut LanDevice) {
+        let Some(r) = self.records.get(&device.mac) else {
+            return;
+        };
+        device.first_seen = r.first_seen.min(device.first_seen);
+        device.custom_name = r.custom_name.clone();
+        if device.hostname.is_none() && !r.hostnames.is_empty() {
+            device.hostname = Some(r.hostnames.join(", "));
+        }
+        merge_parts(&mut device.hints, r.hints.iter().map(String::as_str));
+        if device.services.is_empty() {
+            device.services = r.services.clone();
+        }
+        if device.dhcp.is_none() {
+            device.dhcp = r.dhcp.clone();
+        }
+    }
+
+    /// Devices recorded on `network` that are not in `present`, as offline
+    /// entries, most recently seen first.
+    pub fn history(&self, network: &str, present: &HashSet<&str>) -> Vec<LanDevice> {
+        let mut records: Vec<&InventoryRecord> = self
+            .records
+            .values()
+            .filter(|r| r.networks.iter().any(|n| n == network) && !present.contains(r.mac.as_str()))
+            .collect();
+        records.sort_by_key(|r| std::cmp::Reverse(r.last_seen));
+        records.into_iter().map(InventoryRecord::to_device).collect()
+    }
+
+    /// Merge a live device into its record. Devices without a MAC are not kept.
+    pub fn record(&mut self, device: &LanDevice, network: &str) {
+        if device.mac.is_empty() {
+            return;
+        }
+        let r = self
+            .records
+            .entry(device.mac.clone())
+            .or_insert_with(|| InventoryRecord::new(&device.mac, device.first_seen));
+        r.first_seen = r.first_seen.min(device.first_seen);
+        r.last_seen = r.last_seen.max(device.last_seen);
+        if device.vendor.is_some() {
+            r.vendor = device.vendor.clone();
+        }
+        if r.ips.last() != Some(&device.ip) {
+            r.ips.retain(|ip| *ip != device.ip);
+            r.ips.push(device.ip);
+        }
+        if let Some(ref names) = device.hostname {
+            merge_parts(&mut r.hostnames, names.split(", "));
+        }
+        merge_parts(&mut r.discovery, device.discovery_info.split("  "));
+        merge_parts(&mut r.open_ports, device.open_ports.split(' '));
+        merge_parts(&mut r.networks, std::iter::once(network));
+        merge_parts(&mut r.hints, device.hints.iter().map(String::as_str));
+        super::device_services::merge(&mut r.services, &device.services);
+        if device.dhcp.is_some() {
+            r.dhcp = device.dhcp.clone();
+        }
+        self.dirty = true;
+    }
+
+    /// Set or clear (empty label) the custom name for a MAC.
+    pub fn set_name(&mut self, mac: &str, label: &str) {
+        let name = if label.is_empty() { None } else { Some(label.to_string()) };
+        if let Some(r) = self.records.get_mut(mac) {
+            r.custom_name = name;
+        } else if let Some(name) = name {
+            let mut r = InventoryRecord::new(mac, Local::now());
+            r.custom_name = Some(name);
+            self.records.insert(mac.to_string(), r);
+        }
+        self.dirty = true;
+    }
+
+    /// Extend a device's online history with a sighting from a finished scan.
+    pub fn mark_present(&mut self, mac: &str, at: DateTime<Local>, gap: chrono::Duration) {
+        if let Some(r) = self.records.get_mut(mac) {
+            super::presence::mark_present(&mut r.presence, at, gap);
+            self.dirty = true;
+        }
+    }
+
+    /// Set or clear (empty) the SecureOn password for a MAC.
+    pub fn set_wol_password(&mut self, mac: &str, password: &str) {
+        let password = if password.is_empty() { None } else { Some(password.to_string()) };
+        let r = self
+            .records
+            .entry(mac.to_string())
+            .or_insert_with(|| InventoryRecord::new(mac, Local::now()));
+        r.wol_password = password;
+        self.dirty = true;
+    }
+
+    /// Remember a device's DHCP fingerprint, even before a scan has found it.
+    pub fn set_dhcp(&mut self, mac: &str, fingerprint: &DhcpFingerprint) {
+        let r = self
+            .records
+            .entry(mac.to_string())
+            .or_insert_with(|| InventoryRecord::new(mac, Local::now()));
+        if r.dhcp.as_ref() != Some(fingerprint) {
+            r.dhcp = Some(fingerprint.clone());
+            self.dirty = true;
+        }
+    }
+
+    /// Write the inventory if it changed since the last save.
+    pub fn save_if_dirty(&mut self) {
+        if !self.dirty {
+            return;
+        }
+        let mut devices: Vec<InventoryRecord> = self.records.values().cloned().collect();
+        devices.sort_by(|a, b| a.mac.cmp(&b.mac));
+        if let Ok(json) = serde_json::to_string_pretty(&InventoryFile { devices }) {
+            let _ = std::fs::write(&self.path, json);
+        }
+        self.dirty = false;
+    }
+}
+
+/// Append the non-empty parts not already present (case-insensitive).
+fn merge_parts<'a>(list: &mut Vec<String>, parts: impl Iterator<Item = &'a str>) {
+    for part in parts {
+        let part = part.trim();
+        if !part.is_empty() && !list.iter().any(|p| p.eq_ignore_ascii_case(part)) {
+            list.push(part.to_string());
+        }
+    }
+}
+
+/// Timestamps are stored as RFC 3339 strings with the local offset.
+pub(super) mod rfc3339 {
+    use chrono::{DateTime, Local};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(dt: &DateTime<Local>, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&dt.to_rfc3339())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<DateTime<Local>, D::Error> {
+        let s = String::deserialize(d)?;
+        DateTime::parse_from_rfc3339(&s)
+            .map(|dt| dt.with_timezone(&Local))
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use std::net::Ipv4Addr;
+
+    fn device(mac: &str, ip: [u8; 4], hostname: Option<&str>, seen: DateTime<Local>) -> LanDevice {
+        LanDevice {
+            hostname: hostname.map(String::from),
+            vendor: Some("Raspberry Pi Trading".into()),
+            first_seen: seen,
+            last_seen: seen,
+            discovery_info: "ARP:aa".into(),
+            open_ports: "22/ssh".into(),
+            ..LanDevice::new(IpAddr::V4(Ipv4Addr::from(ip)), mac.into())
+        }
+    }
+
+    fn empty() -> DeviceInventory {
+        DeviceInventory { records: HashMap::new(), dirty: false, path: PathBuf::new() }
+    }
+
+    #[test]
+    fn test_record_merges_sightings() {
+        let mut inv = empty();
+        let day1 = Local::now() - Duration::days(1);
+        let now = Local::now();
+        inv.record(&device("b8:27:eb:01:02:03", [192, 168, 1, 20], Some("pi"), day1), "192.168.1.0/24");
+        let mut later = device("b8:27:eb:01:02:03", [192, 168, 1, 31], Some("pi, pi.local"), now);
+        later.open_ports = "22/ssh 80/http".into();
+        inv.record(&later, "192.168.1.0/24");
+        inv.record(&device("", [192, 168, 1, 99], None, now), "192.168.1.0/24");
+
+        assert_eq!(inv.records.len(), 1);
+        let r = inv.get("b8:27:eb:01:02:03").unwrap();
+        assert_eq!(r.first_seen, day1);
+        assert_eq!(r.last_seen, now);
+        assert_eq!(r.ips.last(), Some(&IpAddr::V4(Ipv4Addr::new(192, 168, 1, 31))));
+        assert_eq!(r.hostnames, vec!["pi", "pi.local"]);
+        assert_eq!(r.open_ports, vec!["22/ssh", "80/http"]);
+        assert_eq!(r.networks, vec!["192.168.1.0/24"]);
+    }
+
+    #[test]
+    fn test_history_and_apply() {
+        let mut inv = empty();
+        let day1 = Local::now() - Duration::days(1);
+        inv.record(&device("aa:aa:aa:00:00:01", [10, 0, 0, 5], Some("nas"), day1), "10.0.0.0/24");
+        inv.record(&device("aa:aa:aa:00:00:02", [10, 0, 0, 6], None, day1), "10.0.0.0/24");
+        inv.record(&device("aa:aa:aa:00:00:03", [192, 168, 0, 2], None, day1), "192.168.0.0/24");
+        inv.set_name("aa:aa:aa:00:00:01", "Backup NAS");
+
+        let present: HashSet<&str> = ["aa:aa:aa:00:00:02"].into_iter().collect();
+        let history = inv.history("10.0.0.0/24", &present);
+        assert_eq!(history.len(), 1);
+        assert!(!history[0].is_online);
+        assert_eq!(history[0].custom_name.as_deref(), Some("Backup NAS"));
+        assert_eq!(history[0].hostname.as_deref(), Some("nas"));
+
+        let mut live = device("aa:aa:aa:00:00:01", [10, 0, 0, 7], None, Local::now());
+        inv.apply(&mut live);
+        assert_eq!(live.first_seen, day1);
+        assert_eq!(live.custom_name.as_deref(), Some("Backup NAS"));
+        assert_eq!(live.hostname.as_deref(), Some("nas"));
+    }
+
+    #[test]
+    fn test_file_round_trip() {
+        let mut inv = empty();
+        inv.record(&device("b8:27:eb:01:02:03", [192, 168, 1, 20], Some("pi"), Local::now()), "192.168.1.0/24");
+        let file = InventoryFile { devices: inv.records.values().cloned().collect() };
+        let json = serde_json::to_string(&file).unwrap();
+        let back: InventoryFile = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.devices, file.devices);
+    }
+}
@@ -6,13 +6,28 @@ pub mod speed;
 pub mod alerts;
 pub mod bandwidth;
 pub mod firewall;
+pub mod upnp;
 pub mod scanner;
+pub mod inventory;
+pub mod allowlist;
+pub mod device_type;
+pub mod device_services;
+pub mod dhcp_fingerprint;
+pub mod dhcp_servers;
+pub mod os_fingerprint;
+pub mod wol;
+pub mod presence;
+pub mod range_scan;
+pub mod traceroute;
 pub mod threats;
+pub mod topology;
 pub mod system_monitor;
 pub mod usage;
 pub mod geoip;
 pub mod oui;
 pub mod hostnames;
+pub mod snmp;
+pub mod dnssd;
 pub mod networks;
 pub mod protocols;
 pub mod servers;
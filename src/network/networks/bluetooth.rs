@@ -9,7 +9,6 @@
 use std::net::{IpAddr, Ipv4Addr};
 use std::process::Command;
 
-use chrono::Local;
 
 use crate::types::{LanDevice, NetworkCategory, RemoteNetwork};
 
@@ -32,8 +31,6 @@ fn quiet_powershell() -> Command {
 
 /// Discover Bluetooth PAN networks and paired devices.
 pub fn discover() -> Vec<RemoteNetwork> {
-    let now = Local::now().time();
-
     // Discover BT network adapters
     let adapters = discover_bt_adapters();
 
@@ -49,22 +46,11 @@ pub fn discover() -> Vec<RemoteNetwork> {
     // Add paired BT devices as LanDevices
     for pd in &paired {
         devices.push(LanDevice {
-            ip: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
-            mac: pd.mac.clone(),
             hostname: Some(pd.name.clone()),
             vendor: Some("Bluetooth Device".to_string()),
-            first_seen: now,
-            last_seen: now,
             is_online: pd.connected,
-            custom_name: None,
             discovery_info: format!("BT:{} Class:{}", pd.status, pd.device_class),
-            open_ports: String::new(),
-            bytes_sent: 0,
-            bytes_received: 0,
-            tick_sent: 0,
-            tick_received: 0,
-            speed_sent: 0.0,
-            speed_received: 0.0,
+            ..LanDevice::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), pd.mac.clone())
         });
     }
 
@@ -10,7 +10,6 @@
 use std::net::{IpAddr, Ipv4Addr};
 use std::process::Command;
 
-use chrono::Local;
 
 use crate::types::{LanDevice, NetworkCategory, RemoteNetwork};
 
@@ -58,8 +57,6 @@ pub fn discover() -> Vec<RemoteNetwork> {
 // ─── Windows Mobile Hotspot ────────────────────────────────────────────────
 
 fn discover_mobile_hotspot() -> Option<RemoteNetwork> {
-    let now = Local::now().time();
-
     // Check hosted network status
     let output = quiet_command("netsh")
         .args(["wlan", "show", "hostednetwork"])
@@ -103,7 +100,7 @@ fn discover_mobile_hotspot() -> Option<RemoteNetwork> {
         .unwrap_or(Ipv4Addr::new(192, 168, 137, 1));
 
     // Get connected clients from ARP table
-    let clients = discover_hotspot_clients(hotspot_ip, now);
+    let clients = discover_hotspot_clients(hotspot_ip);
 
     let client_count = clients.len();
 
@@ -117,22 +114,10 @@ fn discover_mobile_hotspot() -> Option<RemoteNetwork> {
         gateway: None,
         devices: if client_count > 0 { clients } else {
             vec![LanDevice {
-                ip: IpAddr::V4(hotspot_ip),
-                mac: String::new(),
                 hostname: Some("Hotspot Gateway".to_string()),
                 vendor: Some("Windows Mobile Hotspot".to_string()),
-                first_seen: now,
-                last_seen: now,
-                is_online: true,
-                custom_name: None,
                 discovery_info: format!("Hotspot SSID:{} active", ssid),
-                open_ports: String::new(),
-                bytes_sent: 0,
-                bytes_received: 0,
-                tick_sent: 0,
-                tick_received: 0,
-                speed_sent: 0.0,
-                speed_received: 0.0,
+                ..LanDevice::new(IpAddr::V4(hotspot_ip), String::new())
             }]
         },
     })
@@ -148,7 +133,7 @@ fn discover_hotspot_adapter_ip() -> Option<Ipv4Addr> {
     text.trim().parse::<Ipv4Addr>().ok()
 }
 
-fn discover_hotspot_clients(hotspot_ip: Ipv4Addr, now: chrono::NaiveTime) -> Vec<LanDevice> {
+fn discover_hotspot_clients(hotspot_ip: Ipv4Addr) -> Vec<LanDevice> {
     let output = quiet_command("arp")
         .arg("-a")
         .output();
@@ -202,22 +187,9 @@ fn discover_hotspot_clients(hotspot_ip: Ipv4Addr, now: chrono::NaiveTime) -> Vec
         };
 
         devices.push(LanDevice {
-            ip: IpAddr::V4(ip),
-            mac,
-            hostname: None,
             vendor,
-            first_seen: now,
-            last_seen: now,
-            is_online: true,
-            custom_name: None,
             discovery_info: "Hotspot client".to_string(),
-            open_ports: String::new(),
-            bytes_sent: 0,
-            bytes_received: 0,
-            tick_sent: 0,
-            tick_received: 0,
-            speed_sent: 0.0,
-            speed_received: 0.0,
+            ..LanDevice::new(IpAddr::V4(ip), mac)
         });
     }
 
@@ -227,8 +199,6 @@ fn discover_hotspot_clients(hotspot_ip: Ipv4Addr, now: chrono::NaiveTime) -> Vec
 // ─── USB Tethering ──────────────────────────────────────────────────────────
 
 fn discover_usb_tethering() -> Vec<RemoteNetwork> {
-    let now = Local::now().time();
-
     // Look for RNDIS or CDC Ethernet adapters (USB tethering)
     let output = quiet_powershell()
         .arg("Get-NetAdapter | Where-Object { $_.InterfaceDescription -like '*RNDIS*' -or $_.InterfaceDescription -like '*CDC Ethernet*' -or $_.InterfaceDescription -like '*USB Ethernet*' -or $_.InterfaceDescription -like '*Android*' -or $_.InterfaceDescription -like '*iPhone*' } | Select-Object Name,InterfaceDescription,Status,MacAddress | ConvertTo-Json -Compress")
@@ -276,22 +246,10 @@ fn discover_usb_tethering() -> Vec<RemoteNetwork> {
             subnet_cidr: format!("{}/24", ip),
             gateway: None,
             devices: vec![LanDevice {
-                ip: IpAddr::V4(ip),
-                mac: String::new(),
                 hostname: Some(desc.to_string()),
                 vendor: Some("USB Tethering".to_string()),
-                first_seen: now,
-                last_seen: now,
-                is_online: true,
-                custom_name: None,
                 discovery_info: format!("USB tethering via {}", desc),
-                open_ports: String::new(),
-                bytes_sent: 0,
-                bytes_received: 0,
-                tick_sent: 0,
-                tick_received: 0,
-                speed_sent: 0.0,
-                speed_received: 0.0,
+                ..LanDevice::new(IpAddr::V4(ip), String::new())
             }],
         });
     }
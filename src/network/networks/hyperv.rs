@@ -14,7 +14,6 @@ use std::process::Command;
 use std::sync::Mutex;
 use std::thread;
 
-use chrono::Local;
 
 use crate::types::{LanDevice, NetworkCategory, RemoteNetwork};
 
@@ -173,7 +172,6 @@ pub fn discover() -> Vec<RemoteNetwork> {
         }
     }
 
-    let now = Local::now().time();
     let mut networks = Vec::new();
 
     for (switch_name, switch_adapters) in &by_switch {
@@ -210,22 +208,11 @@ pub fn discover() -> Vec<RemoteNetwork> {
             );
 
             devices.push(LanDevice {
-                ip: IpAddr::V4(ip),
-                mac,
                 hostname: Some(adapter.vm_name.clone()),
                 vendor: Some("Hyper-V VM".to_string()),
-                first_seen: now,
-                last_seen: now,
                 is_online,
-                custom_name: None,
                 discovery_info,
-                open_ports: String::new(),
-                bytes_sent: 0,
-                bytes_received: 0,
-                tick_sent: 0,
-                tick_received: 0,
-                speed_sent: 0.0,
-                speed_received: 0.0,
+                ..LanDevice::new(IpAddr::V4(ip), mac)
             });
         }
 
@@ -235,22 +222,11 @@ pub fn discover() -> Vec<RemoteNetwork> {
                 if !vms_with_adapters.contains(&vm.name) && !seen_vms.contains(&vm.name) {
                     let is_online = vm.state.eq_ignore_ascii_case("Running");
                     devices.push(LanDevice {
-                        ip: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
-                        mac: String::new(),
                         hostname: Some(vm.name.clone()),
                         vendor: Some("Hyper-V VM".to_string()),
-                        first_seen: now,
-                        last_seen: now,
                         is_online,
-                        custom_name: None,
                         discovery_info: format!("State:{}  Switch:None", vm.state),
-                        open_ports: String::new(),
-                        bytes_sent: 0,
-                        bytes_received: 0,
-                        tick_sent: 0,
-                        tick_received: 0,
-                        speed_sent: 0.0,
-                        speed_received: 0.0,
+                        ..LanDevice::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), String::new())
                     });
                 }
             }
@@ -12,7 +12,6 @@ use std::process::Command;
 use std::sync::Mutex;
 use std::thread;
 
-use chrono::Local;
 
 use crate::types::{LanDevice, NetworkCategory, RemoteNetwork};
 
@@ -82,7 +81,6 @@ fn discover_tailscale() -> Vec<RemoteNetwork> {
         Err(_) => return Vec::new(),
     };
 
-    let now = Local::now().time();
     let mut devices = Vec::new();
 
     // Get self IP for local_ip
@@ -132,25 +130,16 @@ fn discover_tailscale() -> Vec<RemoteNetwork> {
             let tx_bytes = peer.get("TxBytes").and_then(|v| v.as_u64()).unwrap_or(0);
 
             devices.push(LanDevice {
-                ip: IpAddr::V4(ip),
-                mac: String::new(),
                 hostname: if hostname.is_empty() { None } else { Some(hostname) },
                 vendor: Some(format!("Tailscale ({})", os)),
-                first_seen: now,
-                last_seen: now,
                 is_online: online,
-                custom_name: None,
                 discovery_info: format!(
                     "Tailscale  OS:{}  Relay:{}  RX:{}  TX:{}",
                     os, relay, rx_bytes, tx_bytes
                 ),
-                open_ports: String::new(),
                 bytes_sent: tx_bytes,
                 bytes_received: rx_bytes,
-                tick_sent: 0,
-                tick_received: 0,
-                speed_sent: 0.0,
-                speed_received: 0.0,
+                ..LanDevice::new(IpAddr::V4(ip), String::new())
             });
         }
     }
@@ -184,7 +173,6 @@ fn discover_zerotier() -> Vec<RemoteNetwork> {
         _ => return Vec::new(),
     };
 
-    let now = Local::now().time();
     let net_text = String::from_utf8_lossy(&net_output.stdout);
     let mut networks = Vec::new();
 
@@ -212,7 +200,7 @@ fn discover_zerotier() -> Vec<RemoteNetwork> {
             .unwrap_or(Ipv4Addr::new(10, 147, 0, 1));
 
         // Get peers for this network
-        let peers = discover_zt_peers(nwid, now);
+        let peers = discover_zt_peers(nwid);
 
         networks.push(RemoteNetwork {
             name: format!("ZeroTier: {}", net_name),
@@ -229,7 +217,7 @@ fn discover_zerotier() -> Vec<RemoteNetwork> {
     networks
 }
 
-fn discover_zt_peers(_nwid: &str, now: chrono::NaiveTime) -> Vec<LanDevice> {
+fn discover_zt_peers(_nwid: &str) -> Vec<LanDevice> {
     let output = quiet_command("zerotier-cli")
         .arg("listpeers")
         .output();
@@ -263,22 +251,11 @@ fn discover_zt_peers(_nwid: &str, now: chrono::NaiveTime) -> Vec<LanDevice> {
         let latency = parts.get(6).unwrap_or(&"-");
 
         devices.push(LanDevice {
-            ip: IpAddr::V4(ip),
-            mac: String::new(),
             hostname: Some(format!("ZT-{}", zt_addr)),
             vendor: Some(format!("ZeroTier {} ({})", version, role)),
-            first_seen: now,
-            last_seen: now,
             is_online: path != "-",
-            custom_name: None,
             discovery_info: format!("ZeroTier  Role:{}  Path:{}  Latency:{}", role, path, latency),
-            open_ports: String::new(),
-            bytes_sent: 0,
-            bytes_received: 0,
-            tick_sent: 0,
-            tick_received: 0,
-            speed_sent: 0.0,
-            speed_received: 0.0,
+            ..LanDevice::new(IpAddr::V4(ip), String::new())
         });
     }
 
@@ -324,7 +301,6 @@ fn discover_nebula() -> Vec<RemoteNetwork> {
         return Vec::new();
     }
 
-    let now = Local::now().time();
 
     // Nebula doesn't have a CLI to list peers, but we can report it exists
     vec![RemoteNetwork {
@@ -336,22 +312,10 @@ fn discover_nebula() -> Vec<RemoteNetwork> {
         subnet_cidr: "10.0.0.0/8".to_string(),
         gateway: None,
         devices: vec![LanDevice {
-            ip: IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)),
-            mac: String::new(),
             hostname: Some("Nebula Lighthouse".to_string()),
             vendor: Some("Nebula Mesh VPN".to_string()),
-            first_seen: now,
-            last_seen: now,
-            is_online: true,
-            custom_name: None,
             discovery_info: "Nebula mesh overlay detected".to_string(),
-            open_ports: String::new(),
-            bytes_sent: 0,
-            bytes_received: 0,
-            tick_sent: 0,
-            tick_received: 0,
-            speed_sent: 0.0,
-            speed_received: 0.0,
+            ..LanDevice::new(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)), String::new())
         }],
     }]
 }
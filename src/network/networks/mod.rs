@@ -39,7 +39,6 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
 
-use chrono::{Local, NaiveTime};
 
 use crate::types::{LanDevice, NetworkCategory, RemoteNetwork};
 
@@ -153,11 +152,14 @@ impl NetworksScanner {
         }
 
         for incoming in batch {
-            if let Some(existing) = self.networks.iter_mut().find(|n| n.name == incoming.name) {
+            let net = if let Some(existing) = self.networks.iter_mut().find(|n| n.name == incoming.name) {
                 merge_into_network(existing, incoming);
+                existing
             } else {
                 self.networks.push(incoming);
-            }
+                self.networks.last_mut().unwrap()
+            };
+            classify_devices(net);
         }
 
         true
@@ -247,11 +249,23 @@ fn spawn_adapter_probes(
                 let hosts = probes::deep_scan_subnet(net.local_ip, net.subnet_mask, net.gateway);
 
                 if !hosts.is_empty() {
-                    let now = Local::now().time();
                     let ips: Vec<Ipv4Addr> = hosts.iter().map(|h| h.ip).collect();
-                    let resolved =
-                        crate::network::hostnames::resolve_all(&ips, net.gateway, &std::collections::HashMap::new());
-                    let devices = hosts_to_lan_devices(&hosts, &resolved, now);
+                    let schedule = crate::network::presence::ScheduleConfig::new().schedule;
+                    let polled = crate::network::snmp::poll_batch(
+                        &ips,
+                        &schedule.snmp_communities,
+                        std::time::Duration::from_millis(700),
+                    );
+                    let resolved = crate::network::hostnames::resolve_all(
+                        &ips,
+                        net.gateway,
+                        &std::collections::HashMap::new(),
+                        &schedule.scan_ports,
+                        &std::collections::HashMap::new(),
+                        &polled,
+                        &std::sync::Mutex::new(crate::network::dnssd::DnsSdCache::default()),
+                    );
+                    let devices = hosts_to_lan_devices(&hosts, &resolved);
 
                     if let Ok(mut lock) = p2.lock() {
                         lock.push(RemoteNetwork {
@@ -288,8 +302,6 @@ fn spawn_instant_sources(
     let a = Arc::clone(active);
 
     thread::spawn(move || {
-        let now = Local::now().time();
-
         // Step 1: Read all ARP cache entries (instant, zero network traffic)
         let arp_entries = probes::arp_cache_read_all();
 
@@ -367,22 +379,9 @@ fn spawn_instant_sources(
                         None
                     };
                     LanDevice {
-                        ip: IpAddr::V4(e.ip),
-                        mac,
-                        hostname: None,
                         vendor,
-                        first_seen: now,
-                        last_seen: now,
-                        is_online: true,
-                        custom_name: None,
                         discovery_info: "ARP-Cache (instant)".to_string(),
-                        open_ports: String::new(),
-                        bytes_sent: 0,
-                        bytes_received: 0,
-                        tick_sent: 0,
-                        tick_received: 0,
-                        speed_sent: 0.0,
-                        speed_received: 0.0,
+                        ..LanDevice::new(IpAddr::V4(e.ip), mac)
                     }
                 })
                 .collect();
@@ -450,6 +449,12 @@ fn merge_into_network(existing: &mut RemoteNetwork, incoming: RemoteNetwork) {
             if ex_dev.open_ports.is_empty() && !dev.open_ports.is_empty() {
                 ex_dev.open_ports = dev.open_ports;
             }
+            for hint in dev.hints {
+                if !ex_dev.hints.contains(&hint) {
+                    ex_dev.hints.push(hint);
+                }
+            }
+            crate::network::device_services::merge(&mut ex_dev.services, &dev.services);
             ex_dev.last_seen = dev.last_seen;
             ex_dev.is_online = true;
         } else {
@@ -459,11 +464,19 @@ fn merge_into_network(existing: &mut RemoteNetwork, incoming: RemoteNetwork) {
     }
 }
 
+/// (Re)classify every device on a network after new evidence arrived.
+fn classify_devices(net: &mut RemoteNetwork) {
+    let gateway = net.gateway.map(IpAddr::V4);
+    for dev in &mut net.devices {
+        let is_gateway = gateway == Some(dev.ip);
+        crate::network::device_type::apply(dev, is_gateway);
+    }
+}
+
 /// Convert probes::MergedHost results + hostname resolutions into LanDevices.
 fn hosts_to_lan_devices(
     hosts: &[probes::MergedHost],
     resolved: &HashMap<Ipv4Addr, crate::network::hostnames::ResolvedDevice>,
-    now: NaiveTime,
 ) -> Vec<LanDevice> {
     let mut devices = Vec::new();
 
@@ -490,6 +503,9 @@ fn hosts_to_lan_devices(
             .map(|r| crate::network::hostnames::format_ports(&r.open_ports))
             .unwrap_or_default();
 
+        let hints = resolved.get(&host.ip).map(|r| r.hints.clone()).unwrap_or_default();
+        let services = resolved.get(&host.ip).map(|r| r.services.clone()).unwrap_or_default();
+
         let mac = host.mac.clone().unwrap_or_default();
         let vendor = if !mac.is_empty() {
             crate::network::scanner::mac_vendor(&mac)
@@ -498,22 +514,13 @@ fn hosts_to_lan_devices(
         };
 
         devices.push(LanDevice {
-            ip: IpAddr::V4(host.ip),
-            mac,
             hostname,
             vendor,
-            first_seen: now,
-            last_seen: now,
-            is_online: true,
-            custom_name: None,
             discovery_info,
             open_ports,
-            bytes_sent: 0,
-            bytes_received: 0,
-            tick_sent: 0,
-            tick_received: 0,
-            speed_sent: 0.0,
-            speed_received: 0.0,
+            hints,
+            services,
+            ..LanDevice::new(IpAddr::V4(host.ip), mac)
         });
     }
 
@@ -638,7 +645,6 @@ fn discover_docker_networks() -> Vec<RemoteNetwork> {
         return Vec::new();
     }
 
-    let now = Local::now().time();
     let mut networks = Vec::new();
 
     for dnet in docker_nets {
@@ -658,29 +664,19 @@ fn discover_docker_networks() -> Vec<RemoteNetwork> {
                 };
 
                 devices.push(LanDevice {
-                    ip: IpAddr::V4(ip),
-                    mac: c.mac.clone(),
                     hostname: Some(c.name.clone()),
                     vendor: if image_str.is_empty() {
                         Some("Docker Container".to_string())
                     } else {
                         Some(format!("Docker: {}", image_str))
                     },
-                    first_seen: now,
-                    last_seen: now,
                     is_online: status_str.to_lowercase().contains("up"),
-                    custom_name: None,
                     discovery_info: format!(
                         "Image:{}  Status:{}  Network:{}  Driver:{}",
                         image_str, status_str, dnet.name, dnet.driver
                     ),
                     open_ports: c.ports.clone(),
-                    bytes_sent: 0,
-                    bytes_received: 0,
-                    tick_sent: 0,
-                    tick_received: 0,
-                    speed_sent: 0.0,
-                    speed_received: 0.0,
+                    ..LanDevice::new(IpAddr::V4(ip), c.mac.clone())
                 });
             }
         }
@@ -731,7 +727,6 @@ fn discover_wsl_networks() -> Vec<RemoteNetwork> {
         return Vec::new();
     }
 
-    let now = Local::now().time();
     let mut devices = Vec::new();
     let mut first_ip: Option<Ipv4Addr> = None;
 
@@ -787,42 +782,20 @@ fn discover_wsl_networks() -> Vec<RemoteNetwork> {
                 first_ip = Some(ip);
             }
             devices.push(LanDevice {
-                ip: IpAddr::V4(ip),
-                mac: inst.mac.clone(),
                 hostname: Some(hostname),
                 vendor: Some(vendor),
-                first_seen: now,
-                last_seen: now,
                 is_online: inst.is_running,
-                custom_name: None,
                 discovery_info: info_parts.join("  "),
-                open_ports: String::new(),
-                bytes_sent: 0,
-                bytes_received: 0,
-                tick_sent: 0,
-                tick_received: 0,
-                speed_sent: 0.0,
-                speed_received: 0.0,
+                ..LanDevice::new(IpAddr::V4(ip), inst.mac.clone())
             });
         } else if !inst.is_running {
             // Include stopped instances with no IP so user sees them
             devices.push(LanDevice {
-                ip: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
-                mac: String::new(),
                 hostname: Some(hostname),
                 vendor: Some(vendor),
-                first_seen: now,
-                last_seen: now,
                 is_online: false,
-                custom_name: None,
                 discovery_info: info_parts.join("  "),
-                open_ports: String::new(),
-                bytes_sent: 0,
-                bytes_received: 0,
-                tick_sent: 0,
-                tick_received: 0,
-                speed_sent: 0.0,
-                speed_received: 0.0,
+                ..LanDevice::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), String::new())
             });
         }
     }
@@ -13,7 +13,6 @@ use std::sync::Mutex;
 use std::thread;
 use std::time::Duration;
 
-use chrono::Local;
 
 use crate::types::{LanDevice, NetworkCategory, RemoteNetwork};
 
@@ -68,8 +67,6 @@ pub fn discover() -> Vec<RemoteNetwork> {
 // ─── SSH / PuTTY tunnels ─────────────────────────────────────────────────
 
 fn discover_ssh_tunnels() -> Vec<RemoteNetwork> {
-    let now = Local::now().time();
-
     // Check for ssh.exe, putty.exe, plink.exe processes
     let ssh_procs = ["ssh.exe", "putty.exe", "plink.exe"];
     let mut tunnel_devices = Vec::new();
@@ -97,45 +94,22 @@ fn discover_ssh_tunnels() -> Vec<RemoteNetwork> {
         if listeners.is_empty() {
             // Process exists but no detected forwarded ports
             tunnel_devices.push(LanDevice {
-                ip: IpAddr::V4(Ipv4Addr::LOCALHOST),
-                mac: String::new(),
                 hostname: Some(proc_name.to_string()),
                 vendor: Some("SSH Tunnel".to_string()),
-                first_seen: now,
-                last_seen: now,
-                is_online: true,
-                custom_name: None,
                 discovery_info: format!("{} process detected", proc_name),
-                open_ports: String::new(),
-                bytes_sent: 0,
-                bytes_received: 0,
-                tick_sent: 0,
-                tick_received: 0,
-                speed_sent: 0.0,
-                speed_received: 0.0,
+                ..LanDevice::new(IpAddr::V4(Ipv4Addr::LOCALHOST), String::new())
             });
         } else {
             for (port, remote_info) in &listeners {
                 tunnel_devices.push(LanDevice {
-                    ip: IpAddr::V4(Ipv4Addr::LOCALHOST),
-                    mac: String::new(),
                     hostname: Some(format!("{}:{}", proc_name, port)),
                     vendor: Some("SSH Port Forward".to_string()),
-                    first_seen: now,
-                    last_seen: now,
-                    is_online: true,
-                    custom_name: None,
                     discovery_info: format!(
                         "{} tunnel  Local:{}  Remote:{}",
                         proc_name, port, remote_info
                     ),
                     open_ports: port.to_string(),
-                    bytes_sent: 0,
-                    bytes_received: 0,
-                    tick_sent: 0,
-                    tick_received: 0,
-                    speed_sent: 0.0,
-                    speed_received: 0.0,
+                    ..LanDevice::new(IpAddr::V4(Ipv4Addr::LOCALHOST), String::new())
                 });
             }
         }
@@ -232,7 +206,6 @@ fn find_tunnel_listeners(proc_name: &str) -> Vec<(u16, String)> {
 // ─── SOCKS / HTTP Proxies ──────────────────────────────────────────────────
 
 fn discover_proxy_ports() -> Vec<RemoteNetwork> {
-    let now = Local::now().time();
     let proxy_ports: Vec<(u16, &str)> = vec![
         (1080, "SOCKS5"),
         (1081, "SOCKS5"),
@@ -265,22 +238,11 @@ fn discover_proxy_ports() -> Vec<RemoteNetwork> {
     let devices: Vec<LanDevice> = found
         .iter()
         .map(|(port, label)| LanDevice {
-            ip: IpAddr::V4(Ipv4Addr::LOCALHOST),
-            mac: String::new(),
             hostname: Some(format!("{} :{}", label, port)),
             vendor: Some(label.to_string()),
-            first_seen: now,
-            last_seen: now,
-            is_online: true,
-            custom_name: None,
             discovery_info: format!("{} proxy on port {}", label, port),
             open_ports: port.to_string(),
-            bytes_sent: 0,
-            bytes_received: 0,
-            tick_sent: 0,
-            tick_received: 0,
-            speed_sent: 0.0,
-            speed_received: 0.0,
+            ..LanDevice::new(IpAddr::V4(Ipv4Addr::LOCALHOST), String::new())
         })
         .collect();
 
@@ -299,8 +261,6 @@ fn discover_proxy_ports() -> Vec<RemoteNetwork> {
 // ─── Tunnel processes (ngrok, cloudflared, stunnel, etc.) ──────────────────
 
 fn discover_tunnel_processes() -> Vec<RemoteNetwork> {
-    let now = Local::now().time();
-
     let tunnel_apps: Vec<(&str, &str)> = vec![
         ("ngrok.exe", "ngrok"),
         ("cloudflared.exe", "Cloudflare Tunnel"),
@@ -331,22 +291,10 @@ fn discover_tunnel_processes() -> Vec<RemoteNetwork> {
         }
 
         devices.push(LanDevice {
-            ip: IpAddr::V4(Ipv4Addr::LOCALHOST),
-            mac: String::new(),
             hostname: Some(label.to_string()),
             vendor: Some(format!("{} Tunnel", label)),
-            first_seen: now,
-            last_seen: now,
-            is_online: true,
-            custom_name: None,
             discovery_info: format!("{} process active", exe),
-            open_ports: String::new(),
-            bytes_sent: 0,
-            bytes_received: 0,
-            tick_sent: 0,
-            tick_received: 0,
-            speed_sent: 0.0,
-            speed_received: 0.0,
+            ..LanDevice::new(IpAddr::V4(Ipv4Addr::LOCALHOST), String::new())
         });
     }
 
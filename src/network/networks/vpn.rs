@@ -17,7 +17,6 @@ use std::sync::Mutex;
 use std::thread;
 use std::time::Duration;
 
-use chrono::Local;
 
 use crate::types::{LanDevice, NetworkCategory, RemoteNetwork};
 
@@ -936,7 +935,6 @@ fn build_networks(
     ras_connections: Vec<String>,
     primary_ip: Option<Ipv4Addr>,
 ) -> Vec<RemoteNetwork> {
-    let now = Local::now().time();
     let primary = primary_ip.unwrap_or(Ipv4Addr::UNSPECIFIED);
 
     // Group peers by which subnet they belong to
@@ -988,22 +986,10 @@ fn build_networks(
                 }
 
                 devices.push(LanDevice {
-                    ip: IpAddr::V4(peer.ip),
-                    mac: String::new(),
                     hostname: peer.hostname.clone(),
                     vendor: Some("VPN Peer".to_string()),
-                    first_seen: now,
-                    last_seen: now,
-                    is_online: true,
-                    custom_name: None,
                     discovery_info: format!("{}:{}", peer.method, peer.info),
-                    open_ports: String::new(),
-                    bytes_sent: 0,
-                    bytes_received: 0,
-                    tick_sent: 0,
-                    tick_received: 0,
-                    speed_sent: 0.0,
-                    speed_received: 0.0,
+                    ..LanDevice::new(IpAddr::V4(peer.ip), String::new())
                 });
             }
         }
@@ -1062,22 +1048,10 @@ fn build_networks(
             }
 
             devices.push(LanDevice {
-                ip: IpAddr::V4(peer.ip),
-                mac: String::new(),
                 hostname: peer.hostname.clone(),
                 vendor: Some("VPN Peer".to_string()),
-                first_seen: now,
-                last_seen: now,
-                is_online: true,
-                custom_name: None,
                 discovery_info: format!("{}:{}", peer.method, peer.info),
-                open_ports: String::new(),
-                bytes_sent: 0,
-                bytes_received: 0,
-                tick_sent: 0,
-                tick_received: 0,
-                speed_sent: 0.0,
-                speed_received: 0.0,
+                ..LanDevice::new(IpAddr::V4(peer.ip), String::new())
             });
         }
 
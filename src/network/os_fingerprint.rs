@@ -0,0 +1,424 @@
+//! Passive OS fingerprinting from TCP/IP header traits, p0f style.
+//!
+//! A host's first packet of a connection, the SYN, or its answer, the
+//! SYN-ACK, carries defaults its TCP stack picked: the initial TTL, the
+//! window size and scale, the MSS and the order of the TCP options. The set
+//! differs between operating systems and rarely between versions of one,
+//! so matching it against `data/os_signatures.json` names the OS without
+//! sending a single packet. How far the observed TTL is below the initial
+//! one gives the hop distance.
+//!
+//! Signatures use p0f 3's `ver:ittl:olen:mss:wsize,scale:olayout:quirks:pclass`
+//! syntax so entries can be copied from `p0f.fp`. Only ittl, mss, wsize,
+//! scale and olayout are compared; the other fields are accepted and ignored.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+use crate::types::{ConnProto, PacketDirection, PacketSnippet};
+
+static SIGNATURES_JSON: &str = include_str!("../../data/os_signatures.json");
+
+/// Farthest a host can be and still match a signature's initial TTL.
+const MAX_DISTANCE: u8 = 35;
+
+/// Hosts remembered at most; later ones are not fingerprinted.
+const MAX_HOSTS: usize = 4096;
+
+#[derive(Debug, Deserialize)]
+struct RawSignature {
+    os: String,
+    class: String,
+    /// "request" for SYN, "response" for SYN-ACK.
+    dir: String,
+    sig: String,
+}
+
+/// `mss*N`, `mtu*N`, `%N`, an exact size, or any.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum WindowRule {
+    Any,
+    Exact(u16),
+    MssMultiple(u32),
+    MtuMultiple(u32),
+    Modulo(u32),
+}
+
+#[derive(Debug)]
+struct Signature {
+    os: String,
+    class: String,
+    syn_ack: bool,
+    ittl: u8,
+    mss: Option<u16>,
+    window: WindowRule,
+    scale: Option<u8>,
+    layout: String,
+}
+
+impl Signature {
+    fn parse(raw: &RawSignature) -> Option<Self> {
+        let fields: Vec<&str> = raw.sig.split(':').collect();
+        if fields.len() != 8 {
+            return None;
+        }
+        let ittl = fields[1].trim_end_matches('-').parse().ok()?;
+        let mss = match fields[3] {
+            "*" => None,
+            m => Some(m.parse().ok()?),
+        };
+        let (wsize, scale) = fields[4].split_once(',')?;
+        let window = match wsize {
+            "*" => WindowRule::Any,
+            w if w.starts_with("mss*") => WindowRule::MssMultiple(w[4..].parse().ok()?),
+            w if w.starts_with("mtu*") => WindowRule::MtuMultiple(w[4..].parse().ok()?),
+            w if w.starts_with('%') => WindowRule::Modulo(w[1..].parse().ok().filter(|&n| n > 0)?),
+            w => WindowRule::Exact(w.parse().ok()?),
+        };
+        let scale = match scale {
+            "*" => None,
+            s => Some(s.parse().ok()?),
+        };
+        Some(Signature {
+            os: raw.os.clone(),
+            class: raw.class.clone(),
+            syn_ack: raw.dir == "response",
+            ittl,
+            mss,
+            window,
+            scale,
+            layout: fields[5].to_string(),
+        })
+    }
+
+    fn matches(&self, t: &TcpTraits) -> bool {
+        if self.syn_ack != t.syn_ack
+            || t.ttl > self.ittl
+            || self.ittl - t.ttl > MAX_DISTANCE
+            || self.layout != t.layout
+        {
+            return false;
+        }
+        if self.mss.is_some_and(|m| Some(m) != t.mss) {
+            return false;
+        }
+        if self.scale.is_some_and(|s| s != t.wscale.unwrap_or(0)) {
+            return false;
+        }
+        let window = t.window as u32;
+        let mss = t.mss.unwrap_or(0) as u32;
+        match self.window {
+            WindowRule::Any => true,
+            WindowRule::Exact(w) => w == t.window,
+            WindowRule::MssMultiple(n) => mss > 0 && window == mss * n,
+            WindowRule::MtuMultiple(n) => mss > 0 && window == (mss + 40) * n,
+            WindowRule::Modulo(n) => window.is_multiple_of(n),
+        }
+    }
+
+    /// How many fields are pinned down; the most specific match wins.
+    fn specificity(&self) -> u8 {
+        self.mss.is_some() as u8
+            + (self.window != WindowRule::Any) as u8
+            + self.scale.is_some() as u8
+    }
+}
+
+fn signatures() -> &'static [Signature] {
+    static SIGS: OnceLock<Vec<Signature>> = OnceLock::new();
+    SIGS.get_or_init(|| {
+        let raw: Vec<RawSignature> = serde_json::from_str(SIGNATURES_JSON).unwrap_or_default();
+        raw.iter().filter_map(Signature::parse).collect()
+    })
+}
+
+/// The fingerprintable traits of one SYN or SYN-ACK.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TcpTraits {
+    pub syn_ack: bool,
+    pub ttl: u8,
+    pub window: u16,
+    pub mss: Option<u16>,
+    pub wscale: Option<u8>,
+    /// Option order in p0f notation: "mss,nop,ws,nop,nop,sok".
+    pub layout: String,
+}
+
+impl TcpTraits {
+    /// Traits of a SYN or SYN-ACK; every other packet gives `None`.
+    pub fn from_packet(pkt: &PacketSnippet) -> Option<Self> {
+        if pkt.protocol != ConnProto::Tcp || pkt.tcp_flags & 0x02 == 0 || pkt.tcp_flags & 0x04 != 0 {
+            return None;
+        }
+        let (mss, wscale, layout) = parse_options(&pkt.tcp_options);
+        Some(TcpTraits {
+            syn_ack: pkt.tcp_flags & 0x10 != 0,
+            ttl: pkt.ttl,
+            window: pkt.tcp_window,
+            mss,
+            wscale,
+            layout,
+        })
+    }
+
+    /// Initial TTL the sender most likely started from.
+    pub fn initial_ttl(&self) -> u8 {
+        match self.ttl {
+            0..=32 => 32,
+            33..=64 => 64,
+            65..=128 => 128,
+            _ => 255,
+        }
+    }
+
+    /// Observed signature in p0f's notation, for the detail popups.
+    pub fn signature(&self) -> String {
+        let ittl = self.initial_ttl();
+        format!(
+            "4:{}+{}:0:{}:{},{}:{}",
+            ittl,
+            ittl - self.ttl,
+            self.mss.map_or("*".to_string(), |m| m.to_string()),
+            self.window,
+            self.wscale.unwrap_or(0),
+            self.layout,
+        )
+    }
+}
+
+/// MSS, window scale and option layout from raw TCP option bytes.
+fn parse_options(opts: &[u8]) -> (Option<u16>, Option<u8>, String) {
+    let mut mss = None;
+    let mut wscale = None;
+    let mut layout: Vec<String> = Vec::new();
+    let mut pos = 0;
+    while pos < opts.len() {
+        let kind = opts[pos];
+        match kind {
+            0 => {
+                // Trailing padding after EOL is itself a trait
+                layout.push(format!("eol+{}", opts.len() - pos - 1));
+                break;
+            }
+            1 => {
+                layout.push("nop".into());
+                pos += 1;
+                continue;
+            }
+            _ => {}
+        }
+        let Some(&len) = opts.get(pos + 1) else { break };
+        let len = len as usize;
+        if len < 2 || pos + len > opts.len() {
+            break;
+        }
+        let value = &opts[pos + 2..pos + len];
+        layout.push(match kind {
+            2 => {
+                if let [a, b] = value {
+                    mss = Some(u16::from_be_bytes([*a, *b]));
+                }
+                "mss".into()
+            }
+            3 => {
+                wscale = value.first().copied();
+                "ws".into()
+            }
+            4 => "sok".into(),
+            5 => "sack".into(),
+            8 => "ts".into(),
+            other => format!("?{}", other),
+        });
+        pos += len;
+    }
+    (mss, wscale, layout.join(","))
+}
+
+/// What the SYN or SYN-ACK of a host says about it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OsGuess {
+    /// "Windows 10/11", "Linux 3.11+"; for TTL-only guesses the OS family.
+    pub os: String,
+    /// "Windows", "Linux", "Apple", "BSD", ...
+    pub class: String,
+    /// Hops between the host and this machine.
+    pub distance: u8,
+    /// `false` when no signature matched and only the initial TTL was used.
+    pub exact: bool,
+    /// The observed signature, see [`TcpTraits::signature`].
+    pub signature: String,
+    pub from_syn_ack: bool,
+}
+
+impl OsGuess {
+    /// "Linux 3.11+" or "Windows?" for a TTL-only guess.
+    pub fn label(&self) -> String {
+        if self.exact {
+            self.os.clone()
+        } else {
+            format!("{}?", self.os)
+        }
+    }
+}
+
+/// Match traits against the signature table, falling back to a guess from
+/// the initial TTL alone.
+pub fn identify(traits: &TcpTraits) -> OsGuess {
+    let best = signatures()
+        .iter()
+        .filter(|s| s.matches(traits))
+        .fold(None::<&Signature>, |best, s| match best {
+            Some(b) if b.specificity() >= s.specificity() => Some(b),
+            _ => Some(s),
+        });
+    let (os, class, ittl, exact) = match best {
+        Some(sig) => (sig.os.clone(), sig.class.clone(), sig.ittl, true),
+        None => {
+            let ittl = traits.initial_ttl();
+            let family = match ittl {
+                64 => "Linux / Unix",
+                128 => "Windows",
+                255 => "Network device",
+                _ => "Unknown",
+            };
+            (family.to_string(), family.to_string(), ittl, false)
+        }
+    };
+    OsGuess {
+        os,
+        class,
+        distance: ittl.saturating_sub(traits.ttl),
+        exact,
+        signature: traits.signature(),
+        from_syn_ack: traits.syn_ack,
+    }
+}
+
+/// Latest OS guess per remote host, fed from captured packets.
+pub struct OsFingerprinter {
+    hosts: HashMap<IpAddr, OsGuess>,
+}
+
+impl OsFingerprinter {
+    pub fn new() -> Self {
+        Self { hosts: HashMap::new() }
+    }
+
+    /// Fingerprint the inbound SYNs and SYN-ACKs among `packets`. Outbound
+    /// ones describe this machine. A TTL-only guess never replaces a
+    /// signature match.
+    pub fn ingest(&mut self, packets: &[PacketSnippet]) {
+        for pkt in packets {
+            if pkt.direction != PacketDirection::Inbound {
+                continue;
+            }
+            let Some(traits) = TcpTraits::from_packet(pkt) else { continue };
+            let guess = identify(&traits);
+            let full = self.hosts.len() >= MAX_HOSTS;
+            match self.hosts.get_mut(&pkt.src_ip) {
+                Some(existing) if existing.exact && !guess.exact => {}
+                Some(existing) => *existing = guess,
+                None if !full => {
+                    self.hosts.insert(pkt.src_ip, guess);
+                }
+                None => {}
+            }
+        }
+    }
+
+    pub fn get(&self, ip: &IpAddr) -> Option<&OsGuess> {
+        self.hosts.get(ip)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    fn traits(syn_ack: bool, ttl: u8, window: u16, opts: &[u8]) -> TcpTraits {
+        let (mss, wscale, layout) = parse_options(opts);
+        TcpTraits { syn_ack, ttl, window, mss, wscale, layout }
+    }
+
+    /// mss 1460, nop, ws 8, nop, nop, sackOK: what Windows sends.
+    const WINDOWS_OPTS: &[u8] = &[2, 4, 0x05, 0xB4, 1, 3, 3, 8, 1, 1, 4, 2];
+    /// mss 1460, sackOK, ts, nop, ws 7: Linux.
+    const LINUX_OPTS: &[u8] = &[2, 4, 0x05, 0xB4, 4, 2, 8, 10, 0, 0, 0, 1, 0, 0, 0, 0, 1, 3, 3, 7];
+
+    #[test]
+    fn test_parse_options_layout() {
+        let (mss, ws, layout) = parse_options(WINDOWS_OPTS);
+        assert_eq!((mss, ws), (Some(1460), Some(8)));
+        assert_eq!(layout, "mss,nop,ws,nop,nop,sok");
+
+        // macOS ends with EOL and one byte of padding
+        let (_, _, layout) = parse_options(&[2, 4, 0x05, 0xB4, 1, 3, 3, 6, 1, 1, 8, 10, 0, 0, 0, 1, 0, 0, 0, 0, 4, 2, 0, 0]);
+        assert_eq!(layout, "mss,nop,ws,nop,nop,ts,sok,eol+1");
+
+        // Truncated option list stops cleanly
+        assert_eq!(parse_options(&[2, 4, 0x05]).2, "");
+    }
+
+    #[test]
+    fn test_identifies_windows_and_linux_syn() {
+        let win = identify(&traits(false, 126, 64240, WINDOWS_OPTS));
+        assert_eq!(win.os, "Windows 10/11");
+        assert_eq!(win.distance, 2);
+        assert!(win.exact);
+        assert_eq!(win.signature, "4:128+2:0:1460:64240,8:mss,nop,ws,nop,nop,sok");
+
+        // 64240 = 1460 * 44
+        let linux = identify(&traits(false, 61, 64240, LINUX_OPTS));
+        assert_eq!(linux.os, "Linux 3.11+");
+        assert_eq!(linux.class, "Linux");
+        assert_eq!(linux.distance, 3);
+    }
+
+    #[test]
+    fn test_syn_ack_and_ttl_fallback() {
+        let server = identify(&traits(true, 52, 65160, LINUX_OPTS));
+        assert_eq!(server.os, "Linux 3.x+");
+        assert!(server.from_syn_ack);
+        assert_eq!(server.distance, 12);
+
+        // A request signature does not describe a response
+        let odd = identify(&traits(true, 120, 1234, WINDOWS_OPTS));
+        assert!(!odd.exact);
+        assert_eq!(odd.label(), "Windows?");
+        assert_eq!(odd.distance, 8);
+    }
+
+    #[test]
+    fn test_fingerprinter_keeps_exact_guess() {
+        let host = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 20));
+        let packet = |flags: u8, window: u16, opts: &[u8]| PacketSnippet {
+            timestamp: chrono::Local::now().time(),
+            direction: PacketDirection::Inbound,
+            src_ip: host,
+            dst_ip: IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)),
+            src_port: 50000,
+            dst_port: 445,
+            protocol: ConnProto::Tcp,
+            snippet: String::new(),
+            payload_size: 0,
+            ttl: 128,
+            ip_total_len: 52,
+            ip_id: 1,
+            tcp_flags: flags,
+            tcp_seq: 0,
+            tcp_ack_num: 0,
+            tcp_window: window,
+            tcp_options: opts.to_vec(),
+            raw_payload: Vec::new(),
+        };
+        let mut fp = OsFingerprinter::new();
+        fp.ingest(&[packet(0x02, 64240, WINDOWS_OPTS), packet(0x02, 999, &[]), packet(0x10, 0, &[])]);
+        let guess = fp.get(&host).unwrap();
+        assert_eq!(guess.os, "Windows 10/11");
+        assert_eq!(guess.distance, 0);
+    }
+}
@@ -1,49 +1,257 @@
+//! MAC vendor lookup across the IEEE registries.
+//!
+//! The IEEE hands out MAC blocks of three sizes: MA-L (24-bit prefix), MA-M
+//! (28-bit) and MA-S (36-bit), plus CIDs, 24-bit company IDs used in
+//! locally administered addresses. Many IoT makers only own an MA-M or MA-S
+//! block inside someone else's 24-bit range, so lookups try the longest
+//! prefix first.
+//!
+//! The bundled `data/oui.txt` holds `HEX<TAB>Vendor` lines, where HEX is 6, 7
+//! or 9 digits; `scripts/sync_oui.py` rebuilds it from all four registries.
+//! The list shipped today is MA-L only, so 28- and 36-bit vendors need the
+//! official registry CSVs (`oui.csv`, `mam.csv`, `oui36.csv`, `cid.csv`).
+//! Those, imported with [`import`], are copied to `%APPDATA%\psnet\oui\`
+//! and take precedence over the bundled list.
+//!
+//! MA-L blocks the IEEE split into MA-M / MA-S assignments are listed under
+//! "IEEE Registration Authority". That names no vendor, so those entries are
+//! skipped and an unassigned address in such a block stays unknown.
+
 use std::collections::HashMap;
-use std::sync::OnceLock;
-
-static OUI_DB: OnceLock<HashMap<[u8; 3], &'static str>> = OnceLock::new();
-
-fn parse_hex_byte(s: &str) -> Option<u8> {
-    u8::from_str_radix(s, 16).ok()
-}
-
-fn get_db() -> &'static HashMap<[u8; 3], &'static str> {
-    OUI_DB.get_or_init(|| {
-        let raw = include_str!("../../data/oui.txt");
-        let mut map = HashMap::with_capacity(32000);
-        for line in raw.lines() {
-            if let Some((hex, name)) = line.split_once('\t') {
-                let hex = hex.trim();
-                if hex.len() == 6 {
-                    if let (Some(a), Some(b), Some(c)) = (
-                        parse_hex_byte(&hex[0..2]),
-                        parse_hex_byte(&hex[2..4]),
-                        parse_hex_byte(&hex[4..6]),
-                    ) {
-                        map.insert([a, b, c], name.trim());
-                    }
-                }
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+/// Prefix lengths in bits, longest first.
+const PREFIX_BITS: [u8; 3] = [36, 28, 24];
+
+/// Organization the MA-L registry lists for blocks split into MA-M / MA-S.
+const PLACEHOLDER_VENDOR: &str = "IEEE Registration Authority";
+
+static OUI_DB: RwLock<Option<Arc<OuiDb>>> = RwLock::new(None);
+
+#[derive(Default)]
+struct OuiDb {
+    /// (prefix bits, prefix value) -> vendor
+    entries: HashMap<(u8, u64), String>,
+}
+
+impl OuiDb {
+    fn insert(&mut self, hex: &str, vendor: &str) -> bool {
+        let vendor = vendor.trim();
+        let bits = hex.len() as u8 * 4;
+        if vendor.is_empty() || vendor == PLACEHOLDER_VENDOR || !PREFIX_BITS.contains(&bits) {
+            return false;
+        }
+        match u64::from_str_radix(hex, 16) {
+            Ok(prefix) => {
+                self.entries.insert((bits, prefix), vendor.to_string());
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    fn lookup(&self, mac: &str) -> Option<&str> {
+        let clean: String = mac.chars().filter(|c| c.is_ascii_hexdigit()).take(12).collect();
+        PREFIX_BITS.iter().find_map(|&bits| {
+            let digits = bits as usize / 4;
+            if clean.len() < digits {
+                return None;
+            }
+            let prefix = u64::from_str_radix(&clean[..digits], 16).ok()?;
+            self.entries.get(&(bits, prefix)).map(String::as_str)
+        })
+    }
+}
+
+fn db() -> Arc<OuiDb> {
+    if let Some(db) = OUI_DB.read().ok().and_then(|g| g.clone()) {
+        return db;
+    }
+    reload();
+    OUI_DB.read().ok().and_then(|g| g.clone()).unwrap_or_default()
+}
+
+/// Rebuild the database from the bundled list and the imported CSVs.
+pub fn reload() {
+    let mut db = OuiDb { entries: HashMap::with_capacity(40000) };
+    for line in include_str!("../../data/oui.txt").lines() {
+        if let Some((hex, name)) = line.split_once('\t') {
+            db.insert(hex.trim(), name);
+        }
+    }
+    for path in imported_files() {
+        if let Ok(text) = std::fs::read_to_string(&path) {
+            for (hex, vendor) in parse_registry_csv(&text).entries {
+                db.insert(&hex, &vendor);
             }
         }
-        map
-    })
+    }
+    if let Ok(mut g) = OUI_DB.write() {
+        *g = Some(Arc::new(db));
+    }
 }
 
 /// Pre-warm the OUI database (call from a background thread on startup).
 pub fn warm() {
-    let _ = get_db();
+    let _ = db();
 }
 
 /// Look up the manufacturer for a MAC address like "AA:BB:CC:DD:EE:FF".
-pub fn lookup(mac: &str) -> Option<&'static str> {
-    let clean: String = mac.chars().filter(|c| c.is_ascii_hexdigit()).collect();
-    if clean.len() < 6 {
-        return None;
-    }
-    let prefix = [
-        parse_hex_byte(&clean[0..2])?,
-        parse_hex_byte(&clean[2..4])?,
-        parse_hex_byte(&clean[4..6])?,
-    ];
-    get_db().get(&prefix).copied()
+pub fn lookup(mac: &str) -> Option<String> {
+    db().lookup(mac).map(str::to_string)
+}
+
+// ─── IEEE registry CSVs ──────────────────────────────────────────────────────
+
+fn user_dir() -> PathBuf {
+    match dirs::data_dir() {
+        Some(data_dir) => data_dir.join("psnet").join("oui"),
+        None => PathBuf::from("psnet_oui"),
+    }
+}
+
+/// Imported CSVs in a fixed order, so an MA-L file can't shadow CID entries
+/// from a later import at random.
+fn imported_files() -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(user_dir())
+        .map(|rd| rd.flatten().map(|e| e.path()).collect())
+        .unwrap_or_default();
+    files.retain(|p| p.extension().is_some_and(|e| e.eq_ignore_ascii_case("csv")));
+    files.sort();
+    files
+}
+
+/// Entries of one registry CSV ("Registry,Assignment,Organization Name,...").
+#[derive(Debug, Default)]
+struct RegistryCsv {
+    /// "MA-L", "MA-M", "MA-S" or "CID", from the first row.
+    registry: String,
+    /// (assignment hex, organization)
+    entries: Vec<(String, String)>,
+}
+
+fn parse_registry_csv(text: &str) -> RegistryCsv {
+    let mut out = RegistryCsv::default();
+    let mut lines = text.lines();
+    let header = lines.next().map(split_csv_line).unwrap_or_default();
+    let column = |name: &str| header.iter().position(|h| h.trim().eq_ignore_ascii_case(name));
+    let (Some(reg_col), Some(hex_col), Some(org_col)) =
+        (column("Registry"), column("Assignment"), column("Organization Name"))
+    else {
+        return out;
+    };
+    for line in lines {
+        let fields = split_csv_line(line);
+        let (Some(reg), Some(hex), Some(org)) = (fields.get(reg_col), fields.get(hex_col), fields.get(org_col)) else {
+            continue;
+        };
+        let hex = hex.trim();
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) || org.trim().is_empty() {
+            continue;
+        }
+        if out.registry.is_empty() {
+            out.registry = reg.trim().to_string();
+        }
+        out.entries.push((hex.to_string(), org.trim().to_string()));
+    }
+    out
+}
+
+/// Split one CSV line, honouring double-quoted fields with `""` escapes.
+pub(super) fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+/// Import IEEE registry CSVs from a file or a folder of them into the data
+/// dir and reload. Returns a summary like "MA-L 38112, MA-S 6120 entries".
+pub fn import(path: &Path) -> Result<String, String> {
+    let sources: Vec<PathBuf> = if path.is_dir() {
+        let mut files: Vec<PathBuf> = std::fs::read_dir(path)
+            .map_err(|e| format!("{}: {}", path.display(), e))?
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|e| e.eq_ignore_ascii_case("csv")))
+            .collect();
+        files.sort();
+        files
+    } else {
+        vec![path.to_path_buf()]
+    };
+
+    let dir = user_dir();
+    let mut imported = Vec::new();
+    for src in &sources {
+        let text = std::fs::read_to_string(src).map_err(|e| format!("{}: {}", src.display(), e))?;
+        let csv = parse_registry_csv(&text);
+        if csv.entries.is_empty() {
+            // Unrelated CSVs in a folder are skipped; a single file must be a registry
+            if sources.len() == 1 {
+                return Err(format!("{}: not an IEEE registry CSV", src.display()));
+            }
+            continue;
+        }
+        std::fs::create_dir_all(&dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+        let dest = dir.join(format!("{}.csv", csv.registry.replace(['/', '\\'], "_")));
+        std::fs::write(&dest, &text).map_err(|e| format!("{}: {}", dest.display(), e))?;
+        imported.push(format!("{} {}", csv.registry, csv.entries.len()));
+    }
+    if imported.is_empty() {
+        return Err(format!("no IEEE registry CSVs in {}", path.display()));
+    }
+    reload();
+    Ok(format!("{} entries", imported.join(", ")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_longest_prefix_wins() {
+        let mut db = OuiDb::default();
+        db.insert("70B3D5", "IEEE Registration Authority");
+        db.insert("70B3D5A", "Some MA-M Maker");
+        db.insert("70B3D5A12", "Tiny IoT Co");
+        assert_eq!(db.lookup("70:B3:D5:A1:23:45"), Some("Tiny IoT Co"));
+        assert_eq!(db.lookup("70:B3:D5:A9:00:01"), Some("Some MA-M Maker"));
+        assert_eq!(db.lookup("70-B3-D5-10-00-01"), None);
+        assert_eq!(db.lookup("00:00:00:00:00:01"), None);
+    }
+
+    #[test]
+    fn test_bundled_list_loads() {
+        assert!(lookup("28:6F:B9:00:00:01").is_some());
+    }
+
+    #[test]
+    fn test_parse_registry_csv() {
+        let text = "Registry,Assignment,Organization Name,Organization Address\n\
+                    MA-S,70B3D5F2C,\"Acme, Inc.\",\"1 Road, Town\"\n\
+                    MA-S,8C1F64001,\"Say \"\"Hi\"\" Ltd\",Somewhere\n\
+                    MA-S,not-hex,Broken,Nowhere\n";
+        let csv = parse_registry_csv(text);
+        assert_eq!(csv.registry, "MA-S");
+        assert_eq!(csv.entries, vec![
+            ("70B3D5F2C".to_string(), "Acme, Inc.".to_string()),
+            ("8C1F64001".to_string(), "Say \"Hi\" Ltd".to_string()),
+        ]);
+        assert!(parse_registry_csv("Name,Value\nfoo,bar\n").entries.is_empty());
+    }
 }
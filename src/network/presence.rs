@@ -0,0 +1,296 @@
+//! Device presence: scheduled scans, online history and offline alerts.
+//!
+//! Every completed scan extends the online spans of the devices it saw. The
+//! spans live in each device's inventory record, pruned to the last week,
+//! and feed the 24h / 7d presence bars in the device popup. Scans run on the
+//! interval from `%APPDATA%\psnet\devices.json`:
+//!
+//! ```json
+//! { "scan_interval_secs": 300, "offline_alert_mins": 120, "scan_ports": [22, 80, 443, 5000] }
+//! ```
+//!
+//! `scan_ports` replaces the built-in port list for every following scan.
+
+use std::collections::HashSet;
+use std::net::{IpAddr, Ipv4Addr};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::types::LanDevice;
+
+use crate::config;
+
+const CONFIG_FILE: &str = "devices.json";
+/// Presence older than this is dropped from the inventory.
+const HISTORY_DAYS: i64 = 7;
+const MIN_INTERVAL_SECS: u64 = 30;
+
+// ─── Schedule ────────────────────────────────────────────────────────────────
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct ScanSchedule {
+    /// Seconds between background scans (the Devices tab scans every 15s).
+    pub scan_interval_secs: u64,
+    /// Alert when a device seen this session has been offline this long; 0 disables.
+    pub offline_alert_mins: u64,
+    /// Ports connect-scanned and fingerprinted on each device.
+    pub scan_ports: Vec<u16>,
+    /// SNMP communities tried, in order, when querying devices.
+    pub snmp_communities: Vec<String>,
+    /// DHCP servers expected besides the gateway; offers from any other
+    /// server are flagged as rogue.
+    pub dhcp_servers: Vec<Ipv4Addr>,
+    /// Hosts probed at once by a range scan.
+    pub range_scan_concurrency: usize,
+    /// Milliseconds between two probe methods hitting the same host in a
+    /// range scan.
+    pub range_scan_host_interval_ms: u64,
+}
+
+impl Default for ScanSchedule {
+    fn default() -> Self {
+        Self {
+            scan_interval_secs: 300,
+            offline_alert_mins: 120,
+            scan_ports: super::hostnames::SCAN_PORTS.to_vec(),
+            snmp_communities: vec!["public".to_string()],
+            dhcp_servers: Vec::new(),
+            range_scan_concurrency: 64,
+            range_scan_host_interval_ms: 50,
+        }
+    }
+}
+
+impl ScanSchedule {
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.scan_interval_secs.max(MIN_INTERVAL_SECS))
+    }
+
+    /// Longest pause between sightings that still counts as continuously
+    /// online: two missed scans plus slack.
+    pub fn presence_gap(&self) -> chrono::Duration {
+        chrono::Duration::seconds(2 * self.interval().as_secs() as i64 + 60)
+    }
+}
+
+/// The schedule from `devices.json`, re-read when the file changes.
+pub struct ScheduleConfig {
+    pub schedule: ScanSchedule,
+    /// Parse error from the last reload, for the status bar.
+    pub error: Option<String>,
+    path: PathBuf,
+    loaded_stamp: Option<Option<SystemTime>>,
+}
+
+impl ScheduleConfig {
+    pub fn new() -> Self {
+        let mut config = Self {
+            schedule: ScanSchedule::default(),
+            error: None,
+            path: config::path(CONFIG_FILE),
+            loaded_stamp: None,
+        };
+        config.reload_if_changed();
+        config
+    }
+
+    pub fn reload_if_changed(&mut self) {
+        let stamp = config::modified(&self.path);
+        if self.loaded_stamp == Some(stamp) {
+            return;
+        }
+        self.loaded_stamp = Some(stamp);
+
+        let parsed = match std::fs::read_to_string(&self.path) {
+            Ok(content) => serde_json::from_str::<ScanSchedule>(&content).map_err(|e| e.to_string()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(ScanSchedule::default()),
+            Err(e) => Err(e.to_string()),
+        };
+        match parsed {
+            Ok(schedule) => {
+                self.schedule = schedule;
+                self.error = None;
+            }
+            Err(e) => self.error = Some(format!("{}: {}", CONFIG_FILE, e)),
+        }
+    }
+}
+
+// ─── History ─────────────────────────────────────────────────────────────────
+
+/// A stretch of time the device was seen online.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PresenceSpan {
+    #[serde(with = "super::inventory::rfc3339")]
+    pub from: DateTime<Local>,
+    #[serde(with = "super::inventory::rfc3339")]
+    pub to: DateTime<Local>,
+}
+
+/// Record a sighting at `at`: extend the last span if it ended within `gap`,
+/// otherwise start a new one. Spans older than a week are dropped.
+pub fn mark_present(spans: &mut Vec<PresenceSpan>, at: DateTime<Local>, gap: chrono::Duration) {
+    match spans.last_mut() {
+        Some(last) if at >= last.to && at - last.to <= gap => last.to = at,
+        Some(last) if at <= last.to => {}
+        _ => spans.push(PresenceSpan { from: at, to: at }),
+    }
+    let cutoff = at - chrono::Duration::days(HISTORY_DAYS);
+    spans.retain(|s| s.to >= cutoff);
+}
+
+/// Fraction of each of `buckets` equal slices of `[from, to)` the device was
+/// online. A lone sighting counts as a sliver so it still shows.
+pub fn online_fraction(
+    spans: &[PresenceSpan],
+    from: DateTime<Local>,
+    to: DateTime<Local>,
+    buckets: usize,
+) -> Vec<f32> {
+    let total = (to - from).num_seconds().max(1) as f64;
+    let width = total / buckets as f64;
+    (0..buckets)
+        .map(|i| {
+            let b0 = from + chrono::Duration::milliseconds((i as f64 * width * 1000.0) as i64);
+            let b1 = from + chrono::Duration::milliseconds(((i + 1) as f64 * width * 1000.0) as i64);
+            let mut online = 0.0;
+            let mut touched = false;
+            for s in spans {
+                if s.to < b0 || s.from >= b1 {
+                    continue;
+                }
+                touched = true;
+                online += (s.to.min(b1) - s.from.max(b0)).num_seconds() as f64;
+            }
+            let frac = (online / width) as f32;
+            if touched { frac.clamp(0.05, 1.0) } else { 0.0 }
+        })
+        .collect()
+}
+
+// ─── Offline alerts ──────────────────────────────────────────────────────────
+
+/// A device that has been offline longer than the alert threshold.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeviceOffline {
+    pub name: String,
+    pub ip: IpAddr,
+    pub mac: String,
+    pub minutes: i64,
+}
+
+/// Raises one [`DeviceOffline`] per absence, and only for devices seen
+/// online this session — not for everything in the inventory's past.
+#[derive(Default)]
+pub struct PresenceWatch {
+    seen: HashSet<String>,
+    alerted: HashSet<String>,
+}
+
+impl PresenceWatch {
+    pub fn check(&mut self, devices: &[LanDevice], now: DateTime<Local>, threshold_mins: u64) -> Vec<DeviceOffline> {
+        let mut offline = Vec::new();
+        for d in devices.iter().filter(|d| !d.mac.is_empty()) {
+            if d.is_online {
+                self.seen.insert(d.mac.clone());
+                self.alerted.remove(&d.mac);
+                continue;
+            }
+            let minutes = (now - d.last_seen).num_minutes();
+            if threshold_mins == 0
+                || minutes <= threshold_mins as i64
+                || !self.seen.contains(&d.mac)
+                || self.alerted.contains(&d.mac)
+            {
+                continue;
+            }
+            self.alerted.insert(d.mac.clone());
+            offline.push(DeviceOffline {
+                name: d.custom_name.clone()
+                    .or_else(|| d.hostname.clone())
+                    .unwrap_or_else(|| d.mac.clone()),
+                ip: d.ip,
+                mac: d.mac.clone(),
+                minutes,
+            });
+        }
+        offline
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    use chrono::Duration as CDuration;
+
+    fn device(mac: &str, online: bool, last_seen: DateTime<Local>) -> LanDevice {
+        LanDevice {
+            hostname: Some("tv".into()),
+            first_seen: last_seen,
+            last_seen,
+            is_online: online,
+            ..LanDevice::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 40)), mac.into())
+        }
+    }
+
+    #[test]
+    fn test_mark_present_merges_and_prunes() {
+        let t0 = Local::now() - CDuration::days(8);
+        let gap = CDuration::minutes(11);
+        let mut spans = Vec::new();
+        mark_present(&mut spans, t0, gap);
+        mark_present(&mut spans, t0 + CDuration::minutes(5), gap);
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].to, t0 + CDuration::minutes(5));
+
+        // A long absence starts a new span
+        mark_present(&mut spans, t0 + CDuration::hours(1), gap);
+        assert_eq!(spans.len(), 2);
+
+        // A week later the old spans fall out
+        mark_present(&mut spans, t0 + CDuration::days(8), gap);
+        assert_eq!(spans.len(), 1);
+    }
+
+    #[test]
+    fn test_online_fraction() {
+        let from = Local::now();
+        let to = from + CDuration::hours(4);
+        let spans = vec![
+            PresenceSpan { from, to: from + CDuration::minutes(30) },
+            PresenceSpan { from: from + CDuration::hours(3) + CDuration::minutes(10), to: from + CDuration::hours(3) + CDuration::minutes(10) },
+        ];
+        let bars = online_fraction(&spans, from, to, 4);
+        assert!((bars[0] - 0.5).abs() < 0.01, "{:?}", bars);
+        assert_eq!(bars[1], 0.0);
+        assert_eq!(bars[2], 0.0);
+        assert_eq!(bars[3], 0.05);
+    }
+
+    #[test]
+    fn test_offline_alert_once_per_absence() {
+        let now = Local::now();
+        let mut watch = PresenceWatch::default();
+
+        // Offline in the inventory's past but never seen this session: no alert
+        let gone = [device("AA", false, now - CDuration::hours(5))];
+        assert!(watch.check(&gone, now, 120).is_empty());
+
+        watch.check(&[device("AA", true, now - CDuration::hours(3))], now, 120);
+        let offline = watch.check(&gone, now, 120);
+        assert_eq!(offline.len(), 1);
+        assert_eq!(offline[0].name, "tv");
+        assert!(offline[0].minutes >= 299);
+        assert!(watch.check(&gone, now, 120).is_empty());
+
+        // Back online re-arms it
+        watch.check(&[device("AA", true, now)], now, 120);
+        assert_eq!(watch.check(&gone, now, 120).len(), 1);
+    }
+}
@@ -51,26 +51,27 @@ impl Protocol {
 
     /// Distinct color per protocol for the tag cloud.
     pub fn color(&self) -> Color {
-        match self {
-            Self::Dhcp => Color::Rgb(180, 140, 255),
-            Self::Dns => Color::Rgb(80, 200, 255),
-            Self::Http => Color::Rgb(100, 220, 100),
-            Self::Https => Color::Rgb(60, 180, 60),
-            Self::Ssh => Color::Rgb(255, 160, 60),
-            Self::Ftp => Color::Rgb(200, 100, 255),
-            Self::Smtp => Color::Rgb(255, 120, 120),
-            Self::Imap => Color::Rgb(255, 140, 180),
-            Self::Pop3 => Color::Rgb(220, 100, 160),
-            Self::Rdp => Color::Rgb(255, 80, 80),
-            Self::Smb => Color::Rgb(200, 180, 100),
-            Self::Ntp => Color::Rgb(100, 200, 200),
-            Self::Snmp => Color::Rgb(160, 200, 100),
-            Self::Mdns => Color::Rgb(120, 180, 255),
-            Self::Llmnr => Color::Rgb(140, 160, 255),
-            Self::Ssdp => Color::Rgb(200, 160, 200),
-            Self::Quic => Color::Rgb(0, 220, 180),
-            Self::Other => Color::Rgb(140, 140, 140),
-        }
+        let (r, g, b) = match self {
+            Self::Dhcp => (180, 140, 255),
+            Self::Dns => (80, 200, 255),
+            Self::Http => (100, 220, 100),
+            Self::Https => (60, 180, 60),
+            Self::Ssh => (255, 160, 60),
+            Self::Ftp => (200, 100, 255),
+            Self::Smtp => (255, 120, 120),
+            Self::Imap => (255, 140, 180),
+            Self::Pop3 => (220, 100, 160),
+            Self::Rdp => (255, 80, 80),
+            Self::Smb => (200, 180, 100),
+            Self::Ntp => (100, 200, 200),
+            Self::Snmp => (160, 200, 100),
+            Self::Mdns => (120, 180, 255),
+            Self::Llmnr => (140, 160, 255),
+            Self::Ssdp => (200, 160, 200),
+            Self::Quic => (0, 220, 180),
+            Self::Other => (140, 140, 140),
+        };
+        crate::ui::theme::theme().rgb(r, g, b)
     }
 
     /// Detect protocol from source/destination ports and transport type.
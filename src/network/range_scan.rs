@@ -0,0 +1,436 @@
+//! Scans of user-specified target ranges.
+//!
+//! `n` on the Devices tab takes a list of targets — CIDR blocks
+//! (`10.20.0.0/22`), ranges (`192.168.5.10-50`, `10.0.0.1-10.0.1.254`) and
+//! single addresses, separated by commas or spaces — optionally named
+//! (`office: 10.20.0.0/22`). Hosts are probed in batches of
+//! `range_scan_concurrency` with the unicast methods of the LAN scan (ARP
+//! for on-link targets, ICMP, TCP connect, NetBIOS, reverse DNS), waiting
+//! `range_scan_host_interval_ms` between two methods hitting the same host.
+//! Hosts that answer go through the usual hostname, port and service
+//! resolution. Finished scans are kept by name in
+//! `%APPDATA%\psnet\range_scans.json`; running a name again replaces it.
+
+use std::collections::HashMap;
+use std::net::Ipv4Addr;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::types::DeviceService;
+
+use super::networks::probes::{self, ProbeHit};
+
+/// Largest target list accepted (a /16).
+pub const MAX_TARGETS: usize = 65_536;
+/// Saved scans kept, newest first.
+const MAX_SAVED: usize = 50;
+
+pub const PHASE_IDLE: u8 = 0;
+pub const PHASE_PROBE: u8 = 1;
+pub const PHASE_RESOLVE: u8 = 2;
+
+// ─── Target parsing ──────────────────────────────────────────────────────────
+
+/// Split `name: targets`. Without a name the targets name the scan.
+pub fn split_name(input: &str) -> (String, String) {
+    match input.split_once(':') {
+        Some((name, targets)) if !name.trim().is_empty() => (name.trim().to_string(), targets.trim().to_string()),
+        Some((_, targets)) => (targets.trim().to_string(), targets.trim().to_string()),
+        None => (input.trim().to_string(), input.trim().to_string()),
+    }
+}
+
+/// Expand a target list into addresses, in order and without duplicates.
+/// CIDR blocks of /30 and wider skip their network and broadcast address.
+pub fn parse_targets(spec: &str) -> Result<Vec<Ipv4Addr>, String> {
+    let mut out = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    let items: Vec<&str> = spec.split(|c: char| c == ',' || c.is_whitespace()).filter(|s| !s.is_empty()).collect();
+    if items.is_empty() {
+        return Err("no targets given".to_string());
+    }
+    for item in items {
+        let (first, last) = parse_item(item).ok_or_else(|| format!("cannot read target \"{}\"", item))?;
+        if first > last {
+            return Err(format!("\"{}\" ends before it starts", item));
+        }
+        if (last - first) as usize + out.len() >= MAX_TARGETS {
+            return Err(format!("more than {} addresses", MAX_TARGETS));
+        }
+        for n in first..=last {
+            if seen.insert(n) {
+                out.push(Ipv4Addr::from(n));
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// First and last address of one target as integers.
+fn parse_item(item: &str) -> Option<(u32, u32)> {
+    if let Some((addr, prefix)) = item.split_once('/') {
+        let addr = u32::from(addr.parse::<Ipv4Addr>().ok()?);
+        let prefix: u32 = prefix.parse().ok().filter(|p| *p <= 32)?;
+        let mask = if prefix == 0 { 0 } else { u32::MAX << (32 - prefix) };
+        let network = addr & mask;
+        let broadcast = network | !mask;
+        return Some(if prefix <= 30 { (network + 1, broadcast - 1) } else { (network, broadcast) });
+    }
+    if let Some((start, end)) = item.split_once('-') {
+        let start = start.parse::<Ipv4Addr>().ok()?;
+        let end = match end.parse::<Ipv4Addr>() {
+            Ok(end) => end,
+            // "192.168.5.10-50": the end replaces the last octet
+            Err(_) => {
+                let last: u8 = end.parse().ok()?;
+                let o = start.octets();
+                Ipv4Addr::new(o[0], o[1], o[2], last)
+            }
+        };
+        return Some((u32::from(start), u32::from(end)));
+    }
+    let addr = u32::from(item.parse::<Ipv4Addr>().ok()?);
+    Some((addr, addr))
+}
+
+// ─── Results ─────────────────────────────────────────────────────────────────
+
+/// A host that answered a range scan.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScannedHost {
+    pub ip: Ipv4Addr,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mac: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vendor: Option<String>,
+    /// Probe methods that found the host ("ICMP", "TCP", "NBSTAT", ...).
+    pub methods: Vec<String>,
+    #[serde(default)]
+    pub open_ports: String,
+    #[serde(default)]
+    pub services: Vec<DeviceService>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RangeScan {
+    pub name: String,
+    /// The target list as typed.
+    pub targets: String,
+    /// Addresses probed.
+    pub probed: usize,
+    /// Stopped before every address was probed.
+    #[serde(default)]
+    pub cancelled: bool,
+    #[serde(with = "super::inventory::rfc3339")]
+    pub started: DateTime<Local>,
+    #[serde(with = "super::inventory::rfc3339")]
+    pub finished: DateTime<Local>,
+    pub hosts: Vec<ScannedHost>,
+}
+
+/// Batch size and per-host pacing, from `devices.json`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScanLimits {
+    /// Hosts probed at once.
+    pub concurrency: usize,
+    /// Pause between two probe methods hitting the same host.
+    pub host_interval: Duration,
+}
+
+/// What the probes need to know about this machine's network.
+pub struct ScanContext {
+    pub local: Option<(Ipv4Addr, Ipv4Addr)>,
+    pub ports: Vec<u16>,
+    pub communities: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RangeScansFile {
+    scans: Vec<RangeScan>,
+}
+
+// ─── RangeScanner ────────────────────────────────────────────────────────────
+
+pub struct RangeScanner {
+    /// Saved scans, newest first.
+    pub scans: Vec<RangeScan>,
+    /// Name and targets of the scan in progress.
+    pub running: Option<(String, String)>,
+    /// (done, total) for the current phase.
+    progress: Arc<(AtomicUsize, AtomicUsize)>,
+    phase: Arc<AtomicU8>,
+    cancel: Arc<AtomicBool>,
+    result: Arc<Mutex<Option<RangeScan>>>,
+    /// Set when the saved scans changed and have not been written yet.
+    dirty: bool,
+    path: PathBuf,
+}
+
+impl RangeScanner {
+    pub fn load() -> Self {
+        let path = match dirs::data_dir() {
+            Some(data_dir) => {
+                let dir = data_dir.join("psnet");
+                let _ = std::fs::create_dir_all(&dir);
+                dir.join("range_scans.json")
+            }
+            None => PathBuf::from("psnet_range_scans.json"),
+        };
+        let file: RangeScansFile = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default();
+        Self::new(file.scans, path)
+    }
+
+    fn new(scans: Vec<RangeScan>, path: PathBuf) -> Self {
+        Self {
+            scans,
+            running: None,
+            progress: Arc::new((AtomicUsize::new(0), AtomicUsize::new(0))),
+            phase: Arc::new(AtomicU8::new(PHASE_IDLE)),
+            cancel: Arc::new(AtomicBool::new(false)),
+            result: Arc::new(Mutex::new(None)),
+            dirty: false,
+            path,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.is_some()
+    }
+
+    /// Current phase and its (done, total) progress.
+    pub fn progress(&self) -> (u8, usize, usize) {
+        (
+            self.phase.load(Ordering::Relaxed),
+            self.progress.0.load(Ordering::Relaxed),
+            self.progress.1.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Parse `input` and scan it on a background thread.
+    pub fn start(&mut self, input: &str, limits: ScanLimits, ctx: ScanContext) -> Result<String, String> {
+        if let Some((name, _)) = &self.running {
+            return Err(format!("\"{}\" is still running", name));
+        }
+        let (name, spec) = split_name(input);
+        let targets = parse_targets(&spec)?;
+        let summary = format!("{} addresses in \"{}\"", targets.len(), name);
+
+        self.running = Some((name.clone(), spec.clone()));
+        self.cancel.store(false, Ordering::SeqCst);
+        let progress = Arc::clone(&self.progress);
+        let phase = Arc::clone(&self.phase);
+        let cancel = Arc::clone(&self.cancel);
+        let result = Arc::clone(&self.result);
+        thread::spawn(move || {
+            let scan = run_scan(name, spec, &targets, limits, &ctx, &progress, &phase, &cancel);
+            phase.store(PHASE_IDLE, Ordering::Relaxed);
+            if let Ok(mut r) = result.lock() {
+                *r = Some(scan);
+            }
+        });
+        Ok(summary)
+    }
+
+    /// Stop probing new batches; hosts found so far are still resolved and saved.
+    pub fn cancel(&self) -> bool {
+        self.is_running() && !self.cancel.swap(true, Ordering::SeqCst)
+    }
+
+    /// Collect a finished scan. Returns a summary for the status bar.
+    pub fn poll(&mut self) -> Option<String> {
+        let scan = self.result.lock().ok()?.take()?;
+        self.running = None;
+        let summary = format!(
+            "Range scan \"{}\" {}: {} hosts up of {} probed",
+            scan.name,
+            if scan.cancelled { "stopped" } else { "finished" },
+            scan.hosts.len(),
+            scan.probed,
+        );
+        self.store(scan);
+        Some(summary)
+    }
+
+    fn store(&mut self, scan: RangeScan) {
+        self.scans.retain(|s| s.name != scan.name);
+        self.scans.insert(0, scan);
+        self.scans.truncate(MAX_SAVED);
+        self.dirty = true;
+    }
+
+    /// Write the saved scans if they changed since the last save.
+    pub fn save_if_dirty(&mut self) {
+        if !self.dirty {
+            return;
+        }
+        let file = RangeScansFile { scans: self.scans.clone() };
+        if let Ok(json) = serde_json::to_string_pretty(&file) {
+            let _ = std::fs::write(&self.path, json);
+        }
+        self.dirty = false;
+    }
+}
+
+// ─── Scan pipeline ───────────────────────────────────────────────────────────
+
+#[allow(clippy::too_many_arguments)]
+fn run_scan(
+    name: String,
+    spec: String,
+    targets: &[Ipv4Addr],
+    limits: ScanLimits,
+    ctx: &ScanContext,
+    progress: &(AtomicUsize, AtomicUsize),
+    phase: &AtomicU8,
+    cancel: &AtomicBool,
+) -> RangeScan {
+    let started = Local::now();
+    let batch = limits.concurrency.max(1);
+    let local_ip = ctx.local.map(|(ip, _)| ip);
+    let on_link = |ip: &Ipv4Addr| {
+        ctx.local.is_some_and(|(local, mask)| u32::from(*ip) & u32::from(mask) == u32::from(local) & u32::from(mask))
+    };
+
+    // ═══ Phase 1: unicast discovery, one batch at a time ═════════════════
+    phase.store(PHASE_PROBE, Ordering::Relaxed);
+    progress.0.store(0, Ordering::Relaxed);
+    progress.1.store(targets.len(), Ordering::Relaxed);
+    let mut hits: Vec<ProbeHit> = Vec::new();
+    let mut probed = 0;
+    for chunk in targets.chunks(batch) {
+        if cancel.load(Ordering::SeqCst) {
+            break;
+        }
+        let linked: Vec<Ipv4Addr> = chunk.iter().copied().filter(on_link).collect();
+        hits.extend(probes::arp_cache_read(chunk));
+        let methods: [&dyn Fn() -> Vec<ProbeHit>; 5] = [
+            &|| match local_ip {
+                Some(ip) if !linked.is_empty() => probes::arp_scan(&linked, ip),
+                _ => Vec::new(),
+            },
+            &|| probes::icmp_ping_sweep(chunk),
+            &|| probes::tcp_connect_probe(chunk),
+            &|| probes::netbios_scan(chunk),
+            &|| probes::dns_reverse_scan(chunk),
+        ];
+        for (i, method) in methods.iter().enumerate() {
+            if i > 0 && !limits.host_interval.is_zero() {
+                thread::sleep(limits.host_interval);
+            }
+            hits.extend(method());
+        }
+        probed += chunk.len();
+        progress.0.store(probed, Ordering::Relaxed);
+    }
+    let merged = probes::merge_hits(hits, local_ip.unwrap_or(Ipv4Addr::UNSPECIFIED));
+
+    // ═══ Phase 2: hostnames, ports and services of the hosts that answered ═
+    phase.store(PHASE_RESOLVE, Ordering::Relaxed);
+    progress.0.store(0, Ordering::Relaxed);
+    progress.1.store(merged.len(), Ordering::Relaxed);
+    let ips: Vec<Ipv4Addr> = merged.iter().map(|h| h.ip).collect();
+    let mut resolved = HashMap::new();
+    for chunk in ips.chunks(batch) {
+        let polled = super::snmp::poll_batch(chunk, &ctx.communities, Duration::from_millis(700));
+        resolved.extend(super::hostnames::resolve_unicast(chunk, &ctx.ports, &polled));
+        progress.0.fetch_add(chunk.len(), Ordering::Relaxed);
+    }
+
+    let hosts = merged
+        .into_iter()
+        .map(|h| {
+            let r = resolved.remove(&h.ip);
+            let hostname = r.as_ref()
+                .map(|r| r.hostname.clone())
+                .filter(|n| !n.is_empty())
+                .or(h.hostname);
+            ScannedHost {
+                ip: h.ip,
+                vendor: h.mac.as_deref().and_then(super::scanner::mac_vendor),
+                mac: h.mac,
+                hostname,
+                methods: h.methods.iter().map(|m| m.to_string()).collect(),
+                open_ports: r.as_ref().map(|r| super::hostnames::format_ports(&r.open_ports)).unwrap_or_default(),
+                services: r.map(|r| r.services).unwrap_or_default(),
+            }
+        })
+        .collect();
+
+    RangeScan {
+        name,
+        targets: spec,
+        probed,
+        cancelled: probed < targets.len(),
+        started,
+        finished: Local::now(),
+        hosts,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cidr_range_and_list() {
+        let t = parse_targets("10.20.0.0/22").unwrap();
+        assert_eq!(t.len(), 1022);
+        assert_eq!(t[0], Ipv4Addr::new(10, 20, 0, 1));
+        assert_eq!(t[1021], Ipv4Addr::new(10, 20, 3, 254));
+
+        let t = parse_targets("192.168.5.10-50").unwrap();
+        assert_eq!(t.len(), 41);
+        assert_eq!(t[40], Ipv4Addr::new(192, 168, 5, 50));
+
+        let t = parse_targets("10.0.0.250-10.0.1.5, 10.0.0.1 10.0.0.252").unwrap();
+        assert_eq!(t.len(), 13);
+        assert_eq!(t[5], Ipv4Addr::new(10, 0, 0, 255));
+        assert_eq!(t[12], Ipv4Addr::new(10, 0, 0, 1));
+
+        assert_eq!(parse_targets("10.1.1.7/32").unwrap(), vec![Ipv4Addr::new(10, 1, 1, 7)]);
+        assert_eq!(parse_targets("10.1.1.6/31").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_targets() {
+        assert!(parse_targets("").is_err());
+        assert!(parse_targets("10.0.0.0/33").is_err());
+        assert!(parse_targets("192.168.5.50-10").is_err());
+        assert!(parse_targets("printer.local").is_err());
+        assert!(parse_targets("10.0.0.0/8").is_err());
+        assert!(parse_targets("10.0.0.0/16").is_ok());
+    }
+
+    #[test]
+    fn test_named_scans_replace_older_runs() {
+        assert_eq!(split_name("office: 10.20.0.0/22"), ("office".to_string(), "10.20.0.0/22".to_string()));
+        assert_eq!(split_name("10.20.0.0/22"), ("10.20.0.0/22".to_string(), "10.20.0.0/22".to_string()));
+
+        let mut rs = RangeScanner::new(Vec::new(), PathBuf::new());
+        let scan = |name: &str, probed| RangeScan {
+            name: name.to_string(),
+            targets: "10.0.0.0/30".to_string(),
+            probed,
+            cancelled: false,
+            started: Local::now(),
+            finished: Local::now(),
+            hosts: Vec::new(),
+        };
+        rs.store(scan("office", 2));
+        rs.store(scan("lab", 2));
+        rs.store(scan("office", 1));
+        let names: Vec<(&str, usize)> = rs.scans.iter().map(|s| (s.name.as_str(), s.probed)).collect();
+        assert_eq!(names, vec![("office", 1), ("lab", 2)]);
+    }
+}
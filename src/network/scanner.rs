@@ -7,15 +7,23 @@
 
 use std::collections::{HashMap, HashSet};
 use std::net::{IpAddr, Ipv4Addr};
-use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use chrono::Local;
 
-use crate::types::LanDevice;
+use crate::types::{DeviceService, DhcpFingerprint, LanDevice};
+
+use super::allowlist::Allowlist;
+use super::dhcp_servers::{DhcpReply, DhcpServers};
+use super::dnssd::DnsSdCache;
+use super::inventory::DeviceInventory;
+use super::presence::{DeviceOffline, PresenceWatch, ScheduleConfig};
+use super::range_scan::{RangeScanner, ScanContext, ScanLimits};
+use super::snmp::SnmpInfo;
+use super::wol::WakeWatch;
 
 /// Scan phases for UI display.
 pub const SCAN_PHASE_IDLE: u8 = 0;
@@ -219,12 +227,14 @@ struct DeviceUpdate {
     hostname: Option<String>,
     discovery_info: String,
     open_ports: String,
+    hints: Vec<String>,
+    services: Vec<DeviceService>,
 }
 
 // ─── Scanner state ───────────────────────────────────────────────────────────
 
 pub struct NetworkScanner {
-    /// Known devices keyed by IP (authoritative list).
+    /// Live devices plus offline ones from the inventory for this network.
     pub devices: Vec<LanDevice>,
     /// Streaming pending buffer — background threads push updates here.
     pending: Arc<Mutex<Vec<DeviceUpdate>>>,
@@ -232,8 +242,8 @@ pub struct NetworkScanner {
     scanning: Arc<AtomicBool>,
     /// Track previous scanning state to detect scan completion.
     was_scanning: bool,
-    /// IPs seen during the current scan (for offline marking).
-    scan_seen_ips: HashSet<IpAddr>,
+    /// Devices seen during the current scan, by MAC (IP when unknown), for offline marking.
+    scan_seen: HashSet<String>,
     /// Scan progress: (probed, total) shared with background thread.
     scan_progress: Arc<(AtomicUsize, AtomicUsize)>,
     /// Current scan phase (0=idle, 1=ARP, 2=DNS).
@@ -244,14 +254,29 @@ pub struct NetworkScanner {
     pub local_ip: Option<Ipv4Addr>,
     pub gateway: Option<Ipv4Addr>,
     pub subnet_mask: Option<Ipv4Addr>,
-    /// Scan interval tracking.
-    scan_tick: u32,
-    /// User-assigned labels: MAC → display name.
-    pub custom_labels: HashMap<String, String>,
-    /// Persistence path for labels.
-    labels_path: PathBuf,
+    /// When the last scheduled scan was started.
+    last_scan_start: Option<Instant>,
+    /// Background scan interval and offline alert threshold.
+    pub schedule: ScheduleConfig,
+    presence_watch: PresenceWatch,
+    /// Devices newly offline past the alert threshold, drained by the app.
+    pub new_offline: Vec<DeviceOffline>,
+    /// Every device seen on any network, persisted between sessions.
+    pub inventory: DeviceInventory,
+    /// MACs trusted on each network.
+    pub allowlist: Allowlist,
+    /// DHCP servers heard on each network and the leases they hand out.
+    pub dhcp_servers: DhcpServers,
+    /// Scans of typed target ranges, saved by name.
+    pub range_scans: RangeScanner,
+    /// Devices sent a Wake-on-LAN packet, awaiting their return.
+    pub wake_watch: WakeWatch,
     /// DHCP hostname cache: IP → hostname (fed from sniffer DHCP packets).
     pub dhcp_hostnames: Arc<Mutex<HashMap<Ipv4Addr, String>>>,
+    /// SNMP inventory of devices that answered a configured community.
+    pub snmp: Arc<Mutex<HashMap<Ipv4Addr, SnmpInfo>>>,
+    /// DNS-SD services advertised on the LAN, refreshed by every scan.
+    pub dnssd: Arc<Mutex<DnsSdCache>>,
 }
 
 impl NetworkScanner {
@@ -260,34 +285,28 @@ impl NetworkScanner {
             .map(|(ip, mask, gw)| (Some(ip), Some(mask), Some(gw)))
             .unwrap_or((None, None, None));
 
-        let labels_path = Self::labels_path();
-        let custom_labels = Self::load_labels(&labels_path);
+        let inventory = DeviceInventory::load();
 
         // Instant seed: read OS ARP cache (zero network traffic, sub-millisecond)
-        let now = Local::now().time();
         let mut devices = Vec::new();
         if let (Some(ip), Some(mask)) = (local_ip, subnet_mask) {
             let cached = arp_cache_read(ip, mask);
             for (cached_ip, mac) in cached {
-                devices.push(LanDevice {
-                    ip: IpAddr::V4(cached_ip),
-                    mac: mac.clone(),
-                    hostname: None,
+                let mut device = LanDevice {
                     vendor: mac_vendor(&mac),
-                    first_seen: now,
-                    last_seen: now,
-                    is_online: true,
-                    custom_name: custom_labels.get(&mac).cloned(),
-                    discovery_info: String::new(),
-                    open_ports: String::new(),
-                    bytes_sent: 0,
-                    bytes_received: 0,
-                    tick_sent: 0,
-                    tick_received: 0,
-                    speed_sent: 0.0,
-                    speed_received: 0.0,
-                });
+                    ..LanDevice::new(IpAddr::V4(cached_ip), mac.clone())
+                };
+                inventory.apply(&mut device);
+                devices.push(device);
             }
+            // Everything seen on this network in earlier sessions, offline until a scan finds it
+            let present: HashSet<&str> = devices.iter().map(|d| d.mac.as_str()).collect();
+            let history = inventory.history(&network_id(ip, mask), &present);
+            devices.extend(history);
+        }
+        for device in &mut devices {
+            let is_gateway = gateway.is_some_and(|gw| device.ip == IpAddr::V4(gw));
+            super::device_type::apply(device, is_gateway);
         }
 
         Self {
@@ -295,17 +314,25 @@ impl NetworkScanner {
             pending: Arc::new(Mutex::new(Vec::new())),
             scanning: Arc::new(AtomicBool::new(false)),
             was_scanning: false,
-            scan_seen_ips: HashSet::new(),
+            scan_seen: HashSet::new(),
             scan_progress: Arc::new((AtomicUsize::new(0), AtomicUsize::new(0))),
             scan_phase: Arc::new(AtomicU8::new(SCAN_PHASE_IDLE)),
             last_scan: None,
             local_ip,
             gateway,
             subnet_mask,
-            scan_tick: 0,
-            custom_labels,
-            labels_path,
+            last_scan_start: None,
+            schedule: ScheduleConfig::new(),
+            presence_watch: PresenceWatch::default(),
+            new_offline: Vec::new(),
+            inventory,
+            allowlist: Allowlist::load(),
+            dhcp_servers: DhcpServers::load(),
+            range_scans: RangeScanner::load(),
+            wake_watch: WakeWatch::default(),
             dhcp_hostnames: Arc::new(Mutex::new(HashMap::new())),
+            snmp: Arc::new(Mutex::new(HashMap::new())),
+            dnssd: Arc::new(Mutex::new(DnsSdCache::default())),
         }
     }
 
@@ -325,6 +352,13 @@ impl NetworkScanner {
     ///
     /// **Phase 2 — Hostname enrichment** via 12+ parallel resolution methods.
     pub fn start_scan(&self) {
+        self.spawn_scan(true);
+    }
+
+    /// Run a scan in the background. Without `refingerprint`, only ports that
+    /// were not open on the previous scan get service probes; the frequent
+    /// Devices-tab rescans use that so they stay down to connect checks.
+    fn spawn_scan(&self, refingerprint: bool) {
         if self.scanning.swap(true, Ordering::SeqCst) {
             return;
         }
@@ -343,6 +377,23 @@ impl NetworkScanner {
         let phase = Arc::clone(&self.scan_phase);
         let gateway = self.gateway;
         let dhcp_hostnames = Arc::clone(&self.dhcp_hostnames);
+        let scan_ports = self.schedule.schedule.scan_ports.clone();
+        let communities = self.schedule.schedule.snmp_communities.clone();
+        let snmp = Arc::clone(&self.snmp);
+        let dnssd = Arc::clone(&self.dnssd);
+
+        // Ports whose services the previous scan already identified
+        let fingerprinted: HashMap<Ipv4Addr, Vec<u16>> = if refingerprint {
+            HashMap::new()
+        } else {
+            self.devices.iter()
+                .filter(|d| d.is_online)
+                .filter_map(|d| match d.ip {
+                    IpAddr::V4(v4) => Some((v4, super::device_type::parse_ports(&d.open_ports))),
+                    IpAddr::V6(_) => None,
+                })
+                .collect()
+        };
 
         // Snapshot known hostnames to skip redundant resolution
         let known_hostnames: HashMap<Ipv4Addr, String> = self.devices.iter()
@@ -392,6 +443,8 @@ impl NetworkScanner {
                         hostname: hit.hostname.clone().or_else(|| known_ref.get(&hit.ip).cloned()),
                         discovery_info: hit.method.to_string(),
                         open_ports: String::new(),
+                        hints: Vec::new(),
+                        services: Vec::new(),
                     });
                 }
                 drop(disc);
@@ -474,9 +527,19 @@ impl NetworkScanner {
             progress.0.store(0, Ordering::Relaxed);
             progress.1.store(need_resolve.len(), Ordering::Relaxed);
 
-            // Multi-method parallel hostname resolution (13+ methods)
+            // SNMP inventory first; its sysNames feed the multi-method
+            // parallel hostname resolution (13+ methods)
             let dhcp_snap = dhcp_hostnames.lock().map(|h| h.clone()).unwrap_or_default();
-            let resolved_map = super::hostnames::resolve_all(&need_resolve, gateway, &dhcp_snap);
+            let polled = super::snmp::poll_batch(&need_resolve, &communities, Duration::from_millis(700));
+            let resolved_map = super::hostnames::resolve_all(&need_resolve, gateway, &dhcp_snap, &scan_ports, &fingerprinted, &polled, &dnssd);
+            if let Ok(mut map) = snmp.lock() {
+                for (ip, mut info) in polled {
+                    if let Some(prev) = map.get(&ip) {
+                        info.carry_rates(prev);
+                    }
+                    map.insert(ip, info);
+                }
+            }
             progress.0.store(need_resolve.len(), Ordering::Relaxed);
 
             // Stream resolved hostnames to pending buffer
@@ -497,6 +560,8 @@ impl NetworkScanner {
                             hostname,
                             discovery_info: resolved.details.clone(),
                             open_ports: ports_str,
+                            hints: resolved.hints.clone(),
+                            services: resolved.services.clone(),
                         }
                     })
                 }).collect();
@@ -542,21 +607,23 @@ impl NetworkScanner {
         }
 
         let prev_devices = self.devices.clone();
-        let now = Local::now().time();
+        let now = Local::now();
+        let network = self.network_id();
 
         // Merge each update into device list
         for update in batch {
             let ip_addr = IpAddr::V4(update.ip);
-            self.scan_seen_ips.insert(ip_addr);
 
-            if let Some(existing) = self.devices.iter_mut().find(|d| d.ip == ip_addr) {
-                if existing.mac != update.mac && !update.mac.is_empty() {
+            if let Some(idx) = self.find_device(ip_addr, &update.mac) {
+                let existing = &mut self.devices[idx];
+                if existing.mac.is_empty() && !update.mac.is_empty() {
                     existing.vendor = mac_vendor(&update.mac);
                     existing.mac = update.mac.clone();
+                    self.inventory.apply(existing);
                 }
+                existing.ip = ip_addr;
                 existing.last_seen = now;
                 existing.is_online = true;
-                existing.custom_name = self.custom_labels.get(&existing.mac).cloned();
                 if let Some(ref new_name) = update.hostname {
                     if let Some(ref mut old_name) = existing.hostname {
                         // Merge: add any new comma-separated parts not already present
@@ -611,29 +678,34 @@ impl NetworkScanner {
                         }
                     }
                 }
+                for hint in update.hints {
+                    if !existing.hints.contains(&hint) {
+                        existing.hints.push(hint);
+                    }
+                }
+                super::device_services::merge(&mut existing.services, &update.services);
+                super::device_type::apply(existing, Some(update.ip) == self.gateway);
+                self.scan_seen.insert(device_key(existing));
+                self.inventory.record(existing, &network);
             } else {
                 // Skip ghost entries: no MAC and no hostname = unresponsive IP
                 if update.mac.is_empty() && update.hostname.is_none() {
                     continue;
                 }
-                self.devices.push(LanDevice {
-                    ip: ip_addr,
-                    mac: update.mac.clone(),
+                let mut device = LanDevice {
                     hostname: update.hostname,
                     vendor: mac_vendor(&update.mac),
-                    first_seen: now,
-                    last_seen: now,
-                    is_online: true,
-                    custom_name: self.custom_labels.get(&update.mac).cloned(),
                     discovery_info: update.discovery_info,
                     open_ports: update.open_ports,
-                    bytes_sent: 0,
-                    bytes_received: 0,
-                    tick_sent: 0,
-                    tick_received: 0,
-                    speed_sent: 0.0,
-                    speed_received: 0.0,
-                });
+                    hints: update.hints,
+                    services: update.services,
+                    ..LanDevice::new(ip_addr, update.mac.clone())
+                };
+                self.inventory.apply(&mut device);
+                super::device_type::apply(&mut device, Some(update.ip) == self.gateway);
+                self.scan_seen.insert(device_key(&device));
+                self.inventory.record(&device, &network);
+                self.devices.push(device);
             }
         }
 
@@ -641,12 +713,18 @@ impl NetworkScanner {
         if scan_just_completed {
             self.was_scanning = false;
             self.last_scan = Some(Instant::now());
+            let gap = self.schedule.schedule.presence_gap();
             for device in &mut self.devices {
-                if !self.scan_seen_ips.contains(&device.ip) {
+                if self.scan_seen.contains(&device_key(device)) {
+                    self.inventory.mark_present(&device.mac, now, gap);
+                } else {
                     device.is_online = false;
                 }
             }
-            self.scan_seen_ips.clear();
+            self.scan_seen.clear();
+            let threshold = self.schedule.schedule.offline_alert_mins;
+            let offline = self.presence_watch.check(&self.devices, now, threshold);
+            self.new_offline.extend(offline);
         }
 
         if has_updates || scan_just_completed {
@@ -656,12 +734,20 @@ impl NetworkScanner {
         }
     }
 
-    /// Called each tick. Auto-triggers scan every N ticks.
-    pub fn tick(&mut self) {
-        self.scan_tick += 1;
-        // Scan every 15 ticks (~15 seconds) for faster device discovery
-        if self.scan_tick % 15 == 1 {
-            self.start_scan();
+    /// Called each tick. Starts a scan every 15 seconds while the Devices
+    /// tab is open or a woken device is awaited, otherwise on the configured
+    /// schedule. The 15-second scans fingerprint only newly opened ports.
+    pub fn tick(&mut self, on_devices_tab: bool) {
+        self.schedule.reload_if_changed();
+        let quick = on_devices_tab || self.wake_watch.has_pending();
+        let interval = if quick {
+            Duration::from_secs(15)
+        } else {
+            self.schedule.schedule.interval()
+        };
+        if self.last_scan_start.is_none_or(|t| t.elapsed() >= interval) && !self.is_scanning() {
+            self.last_scan_start = Some(Instant::now());
+            self.spawn_scan(!quick);
         }
     }
 
@@ -683,51 +769,136 @@ impl NetworkScanner {
         self.scan_phase.load(Ordering::Relaxed)
     }
 
-    fn labels_path() -> PathBuf {
-        if let Some(data_dir) = dirs::data_dir() {
-            let dir = data_dir.join("psnet");
-            let _ = std::fs::create_dir_all(&dir);
-            dir.join("device_labels.json")
-        } else {
-            PathBuf::from("psnet_device_labels.json")
-        }
-    }
-
-    fn load_labels(path: &PathBuf) -> HashMap<String, String> {
-        match std::fs::read_to_string(path) {
-            Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
-            Err(_) => HashMap::new(),
+    /// Identifier of the local network in the inventory: its subnet in CIDR form.
+    pub fn network_id(&self) -> String {
+        match (self.local_ip, self.subnet_mask) {
+            (Some(ip), Some(mask)) => network_id(ip, mask),
+            _ => "unknown".to_string(),
         }
     }
 
-    fn save_labels(&self) {
-        if let Ok(json) = serde_json::to_string_pretty(&self.custom_labels) {
-            let _ = std::fs::write(&self.labels_path, json);
+    /// Match a scan result to a known device: by MAC when the result has one,
+    /// otherwise by IP, preferring the device that is online or was seen last.
+    fn find_device(&self, ip: IpAddr, mac: &str) -> Option<usize> {
+        if !mac.is_empty() {
+            if let Some(i) = self.devices.iter().position(|d| d.mac == mac) {
+                return Some(i);
+            }
         }
+        self.devices
+            .iter()
+            .enumerate()
+            .filter(|(_, d)| d.ip == ip && (mac.is_empty() || d.mac.is_empty()))
+            .max_by_key(|(_, d)| (d.is_online, d.last_seen))
+            .map(|(i, _)| i)
     }
 
     /// Set a custom label for a device (by MAC address).
     pub fn set_label(&mut self, mac: &str, label: String) {
-        if label.is_empty() {
-            self.custom_labels.remove(mac);
-        } else {
-            self.custom_labels.insert(mac.to_string(), label);
-        }
-        self.save_labels();
+        self.inventory.set_name(mac, &label);
+        let name = if label.is_empty() { None } else { Some(label) };
         // Apply immediately to in-memory devices
         for device in &mut self.devices {
             if device.mac == mac {
-                device.custom_name = self.custom_labels.get(mac).cloned();
+                device.custom_name = name.clone();
             }
         }
     }
 
+    /// Whether a MAC is on the allowlist of the current network.
+    pub fn is_trusted(&self, mac: &str) -> bool {
+        !mac.is_empty() && self.allowlist.is_trusted(&self.network_id(), mac)
+    }
+
+    /// Trust or untrust a device on the current network. Returns whether it
+    /// is trusted afterwards.
+    pub fn toggle_trusted(&mut self, mac: &str, name: Option<&str>) -> bool {
+        let network = self.network_id();
+        self.allowlist.toggle(&network, mac, name)
+    }
+
+    /// Send a Wake-on-LAN packet to a device on the local network, with its
+    /// SecureOn password if one is set, and watch the next scans for it. Scans
+    /// run every 15 seconds until it shows up or the wake times out.
+    pub fn wake(&mut self, mac: &str, name: &str) -> Result<(), String> {
+        let target = super::wol::parse_mac(mac).ok_or_else(|| format!("no usable MAC ({})", mac))?;
+        let password = match self.inventory.get(mac).and_then(|r| r.wol_password.as_deref()) {
+            Some(pw) => Some(super::wol::parse_mac(pw).ok_or("SecureOn password must look like a MAC")?),
+            None => None,
+        };
+        let (Some(local_ip), Some(mask)) = (self.local_ip, self.subnet_mask) else {
+            return Err("local network not detected".to_string());
+        };
+        super::wol::send_magic_packet(&target, password.as_ref(), local_ip, mask).map_err(|e| e.to_string())?;
+        self.wake_watch.add(mac, name, Local::now());
+        Ok(())
+    }
+
+    /// Record a captured DHCP OFFER / ACK from `source` against the current
+    /// network. The gateway and the servers configured in `devices.json` are
+    /// the expected ones. The capture carries no MAC, so the sender's is
+    /// looked up by IP in the device list.
+    pub fn record_dhcp_reply(&mut self, reply: &DhcpReply, source: Ipv4Addr) {
+        let network = self.network_id();
+        let mut expected: Vec<Ipv4Addr> = self.gateway.into_iter().collect();
+        expected.extend(self.schedule.schedule.dhcp_servers.iter().copied());
+        let source_device_mac = self.devices.iter()
+            .find(|d| d.ip == IpAddr::V4(source) && d.is_online && !d.mac.is_empty())
+            .map(|d| d.mac.clone());
+        self.dhcp_servers.record(&network, reply, source, source_device_mac.as_deref(), &expected, Local::now());
+    }
+
+    /// Scan a typed target list (`name: 10.20.0.0/22, 192.168.5.10-50`) with
+    /// the concurrency and pacing from `devices.json`.
+    pub fn start_range_scan(&mut self, input: &str) -> Result<String, String> {
+        let schedule = &self.schedule.schedule;
+        let limits = ScanLimits {
+            concurrency: schedule.range_scan_concurrency,
+            host_interval: Duration::from_millis(schedule.range_scan_host_interval_ms),
+        };
+        let ctx = ScanContext {
+            local: self.local_ip.zip(self.subnet_mask),
+            ports: schedule.scan_ports.clone(),
+            communities: schedule.snmp_communities.clone(),
+        };
+        self.range_scans.start(input, limits, ctx)
+    }
+
+    /// Attach a fingerprint from a captured DHCP request to its device, and
+    /// keep it in the inventory for when a scan finds the device later.
+    pub fn record_dhcp(&mut self, mac: &str, fingerprint: DhcpFingerprint) {
+        self.inventory.set_dhcp(mac, &fingerprint);
+        let gateway = self.gateway.map(IpAddr::V4);
+        for device in self.devices.iter_mut().filter(|d| d.mac == mac) {
+            device.dhcp = Some(fingerprint.clone());
+            super::device_type::apply(device, Some(device.ip) == gateway);
+        }
+    }
+
+    /// Look every vendor up again after the OUI database changed.
+    pub fn refresh_vendors(&mut self) {
+        for device in self.devices.iter_mut().filter(|d| !d.mac.is_empty()) {
+            device.vendor = mac_vendor(&device.mac);
+            super::device_type::apply(device, Some(device.ip) == self.gateway.map(IpAddr::V4));
+        }
+    }
+
     /// Online device count.
     pub fn online_count(&self) -> usize {
         self.devices.iter().filter(|d| d.is_online).count()
     }
 }
 
+fn network_id(ip: Ipv4Addr, mask: Ipv4Addr) -> String {
+    let net = Ipv4Addr::from(u32::from(ip) & u32::from(mask));
+    format!("{}/{}", net, u32::from(mask).count_ones())
+}
+
+/// Identity of a device within one scan.
+fn device_key(d: &LanDevice) -> String {
+    if d.mac.is_empty() { d.ip.to_string() } else { d.mac.clone() }
+}
+
 // ─── MAC vendor lookup ──────────────────────────────────────────────────────
 
 /// Check if a MAC address has the locally-administered bit set (bit 1 of octet 0).
@@ -784,9 +955,9 @@ fn local_admin_vendor(mac: &str) -> Option<&'static str> {
 }
 
 pub(crate) fn mac_vendor(mac: &str) -> Option<String> {
-    // 1. Standard IEEE OUI lookup (39,000+ vendors)
+    // 1. IEEE registry lookup, longest prefix first (39,000+ vendors)
     if let Some(vendor) = super::oui::lookup(mac) {
-        return Some(vendor.to_string());
+        return Some(vendor);
     }
 
     // 2. Known locally-administered prefixes (Docker, VMs, VPNs, etc.)
@@ -0,0 +1,381 @@
+//! Offline end-of-life and vulnerability matching for detected servers.
+//!
+//! `data/advisories.json` (embedded at compile time) lists, per product, the
+//! release cycles that are past end-of-life and notable CVEs with the version
+//! ranges they affect. A product is matched by name against the server kind and
+//! against every detected technology, so a PHP version seen in `X-Powered-By`
+//! is checked as well as the server itself.
+//!
+//! The dataset can be extended from `advisories.json` in the config dir (see
+//! `overrides`); `disable` takes product names or advisory ids.
+//! `scripts/sync_eol.py` refreshes the EOL dates from endoflife.date.
+
+use std::cmp::Ordering;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::SystemTime;
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+use crate::config;
+use crate::types::AlertSeverity;
+
+use super::overrides::{self, OverrideReport};
+use super::types::ListeningPort;
+
+// ─── Dataset ────────────────────────────────────────────────────────────────
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Product {
+    pub product: String,
+    /// Server kind names (as in fingerprints.json) and technology names that
+    /// identify this product, compared case-insensitively.
+    pub names: Vec<String>,
+    #[serde(default)]
+    pub eol: Vec<EolCycle>,
+    #[serde(default)]
+    pub vulns: Vec<Vuln>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct EolCycle {
+    /// Leading version components, e.g. "6.0" or "13".
+    pub cycle: String,
+    /// Date the cycle stopped receiving fixes (YYYY-MM-DD).
+    pub eol: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Vuln {
+    pub id: String,
+    /// critical / high / medium / low
+    pub severity: String,
+    pub summary: String,
+    pub ranges: Vec<VersionRange>,
+}
+
+/// Affected versions: `introduced <= v < fixed`. A missing bound is open.
+#[derive(Clone, Debug, Deserialize)]
+pub struct VersionRange {
+    #[serde(default)]
+    pub introduced: Option<String>,
+    #[serde(default)]
+    pub fixed: Option<String>,
+}
+
+static ADVISORIES_JSON: &str = include_str!("../../../data/advisories.json");
+
+const OVERRIDE_FILE: &str = "advisories.json";
+
+static ADVISORIES: RwLock<Option<Arc<Vec<Product>>>> = RwLock::new(None);
+
+static LOADED_STAMP: Mutex<Option<Option<SystemTime>>> = Mutex::new(None);
+
+fn products() -> Arc<Vec<Product>> {
+    if let Some(db) = ADVISORIES.read().ok().and_then(|g| g.clone()) {
+        return db;
+    }
+    reload();
+    ADVISORIES.read().ok().and_then(|g| g.clone()).unwrap_or_default()
+}
+
+/// Rebuild the dataset from the embedded JSON and the override file.
+pub fn reload() -> OverrideReport {
+    let path = config::path(OVERRIDE_FILE);
+    let stamp = config::modified(&path);
+    let builtin = load_products(ADVISORIES_JSON);
+    let (db, report) = match overrides::read_override(&path) {
+        Ok(file) => merge(builtin, &file),
+        Err(e) => (builtin, OverrideReport { error: Some(e), ..Default::default() }),
+    };
+    if let Ok(mut g) = ADVISORIES.write() {
+        *g = Some(Arc::new(db));
+    }
+    if let Ok(mut s) = LOADED_STAMP.lock() {
+        *s = Some(stamp);
+    }
+    report
+}
+
+/// Reload if the override file was created, edited or removed since the last load.
+pub fn reload_if_changed() -> Option<OverrideReport> {
+    let stamp = config::modified(&config::path(OVERRIDE_FILE));
+    let loaded = LOADED_STAMP.lock().ok().and_then(|s| *s);
+    match loaded {
+        Some(prev) if prev == stamp => None,
+        None if stamp.is_none() => None,
+        _ => Some(reload()),
+    }
+}
+
+/// User products replace built-ins of the same name; `disable` drops whole
+/// products or single advisories by id.
+fn merge(builtin: Vec<Product>, file: &overrides::OverrideFile) -> (Vec<Product>, OverrideReport) {
+    let mut report = OverrideReport::default();
+    let user: Vec<Product> = file
+        .entries
+        .iter()
+        .filter_map(|v| serde_json::from_value(v.clone()).ok())
+        .collect();
+    report.added = user.len();
+    report.skipped = file.entries.len() - user.len();
+
+    let disabled = |name: &str| file.disable.iter().any(|d| d.eq_ignore_ascii_case(name));
+    let mut db = user;
+    for mut p in builtin {
+        if disabled(&p.product) || db.iter().any(|u| u.product.eq_ignore_ascii_case(&p.product)) {
+            report.disabled += 1;
+            continue;
+        }
+        let before = p.vulns.len();
+        p.vulns.retain(|v| !disabled(&v.id));
+        report.disabled += before - p.vulns.len();
+        db.push(p);
+    }
+    (db, report)
+}
+
+fn load_products(json: &str) -> Vec<Product> {
+    serde_json::from_str(json).expect("advisories.json: invalid JSON")
+}
+
+// ─── Matching ───────────────────────────────────────────────────────────────
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AdvisoryKind {
+    EndOfLife { date: String },
+    Vulnerability { id: String, fixed: Option<String> },
+}
+
+/// One advisory that applies to a running version.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Advisory {
+    pub product: String,
+    pub version: String,
+    pub kind: AdvisoryKind,
+    pub severity: AlertSeverity,
+    pub summary: String,
+}
+
+impl Advisory {
+    pub fn is_eol(&self) -> bool {
+        matches!(self.kind, AdvisoryKind::EndOfLife { .. })
+    }
+
+    /// Short heading: the CVE id, or "End of life".
+    pub fn title(&self) -> String {
+        match &self.kind {
+            AdvisoryKind::EndOfLife { .. } => "End of life".to_string(),
+            AdvisoryKind::Vulnerability { id, .. } => id.clone(),
+        }
+    }
+
+    /// One-line explanation including the upgrade target, if known.
+    pub fn detail(&self) -> String {
+        match &self.kind {
+            AdvisoryKind::EndOfLife { date } => {
+                format!("{} {} has received no fixes since {}", self.product, self.version, date)
+            }
+            AdvisoryKind::Vulnerability { fixed: Some(f), .. } => format!("{} (fixed in {})", self.summary, f),
+            AdvisoryKind::Vulnerability { fixed: None, .. } => self.summary.clone(),
+        }
+    }
+}
+
+/// Advisories for a server's own version and for every versioned technology
+/// detected on it, most severe first.
+pub fn check(server: &ListeningPort) -> Vec<Advisory> {
+    let db = products();
+    let today = chrono::Local::now().date_naive();
+    let kind_name = format!("{:?}", server.server_kind);
+
+    let mut out: Vec<Advisory> = Vec::new();
+    let mut candidates: Vec<(&str, &str)> = Vec::new();
+    if let Some(v) = server.version.as_deref() {
+        candidates.push((kind_name.as_str(), v));
+        candidates.push((server.server_kind.label(), v));
+    }
+    for t in server.detected_techs.iter().filter(|t| !t.version.is_empty()) {
+        candidates.push((t.name.as_str(), t.version.as_str()));
+    }
+
+    for (name, version) in candidates {
+        for a in check_in(&db, name, version, today) {
+            if !out.iter().any(|o| o.product == a.product && o.kind == a.kind) {
+                out.push(a);
+            }
+        }
+    }
+    out.sort_by(|a, b| b.severity.cmp(&a.severity).then(a.is_eol().cmp(&b.is_eol())));
+    out
+}
+
+fn check_in(db: &[Product], name: &str, version: &str, today: NaiveDate) -> Vec<Advisory> {
+    let parsed = parse_version(version);
+    if parsed.is_empty() {
+        return Vec::new();
+    }
+    let mut out = Vec::new();
+    for p in db.iter().filter(|p| p.names.iter().any(|n| n.eq_ignore_ascii_case(name))) {
+        for c in &p.eol {
+            let past = NaiveDate::parse_from_str(&c.eol, "%Y-%m-%d").map(|d| d <= today).unwrap_or(false);
+            if past && in_cycle(&parsed, &parse_version(&c.cycle)) {
+                out.push(Advisory {
+                    product: p.product.clone(),
+                    version: version.to_string(),
+                    kind: AdvisoryKind::EndOfLife { date: c.eol.clone() },
+                    severity: AlertSeverity::Warning,
+                    summary: format!("{} {} is end-of-life", p.product, c.cycle),
+                });
+            }
+        }
+        for v in &p.vulns {
+            if let Some(range) = v.ranges.iter().find(|r| in_range(&parsed, r)) {
+                out.push(Advisory {
+                    product: p.product.clone(),
+                    version: version.to_string(),
+                    kind: AdvisoryKind::Vulnerability { id: v.id.clone(), fixed: range.fixed.clone() },
+                    severity: severity_from_name(&v.severity),
+                    summary: v.summary.clone(),
+                });
+            }
+        }
+    }
+    out
+}
+
+fn severity_from_name(s: &str) -> AlertSeverity {
+    match s.to_ascii_lowercase().as_str() {
+        "critical" | "high" => AlertSeverity::Critical,
+        "medium" | "moderate" => AlertSeverity::Warning,
+        _ => AlertSeverity::Info,
+    }
+}
+
+/// Numeric components of a version string: "8.9p1" → [8, 9, 1],
+/// "1.25.3 (Ubuntu)" → [1, 25, 3], "8.0.35-0ubuntu0.22.04.1" → [8, 0, 35].
+/// Anything after the first space, '-', '+' or '(' is a distro suffix.
+fn parse_version(v: &str) -> Vec<u64> {
+    let core = v
+        .trim()
+        .trim_start_matches(|c: char| !c.is_ascii_digit())
+        .split([' ', '-', '+', '('])
+        .next()
+        .unwrap_or("");
+    core.split(|c: char| !c.is_ascii_digit())
+        .filter(|s| !s.is_empty())
+        .filter_map(|s| s.parse().ok())
+        .collect()
+}
+
+/// Compare two versions, treating missing components as 0 (so 9.8 == 9.8.0).
+fn cmp_versions(a: &[u64], b: &[u64]) -> Ordering {
+    for i in 0..a.len().max(b.len()) {
+        let (x, y) = (a.get(i).copied().unwrap_or(0), b.get(i).copied().unwrap_or(0));
+        match x.cmp(&y) {
+            Ordering::Equal => continue,
+            other => return other,
+        }
+    }
+    Ordering::Equal
+}
+
+fn in_cycle(version: &[u64], cycle: &[u64]) -> bool {
+    !cycle.is_empty() && version.len() >= cycle.len() && version[..cycle.len()] == *cycle
+}
+
+fn in_range(version: &[u64], r: &VersionRange) -> bool {
+    let after_start = r
+        .introduced
+        .as_deref()
+        .map(|i| cmp_versions(version, &parse_version(i)) != Ordering::Less)
+        .unwrap_or(true);
+    let before_fix = r
+        .fixed
+        .as_deref()
+        .map(|f| cmp_versions(version, &parse_version(f)) == Ordering::Less)
+        .unwrap_or(true);
+    after_start && before_fix
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_embedded_dataset_parses() {
+        let db = load_products(ADVISORIES_JSON);
+        assert!(db.len() >= 10);
+        for p in &db {
+            for c in &p.eol {
+                assert!(NaiveDate::parse_from_str(&c.eol, "%Y-%m-%d").is_ok(), "{} {}", p.product, c.cycle);
+            }
+            for v in &p.vulns {
+                assert!(!v.ranges.is_empty(), "{}", v.id);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_and_compare_versions() {
+        assert_eq!(parse_version("8.9p1"), vec![8, 9, 1]);
+        assert_eq!(parse_version("1.25.3 (Ubuntu)"), vec![1, 25, 3]);
+        assert_eq!(parse_version("8.0.35-0ubuntu0.22.04.1"), vec![8, 0, 35]);
+        assert_eq!(parse_version("v20.11.1"), vec![20, 11, 1]);
+        assert!(parse_version("unknown").is_empty());
+        assert_eq!(cmp_versions(&[9, 8], &[9, 8, 0]), Ordering::Equal);
+        assert_eq!(cmp_versions(&[9, 8, 1], &[9, 8]), Ordering::Greater);
+        assert!(in_cycle(&[6, 0, 16], &[6, 0]));
+        assert!(!in_cycle(&[6, 2, 1], &[6, 0]));
+    }
+
+    #[test]
+    fn test_redis_eol_and_cve() {
+        let db = load_products(ADVISORIES_JSON);
+        let found = check_in(&db, "redis", "6.0.16", day("2026-01-01"));
+        assert!(found.iter().any(|a| a.is_eol()));
+        assert!(found.iter().any(|a| a.title() == "CVE-2025-49844"));
+
+        // Patched release on a supported branch
+        let found = check_in(&db, "Redis", "7.4.6", day("2026-01-01"));
+        assert!(found.iter().all(|a| a.title() != "CVE-2025-49844"));
+        assert!(!found.iter().any(|a| a.is_eol()));
+
+        // EOL only once the date has passed
+        let found = check_in(&db, "Redis", "7.0.15", day("2024-01-01"));
+        assert!(!found.iter().any(|a| a.is_eol()));
+    }
+
+    #[test]
+    fn test_openssh_regresshion_range() {
+        let db = load_products(ADVISORIES_JSON);
+        let today = day("2026-01-01");
+        let ids = |v: &str| check_in(&db, "OpenSSH", v, today).iter().map(|a| a.title()).collect::<Vec<_>>();
+        assert!(ids("8.9p1").contains(&"CVE-2024-6387".to_string()));
+        assert!(!ids("9.8p1").contains(&"CVE-2024-6387".to_string()));
+        assert!(!ids("8.4p1").contains(&"CVE-2024-6387".to_string()));
+        assert!(ids("9.3p1").contains(&"CVE-2023-38408".to_string()));
+        assert!(!ids("9.3p2").contains(&"CVE-2023-38408".to_string()));
+    }
+
+    #[test]
+    fn test_override_replaces_and_disables() {
+        let builtin = load_products(ADVISORIES_JSON);
+        let count = builtin.len();
+        let file = overrides::parse_override(r#"{
+            "disable": ["jQuery", "CVE-2022-24834"],
+            "entries": [{ "product": "Caddy", "names": ["Caddy"], "eol": [{ "cycle": "1", "eol": "2020-09-30" }] }]
+        }"#).unwrap();
+        let (db, report) = merge(builtin, &file);
+        assert_eq!(report.added, 1);
+        assert_eq!(report.disabled, 2);
+        assert_eq!(db.len(), count);
+        assert!(check_in(&db, "jQuery", "3.4.1", day("2026-01-01")).is_empty());
+        assert!(check_in(&db, "Redis", "6.0.5", day("2026-01-01")).iter().all(|a| a.title() != "CVE-2022-24834"));
+        assert!(check_in(&db, "caddy", "1.0.4", day("2026-01-01")).iter().any(|a| a.is_eol()));
+    }
+}
@@ -0,0 +1,394 @@
+//! Listener baseline: the set of services this PC is expected to expose.
+//!
+//! The first full scan is recorded to `%APPDATA%\psnet\listener_baseline.json`.
+//! Later scans are diffed against it, keyed by (port, protocol, executable),
+//! so a new listener, a vanished one, or a service whose version or binary
+//! changed shows up as a [`ListenerChange`] until the user accepts the current
+//! state as the new baseline. Changes already alerted are saved alongside the
+//! baseline so a restart does not raise them again.
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+
+use super::types::ListeningPort;
+
+/// One listener as recorded in the baseline.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub port: u16,
+    /// "TCP" or "UDP".
+    pub proto: String,
+    /// Lowercased executable path, or process name when the path is unknown.
+    pub exe: String,
+    pub name: String,
+    #[serde(default)]
+    pub version: Option<String>,
+}
+
+impl BaselineEntry {
+    pub fn from_server(s: &ListeningPort) -> Self {
+        let exe = if s.exe_path.is_empty() { &s.process_name } else { &s.exe_path };
+        Self {
+            port: s.port,
+            proto: s.proto.label().to_string(),
+            exe: exe.to_lowercase(),
+            name: s.display_name(),
+            version: s.version.clone(),
+        }
+    }
+
+    fn key(&self) -> (u16, &str, &str) {
+        (self.port, self.proto.as_str(), self.exe.as_str())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    VersionChanged { old: String, new: String },
+    ExeChanged { old: String, new: String },
+}
+
+/// A difference between the live listeners and the baseline.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ListenerChange {
+    pub port: u16,
+    pub proto: String,
+    pub name: String,
+    /// Executable as keyed in the baseline (the new one for a changed binary).
+    pub exe: String,
+    pub kind: ChangeKind,
+}
+
+impl ListenerChange {
+    pub fn label(&self) -> &'static str {
+        match self.kind {
+            ChangeKind::Added => "NEW",
+            ChangeKind::Removed => "GONE",
+            ChangeKind::VersionChanged { .. } => "VERSION",
+            ChangeKind::ExeChanged { .. } => "BINARY",
+        }
+    }
+
+    pub fn color(&self) -> (u8, u8, u8) {
+        match self.kind {
+            ChangeKind::Added => (80, 220, 160),
+            ChangeKind::Removed => (150, 160, 180),
+            ChangeKind::VersionChanged { .. } => (220, 185, 60),
+            ChangeKind::ExeChanged { .. } => (255, 120, 80),
+        }
+    }
+
+    pub fn description(&self) -> String {
+        let what = format!("{} on {}/{}", self.name, self.proto.to_lowercase(), self.port);
+        match &self.kind {
+            ChangeKind::Added => format!("{} started listening", what),
+            ChangeKind::Removed => format!("{} stopped listening", what),
+            ChangeKind::VersionChanged { old, new } => format!("{} changed version {} → {}", what, old, new),
+            ChangeKind::ExeChanged { old, new } => format!("{} is now served by {} (was {})", what, new, old),
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct BaselineFile {
+    recorded: String,
+    listeners: Vec<BaselineEntry>,
+    #[serde(default)]
+    alerted: Vec<ListenerChange>,
+}
+
+pub struct ListenerBaseline {
+    pub entries: Vec<BaselineEntry>,
+    /// When the baseline was recorded; empty until the first full scan.
+    pub recorded: String,
+    /// Changes already raised as alerts, in this session or an earlier one.
+    alerted: Vec<ListenerChange>,
+    /// Set when the entries changed and have not been written yet.
+    dirty: bool,
+    path: PathBuf,
+}
+
+impl ListenerBaseline {
+    /// Load the saved baseline, or start empty if there is none.
+    pub fn load() -> Self {
+        let path = Self::default_path();
+        let file: BaselineFile = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default();
+        Self {
+            entries: file.listeners,
+            recorded: file.recorded,
+            alerted: file.alerted,
+            dirty: false,
+            path,
+        }
+    }
+
+    fn default_path() -> PathBuf {
+        if let Some(data_dir) = dirs::data_dir() {
+            let dir = data_dir.join("psnet");
+            let _ = std::fs::create_dir_all(&dir);
+            dir.join("listener_baseline.json")
+        } else {
+            PathBuf::from("psnet_listener_baseline.json")
+        }
+    }
+
+    pub fn is_established(&self) -> bool {
+        !self.recorded.is_empty()
+    }
+
+    /// Replace the baseline with the given listeners.
+    pub fn accept(&mut self, servers: &[ListeningPort]) {
+        let mut seen = HashSet::new();
+        self.entries = servers
+            .iter()
+            .map(BaselineEntry::from_server)
+            .filter(|e| seen.insert((e.port, e.proto.clone(), e.exe.clone())))
+            .collect();
+        self.entries.sort_by(|a, b| (a.port, &a.proto).cmp(&(b.port, &b.proto)));
+        self.recorded = Local::now().format("%Y-%m-%d %H:%M").to_string();
+        self.alerted.clear();
+        self.dirty = true;
+    }
+
+    /// The changes that have not been alerted yet. All current changes then
+    /// count as alerted; ones that went away are forgotten, so they alert
+    /// again if they come back.
+    pub fn take_unalerted(&mut self, changes: &[ListenerChange]) -> Vec<ListenerChange> {
+        let fresh = changes.iter().filter(|c| !self.alerted.contains(c)).cloned().collect();
+        if self.alerted != changes {
+            self.alerted = changes.to_vec();
+            self.dirty = true;
+        }
+        fresh
+    }
+
+    /// Compare a full scan with the baseline. The first scan becomes the
+    /// baseline; versions the baseline did not know yet are filled in rather
+    /// than reported.
+    pub fn update(&mut self, servers: &[ListeningPort]) -> Vec<ListenerChange> {
+        if !self.is_established() {
+            self.accept(servers);
+            return Vec::new();
+        }
+        for s in servers {
+            let current = BaselineEntry::from_server(s);
+            if current.version.is_none() {
+                continue;
+            }
+            if let Some(e) = self.entries.iter_mut().find(|e| e.key() == current.key() && e.version.is_none()) {
+                e.version = current.version;
+                self.dirty = true;
+            }
+        }
+        self.diff(servers)
+    }
+
+    /// Changes between the baseline and the given listeners, sorted by port.
+    pub fn diff(&self, servers: &[ListeningPort]) -> Vec<ListenerChange> {
+        let mut current: HashMap<(u16, String, String), BaselineEntry> = HashMap::new();
+        for s in servers {
+            let e = BaselineEntry::from_server(s);
+            current.entry((e.port, e.proto.clone(), e.exe.clone())).or_insert(e);
+        }
+        let in_current = |b: &BaselineEntry| current.contains_key(&(b.port, b.proto.clone(), b.exe.clone()));
+
+        let mut changes = Vec::new();
+        // Baseline entries accounted for by an executable change
+        let mut replaced: HashSet<(u16, &str, &str)> = HashSet::new();
+
+        for cur in current.values() {
+            let known = self.entries.iter().find(|b| b.key() == cur.key());
+            match known {
+                Some(b) => {
+                    if let (Some(old), Some(new)) = (&b.version, &cur.version) {
+                        if old != new {
+                            changes.push(change(cur, ChangeKind::VersionChanged { old: old.clone(), new: new.clone() }));
+                        }
+                    }
+                }
+                None => {
+                    // Same port and protocol, different binary, and the old one is gone
+                    let prev = self.entries.iter().find(|b| {
+                        b.port == cur.port && b.proto == cur.proto
+                            && !in_current(b) && !replaced.contains(&b.key())
+                    });
+                    match prev {
+                        Some(b) => {
+                            replaced.insert(b.key());
+                            changes.push(change(cur, ChangeKind::ExeChanged { old: b.exe.clone(), new: cur.exe.clone() }));
+                        }
+                        None => changes.push(change(cur, ChangeKind::Added)),
+                    }
+                }
+            }
+        }
+
+        for b in &self.entries {
+            if !in_current(b) && !replaced.contains(&b.key()) {
+                changes.push(change(b, ChangeKind::Removed));
+            }
+        }
+
+        changes.sort_by(|a, b| (a.port, &a.proto, &a.name).cmp(&(b.port, &b.proto, &b.name)));
+        changes
+    }
+
+    /// Write the baseline if it changed since the last save.
+    pub fn save_if_dirty(&mut self) {
+        if !self.dirty {
+            return;
+        }
+        let file = BaselineFile {
+            recorded: self.recorded.clone(),
+            listeners: self.entries.clone(),
+            alerted: self.alerted.clone(),
+        };
+        if let Ok(json) = serde_json::to_string_pretty(&file) {
+            let _ = std::fs::write(&self.path, json);
+        }
+        self.dirty = false;
+    }
+}
+
+fn change(e: &BaselineEntry, kind: ChangeKind) -> ListenerChange {
+    ListenerChange {
+        port: e.port,
+        proto: e.proto.clone(),
+        name: e.name.clone(),
+        exe: e.exe.clone(),
+        kind,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    use chrono::NaiveTime;
+
+    use super::super::types::{ListenProto, ServerKind};
+
+    fn server(kind: ServerKind, port: u16, exe: &str, version: Option<&str>) -> ListeningPort {
+        ListeningPort {
+            proto: ListenProto::Tcp,
+            bind_addr: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            port,
+            pid: 100,
+            process_name: "svc.exe".into(),
+            exe_path: exe.into(),
+            cmdline: String::new(),
+            product_name: String::new(),
+            file_description: String::new(),
+            company_name: String::new(),
+            server_kind: kind,
+            version: version.map(String::from),
+            http_title: None,
+            banner: None,
+            response_headers: Vec::new(),
+            first_seen: NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
+            is_responsive: true,
+            details: String::new(),
+            detected_techs: Vec::new(),
+            protocol_details: Vec::new(),
+            auth_required: None,
+            exposure: None,
+            advisories: Vec::new(),
+        }
+    }
+
+    fn empty() -> ListenerBaseline {
+        ListenerBaseline {
+            entries: Vec::new(),
+            recorded: String::new(),
+            alerted: Vec::new(),
+            dirty: false,
+            path: PathBuf::new(),
+        }
+    }
+
+    #[test]
+    fn test_first_scan_becomes_baseline() {
+        let mut b = empty();
+        let scan = vec![server(ServerKind::Nginx, 80, r"C:\nginx\nginx.exe", None)];
+        assert!(b.update(&scan).is_empty());
+        assert!(b.is_established());
+        assert_eq!(b.entries[0].exe, r"c:\nginx\nginx.exe");
+        assert!(b.update(&scan).is_empty());
+    }
+
+    #[test]
+    fn test_added_and_removed() {
+        let mut b = empty();
+        b.accept(&[server(ServerKind::Nginx, 80, "nginx.exe", None)]);
+        let changes = b.diff(&[server(ServerKind::Redis, 6379, "redis-server.exe", None)]);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].kind, ChangeKind::Removed);
+        assert_eq!(changes[0].port, 80);
+        assert_eq!(changes[1].kind, ChangeKind::Added);
+        assert_eq!(changes[1].port, 6379);
+    }
+
+    #[test]
+    fn test_version_and_exe_changes() {
+        let mut b = empty();
+        b.accept(&[
+            server(ServerKind::Nginx, 80, "nginx.exe", Some("1.24.0")),
+            server(ServerKind::PostgreSQL, 5432, r"C:\pg15\postgres.exe", None),
+        ]);
+
+        // A version learned later is filled in, not reported
+        let changes = b.update(&[
+            server(ServerKind::Nginx, 80, "nginx.exe", Some("1.24.0")),
+            server(ServerKind::PostgreSQL, 5432, r"C:\pg15\postgres.exe", Some("15.4")),
+        ]);
+        assert!(changes.is_empty());
+        assert_eq!(b.entries[1].version.as_deref(), Some("15.4"));
+
+        let changes = b.update(&[
+            server(ServerKind::Nginx, 80, "nginx.exe", Some("1.25.3")),
+            server(ServerKind::PostgreSQL, 5432, r"C:\pg16\postgres.exe", Some("16.1")),
+        ]);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].kind, ChangeKind::VersionChanged { old: "1.24.0".into(), new: "1.25.3".into() });
+        assert!(matches!(&changes[1].kind, ChangeKind::ExeChanged { old, .. } if old == r"c:\pg15\postgres.exe"));
+    }
+
+    #[test]
+    fn test_alerted_changes_survive_a_restart() {
+        let mut b = empty();
+        b.accept(&[server(ServerKind::Nginx, 80, "nginx.exe", None)]);
+        let redis = server(ServerKind::Redis, 6379, "redis-server.exe", None);
+        let changes = b.update(&[server(ServerKind::Nginx, 80, "nginx.exe", None), redis.clone()]);
+        assert_eq!(b.take_unalerted(&changes).len(), 1);
+        assert!(b.take_unalerted(&changes).is_empty());
+
+        // Next session: the saved alerted set suppresses the repeat, while a
+        // listener that appeared while psnet was closed still alerts
+        let file: BaselineFile = serde_json::from_str(&serde_json::to_string(&BaselineFile {
+            recorded: b.recorded.clone(),
+            listeners: b.entries.clone(),
+            alerted: b.alerted.clone(),
+        }).unwrap()).unwrap();
+        let mut b = ListenerBaseline { entries: file.listeners, recorded: file.recorded, alerted: file.alerted, ..empty() };
+        let scan = [server(ServerKind::Nginx, 80, "nginx.exe", None), redis, server(ServerKind::MySQL, 3306, "mysqld.exe", None)];
+        let changes = b.update(&scan);
+        let fresh = b.take_unalerted(&changes);
+        assert_eq!(fresh.len(), 1);
+        assert_eq!(fresh[0].port, 3306);
+
+        // A change that resolves and comes back alerts again
+        let changes = b.update(&scan[..1]);
+        assert!(b.take_unalerted(&changes).is_empty());
+        let changes = b.update(&scan);
+        assert_eq!(b.take_unalerted(&changes).len(), 2);
+    }
+}
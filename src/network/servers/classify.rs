@@ -18,6 +18,10 @@ const SCORE_HTML_TITLE: u32 = 20;
 const SCORE_BANNER_STARTS: u32 = 45;
 const SCORE_BANNER_CONTAINS: u32 = 25;
 const SCORE_PORT: u32 = 5;
+/// A completed protocol handshake is the strongest evidence there is.
+const SCORE_HANDSHAKE: u32 = 60;
+/// Added to a matching user-defined fingerprint so it beats any built-in.
+const SCORE_USER_OVERRIDE: u32 = 1000;
 
 // ─── Main classification entry point ─────────────────────────────────────────
 
@@ -50,6 +54,9 @@ pub fn classify(
         .map(|s| s.to_lowercase());
     let banner = probe.and_then(|p| p.banner.as_deref());
     let banner_lower = banner.map(|b| b.to_lowercase());
+    let handshake_kind = probe
+        .and_then(|p| p.handshake.as_ref())
+        .and_then(|h| h.kind.as_ref());
 
     let db = fingerprints::fingerprints();
 
@@ -149,6 +156,11 @@ pub fn classify(
             }
         }
 
+        // ── Handshake match ──
+        if handshake_kind == Some(&fp.kind) {
+            score += SCORE_HANDSHAKE;
+        }
+
         // ── Default port match ──
         if !fp.default_ports.is_empty() && fp.default_ports.contains(&port) {
             score += SCORE_PORT;
@@ -158,6 +170,12 @@ pub fn classify(
             continue;
         }
 
+        // User-defined entries take priority over built-ins whenever they
+        // clear the threshold on their own.
+        if fp.user_defined && score > SCORE_PORT {
+            score += SCORE_USER_OVERRIDE;
+        }
+
         // Tie-break: prefer lower priority value (higher specificity).
         if score > best_score
             || (score == best_score
@@ -194,6 +212,13 @@ fn extract_version_for_match(
     probe: Option<&ProbeResult>,
     banner: Option<&str>,
 ) -> Option<String> {
+    // A handshake plugin reports the exact version — trust it over any heuristic.
+    if let Some(hs) = probe.and_then(|p| p.handshake.as_ref()) {
+        if hs.kind.as_ref() == Some(&fp.kind) && hs.version.is_some() {
+            return hs.version.clone();
+        }
+    }
+
     // Try version_from_header_prefix against the Server header.
     if let Some(ref prefix) = fp.version_from_header_prefix {
         if let Some(pr) = probe {
@@ -218,9 +243,8 @@ fn extract_version_for_match(
                         .unwrap_or(rest)
                         .to_string()
                 });
-            if version.is_some() {
-                return version;
-            }
+            // Other SSH servers' versions say nothing about OpenSSH
+            return version;
         }
 
         // SMTP/FTP banner: "220 ..." — extract the greeting text.
@@ -510,8 +534,11 @@ mod tests {
             http_powered_by: None,
             http_title: None,
             http_headers: vec![],
+            http_cookies: vec![],
+            http_body: None,
             tls_detected: false,
             is_responsive: true,
+            handshake: None,
         };
         let (kind, ver) = classify("sshd", "", "", 22, Some(&probe));
         assert!(matches!(kind, ServerKind::OpenSSH));
@@ -526,8 +553,11 @@ mod tests {
             http_powered_by: None,
             http_title: None,
             http_headers: vec![],
+            http_cookies: vec![],
+            http_body: None,
             tls_detected: false,
             is_responsive: true,
+            handshake: None,
         };
         let (kind, _ver) = classify("", "", "", 25, Some(&probe));
         assert!(matches!(kind, ServerKind::SMTP | ServerKind::Postfix));
@@ -541,13 +571,69 @@ mod tests {
             http_powered_by: None,
             http_title: None,
             http_headers: vec![],
+            http_cookies: vec![],
+            http_body: None,
             tls_detected: false,
             is_responsive: true,
+            handshake: None,
         };
         let (kind, _ver) = classify("redis-server", "", "", 6379, Some(&probe));
         assert!(matches!(kind, ServerKind::Redis));
     }
 
+    #[test]
+    fn test_classify_handshake_version_wins() {
+        // Unknown process on a non-default port: the handshake alone decides,
+        // and its exact version beats generic banner extraction.
+        let probe = ProbeResult {
+            banner: Some("MariaDB 10.11.6-MariaDB-0+deb12u1".to_string()),
+            http_server: None,
+            http_powered_by: None,
+            http_title: None,
+            http_headers: vec![],
+            http_cookies: vec![],
+            http_body: None,
+            tls_detected: false,
+            is_responsive: true,
+            handshake: Some(super::super::handshakes::HandshakeInfo {
+                kind: Some(ServerKind::MariaDB),
+                product: "MariaDB".to_string(),
+                version: Some("10.11.6".to_string()),
+                banner: String::new(),
+                details: vec![],
+                auth_required: None,
+            }),
+        };
+        let (kind, ver) = classify("dbsvc", "", "", 13306, Some(&probe));
+        assert_eq!(kind, ServerKind::MariaDB);
+        assert_eq!(ver.as_deref(), Some("10.11.6"));
+    }
+
+    #[test]
+    fn test_classify_dropbear_has_no_openssh_version() {
+        let probe = ProbeResult {
+            banner: Some("SSH-2.0-dropbear_2022.83".to_string()),
+            http_server: None,
+            http_powered_by: None,
+            http_title: None,
+            http_headers: vec![],
+            http_cookies: vec![],
+            http_body: None,
+            tls_detected: false,
+            is_responsive: true,
+            handshake: Some(super::super::handshakes::HandshakeInfo {
+                kind: None,
+                product: "dropbear".to_string(),
+                version: Some("2022.83".to_string()),
+                banner: "SSH-2.0-dropbear_2022.83".to_string(),
+                details: vec![],
+                auth_required: None,
+            }),
+        };
+        let (_, ver) = classify("dropbear", "", "", 22, Some(&probe));
+        assert!(ver.is_none(), "{:?}", ver);
+    }
+
     #[test]
     fn test_classify_by_process_path_fallback() {
         // Unknown process name but recognizable path
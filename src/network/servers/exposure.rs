@@ -0,0 +1,273 @@
+//! Exposure analysis: who can actually reach each listener.
+//!
+//! The bind address says which interfaces a socket accepts on; Windows
+//! Firewall decides whether unsolicited inbound traffic gets that far; a UPnP
+//! mapping on the gateway makes it reachable from the Internet. This module
+//! folds the three into one level per listener and flags services that are
+//! dangerous at that level (no authentication, remote-control APIs).
+
+use std::net::IpAddr;
+
+use crate::network::firewall::InboundAccess;
+use crate::network::upnp::PortMapping;
+use crate::types::AlertSeverity;
+
+use super::types::{ListeningPort, ServerCategory, ServerKind};
+
+/// How far a listener is reachable, from least to most exposed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ExposureLevel {
+    /// Bound to 127.0.0.1 / ::1.
+    Loopback,
+    /// Bound to a reachable address, but the firewall drops inbound traffic.
+    Firewalled,
+    /// Reachable from the local network.
+    Lan,
+    /// Forwarded from the Internet by a UPnP port mapping.
+    Internet,
+}
+
+impl ExposureLevel {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Loopback => "Loopback only",
+            Self::Firewalled => "Firewalled",
+            Self::Lan => "LAN-reachable",
+            Self::Internet => "Internet-reachable",
+        }
+    }
+
+    /// Badge text for the server cards.
+    pub fn short_label(&self) -> &'static str {
+        match self {
+            Self::Loopback => "LO",
+            Self::Firewalled => "FW",
+            Self::Lan => "LAN",
+            Self::Internet => "WAN",
+        }
+    }
+
+    pub fn color(&self) -> (u8, u8, u8) {
+        match self {
+            Self::Loopback => (80, 180, 230),
+            Self::Firewalled => (70, 195, 110),
+            Self::Lan => (220, 185, 60),
+            Self::Internet => (255, 80, 80),
+        }
+    }
+}
+
+/// A dangerous combination of service and reachability.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExposureRisk {
+    pub severity: AlertSeverity,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Exposure {
+    pub level: ExposureLevel,
+    /// Why the level was chosen: bind address, firewall verdict, UPnP mapping.
+    pub reasons: Vec<String>,
+    pub risks: Vec<ExposureRisk>,
+}
+
+impl Exposure {
+    /// Highest risk severity, if any.
+    pub fn worst_risk(&self) -> Option<AlertSeverity> {
+        self.risks.iter().map(|r| r.severity).max()
+    }
+}
+
+/// Compute a listener's exposure from its firewall verdict and UPnP mapping.
+pub fn assess(server: &ListeningPort, firewall: &InboundAccess, mapping: Option<&PortMapping>) -> Exposure {
+    let loopback = match server.bind_addr {
+        IpAddr::V4(v4) => v4.is_loopback(),
+        IpAddr::V6(v6) => v6.is_loopback(),
+    };
+    let mut reasons = Vec::new();
+
+    let level = if loopback {
+        reasons.push(format!("bound to {}", server.bind_addr));
+        ExposureLevel::Loopback
+    } else {
+        reasons.push(if server.bind_addr.is_unspecified() {
+            "bound to all interfaces".to_string()
+        } else {
+            format!("bound to {}", server.bind_addr)
+        });
+        reasons.push(firewall.describe());
+        match (firewall.is_allowed(), mapping) {
+            (true, Some(m)) => {
+                reasons.push(mapping_reason(m));
+                ExposureLevel::Internet
+            }
+            (true, None) => ExposureLevel::Lan,
+            (false, Some(m)) => {
+                reasons.push(format!("{} (dropped by the firewall)", mapping_reason(m)));
+                ExposureLevel::Firewalled
+            }
+            (false, None) => ExposureLevel::Firewalled,
+        }
+    };
+
+    let risks = risks_for(server, level, mapping);
+    Exposure { level, reasons, risks }
+}
+
+fn mapping_reason(m: &PortMapping) -> String {
+    let mut s = format!("UPnP forwards external {}/{}", m.protocol.to_lowercase(), m.external_port);
+    if !m.description.is_empty() {
+        s.push_str(&format!(" (\"{}\")", m.description));
+    }
+    s
+}
+
+/// Flag combinations that are dangerous at the given level.
+fn risks_for(server: &ListeningPort, level: ExposureLevel, mapping: Option<&PortMapping>) -> Vec<ExposureRisk> {
+    let mut risks = Vec::new();
+    if level < ExposureLevel::Lan {
+        return risks;
+    }
+    let name = server.display_name();
+    let from = if level == ExposureLevel::Internet { "the Internet" } else { "the LAN" };
+
+    if let Some(why) = unprotected_reason(server) {
+        risks.push(ExposureRisk {
+            severity: AlertSeverity::Critical,
+            message: format!("{} is reachable from {} and {}", name, from, why),
+        });
+    }
+
+    if let Some(m) = mapping.filter(|_| level == ExposureLevel::Internet) {
+        let (severity, what) = match server.server_kind.category() {
+            ServerCategory::Database => (AlertSeverity::Critical, "Database"),
+            _ if is_remote_access(&server.server_kind) => (AlertSeverity::Critical, "Remote access service"),
+            _ => (AlertSeverity::Warning, "Service"),
+        };
+        risks.push(ExposureRisk {
+            severity,
+            message: format!("{} forwarded from the Internet on port {}", what, m.external_port),
+        });
+    }
+
+    risks
+}
+
+/// Why anyone who can connect gets full access, if that is the case.
+fn unprotected_reason(server: &ListeningPort) -> Option<String> {
+    if server.auth_required == Some(false) {
+        return Some("requires no authentication".to_string());
+    }
+    match server.server_kind {
+        // 2375 is the plaintext, unauthenticated Docker API; 2376 requires TLS client certs.
+        ServerKind::Docker if server.port == 2375 => {
+            Some("exposes the Docker API without TLS (root on the host)".to_string())
+        }
+        ServerKind::Memcached => Some("has no authentication by default".to_string()),
+        _ => None,
+    }
+}
+
+fn is_remote_access(kind: &ServerKind) -> bool {
+    matches!(
+        kind,
+        ServerKind::RDP | ServerKind::VNC | ServerKind::TightVNC | ServerKind::UltraVNC | ServerKind::RealVNC
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    use chrono::NaiveTime;
+
+    use super::super::types::ListenProto;
+
+    fn server(kind: ServerKind, bind: IpAddr, port: u16) -> ListeningPort {
+        ListeningPort {
+            proto: ListenProto::Tcp,
+            bind_addr: bind,
+            port,
+            pid: 100,
+            process_name: "svc.exe".into(),
+            exe_path: String::new(),
+            cmdline: String::new(),
+            product_name: String::new(),
+            file_description: String::new(),
+            company_name: String::new(),
+            server_kind: kind,
+            version: None,
+            http_title: None,
+            banner: None,
+            response_headers: Vec::new(),
+            first_seen: NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
+            is_responsive: true,
+            details: String::new(),
+            detected_techs: Vec::new(),
+            protocol_details: Vec::new(),
+            auth_required: None,
+            exposure: None,
+            advisories: Vec::new(),
+        }
+    }
+
+    fn mapping(port: u16) -> PortMapping {
+        PortMapping {
+            remote_host: String::new(),
+            external_port: port,
+            protocol: "TCP".into(),
+            internal_port: port,
+            internal_client: Ipv4Addr::new(192, 168, 1, 20),
+            enabled: true,
+            description: String::new(),
+            lease_secs: 0,
+        }
+    }
+
+    const ANY: IpAddr = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+    const LOOPBACK: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+    #[test]
+    fn test_levels() {
+        let s = server(ServerKind::Nginx, LOOPBACK, 80);
+        assert_eq!(assess(&s, &InboundAccess::FirewallOff, None).level, ExposureLevel::Loopback);
+
+        let s = server(ServerKind::Nginx, ANY, 80);
+        assert_eq!(assess(&s, &InboundAccess::Blocked(None), None).level, ExposureLevel::Firewalled);
+        assert_eq!(assess(&s, &InboundAccess::Allowed("nginx".into()), None).level, ExposureLevel::Lan);
+        let m = mapping(80);
+        assert_eq!(assess(&s, &InboundAccess::FirewallOff, Some(&m)).level, ExposureLevel::Internet);
+        let e = assess(&s, &InboundAccess::Blocked(None), Some(&m));
+        assert_eq!(e.level, ExposureLevel::Firewalled);
+        assert!(e.reasons.iter().any(|r| r.contains("dropped by the firewall")));
+    }
+
+    #[test]
+    fn test_unauthenticated_redis_is_critical() {
+        let mut s = server(ServerKind::Redis, ANY, 6379);
+        s.auth_required = Some(false);
+        let e = assess(&s, &InboundAccess::FirewallOff, None);
+        assert_eq!(e.worst_risk(), Some(AlertSeverity::Critical));
+        assert!(e.risks[0].message.contains("no authentication"));
+
+        // Same service on loopback is fine
+        s.bind_addr = LOOPBACK;
+        assert!(assess(&s, &InboundAccess::FirewallOff, None).risks.is_empty());
+    }
+
+    #[test]
+    fn test_docker_api_and_forwarded_database() {
+        let s = server(ServerKind::Docker, ANY, 2375);
+        assert_eq!(assess(&s, &InboundAccess::FirewallOff, None).risks.len(), 1);
+        let s = server(ServerKind::Docker, ANY, 2376);
+        assert!(assess(&s, &InboundAccess::FirewallOff, None).risks.is_empty());
+
+        let s = server(ServerKind::PostgreSQL, ANY, 5432);
+        let m = mapping(5432);
+        let e = assess(&s, &InboundAccess::Allowed("pg".into()), Some(&m));
+        assert_eq!(e.level, ExposureLevel::Internet);
+        assert_eq!(e.worst_risk(), Some(AlertSeverity::Critical));
+    }
+}
@@ -3,13 +3,19 @@ use std::io::{Read, Write};
 use std::net::{IpAddr, SocketAddr, TcpStream};
 use std::time::Duration;
 
+use super::handshakes::{self, HandshakeInfo};
+
 const CONNECT_TIMEOUT: Duration = Duration::from_millis(500);
 const READ_TIMEOUT: Duration = Duration::from_millis(500);
 const HTTP_READ_TIMEOUT: Duration = Duration::from_secs(1);
 
-/// Maximum bytes to read from a banner or HTTP response.
+/// Maximum bytes to read from a banner.
 const MAX_READ: usize = 8192;
 
+/// Maximum bytes to read from an HTTP response. Larger than a banner so
+/// Wappalyzer `html` / `meta` / `scriptSrc` rules see the whole `<head>`.
+const MAX_HTTP_READ: usize = 32 * 1024;
+
 /// Ports at or above this threshold are ephemeral and skipped.
 const EPHEMERAL_PORT_MIN: u16 = 49153;
 
@@ -20,9 +26,16 @@ pub struct ProbeResult {
     pub http_server: Option<String>,
     pub http_powered_by: Option<String>,
     pub http_title: Option<String>,
+    /// All response headers, Set-Cookie included.
     pub http_headers: Vec<(String, String)>,
+    /// Cookie name/value pairs from Set-Cookie.
+    pub http_cookies: Vec<(String, String)>,
+    /// HTML body (capped at MAX_HTTP_READ), only for text/html responses.
+    pub http_body: Option<String>,
     pub tls_detected: bool,
     pub is_responsive: bool,
+    /// What a protocol handshake plugin learned (exact version, auth, capabilities).
+    pub handshake: Option<HandshakeInfo>,
 }
 
 /// Intermediate struct for parsed HTTP responses.
@@ -31,6 +44,28 @@ struct HttpInfo {
     pub powered_by: Option<String>,
     pub title: Option<String>,
     pub headers: Vec<(String, String)>,
+    pub cookies: Vec<(String, String)>,
+    pub body: Option<String>,
+}
+
+/// Headers worth showing in the detail popup (detection sees all of them).
+const DISPLAY_HEADERS: &[&str] = &[
+    "server",
+    "x-powered-by",
+    "x-framework",
+    "via",
+    "content-type",
+    "x-aspnet-version",
+    "x-generator",
+    "x-drupal-cache",
+    "x-varnish",
+    "x-cache",
+    "x-runtime",
+    "x-request-id",
+];
+
+pub fn is_display_header(name: &str) -> bool {
+    DISPLAY_HEADERS.iter().any(|h| h.eq_ignore_ascii_case(name))
 }
 
 /// Probe multiple ports in parallel. Returns map of port -> ProbeResult.
@@ -81,8 +116,11 @@ fn probe_single(addr: IpAddr, port: u16) -> Option<ProbeResult> {
         http_powered_by: None,
         http_title: None,
         http_headers: Vec::new(),
+        http_cookies: Vec::new(),
+        http_body: None,
         tls_detected: false,
         is_responsive: false,
+        handshake: None,
     };
 
     // 2. Try raw read first — catches SSH, SMTP, FTP, MySQL banners
@@ -104,6 +142,13 @@ fn probe_single(addr: IpAddr, port: u16) -> Option<ProbeResult> {
             return Some(result);
         }
 
+        // Server-speaks-first protocols: let the matching plugin finish the handshake.
+        if let Some(info) = handshakes::run_for_greeting(&mut stream, sock_addr, raw) {
+            result.banner = Some(info.banner.clone());
+            result.handshake = Some(info);
+            return Some(result);
+        }
+
         // Check MySQL protocol: byte[4] == 0x0a means protocol version 10
         if raw.len() > 4 && raw[4] == 0x0a {
             // Likely MySQL greeting packet. The banner starts after the packet header.
@@ -156,10 +201,14 @@ fn probe_single(addr: IpAddr, port: u16) -> Option<ProbeResult> {
         }
     }
 
-    // 3. Try Redis PING before HTTP — Redis responds immediately
-    if banner_bytes.is_none() && is_likely_redis_port(port) {
-        if let Some(redis_result) = try_redis_ping(addr, port) {
-            return Some(redis_result);
+    // 3. Silent server — try the client-first handshake registered for this port
+    //    (Redis PING, PostgreSQL SSLRequest, MongoDB hello, ...) before HTTP.
+    if banner_bytes.is_none() {
+        if let Some(info) = handshakes::run_for_port(&mut stream, sock_addr) {
+            result.is_responsive = true;
+            result.banner = Some(info.banner.clone());
+            result.handshake = Some(info);
+            return Some(result);
         }
     }
 
@@ -192,13 +241,13 @@ fn probe_single(addr: IpAddr, port: u16) -> Option<ProbeResult> {
                 let mut response = Vec::with_capacity(MAX_READ);
                 let mut chunk = [0u8; 4096];
 
-                // Read until EOF or MAX_READ
+                // Read until EOF or MAX_HTTP_READ
                 loop {
                     match http_stream.read(&mut chunk) {
                         Ok(0) => break,
                         Ok(n) => {
                             response.extend_from_slice(&chunk[..n]);
-                            if response.len() >= MAX_READ {
+                            if response.len() >= MAX_HTTP_READ {
                                 break;
                             }
                         }
@@ -272,77 +321,7 @@ fn parse_http_text(text: &str) -> Option<HttpInfo> {
         (text, "")
     };
 
-    let mut lines = header_section.lines();
-
-    // First line must be status line
-    let status_line = lines.next()?;
-    if !status_line.starts_with("HTTP/") {
-        return None;
-    }
-
-    let mut server = None;
-    let mut powered_by = None;
-    let mut content_type = None;
-    let mut interesting_headers = Vec::new();
-
-    // Headers we consider interesting
-    const INTERESTING: &[&str] = &[
-        "server",
-        "x-powered-by",
-        "x-framework",
-        "via",
-        "content-type",
-        "x-aspnet-version",
-        "x-generator",
-        "x-drupal-cache",
-        "x-varnish",
-        "x-cache",
-        "x-runtime",
-        "x-request-id",
-    ];
-
-    for line in lines {
-        let line = line.trim();
-        if line.is_empty() {
-            break;
-        }
-
-        if let Some((name, value)) = line.split_once(':') {
-            let name_trimmed = name.trim();
-            let value_trimmed = value.trim();
-            let name_lower = name_trimmed.to_lowercase();
-
-            match name_lower.as_str() {
-                "server" => server = Some(value_trimmed.to_string()),
-                "x-powered-by" => powered_by = Some(value_trimmed.to_string()),
-                "content-type" => content_type = Some(value_trimmed.to_string()),
-                _ => {}
-            }
-
-            if INTERESTING.contains(&name_lower.as_str()) {
-                interesting_headers
-                    .push((name_trimmed.to_string(), value_trimmed.to_string()));
-            }
-        }
-    }
-
-    // Extract <title> from body if Content-Type is text/html
-    let title = if content_type
-        .as_ref()
-        .map(|ct| ct.contains("text/html"))
-        .unwrap_or(false)
-    {
-        extract_html_title(body)
-    } else {
-        None
-    };
-
-    Some(HttpInfo {
-        server,
-        powered_by,
-        title,
-        headers: interesting_headers,
-    })
+    parse_http_head(header_section, body)
 }
 
 /// Try parsing HTTP response using lossy UTF-8 conversion (for binary bodies with ASCII headers).
@@ -365,7 +344,14 @@ fn parse_http_response_lossy(data: &[u8]) -> Option<HttpInfo> {
         std::borrow::Cow::Borrowed("")
     };
 
-    let mut lines = header_text.lines();
+    parse_http_head(header_text, &body)
+}
+
+/// Parse the status line and headers; keep the body when it is HTML.
+fn parse_http_head(header_section: &str, body: &str) -> Option<HttpInfo> {
+    let mut lines = header_section.lines();
+
+    // First line must be status line
     let status_line = lines.next()?;
     if !status_line.starts_with("HTTP/") {
         return None;
@@ -374,28 +360,15 @@ fn parse_http_response_lossy(data: &[u8]) -> Option<HttpInfo> {
     let mut server = None;
     let mut powered_by = None;
     let mut content_type = None;
-    let mut interesting_headers = Vec::new();
-
-    const INTERESTING: &[&str] = &[
-        "server",
-        "x-powered-by",
-        "x-framework",
-        "via",
-        "content-type",
-        "x-aspnet-version",
-        "x-generator",
-        "x-drupal-cache",
-        "x-varnish",
-        "x-cache",
-        "x-runtime",
-        "x-request-id",
-    ];
+    let mut headers = Vec::new();
+    let mut cookies = Vec::new();
 
     for line in lines {
         let line = line.trim();
         if line.is_empty() {
             break;
         }
+
         if let Some((name, value)) = line.split_once(':') {
             let name_trimmed = name.trim();
             let value_trimmed = value.trim();
@@ -405,34 +378,46 @@ fn parse_http_response_lossy(data: &[u8]) -> Option<HttpInfo> {
                 "server" => server = Some(value_trimmed.to_string()),
                 "x-powered-by" => powered_by = Some(value_trimmed.to_string()),
                 "content-type" => content_type = Some(value_trimmed.to_string()),
+                "set-cookie" => cookies.extend(parse_set_cookie(value_trimmed)),
                 _ => {}
             }
 
-            if INTERESTING.contains(&name_lower.as_str()) {
-                interesting_headers
-                    .push((name_trimmed.to_string(), value_trimmed.to_string()));
-            }
+            headers.push((name_trimmed.to_string(), value_trimmed.to_string()));
         }
     }
 
-    let title = if content_type
+    // Title and body are only meaningful for HTML
+    let is_html = content_type
         .as_ref()
         .map(|ct| ct.contains("text/html"))
-        .unwrap_or(false)
-    {
-        extract_html_title(&body)
+        .unwrap_or(false);
+    let (title, body) = if is_html {
+        (extract_html_title(body), Some(body.to_string()).filter(|b| !b.is_empty()))
     } else {
-        None
+        (None, None)
     };
 
     Some(HttpInfo {
         server,
         powered_by,
         title,
-        headers: interesting_headers,
+        headers,
+        cookies,
+        body,
     })
 }
 
+/// `name=value; Path=/; HttpOnly` -> (name, value)
+fn parse_set_cookie(value: &str) -> Option<(String, String)> {
+    let pair = value.split(';').next()?;
+    let (name, value) = pair.split_once('=')?;
+    let name = name.trim();
+    if name.is_empty() {
+        return None;
+    }
+    Some((name.to_string(), value.trim().trim_matches('"').to_string()))
+}
+
 /// Find `\r\n\r\n` boundary in raw bytes. Returns the index of the first `\r` in the sequence.
 fn find_header_end(data: &[u8]) -> Option<usize> {
     data.windows(4)
@@ -474,55 +459,6 @@ fn extract_html_title(body: &str) -> Option<String> {
     }
 }
 
-/// Try to connect and send Redis PING. Returns a ProbeResult if Redis responds.
-fn try_redis_ping(addr: IpAddr, port: u16) -> Option<ProbeResult> {
-    let sock_addr = SocketAddr::new(addr, port);
-    let mut stream = TcpStream::connect_timeout(&sock_addr, CONNECT_TIMEOUT).ok()?;
-    stream.set_read_timeout(Some(READ_TIMEOUT)).ok();
-    stream.set_write_timeout(Some(CONNECT_TIMEOUT)).ok();
-    stream.set_nodelay(true).ok();
-
-    stream.write_all(b"PING\r\n").ok()?;
-
-    let mut buf = [0u8; 256];
-    let n = stream.read(&mut buf).ok()?;
-    if n == 0 {
-        return None;
-    }
-
-    let response = std::str::from_utf8(&buf[..n]).ok()?;
-    if response.trim().starts_with("+PONG") {
-        Some(ProbeResult {
-            banner: Some("Redis".to_string()),
-            http_server: None,
-            http_powered_by: None,
-            http_title: None,
-            http_headers: Vec::new(),
-            tls_detected: false,
-            is_responsive: true,
-        })
-    } else if response.starts_with("-") {
-        // Redis error response (e.g., -NOAUTH) — still Redis
-        let msg = response.trim().trim_start_matches('-');
-        Some(ProbeResult {
-            banner: Some(format!("Redis ({})", msg.lines().next().unwrap_or("auth required"))),
-            http_server: None,
-            http_powered_by: None,
-            http_title: None,
-            http_headers: Vec::new(),
-            tls_detected: false,
-            is_responsive: true,
-        })
-    } else {
-        None
-    }
-}
-
-/// Check if a port is commonly associated with Redis.
-fn is_likely_redis_port(port: u16) -> bool {
-    port == 6379 || port == 6380 || port == 6381
-}
-
 /// Check if raw bytes suggest we need to send an HTTP request to get useful info.
 /// Returns true if we got nothing recognizable from the initial read.
 fn looks_like_needs_http(data: &[u8]) -> bool {
@@ -594,6 +530,8 @@ fn apply_http_info(result: &mut ProbeResult, info: HttpInfo) {
     result.http_powered_by = info.powered_by;
     result.http_title = info.title;
     result.http_headers = info.headers;
+    result.http_cookies = info.cookies;
+    result.http_body = info.body;
 }
 
 #[cfg(test)]
@@ -616,6 +554,21 @@ mod tests {
         let info = parse_http_response(response).unwrap();
         assert_eq!(info.server, Some("Apache".to_string()));
         assert_eq!(info.title, None);
+        assert_eq!(info.body, None);
+    }
+
+    #[test]
+    fn test_parse_http_response_cookies_and_body() {
+        let response = b"HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nSet-Cookie: laravel_session=abc123; path=/; httponly\r\nSet-Cookie: XSRF-TOKEN=\"x\"; path=/\r\nX-Shopify-Stage: production\r\n\r\n<html><head><meta name=\"generator\" content=\"Hugo 0.120\"></head></html>";
+        let info = parse_http_response(response).unwrap();
+        assert_eq!(info.cookies, vec![
+            ("laravel_session".to_string(), "abc123".to_string()),
+            ("XSRF-TOKEN".to_string(), "x".to_string()),
+        ]);
+        assert!(info.body.unwrap().contains("Hugo 0.120"));
+        assert!(info.headers.iter().any(|(k, _)| k == "X-Shopify-Stage"));
+        assert!(!is_display_header("X-Shopify-Stage"));
+        assert!(is_display_header("Server"));
     }
 
     #[test]
@@ -1,11 +1,17 @@
 //! Technology fingerprint database for server detection.
 //!
 //! Fingerprints are loaded from `data/fingerprints.json` (embedded at compile time)
-//! and parsed once at first access. To add new fingerprints, edit the JSON file —
+//! and merged with the user's `fingerprints.json` override in the config dir
+//! (see `overrides`). To add new built-in fingerprints, edit the JSON file —
 //! no Rust code changes required.
 
-use std::sync::OnceLock;
-use super::types::ServerKind;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::SystemTime;
+
+use crate::config;
+
+use super::overrides::{self, OverrideReport};
+use super::types::{ServerCategory, ServerKind, UserKind};
 
 // ─── Fingerprint struct (owned data, loaded from JSON) ──────────────────────
 
@@ -26,17 +32,93 @@ pub struct TechFingerprint {
     pub banner_contains: Vec<String>,
     pub default_ports: Vec<u16>,
     pub version_from_header_prefix: Option<String>,
+    /// Came from the user's override file — wins over built-ins when it matches.
+    pub user_defined: bool,
 }
 
-// ─── Embedded JSON database ─────────────────────────────────────────────────
+// ─── Database (embedded + user overrides) ───────────────────────────────────
 
 static FINGERPRINTS_JSON: &str = include_str!("../../../data/fingerprints.json");
 
-static FINGERPRINTS: OnceLock<Vec<TechFingerprint>> = OnceLock::new();
+const OVERRIDE_FILE: &str = "fingerprints.json";
+
+static FINGERPRINTS: RwLock<Option<Arc<Vec<TechFingerprint>>>> = RwLock::new(None);
+
+/// Override file mtime the active database was built from.
+static LOADED_STAMP: Mutex<Option<Option<SystemTime>>> = Mutex::new(None);
+
+/// Get the active fingerprint database (built on first access, swapped on reload).
+pub fn fingerprints() -> Arc<Vec<TechFingerprint>> {
+    if let Some(db) = FINGERPRINTS.read().ok().and_then(|g| g.clone()) {
+        return db;
+    }
+    reload();
+    FINGERPRINTS.read().ok().and_then(|g| g.clone()).unwrap_or_default()
+}
+
+/// Rebuild the database from the embedded JSON and the override file.
+pub fn reload() -> OverrideReport {
+    let path = config::path(OVERRIDE_FILE);
+    let stamp = config::modified(&path);
+    let (db, report) = match overrides::read_override(&path) {
+        Ok(file) => merge(load_fingerprints(FINGERPRINTS_JSON), &file),
+        Err(e) => (
+            load_fingerprints(FINGERPRINTS_JSON),
+            OverrideReport { error: Some(e), ..Default::default() },
+        ),
+    };
+    if let Ok(mut g) = FINGERPRINTS.write() {
+        *g = Some(Arc::new(db));
+    }
+    if let Ok(mut s) = LOADED_STAMP.lock() {
+        *s = Some(stamp);
+    }
+    report
+}
+
+/// Reload if the override file was created, edited or removed since the last load.
+pub fn reload_if_changed() -> Option<OverrideReport> {
+    let stamp = config::modified(&config::path(OVERRIDE_FILE));
+    let loaded = LOADED_STAMP.lock().ok().and_then(|s| *s);
+    match loaded {
+        Some(prev) if prev == stamp => None,
+        None if stamp.is_none() => None,
+        _ => Some(reload()),
+    }
+}
+
+/// Merge user entries into the built-in list: drop disabled kinds, then put
+/// user entries first so they are also preferred on equal scores.
+fn merge(builtin: Vec<TechFingerprint>, file: &overrides::OverrideFile) -> (Vec<TechFingerprint>, OverrideReport) {
+    let mut report = OverrideReport::default();
+
+    let before = builtin.len();
+    let builtin: Vec<TechFingerprint> = builtin
+        .into_iter()
+        .filter(|fp| !file.disable.iter().any(|d| d.eq_ignore_ascii_case(&kind_name(&fp.kind))))
+        .collect();
+    report.disabled = before - builtin.len();
+
+    let mut db = Vec::with_capacity(file.entries.len() + builtin.len());
+    for v in &file.entries {
+        match parse_user_fingerprint(v) {
+            Some(fp) => {
+                db.push(fp);
+                report.added += 1;
+            }
+            None => report.skipped += 1,
+        }
+    }
+    db.extend(builtin);
+    (db, report)
+}
 
-/// Get the fingerprint database (parsed once, cached forever).
-pub fn fingerprints() -> &'static [TechFingerprint] {
-    FINGERPRINTS.get_or_init(|| load_fingerprints(FINGERPRINTS_JSON))
+/// The JSON name of a kind (its Debug form for built-ins, the given name for custom kinds).
+fn kind_name(kind: &ServerKind) -> String {
+    match kind {
+        ServerKind::UserDefined(u) => u.name.clone(),
+        other => format!("{:?}", other),
+    }
 }
 
 // ─── JSON parser ────────────────────────────────────────────────────────────
@@ -47,13 +129,47 @@ fn load_fingerprints(json: &str) -> Vec<TechFingerprint> {
 
     entries
         .into_iter()
-        .filter_map(|v| parse_fingerprint(&v))
+        .filter_map(|v| {
+            let kind_str = v.get("kind")?.as_str()?;
+            let Some(kind) = parse_server_kind(kind_str) else {
+                eprintln!("fingerprints.json: unknown kind {:?}, skipping", kind_str);
+                return None;
+            };
+            parse_fingerprint(&v, kind)
+        })
         .collect()
 }
 
-fn parse_fingerprint(v: &serde_json::Value) -> Option<TechFingerprint> {
-    let kind_str = v.get("kind")?.as_str()?;
-    let kind = parse_server_kind(kind_str)?;
+/// A user entry names either a built-in kind or `"Custom"` with its own
+/// `name`, `category`, `icon`, `unicode_icon` and `description`.
+fn parse_user_fingerprint(v: &serde_json::Value) -> Option<TechFingerprint> {
+    let kind_str = v.get("kind").and_then(|k| k.as_str()).unwrap_or("Custom");
+    let kind = if kind_str.eq_ignore_ascii_case("custom") {
+        ServerKind::UserDefined(Box::new(parse_user_kind(v)?))
+    } else {
+        parse_server_kind(kind_str)?
+    };
+    let mut fp = parse_fingerprint(v, kind)?;
+    fp.user_defined = true;
+    Some(fp)
+}
+
+fn parse_user_kind(v: &serde_json::Value) -> Option<UserKind> {
+    let text = |key: &str| v.get(key).and_then(|s| s.as_str()).map(|s| s.to_string());
+    let name = text("name").filter(|n| !n.trim().is_empty())?;
+    let category = text("category")
+        .and_then(|c| ServerCategory::from_name(&c))
+        .unwrap_or(ServerCategory::Other);
+    Some(UserKind {
+        icon: text("icon").unwrap_or_else(|| name.chars().take(2).collect::<String>().to_uppercase()),
+        unicode_icon: text("unicode_icon").unwrap_or_else(|| "\u{1F9E9}".to_string()),
+        description: text("description").unwrap_or_else(|| "Service defined in fingerprints override".to_string()),
+        name,
+        category,
+    })
+}
+
+fn parse_fingerprint(v: &serde_json::Value, kind: ServerKind) -> Option<TechFingerprint> {
     let priority = v.get("priority").and_then(|p| p.as_u64()).unwrap_or(10) as u8;
 
     Some(TechFingerprint {
@@ -73,6 +189,7 @@ fn parse_fingerprint(v: &serde_json::Value) -> Option<TechFingerprint> {
         version_from_header_prefix: v.get("version_from_header_prefix")
             .and_then(|s| s.as_str())
             .map(|s| s.to_string()),
+        user_defined: false,
     })
 }
 
@@ -120,6 +237,7 @@ fn header_pairs(v: &serde_json::Value, key: &str) -> Vec<(String, String)> {
 }
 
 /// Parse a ServerKind variant name from its Debug representation.
+/// Returns None for unknown names; callers decide how to report them.
 fn parse_server_kind(s: &str) -> Option<ServerKind> {
     Some(match s {
         // Web servers
@@ -414,9 +532,39 @@ fn parse_server_kind(s: &str) -> Option<ServerKind> {
         "GenericUdp" => ServerKind::GenericUdp,
         "Unknown" => ServerKind::Unknown,
 
-        _ => {
-            eprintln!("fingerprints.json: unknown kind {:?}, skipping", s);
-            return None;
-        }
+        _ => return None,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_custom_kind_and_disable() {
+        let builtin = load_fingerprints(FINGERPRINTS_JSON);
+        let grafana_entries = builtin.iter().filter(|fp| fp.kind == ServerKind::Grafana).count();
+        let file = overrides::parse_override(r#"{
+            "disable": ["grafana"],
+            "entries": [
+                {"kind": "Custom", "name": "Billing API", "category": "Web Frameworks", "icon": "BA",
+                 "process_names": ["billing-svc"], "default_ports": [7400]},
+                {"kind": "Redis", "process_names": ["cachebox"]},
+                {"kind": "NoSuchKind"},
+                {"kind": "Custom"}
+            ]
+        }"#).unwrap();
+
+        let (db, report) = merge(builtin, &file);
+        assert_eq!(report.added, 2);
+        assert_eq!(report.skipped, 2);
+        assert_eq!(report.disabled, grafana_entries);
+        assert!(!db.iter().any(|fp| fp.kind == ServerKind::Grafana));
+
+        let ServerKind::UserDefined(ref custom) = db[0].kind else { panic!("expected custom kind") };
+        assert_eq!(custom.name, "Billing API");
+        assert_eq!(custom.category, ServerCategory::WebFramework);
+        assert_eq!(db[0].kind.icon(), "BA");
+        assert!(db[0].user_defined && db[1].user_defined && !db[2].user_defined);
+    }
+}
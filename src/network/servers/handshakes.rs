@@ -0,0 +1,1421 @@
+//! Protocol handshake probes for the Servers scanner.
+//!
+//! A raw banner read only identifies protocols that talk first, and even then
+//! rarely says more than a product name. Each plugin here speaks just enough
+//! of one wire protocol to get the server to describe itself — exact version,
+//! auth requirements, capabilities — without ever authenticating.
+//!
+//! Plugins are selected in one of two ways:
+//!   * `Trigger::Greeting` — the server sent an unsolicited greeting (SSH,
+//!     MySQL, SMTP, FTP); the matcher inspects those bytes.
+//!   * `Trigger::Ports` — the server stays silent until the client speaks
+//!     (PostgreSQL, MongoDB, Redis, ...); tried on the protocol's usual ports.
+
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::time::Duration;
+
+use super::types::ServerKind;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(500);
+const READ_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Upper bound on bytes accumulated from a single handshake.
+const MAX_RESPONSE: usize = 64 * 1024;
+
+// ─── Registry ───────────────────────────────────────────────────────────────
+
+/// What a successful handshake learned about the service.
+#[derive(Clone, Debug)]
+pub struct HandshakeInfo {
+    /// Server kind the protocol proves, or None when the protocol is confirmed
+    /// but the product behind it is not (e.g. an anonymous AMQP 1.0 broker).
+    pub kind: Option<ServerKind>,
+    /// Product name as reported by the server ("OpenSSH", "MariaDB", ...).
+    pub product: String,
+    /// Exact version reported by the server.
+    pub version: Option<String>,
+    /// One-line summary used as the listener's banner.
+    pub banner: String,
+    /// Protocol facts worth showing: auth, capabilities, host key type, ...
+    pub details: Vec<(String, String)>,
+    /// Whether the service asked for credentials before serving us; None when
+    /// the handshake does not tell.
+    pub auth_required: Option<bool>,
+}
+
+/// Everything a plugin may need besides the open stream.
+pub struct ProbeContext<'a> {
+    /// Address the stream is connected to (for plugins that need a second connection).
+    pub addr: SocketAddr,
+    /// Bytes the server sent before we wrote anything (empty for silent servers).
+    pub greeting: &'a [u8],
+}
+
+/// How a plugin is chosen for a port.
+pub enum Trigger {
+    /// Server talks first; matches the greeting bytes and the port.
+    Greeting(fn(&[u8], u16) -> bool),
+    /// Client talks first; tried on these ports when the server stays silent.
+    Ports(&'static [u16]),
+}
+
+/// A protocol handshake plugin.
+pub struct Handshake {
+    pub trigger: Trigger,
+    pub run: fn(&mut TcpStream, &ProbeContext) -> Option<HandshakeInfo>,
+}
+
+/// All registered handshake plugins, in match order.
+pub static HANDSHAKES: &[Handshake] = &[
+    Handshake { trigger: Trigger::Greeting(is_ssh_greeting), run: ssh_handshake },
+    Handshake { trigger: Trigger::Greeting(is_mysql_greeting), run: mysql_handshake },
+    Handshake { trigger: Trigger::Greeting(is_ftp_greeting), run: ftp_handshake },
+    Handshake { trigger: Trigger::Greeting(is_smtp_greeting), run: smtp_handshake },
+    Handshake { trigger: Trigger::Ports(&[5432, 5433, 6432]), run: postgres_handshake },
+    Handshake { trigger: Trigger::Ports(&[27017, 27018, 27019]), run: mongodb_handshake },
+    Handshake { trigger: Trigger::Ports(&[6379, 6380, 6381]), run: redis_handshake },
+    Handshake { trigger: Trigger::Ports(&[11211]), run: memcached_handshake },
+    Handshake { trigger: Trigger::Ports(&[5671, 5672]), run: amqp_handshake },
+    Handshake { trigger: Trigger::Ports(&[1883]), run: mqtt_handshake },
+    Handshake { trigger: Trigger::Ports(&[9200, 9201]), run: elasticsearch_handshake },
+];
+
+/// Run the first plugin whose greeting matcher accepts `greeting`.
+pub fn run_for_greeting(stream: &mut TcpStream, addr: SocketAddr, greeting: &[u8]) -> Option<HandshakeInfo> {
+    let ctx = ProbeContext { addr, greeting };
+    HANDSHAKES.iter().find_map(|h| match h.trigger {
+        Trigger::Greeting(matches) if matches(greeting, addr.port()) => (h.run)(stream, &ctx),
+        _ => None,
+    })
+}
+
+/// Run the client-first plugin registered for this port, if any.
+pub fn run_for_port(stream: &mut TcpStream, addr: SocketAddr) -> Option<HandshakeInfo> {
+    let ctx = ProbeContext { addr, greeting: &[] };
+    HANDSHAKES.iter().find_map(|h| match h.trigger {
+        Trigger::Ports(ports) if ports.contains(&addr.port()) => (h.run)(stream, &ctx),
+        _ => None,
+    })
+}
+
+// ─── I/O helpers ────────────────────────────────────────────────────────────
+
+/// Keep reading until `done` accepts the buffer, EOF, timeout, or the size cap.
+fn read_until(stream: &mut TcpStream, mut buf: Vec<u8>, done: impl Fn(&[u8]) -> bool) -> Vec<u8> {
+    let mut chunk = [0u8; 4096];
+    while !done(&buf) && buf.len() < MAX_RESPONSE {
+        match stream.read(&mut chunk) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => buf.extend_from_slice(&chunk[..n]),
+        }
+    }
+    buf
+}
+
+/// Read text lines until one satisfies `last` (the final line of a reply).
+fn read_reply_lines(stream: &mut TcpStream, last: fn(&str) -> bool) -> String {
+    let buf = read_until(stream, Vec::new(), |b| {
+        String::from_utf8_lossy(b).lines().any(last) && b.ends_with(b"\n")
+    });
+    String::from_utf8_lossy(&buf).into_owned()
+}
+
+fn connect(addr: SocketAddr) -> Option<TcpStream> {
+    let stream = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT).ok()?;
+    stream.set_read_timeout(Some(READ_TIMEOUT)).ok();
+    stream.set_write_timeout(Some(CONNECT_TIMEOUT)).ok();
+    stream.set_nodelay(true).ok();
+    Some(stream)
+}
+
+fn be_u16(b: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(b.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn be_u32(b: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(b.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn le_u16(b: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(b.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn le_i32(b: &[u8], at: usize) -> Option<i32> {
+    Some(i32::from_le_bytes(b.get(at..at + 4)?.try_into().ok()?))
+}
+
+/// A little-endian i32 length; None when it is negative.
+fn le_len(b: &[u8], at: usize) -> Option<usize> {
+    usize::try_from(le_i32(b, at)?).ok()
+}
+
+/// Read a NUL-terminated string starting at `at`; returns (string, index after NUL).
+fn cstr(b: &[u8], at: usize) -> Option<(String, usize)> {
+    let rest = b.get(at..)?;
+    let end = rest.iter().position(|&c| c == 0)?;
+    Some((String::from_utf8_lossy(&rest[..end]).into_owned(), at + end + 1))
+}
+
+/// Find a known product name in a text greeting and the version token after it.
+/// e.g. "220 (vsFTPd 3.0.5)" with "vsftpd" → ("vsFTPd", Some("3.0.5")).
+fn product_in_greeting(text: &str, known: &[&str]) -> Option<(String, Option<String>)> {
+    // ASCII lowering keeps byte offsets aligned with `text`.
+    let lower = text.to_ascii_lowercase();
+    for name in known {
+        let Some(idx) = lower.find(name) else { continue };
+        let product = text[idx..idx + name.len()].to_string();
+        let version = text[idx + name.len()..]
+            .split(|c: char| c.is_whitespace() || c == '/' || c == '_')
+            .filter(|t| !t.is_empty())
+            .take(2)
+            .map(|t| t.trim_matches(|c: char| !c.is_ascii_alphanumeric() && c != '.'))
+            .find(|t| t.starts_with(|c: char| c.is_ascii_digit()))
+            .map(|t| t.to_string());
+        return Some((product, version));
+    }
+    None
+}
+
+// ─── SSH ────────────────────────────────────────────────────────────────────
+
+fn is_ssh_greeting(data: &[u8], _port: u16) -> bool {
+    data.starts_with(b"SSH-")
+}
+
+/// Exchange identification strings and read the server's KEXINIT, which lists
+/// the host key algorithms in order of preference.
+fn ssh_handshake(stream: &mut TcpStream, ctx: &ProbeContext) -> Option<HandshakeInfo> {
+    let ident_end = ctx.greeting.iter().position(|&b| b == b'\n')?;
+    let ident = String::from_utf8_lossy(&ctx.greeting[..ident_end]).trim().to_string();
+    let (product, version, comment) = parse_ssh_ident(&ident)?;
+
+    let mut details = vec![("Protocol".to_string(), ident.split('-').nth(1).unwrap_or("2.0").to_string())];
+    if let Some(c) = comment {
+        details.push(("Build".to_string(), c));
+    }
+
+    if stream.write_all(b"SSH-2.0-psnet_probe\r\n").is_ok() {
+        let rest = ctx.greeting[ident_end + 1..].to_vec();
+        let packet = read_until(stream, rest, |b| {
+            be_u32(b, 0).map(|len| b.len() >= len as usize + 4).unwrap_or(false)
+        });
+        if let Some((kex, host_keys)) = parse_kexinit(&packet) {
+            if let Some(first) = host_keys.first() {
+                details.push(("Host key".to_string(), first.clone()));
+            }
+            if host_keys.len() > 1 {
+                details.push(("Host key algs".to_string(), host_keys.join(", ")));
+            }
+            if let Some(first) = kex.first() {
+                details.push(("Key exchange".to_string(), first.clone()));
+            }
+        }
+    }
+
+    // Dropbear, libssh, Cisco, ... speak the same protocol but are not OpenSSH
+    let kind = (product == "OpenSSH").then_some(ServerKind::OpenSSH);
+    Some(HandshakeInfo {
+        kind,
+        banner: ident.clone(),
+        product,
+        version,
+        details,
+        auth_required: None,
+    })
+}
+
+/// Split "SSH-2.0-OpenSSH_9.6p1 Ubuntu-3ubuntu13" into
+/// ("OpenSSH", Some("9.6p1"), Some("Ubuntu-3ubuntu13")).
+fn parse_ssh_ident(ident: &str) -> Option<(String, Option<String>, Option<String>)> {
+    let rest = ident.strip_prefix("SSH-")?;
+    let (_proto, software) = rest.split_once('-')?;
+    let (software, comment) = match software.split_once(' ') {
+        Some((s, c)) => (s, Some(c.trim().to_string())),
+        None => (software, None),
+    };
+    let (product, version) = match software.split_once('_') {
+        Some((p, v)) => (p.to_string(), Some(v.to_string())),
+        None => (software.to_string(), None),
+    };
+    Some((product, version, comment.filter(|c| !c.is_empty())))
+}
+
+/// Parse a binary SSH packet holding SSH_MSG_KEXINIT.
+/// Returns (kex_algorithms, server_host_key_algorithms).
+fn parse_kexinit(packet: &[u8]) -> Option<(Vec<String>, Vec<String>)> {
+    const SSH_MSG_KEXINIT: u8 = 20;
+    let len = be_u32(packet, 0)? as usize;
+    let payload = packet.get(5..4 + len)?;
+    if payload.first() != Some(&SSH_MSG_KEXINIT) {
+        return None;
+    }
+    // msg byte + 16-byte cookie, then name-lists
+    let mut at = 17;
+    let mut lists = Vec::with_capacity(2);
+    for _ in 0..2 {
+        let n = be_u32(payload, at)? as usize;
+        let raw = payload.get(at + 4..at + 4 + n)?;
+        lists.push(
+            String::from_utf8_lossy(raw)
+                .split(',')
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>(),
+        );
+        at += 4 + n;
+    }
+    let host_keys = lists.pop()?;
+    let kex = lists.pop()?;
+    Some((kex, host_keys))
+}
+
+// ─── MySQL / MariaDB ────────────────────────────────────────────────────────
+
+fn is_mysql_greeting(data: &[u8], _port: u16) -> bool {
+    // 3-byte length + sequence id 0, then protocol 10 or an error packet.
+    data.len() > 5 && data[3] == 0 && (data[4] == 0x0a || data[4] == 0xff)
+}
+
+/// MySQL sends its full handshake packet on connect; nothing to write.
+fn mysql_handshake(_stream: &mut TcpStream, ctx: &ProbeContext) -> Option<HandshakeInfo> {
+    parse_mysql_greeting(ctx.greeting)
+}
+
+fn parse_mysql_greeting(data: &[u8]) -> Option<HandshakeInfo> {
+    const CLIENT_SSL: u32 = 0x0800;
+    let body = data.get(4..)?;
+
+    // Error packet: the server refused us before the handshake (host not allowed, too many connections).
+    if body.first() == Some(&0xff) {
+        let code = le_u16(body, 1)?;
+        let msg_start = if body.get(3) == Some(&b'#') { 9 } else { 3 };
+        let msg = String::from_utf8_lossy(body.get(msg_start..)?).trim().to_string();
+        return Some(HandshakeInfo {
+            kind: Some(ServerKind::MySQL),
+            product: "MySQL".to_string(),
+            version: None,
+            banner: format!("MySQL (error {}: {})", code, msg),
+            details: vec![("Refused".to_string(), format!("{} ({})", msg, code))],
+            auth_required: None,
+        });
+    }
+
+    if body.first() != Some(&0x0a) {
+        return None;
+    }
+    let (raw_version, mut at) = cstr(body, 1)?;
+    let mut details = vec![("Server version".to_string(), raw_version.clone())];
+
+    // thread id (4) + auth-plugin-data part 1 (8) + filler (1)
+    at += 13;
+    let cap_low = le_u16(body, at).unwrap_or(0) as u32;
+    // capability low (2) + charset (1) + status (2)
+    let cap_high = le_u16(body, at + 5).unwrap_or(0) as u32;
+    let caps = cap_low | cap_high << 16;
+    let auth_data_len = body.get(at + 7).copied().unwrap_or(0) as usize;
+    // + auth data len (1) + reserved (10) + auth-plugin-data part 2
+    let plugin_at = at + 8 + 10 + auth_data_len.saturating_sub(8).max(13);
+    if let Some((plugin, _)) = cstr(body, plugin_at) {
+        if !plugin.is_empty() {
+            details.push(("Auth plugin".to_string(), plugin));
+        }
+    }
+    details.push((
+        "TLS".to_string(),
+        if caps & CLIENT_SSL != 0 { "supported" } else { "not offered" }.to_string(),
+    ));
+
+    let is_maria = raw_version.to_lowercase().contains("mariadb");
+    // MariaDB prefixes "5.5.5-" so old clients accept it.
+    let trimmed = raw_version.strip_prefix("5.5.5-").unwrap_or(&raw_version);
+    let version = trimmed.split('-').next().filter(|v| !v.is_empty()).map(|v| v.to_string());
+    let (kind, product) = if is_maria {
+        (ServerKind::MariaDB, "MariaDB")
+    } else {
+        (ServerKind::MySQL, "MySQL")
+    };
+
+    Some(HandshakeInfo {
+        kind: Some(kind),
+        product: product.to_string(),
+        banner: format!("{} {}", product, trimmed),
+        version,
+        details,
+        auth_required: None,
+    })
+}
+
+// ─── SMTP / FTP ─────────────────────────────────────────────────────────────
+
+const FTP_PORTS: &[u16] = &[21, 990, 2121];
+
+const FTP_PRODUCTS: &[&str] = &[
+    "filezilla server", "vsftpd", "proftpd", "pure-ftpd", "microsoft ftp service", "serv-u", "pyftpdlib",
+];
+
+const SMTP_PRODUCTS: &[&str] = &[
+    "postfix", "exim", "sendmail", "microsoft esmtp mail service", "hmailserver", "mailhog", "mailpit",
+    "haraka", "opensmtpd", "zimbra",
+];
+
+fn looks_like_ftp(text: &str, port: u16) -> bool {
+    FTP_PORTS.contains(&port) || text.to_lowercase().contains("ftp")
+}
+
+fn is_ftp_greeting(data: &[u8], port: u16) -> bool {
+    let text = String::from_utf8_lossy(data);
+    (text.starts_with("220") || text.starts_with("230")) && looks_like_ftp(&text, port)
+}
+
+fn is_smtp_greeting(data: &[u8], port: u16) -> bool {
+    let text = String::from_utf8_lossy(data);
+    text.starts_with("220") && !looks_like_ftp(&text, port)
+}
+
+/// A reply is complete once a line has a 3-digit code followed by a space.
+fn is_final_reply_line(line: &str) -> bool {
+    line.len() >= 4 && line.as_bytes()[..3].iter().all(u8::is_ascii_digit) && line.as_bytes()[3] == b' '
+}
+
+/// Send EHLO and collect the advertised extensions.
+fn smtp_handshake(stream: &mut TcpStream, ctx: &ProbeContext) -> Option<HandshakeInfo> {
+    let greeting = String::from_utf8_lossy(ctx.greeting);
+    let first_line = greeting.lines().next().unwrap_or("").trim().to_string();
+
+    let mut details = Vec::new();
+    if stream.write_all(b"EHLO psnet.local\r\n").is_ok() {
+        let reply = read_reply_lines(stream, |l| l.starts_with("250 ") || (is_final_reply_line(l) && !l.starts_with("220")));
+        let caps = parse_ehlo_reply(&reply);
+        if !caps.is_empty() {
+            let starttls = caps.iter().any(|c| c.eq_ignore_ascii_case("STARTTLS"));
+            details.push(("STARTTLS".to_string(), if starttls { "yes" } else { "no" }.to_string()));
+            if let Some(auth) = caps.iter().find(|c| c.to_uppercase().starts_with("AUTH")) {
+                details.push(("Auth".to_string(), auth[4..].trim_start_matches(['=', ' ']).to_string()));
+            }
+            details.push(("Extensions".to_string(), caps.join(", ")));
+        }
+        let _ = stream.write_all(b"QUIT\r\n");
+    }
+
+    let (product, version) = product_in_greeting(&greeting, SMTP_PRODUCTS)
+        .unwrap_or_else(|| ("SMTP".to_string(), None));
+    let kind = if product.eq_ignore_ascii_case("postfix") { ServerKind::Postfix } else { ServerKind::SMTP };
+
+    Some(HandshakeInfo {
+        kind: Some(kind),
+        product,
+        version,
+        banner: first_line,
+        details,
+        auth_required: None,
+    })
+}
+
+/// Extensions from an EHLO reply ("250-PIPELINING" ... "250 SMTPUTF8"),
+/// skipping the first line which only echoes the server's hostname.
+fn parse_ehlo_reply(reply: &str) -> Vec<String> {
+    reply
+        .lines()
+        .filter(|l| l.starts_with("250"))
+        .skip(1)
+        .map(|l| l[3..].trim_start_matches(['-', ' ']).trim().to_string())
+        .filter(|l| !l.is_empty())
+        .collect()
+}
+
+/// Send FEAT and collect the feature list.
+fn ftp_handshake(stream: &mut TcpStream, ctx: &ProbeContext) -> Option<HandshakeInfo> {
+    let greeting = String::from_utf8_lossy(ctx.greeting);
+    let first_line = greeting.lines().next().unwrap_or("").trim().to_string();
+
+    let mut details = Vec::new();
+    if stream.write_all(b"FEAT\r\n").is_ok() {
+        let reply = read_reply_lines(stream, |l| {
+            is_final_reply_line(l) && !l.starts_with("220") && !l.starts_with("230")
+        });
+        let feats = parse_feat_reply(&reply);
+        if !feats.is_empty() {
+            let tls = feats.iter().any(|f| f.to_uppercase().starts_with("AUTH TLS"));
+            details.push(("Explicit TLS".to_string(), if tls { "yes" } else { "no" }.to_string()));
+            details.push(("Features".to_string(), feats.join(", ")));
+        }
+        let _ = stream.write_all(b"QUIT\r\n");
+    }
+
+    let (product, version) = product_in_greeting(&greeting, FTP_PRODUCTS)
+        .unwrap_or_else(|| ("FTP".to_string(), None));
+
+    Some(HandshakeInfo {
+        kind: Some(ServerKind::FTP),
+        product,
+        version,
+        banner: first_line,
+        details,
+        auth_required: None,
+    })
+}
+
+/// Feature lines of a FEAT reply are the space-indented lines between "211-" and "211 ".
+fn parse_feat_reply(reply: &str) -> Vec<String> {
+    reply
+        .lines()
+        .filter(|l| l.starts_with(' '))
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .collect()
+}
+
+// ─── PostgreSQL ─────────────────────────────────────────────────────────────
+
+/// Ask for TLS, then send a startup message for a throwaway role and report
+/// which authentication method the server demands. Servers configured for
+/// trust auth also reveal `server_version`.
+fn postgres_handshake(stream: &mut TcpStream, ctx: &ProbeContext) -> Option<HandshakeInfo> {
+    const SSL_REQUEST: [u8; 8] = [0, 0, 0, 8, 0x04, 0xd2, 0x16, 0x2f];
+    stream.write_all(&SSL_REQUEST).ok()?;
+    let mut answer = [0u8; 1];
+    stream.read_exact(&mut answer).ok()?;
+    let ssl = match answer[0] {
+        b'S' => true,
+        b'N' => false,
+        _ => return None,
+    };
+
+    // After 'S' the server expects a TLS ClientHello, so continue on a fresh connection.
+    let mut fresh;
+    let startup_stream = if ssl {
+        fresh = connect(ctx.addr)?;
+        &mut fresh
+    } else {
+        stream
+    };
+    startup_stream.write_all(&postgres_startup_message("psnet", "postgres")).ok()?;
+    let reply = read_until(startup_stream, Vec::new(), postgres_reply_complete);
+    let _ = startup_stream.write_all(&[b'X', 0, 0, 0, 4]);
+
+    let mut info = parse_postgres_reply(&reply)?;
+    info.details.insert(0, ("SSL".to_string(), if ssl { "supported" } else { "not offered" }.to_string()));
+    Some(info)
+}
+
+fn postgres_startup_message(user: &str, database: &str) -> Vec<u8> {
+    const PROTOCOL_3_0: u32 = 196_608;
+    let mut body = PROTOCOL_3_0.to_be_bytes().to_vec();
+    for (k, v) in [("user", user), ("database", database), ("application_name", "psnet")] {
+        body.extend_from_slice(k.as_bytes());
+        body.push(0);
+        body.extend_from_slice(v.as_bytes());
+        body.push(0);
+    }
+    body.push(0);
+    let mut msg = ((body.len() + 4) as u32).to_be_bytes().to_vec();
+    msg.extend_from_slice(&body);
+    msg
+}
+
+/// Split a buffer of backend messages into (tag, payload) pairs.
+fn postgres_messages(buf: &[u8]) -> Vec<(u8, &[u8])> {
+    let mut out = Vec::new();
+    let mut at = 0;
+    while let Some(len) = be_u32(buf, at + 1) {
+        let end = at + 1 + len as usize;
+        let Some(payload) = buf.get(at + 5..end) else { break };
+        out.push((buf[at], payload));
+        at = end;
+    }
+    out
+}
+
+fn postgres_reply_complete(buf: &[u8]) -> bool {
+    postgres_messages(buf).iter().any(|(tag, payload)| match tag {
+        b'E' | b'Z' => true,
+        b'R' => be_u32(payload, 0) != Some(0),
+        _ => false,
+    })
+}
+
+fn parse_postgres_reply(buf: &[u8]) -> Option<HandshakeInfo> {
+    let messages = postgres_messages(buf);
+    if messages.is_empty() {
+        return None;
+    }
+    let mut details = Vec::new();
+    let mut version = None;
+    let mut auth_required = None;
+
+    for (tag, payload) in messages {
+        match tag {
+            b'R' => {
+                let code = be_u32(payload, 0)?;
+                // AuthenticationOk straight after the startup message means trust
+                auth_required = Some(code != 0);
+                let method = match code {
+                    0 => "trust (no password)".to_string(),
+                    3 => "cleartext password".to_string(),
+                    5 => "md5".to_string(),
+                    7 => "GSSAPI".to_string(),
+                    9 => "SSPI".to_string(),
+                    10 => String::from_utf8_lossy(&payload[4..])
+                        .split('\0')
+                        .filter(|m| !m.is_empty())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    other => format!("method {}", other),
+                };
+                details.push(("Auth".to_string(), method));
+            }
+            b'S' => {
+                let mut parts = payload.split(|&b| b == 0);
+                if parts.next() == Some(b"server_version".as_slice()) {
+                    version = parts.next().map(|v| String::from_utf8_lossy(v).into_owned());
+                }
+            }
+            b'E' => {
+                // Fields: 1-byte code + C string, terminated by a zero byte.
+                let message = payload
+                    .split(|&b| b == 0)
+                    .find(|f| f.first() == Some(&b'M'))
+                    .map(|f| String::from_utf8_lossy(&f[1..]).into_owned());
+                if let Some(m) = message {
+                    details.push(("Refused".to_string(), m));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let summary = details
+        .iter()
+        .find(|(k, _)| k == "Auth")
+        .map(|(_, v)| format!(" (auth: {})", v))
+        .unwrap_or_default();
+    Some(HandshakeInfo {
+        kind: Some(ServerKind::PostgreSQL),
+        product: "PostgreSQL".to_string(),
+        banner: match &version {
+            Some(v) => format!("PostgreSQL {}{}", v, summary),
+            None => format!("PostgreSQL{}", summary),
+        },
+        version: version.map(|v| v.split_whitespace().next().unwrap_or(&v).to_string()),
+        details,
+        auth_required,
+    })
+}
+
+// ─── MongoDB ────────────────────────────────────────────────────────────────
+
+/// Send `hello` and `buildInfo` as OP_MSG commands. Both are allowed
+/// before authentication on every supported MongoDB release; `listDatabases`
+/// is not, so its answer tells whether access control is on.
+fn mongodb_handshake(stream: &mut TcpStream, _ctx: &ProbeContext) -> Option<HandshakeInfo> {
+    let hello = mongo_command(stream, 1, "hello")?;
+    let build = mongo_command(stream, 2, "buildInfo").unwrap_or_default();
+    let list = mongo_command(stream, 3, "listDatabases").unwrap_or_default();
+
+    let get = |doc: &[(String, String)], key: &str| {
+        doc.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone())
+    };
+
+    let mut details = Vec::new();
+    if let Some(w) = get(&hello, "maxWireVersion") {
+        details.push(("Wire version".to_string(), w));
+    }
+    if let Some(set) = get(&hello, "setName") {
+        details.push(("Replica set".to_string(), set));
+    }
+    if get(&hello, "msg").as_deref() == Some("isdbgrid") {
+        details.push(("Role".to_string(), "mongos router".to_string()));
+    } else if let Some(primary) = get(&hello, "isWritablePrimary") {
+        details.push(("Writable primary".to_string(), primary));
+    }
+    let version = get(&build, "version");
+    if version.is_none() {
+        if let Some(err) = get(&build, "errmsg") {
+            details.push(("buildInfo".to_string(), err));
+        }
+    }
+    let auth_required = mongo_auth_required(&list);
+    match auth_required {
+        Some(true) => details.push(("Auth".to_string(), "required".to_string())),
+        Some(false) => details.push(("Auth".to_string(), "none required".to_string())),
+        None => {}
+    }
+
+    Some(HandshakeInfo {
+        kind: Some(ServerKind::MongoDB),
+        product: "MongoDB".to_string(),
+        banner: match &version {
+            Some(v) => format!("MongoDB {}", v),
+            None => "MongoDB".to_string(),
+        },
+        version,
+        details,
+        auth_required,
+    })
+}
+
+/// Read the `listDatabases` reply: success means no access control,
+/// Unauthorized (code 13) means credentials are required.
+fn mongo_auth_required(reply: &[(String, String)]) -> Option<bool> {
+    let get = |key: &str| reply.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str());
+    if get("ok") == Some("1") {
+        Some(false)
+    } else if get("code") == Some("13") || get("codeName") == Some("Unauthorized") {
+        Some(true)
+    } else {
+        None
+    }
+}
+
+fn mongo_command(stream: &mut TcpStream, request_id: i32, command: &str) -> Option<Vec<(String, String)>> {
+    stream.write_all(&mongo_op_msg(request_id, command)).ok()?;
+    let reply = read_until(stream, Vec::new(), |b| {
+        le_i32(b, 0).map(|len| b.len() >= len.max(0) as usize).unwrap_or(false)
+    });
+    parse_mongo_reply(&reply)
+}
+
+/// Build an OP_MSG carrying `{ <command>: 1, $db: "admin" }`.
+fn mongo_op_msg(request_id: i32, command: &str) -> Vec<u8> {
+    const OP_MSG: i32 = 2013;
+    let mut doc = Vec::new();
+    doc.push(0x10); // int32
+    doc.extend_from_slice(command.as_bytes());
+    doc.push(0);
+    doc.extend_from_slice(&1i32.to_le_bytes());
+    doc.push(0x02); // string
+    doc.extend_from_slice(b"$db\0");
+    doc.extend_from_slice(&6i32.to_le_bytes());
+    doc.extend_from_slice(b"admin\0");
+    doc.push(0);
+    let mut bson = ((doc.len() + 4) as i32).to_le_bytes().to_vec();
+    bson.extend_from_slice(&doc);
+
+    let total = 16 + 4 + 1 + bson.len();
+    let mut msg = Vec::with_capacity(total);
+    msg.extend_from_slice(&(total as i32).to_le_bytes());
+    msg.extend_from_slice(&request_id.to_le_bytes());
+    msg.extend_from_slice(&0i32.to_le_bytes());
+    msg.extend_from_slice(&OP_MSG.to_le_bytes());
+    msg.extend_from_slice(&0u32.to_le_bytes()); // flagBits
+    msg.push(0); // section kind 0: body
+    msg.extend_from_slice(&bson);
+    msg
+}
+
+/// Parse an OP_MSG reply into its top-level scalar fields.
+fn parse_mongo_reply(reply: &[u8]) -> Option<Vec<(String, String)>> {
+    const OP_MSG: i32 = 2013;
+    if le_i32(reply, 12)? != OP_MSG || reply.get(20) != Some(&0) {
+        return None;
+    }
+    parse_bson_scalars(reply.get(21..)?)
+}
+
+/// Top-level scalar fields of a BSON document, rendered as strings.
+/// Nested documents and arrays are skipped.
+fn parse_bson_scalars(doc: &[u8]) -> Option<Vec<(String, String)>> {
+    // Lengths come from the server: negative or overflowing ones end the parse
+    let len = le_len(doc, 0)?;
+    let doc = doc.get(..len)?;
+    let mut out = Vec::new();
+    let mut at = 4;
+    while at < doc.len() && doc[at] != 0 {
+        let ty = doc[at];
+        let (name, next) = cstr(doc, at + 1)?;
+        at = next;
+        let (value, size) = match ty {
+            0x01 => (f64::from_le_bytes(doc.get(at..at + 8)?.try_into().ok()?).to_string(), 8),
+            0x02 => {
+                let n = le_len(doc, at)?;
+                let s = doc.get(at + 4..(at + 4).checked_add(n.saturating_sub(1))?)?;
+                (String::from_utf8_lossy(s).into_owned(), n.checked_add(4)?)
+            }
+            0x03 | 0x04 => (String::new(), le_len(doc, at)?),
+            0x07 => (String::new(), 12),
+            0x08 => ((doc.get(at)? != &0).to_string(), 1),
+            0x09 | 0x11 => (String::new(), 8),
+            0x0A => (String::new(), 0),
+            0x10 => (le_i32(doc, at)?.to_string(), 4),
+            0x12 => (i64::from_le_bytes(doc.get(at..at + 8)?.try_into().ok()?).to_string(), 8),
+            _ => break,
+        };
+        if !matches!(ty, 0x03 | 0x04 | 0x07 | 0x09 | 0x0A | 0x11) {
+            out.push((name, value));
+        }
+        at = at.checked_add(size)?;
+    }
+    Some(out)
+}
+
+// ─── Redis ──────────────────────────────────────────────────────────────────
+
+/// PING, then `INFO server` for the exact version when no auth is required.
+fn redis_handshake(stream: &mut TcpStream, _ctx: &ProbeContext) -> Option<HandshakeInfo> {
+    stream.write_all(b"PING\r\n").ok()?;
+    let mut buf = [0u8; 256];
+    let n = stream.read(&mut buf).ok()?;
+    let response = std::str::from_utf8(&buf[..n]).ok()?.trim();
+
+    if let Some(err) = response.strip_prefix('-') {
+        // -NOAUTH / -DENIED: protected instance — still Redis.
+        let msg = err.lines().next().unwrap_or("auth required").to_string();
+        return Some(HandshakeInfo {
+            kind: Some(ServerKind::Redis),
+            product: "Redis".to_string(),
+            version: None,
+            banner: format!("Redis ({})", msg),
+            details: vec![("Auth".to_string(), msg)],
+            auth_required: Some(true),
+        });
+    }
+    if !response.starts_with("+PONG") {
+        return None;
+    }
+
+    let mut details = vec![("Auth".to_string(), "none required".to_string())];
+    let mut version = None;
+    let mut product = "Redis".to_string();
+    if stream.write_all(b"INFO server\r\n").is_ok() {
+        let reply = read_until(stream, Vec::new(), redis_bulk_complete);
+        let fields = parse_redis_info(&String::from_utf8_lossy(&reply));
+        for (key, value) in fields {
+            match key.as_str() {
+                "redis_version" => version = Some(value),
+                "dragonfly_version" => product = "Dragonfly".to_string(),
+                "redis_mode" => details.push(("Mode".to_string(), value)),
+                "os" => details.push(("OS".to_string(), value)),
+                "uptime_in_days" => details.push(("Uptime".to_string(), format!("{} days", value))),
+                _ => {}
+            }
+        }
+    }
+
+    Some(HandshakeInfo {
+        kind: Some(ServerKind::Redis),
+        banner: match &version {
+            Some(v) => format!("{} {}", product, v),
+            None => product.clone(),
+        },
+        product,
+        version,
+        details,
+        auth_required: Some(false),
+    })
+}
+
+/// A RESP bulk string ("$<len>\r\n<data>\r\n") has fully arrived.
+fn redis_bulk_complete(buf: &[u8]) -> bool {
+    let text = String::from_utf8_lossy(buf);
+    let Some(header_end) = text.find("\r\n") else { return false };
+    match text[..header_end].strip_prefix('$').and_then(|n| n.parse::<usize>().ok()) {
+        Some(len) => buf.len() >= header_end + 2 + len,
+        None => true,
+    }
+}
+
+fn parse_redis_info(text: &str) -> Vec<(String, String)> {
+    text.lines()
+        .filter(|l| !l.starts_with('#') && !l.starts_with('$'))
+        .filter_map(|l| l.trim().split_once(':'))
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+// ─── memcached ──────────────────────────────────────────────────────────────
+
+fn memcached_handshake(stream: &mut TcpStream, _ctx: &ProbeContext) -> Option<HandshakeInfo> {
+    stream.write_all(b"stats\r\n").ok()?;
+    let reply = read_until(stream, Vec::new(), |b| b.ends_with(b"END\r\n") || b.ends_with(b"ERROR\r\n"));
+    let stats = parse_memcached_stats(&String::from_utf8_lossy(&reply));
+    let version = stats.iter().find(|(k, _)| k == "version").map(|(_, v)| v.clone())?;
+
+    let mut details = Vec::new();
+    for (key, label) in [("uptime", "Uptime (s)"), ("curr_connections", "Connections"), ("curr_items", "Items")] {
+        if let Some((_, v)) = stats.iter().find(|(k, _)| k == key) {
+            details.push((label.to_string(), v.clone()));
+        }
+    }
+
+    Some(HandshakeInfo {
+        kind: Some(ServerKind::Memcached),
+        product: "memcached".to_string(),
+        banner: format!("memcached {}", version),
+        version: Some(version),
+        details,
+        auth_required: None,
+    })
+}
+
+fn parse_memcached_stats(text: &str) -> Vec<(String, String)> {
+    text.lines()
+        .filter_map(|l| l.trim().strip_prefix("STAT "))
+        .filter_map(|l| l.split_once(' '))
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+// ─── AMQP ───────────────────────────────────────────────────────────────────
+
+/// Send the AMQP 0-9-1 protocol header. A 0-9-1 broker answers with
+/// Connection.Start carrying its server properties; a broker that only speaks
+/// another version echoes back the header it does support.
+fn amqp_handshake(stream: &mut TcpStream, _ctx: &ProbeContext) -> Option<HandshakeInfo> {
+    stream.write_all(b"AMQP\x00\x00\x09\x01").ok()?;
+    let reply = read_until(stream, Vec::new(), |b| {
+        (b.starts_with(b"AMQP") && b.len() >= 8)
+            || be_u32(b, 3).map(|size| b.len() >= size as usize + 8).unwrap_or(false)
+    });
+    parse_amqp_reply(&reply)
+}
+
+fn parse_amqp_reply(reply: &[u8]) -> Option<HandshakeInfo> {
+    if let Some(header) = reply.strip_prefix(b"AMQP") {
+        let proto = match header.get(..4)? {
+            [_, 1, 0, 0] | [_, 1, 0, 10] => "AMQP 1.0".to_string(),
+            [id, major, minor, rev] => format!("AMQP {}-{}-{} (id {})", major, minor, rev, id),
+            _ => return None,
+        };
+        return Some(HandshakeInfo {
+            kind: None,
+            product: "AMQP broker".to_string(),
+            version: None,
+            banner: format!("{} broker", proto),
+            details: vec![("Protocol".to_string(), proto)],
+            auth_required: None,
+        });
+    }
+
+    // Method frame on channel 0: Connection (10) . Start (10)
+    if reply.first() != Some(&1) || be_u16(reply, 7)? != 10 || be_u16(reply, 9)? != 10 {
+        return None;
+    }
+    let (major, minor) = (*reply.get(11)?, *reply.get(12)?);
+    let table_len = be_u32(reply, 13)? as usize;
+    let props = parse_amqp_table(reply.get(17..17 + table_len)?);
+    let mech_at = 17 + table_len;
+    let mechanisms = be_u32(reply, mech_at)
+        .and_then(|n| reply.get(mech_at + 4..mech_at + 4 + n as usize))
+        .map(|m| String::from_utf8_lossy(m).into_owned());
+
+    let prop = |key: &str| props.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone());
+    let product = prop("product").unwrap_or_else(|| "AMQP broker".to_string());
+    let version = prop("version");
+    let lower = product.to_lowercase();
+    let kind = if lower.contains("rabbitmq") {
+        Some(ServerKind::RabbitMQ)
+    } else if lower.contains("activemq") || lower.contains("artemis") {
+        Some(ServerKind::ActiveMQ)
+    } else {
+        None
+    };
+
+    let mut details = vec![("Protocol".to_string(), format!("AMQP {}-{}", major, minor))];
+    for (key, label) in [("platform", "Platform"), ("cluster_name", "Cluster")] {
+        if let Some(v) = prop(key) {
+            details.push((label.to_string(), v));
+        }
+    }
+    if let Some(m) = mechanisms {
+        details.push(("Auth".to_string(), m));
+    }
+
+    Some(HandshakeInfo {
+        kind,
+        banner: match &version {
+            Some(v) => format!("AMQP {} {}", product, v),
+            None => format!("AMQP {}", product),
+        },
+        product,
+        version,
+        details,
+        auth_required: None,
+    })
+}
+
+/// String-valued entries of an AMQP field table; other value types are skipped.
+fn parse_amqp_table(table: &[u8]) -> Vec<(String, String)> {
+    let mut out = Vec::new();
+    let mut at = 0;
+    while at < table.len() {
+        let Some(&key_len) = table.get(at) else { break };
+        let Some(key) = table.get(at + 1..at + 1 + key_len as usize) else { break };
+        at += 1 + key_len as usize;
+        let Some(&ty) = table.get(at) else { break };
+        at += 1;
+        let size = match ty {
+            b't' | b'b' | b'B' => 1,
+            b's' | b'u' => 2,
+            b'I' | b'i' | b'f' => 4,
+            b'D' => 5,
+            b'l' | b'L' | b'd' | b'T' => 8,
+            b'V' => 0,
+            b'S' | b'x' | b'F' | b'A' => match be_u32(table, at) {
+                Some(n) => 4 + n as usize,
+                None => break,
+            },
+            _ => break,
+        };
+        if ty == b'S' {
+            if let Some(v) = table.get(at + 4..at + size) {
+                out.push((String::from_utf8_lossy(key).into_owned(), String::from_utf8_lossy(v).into_owned()));
+            }
+        }
+        at += size;
+    }
+    out
+}
+
+// ─── MQTT ───────────────────────────────────────────────────────────────────
+
+/// Send an anonymous MQTT 3.1.1 CONNECT and read the CONNACK return code.
+fn mqtt_handshake(stream: &mut TcpStream, _ctx: &ProbeContext) -> Option<HandshakeInfo> {
+    const CONNECT: &[u8] = &[
+        0x10, 23, // CONNECT, remaining length
+        0x00, 0x04, b'M', b'Q', b'T', b'T', // protocol name
+        0x04, // level 4 = 3.1.1
+        0x02, // clean session
+        0x00, 0x1e, // keep-alive 30s
+        0x00, 0x0b, b'p', b's', b'n', b'e', b't', b'-', b'p', b'r', b'o', b'b', b'e',
+    ];
+    stream.write_all(CONNECT).ok()?;
+    let mut connack = [0u8; 4];
+    stream.read_exact(&mut connack).ok()?;
+    let _ = stream.write_all(&[0xe0, 0x00]); // DISCONNECT
+    parse_mqtt_connack(&connack)
+}
+
+/// CONNACK proves an MQTT broker but not which one (Mosquitto, EMQX,
+/// HiveMQ, VerneMQ, RabbitMQ's plugin all answer alike), so the kind is left
+/// to process and port evidence.
+fn parse_mqtt_connack(connack: &[u8; 4]) -> Option<HandshakeInfo> {
+    if connack[0] != 0x20 || connack[1] != 0x02 {
+        return None;
+    }
+    let auth_required = match connack[3] {
+        0 => Some(false),
+        4 | 5 => Some(true),
+        _ => None,
+    };
+    let anonymous = match connack[3] {
+        0 => "allowed",
+        1 => "protocol 3.1.1 rejected",
+        2 => "client id rejected",
+        3 => "server unavailable",
+        4 | 5 => "refused (credentials required)",
+        _ => "refused",
+    };
+    Some(HandshakeInfo {
+        kind: None,
+        product: "MQTT broker".to_string(),
+        version: None,
+        banner: format!("MQTT broker (anonymous {})", anonymous),
+        details: vec![
+            ("Protocol".to_string(), "MQTT 3.1.1".to_string()),
+            ("Anonymous".to_string(), anonymous.to_string()),
+        ],
+        auth_required,
+    })
+}
+
+// ─── Elasticsearch / OpenSearch ─────────────────────────────────────────────
+
+/// GET / and read the cluster info document.
+fn elasticsearch_handshake(stream: &mut TcpStream, ctx: &ProbeContext) -> Option<HandshakeInfo> {
+    let request = format!(
+        "GET / HTTP/1.0\r\nHost: {}\r\nAccept: application/json\r\nUser-Agent: psnet/1.0\r\n\r\n",
+        ctx.addr
+    );
+    stream.write_all(request.as_bytes()).ok()?;
+    let reply = read_until(stream, Vec::new(), |_| false);
+    parse_elasticsearch_reply(&String::from_utf8_lossy(&reply))
+}
+
+fn parse_elasticsearch_reply(reply: &str) -> Option<HandshakeInfo> {
+    let (head, body) = reply.split_once("\r\n\r\n")?;
+    let head_lower = head.to_lowercase();
+    let status = head.split_whitespace().nth(1)?;
+
+    // Secured clusters answer 401 but still identify themselves.
+    if status == "401" {
+        if !head_lower.contains("x-elastic-product") && !head_lower.contains("realm=\"security\"") {
+            return None;
+        }
+        return Some(HandshakeInfo {
+            kind: Some(ServerKind::Elasticsearch),
+            product: "Elasticsearch".to_string(),
+            version: None,
+            banner: "Elasticsearch (security enabled)".to_string(),
+            details: vec![("Auth".to_string(), "required".to_string())],
+            auth_required: Some(true),
+        });
+    }
+
+    let json: serde_json::Value = serde_json::from_str(body.trim()).ok()?;
+    let version = json.get("version")?;
+    let number = version.get("number")?.as_str()?.to_string();
+    if json.get("cluster_name").is_none() && json.get("tagline").is_none() {
+        return None;
+    }
+    let product = match version.get("distribution").and_then(|d| d.as_str()) {
+        Some("opensearch") => "OpenSearch",
+        _ => "Elasticsearch",
+    };
+
+    let mut details = vec![("Auth".to_string(), "none required".to_string())];
+    if let Some(cluster) = json.get("cluster_name").and_then(|v| v.as_str()) {
+        details.push(("Cluster".to_string(), cluster.to_string()));
+    }
+    if let Some(node) = json.get("name").and_then(|v| v.as_str()) {
+        details.push(("Node".to_string(), node.to_string()));
+    }
+    if let Some(lucene) = version.get("lucene_version").and_then(|v| v.as_str()) {
+        details.push(("Lucene".to_string(), lucene.to_string()));
+    }
+
+    Some(HandshakeInfo {
+        kind: Some(ServerKind::Elasticsearch),
+        product: product.to_string(),
+        banner: format!("{} {}", product, number),
+        version: Some(number),
+        details,
+        auth_required: Some(false),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ssh_ident() {
+        let (product, version, comment) = parse_ssh_ident("SSH-2.0-OpenSSH_9.6p1 Ubuntu-3ubuntu13").unwrap();
+        assert_eq!(product, "OpenSSH");
+        assert_eq!(version.as_deref(), Some("9.6p1"));
+        assert_eq!(comment.as_deref(), Some("Ubuntu-3ubuntu13"));
+
+        let (product, version, comment) = parse_ssh_ident("SSH-2.0-dropbear_2022.83").unwrap();
+        assert_eq!(product, "dropbear");
+        assert_eq!(version.as_deref(), Some("2022.83"));
+        assert!(comment.is_none());
+
+        assert!(parse_ssh_ident("HTTP/1.1 200 OK").is_none());
+    }
+
+    #[test]
+    fn test_parse_kexinit() {
+        let mut payload = vec![20u8];
+        payload.extend_from_slice(&[0xAA; 16]);
+        for list in ["curve25519-sha256,diffie-hellman-group14-sha256", "ssh-ed25519,rsa-sha2-512"] {
+            payload.extend_from_slice(&(list.len() as u32).to_be_bytes());
+            payload.extend_from_slice(list.as_bytes());
+        }
+        let padding = 4usize;
+        let mut packet = ((payload.len() + padding + 1) as u32).to_be_bytes().to_vec();
+        packet.push(padding as u8);
+        packet.extend_from_slice(&payload);
+        packet.extend_from_slice(&[0; 4]);
+
+        let (kex, host_keys) = parse_kexinit(&packet).unwrap();
+        assert_eq!(kex[0], "curve25519-sha256");
+        assert_eq!(host_keys, vec!["ssh-ed25519", "rsa-sha2-512"]);
+    }
+
+    fn mysql_packet(version: &str, plugin: &str, caps: u32) -> Vec<u8> {
+        let mut body = vec![0x0a];
+        body.extend_from_slice(version.as_bytes());
+        body.push(0);
+        body.extend_from_slice(&7u32.to_le_bytes()); // thread id
+        body.extend_from_slice(&[1; 8]); // auth data part 1
+        body.push(0);
+        body.extend_from_slice(&(caps as u16).to_le_bytes());
+        body.push(0xff); // charset
+        body.extend_from_slice(&2u16.to_le_bytes()); // status
+        body.extend_from_slice(&((caps >> 16) as u16).to_le_bytes());
+        body.push(21); // auth data len
+        body.extend_from_slice(&[0; 10]);
+        body.extend_from_slice(&[2; 13]); // auth data part 2
+        body.extend_from_slice(plugin.as_bytes());
+        body.push(0);
+        let mut pkt = (body.len() as u32).to_le_bytes()[..3].to_vec();
+        pkt.push(0);
+        pkt.extend_from_slice(&body);
+        pkt
+    }
+
+    #[test]
+    fn test_parse_mysql_greeting() {
+        let pkt = mysql_packet("8.0.36-0ubuntu0.22.04.1", "caching_sha2_password", 0x0800);
+        assert!(is_mysql_greeting(&pkt, 3306));
+        let info = parse_mysql_greeting(&pkt).unwrap();
+        assert_eq!(info.kind, Some(ServerKind::MySQL));
+        assert_eq!(info.version.as_deref(), Some("8.0.36"));
+        assert!(info.details.contains(&("Auth plugin".to_string(), "caching_sha2_password".to_string())));
+        assert!(info.details.contains(&("TLS".to_string(), "supported".to_string())));
+
+        let pkt = mysql_packet("5.5.5-10.11.6-MariaDB-0+deb12u1", "mysql_native_password", 0);
+        let info = parse_mysql_greeting(&pkt).unwrap();
+        assert_eq!(info.kind, Some(ServerKind::MariaDB));
+        assert_eq!(info.version.as_deref(), Some("10.11.6"));
+    }
+
+    #[test]
+    fn test_parse_mysql_error_packet() {
+        let msg = b"Host '10.0.0.5' is not allowed to connect to this MySQL server";
+        let mut body = vec![0xff];
+        body.extend_from_slice(&1130u16.to_le_bytes());
+        body.extend_from_slice(msg);
+        let mut pkt = (body.len() as u32).to_le_bytes()[..3].to_vec();
+        pkt.push(0);
+        pkt.extend_from_slice(&body);
+        let info = parse_mysql_greeting(&pkt).unwrap();
+        assert!(info.version.is_none());
+        assert!(info.banner.contains("1130"));
+    }
+
+    #[test]
+    fn test_smtp_vs_ftp_greeting() {
+        assert!(is_smtp_greeting(b"220 mail.example.com ESMTP Postfix\r\n", 25));
+        assert!(!is_ftp_greeting(b"220 mail.example.com ESMTP Postfix\r\n", 25));
+        assert!(is_ftp_greeting(b"220 (vsFTPd 3.0.5)\r\n", 21));
+        assert!(is_ftp_greeting(b"220 Welcome\r\n", 21));
+        assert!(!is_smtp_greeting(b"220 ProFTPD Server ready\r\n", 2100));
+    }
+
+    #[test]
+    fn test_parse_ehlo_reply() {
+        let reply = "250-mail.example.com\r\n250-PIPELINING\r\n250-SIZE 10240000\r\n250-STARTTLS\r\n250-AUTH PLAIN LOGIN\r\n250 SMTPUTF8\r\n";
+        let caps = parse_ehlo_reply(reply);
+        assert_eq!(caps, vec!["PIPELINING", "SIZE 10240000", "STARTTLS", "AUTH PLAIN LOGIN", "SMTPUTF8"]);
+    }
+
+    #[test]
+    fn test_parse_feat_reply() {
+        let reply = "211-Features:\r\n EPRT\r\n MDTM\r\n AUTH TLS\r\n UTF8\r\n211 End\r\n";
+        assert_eq!(parse_feat_reply(reply), vec!["EPRT", "MDTM", "AUTH TLS", "UTF8"]);
+    }
+
+    #[test]
+    fn test_product_in_greeting() {
+        assert_eq!(
+            product_in_greeting("220 (vsFTPd 3.0.5)", FTP_PRODUCTS),
+            Some(("vsFTPd".to_string(), Some("3.0.5".to_string())))
+        );
+        assert_eq!(
+            product_in_greeting("220-FileZilla Server 1.8.1", FTP_PRODUCTS),
+            Some(("FileZilla Server".to_string(), Some("1.8.1".to_string())))
+        );
+        assert_eq!(
+            product_in_greeting("220 mx.example.org ESMTP Exim 4.96 Mon, 01 Jan 2024", SMTP_PRODUCTS),
+            Some(("Exim".to_string(), Some("4.96".to_string())))
+        );
+        assert_eq!(
+            product_in_greeting("220 mail.example.com ESMTP Postfix (Ubuntu)", SMTP_PRODUCTS),
+            Some(("Postfix".to_string(), None))
+        );
+    }
+
+    fn pg_message(tag: u8, payload: &[u8]) -> Vec<u8> {
+        let mut m = vec![tag];
+        m.extend_from_slice(&((payload.len() + 4) as u32).to_be_bytes());
+        m.extend_from_slice(payload);
+        m
+    }
+
+    #[test]
+    fn test_parse_postgres_sasl_reply() {
+        let mut payload = 10u32.to_be_bytes().to_vec();
+        payload.extend_from_slice(b"SCRAM-SHA-256\0\0");
+        let reply = pg_message(b'R', &payload);
+        assert!(postgres_reply_complete(&reply));
+        let info = parse_postgres_reply(&reply).unwrap();
+        assert_eq!(info.banner, "PostgreSQL (auth: SCRAM-SHA-256)");
+        assert!(info.version.is_none());
+        assert_eq!(info.auth_required, Some(true));
+    }
+
+    #[test]
+    fn test_parse_postgres_trust_reply() {
+        let mut reply = pg_message(b'R', &0u32.to_be_bytes());
+        assert!(!postgres_reply_complete(&reply));
+        reply.extend(pg_message(b'S', b"server_version\x0016.2 (Debian 16.2-1.pgdg120+2)\0"));
+        reply.extend(pg_message(b'Z', b"I"));
+        assert!(postgres_reply_complete(&reply));
+        let info = parse_postgres_reply(&reply).unwrap();
+        assert_eq!(info.version.as_deref(), Some("16.2"));
+        assert_eq!(info.auth_required, Some(false));
+    }
+
+    #[test]
+    fn test_postgres_startup_message_length() {
+        let msg = postgres_startup_message("psnet", "postgres");
+        assert_eq!(be_u32(&msg, 0), Some(msg.len() as u32));
+        assert_eq!(be_u32(&msg, 4), Some(196_608));
+        assert_eq!(msg.last(), Some(&0));
+    }
+
+    #[test]
+    fn test_mongo_op_msg_roundtrip() {
+        let msg = mongo_op_msg(1, "hello");
+        assert_eq!(le_i32(&msg, 0), Some(msg.len() as i32));
+        let fields = parse_mongo_reply(&msg).unwrap();
+        assert_eq!(fields[0], ("hello".to_string(), "1".to_string()));
+        assert_eq!(fields[1], ("$db".to_string(), "admin".to_string()));
+    }
+
+    #[test]
+    fn test_parse_bson_skips_nested() {
+        // { version: "7.0.5", versionArray: [7], ok: 1.0 }
+        let mut doc = Vec::new();
+        doc.push(0x02);
+        doc.extend_from_slice(b"version\0");
+        doc.extend_from_slice(&6i32.to_le_bytes());
+        doc.extend_from_slice(b"7.0.5\0");
+        doc.push(0x04);
+        doc.extend_from_slice(b"versionArray\0");
+        let arr = [12i32.to_le_bytes().as_slice(), &[0x10, b'0', 0], &7i32.to_le_bytes(), &[0]].concat();
+        doc.extend_from_slice(&arr);
+        doc.push(0x01);
+        doc.extend_from_slice(b"ok\0");
+        doc.extend_from_slice(&1.0f64.to_le_bytes());
+        doc.push(0);
+        let mut bson = ((doc.len() + 4) as i32).to_le_bytes().to_vec();
+        bson.extend_from_slice(&doc);
+
+        let fields = parse_bson_scalars(&bson).unwrap();
+        assert_eq!(fields, vec![
+            ("version".to_string(), "7.0.5".to_string()),
+            ("ok".to_string(), "1".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_mongo_auth_required() {
+        let field = |k: &str, v: &str| (k.to_string(), v.to_string());
+        assert_eq!(mongo_auth_required(&[field("totalSize", "8192"), field("ok", "1")]), Some(false));
+        let denied = [
+            field("ok", "0"),
+            field("errmsg", "command listDatabases requires authentication"),
+            field("code", "13"),
+            field("codeName", "Unauthorized"),
+        ];
+        assert_eq!(mongo_auth_required(&denied), Some(true));
+        assert_eq!(mongo_auth_required(&[]), None);
+    }
+
+    #[test]
+    fn test_parse_bson_rejects_bad_lengths() {
+        let bson = |doc: &[u8]| {
+            let mut out = ((doc.len() + 5) as i32).to_le_bytes().to_vec();
+            out.extend_from_slice(doc);
+            out.push(0);
+            out
+        };
+        // Negative string length
+        let mut doc = vec![0x02];
+        doc.extend_from_slice(b"version\0");
+        doc.extend_from_slice(&(-1i32).to_le_bytes());
+        doc.extend_from_slice(b"7.0\0");
+        assert!(parse_bson_scalars(&bson(&doc)).is_none());
+        // Nested document claiming a negative / huge size
+        for size in [-8i32, i32::MAX] {
+            let mut doc = vec![0x03];
+            doc.extend_from_slice(b"nested\0");
+            doc.extend_from_slice(&size.to_le_bytes());
+            doc.extend_from_slice(&[0x10, b'a', 0, 1, 0, 0, 0]);
+            let fields = parse_bson_scalars(&bson(&doc)).unwrap_or_default();
+            assert!(fields.is_empty(), "size {}: {:?}", size, fields);
+        }
+        // Negative document length
+        assert!(parse_bson_scalars(&(-5i32).to_le_bytes()).is_none());
+    }
+
+    #[test]
+    fn test_parse_redis_info() {
+        let reply = "$120\r\n# Server\r\nredis_version:7.2.4\r\nredis_mode:standalone\r\nos:Linux 6.1.0 x86_64\r\n";
+        let fields = parse_redis_info(reply);
+        assert!(fields.contains(&("redis_version".to_string(), "7.2.4".to_string())));
+        assert!(fields.contains(&("os".to_string(), "Linux 6.1.0 x86_64".to_string())));
+        assert!(redis_bulk_complete(b"$5\r\nhello\r\n"));
+        assert!(!redis_bulk_complete(b"$50\r\nhello"));
+    }
+
+    #[test]
+    fn test_parse_memcached_stats() {
+        let stats = parse_memcached_stats("STAT pid 1\r\nSTAT version 1.6.23\r\nSTAT uptime 42\r\nEND\r\n");
+        assert!(stats.contains(&("version".to_string(), "1.6.23".to_string())));
+        assert!(stats.contains(&("uptime".to_string(), "42".to_string())));
+    }
+
+    #[test]
+    fn test_parse_amqp_connection_start() {
+        let mut table = Vec::new();
+        for (k, v) in [("product", "RabbitMQ"), ("version", "3.13.0"), ("platform", "Erlang/OTP 26.2")] {
+            table.push(k.len() as u8);
+            table.extend_from_slice(k.as_bytes());
+            table.push(b'S');
+            table.extend_from_slice(&(v.len() as u32).to_be_bytes());
+            table.extend_from_slice(v.as_bytes());
+        }
+        // a nested table value that must be skipped
+        table.push(12);
+        table.extend_from_slice(b"capabilities");
+        table.push(b'F');
+        table.extend_from_slice(&0u32.to_be_bytes());
+
+        let mut payload = vec![0, 10, 0, 10, 0, 9];
+        payload.extend_from_slice(&(table.len() as u32).to_be_bytes());
+        payload.extend_from_slice(&table);
+        payload.extend_from_slice(&5u32.to_be_bytes());
+        payload.extend_from_slice(b"PLAIN");
+        let mut frame = vec![1, 0, 0];
+        frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        frame.extend_from_slice(&payload);
+        frame.push(0xce);
+
+        let info = parse_amqp_reply(&frame).unwrap();
+        assert_eq!(info.kind, Some(ServerKind::RabbitMQ));
+        assert_eq!(info.version.as_deref(), Some("3.13.0"));
+        assert!(info.details.contains(&("Auth".to_string(), "PLAIN".to_string())));
+    }
+
+    #[test]
+    fn test_parse_amqp_version_mismatch() {
+        let info = parse_amqp_reply(b"AMQP\x00\x01\x00\x00").unwrap();
+        assert!(info.kind.is_none());
+        assert_eq!(info.details[0].1, "AMQP 1.0");
+    }
+
+    #[test]
+    fn test_parse_mqtt_connack() {
+        let info = parse_mqtt_connack(&[0x20, 0x02, 0x00, 0x00]).unwrap();
+        assert!(info.banner.contains("allowed"));
+        assert_eq!(info.kind, None);
+        assert_eq!(info.auth_required, Some(false));
+        let info = parse_mqtt_connack(&[0x20, 0x02, 0x00, 0x05]).unwrap();
+        assert!(info.banner.contains("credentials"));
+        assert_eq!(info.auth_required, Some(true));
+        assert_eq!(parse_mqtt_connack(&[0x20, 0x02, 0x00, 0x03]).unwrap().auth_required, None);
+        assert!(parse_mqtt_connack(&[0x48, 0x54, 0x54, 0x50]).is_none());
+    }
+
+    #[test]
+    fn test_parse_elasticsearch_reply() {
+        let reply = "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\n\r\n{\"name\":\"node-1\",\"cluster_name\":\"docker-cluster\",\"version\":{\"number\":\"8.12.2\",\"lucene_version\":\"9.9.2\"},\"tagline\":\"You Know, for Search\"}";
+        let info = parse_elasticsearch_reply(reply).unwrap();
+        assert_eq!(info.product, "Elasticsearch");
+        assert_eq!(info.version.as_deref(), Some("8.12.2"));
+        assert_eq!(info.auth_required, Some(false));
+
+        let reply = "HTTP/1.1 200 OK\r\n\r\n{\"cluster_name\":\"c\",\"version\":{\"distribution\":\"opensearch\",\"number\":\"2.12.0\"}}";
+        assert_eq!(parse_elasticsearch_reply(reply).unwrap().product, "OpenSearch");
+
+        let reply = "HTTP/1.1 401 Unauthorized\r\nWWW-Authenticate: Basic realm=\"security\" charset=\"UTF-8\"\r\n\r\n{}";
+        let info = parse_elasticsearch_reply(reply).unwrap();
+        assert!(info.version.is_none());
+        assert_eq!(info.auth_required, Some(true));
+
+        assert!(parse_elasticsearch_reply("HTTP/1.1 200 OK\r\n\r\n<html></html>").is_none());
+    }
+}
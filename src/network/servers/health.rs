@@ -0,0 +1,537 @@
+//! User-defined health checks for listening services.
+//!
+//! Checks live in `%APPDATA%\psnet\healthchecks.json`, one per port:
+//!
+//! ```json
+//! [
+//!   { "port": 6379, "check": "redis" },
+//!   { "port": 8080, "check": "http", "path": "/healthz", "expect_status": 200, "body_regex": "\"ok\"" },
+//!   { "port": 5432, "check": "tcp", "interval_secs": 60 }
+//! ]
+//! ```
+//!
+//! `host` defaults to 127.0.0.1 and `interval_secs` to 30. Each check runs on
+//! its own thread when due; the latency of every run (or its failure) is kept
+//! for the sparkline, and a check going from passing to failing is queued for
+//! the alert engine. The file is re-read whenever its modification time
+//! changes, and `h` on the Servers tab adds or removes a check for the
+//! selected listener.
+
+use std::collections::VecDeque;
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config;
+use super::types::{ListeningPort, ServerKind};
+
+const CONFIG_FILE: &str = "healthchecks.json";
+/// Runs kept per check for the sparkline.
+const HISTORY_LEN: usize = 60;
+const TIMEOUT: Duration = Duration::from_secs(3);
+/// Most of an HTTP response read when matching the body.
+const MAX_BODY: usize = 64 * 1024;
+const MIN_INTERVAL_SECS: u64 = 5;
+
+// ─── Configuration ──────────────────────────────────────────────────────────
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HealthCheck {
+    pub port: u16,
+    #[serde(default = "default_host")]
+    pub host: String,
+    #[serde(flatten)]
+    pub kind: CheckKind,
+    #[serde(default = "default_interval")]
+    pub interval_secs: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "check", rename_all = "lowercase")]
+pub enum CheckKind {
+    /// The port accepts a TCP connection.
+    Tcp,
+    /// `GET path` answers with the expected status (any 2xx/3xx when unset)
+    /// and, if given, a body matching the regex.
+    Http {
+        #[serde(default = "default_path")]
+        path: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        expect_status: Option<u16>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        body_regex: Option<String>,
+    },
+    /// `PING` answers `+PONG` (or asks for authentication).
+    Redis,
+}
+
+fn default_host() -> String {
+    "127.0.0.1".to_string()
+}
+
+fn default_interval() -> u64 {
+    30
+}
+
+fn default_path() -> String {
+    "/".to_string()
+}
+
+impl HealthCheck {
+    /// The check `h` adds for a listener: PING for Redis, GET / for anything
+    /// that answered HTTP during the scan, a plain connect otherwise.
+    pub fn for_server(s: &ListeningPort) -> Self {
+        let kind = if s.server_kind == ServerKind::Redis {
+            CheckKind::Redis
+        } else if s.http_title.is_some() || !s.response_headers.is_empty() {
+            CheckKind::Http { path: default_path(), expect_status: None, body_regex: None }
+        } else {
+            CheckKind::Tcp
+        };
+        Self { port: s.port, host: default_host(), kind, interval_secs: default_interval() }
+    }
+
+    /// Short description, e.g. "HTTP GET /healthz → 200".
+    pub fn describe(&self) -> String {
+        match &self.kind {
+            CheckKind::Tcp => "TCP connect".to_string(),
+            CheckKind::Http { path, expect_status, body_regex } => {
+                let mut s = format!("HTTP GET {}", path);
+                if let Some(code) = expect_status {
+                    s.push_str(&format!(" → {}", code));
+                }
+                if let Some(re) = body_regex {
+                    s.push_str(&format!(" ~ /{}/", re));
+                }
+                s
+            }
+            CheckKind::Redis => "Redis PING".to_string(),
+        }
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_secs.max(MIN_INTERVAL_SECS))
+    }
+}
+
+fn parse_config(content: &str) -> Result<Vec<HealthCheck>, String> {
+    serde_json::from_str(content).map_err(|e| format!("invalid JSON ({})", e))
+}
+
+// ─── Running a check ────────────────────────────────────────────────────────
+
+/// Run one check, returning its latency in milliseconds or why it failed.
+pub fn run_check(check: &HealthCheck) -> Result<u64, String> {
+    let start = Instant::now();
+    let addr = resolve(&check.host, check.port)?;
+    let mut stream = TcpStream::connect_timeout(&addr, TIMEOUT).map_err(|e| format!("connect: {}", e))?;
+    let _ = stream.set_read_timeout(Some(TIMEOUT));
+    let _ = stream.set_write_timeout(Some(TIMEOUT));
+
+    match &check.kind {
+        CheckKind::Tcp => {}
+        CheckKind::Http { path, expect_status, body_regex } => {
+            let request = format!(
+                "GET {} HTTP/1.1\r\nHost: {}:{}\r\nUser-Agent: psnet-health\r\nConnection: close\r\n\r\n",
+                path, check.host, check.port,
+            );
+            stream.write_all(request.as_bytes()).map_err(|e| format!("send: {}", e))?;
+            let response = read_response(&mut stream)?;
+            check_http(&response, *expect_status, body_regex.as_deref())?;
+        }
+        CheckKind::Redis => {
+            stream.write_all(b"*1\r\n$4\r\nPING\r\n").map_err(|e| format!("send: {}", e))?;
+            let mut buf = [0u8; 256];
+            let n = stream.read(&mut buf).map_err(|e| format!("read: {}", e))?;
+            let reply = String::from_utf8_lossy(&buf[..n]);
+            // A server that wants AUTH is still up and speaking the protocol
+            if !(reply.starts_with("+PONG") || reply.starts_with("-NOAUTH")) {
+                return Err(format!("unexpected reply: {}", reply.lines().next().unwrap_or("(empty)")));
+            }
+        }
+    }
+    Ok(start.elapsed().as_millis() as u64)
+}
+
+fn resolve(host: &str, port: u16) -> Result<SocketAddr, String> {
+    (host, port)
+        .to_socket_addrs()
+        .map_err(|e| format!("resolve {}: {}", host, e))?
+        .next()
+        .ok_or_else(|| format!("resolve {}: no address", host))
+}
+
+fn read_response(stream: &mut TcpStream) -> Result<String, String> {
+    let mut data = Vec::new();
+    let mut buf = [0u8; 4096];
+    while data.len() < MAX_BODY {
+        match stream.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => data.extend_from_slice(&buf[..n]),
+            // Keep what arrived before a slow server stopped sending
+            Err(_) if !data.is_empty() => break,
+            Err(e) => return Err(format!("read: {}", e)),
+        }
+    }
+    if data.is_empty() {
+        return Err("empty response".to_string());
+    }
+    Ok(String::from_utf8_lossy(&data).into_owned())
+}
+
+fn check_http(response: &str, expect_status: Option<u16>, body_regex: Option<&str>) -> Result<(), String> {
+    let status_line = response.lines().next().unwrap_or("");
+    let status: u16 = status_line
+        .strip_prefix("HTTP/")
+        .and_then(|rest| rest.split_whitespace().nth(1))
+        .and_then(|code| code.parse().ok())
+        .ok_or_else(|| "not an HTTP response".to_string())?;
+
+    let status_ok = match expect_status {
+        Some(code) => status == code,
+        None => (200..400).contains(&status),
+    };
+    if !status_ok {
+        return Err(format!("HTTP {}", status));
+    }
+
+    if let Some(pattern) = body_regex {
+        let re = regex::Regex::new(pattern).map_err(|e| format!("bad body_regex: {}", e))?;
+        let body = response.split_once("\r\n\r\n").map(|(_, b)| b).unwrap_or("");
+        if !re.is_match(body) {
+            return Err(format!("body does not match /{}/", pattern));
+        }
+    }
+    Ok(())
+}
+
+// ─── Monitor ────────────────────────────────────────────────────────────────
+
+/// A configured check and its recent results.
+pub struct CheckState {
+    pub check: HealthCheck,
+    /// Latency of each run in ms, oldest first; None for a failed run.
+    pub history: VecDeque<Option<u64>>,
+    pub last_error: Option<String>,
+    pub consecutive_failures: u32,
+    last_run: Option<Instant>,
+    running: bool,
+}
+
+impl CheckState {
+    fn new(check: HealthCheck) -> Self {
+        Self {
+            check,
+            history: VecDeque::new(),
+            last_error: None,
+            consecutive_failures: 0,
+            last_run: None,
+            running: false,
+        }
+    }
+
+    /// None until the first run completes.
+    pub fn is_passing(&self) -> Option<bool> {
+        self.history.back().map(|r| r.is_some())
+    }
+
+    pub fn last_latency(&self) -> Option<u64> {
+        self.history.back().copied().flatten()
+    }
+
+    fn record(&mut self, result: Result<u64, String>) {
+        self.running = false;
+        match result {
+            Ok(ms) => {
+                self.history.push_back(Some(ms));
+                self.last_error = None;
+                self.consecutive_failures = 0;
+            }
+            Err(e) => {
+                self.history.push_back(None);
+                self.last_error = Some(e);
+                self.consecutive_failures += 1;
+            }
+        }
+        while self.history.len() > HISTORY_LEN {
+            self.history.pop_front();
+        }
+    }
+}
+
+/// A check that was passing (or had not run yet) and has just failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HealthFailure {
+    pub name: String,
+    pub port: u16,
+    pub check: String,
+    pub error: String,
+}
+
+type CheckResult = (HealthCheck, Result<u64, String>);
+
+pub struct HealthMonitor {
+    pub checks: Vec<CheckState>,
+    /// Failures not yet raised; taken by the app and passed to the alert engine.
+    pub new_failures: Vec<HealthFailure>,
+    /// Problem reading the config file; taken for the status line.
+    pub error: Option<String>,
+    results: Arc<Mutex<Vec<CheckResult>>>,
+    path: PathBuf,
+    loaded_stamp: Option<Option<SystemTime>>,
+    /// Set when `checks` was edited in the app and not written yet.
+    dirty: bool,
+}
+
+impl HealthMonitor {
+    pub fn new() -> Self {
+        let mut monitor = Self::with_path(config::path(CONFIG_FILE));
+        monitor.reload_if_changed();
+        monitor
+    }
+
+    fn with_path(path: PathBuf) -> Self {
+        Self {
+            checks: Vec::new(),
+            new_failures: Vec::new(),
+            error: None,
+            results: Arc::new(Mutex::new(Vec::new())),
+            path,
+            loaded_stamp: None,
+            dirty: false,
+        }
+    }
+
+    /// Check for config edits, collect finished runs and start the due ones.
+    pub fn tick(&mut self, servers: &[ListeningPort]) {
+        self.reload_if_changed();
+        self.poll_results(servers);
+
+        let now = Instant::now();
+        for state in &mut self.checks {
+            let due = !state.running && state.last_run.is_none_or(|t| now.duration_since(t) >= state.check.interval());
+            if !due {
+                continue;
+            }
+            state.running = true;
+            state.last_run = Some(now);
+            let check = state.check.clone();
+            let results = Arc::clone(&self.results);
+            thread::spawn(move || {
+                let result = run_check(&check);
+                if let Ok(mut r) = results.lock() {
+                    r.push((check, result));
+                }
+            });
+        }
+    }
+
+    fn poll_results(&mut self, servers: &[ListeningPort]) {
+        let finished = match self.results.lock() {
+            Ok(mut r) => std::mem::take(&mut *r),
+            Err(_) => return,
+        };
+        for (check, result) in finished {
+            // The check may have been edited or removed while it was running
+            let Some(state) = self.checks.iter_mut().find(|s| s.check == check) else {
+                continue;
+            };
+            let was_failing = state.consecutive_failures > 0;
+            state.record(result);
+            if !was_failing {
+                if let Some(ref error) = state.last_error {
+                    let name = servers
+                        .iter()
+                        .find(|s| s.port == check.port)
+                        .map(|s| s.display_name())
+                        .unwrap_or_else(|| format!("{}:{}", check.host, check.port));
+                    self.new_failures.push(HealthFailure {
+                        name,
+                        port: check.port,
+                        check: check.describe(),
+                        error: error.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    /// Re-read the config file if it changed on disk. Checks that are still
+    /// configured keep their history.
+    fn reload_if_changed(&mut self) {
+        let stamp = config::modified(&self.path);
+        if self.loaded_stamp == Some(stamp) {
+            return;
+        }
+        self.loaded_stamp = Some(stamp);
+
+        let parsed = match std::fs::read_to_string(&self.path) {
+            Ok(content) => parse_config(&content),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e.to_string()),
+        };
+        match parsed {
+            Ok(checks) => {
+                self.error = None;
+                self.set_checks(checks);
+            }
+            Err(e) => self.error = Some(format!("Health checks: {}", e)),
+        }
+    }
+
+    fn set_checks(&mut self, checks: Vec<HealthCheck>) {
+        let mut old = std::mem::take(&mut self.checks);
+        self.checks = checks
+            .into_iter()
+            .map(|c| match old.iter().position(|s| s.check == c) {
+                Some(i) => old.swap_remove(i),
+                None => CheckState::new(c),
+            })
+            .collect();
+    }
+
+    /// The check configured for a listener port.
+    pub fn for_port(&self, port: u16) -> Option<&CheckState> {
+        self.checks.iter().find(|s| s.check.port == port)
+    }
+
+    /// Add the default check for a listener, or remove its check if it has
+    /// one. Returns whether the listener is now checked.
+    pub fn toggle(&mut self, server: &ListeningPort) -> bool {
+        self.dirty = true;
+        if let Some(i) = self.checks.iter().position(|s| s.check.port == server.port) {
+            self.checks.remove(i);
+            return false;
+        }
+        self.checks.push(CheckState::new(HealthCheck::for_server(server)));
+        true
+    }
+
+    /// Write the config file if checks were added or removed in the app.
+    pub fn save_if_dirty(&mut self) {
+        if !self.dirty {
+            return;
+        }
+        self.dirty = false;
+        let checks: Vec<&HealthCheck> = self.checks.iter().map(|s| &s.check).collect();
+        if let Ok(json) = serde_json::to_string_pretty(&checks) {
+            if let Some(dir) = self.path.parent() {
+                let _ = std::fs::create_dir_all(dir);
+            }
+            let _ = std::fs::write(&self.path, json);
+        }
+        // Our own write is not an external edit
+        self.loaded_stamp = Some(config::modified(&self.path));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    /// Serve one connection on a local port with a canned reply.
+    fn stand_in(reply: &'static [u8]) -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        thread::spawn(move || {
+            if let Ok((mut conn, _)) = listener.accept() {
+                let mut buf = [0u8; 1024];
+                let _ = conn.read(&mut buf);
+                let _ = conn.write_all(reply);
+            }
+        });
+        port
+    }
+
+    fn check(port: u16, kind: CheckKind) -> HealthCheck {
+        HealthCheck { port, host: default_host(), kind, interval_secs: 30 }
+    }
+
+    fn http(path: &str, expect_status: Option<u16>, body_regex: Option<&str>) -> CheckKind {
+        CheckKind::Http {
+            path: path.into(),
+            expect_status,
+            body_regex: body_regex.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_parse_config() {
+        let checks = parse_config(
+            r#"[{"port":6379,"check":"redis"},
+                {"port":8080,"check":"http","path":"/healthz","expect_status":204},
+                {"port":22,"host":"10.0.0.5","check":"tcp","interval_secs":60}]"#,
+        )
+        .unwrap();
+        assert_eq!(checks[0], check(6379, CheckKind::Redis));
+        assert_eq!(checks[1].kind, http("/healthz", Some(204), None));
+        assert_eq!(checks[2].host, "10.0.0.5");
+        assert_eq!(checks[2].interval_secs, 60);
+        assert!(parse_config(r#"[{"port":1,"check":"icmp"}]"#).is_err());
+    }
+
+    #[test]
+    fn test_tcp_check() {
+        let port = stand_in(b"");
+        assert!(run_check(&check(port, CheckKind::Tcp)).is_ok());
+
+        // Nothing listens on a port we just released
+        let closed = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        assert!(run_check(&check(closed, CheckKind::Tcp)).is_err());
+    }
+
+    #[test]
+    fn test_http_check() {
+        let ok = b"HTTP/1.1 200 OK\r\nContent-Length: 15\r\n\r\n{\"status\":\"up\"}";
+        assert!(run_check(&check(stand_in(ok), http("/", None, Some(r#""status":"up""#)))).is_ok());
+
+        let err = run_check(&check(stand_in(ok), http("/", None, Some("down")))).unwrap_err();
+        assert!(err.contains("does not match"));
+
+        let err = run_check(&check(stand_in(ok), http("/", Some(204), None))).unwrap_err();
+        assert_eq!(err, "HTTP 200");
+
+        let unavailable = b"HTTP/1.1 503 Service Unavailable\r\n\r\n";
+        assert_eq!(run_check(&check(stand_in(unavailable), http("/", None, None))).unwrap_err(), "HTTP 503");
+    }
+
+    #[test]
+    fn test_redis_check() {
+        assert!(run_check(&check(stand_in(b"+PONG\r\n"), CheckKind::Redis)).is_ok());
+        assert!(run_check(&check(stand_in(b"-NOAUTH Authentication required.\r\n"), CheckKind::Redis)).is_ok());
+        assert!(run_check(&check(stand_in(b"SSH-2.0-OpenSSH_9.6\r\n"), CheckKind::Redis)).is_err());
+    }
+
+    #[test]
+    fn test_failure_queued_once() {
+        let mut m = HealthMonitor::with_path(PathBuf::new());
+        let c = check(8080, CheckKind::Tcp);
+        m.set_checks(vec![c.clone()]);
+        let push = |m: &mut HealthMonitor, r: Result<u64, String>| {
+            m.results.lock().unwrap().push((c.clone(), r));
+            m.poll_results(&[]);
+        };
+
+        push(&mut m, Ok(3));
+        push(&mut m, Err("connect: refused".into()));
+        push(&mut m, Err("connect: refused".into()));
+        assert_eq!(m.new_failures.len(), 1);
+        assert_eq!(m.new_failures[0].name, "127.0.0.1:8080");
+        assert_eq!(m.checks[0].consecutive_failures, 2);
+
+        push(&mut m, Ok(4));
+        assert_eq!(m.checks[0].is_passing(), Some(true));
+        assert_eq!(m.checks[0].history, VecDeque::from([Some(3), None, None, Some(4)]));
+
+        // History survives a reload that keeps the check
+        m.set_checks(vec![c.clone(), check(22, CheckKind::Tcp)]);
+        assert_eq!(m.checks[0].history.len(), 4);
+        assert!(m.checks[1].history.is_empty());
+    }
+}
@@ -1,9 +1,15 @@
 pub mod types;
 pub mod listeners;
 pub mod fingerprint;
+pub mod handshakes;
 pub mod fingerprints;
 pub mod classify;
 pub mod wappalyzer_db;
+pub mod overrides;
+pub mod exposure;
+pub mod baseline;
+pub mod advisories;
+pub mod health;
 
 use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}};
@@ -12,6 +18,10 @@ use std::time::Instant;
 
 use chrono::Local;
 
+use crate::network::firewall::FirewallManager;
+use crate::network::upnp::UpnpMonitor;
+use baseline::{ListenerBaseline, ListenerChange};
+use health::HealthMonitor;
 use types::{ListeningPort, ServerKind, ListenProto};
 use fingerprint::ProbeResult;
 
@@ -37,6 +47,21 @@ pub struct ServersScanner {
     pub sort_column: usize,
     /// Sort ascending.
     pub sort_ascending: bool,
+    /// Set when the detection databases were reloaded from the override files;
+    /// taken by the app and shown in the status line.
+    pub db_notice: Option<String>,
+    /// Expected listeners, persisted between sessions.
+    pub baseline: ListenerBaseline,
+    /// Differences between the last full scan and the baseline.
+    pub changes: Vec<ListenerChange>,
+    /// Changes not seen in the previous scan; taken by the app and raised as alerts.
+    pub new_changes: Vec<ListenerChange>,
+    /// Show the "changes since baseline" view instead of the server cards.
+    pub show_changes: bool,
+    /// Show the router's UPnP port mappings instead of the server cards.
+    pub show_upnp: bool,
+    /// User-defined health checks and their latency history.
+    pub health: HealthMonitor,
 }
 
 impl ServersScanner {
@@ -55,6 +80,13 @@ impl ServersScanner {
             scroll_offset: 0,
             sort_column: 0,
             sort_ascending: true,
+            db_notice: None,
+            baseline: ListenerBaseline::load(),
+            changes: Vec::new(),
+            new_changes: Vec::new(),
+            show_changes: false,
+            show_upnp: false,
+            health: HealthMonitor::new(),
         }
     }
 
@@ -81,6 +113,7 @@ impl ServersScanner {
         self.scan_tick += 1;
         // Quick listener refresh every 5 ticks (~5 seconds)
         if self.scan_tick % 5 == 1 {
+            self.check_database_overrides();
             self.quick_refresh();
         }
         // Full probe scan every 30 ticks (~30 seconds)
@@ -88,6 +121,44 @@ impl ServersScanner {
             self.start_scan();
         }
         self.poll_results();
+        self.health.tick(&self.servers);
+        if let Some(e) = self.health.error.take() {
+            self.db_notice = Some(e);
+        }
+    }
+
+    /// Reload the fingerprint and Wappalyzer databases from the override files
+    /// and rescan so every listener is re-classified. Returns a one-line summary.
+    pub fn reload_databases(&mut self) -> String {
+        let fp = fingerprints::reload();
+        let wap = wappalyzer_db::reload();
+        let adv = advisories::reload();
+        self.start_scan();
+        format!("{} · {} · {}", fp.summary("Fingerprints"), wap.summary("Wappalyzer"), adv.summary("Advisories"))
+    }
+
+    /// Pick up edits to the override files without a restart.
+    fn check_database_overrides(&mut self) {
+        let fp = fingerprints::reload_if_changed();
+        let wap = wappalyzer_db::reload_if_changed();
+        let adv = advisories::reload_if_changed();
+        if fp.is_none() && wap.is_none() && adv.is_none() {
+            return;
+        }
+        let mut parts = Vec::new();
+        if let Some(r) = fp {
+            parts.push(r.summary("Fingerprints"));
+        }
+        if let Some(r) = wap {
+            parts.push(r.summary("Wappalyzer"));
+        }
+        if let Some(r) = adv {
+            parts.push(r.summary("Advisories"));
+        }
+        self.db_notice = Some(format!("Reloaded {}", parts.join(" · ")));
+        // A scan may already be running with the old database; poll_results
+        // will apply it, and the next periodic scan picks up the new one.
+        self.start_scan();
     }
 
     /// Fast refresh: just re-enumerate listeners and update connection counts.
@@ -164,6 +235,10 @@ impl ServersScanner {
                     is_responsive: false,
                     details: build_details(name, exe, cmd, None),
                     detected_techs: Vec::new(),
+                    protocol_details: Vec::new(),
+                    auth_required: None,
+                    exposure: None,
+                    advisories: Vec::new(),
                 });
             }
         }
@@ -179,26 +254,54 @@ impl ServersScanner {
 
     /// Poll background scan results.
     fn poll_results(&mut self) {
-        if let Ok(mut p) = self.pending.lock() {
-            if let Some(mut results) = p.take() {
-                // Preserve first_seen from existing entries
-                let existing_times: HashMap<(u16, ListenProto), chrono::NaiveTime> = self
-                    .servers
-                    .iter()
-                    .map(|s| ((s.port, s.proto.clone()), s.first_seen))
-                    .collect();
-
-                for entry in &mut results {
-                    let key = (entry.port, entry.proto.clone());
-                    if let Some(&first) = existing_times.get(&key) {
-                        entry.first_seen = first;
-                    }
-                }
-
-                self.servers = results;
-                self.last_scan = Some(Instant::now());
+        let Some(mut results) = self.pending.lock().ok().and_then(|mut p| p.take()) else {
+            return;
+        };
+
+        // Preserve first_seen from existing entries
+        let existing_times: HashMap<(u16, ListenProto), chrono::NaiveTime> = self
+            .servers
+            .iter()
+            .map(|s| ((s.port, s.proto.clone()), s.first_seen))
+            .collect();
+
+        for entry in &mut results {
+            let key = (entry.port, entry.proto.clone());
+            if let Some(&first) = existing_times.get(&key) {
+                entry.first_seen = first;
             }
         }
+
+        self.servers = results;
+        self.last_scan = Some(Instant::now());
+        self.update_changes();
+    }
+
+    /// Diff the current listeners against the baseline, queueing changes that
+    /// have not been alerted yet (the baseline remembers them across sessions).
+    fn update_changes(&mut self) {
+        let changes = self.baseline.update(&self.servers);
+        let fresh = self.baseline.take_unalerted(&changes);
+        self.new_changes.extend(fresh);
+        self.changes = changes;
+    }
+
+    /// Make the current listeners the new baseline.
+    pub fn accept_baseline(&mut self) {
+        self.baseline.accept(&self.servers);
+        self.changes.clear();
+        self.new_changes.clear();
+    }
+
+    /// Recompute every listener's exposure from the current firewall rules and
+    /// UPnP mappings. Cheap enough to run each tick.
+    pub fn update_exposure(&mut self, firewall: &FirewallManager, upnp: &UpnpMonitor) {
+        for s in &mut self.servers {
+            let proto = s.proto.label();
+            let access = firewall.inbound_access(&s.exe_path, s.port, proto);
+            let mapping = upnp.mapping_for(s.bind_addr, s.port, proto);
+            s.exposure = Some(exposure::assess(s, &access, mapping));
+        }
     }
 
     /// Is scanning in progress?
@@ -286,6 +389,10 @@ fn quick_scan() -> Vec<ListeningPort> {
                 is_responsive: false,
                 details: build_details(name, exe, cmd, None),
                 detected_techs: Vec::new(),
+                protocol_details: Vec::new(),
+                auth_required: None,
+                exposure: None,
+                advisories: Vec::new(),
             })
         })
         .collect();
@@ -353,9 +460,21 @@ fn full_scan() -> Vec<ListeningPort> {
                 return None;
             }
 
-            let detected_techs = probe
-                .map(|p| wappalyzer_db::detect_from_headers(&p.http_headers, p.http_server.as_deref(), p.http_powered_by.as_deref()))
+            let mut detected_techs = probe
+                .map(|p| wappalyzer_db::detect(&wappalyzer_db::HttpEvidence {
+                    headers: &p.http_headers,
+                    cookies: &p.http_cookies,
+                    html: p.http_body.as_deref().unwrap_or(""),
+                }))
                 .unwrap_or_default();
+            let handshake = probe.and_then(|p| p.handshake.as_ref());
+            if let Some(hs) = handshake {
+                detected_techs.insert(0, types::DetectedTech {
+                    name: hs.product.clone(),
+                    category: hs.kind.as_ref().unwrap_or(&kind).category().label().to_string(),
+                    version: hs.version.clone().unwrap_or_default(),
+                });
+            }
 
             // Upgrade generic kinds (Nginx, NodeJs, etc.) to specific apps/frameworks
             // when wappalyzer detects something more specific from HTTP headers.
@@ -365,7 +484,7 @@ fn full_scan() -> Vec<ListeningPort> {
                 (kind, version)
             };
 
-            Some(ListeningPort {
+            let mut server = ListeningPort {
                 proto: r.proto.clone(),
                 bind_addr: r.bind_addr,
                 port: r.port,
@@ -381,13 +500,19 @@ fn full_scan() -> Vec<ListeningPort> {
                 http_title: probe.and_then(|p| p.http_title.clone()),
                 banner: probe.and_then(|p| p.banner.clone()),
                 response_headers: probe
-                    .map(|p| p.http_headers.clone())
+                    .map(|p| p.http_headers.iter().filter(|(k, _)| fingerprint::is_display_header(k)).cloned().collect())
                     .unwrap_or_default(),
                 first_seen: now,
                 is_responsive: probe.map(|p| p.is_responsive).unwrap_or(false),
                 details: build_details(name, exe, cmd, probe),
                 detected_techs,
-            })
+                protocol_details: handshake.map(|h| h.details.clone()).unwrap_or_default(),
+                auth_required: handshake.and_then(|h| h.auth_required),
+                exposure: None,
+                advisories: Vec::new(),
+            };
+            server.advisories = advisories::check(&server);
+            Some(server)
         })
         .collect();
 
@@ -422,7 +547,7 @@ fn is_system_noise(port: u16, pid: u32) -> bool {
 /// For example: Nginx + WordPress headers → WordPress, NodeJs + Next.js headers → NextJs.
 /// Only upgrades when the wappalyzer detection is MORE specific than the process-level
 /// classification (e.g., a web server → specific CMS/framework).
-fn upgrade_kind_from_techs(kind: &ServerKind, techs: &[types::DetectedTech]) -> Option<(ServerKind, Option<String>)> {
+pub(crate) fn upgrade_kind_from_techs(kind: &ServerKind, techs: &[types::DetectedTech]) -> Option<(ServerKind, Option<String>)> {
     if techs.is_empty() { return None; }
 
     // Only upgrade generic web server / runtime kinds — not already-specific kinds
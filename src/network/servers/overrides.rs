@@ -0,0 +1,107 @@
+//! User override files for the embedded detection databases.
+//!
+//! Both `fingerprints.json` and `wappalyzer.json` can be extended from the
+//! config dir (`%APPDATA%\psnet\` on Windows). An override file is either a
+//! plain array of entries in the embedded format, or an object:
+//!
+//! ```json
+//! {
+//!   "disable": ["Grafana"],
+//!   "entries": [ { "kind": "Custom", "name": "Billing API", "process_names": ["billing"] } ]
+//! }
+//! ```
+//!
+//! `disable` names built-in entries to drop (by kind for fingerprints, by
+//! technology name for Wappalyzer). Files are re-read whenever their
+//! modification time changes.
+
+use std::path::Path;
+
+/// Parsed contents of one override file.
+#[derive(Debug, Default)]
+pub struct OverrideFile {
+    pub entries: Vec<serde_json::Value>,
+    pub disable: Vec<String>,
+}
+
+/// Outcome of (re)building a database, for the status line.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct OverrideReport {
+    /// User entries merged into the database.
+    pub added: usize,
+    /// Built-in entries removed by `disable`.
+    pub disabled: usize,
+    /// User entries that could not be parsed.
+    pub skipped: usize,
+    /// File-level error (unreadable or invalid JSON).
+    pub error: Option<String>,
+}
+
+impl OverrideReport {
+    pub fn summary(&self, db: &str) -> String {
+        if let Some(ref e) = self.error {
+            return format!("{} overrides: {}", db, e);
+        }
+        let mut s = format!("{}: {} custom, {} disabled", db, self.added, self.disabled);
+        if self.skipped > 0 {
+            s.push_str(&format!(", {} skipped", self.skipped));
+        }
+        s
+    }
+}
+
+/// Read an override file. A missing file is not an error.
+pub fn read_override(path: &Path) -> Result<OverrideFile, String> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => parse_override(&content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(OverrideFile::default()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+pub fn parse_override(content: &str) -> Result<OverrideFile, String> {
+    let value: serde_json::Value = serde_json::from_str(content).map_err(|e| format!("invalid JSON ({})", e))?;
+    match value {
+        serde_json::Value::Array(entries) => Ok(OverrideFile { entries, disable: Vec::new() }),
+        serde_json::Value::Object(mut obj) => {
+            let entries = match obj.remove("entries") {
+                Some(serde_json::Value::Array(a)) => a,
+                Some(_) => return Err("\"entries\" must be an array".to_string()),
+                None => Vec::new(),
+            };
+            let disable = obj
+                .get("disable")
+                .and_then(|d| d.as_array())
+                .map(|a| a.iter().filter_map(|s| s.as_str().map(|s| s.to_string())).collect())
+                .unwrap_or_default();
+            Ok(OverrideFile { entries, disable })
+        }
+        _ => Err("expected an array or an object".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_override_array() {
+        let f = parse_override(r#"[{"kind":"Redis"}]"#).unwrap();
+        assert_eq!(f.entries.len(), 1);
+        assert!(f.disable.is_empty());
+    }
+
+    #[test]
+    fn test_parse_override_object() {
+        let f = parse_override(r#"{"disable":["Grafana"],"entries":[{"kind":"Redis"},{"kind":"MySQL"}]}"#).unwrap();
+        assert_eq!(f.entries.len(), 2);
+        assert_eq!(f.disable, vec!["Grafana"]);
+    }
+
+    #[test]
+    fn test_parse_override_errors() {
+        assert!(parse_override("{not json").is_err());
+        assert!(parse_override("42").is_err());
+        assert!(parse_override(r#"{"entries":{}}"#).is_err());
+    }
+}
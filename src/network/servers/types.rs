@@ -4,6 +4,9 @@ use std::net::IpAddr;
 
 use chrono::NaiveTime;
 
+use super::advisories::Advisory;
+use super::exposure::Exposure;
+
 /// What kind of server technology is running.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[allow(dead_code)]
@@ -363,6 +366,21 @@ pub enum ServerKind {
     GenericTcp,
     GenericUdp,
     Unknown,
+
+    // -- Defined in the user's fingerprints override --
+    UserDefined(Box<UserKind>),
+}
+
+/// A service kind declared in the user's fingerprint override file,
+/// e.g. an in-house API that should show up by name in the Servers tab.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct UserKind {
+    pub name: String,
+    pub category: ServerCategory,
+    /// Short ASCII tag (like the built-in `icon()` values).
+    pub icon: String,
+    pub unicode_icon: String,
+    pub description: String,
 }
 
 impl ServerKind {
@@ -699,6 +717,7 @@ impl ServerKind {
             Self::GenericTcp => "TCP Listener",
             Self::GenericUdp => "UDP Listener",
             Self::Unknown => "Unknown",
+            Self::UserDefined(u) => &u.name,
         }
     }
 
@@ -1005,6 +1024,8 @@ impl ServerKind {
             | Self::GenericTcp
             | Self::GenericUdp
             | Self::Unknown => ServerCategory::Other,
+
+            Self::UserDefined(u) => u.category.clone(),
         }
     }
 
@@ -1276,6 +1297,7 @@ impl ServerKind {
             Self::GenericTcp => "t?",
             Self::GenericUdp => "u?",
             Self::Unknown => "??",
+            Self::UserDefined(u) => &u.icon,
         }
     }
 
@@ -1627,6 +1649,7 @@ impl ServerKind {
             Self::GenericTcp => "TCP service listening for connections",
             Self::GenericUdp => "UDP service accepting datagrams",
             Self::Unknown => "Unidentified service detected on this port",
+            Self::UserDefined(u) => &u.description,
         }
     }
 
@@ -1891,6 +1914,7 @@ impl ServerKind {
             Self::GenericTcp => "\u{1F50C}",
             Self::GenericUdp => "\u{1F4E1}",
             Self::Unknown => "\u{2753}",
+            Self::UserDefined(u) => &u.unicode_icon,
         }
     }
 }
@@ -1910,6 +1934,17 @@ pub enum ServerCategory {
 }
 
 impl ServerCategory {
+    /// Parse a category from its variant name or its label ("Database" / "Databases").
+    pub fn from_name(name: &str) -> Option<Self> {
+        let all = [
+            Self::WebServer, Self::AppRuntime, Self::WebFramework, Self::DevTool, Self::Database,
+            Self::MessageBroker, Self::Infrastructure, Self::SystemService, Self::Other,
+        ];
+        all.into_iter().find(|c| {
+            format!("{:?}", c).eq_ignore_ascii_case(name) || c.label().eq_ignore_ascii_case(name)
+        })
+    }
+
     pub fn label(&self) -> &str {
         match self {
             Self::WebServer => "Web Servers",
@@ -1996,6 +2031,14 @@ pub struct ListeningPort {
     /// Additional technologies detected via HTTP headers (Wappalyzer-style).
     /// Each entry: (technology_name, category, version_or_empty).
     pub detected_techs: Vec<DetectedTech>,
+    /// Facts reported by a protocol handshake (auth method, capabilities, host key, ...).
+    pub protocol_details: Vec<(String, String)>,
+    /// Whether the handshake saw the service demand credentials; None when unknown.
+    pub auth_required: Option<bool>,
+    /// Reachability (bind + firewall + UPnP) and risky combinations; None until assessed.
+    pub exposure: Option<Exposure>,
+    /// End-of-life and CVE advisories matching the detected versions.
+    pub advisories: Vec<Advisory>,
 }
 
 impl ListeningPort {
@@ -1,133 +1,385 @@
-//! Wappalyzer-style HTTP header fingerprint database.
+//! Wappalyzer-style technology fingerprint database.
 //!
-//! Signatures are loaded from `data/wappalyzer.json` (embedded at compile time).
-//! To add new signatures or sync with upstream Wappalyzer data, edit the JSON file.
+//! Rules are loaded from `data/wappalyzer.json` (embedded at compile time)
+//! and merged with the user's `wappalyzer.json` override in the config dir.
+//! Two entry shapes are accepted:
+//!
+//! - A technology in Wappalyzer's own format: `headers`, `cookies` and `meta`
+//!   maps, `html` and `scriptSrc` lists, all regexes that may carry a
+//!   `\;version:\1` tag, plus `implies` / `excludes`. `js` and `dom` rules
+//!   need a browser and are ignored. The embedded database only uses this.
+//! - A flat header signature, for hand-written overrides: `header`,
+//!   substring `pattern` and `version_prefix`. `x-meta-<name>` headers match
+//!   `<meta name>` tags.
 //!
 //! Sync with upstream:
 //!   1. Clone https://github.com/dochne/wappalyzer
-//!   2. Run: scripts/sync_wappalyzer.py (converts their format to ours)
+//!   2. Run: scripts/sync_wappalyzer.py (converts their format to ours and
+//!      drops patterns the regex crate cannot compile)
 //!   3. Rebuild psnet
 
-use std::sync::OnceLock;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::SystemTime;
+
+use regex::{Captures, Regex, RegexBuilder};
+
+use crate::config;
+
+use super::overrides::{self, OverrideReport};
 use super::types::DetectedTech;
 
-// ─── Signature struct ───────────────────────────────────────────────────────
+// ─── Rule structs ───────────────────────────────────────────────────────────
+
+/// Where a rule looks for its evidence.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Source {
+    Header(String),
+    Cookie(String),
+    Meta(String),
+    Html,
+    ScriptSrc,
+}
 
 #[derive(Clone, Debug)]
-struct HeaderSig {
+enum Matcher {
+    /// Flat signature: lowercase substring, version read after `<prefix>/`.
+    Substring { pattern: String, version_prefix: String },
+    /// Wappalyzer regex with an optional `\;version:` template.
+    Pattern { regex: Regex, version: Option<String> },
+}
+
+#[derive(Clone, Debug)]
+struct Rule {
+    source: Source,
+    matcher: Matcher,
+}
+
+#[derive(Clone, Debug)]
+struct Technology {
     name: String,
     category: String,
-    header: String,
-    pattern: String,
-    version_prefix: String,
+    rules: Vec<Rule>,
+    implies: Vec<String>,
+    excludes: Vec<String>,
 }
 
-// ─── Embedded JSON database ─────────────────────────────────────────────────
+/// Everything the HTTP probe captured that rules can match against.
+pub struct HttpEvidence<'a> {
+    pub headers: &'a [(String, String)],
+    pub cookies: &'a [(String, String)],
+    pub html: &'a str,
+}
+
+/// Upstream patterns are JavaScript regexes; anything larger than this after
+/// compilation is almost certainly pathological and is skipped.
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+// ─── Database (embedded + user overrides) ───────────────────────────────────
 
 static WAPPALYZER_JSON: &str = include_str!("../../../data/wappalyzer.json");
 
-static SIGNATURES: OnceLock<Vec<HeaderSig>> = OnceLock::new();
+/// User override in the config dir: extra signatures, or `disable` by technology name.
+/// A user signature named like a built-in technology replaces all of its built-in rules.
+const OVERRIDE_FILE: &str = "wappalyzer.json";
 
-fn signatures() -> &'static [HeaderSig] {
-    SIGNATURES.get_or_init(|| load_signatures(WAPPALYZER_JSON))
+static SIGNATURES: RwLock<Option<Arc<Vec<Technology>>>> = RwLock::new(None);
+
+static LOADED_STAMP: Mutex<Option<Option<SystemTime>>> = Mutex::new(None);
+
+fn signatures() -> Arc<Vec<Technology>> {
+    if let Some(db) = SIGNATURES.read().ok().and_then(|g| g.clone()) {
+        return db;
+    }
+    reload();
+    SIGNATURES.read().ok().and_then(|g| g.clone()).unwrap_or_default()
 }
 
-fn load_signatures(json: &str) -> Vec<HeaderSig> {
+/// Rebuild the signature list from the embedded JSON and the override file.
+pub fn reload() -> OverrideReport {
+    let path = config::path(OVERRIDE_FILE);
+    let stamp = config::modified(&path);
+    let builtin = load_signatures(WAPPALYZER_JSON);
+    let (db, report) = match overrides::read_override(&path) {
+        Ok(file) => merge(builtin, &file),
+        Err(e) => (builtin, OverrideReport { error: Some(e), ..Default::default() }),
+    };
+    if let Ok(mut g) = SIGNATURES.write() {
+        *g = Some(Arc::new(db));
+    }
+    if let Ok(mut s) = LOADED_STAMP.lock() {
+        *s = Some(stamp);
+    }
+    report
+}
+
+/// Reload if the override file was created, edited or removed since the last load.
+pub fn reload_if_changed() -> Option<OverrideReport> {
+    let stamp = config::modified(&config::path(OVERRIDE_FILE));
+    let loaded = LOADED_STAMP.lock().ok().and_then(|s| *s);
+    match loaded {
+        Some(prev) if prev == stamp => None,
+        None if stamp.is_none() => None,
+        _ => Some(reload()),
+    }
+}
+
+fn merge(builtin: Vec<Technology>, file: &overrides::OverrideFile) -> (Vec<Technology>, OverrideReport) {
+    let mut report = OverrideReport::default();
+    let user: Vec<Technology> = file.entries.iter().filter_map(parse_entry).collect();
+    report.added = user.len();
+    report.skipped = file.entries.len() - user.len();
+
+    let before = builtin.len();
+    let replaced = |name: &str| {
+        file.disable.iter().any(|d| d.eq_ignore_ascii_case(name))
+            || user.iter().any(|u| u.name.eq_ignore_ascii_case(name))
+    };
+    let builtin: Vec<Technology> = builtin.into_iter().filter(|s| !replaced(&s.name)).collect();
+    report.disabled = before - builtin.len();
+
+    let mut db = user;
+    db.extend(builtin);
+    (db, report)
+}
+
+fn load_signatures(json: &str) -> Vec<Technology> {
     let entries: Vec<serde_json::Value> = serde_json::from_str(json)
         .expect("wappalyzer.json: invalid JSON");
 
-    entries
-        .into_iter()
-        .filter_map(|v| {
-            Some(HeaderSig {
-                name: v.get("name")?.as_str()?.to_string(),
-                category: v.get("category")?.as_str()?.to_string(),
-                header: v.get("header")?.as_str()?.to_string(),
-                pattern: v.get("pattern").and_then(|s| s.as_str()).unwrap_or("").to_string(),
-                version_prefix: v.get("version_prefix").and_then(|s| s.as_str()).unwrap_or("").to_string(),
-            })
-        })
-        .collect()
+    entries.iter().filter_map(parse_entry).collect()
 }
 
-// ─── Detection engine ───────────────────────────────────────────────────────
+fn parse_entry(v: &serde_json::Value) -> Option<Technology> {
+    let name = v.get("name")?.as_str()?.to_string();
+    let category = v.get("category")?.as_str()?.to_string();
 
-/// Detect technologies from HTTP headers using the Wappalyzer signature database.
-/// Returns all matching technologies (there can be multiple: e.g., Nginx + PHP + WordPress).
-pub fn detect_from_headers(
-    headers: &[(String, String)],
-    server_header: Option<&str>,
-    powered_by: Option<&str>,
-) -> Vec<DetectedTech> {
-    let sigs = signatures();
-    let mut results: Vec<DetectedTech> = Vec::new();
-    let mut seen = std::collections::HashSet::new();
+    // Flat header signature
+    if let Some(header) = v.get("header").and_then(|h| h.as_str()) {
+        let header = header.to_lowercase();
+        let source = match header.strip_prefix("x-meta-") {
+            Some(meta) => Source::Meta(meta.to_string()),
+            None => Source::Header(header),
+        };
+        let matcher = Matcher::Substring {
+            pattern: v.get("pattern").and_then(|s| s.as_str()).unwrap_or("").to_lowercase(),
+            version_prefix: v.get("version_prefix").and_then(|s| s.as_str()).unwrap_or("").to_string(),
+        };
+        return Some(Technology {
+            name,
+            category,
+            rules: vec![Rule { source, matcher }],
+            implies: Vec::new(),
+            excludes: Vec::new(),
+        });
+    }
 
-    // Build lowercase header map for efficient lookup
-    let header_map: Vec<(String, String)> = headers
-        .iter()
-        .map(|(k, v)| (k.to_lowercase(), v.to_lowercase()))
+    // Wappalyzer technology
+    let mut patterns: Vec<(Source, String)> = Vec::new();
+    for (field, source) in [("headers", Source::Header as fn(String) -> Source), ("cookies", Source::Cookie), ("meta", Source::Meta)] {
+        if let Some(map) = v.get(field).and_then(|m| m.as_object()) {
+            for (key, value) in map {
+                for p in string_list(Some(value)) {
+                    patterns.push((source(key.to_lowercase()), p));
+                }
+            }
+        }
+    }
+    for p in string_list(v.get("html")) {
+        patterns.push((Source::Html, p));
+    }
+    for p in string_list(v.get("scriptSrc")) {
+        patterns.push((Source::ScriptSrc, p));
+    }
+
+    let total = patterns.len();
+    let rules: Vec<Rule> = patterns
+        .into_iter()
+        .filter_map(|(source, p)| Some(Rule { source, matcher: compile_pattern(&p)? }))
         .collect();
+    // Every pattern failed to compile: report the entry as skipped
+    if total > 0 && rules.is_empty() {
+        return None;
+    }
 
-    let server_lower = server_header.map(|s| s.to_lowercase());
-    let powered_lower = powered_by.map(|s| s.to_lowercase());
+    let names = |field: &str| -> Vec<String> {
+        string_list(v.get(field))
+            .into_iter()
+            .map(|s| strip_tags(&s).trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect()
+    };
+    Some(Technology {
+        name,
+        category,
+        rules,
+        implies: names("implies"),
+        excludes: names("excludes"),
+    })
+}
 
-    for sig in sigs {
-        let matched = match sig.header.as_str() {
-            "server" => {
-                if let Some(ref sv) = server_lower {
-                    if sig.pattern.is_empty() { true } else { sv.contains(sig.pattern.as_str()) }
-                } else {
-                    check_header_map(&header_map, &sig.header, &sig.pattern)
-                }
-            }
-            "x-powered-by" => {
-                if let Some(ref pb) = powered_lower {
-                    if sig.pattern.is_empty() { true } else { pb.contains(sig.pattern.as_str()) }
-                } else {
-                    check_header_map(&header_map, &sig.header, &sig.pattern)
-                }
-            }
-            _ => check_header_map(&header_map, &sig.header, &sig.pattern),
-        };
+/// Wappalyzer allows a single string or an array wherever a pattern list is expected.
+fn string_list(v: Option<&serde_json::Value>) -> Vec<String> {
+    match v {
+        Some(serde_json::Value::String(s)) => vec![s.clone()],
+        Some(serde_json::Value::Array(a)) => a.iter().filter_map(|s| s.as_str().map(|s| s.to_string())).collect(),
+        _ => Vec::new(),
+    }
+}
 
-        if !matched { continue; }
+/// The regex part of a pattern, without its `\;version:` / `\;confidence:` tags.
+fn strip_tags(pattern: &str) -> &str {
+    pattern.split("\\;").next().unwrap_or("")
+}
 
-        if !seen.insert(sig.name.as_str() as *const str) { continue; }
+fn compile_pattern(pattern: &str) -> Option<Matcher> {
+    let version = pattern
+        .split("\\;")
+        .skip(1)
+        .find_map(|tag| tag.strip_prefix("version:"))
+        .map(|t| t.to_string());
+    // Lookarounds and backreferences are not supported by the regex crate;
+    // the sync script leaves them out of the embedded file, and override
+    // patterns using them are dropped rather than approximated.
+    let regex = RegexBuilder::new(strip_tags(pattern))
+        .case_insensitive(true)
+        .size_limit(REGEX_SIZE_LIMIT)
+        .build()
+        .ok()?;
+    Some(Matcher::Pattern { regex, version })
+}
 
-        let version = if !sig.version_prefix.is_empty() {
-            let source = match sig.header.as_str() {
-                "server" => server_header.unwrap_or(""),
-                "x-powered-by" => powered_by.unwrap_or(""),
-                _ => headers
-                    .iter()
-                    .find(|(k, _)| k.to_lowercase() == sig.header)
-                    .map(|(_, v)| v.as_str())
-                    .unwrap_or(""),
-            };
-            extract_header_version(source, &sig.version_prefix)
-        } else {
-            String::new()
-        };
+// ─── Detection engine ───────────────────────────────────────────────────────
 
-        results.push(DetectedTech {
-            name: sig.name.clone(),
-            category: sig.category.clone(),
-            version,
-        });
+/// Detect technologies from an HTTP response using the Wappalyzer database.
+/// Returns all matching technologies (there can be multiple: e.g., Nginx + PHP + WordPress),
+/// after applying `implies` and `excludes`.
+pub fn detect(evidence: &HttpEvidence) -> Vec<DetectedTech> {
+    detect_in(&signatures(), evidence)
+}
+
+fn detect_in(db: &[Technology], evidence: &HttpEvidence) -> Vec<DetectedTech> {
+
+    let headers: Vec<(String, &str)> = evidence.headers.iter().map(|(k, v)| (k.to_lowercase(), v.as_str())).collect();
+    let cookies: Vec<(String, &str)> = evidence.cookies.iter().map(|(k, v)| (k.to_lowercase(), v.as_str())).collect();
+    let meta = extract_meta_tags(evidence.html);
+    let scripts = extract_script_srcs(evidence.html);
+
+    let values = |source: &Source| -> Vec<&str> {
+        match source {
+            Source::Header(name) => values_named(&headers, name),
+            Source::Cookie(name) => values_named(&cookies, name),
+            Source::Meta(name) => meta.iter().filter(|(k, _)| k == name).map(|(_, v)| v.as_str()).collect(),
+            Source::Html if !evidence.html.is_empty() => vec![evidence.html],
+            Source::Html => Vec::new(),
+            Source::ScriptSrc => scripts.iter().map(|s| s.as_str()).collect(),
+        }
+    };
+
+    let mut results: Vec<DetectedTech> = Vec::new();
+    for tech in db.iter() {
+        for rule in &tech.rules {
+            let Some(version) = values(&rule.source).into_iter().find_map(|v| match_value(&rule.matcher, v)) else {
+                continue;
+            };
+            match results.iter_mut().find(|r| r.name == tech.name) {
+                Some(found) if found.version.is_empty() => found.version = version,
+                Some(_) => {}
+                None => results.push(DetectedTech {
+                    name: tech.name.clone(),
+                    category: tech.category.clone(),
+                    version,
+                }),
+            }
+        }
     }
 
+    resolve_relations(db, &mut results);
     results
 }
 
-fn check_header_map(headers: &[(String, String)], header_name: &str, pattern: &str) -> bool {
-    for (k, v) in headers {
-        if k == header_name {
-            if pattern.is_empty() { return true; }
-            if v.contains(pattern) { return true; }
+fn values_named<'a>(pairs: &[(String, &'a str)], name: &str) -> Vec<&'a str> {
+    pairs.iter().filter(|(k, _)| k == name).map(|(_, v)| *v).collect()
+}
+
+/// Match one value; `Some(version)` on a hit (empty when no version was extracted).
+fn match_value(matcher: &Matcher, value: &str) -> Option<String> {
+    match matcher {
+        Matcher::Substring { pattern, version_prefix } => {
+            if !pattern.is_empty() && !value.to_lowercase().contains(pattern.as_str()) {
+                return None;
+            }
+            if version_prefix.is_empty() {
+                Some(String::new())
+            } else {
+                Some(extract_header_version(value, version_prefix))
+            }
+        }
+        Matcher::Pattern { regex, version } => {
+            let caps = regex.captures(value)?;
+            Some(version.as_deref().map(|t| render_version(t, &caps)).unwrap_or_default())
         }
     }
-    false
+}
+
+/// Expand a `\;version:` template: `\1` is a capture group, `\1?a:b` picks
+/// `a` when group 1 matched and `b` otherwise.
+fn render_version(template: &str, caps: &Captures) -> String {
+    let substitute = |s: &str| -> String {
+        let mut out = String::new();
+        let mut chars = s.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c == '\\' && chars.peek().is_some_and(|d| d.is_ascii_digit()) {
+                let mut n = 0usize;
+                while let Some(d) = chars.peek().and_then(|d| d.to_digit(10)) {
+                    n = n * 10 + d as usize;
+                    chars.next();
+                }
+                out.push_str(caps.get(n).map(|m| m.as_str()).unwrap_or(""));
+            } else {
+                out.push(c);
+            }
+        }
+        out
+    };
+
+    let rendered = match template.split_once('?').and_then(|(cond, rest)| Some((cond, rest.split_once(':')?))) {
+        Some((cond, (yes, no))) => {
+            if substitute(cond).is_empty() { substitute(no) } else { substitute(yes) }
+        }
+        None => substitute(template),
+    };
+    rendered.trim().to_string()
+}
+
+/// Add implied technologies (transitively), then drop excluded ones.
+fn resolve_relations(db: &[Technology], found: &mut Vec<DetectedTech>) {
+    fn entries<'a>(db: &'a [Technology], name: &'a str) -> impl Iterator<Item = &'a Technology> + 'a {
+        db.iter().filter(move |t| t.name.eq_ignore_ascii_case(name))
+    }
+
+    // Implied entries are appended and visited in turn; a name is only added
+    // once, so cycles terminate.
+    let mut i = 0;
+    while i < found.len() {
+        let implied: Vec<String> = entries(db, &found[i].name).flat_map(|t| t.implies.iter().cloned()).collect();
+        for name in implied {
+            if found.iter().any(|f| f.name.eq_ignore_ascii_case(&name)) {
+                continue;
+            }
+            let category = entries(db, &name)
+                .next()
+                .map(|t| t.category.clone())
+                .unwrap_or_else(|| "Technology".to_string());
+            found.push(DetectedTech { name, category, version: String::new() });
+        }
+        i += 1;
+    }
+
+    let excluded: Vec<String> = found
+        .iter()
+        .flat_map(|f| entries(db, &f.name).flat_map(|t| t.excludes.iter().cloned()))
+        .collect();
+    found.retain(|f| !excluded.iter().any(|e| e.eq_ignore_ascii_case(&f.name)));
 }
 
 fn extract_header_version(header_value: &str, prefix: &str) -> String {
@@ -149,34 +401,228 @@ fn extract_header_version(header_value: &str, prefix: &str) -> String {
     String::new()
 }
 
+// ─── HTML scanning ──────────────────────────────────────────────────────────
+
+/// `<meta name|property|http-equiv=... content=...>` pairs, names lowercased.
+fn extract_meta_tags(html: &str) -> Vec<(String, String)> {
+    tag_bodies(html, "meta")
+        .into_iter()
+        .filter_map(|tag| {
+            let name = attr_value(tag, "name")
+                .or_else(|| attr_value(tag, "property"))
+                .or_else(|| attr_value(tag, "http-equiv"))?;
+            Some((name.to_lowercase(), attr_value(tag, "content")?))
+        })
+        .collect()
+}
+
+fn extract_script_srcs(html: &str) -> Vec<String> {
+    tag_bodies(html, "script").into_iter().filter_map(|tag| attr_value(tag, "src")).collect()
+}
+
+/// Attribute text of every `<tag ...>` in the document (case-insensitive).
+fn tag_bodies<'a>(html: &'a str, tag: &str) -> Vec<&'a str> {
+    // ASCII lowercasing keeps byte offsets aligned with `html`
+    let lower = html.to_ascii_lowercase();
+    let open = format!("<{}", tag);
+    let mut out = Vec::new();
+    let mut pos = 0;
+    while let Some(i) = lower[pos..].find(&open) {
+        let start = pos + i + open.len();
+        let Some(len) = lower[start..].find('>') else { break };
+        // `<metadata>` is not `<meta>`
+        if lower[start..].starts_with(|c: char| c.is_ascii_whitespace() || c == '/' || c == '>') {
+            out.push(&html[start..start + len]);
+        }
+        pos = start + len;
+    }
+    out
+}
+
+fn attr_value(tag: &str, attr: &str) -> Option<String> {
+    let lower = tag.to_ascii_lowercase();
+    let mut from = 0;
+    while let Some(i) = lower[from..].find(attr) {
+        let at = from + i;
+        from = at + attr.len();
+        if at > 0 && !lower.as_bytes()[at - 1].is_ascii_whitespace() {
+            continue;
+        }
+        let Some(rest) = tag[from..].trim_start().strip_prefix('=') else { continue };
+        let rest = rest.trim_start();
+        let value = match rest.chars().next() {
+            Some(q @ ('"' | '\'')) => rest[1..].split(q).next().unwrap_or(""),
+            _ => rest.split(|c: char| c.is_ascii_whitespace()).next().unwrap_or(""),
+        };
+        return Some(value.to_string());
+    }
+    None
+}
+
 // ─── Tests ──────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn headers(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    fn detect_headers(pairs: &[(&str, &str)]) -> Vec<DetectedTech> {
+        detect(&HttpEvidence { headers: &headers(pairs), cookies: &[], html: "" })
+    }
+
+    fn tech(json: &str) -> Technology {
+        parse_entry(&serde_json::from_str(json).unwrap()).unwrap()
+    }
+
     #[test]
     fn test_detect_nginx() {
-        let techs = detect_from_headers(&[], Some("nginx/1.24.0"), None);
+        let techs = detect_headers(&[("Server", "nginx/1.24.0")]);
         assert!(techs.iter().any(|t| t.name == "Nginx"));
         let nginx = techs.iter().find(|t| t.name == "Nginx").unwrap();
         assert_eq!(nginx.version, "1.24.0");
     }
 
+    #[test]
+    fn test_nginx_is_not_gin() {
+        let techs = detect_headers(&[("Server", "nginx/1.24.0")]);
+        assert!(!techs.iter().any(|t| t.name == "Gin"), "{:?}", techs);
+        assert!(detect_headers(&[("Server", "gin")]).iter().any(|t| t.name == "Gin"));
+    }
+
     #[test]
     fn test_detect_php_powered_by() {
-        let techs = detect_from_headers(&[], None, Some("PHP/8.2.0"));
+        let techs = detect_headers(&[("X-Powered-By", "PHP/8.2.0")]);
         assert!(techs.iter().any(|t| t.name == "PHP"));
     }
 
     #[test]
     fn test_detect_multiple_technologies() {
-        let techs = detect_from_headers(
-            &[("x-powered-by".to_string(), "Express".to_string())],
-            Some("nginx/1.24.0"),
-            None,
-        );
+        let techs = detect_headers(&[("x-powered-by", "Express"), ("Server", "nginx/1.24.0")]);
         assert!(techs.iter().any(|t| t.name == "Nginx"));
         assert!(techs.iter().any(|t| t.name == "Express"));
     }
+
+    #[test]
+    fn test_detect_embedded_html_rules() {
+        let html = r#"<head><meta name="generator" content="WordPress 6.4.2"></head>"#;
+        let techs = detect(&HttpEvidence { headers: &[], cookies: &[], html });
+        let wp = techs.iter().find(|t| t.name == "WordPress").unwrap();
+        assert_eq!(wp.version, "6.4.2");
+        assert!(techs.iter().any(|t| t.name == "PHP"));
+    }
+
+    #[test]
+    fn test_merge_user_signature_replaces_builtin() {
+        let builtin = load_signatures(WAPPALYZER_JSON);
+        let file = overrides::parse_override(r#"{
+            "disable": ["PHP"],
+            "entries": [{"name": "Nginx", "category": "Gateway", "header": "Server", "pattern": "nginx"},
+                        {"name": "Billing API", "category": "In-house", "header": "X-Billing-Version", "pattern": ""}]
+        }"#).unwrap();
+        let (db, report) = merge(builtin, &file);
+        assert_eq!(report.added, 2);
+        assert!(report.disabled >= 2);
+        assert!(!db.iter().any(|s| s.name == "PHP"));
+        assert_eq!(db.iter().filter(|s| s.name == "Nginx").count(), 1);
+        assert_eq!(db[0].rules[0].source, Source::Header("server".to_string()));
+        assert_eq!(db[1].rules[0].source, Source::Header("x-billing-version".to_string()));
+    }
+
+    #[test]
+    fn test_builtin_rules_compile_and_relations_resolve() {
+        let entries: Vec<serde_json::Value> = serde_json::from_str(WAPPALYZER_JSON).unwrap();
+        let db = load_signatures(WAPPALYZER_JSON);
+        assert_eq!(db.len(), entries.len());
+        for (entry, tech) in entries.iter().zip(&db) {
+            let patterns: usize = ["headers", "cookies", "meta"].iter()
+                .filter_map(|f| entry.get(*f).and_then(|m| m.as_object()))
+                .flat_map(|m| m.values())
+                .map(|v| string_list(Some(v)).len())
+                .sum::<usize>()
+                + string_list(entry.get("html")).len()
+                + string_list(entry.get("scriptSrc")).len();
+            assert_eq!(tech.rules.len(), patterns, "{}: a pattern failed to compile", tech.name);
+            for target in tech.implies.iter().chain(&tech.excludes) {
+                assert!(db.iter().any(|t| &t.name == target), "{} refers to unknown {}", tech.name, target);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_wappalyzer_technology() {
+        let t = tech(r#"{
+            "name": "WordPress", "category": "CMS",
+            "meta": {"generator": "^WordPress(?: ([\\d.]+))?\\;version:\\1"},
+            "html": ["<link rel=[\"']stylesheet[\"'] [^>]+/wp-(?:content|includes)/"],
+            "scriptSrc": "/wp-(?:content|includes)/",
+            "js": {"wp_username": ""},
+            "implies": ["PHP", "MySQL\\;confidence:50"]
+        }"#);
+        assert_eq!(t.rules.len(), 3);
+        assert_eq!(t.implies, vec!["PHP", "MySQL"]);
+        // Lookaheads are unsupported: the rule is dropped, the entry survives
+        let t = tech(r#"{"name": "X", "category": "Y", "html": ["a(?=b)", "ab"]}"#);
+        assert_eq!(t.rules.len(), 1);
+        assert!(parse_entry(&serde_json::json!({"name": "X", "category": "Y", "html": "a(?=b)"})).is_none());
+    }
+
+    #[test]
+    fn test_match_html_meta_script_and_cookies() {
+        let db = vec![
+            tech(r#"{"name": "WordPress", "category": "CMS",
+                     "meta": {"generator": "^WordPress(?: ([\\d.]+))?\\;version:\\1"}}"#),
+            tech(r#"{"name": "jQuery", "category": "JavaScript libraries",
+                     "scriptSrc": "jquery[.-]([\\d.]+)(?:\\.min)?\\.js\\;version:\\1"}"#),
+            tech(r#"{"name": "Laravel", "category": "Web frameworks", "cookies": {"laravel_session": ""}}"#),
+            tech(r#"{"name": "Next.js", "category": "Web frameworks", "html": "<script id=\"__NEXT_DATA__\""}"#),
+        ];
+        let html = r#"<html><head><META name="Generator" content="WordPress 6.4.2">
+            <script type="text/javascript" src='/static/jquery-3.7.1.min.js'></script>
+            <metadata name="generator" content="nope"></head><body><script id="__NEXT_DATA__"></script></body></html>"#;
+        let cookies = headers(&[("laravel_session", "abc")]);
+        let evidence = HttpEvidence { headers: &[], cookies: &cookies, html };
+
+        let meta = extract_meta_tags(html);
+        assert_eq!(meta, vec![("generator".to_string(), "WordPress 6.4.2".to_string())]);
+        assert_eq!(extract_script_srcs(html), vec!["/static/jquery-3.7.1.min.js"]);
+
+        let found = detect_in(&db, &evidence);
+        let version = |name: &str| found.iter().find(|t| t.name == name).map(|t| t.version.as_str());
+        assert_eq!(version("WordPress"), Some("6.4.2"));
+        assert_eq!(version("jQuery"), Some("3.7.1"));
+        assert_eq!(version("Laravel"), Some(""));
+        assert_eq!(version("Next.js"), Some(""));
+    }
+
+    #[test]
+    fn test_version_template_ternary() {
+        let re = Regex::new(r"v(\d+)?-(\w+)").unwrap();
+        let caps = re.captures("v-beta").unwrap();
+        assert_eq!(render_version(r"\1?\1:\2", &caps), "beta");
+        let caps = re.captures("v7-beta").unwrap();
+        assert_eq!(render_version(r"\1?\1:\2", &caps), "7");
+        assert_eq!(render_version(r"\1.x", &caps), "7.x");
+    }
+
+    #[test]
+    fn test_implies_and_excludes() {
+        let db = vec![
+            tech(r#"{"name": "WordPress", "category": "CMS", "implies": ["PHP", "MySQL"]}"#),
+            tech(r#"{"name": "PHP", "category": "Programming languages", "implies": "WordPress"}"#),
+            tech(r#"{"name": "Ghost", "category": "CMS", "excludes": "WordPress"}"#),
+        ];
+        let mut found = vec![DetectedTech { name: "WordPress".into(), category: "CMS".into(), version: "6.4".into() }];
+        resolve_relations(&db, &mut found);
+        let names: Vec<&str> = found.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["WordPress", "PHP", "MySQL"]);
+        assert_eq!(found[1].category, "Programming languages");
+        assert_eq!(found[2].category, "Technology");
+
+        found.push(DetectedTech { name: "Ghost".into(), category: "CMS".into(), version: String::new() });
+        resolve_relations(&db, &mut found);
+        assert!(!found.iter().any(|t| t.name == "WordPress"));
+    }
 }
@@ -335,7 +335,7 @@ fn parse_packet(pkt: &[u8], local_ip: u32) -> Option<PacketSnippet> {
     let ip_total_len = u16::from_be_bytes([pkt[2], pkt[3]]);
     let ip_id = u16::from_be_bytes([pkt[4], pkt[5]]);
 
-    let (src_port, dst_port, payload_offset, tcp_flags, tcp_seq, tcp_ack_num, tcp_window) = match protocol {
+    let (src_port, dst_port, payload_offset, tcp_flags, tcp_seq, tcp_ack_num, tcp_window, tcp_options) = match protocol {
         6 => {
             // TCP
             if pkt.len() < ihl + 20 {
@@ -348,7 +348,8 @@ fn parse_packet(pkt: &[u8], local_ip: u32) -> Option<PacketSnippet> {
             let seq = u32::from_be_bytes([pkt[ihl + 4], pkt[ihl + 5], pkt[ihl + 6], pkt[ihl + 7]]);
             let ack = u32::from_be_bytes([pkt[ihl + 8], pkt[ihl + 9], pkt[ihl + 10], pkt[ihl + 11]]);
             let win = u16::from_be_bytes([pkt[ihl + 14], pkt[ihl + 15]]);
-            (sp, dp, ihl + tcp_hdr_len, flags, seq, ack, win)
+            let opts = pkt.get(ihl + 20..(ihl + tcp_hdr_len).min(pkt.len())).unwrap_or_default().to_vec();
+            (sp, dp, ihl + tcp_hdr_len, flags, seq, ack, win, opts)
         }
         17 => {
             // UDP
@@ -357,7 +358,7 @@ fn parse_packet(pkt: &[u8], local_ip: u32) -> Option<PacketSnippet> {
             }
             let sp = u16::from_be_bytes([pkt[ihl], pkt[ihl + 1]]);
             let dp = u16::from_be_bytes([pkt[ihl + 2], pkt[ihl + 3]]);
-            (sp, dp, ihl + 8, 0u8, 0u32, 0u32, 0u16)
+            (sp, dp, ihl + 8, 0u8, 0u32, 0u32, 0u16, Vec::new())
         }
         _ => return None, // Skip ICMP, IGMP, etc.
     };
@@ -392,9 +393,12 @@ fn parse_packet(pkt: &[u8], local_ip: u32) -> Option<PacketSnippet> {
         }
     }
 
-    // Extract raw payload bytes (up to 256 bytes) for hex dump
+    // Extract raw payload bytes (up to 256 bytes) for hex dump. DHCP keeps
+    // the whole message, as its options only start at byte 240.
+    let is_dhcp = protocol == 17 && [src_port, dst_port].iter().any(|p| *p == 67 || *p == 68);
+    let raw_cap = if is_dhcp { pkt.len() } else { 256 };
     let raw_payload = if payload_offset < pkt.len() {
-        pkt[payload_offset..pkt.len().min(payload_offset + 256)].to_vec()
+        pkt[payload_offset..pkt.len().min(payload_offset + raw_cap)].to_vec()
     } else {
         Vec::new()
     };
@@ -428,6 +432,7 @@ fn parse_packet(pkt: &[u8], local_ip: u32) -> Option<PacketSnippet> {
         tcp_seq,
         tcp_ack_num,
         tcp_window,
+        tcp_options,
         raw_payload,
     })
 }
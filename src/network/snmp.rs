@@ -0,0 +1,931 @@
+//! SNMP v1/v2c client and device inventory.
+//!
+//! Managed switches, access points, printers and NAS boxes answer SNMP with
+//! far more than a name: a description of the hardware and firmware, uptime,
+//! contact and location, every interface with its traffic counters, and
+//! through LLDP the neighbours plugged into each port. This module speaks
+//! just enough BER to send GET, GETNEXT and GETBULK requests and walk tables,
+//! and collects the system group, ifTable / ifXTable and the LLDP remote
+//! table into an [`SnmpInfo`] per device.
+//!
+//! Communities are tried in the order configured in `devices.json`
+//! (`snmp_communities`), v2c first, since v1-only agents are rare and v2c
+//! brings GETBULK and 64-bit counters.
+
+use std::collections::{BTreeMap, HashMap};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket};
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Rows fetched per GETBULK.
+const BULK_REPETITIONS: i64 = 25;
+/// Upper bound on rows collected by one walk.
+const MAX_WALK_ROWS: usize = 4096;
+
+static REQUEST_ID: AtomicI32 = AtomicI32::new(1);
+
+// ─── OIDs ────────────────────────────────────────────────────────────────────
+
+pub const SYS_DESCR: &[u32] = &[1, 3, 6, 1, 2, 1, 1, 1, 0];
+pub const SYS_UPTIME: &[u32] = &[1, 3, 6, 1, 2, 1, 1, 3, 0];
+pub const SYS_CONTACT: &[u32] = &[1, 3, 6, 1, 2, 1, 1, 4, 0];
+pub const SYS_NAME: &[u32] = &[1, 3, 6, 1, 2, 1, 1, 5, 0];
+pub const SYS_LOCATION: &[u32] = &[1, 3, 6, 1, 2, 1, 1, 6, 0];
+
+/// ifTable entry; columns 2 descr, 3 type, 5 speed, 6 MAC, 8 oper status,
+/// 10 in octets, 16 out octets.
+const IF_ENTRY: &[u32] = &[1, 3, 6, 1, 2, 1, 2, 2, 1];
+/// ifXTable entry; columns 1 name, 6/10 64-bit in/out octets, 15 speed in
+/// Mbit/s, 18 alias.
+const IFX_ENTRY: &[u32] = &[1, 3, 6, 1, 2, 1, 31, 1, 1, 1];
+/// lldpLocPortEntry; column 3 port id, 4 port description.
+const LLDP_LOC_PORT: &[u32] = &[1, 0, 8802, 1, 1, 2, 1, 3, 7, 1];
+/// lldpRemEntry, indexed by timeMark.localPortNum.remIndex; columns 4/5
+/// chassis id subtype/value, 6/7 port id subtype/value, 8 port description,
+/// 9 system name.
+const LLDP_REM: &[u32] = &[1, 0, 8802, 1, 1, 2, 1, 4, 1, 1];
+/// lldpRemManAddrEntry; the management address is part of the index.
+const LLDP_REM_MAN_ADDR: &[u32] = &[1, 0, 8802, 1, 1, 2, 1, 4, 2, 1];
+
+pub fn format_oid(oid: &[u32]) -> String {
+    oid.iter().map(u32::to_string).collect::<Vec<_>>().join(".")
+}
+
+// ─── Values ──────────────────────────────────────────────────────────────────
+
+/// A variable binding value.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Integer(i64),
+    OctetString(Vec<u8>),
+    Null,
+    Oid(Vec<u32>),
+    IpAddress(Ipv4Addr),
+    /// Counter32, Gauge32 and Counter64.
+    Counter(u64),
+    /// Hundredths of a second.
+    TimeTicks(u32),
+    /// noSuchObject / noSuchInstance (v2c).
+    NoSuch,
+    /// endOfMibView (v2c).
+    EndOfMib,
+    Other(u8),
+}
+
+impl Value {
+    pub fn as_u64(&self) -> Option<u64> {
+        match *self {
+            Value::Integer(v) => u64::try_from(v).ok(),
+            Value::Counter(v) => Some(v),
+            Value::TimeTicks(v) => Some(v as u64),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Value::OctetString(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    /// Octet strings as text when printable, otherwise as hex.
+    pub fn as_text(&self) -> Option<String> {
+        let text = match self {
+            Value::OctetString(b) => display_bytes(b),
+            Value::Integer(v) => v.to_string(),
+            Value::Counter(v) => v.to_string(),
+            Value::IpAddress(ip) => ip.to_string(),
+            Value::Oid(oid) => format_oid(oid),
+            _ => return None,
+        };
+        let text = text.trim().to_string();
+        (!text.is_empty()).then_some(text)
+    }
+}
+
+fn display_bytes(bytes: &[u8]) -> String {
+    let bytes = bytes.strip_suffix(&[0]).unwrap_or(bytes);
+    match std::str::from_utf8(bytes) {
+        Ok(s) if s.chars().all(|c| !c.is_control() || c == '\n' || c == '\r' || c == '\t') => s.to_string(),
+        _ => hex_bytes(bytes),
+    }
+}
+
+/// "AA:BB:CC:DD:EE:FF" style hex.
+fn hex_bytes(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(":")
+}
+
+// ─── BER ─────────────────────────────────────────────────────────────────────
+
+const TAG_INTEGER: u8 = 0x02;
+const TAG_OCTET_STRING: u8 = 0x04;
+const TAG_NULL: u8 = 0x05;
+const TAG_OID: u8 = 0x06;
+const TAG_SEQUENCE: u8 = 0x30;
+const TAG_IP_ADDRESS: u8 = 0x40;
+const TAG_COUNTER32: u8 = 0x41;
+const TAG_GAUGE32: u8 = 0x42;
+const TAG_TIMETICKS: u8 = 0x43;
+const TAG_COUNTER64: u8 = 0x46;
+const TAG_NO_SUCH_OBJECT: u8 = 0x80;
+const TAG_NO_SUCH_INSTANCE: u8 = 0x81;
+const TAG_END_OF_MIB: u8 = 0x82;
+
+const PDU_GET: u8 = 0xA0;
+const PDU_GET_NEXT: u8 = 0xA1;
+const PDU_RESPONSE: u8 = 0xA2;
+const PDU_GET_BULK: u8 = 0xA5;
+
+/// v1 error-status for "no such name", which also ends a GETNEXT walk.
+const ERR_NO_SUCH_NAME: i64 = 2;
+
+fn tlv(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut out = vec![tag];
+    let len = content.len();
+    if len < 0x80 {
+        out.push(len as u8);
+    } else {
+        let bytes: Vec<u8> = len.to_be_bytes().iter().copied().skip_while(|&b| b == 0).collect();
+        out.push(0x80 | bytes.len() as u8);
+        out.extend_from_slice(&bytes);
+    }
+    out.extend_from_slice(content);
+    out
+}
+
+fn encode_integer(v: i64) -> Vec<u8> {
+    let bytes = v.to_be_bytes();
+    // Drop leading bytes that only repeat the sign
+    let mut start = 0;
+    while start < 7 {
+        let (b, next) = (bytes[start], bytes[start + 1]);
+        if (b == 0x00 && next & 0x80 == 0) || (b == 0xFF && next & 0x80 != 0) {
+            start += 1;
+        } else {
+            break;
+        }
+    }
+    tlv(TAG_INTEGER, &bytes[start..])
+}
+
+fn encode_oid(oid: &[u32]) -> Vec<u8> {
+    let mut content = Vec::new();
+    if oid.len() >= 2 {
+        content.push((oid[0] * 40 + oid[1]) as u8);
+    }
+    for &sub in oid.iter().skip(2) {
+        let mut chunk = vec![(sub & 0x7F) as u8];
+        let mut rest = sub >> 7;
+        while rest > 0 {
+            chunk.push(0x80 | (rest & 0x7F) as u8);
+            rest >>= 7;
+        }
+        content.extend(chunk.iter().rev());
+    }
+    tlv(TAG_OID, &content)
+}
+
+/// Split the first TLV off `buf`: (tag, content, rest).
+fn read_tlv(buf: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let tag = *buf.first()?;
+    let first = *buf.get(1)?;
+    let (len, header) = if first & 0x80 == 0 {
+        (first as usize, 2)
+    } else {
+        let n = (first & 0x7F) as usize;
+        if n == 0 || n > 4 {
+            return None;
+        }
+        let bytes = buf.get(2..2 + n)?;
+        (bytes.iter().fold(0usize, |acc, &b| (acc << 8) | b as usize), 2 + n)
+    };
+    let content = buf.get(header..header + len)?;
+    Some((tag, content, &buf[header + len..]))
+}
+
+fn decode_integer(content: &[u8]) -> i64 {
+    let mut v: i64 = if content.first().is_some_and(|&b| b & 0x80 != 0) { -1 } else { 0 };
+    for &b in content.iter().take(8) {
+        v = (v << 8) | b as i64;
+    }
+    v
+}
+
+fn decode_unsigned(content: &[u8]) -> u64 {
+    content.iter().take(9).fold(0u64, |acc, &b| (acc << 8) | b as u64)
+}
+
+fn decode_oid(content: &[u8]) -> Vec<u32> {
+    let mut oid = Vec::new();
+    if let Some(&first) = content.first() {
+        oid.push((first / 40).min(2) as u32);
+        oid.push(first as u32 - oid[0] * 40);
+    }
+    let mut sub: u32 = 0;
+    for &b in content.iter().skip(1) {
+        sub = (sub << 7) | (b & 0x7F) as u32;
+        if b & 0x80 == 0 {
+            oid.push(sub);
+            sub = 0;
+        }
+    }
+    oid
+}
+
+fn decode_value(tag: u8, content: &[u8]) -> Value {
+    match tag {
+        TAG_INTEGER => Value::Integer(decode_integer(content)),
+        TAG_OCTET_STRING => Value::OctetString(content.to_vec()),
+        TAG_NULL => Value::Null,
+        TAG_OID => Value::Oid(decode_oid(content)),
+        TAG_IP_ADDRESS if content.len() == 4 => {
+            Value::IpAddress(Ipv4Addr::new(content[0], content[1], content[2], content[3]))
+        }
+        TAG_COUNTER32 | TAG_GAUGE32 | TAG_COUNTER64 => Value::Counter(decode_unsigned(content)),
+        TAG_TIMETICKS => Value::TimeTicks(decode_unsigned(content) as u32),
+        TAG_NO_SUCH_OBJECT | TAG_NO_SUCH_INSTANCE => Value::NoSuch,
+        TAG_END_OF_MIB => Value::EndOfMib,
+        other => Value::Other(other),
+    }
+}
+
+// ─── Messages ────────────────────────────────────────────────────────────────
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Version {
+    V1,
+    V2c,
+}
+
+impl Version {
+    fn number(self) -> i64 {
+        match self {
+            Version::V1 => 0,
+            Version::V2c => 1,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Version::V1 => "v1",
+            Version::V2c => "v2c",
+        }
+    }
+}
+
+/// A decoded PDU.
+#[derive(Debug, PartialEq)]
+struct Pdu {
+    tag: u8,
+    request_id: i64,
+    error_status: i64,
+    varbinds: Vec<(Vec<u32>, Value)>,
+}
+
+/// Build a request message. For GETBULK, `a` and `b` are non-repeaters and
+/// max-repetitions; otherwise they are sent as error-status and index (0).
+fn encode_request(version: Version, community: &str, tag: u8, request_id: i32, oids: &[&[u32]], a: i64, b: i64) -> Vec<u8> {
+    let varbinds: Vec<u8> = oids
+        .iter()
+        .flat_map(|oid| tlv(TAG_SEQUENCE, &[encode_oid(oid), tlv(TAG_NULL, &[])].concat()))
+        .collect();
+    let pdu = [
+        encode_integer(request_id as i64),
+        encode_integer(a),
+        encode_integer(b),
+        tlv(TAG_SEQUENCE, &varbinds),
+    ]
+    .concat();
+    let message = [
+        encode_integer(version.number()),
+        tlv(TAG_OCTET_STRING, community.as_bytes()),
+        tlv(tag, &pdu),
+    ]
+    .concat();
+    tlv(TAG_SEQUENCE, &message)
+}
+
+fn decode_message(buf: &[u8]) -> Option<(i64, Vec<u8>, Pdu)> {
+    let (TAG_SEQUENCE, message, _) = read_tlv(buf)? else { return None };
+    let (TAG_INTEGER, version, rest) = read_tlv(message)? else { return None };
+    let (TAG_OCTET_STRING, community, rest) = read_tlv(rest)? else { return None };
+    let (tag, pdu, _) = read_tlv(rest)?;
+    let (TAG_INTEGER, request_id, rest) = read_tlv(pdu)? else { return None };
+    let (TAG_INTEGER, error_status, rest) = read_tlv(rest)? else { return None };
+    let (TAG_INTEGER, _error_index, rest) = read_tlv(rest)? else { return None };
+    let (TAG_SEQUENCE, mut list, _) = read_tlv(rest)? else { return None };
+
+    let mut varbinds = Vec::new();
+    while !list.is_empty() {
+        let (TAG_SEQUENCE, vb, rest) = read_tlv(list)? else { return None };
+        let (TAG_OID, oid, value) = read_tlv(vb)? else { return None };
+        let (vtag, vcontent, _) = read_tlv(value)?;
+        varbinds.push((decode_oid(oid), decode_value(vtag, vcontent)));
+        list = rest;
+    }
+    Some((
+        decode_integer(version),
+        community.to_vec(),
+        Pdu {
+            tag,
+            request_id: decode_integer(request_id),
+            error_status: decode_integer(error_status),
+            varbinds,
+        },
+    ))
+}
+
+// ─── Client ──────────────────────────────────────────────────────────────────
+
+/// One agent, community and protocol version.
+#[derive(Clone, Debug)]
+pub struct Client {
+    pub addr: SocketAddr,
+    pub community: String,
+    pub version: Version,
+    pub timeout: Duration,
+}
+
+impl Client {
+    pub fn new(addr: SocketAddr, community: &str, version: Version, timeout: Duration) -> Self {
+        Self { addr, community: community.to_string(), version, timeout }
+    }
+
+    fn request(&self, tag: u8, oids: &[&[u32]], a: i64, b: i64) -> Result<Pdu, String> {
+        let id = REQUEST_ID.fetch_add(1, Ordering::Relaxed) & 0x7FFF_FFFF;
+        let packet = encode_request(self.version, &self.community, tag, id, oids, a, b);
+        let bind: SocketAddr = if self.addr.is_ipv4() { ([0, 0, 0, 0], 0).into() } else { "[::]:0".parse().unwrap() };
+        let sock = UdpSocket::bind(bind).map_err(|e| e.to_string())?;
+        sock.send_to(&packet, self.addr).map_err(|e| e.to_string())?;
+
+        let deadline = Instant::now() + self.timeout;
+        let mut buf = vec![0u8; 65535];
+        loop {
+            let left = deadline.saturating_duration_since(Instant::now());
+            if left.is_zero() {
+                return Err("timeout".into());
+            }
+            sock.set_read_timeout(Some(left)).map_err(|e| e.to_string())?;
+            let (len, from) = sock.recv_from(&mut buf).map_err(|_| "timeout".to_string())?;
+            if from.ip() != self.addr.ip() {
+                continue;
+            }
+            // Late answers to an earlier request on a reused port are skipped
+            match decode_message(&buf[..len]) {
+                Some((_, _, pdu)) if pdu.tag == PDU_RESPONSE && pdu.request_id == id as i64 => return Ok(pdu),
+                _ => continue,
+            }
+        }
+    }
+
+    /// GET the given OIDs. Missing objects come back as [`Value::NoSuch`].
+    pub fn get(&self, oids: &[&[u32]]) -> Result<Vec<(Vec<u32>, Value)>, String> {
+        let pdu = self.request(PDU_GET, oids, 0, 0)?;
+        if pdu.error_status == ERR_NO_SUCH_NAME && self.version == Version::V1 {
+            // v1 fails the whole request; ask one by one
+            if oids.len() > 1 {
+                return Ok(oids
+                    .iter()
+                    .map(|oid| {
+                        let value = self.get(&[oid]).ok().and_then(|mut v| v.pop()).map_or(Value::NoSuch, |(_, v)| v);
+                        (oid.to_vec(), value)
+                    })
+                    .collect());
+            }
+            return Ok(vec![(oids[0].to_vec(), Value::NoSuch)]);
+        }
+        if pdu.error_status != 0 {
+            return Err(format!("error-status {}", pdu.error_status));
+        }
+        Ok(pdu.varbinds)
+    }
+
+    /// Every object below `root`, in OID order: GETBULK on v2c, GETNEXT on v1.
+    pub fn walk(&self, root: &[u32]) -> Result<Vec<(Vec<u32>, Value)>, String> {
+        let mut rows = Vec::new();
+        let mut cursor = root.to_vec();
+        loop {
+            let pdu = match self.version {
+                Version::V2c => self.request(PDU_GET_BULK, &[&cursor], 0, BULK_REPETITIONS)?,
+                Version::V1 => self.request(PDU_GET_NEXT, &[&cursor], 0, 0)?,
+            };
+            if pdu.error_status == ERR_NO_SUCH_NAME {
+                break;
+            }
+            if pdu.error_status != 0 {
+                return Err(format!("error-status {}", pdu.error_status));
+            }
+            if pdu.varbinds.is_empty() {
+                break;
+            }
+            for (oid, value) in pdu.varbinds {
+                // Leaving the subtree, the end of the MIB, or an agent that
+                // does not move forward all end the walk
+                if !oid.starts_with(root) || value == Value::EndOfMib || oid <= cursor {
+                    return Ok(rows);
+                }
+                cursor = oid.clone();
+                rows.push((oid, value));
+                if rows.len() >= MAX_WALK_ROWS {
+                    return Ok(rows);
+                }
+            }
+        }
+        Ok(rows)
+    }
+
+    /// One table column, keyed by the index after `entry.column`.
+    fn column(&self, entry: &[u32], column: u32) -> BTreeMap<Vec<u32>, Value> {
+        let root: Vec<u32> = entry.iter().copied().chain([column]).collect();
+        self.walk(&root)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(oid, value)| (oid[root.len()..].to_vec(), value))
+            .collect()
+    }
+}
+
+/// Find a community and version `ip` answers to, by asking for sysDescr.
+pub fn connect(ip: Ipv4Addr, communities: &[String], timeout: Duration) -> Option<Client> {
+    connect_addr(SocketAddr::from((ip, 161)), communities, timeout)
+}
+
+fn connect_addr(addr: SocketAddr, communities: &[String], timeout: Duration) -> Option<Client> {
+    for version in [Version::V2c, Version::V1] {
+        for community in communities {
+            let client = Client::new(addr, community, version, timeout);
+            if client.get(&[SYS_DESCR]).is_ok_and(|vb| vb.iter().any(|(_, v)| *v != Value::NoSuch)) {
+                return Some(client);
+            }
+        }
+    }
+    None
+}
+
+// ─── Device inventory ────────────────────────────────────────────────────────
+
+/// What a device's SNMP agent reports.
+#[derive(Clone, Debug)]
+pub struct SnmpInfo {
+    pub community: String,
+    pub version: Version,
+    pub descr: Option<String>,
+    pub uptime: Option<Duration>,
+    pub contact: Option<String>,
+    pub name: Option<String>,
+    pub location: Option<String>,
+    pub interfaces: Vec<SnmpInterface>,
+    pub neighbors: Vec<LldpNeighbor>,
+    pub polled_at: Instant,
+}
+
+/// One row of ifTable, completed from ifXTable where the agent has it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SnmpInterface {
+    pub index: u32,
+    /// ifName ("Gi0/1"), falling back to ifDescr.
+    pub name: String,
+    pub descr: String,
+    /// ifAlias, the description an admin gave the port.
+    pub alias: Option<String>,
+    /// IANA ifType: 6 Ethernet, 24 loopback, 71 802.11, ...
+    pub if_type: u32,
+    pub speed_mbps: u64,
+    pub mac: Option<String>,
+    pub up: bool,
+    /// Octet counters, 64-bit when ifXTable has them.
+    pub in_octets: u64,
+    pub out_octets: u64,
+    /// Rates since the previous poll, in bytes per second.
+    pub in_rate: Option<f64>,
+    pub out_rate: Option<f64>,
+}
+
+/// A device LLDP says is plugged into one of the agent's ports.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LldpNeighbor {
+    /// The agent's own port, by its LLDP port id or description.
+    pub local_port: String,
+    /// Chassis id; a MAC address is formatted "AA:BB:CC:DD:EE:FF".
+    pub chassis_id: String,
+    pub port_id: String,
+    pub port_descr: Option<String>,
+    pub sys_name: Option<String>,
+    pub mgmt_ip: Option<Ipv4Addr>,
+}
+
+impl LldpNeighbor {
+    /// sysName, else the chassis id.
+    pub fn label(&self) -> &str {
+        self.sys_name.as_deref().unwrap_or(&self.chassis_id)
+    }
+}
+
+impl SnmpInfo {
+    /// sysName, when it reads like a hostname. Catches routers, managed
+    /// switches, network printers, NAS and access points.
+    pub fn hostname(&self) -> Option<&str> {
+        let name = self.name.as_deref()?;
+        (!name.is_empty() && name.len() <= 255 && name.chars().all(|c| c.is_ascii_graphic() || c == ' ')).then_some(name)
+    }
+
+    /// Compute interface rates from the counters of an earlier poll.
+    pub fn carry_rates(&mut self, prev: &SnmpInfo) {
+        let secs = self.polled_at.saturating_duration_since(prev.polled_at).as_secs_f64();
+        if secs < 1.0 {
+            return;
+        }
+        for iface in &mut self.interfaces {
+            if let Some(old) = prev.interfaces.iter().find(|i| i.index == iface.index) {
+                // A counter that went backwards wrapped or was reset
+                iface.in_rate = iface.in_octets.checked_sub(old.in_octets).map(|d| d as f64 / secs);
+                iface.out_rate = iface.out_octets.checked_sub(old.out_octets).map(|d| d as f64 / secs);
+            }
+        }
+    }
+
+    /// Interfaces worth listing: not loopbacks or down ports without traffic.
+    pub fn active_interfaces(&self) -> impl Iterator<Item = &SnmpInterface> {
+        self.interfaces
+            .iter()
+            .filter(|i| i.if_type != 24 && (i.up || i.in_octets > 0 || i.out_octets > 0))
+    }
+}
+
+/// Read the system group, interfaces and LLDP neighbours.
+pub fn poll(client: &Client) -> Option<SnmpInfo> {
+    let system = client.get(&[SYS_DESCR, SYS_UPTIME, SYS_CONTACT, SYS_NAME, SYS_LOCATION]).ok()?;
+    let text = |oid: &[u32]| system.iter().find(|(o, _)| o == oid).and_then(|(_, v)| v.as_text());
+    let uptime = system
+        .iter()
+        .find_map(|(o, v)| match v {
+            Value::TimeTicks(t) if o == SYS_UPTIME => Some(Duration::from_millis(*t as u64 * 10)),
+            _ => None,
+        });
+    Some(SnmpInfo {
+        community: client.community.clone(),
+        version: client.version,
+        descr: text(SYS_DESCR),
+        uptime,
+        contact: text(SYS_CONTACT),
+        name: text(SYS_NAME),
+        location: text(SYS_LOCATION),
+        interfaces: interfaces(client),
+        neighbors: neighbors(client),
+        polled_at: Instant::now(),
+    })
+}
+
+fn single_index(map: BTreeMap<Vec<u32>, Value>) -> HashMap<u32, Value> {
+    map.into_iter().filter_map(|(idx, v)| Some((*idx.first()?, v))).collect()
+}
+
+fn interfaces(client: &Client) -> Vec<SnmpInterface> {
+    let col = |entry: &[u32], c: u32| single_index(client.column(entry, c));
+    let descr = col(IF_ENTRY, 2);
+    if descr.is_empty() {
+        return Vec::new();
+    }
+    let if_type = col(IF_ENTRY, 3);
+    let speed = col(IF_ENTRY, 5);
+    let mac = col(IF_ENTRY, 6);
+    let oper = col(IF_ENTRY, 8);
+    let in_octets = col(IF_ENTRY, 10);
+    let out_octets = col(IF_ENTRY, 16);
+    let name = col(IFX_ENTRY, 1);
+    let hc_in = col(IFX_ENTRY, 6);
+    let hc_out = col(IFX_ENTRY, 10);
+    let high_speed = col(IFX_ENTRY, 15);
+    let alias = col(IFX_ENTRY, 18);
+
+    let num = |m: &HashMap<u32, Value>, i: u32| m.get(&i).and_then(Value::as_u64);
+    let mut list: Vec<SnmpInterface> = descr
+        .iter()
+        .map(|(&index, d)| {
+            let descr = d.as_text().unwrap_or_default();
+            SnmpInterface {
+                index,
+                name: name.get(&index).and_then(Value::as_text).unwrap_or_else(|| descr.clone()),
+                descr,
+                alias: alias.get(&index).and_then(Value::as_text),
+                if_type: num(&if_type, index).unwrap_or(0) as u32,
+                speed_mbps: num(&high_speed, index)
+                    .filter(|&s| s > 0)
+                    .unwrap_or_else(|| num(&speed, index).unwrap_or(0) / 1_000_000),
+                mac: mac.get(&index).and_then(Value::as_bytes).filter(|b| b.len() == 6).map(hex_bytes),
+                up: num(&oper, index) == Some(1),
+                in_octets: num(&hc_in, index).or_else(|| num(&in_octets, index)).unwrap_or(0),
+                out_octets: num(&hc_out, index).or_else(|| num(&out_octets, index)).unwrap_or(0),
+                in_rate: None,
+                out_rate: None,
+            }
+        })
+        .collect();
+    list.sort_by_key(|i| i.index);
+    list
+}
+
+/// Chassis or port id by LLDP subtype: MAC addresses (4 for chassis, 3 for
+/// ports) and IPv4 network addresses (5) are decoded, the rest is text.
+fn lldp_id(subtype: Option<u64>, value: &Value, mac_subtype: u64) -> String {
+    let Some(bytes) = value.as_bytes() else {
+        return value.as_text().unwrap_or_default();
+    };
+    match subtype {
+        Some(s) if s == mac_subtype && bytes.len() == 6 => hex_bytes(bytes),
+        Some(5) if bytes.len() == 5 && bytes[0] == 1 => Ipv4Addr::new(bytes[1], bytes[2], bytes[3], bytes[4]).to_string(),
+        _ => display_bytes(bytes),
+    }
+}
+
+fn neighbors(client: &Client) -> Vec<LldpNeighbor> {
+    let chassis = client.column(LLDP_REM, 5);
+    if chassis.is_empty() {
+        return Vec::new();
+    }
+    let chassis_subtype = client.column(LLDP_REM, 4);
+    let port_subtype = client.column(LLDP_REM, 6);
+    let port_id = client.column(LLDP_REM, 7);
+    let port_descr = client.column(LLDP_REM, 8);
+    let sys_name = client.column(LLDP_REM, 9);
+    let loc_port_id = single_index(client.column(LLDP_LOC_PORT, 3));
+    let loc_port_descr = single_index(client.column(LLDP_LOC_PORT, 4));
+
+    // Management addresses: index is timeMark.localPort.remIndex.subtype.len.addr...
+    let mut mgmt: HashMap<Vec<u32>, Ipv4Addr> = HashMap::new();
+    for idx in client.column(LLDP_REM_MAN_ADDR, 3).keys() {
+        if let [t, l, r, 1, 4, a, b, c, d] = idx[..] {
+            mgmt.insert(vec![t, l, r], Ipv4Addr::new(a as u8, b as u8, c as u8, d as u8));
+        }
+    }
+
+    chassis
+        .iter()
+        .filter(|(idx, _)| idx.len() == 3)
+        .map(|(idx, value)| {
+            let local = idx[1];
+            let local_port = loc_port_descr
+                .get(&local)
+                .and_then(Value::as_text)
+                .or_else(|| loc_port_id.get(&local).and_then(Value::as_text))
+                .unwrap_or_else(|| local.to_string());
+            LldpNeighbor {
+                local_port,
+                chassis_id: lldp_id(chassis_subtype.get(idx).and_then(Value::as_u64), value, 4),
+                port_id: port_id
+                    .get(idx)
+                    .map(|v| lldp_id(port_subtype.get(idx).and_then(Value::as_u64), v, 3))
+                    .unwrap_or_default(),
+                port_descr: port_descr.get(idx).and_then(Value::as_text),
+                sys_name: sys_name.get(idx).and_then(Value::as_text),
+                mgmt_ip: mgmt.get(idx).copied(),
+            }
+        })
+        .collect()
+}
+
+/// Poll every IP that answers one of `communities`, in parallel.
+pub fn poll_batch(ips: &[Ipv4Addr], communities: &[String], timeout: Duration) -> HashMap<Ipv4Addr, SnmpInfo> {
+    let found: Mutex<HashMap<Ipv4Addr, SnmpInfo>> = Mutex::new(HashMap::new());
+    if communities.is_empty() {
+        return HashMap::new();
+    }
+    thread::scope(|s| {
+        for &ip in ips {
+            let found = &found;
+            s.spawn(move || {
+                if let Some(info) = connect(ip, communities, timeout).and_then(|c| poll(&c)) {
+                    found.lock().unwrap().insert(ip, info);
+                }
+            });
+        }
+    });
+    found.into_inner().unwrap()
+}
+
+/// The switch port a device hangs off: (agent IP, agent info, neighbour
+/// entry), matched on the LLDP management address or chassis MAC.
+pub fn uplink_of<'a>(
+    agents: &'a HashMap<Ipv4Addr, SnmpInfo>,
+    ip: IpAddr,
+    mac: &str,
+) -> Option<(Ipv4Addr, &'a SnmpInfo, &'a LldpNeighbor)> {
+    agents.iter().find_map(|(&agent_ip, info)| {
+        if IpAddr::V4(agent_ip) == ip {
+            return None;
+        }
+        info.neighbors
+            .iter()
+            .find(|n| n.mgmt_ip.is_some_and(|m| IpAddr::V4(m) == ip) || (!mac.is_empty() && n.chassis_id.eq_ignore_ascii_case(mac)))
+            .map(|n| (agent_ip, info, n))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode_value(v: &Value) -> Vec<u8> {
+        match v {
+            Value::Integer(i) => encode_integer(*i),
+            Value::OctetString(b) => tlv(TAG_OCTET_STRING, b),
+            Value::Null => tlv(TAG_NULL, &[]),
+            Value::Oid(o) => encode_oid(o),
+            Value::IpAddress(ip) => tlv(TAG_IP_ADDRESS, &ip.octets()),
+            Value::Counter(c) => {
+                let bytes = c.to_be_bytes();
+                let start = bytes.iter().position(|&b| b != 0).unwrap_or(7);
+                // Leading zero keeps the top bit clear, as BER requires
+                let mut content = if bytes[start] & 0x80 != 0 { vec![0] } else { Vec::new() };
+                content.extend_from_slice(&bytes[start..]);
+                tlv(if *c > u32::MAX as u64 { TAG_COUNTER64 } else { TAG_COUNTER32 }, &content)
+            }
+            Value::TimeTicks(t) => {
+                let mut content = encode_integer(*t as i64);
+                content[0] = TAG_TIMETICKS;
+                content
+            }
+            Value::NoSuch => tlv(TAG_NO_SUCH_OBJECT, &[]),
+            Value::EndOfMib => tlv(TAG_END_OF_MIB, &[]),
+            Value::Other(tag) => tlv(*tag, &[]),
+        }
+    }
+
+    /// A loopback agent serving `mib` to `community` with `version`; other
+    /// requests are dropped, like a real agent would.
+    fn agent(mib: BTreeMap<Vec<u32>, Value>, community: &'static str, version: Version) -> SocketAddr {
+        let sock = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let addr = sock.local_addr().unwrap();
+        thread::spawn(move || {
+            let mut buf = [0u8; 2048];
+            while let Ok((len, from)) = sock.recv_from(&mut buf) {
+                let Some((ver, comm, pdu)) = decode_message(&buf[..len]) else { continue };
+                if comm != community.as_bytes() || ver != version.number() {
+                    continue;
+                }
+                let next = |oid: &[u32]| mib.range::<[u32], _>((std::ops::Bound::Excluded(oid), std::ops::Bound::Unbounded)).next();
+                let mut error = 0;
+                let mut out: Vec<(Vec<u32>, Value)> = Vec::new();
+                for (oid, _) in &pdu.varbinds {
+                    match pdu.tag {
+                        PDU_GET => match mib.get(oid) {
+                            Some(v) => out.push((oid.clone(), v.clone())),
+                            None if version == Version::V1 => error = ERR_NO_SUCH_NAME,
+                            None => out.push((oid.clone(), Value::NoSuch)),
+                        },
+                        PDU_GET_NEXT => match next(oid) {
+                            Some((o, v)) => out.push((o.clone(), v.clone())),
+                            None => error = ERR_NO_SUCH_NAME,
+                        },
+                        PDU_GET_BULK => {
+                            let mut cursor = oid.clone();
+                            for _ in 0..3 {
+                                match next(&cursor) {
+                                    Some((o, v)) => {
+                                        out.push((o.clone(), v.clone()));
+                                        cursor = o.clone();
+                                    }
+                                    None => {
+                                        out.push((cursor.clone(), Value::EndOfMib));
+                                        break;
+                                    }
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                let varbinds: Vec<u8> = out
+                    .iter()
+                    .flat_map(|(o, v)| tlv(TAG_SEQUENCE, &[encode_oid(o), encode_value(v)].concat()))
+                    .collect();
+                let body = [encode_integer(pdu.request_id), encode_integer(error), encode_integer(0), tlv(TAG_SEQUENCE, &varbinds)].concat();
+                let msg = [encode_integer(ver), tlv(TAG_OCTET_STRING, &comm), tlv(PDU_RESPONSE, &body)].concat();
+                let _ = sock.send_to(&tlv(TAG_SEQUENCE, &msg), from);
+            }
+        });
+        addr
+    }
+
+    fn oid(prefix: &[u32], rest: &[u32]) -> Vec<u32> {
+        prefix.iter().chain(rest).copied().collect()
+    }
+
+    fn text(s: &str) -> Value {
+        Value::OctetString(s.as_bytes().to_vec())
+    }
+
+    /// A two-port switch with one LLDP neighbour on port 2.
+    fn switch_mib() -> BTreeMap<Vec<u32>, Value> {
+        let mut mib = BTreeMap::new();
+        mib.insert(SYS_DESCR.to_vec(), text("Cisco IOS Software, C2960 Software"));
+        mib.insert(SYS_UPTIME.to_vec(), Value::TimeTicks(8_640_000));
+        mib.insert(SYS_NAME.to_vec(), text("sw-core"));
+        mib.insert(SYS_LOCATION.to_vec(), text("Rack 1"));
+        for (i, name, up, octets) in [(1u32, "Gi0/1", 1, 1_000u64), (2, "Gi0/2", 1, 5_000_000_000)] {
+            mib.insert(oid(IF_ENTRY, &[2, i]), text(&format!("GigabitEthernet0/{}", i)));
+            mib.insert(oid(IF_ENTRY, &[3, i]), Value::Integer(6));
+            mib.insert(oid(IF_ENTRY, &[5, i]), Value::Counter(1_000_000_000));
+            mib.insert(oid(IF_ENTRY, &[8, i]), Value::Integer(up));
+            mib.insert(oid(IF_ENTRY, &[10, i]), Value::Counter(octets & 0xFFFF_FFFF));
+            mib.insert(oid(IFX_ENTRY, &[1, i]), text(name));
+            mib.insert(oid(IFX_ENTRY, &[6, i]), Value::Counter(octets));
+            mib.insert(oid(IFX_ENTRY, &[10, i]), Value::Counter(octets / 2));
+        }
+        mib.insert(oid(LLDP_LOC_PORT, &[3, 2]), text("Gi0/2"));
+        mib.insert(oid(LLDP_REM, &[4, 0, 2, 1]), Value::Integer(4));
+        mib.insert(oid(LLDP_REM, &[5, 0, 2, 1]), Value::OctetString(vec![0x00, 0x11, 0x32, 0xAA, 0xBB, 0xCC]));
+        mib.insert(oid(LLDP_REM, &[6, 0, 2, 1]), Value::Integer(5));
+        mib.insert(oid(LLDP_REM, &[7, 0, 2, 1]), text("eth0"));
+        mib.insert(oid(LLDP_REM, &[9, 0, 2, 1]), text("nas"));
+        mib.insert(oid(LLDP_REM_MAN_ADDR, &[3, 0, 2, 1, 1, 4, 192, 168, 1, 50]), Value::Integer(2));
+        mib
+    }
+
+    #[test]
+    fn test_ber_roundtrip() {
+        for v in [0i64, 1, 127, 128, 255, 256, -1, -129, 2_147_483_647] {
+            let enc = encode_integer(v);
+            let (tag, content, rest) = read_tlv(&enc).unwrap();
+            assert_eq!((tag, decode_integer(content), rest.len()), (TAG_INTEGER, v, 0), "{}", v);
+        }
+        let o = vec![1, 0, 8802, 1, 1, 2, 1, 4, 1, 1, 5, 4_000_000_000];
+        let encoded = encode_oid(&o);
+        let (_, content, _) = read_tlv(&encoded).unwrap();
+        assert_eq!(decode_oid(content), o);
+
+        // Long-form lengths
+        let big = tlv(TAG_OCTET_STRING, &[b'x'; 300]);
+        assert_eq!(&big[..4], &[0x04, 0x82, 0x01, 0x2C]);
+        assert_eq!(read_tlv(&big).unwrap().1.len(), 300);
+    }
+
+    #[test]
+    fn test_connect_tries_communities_and_walks_v1() {
+        let addr = agent(switch_mib(), "s3cret", Version::V1);
+        let communities = vec!["public".to_string(), "s3cret".to_string()];
+        let client = connect_addr(addr, &communities, Duration::from_millis(150)).unwrap();
+        assert_eq!((client.community.as_str(), client.version), ("s3cret", Version::V1));
+
+        let rows = client.walk(&oid(IFX_ENTRY, &[1])).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1].1.as_text().as_deref(), Some("Gi0/2"));
+
+        // v1 fails a GET with any missing object; the rest still comes back
+        let vb = client.get(&[SYS_NAME, SYS_CONTACT]).unwrap();
+        assert_eq!(vb[0].1.as_text().as_deref(), Some("sw-core"));
+        assert_eq!(vb[1].1, Value::NoSuch);
+    }
+
+    #[test]
+    fn test_poll_switch_inventory() {
+        let addr = agent(switch_mib(), "public", Version::V2c);
+        let client = connect_addr(addr, &["public".to_string()], Duration::from_millis(500)).unwrap();
+        assert_eq!(client.version, Version::V2c);
+        let info = poll(&client).unwrap();
+        assert_eq!(info.name.as_deref(), Some("sw-core"));
+        assert_eq!(info.contact, None);
+        assert_eq!(info.uptime, Some(Duration::from_secs(86_400)));
+
+        assert_eq!(info.interfaces.len(), 2);
+        let gi2 = &info.interfaces[1];
+        assert_eq!((gi2.name.as_str(), gi2.speed_mbps, gi2.up), ("Gi0/2", 1000, true));
+        // The 64-bit ifXTable counter wins over the wrapped 32-bit one
+        assert_eq!(gi2.in_octets, 5_000_000_000);
+        assert_eq!(gi2.out_octets, 2_500_000_000);
+
+        assert_eq!(info.neighbors, vec![LldpNeighbor {
+            local_port: "Gi0/2".into(),
+            chassis_id: "00:11:32:AA:BB:CC".into(),
+            port_id: "eth0".into(),
+            port_descr: None,
+            sys_name: Some("nas".into()),
+            mgmt_ip: Some(Ipv4Addr::new(192, 168, 1, 50)),
+        }]);
+    }
+
+    #[test]
+    fn test_rates_and_uplink() {
+        let addr = agent(switch_mib(), "public", Version::V2c);
+        let client = connect_addr(addr, &["public".to_string()], Duration::from_millis(500)).unwrap();
+        let mut prev = poll(&client).unwrap();
+        let mut now = prev.clone();
+        prev.polled_at = now.polled_at.checked_sub(Duration::from_secs(10)).unwrap();
+        prev.interfaces[1].in_octets -= 1_000_000;
+        now.carry_rates(&prev);
+        assert_eq!(now.interfaces[1].in_rate, Some(100_000.0));
+        assert_eq!(now.interfaces[1].out_rate, Some(0.0));
+
+        let agents = HashMap::from([(Ipv4Addr::new(192, 168, 1, 2), now)]);
+        let nas = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 50));
+        let (agent_ip, _, n) = uplink_of(&agents, nas, "").unwrap();
+        assert_eq!((agent_ip, n.local_port.as_str()), (Ipv4Addr::new(192, 168, 1, 2), "Gi0/2"));
+        // By chassis MAC when the IP is unknown
+        assert!(uplink_of(&agents, IpAddr::V4(Ipv4Addr::new(10, 0, 0, 9)), "00:11:32:aa:bb:cc").is_some());
+        assert!(uplink_of(&agents, IpAddr::V4(Ipv4Addr::new(10, 0, 0, 9)), "").is_none());
+    }
+}
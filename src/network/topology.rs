@@ -0,0 +1,347 @@
+//! Topology graph model and its export formats.
+//!
+//! The Topology tab's diagram — this PC, the gateway, DNS servers, LAN
+//! devices and remote hosts — as plain nodes and edges with their traffic,
+//! country and vendor. `e` on the Topology tab writes it as Graphviz DOT
+//! (`.dot` / `.gv`), Mermaid (`.mmd`, or a fenced block in `.md`) or a JSON
+//! node/edge list (anything else).
+
+use std::net::IpAddr;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::utils::format_speed;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NodeKind {
+    Host,
+    Gateway,
+    Dns,
+    Lan,
+    Remote,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EdgeKind {
+    /// This PC to the gateway.
+    Uplink,
+    /// This PC to a DNS server.
+    Dns,
+    /// This PC to a LAN device.
+    Lan,
+    /// A switch to the LAN device on one of its ports (LLDP).
+    Switch,
+    /// This PC to a remote host it has connections with.
+    Connection,
+}
+
+/// Byte counters and rates; unknown values are left out of the JSON.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct Traffic {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bytes_sent: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bytes_received: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub send_bps: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receive_bps: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connections: Option<usize>,
+}
+
+impl Traffic {
+    /// "↓ 1.2 MB/s ↑ 40 KB/s", "3 conn", or empty.
+    fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(c) = self.connections {
+            parts.push(format!("{} conn", c));
+        }
+        if self.receive_bps.is_some() || self.send_bps.is_some() {
+            parts.push(format!(
+                "\u{2193} {} \u{2191} {}",
+                format_speed(self.receive_bps.unwrap_or(0.0)),
+                format_speed(self.send_bps.unwrap_or(0.0)),
+            ));
+        }
+        parts.join(", ")
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct GraphNode {
+    /// Stable identifier usable in DOT and Mermaid, e.g. "lan_192_168_1_20".
+    pub id: String,
+    pub kind: NodeKind,
+    pub label: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip: Option<IpAddr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mac: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vendor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device_type: Option<String>,
+    /// ISO country code of a remote host.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    /// Process with the most connections to a remote host.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub process: Option<String>,
+    pub online: bool,
+    pub traffic: Traffic,
+}
+
+impl GraphNode {
+    pub fn new(id: impl Into<String>, kind: NodeKind, label: impl Into<String>) -> Self {
+        Self {
+            id: node_id(&id.into()),
+            kind,
+            label: label.into(),
+            ip: None,
+            mac: None,
+            vendor: None,
+            device_type: None,
+            country: None,
+            process: None,
+            online: true,
+            traffic: Traffic::default(),
+        }
+    }
+
+    /// Second line of the drawn node: address plus vendor / country / process.
+    fn detail(&self) -> String {
+        let mut parts: Vec<String> = Vec::new();
+        if let Some(ip) = self.ip {
+            if self.label != ip.to_string() {
+                parts.push(ip.to_string());
+            }
+        }
+        parts.extend(self.vendor.clone());
+        parts.extend(self.country.clone());
+        parts.extend(self.process.clone());
+        parts.join(" \u{00B7} ")
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct GraphEdge {
+    pub from: String,
+    pub to: String,
+    pub kind: EdgeKind,
+    /// Switch port name for `switch` edges.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<String>,
+    /// Traffic seen on the edge (an established TCP connection, a DNS server in use).
+    pub active: bool,
+    pub traffic: Traffic,
+}
+
+impl GraphEdge {
+    pub fn new(from: &str, to: &str, kind: EdgeKind) -> Self {
+        Self { from: node_id(from), to: node_id(to), kind, port: None, active: false, traffic: Traffic::default() }
+    }
+
+    fn label(&self) -> String {
+        match (&self.port, self.traffic.summary()) {
+            (Some(port), t) if t.is_empty() => port.clone(),
+            (Some(port), t) => format!("{}, {}", port, t),
+            (None, t) => t,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct TopologyGraph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+/// Identifier safe for DOT and Mermaid: ASCII letters, digits and `_`.
+pub fn node_id(raw: &str) -> String {
+    raw.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect()
+}
+
+// ─── Formats ─────────────────────────────────────────────────────────────────
+
+fn node_color(kind: NodeKind) -> &'static str {
+    match kind {
+        NodeKind::Host => "#64b4ff",
+        NodeKind::Gateway => "#50c878",
+        NodeKind::Dns => "#50c8ff",
+        NodeKind::Lan => "#aa8cff",
+        NodeKind::Remote => "#b4c3dc",
+    }
+}
+
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Graphviz DOT, left to right, nodes filled by kind.
+pub fn to_dot(graph: &TopologyGraph) -> String {
+    let mut out = String::from("digraph psnet {\n    rankdir=LR;\n    node [shape=box, style=\"rounded,filled\", fontname=\"Segoe UI\"];\n\n");
+    for n in &graph.nodes {
+        let detail = n.detail();
+        let label = if detail.is_empty() {
+            dot_escape(&n.label)
+        } else {
+            format!("{}\\n{}", dot_escape(&n.label), dot_escape(&detail))
+        };
+        let style = if n.online { "" } else { ", style=\"rounded,dashed\"" };
+        out.push_str(&format!("    {} [label=\"{}\", fillcolor=\"{}\"{}];\n", n.id, label, node_color(n.kind), style));
+    }
+    out.push('\n');
+    for e in &graph.edges {
+        let mut attrs = Vec::new();
+        let label = e.label();
+        if !label.is_empty() {
+            attrs.push(format!("label=\"{}\"", dot_escape(&label)));
+        }
+        if !e.active {
+            attrs.push("style=dashed".to_string());
+        }
+        let attrs = if attrs.is_empty() { String::new() } else { format!(" [{}]", attrs.join(", ")) };
+        out.push_str(&format!("    {} -> {}{};\n", e.from, e.to, attrs));
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn mermaid_escape(s: &str) -> String {
+    s.replace('"', "#quot;")
+}
+
+/// Mermaid flowchart with one class per node kind.
+pub fn to_mermaid(graph: &TopologyGraph) -> String {
+    let mut out = String::from("graph LR\n");
+    for n in &graph.nodes {
+        let detail = n.detail();
+        let label = if detail.is_empty() {
+            mermaid_escape(&n.label)
+        } else {
+            format!("{}<br/>{}", mermaid_escape(&n.label), mermaid_escape(&detail))
+        };
+        out.push_str(&format!("    {}[\"{}\"]\n", n.id, label));
+    }
+    for e in &graph.edges {
+        let arrow = if e.active { "-->" } else { "-.->" };
+        let label = e.label();
+        if label.is_empty() {
+            out.push_str(&format!("    {} {} {}\n", e.from, arrow, e.to));
+        } else {
+            out.push_str(&format!("    {} {}|\"{}\"| {}\n", e.from, arrow, mermaid_escape(&label), e.to));
+        }
+    }
+    for kind in [NodeKind::Host, NodeKind::Gateway, NodeKind::Dns, NodeKind::Lan, NodeKind::Remote] {
+        let ids: Vec<&str> = graph.nodes.iter().filter(|n| n.kind == kind).map(|n| n.id.as_str()).collect();
+        if ids.is_empty() {
+            continue;
+        }
+        let class = serde_json::to_value(kind).ok().and_then(|v| v.as_str().map(str::to_string)).unwrap_or_default();
+        out.push_str(&format!("    classDef {} fill:{},stroke:#1e3255\n", class, node_color(kind)));
+        out.push_str(&format!("    class {} {}\n", ids.join(","), class));
+    }
+    out
+}
+
+pub fn to_json(graph: &TopologyGraph) -> Result<String, String> {
+    serde_json::to_string_pretty(graph).map_err(|e| e.to_string())
+}
+
+/// Write the graph in the format named by the file extension.
+pub fn export(graph: &TopologyGraph, path: &Path) -> Result<String, String> {
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_ascii_lowercase();
+    let (text, format) = match ext.as_str() {
+        "dot" | "gv" => (to_dot(graph), "DOT"),
+        "mmd" | "mermaid" => (to_mermaid(graph), "Mermaid"),
+        "md" => (format!("```mermaid\n{}```\n", to_mermaid(graph)), "Mermaid"),
+        _ => (to_json(graph)?, "JSON"),
+    };
+    std::fs::write(path, text).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(format!(
+        "{} nodes and {} edges as {} to {}",
+        graph.nodes.len(),
+        graph.edges.len(),
+        format,
+        path.display()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> TopologyGraph {
+        let mut host = GraphNode::new("host", NodeKind::Host, "This PC");
+        host.ip = Some("192.168.1.20".parse().unwrap());
+        let mut nas = GraphNode::new("lan_192.168.1.5", NodeKind::Lan, "Office \"NAS\"");
+        nas.ip = Some("192.168.1.5".parse().unwrap());
+        nas.vendor = Some("Synology".to_string());
+        nas.online = false;
+        let mut remote = GraphNode::new("remote_140.82.121.4", NodeKind::Remote, "github.com");
+        remote.country = Some("DE".to_string());
+
+        let mut lan = GraphEdge::new("host", "lan_192.168.1.5", EdgeKind::Lan);
+        lan.traffic = Traffic { send_bps: Some(0.0), receive_bps: Some(2048.0), ..Traffic::default() };
+        let mut conn = GraphEdge::new("host", "remote_140.82.121.4", EdgeKind::Connection);
+        conn.active = true;
+        conn.traffic.connections = Some(3);
+        TopologyGraph { nodes: vec![host, nas, remote], edges: vec![lan, conn] }
+    }
+
+    #[test]
+    fn test_node_ids_are_sanitized() {
+        assert_eq!(node_id("lan_fe80::1%4"), "lan_fe80__1_4");
+        let g = sample();
+        assert_eq!(g.nodes[1].id, "lan_192_168_1_5");
+        assert_eq!(g.edges[1].to, "remote_140_82_121_4");
+    }
+
+    #[test]
+    fn test_dot_output() {
+        let dot = to_dot(&sample());
+        assert!(dot.starts_with("digraph psnet {"));
+        assert!(dot.contains("host [label=\"This PC\\n192.168.1.20\", fillcolor=\"#64b4ff\"];"));
+        assert!(dot.contains("lan_192_168_1_5 [label=\"Office \\\"NAS\\\"\\n192.168.1.5 \u{00B7} Synology\""));
+        assert!(dot.contains("style=\"rounded,dashed\""));
+        assert!(dot.contains("host -> remote_140_82_121_4 [label=\"3 conn\"];"));
+        assert!(dot.contains("host -> lan_192_168_1_5 [label=\"\u{2193} 2.0 KB/s \u{2191} 0 B/s\", style=dashed];"));
+        assert!(dot.trim_end().ends_with('}'));
+    }
+
+    #[test]
+    fn test_mermaid_output() {
+        let mmd = to_mermaid(&sample());
+        assert!(mmd.starts_with("graph LR\n"));
+        assert!(mmd.contains("    lan_192_168_1_5[\"Office #quot;NAS#quot;<br/>192.168.1.5 \u{00B7} Synology\"]\n"));
+        assert!(mmd.contains("    remote_140_82_121_4[\"github.com<br/>DE\"]\n"));
+        assert!(mmd.contains("    host -->|\"3 conn\"| remote_140_82_121_4\n"));
+        assert!(mmd.contains("    host -.->|"));
+        assert!(mmd.contains("    class lan_192_168_1_5 lan\n"));
+    }
+
+    #[test]
+    fn test_json_and_export_by_extension() {
+        let g = sample();
+        let json: serde_json::Value = serde_json::from_str(&to_json(&g).unwrap()).unwrap();
+        assert_eq!(json["nodes"][1]["kind"], "lan");
+        assert_eq!(json["nodes"][1]["vendor"], "Synology");
+        assert_eq!(json["nodes"][2]["country"], "DE");
+        assert_eq!(json["edges"][1]["traffic"]["connections"], 3);
+        assert!(json["edges"][1]["traffic"].get("send_bps").is_none());
+
+        let dir = std::env::temp_dir().join(format!("psnet_topology_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for (file, start) in [("t.gv", "digraph"), ("t.mmd", "graph LR"), ("t.md", "```mermaid"), ("t.json", "{")] {
+            let path = dir.join(file);
+            let summary = export(&g, &path).unwrap();
+            assert!(summary.starts_with("3 nodes and 2 edges"));
+            assert!(std::fs::read_to_string(&path).unwrap().starts_with(start), "{}", file);
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
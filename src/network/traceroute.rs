@@ -0,0 +1,844 @@
+//! Traceroute engine — hop-by-hop paths to remote hosts.
+//!
+//! Three probe modes:
+//!   ICMP    — IcmpSendEcho with a raising TTL, like `tracert` (no elevation)
+//!   UDP     — datagrams to ports 33434+, like classic Unix traceroute
+//!   TCP-SYN — connection attempts to the host's port, passes most firewalls
+//!
+//! UDP and TCP read the ICMP Time Exceeded replies from a raw socket, which
+//! needs Administrator. Each hop is probed three times and then enriched
+//! with reverse DNS, the origin ASN (Team Cymru's DNS interface) and the
+//! GeoIP country. Traces live for the session; `path_tree` merges them into
+//! a tree of shared hops for the Topology tab.
+
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::dnssd::{self, RData};
+use super::geoip::{CountryInfo, GeoIpResolver};
+
+const MAX_HOPS: u8 = 30;
+const PROBES_PER_HOP: usize = 3;
+const PROBE_TIMEOUT: Duration = Duration::from_millis(1000);
+/// First destination port of UDP probes; each probe uses the next one.
+const UDP_BASE_PORT: u16 = 33434;
+/// Traces kept for the session; the oldest is dropped first.
+const MAX_TRACES: usize = 16;
+
+// ─── Win32 FFI ──────────────────────────────────────────────────────────────
+
+#[repr(C)]
+#[allow(non_snake_case)]
+struct IP_OPTION_INFORMATION {
+    Ttl: u8,
+    Tos: u8,
+    Flags: u8,
+    OptionsSize: u8,
+    OptionsData: *const u8,
+}
+
+#[link(name = "iphlpapi")]
+extern "system" {
+    fn IcmpCreateFile() -> isize;
+    fn IcmpCloseHandle(IcmpHandle: isize) -> i32;
+    fn IcmpSendEcho(
+        IcmpHandle: isize,
+        DestinationAddress: u32,
+        RequestData: *const u8,
+        RequestSize: u16,
+        RequestOptions: *const u8, // IP_OPTION_INFORMATION
+        ReplyBuffer: *mut u8,
+        ReplySize: u32,
+        Timeout: u32,
+    ) -> u32;
+}
+
+#[repr(C)]
+#[allow(non_snake_case)]
+struct SOCKADDR_IN {
+    sin_family: i16,
+    sin_port: u16,
+    sin_addr: u32, // network byte order
+    sin_zero: [u8; 8],
+}
+
+#[repr(C)]
+#[allow(non_camel_case_types)]
+struct fd_set {
+    fd_count: u32,
+    fd_array: [usize; 64],
+}
+
+#[repr(C)]
+#[allow(non_camel_case_types)]
+struct timeval {
+    tv_sec: i32,
+    tv_usec: i32,
+}
+
+#[link(name = "ws2_32")]
+extern "system" {
+    fn socket(af: i32, r#type: i32, protocol: i32) -> usize;
+    fn setsockopt(s: usize, level: i32, optname: i32, optval: *const u8, optlen: i32) -> i32;
+    fn bind(s: usize, addr: *const SOCKADDR_IN, namelen: i32) -> i32;
+    fn connect(s: usize, addr: *const SOCKADDR_IN, namelen: i32) -> i32;
+    fn getsockname(s: usize, addr: *mut SOCKADDR_IN, namelen: *mut i32) -> i32;
+    fn ioctlsocket(s: usize, cmd: i32, argp: *mut u32) -> i32;
+    fn select(nfds: i32, readfds: *mut fd_set, writefds: *mut fd_set, exceptfds: *mut fd_set, timeout: *const timeval) -> i32;
+    fn recv(s: usize, buf: *mut u8, len: i32, flags: i32) -> i32;
+    fn closesocket(s: usize) -> i32;
+}
+
+const AF_INET: i32 = 2;
+const SOCK_STREAM: i32 = 1;
+const SOCK_RAW: i32 = 3;
+const IPPROTO_IP: i32 = 0;
+const IPPROTO_ICMP: i32 = 1;
+const IPPROTO_TCP: i32 = 6;
+const IPPROTO_UDP: u8 = 17;
+const IP_TTL: i32 = 4;
+const SOL_SOCKET: i32 = 0xffff;
+const SO_RCVTIMEO: i32 = 0x1006;
+const FIONBIO: i32 = 0x8004_667E_u32 as i32;
+const INVALID_SOCKET: usize = !0;
+const SOCKET_ERROR: i32 = -1;
+
+const IP_SUCCESS: u32 = 0;
+const IP_DEST_NET_UNREACHABLE: u32 = 11002;
+const IP_DEST_PORT_UNREACHABLE: u32 = 11005;
+const IP_TTL_EXPIRED_TRANSIT: u32 = 11013;
+const IP_TTL_EXPIRED_REASSEM: u32 = 11014;
+
+// ─── Types ───────────────────────────────────────────────────────────────────
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TraceMode {
+    Icmp,
+    Udp,
+    TcpSyn,
+}
+
+impl TraceMode {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Icmp => "ICMP",
+            Self::Udp => "UDP",
+            Self::TcpSyn => "TCP-SYN",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Self::Icmp => Self::Udp,
+            Self::Udp => Self::TcpSyn,
+            Self::TcpSyn => Self::Icmp,
+        }
+    }
+}
+
+/// Origin AS of a hop, from Team Cymru.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AsnInfo {
+    pub asn: u32,
+    /// Announced prefix, e.g. "8.8.8.0/24".
+    pub prefix: String,
+    /// AS name, e.g. "GOOGLE - Google LLC, US".
+    pub name: Option<String>,
+}
+
+impl AsnInfo {
+    /// "AS15169 GOOGLE".
+    pub fn label(&self) -> String {
+        match self.name.as_deref().and_then(|n| n.split([' ', ',']).next()) {
+            Some(short) if !short.is_empty() => format!("AS{} {}", self.asn, short),
+            _ => format!("AS{}", self.asn),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Hop {
+    pub ttl: u8,
+    /// First address that answered at this TTL.
+    pub addr: Option<Ipv4Addr>,
+    /// Round trip of each probe in ms; `None` for no reply.
+    pub rtts: Vec<Option<u32>>,
+    pub hostname: Option<String>,
+    pub asn: Option<AsnInfo>,
+    pub country: Option<CountryInfo>,
+}
+
+impl Hop {
+    fn new(ttl: u8) -> Self {
+        Self { ttl, addr: None, rtts: Vec::new(), hostname: None, asn: None, country: None }
+    }
+
+    /// Share of probes without a reply, 0–100.
+    pub fn loss_pct(&self) -> u8 {
+        if self.rtts.is_empty() {
+            return 0;
+        }
+        let lost = self.rtts.iter().filter(|r| r.is_none()).count();
+        (lost * 100 / self.rtts.len()) as u8
+    }
+
+    fn answered(&self) -> impl Iterator<Item = u32> + '_ {
+        self.rtts.iter().flatten().copied()
+    }
+
+    pub fn last_rtt(&self) -> Option<u32> {
+        self.rtts.last().copied().flatten()
+    }
+
+    pub fn avg_rtt(&self) -> Option<u32> {
+        let n = self.answered().count() as u32;
+        (n > 0).then(|| self.answered().sum::<u32>() / n)
+    }
+
+    pub fn best_rtt(&self) -> Option<u32> {
+        self.answered().min()
+    }
+
+    pub fn worst_rtt(&self) -> Option<u32> {
+        self.answered().max()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TraceState {
+    Running,
+    /// The target answered.
+    Reached,
+    /// Gave up after `MAX_HOPS` hops.
+    Unreached,
+    Failed(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trace {
+    pub id: u64,
+    pub target: Ipv4Addr,
+    /// Hostname or address shown for the target.
+    pub label: String,
+    pub mode: TraceMode,
+    /// Destination port of TCP-SYN probes.
+    pub port: u16,
+    pub hops: Vec<Hop>,
+    pub state: TraceState,
+}
+
+// ─── Tracer ──────────────────────────────────────────────────────────────────
+
+/// Session's traces; each runs on its own thread.
+pub struct Tracer {
+    traces: Arc<Mutex<Vec<Trace>>>,
+    asn_cache: Arc<AsnCache>,
+    next_id: AtomicU64,
+}
+
+impl Default for Tracer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tracer {
+    pub fn new() -> Self {
+        Self {
+            traces: Arc::new(Mutex::new(Vec::new())),
+            asn_cache: Arc::new(AsnCache::default()),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Copy of every trace, oldest first.
+    pub fn snapshot(&self) -> Vec<Trace> {
+        self.traces.lock().map(|t| t.clone()).unwrap_or_default()
+    }
+
+    /// Trace `target` on a background thread. A trace of the same target and
+    /// mode is replaced. Returns the trace's index in `snapshot()`.
+    pub fn start(&self, target: IpAddr, label: String, mode: TraceMode, port: Option<u16>) -> Result<usize, String> {
+        let IpAddr::V4(target) = target else {
+            return Err("IPv6 paths cannot be traced yet".to_string());
+        };
+        if target.is_loopback() || target.is_unspecified() {
+            return Err(format!("{} is this machine", target));
+        }
+        let trace = Trace {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            target,
+            label,
+            mode,
+            port: port.unwrap_or(443),
+            hops: Vec::new(),
+            state: TraceState::Running,
+        };
+        let index = {
+            let mut traces = self.traces.lock().map_err(|_| "trace list unavailable".to_string())?;
+            if let Some(i) = traces.iter().position(|t| t.target == target && t.mode == mode) {
+                if traces[i].state == TraceState::Running {
+                    return Err(format!("{} is already being traced", target));
+                }
+                traces.remove(i);
+            }
+            if traces.len() >= MAX_TRACES {
+                traces.remove(0);
+            }
+            traces.push(trace.clone());
+            traces.len() - 1
+        };
+
+        let traces = Arc::clone(&self.traces);
+        let asn_cache = Arc::clone(&self.asn_cache);
+        thread::spawn(move || run_trace(trace, &traces, &asn_cache));
+        Ok(index)
+    }
+}
+
+/// Apply `f` to the trace with `id`, if it is still kept.
+fn update(traces: &Mutex<Vec<Trace>>, id: u64, f: impl FnOnce(&mut Trace)) {
+    if let Ok(mut traces) = traces.lock() {
+        if let Some(t) = traces.iter_mut().find(|t| t.id == id) {
+            f(t);
+        }
+    }
+}
+
+fn run_trace(trace: Trace, traces: &Mutex<Vec<Trace>>, asn_cache: &AsnCache) {
+    let id = trace.id;
+    let mut prober = match Prober::open(trace.mode, trace.target) {
+        Ok(p) => p,
+        Err(e) => {
+            update(traces, id, |t| t.state = TraceState::Failed(e));
+            return;
+        }
+    };
+    let geoip = GeoIpResolver::new();
+    let mut seq: u16 = 0;
+
+    for ttl in 1..=MAX_HOPS {
+        let mut hop = Hop::new(ttl);
+        let mut reached = false;
+        for _ in 0..PROBES_PER_HOP {
+            match prober.probe(ttl, seq, trace.port) {
+                Some(reply) => {
+                    hop.addr.get_or_insert(reply.from);
+                    hop.rtts.push(Some(reply.rtt_ms));
+                    reached |= reply.from == trace.target || reply.final_hop;
+                }
+                None => hop.rtts.push(None),
+            }
+            seq = seq.wrapping_add(1);
+            let partial = hop.clone();
+            update(traces, id, |t| match t.hops.last_mut() {
+                Some(last) if last.ttl == ttl => *last = partial,
+                _ => t.hops.push(partial),
+            });
+        }
+
+        if let Some(addr) = hop.addr {
+            let hostname = dns_lookup::lookup_addr(&IpAddr::V4(addr)).ok().filter(|n| *n != addr.to_string());
+            let asn = asn_cache.lookup(addr);
+            let country = geoip.lookup(IpAddr::V4(addr));
+            update(traces, id, |t| {
+                if let Some(h) = t.hops.iter_mut().find(|h| h.ttl == ttl) {
+                    h.hostname = hostname;
+                    h.asn = asn;
+                    h.country = country;
+                }
+            });
+        }
+        if reached {
+            update(traces, id, |t| t.state = TraceState::Reached);
+            return;
+        }
+    }
+    update(traces, id, |t| t.state = TraceState::Unreached);
+}
+
+// ─── Probes ──────────────────────────────────────────────────────────────────
+
+struct ProbeReply {
+    from: Ipv4Addr,
+    rtt_ms: u32,
+    /// The answer came from the end of the path (echo reply, port
+    /// unreachable, SYN-ACK or RST) rather than a router on the way.
+    final_hop: bool,
+}
+
+enum Prober {
+    Icmp { handle: isize, target: Ipv4Addr },
+    Udp { listener: IcmpListener, target: Ipv4Addr },
+    Tcp { listener: IcmpListener, target: Ipv4Addr, local: Ipv4Addr },
+}
+
+impl Prober {
+    fn open(mode: TraceMode, target: Ipv4Addr) -> Result<Self, String> {
+        if mode == TraceMode::Icmp {
+            let handle = unsafe { IcmpCreateFile() };
+            if handle == -1 || handle == 0 {
+                return Err("IcmpCreateFile failed".to_string());
+            }
+            return Ok(Self::Icmp { handle, target });
+        }
+        // The source address the OS would route the probes from
+        let local = UdpSocket::bind("0.0.0.0:0")
+            .and_then(|s| s.connect((target, UDP_BASE_PORT)).map(|_| s))
+            .and_then(|s| s.local_addr())
+            .map_err(|e| format!("no route to {}: {}", target, e))?;
+        let SocketAddr::V4(local) = local else {
+            return Err("no IPv4 route".to_string());
+        };
+        let listener = IcmpListener::open(*local.ip())?;
+        Ok(match mode {
+            TraceMode::Udp => Self::Udp { listener, target },
+            _ => Self::Tcp { listener, target, local: *local.ip() },
+        })
+    }
+
+    fn probe(&mut self, ttl: u8, seq: u16, port: u16) -> Option<ProbeReply> {
+        match self {
+            Self::Icmp { handle, target } => icmp_probe(*handle, *target, ttl),
+            Self::Udp { listener, target } => udp_probe(listener, *target, ttl, UDP_BASE_PORT.wrapping_add(seq)),
+            Self::Tcp { listener, target, local } => tcp_probe(listener, *target, *local, ttl, port),
+        }
+    }
+}
+
+impl Drop for Prober {
+    fn drop(&mut self) {
+        if let Self::Icmp { handle, .. } = self {
+            unsafe { IcmpCloseHandle(*handle); }
+        }
+    }
+}
+
+fn icmp_probe(handle: isize, target: Ipv4Addr, ttl: u8) -> Option<ProbeReply> {
+    let data = b"psnet-traceroute";
+    let options = IP_OPTION_INFORMATION { Ttl: ttl, Tos: 0, Flags: 0, OptionsSize: 0, OptionsData: std::ptr::null() };
+    // ICMP_ECHO_REPLY + echoed data + room for the quoted header of an ICMP error
+    let mut reply = [0u8; 256];
+    let n = unsafe {
+        IcmpSendEcho(
+            handle,
+            u32::from(target).to_be(),
+            data.as_ptr(),
+            data.len() as u16,
+            &options as *const IP_OPTION_INFORMATION as *const u8,
+            reply.as_mut_ptr(),
+            reply.len() as u32,
+            PROBE_TIMEOUT.as_millis() as u32,
+        )
+    };
+    if n == 0 {
+        return None;
+    }
+    // ICMP_ECHO_REPLY: Address, Status, RoundTripTime (u32 each)
+    let from = Ipv4Addr::new(reply[0], reply[1], reply[2], reply[3]);
+    let status = u32::from_ne_bytes([reply[4], reply[5], reply[6], reply[7]]);
+    let rtt_ms = u32::from_ne_bytes([reply[8], reply[9], reply[10], reply[11]]);
+    match status {
+        IP_SUCCESS => Some(ProbeReply { from, rtt_ms, final_hop: true }),
+        IP_TTL_EXPIRED_TRANSIT | IP_TTL_EXPIRED_REASSEM => Some(ProbeReply { from, rtt_ms, final_hop: false }),
+        IP_DEST_NET_UNREACHABLE..=IP_DEST_PORT_UNREACHABLE => Some(ProbeReply { from, rtt_ms, final_hop: true }),
+        _ => None,
+    }
+}
+
+fn udp_probe(listener: &IcmpListener, target: Ipv4Addr, ttl: u8, port: u16) -> Option<ProbeReply> {
+    let sock = UdpSocket::bind("0.0.0.0:0").ok()?;
+    sock.set_ttl(ttl as u32).ok()?;
+    let sent = Instant::now();
+    sock.send_to(b"psnet-traceroute", (target, port)).ok()?;
+    let deadline = sent + PROBE_TIMEOUT;
+    while Instant::now() < deadline {
+        let Some(msg) = listener.recv() else { continue };
+        if msg.protocol == IPPROTO_UDP && msg.orig_dst == target && msg.dst_port == port {
+            return Some(ProbeReply { from: msg.from, rtt_ms: sent.elapsed().as_millis() as u32, final_hop: msg.kind == 3 });
+        }
+    }
+    None
+}
+
+fn tcp_probe(listener: &IcmpListener, target: Ipv4Addr, local: Ipv4Addr, ttl: u8, port: u16) -> Option<ProbeReply> {
+    unsafe {
+        let sock = socket(AF_INET, SOCK_STREAM, IPPROTO_TCP);
+        if sock == INVALID_SOCKET {
+            return None;
+        }
+        let ttl_val = ttl as i32;
+        let mut nonblocking: u32 = 1;
+        let bind_addr = SOCKADDR_IN { sin_family: AF_INET as i16, sin_port: 0, sin_addr: u32::from(local).to_be(), sin_zero: [0; 8] };
+        let addr = SOCKADDR_IN { sin_family: AF_INET as i16, sin_port: port.to_be(), sin_addr: u32::from(target).to_be(), sin_zero: [0; 8] };
+        let size = std::mem::size_of::<SOCKADDR_IN>() as i32;
+        if setsockopt(sock, IPPROTO_IP, IP_TTL, &ttl_val as *const i32 as *const u8, 4) == SOCKET_ERROR
+            || ioctlsocket(sock, FIONBIO, &mut nonblocking) == SOCKET_ERROR
+            || bind(sock, &bind_addr, size) == SOCKET_ERROR
+        {
+            closesocket(sock);
+            return None;
+        }
+        let mut bound = SOCKADDR_IN { sin_family: 0, sin_port: 0, sin_addr: 0, sin_zero: [0; 8] };
+        let mut bound_len = size;
+        getsockname(sock, &mut bound, &mut bound_len);
+        let src_port = u16::from_be(bound.sin_port);
+
+        let sent = Instant::now();
+        // Non-blocking connect: WSAEWOULDBLOCK is the expected "error"
+        connect(sock, &addr, size);
+        let deadline = sent + PROBE_TIMEOUT;
+        let mut result = None;
+        while Instant::now() < deadline {
+            // SYN-ACK makes the socket writable, RST puts it in the except set
+            let mut writable = fd_set { fd_count: 1, fd_array: [0; 64] };
+            let mut failed = fd_set { fd_count: 1, fd_array: [0; 64] };
+            writable.fd_array[0] = sock;
+            failed.fd_array[0] = sock;
+            let poll = timeval { tv_sec: 0, tv_usec: 0 };
+            if select(0, std::ptr::null_mut(), &mut writable, &mut failed, &poll) > 0 {
+                result = Some(ProbeReply { from: target, rtt_ms: sent.elapsed().as_millis() as u32, final_hop: true });
+                break;
+            }
+            if let Some(msg) = listener.recv() {
+                if msg.protocol == IPPROTO_TCP as u8 && msg.orig_dst == target && msg.src_port == src_port {
+                    result = Some(ProbeReply { from: msg.from, rtt_ms: sent.elapsed().as_millis() as u32, final_hop: msg.kind == 3 });
+                    break;
+                }
+            }
+        }
+        closesocket(sock);
+        result
+    }
+}
+
+/// Raw socket receiving every ICMP message addressed to this machine.
+struct IcmpListener {
+    sock: usize,
+}
+
+impl IcmpListener {
+    fn open(local: Ipv4Addr) -> Result<Self, String> {
+        unsafe {
+            let sock = socket(AF_INET, SOCK_RAW, IPPROTO_ICMP);
+            if sock == INVALID_SOCKET {
+                return Err("raw ICMP socket refused (UDP and TCP modes need Administrator; ICMP mode does not)".to_string());
+            }
+            let addr = SOCKADDR_IN { sin_family: AF_INET as i16, sin_port: 0, sin_addr: u32::from(local).to_be(), sin_zero: [0; 8] };
+            if bind(sock, &addr, std::mem::size_of::<SOCKADDR_IN>() as i32) == SOCKET_ERROR {
+                closesocket(sock);
+                return Err(format!("cannot bind raw ICMP socket to {}", local));
+            }
+            // Short timeout so probes can poll their own sockets in between
+            let timeout_ms: u32 = 20;
+            setsockopt(sock, SOL_SOCKET, SO_RCVTIMEO, &timeout_ms as *const u32 as *const u8, 4);
+            Ok(Self { sock })
+        }
+    }
+
+    /// Next Time Exceeded or Destination Unreachable message, if one arrives
+    /// within the socket timeout.
+    fn recv(&self) -> Option<IcmpError> {
+        let mut buf = [0u8; 1500];
+        let n = unsafe { recv(self.sock, buf.as_mut_ptr(), buf.len() as i32, 0) };
+        if n <= 0 {
+            return None;
+        }
+        parse_icmp_error(&buf[..n as usize])
+    }
+}
+
+impl Drop for IcmpListener {
+    fn drop(&mut self) {
+        unsafe { closesocket(self.sock); }
+    }
+}
+
+/// An ICMP error quoting one of our probes.
+#[derive(Debug, PartialEq)]
+struct IcmpError {
+    from: Ipv4Addr,
+    /// 11 Time Exceeded, 3 Destination Unreachable.
+    kind: u8,
+    /// Protocol, destination and ports of the quoted probe.
+    protocol: u8,
+    orig_dst: Ipv4Addr,
+    src_port: u16,
+    dst_port: u16,
+}
+
+/// Parse an IPv4 packet carrying an ICMP Time Exceeded / Destination
+/// Unreachable message and the IP header plus 8 bytes it quotes.
+fn parse_icmp_error(pkt: &[u8]) -> Option<IcmpError> {
+    if pkt.len() < 20 || pkt[0] >> 4 != 4 || pkt[9] != IPPROTO_ICMP as u8 {
+        return None;
+    }
+    let from = Ipv4Addr::new(pkt[12], pkt[13], pkt[14], pkt[15]);
+    let icmp = &pkt[((pkt[0] & 0x0f) as usize * 4).min(pkt.len())..];
+    if icmp.len() < 8 || !matches!(icmp[0], 3 | 11) {
+        return None;
+    }
+    let quoted = &icmp[8..];
+    if quoted.len() < 20 || quoted[0] >> 4 != 4 {
+        return None;
+    }
+    let qlen = (quoted[0] & 0x0f) as usize * 4;
+    let ports = quoted.get(qlen..qlen + 4)?;
+    Some(IcmpError {
+        from,
+        kind: icmp[0],
+        protocol: quoted[9],
+        orig_dst: Ipv4Addr::new(quoted[16], quoted[17], quoted[18], quoted[19]),
+        src_port: u16::from_be_bytes([ports[0], ports[1]]),
+        dst_port: u16::from_be_bytes([ports[2], ports[3]]),
+    })
+}
+
+// ─── ASN lookup (Team Cymru DNS) ─────────────────────────────────────────────
+
+#[derive(Default)]
+struct AsnCache {
+    origins: Mutex<HashMap<Ipv4Addr, Option<AsnInfo>>>,
+    names: Mutex<HashMap<u32, Option<String>>>,
+}
+
+impl AsnCache {
+    fn lookup(&self, ip: Ipv4Addr) -> Option<AsnInfo> {
+        if !is_public(ip) {
+            return None;
+        }
+        if let Some(cached) = self.origins.lock().ok()?.get(&ip) {
+            return cached.clone();
+        }
+        let o = ip.octets();
+        let origin = txt_query(&format!("{}.{}.{}.{}.origin.asn.cymru.com", o[3], o[2], o[1], o[0]))
+            .and_then(|txt| parse_origin(&txt))
+            .map(|(asn, prefix)| {
+                let name = self.as_name(asn);
+                AsnInfo { asn, prefix, name }
+            });
+        self.origins.lock().ok()?.insert(ip, origin.clone());
+        origin
+    }
+
+    fn as_name(&self, asn: u32) -> Option<String> {
+        if let Some(cached) = self.names.lock().ok()?.get(&asn) {
+            return cached.clone();
+        }
+        let name = txt_query(&format!("AS{}.asn.cymru.com", asn)).and_then(|txt| parse_as_name(&txt));
+        self.names.lock().ok()?.insert(asn, name.clone());
+        name
+    }
+}
+
+/// Addresses worth an ASN lookup: not private, CGNAT, link-local or reserved.
+fn is_public(ip: Ipv4Addr) -> bool {
+    let o = ip.octets();
+    !(ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        || (o[0] == 100 && (64..128).contains(&o[1]))
+        || o[0] >= 240)
+}
+
+/// First TXT string of `name`, asked of the system's DNS server.
+fn txt_query(name: &str) -> Option<String> {
+    static RESOLVER: OnceLock<Option<Ipv4Addr>> = OnceLock::new();
+    let server = (*RESOLVER.get_or_init(|| {
+        super::dns::get_system_dns_servers().into_iter().find_map(|ip| match ip {
+            IpAddr::V4(v4) => Some(v4),
+            IpAddr::V6(_) => None,
+        })
+    }))?;
+    let sock = UdpSocket::bind("0.0.0.0:0").ok()?;
+    sock.set_read_timeout(Some(Duration::from_millis(1500))).ok()?;
+    let tid = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0x5a5a, |d| d.subsec_nanos() as u16);
+    sock.send_to(&super::hostnames::build_dns_query_with_id(name, 16, tid), (server, 53)).ok()?;
+    let mut buf = [0u8; 1500];
+    let n = sock.recv(&mut buf).ok()?;
+    if n < 2 || u16::from_be_bytes([buf[0], buf[1]]) != tid {
+        return None;
+    }
+    dnssd::parse_records(&buf[..n]).into_iter().find_map(|r| match r.data {
+        RData::Txt(strings) => Some(strings.concat()),
+        _ => None,
+    })
+}
+
+/// "15169 | 8.8.8.0/24 | US | arin | 2014-03-14" → (15169, "8.8.8.0/24").
+/// Multi-origin prefixes list several ASNs; the first is kept.
+fn parse_origin(txt: &str) -> Option<(u32, String)> {
+    let mut fields = txt.split('|').map(str::trim);
+    let asn = fields.next()?.split_whitespace().next()?.parse().ok()?;
+    let prefix = fields.next()?.to_string();
+    Some((asn, prefix))
+}
+
+/// "15169 | US | arin | 2000-03-30 | GOOGLE - Google LLC, US" → the name.
+fn parse_as_name(txt: &str) -> Option<String> {
+    txt.split('|').nth(4).map(str::trim).filter(|n| !n.is_empty()).map(str::to_string)
+}
+
+// ─── Shared-hop tree ─────────────────────────────────────────────────────────
+
+/// One node of the merged path tree, in drawing order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PathLine {
+    /// Tree glyphs before the node, e.g. "│  ├─ ".
+    pub prefix: String,
+    /// The hop as seen by the first trace through it.
+    pub hop: Hop,
+    /// Traces passing through this hop.
+    pub traces: usize,
+    /// Labels of the traces that end here.
+    pub ends: Vec<String>,
+}
+
+struct TreeNode {
+    hop: Option<Hop>,
+    traces: usize,
+    ends: Vec<String>,
+    children: Vec<usize>,
+}
+
+/// Merge traces into a tree rooted at this machine: hops that several traces
+/// share at the same depth become one node. Silent hops merge with silent hops.
+pub fn path_tree(traces: &[Trace]) -> Vec<PathLine> {
+    let mut nodes = vec![TreeNode { hop: None, traces: traces.len(), ends: Vec::new(), children: Vec::new() }];
+    for trace in traces {
+        let mut cur = 0;
+        for hop in &trace.hops {
+            let existing = nodes[cur].children.iter().copied()
+                .find(|&c| nodes[c].hop.as_ref().is_some_and(|h| h.addr == hop.addr));
+            cur = match existing {
+                Some(c) => c,
+                None => {
+                    nodes.push(TreeNode { hop: Some(hop.clone()), traces: 0, ends: Vec::new(), children: Vec::new() });
+                    let c = nodes.len() - 1;
+                    nodes[cur].children.push(c);
+                    c
+                }
+            };
+            nodes[cur].traces += 1;
+        }
+        if cur != 0 {
+            nodes[cur].ends.push(trace.label.clone());
+        }
+    }
+
+    let mut lines = Vec::new();
+    walk(&nodes, 0, "", &mut lines);
+    lines
+}
+
+fn walk(nodes: &[TreeNode], idx: usize, indent: &str, lines: &mut Vec<PathLine>) {
+    let children = &nodes[idx].children;
+    for (i, &c) in children.iter().enumerate() {
+        let last = i + 1 == children.len();
+        let node = &nodes[c];
+        if let Some(hop) = &node.hop {
+            lines.push(PathLine {
+                prefix: format!("{}{}", indent, if last { "└─ " } else { "├─ " }),
+                hop: hop.clone(),
+                traces: node.traces,
+                ends: node.ends.clone(),
+            });
+        }
+        walk(nodes, c, &format!("{}{}", indent, if last { "   " } else { "│  " }), lines);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hop(ttl: u8, addr: Option<[u8; 4]>, rtts: &[Option<u32>]) -> Hop {
+        Hop { addr: addr.map(Ipv4Addr::from), rtts: rtts.to_vec(), ..Hop::new(ttl) }
+    }
+
+    fn trace(label: &str, hops: Vec<Hop>) -> Trace {
+        Trace {
+            id: 0,
+            target: Ipv4Addr::new(8, 8, 8, 8),
+            label: label.to_string(),
+            mode: TraceMode::Icmp,
+            port: 443,
+            hops,
+            state: TraceState::Reached,
+        }
+    }
+
+    #[test]
+    fn test_hop_stats() {
+        let h = hop(1, Some([192, 168, 1, 1]), &[Some(3), None, Some(5), Some(1)]);
+        assert_eq!(h.loss_pct(), 25);
+        assert_eq!(h.avg_rtt(), Some(3));
+        assert_eq!((h.best_rtt(), h.worst_rtt(), h.last_rtt()), (Some(1), Some(5), Some(1)));
+        let silent = hop(2, None, &[None, None, None]);
+        assert_eq!(silent.loss_pct(), 100);
+        assert_eq!(silent.avg_rtt(), None);
+    }
+
+    #[test]
+    fn test_parse_icmp_time_exceeded() {
+        // Outer IP (router 10.0.0.1 → us), ICMP type 11, quoted IP + UDP header
+        let mut pkt = vec![0x45, 0, 0, 56, 0, 0, 0, 0, 64, 1, 0, 0, 10, 0, 0, 1, 192, 168, 1, 20];
+        pkt.extend_from_slice(&[11, 0, 0, 0, 0, 0, 0, 0]);
+        pkt.extend_from_slice(&[0x45, 0, 0, 44, 0, 0, 0, 0, 1, 17, 0, 0, 192, 168, 1, 20, 8, 8, 8, 8]);
+        pkt.extend_from_slice(&[0xc0, 0x01, 0x84, 0x9a, 0, 24, 0, 0]);
+        let e = parse_icmp_error(&pkt).unwrap();
+        assert_eq!(e.from, Ipv4Addr::new(10, 0, 0, 1));
+        assert_eq!((e.kind, e.protocol), (11, IPPROTO_UDP));
+        assert_eq!(e.orig_dst, Ipv4Addr::new(8, 8, 8, 8));
+        assert_eq!((e.src_port, e.dst_port), (49153, 33946));
+
+        // Echo replies and truncated quotes are not probe errors
+        let mut echo = pkt.clone();
+        echo[20] = 0;
+        assert!(parse_icmp_error(&echo).is_none());
+        assert!(parse_icmp_error(&pkt[..40]).is_none());
+    }
+
+    #[test]
+    fn test_parse_cymru_txt() {
+        assert_eq!(parse_origin("15169 | 8.8.8.0/24 | US | arin | 2014-03-14"), Some((15169, "8.8.8.0/24".to_string())));
+        assert_eq!(parse_origin("3356 1299 | 4.0.0.0/9 | US | arin |"), Some((3356, "4.0.0.0/9".to_string())));
+        assert_eq!(parse_origin("garbage"), None);
+        let name = parse_as_name("15169 | US | arin | 2000-03-30 | GOOGLE - Google LLC, US");
+        assert_eq!(name.as_deref(), Some("GOOGLE - Google LLC, US"));
+        let info = AsnInfo { asn: 15169, prefix: "8.8.8.0/24".into(), name };
+        assert_eq!(info.label(), "AS15169 GOOGLE");
+        assert!(is_public(Ipv4Addr::new(8, 8, 8, 8)));
+        assert!(!is_public(Ipv4Addr::new(100, 72, 1, 1)));
+        assert!(!is_public(Ipv4Addr::new(192, 168, 1, 1)));
+    }
+
+    #[test]
+    fn test_path_tree_merges_shared_hops() {
+        let gw = Some([192, 168, 1, 1]);
+        let isp = Some([10, 0, 0, 1]);
+        let a = trace("dns.google", vec![hop(1, gw, &[Some(1)]), hop(2, isp, &[Some(8)]), hop(3, Some([8, 8, 8, 8]), &[Some(12)])]);
+        let b = trace("one.one.one.one", vec![hop(1, gw, &[Some(1)]), hop(2, isp, &[Some(9)]), hop(3, None, &[None]), hop(4, Some([1, 1, 1, 1]), &[Some(14)])]);
+        let lines = path_tree(&[a, b]);
+        let view: Vec<(String, usize, Vec<String>)> = lines.iter()
+            .map(|l| (format!("{}{}", l.prefix, l.hop.addr.map_or("*".to_string(), |a| a.to_string())), l.traces, l.ends.clone()))
+            .collect();
+        assert_eq!(view, vec![
+            ("└─ 192.168.1.1".to_string(), 2, vec![]),
+            ("   └─ 10.0.0.1".to_string(), 2, vec![]),
+            ("      ├─ 8.8.8.8".to_string(), 1, vec!["dns.google".to_string()]),
+            ("      └─ *".to_string(), 1, vec![]),
+            ("         └─ 1.1.1.1".to_string(), 1, vec!["one.one.one.one".to_string()]),
+        ]);
+    }
+}
@@ -0,0 +1,532 @@
+//! UPnP Internet Gateway Device client.
+//!
+//! Finds the router's WANIPConnection / WANPPPConnection service over SSDP
+//! and enumerates its port mappings with `GetGenericPortMappingEntry`, so
+//! listeners forwarded from the Internet can be identified. Plain HTTP over
+//! `TcpStream` — no UPnP library. Refreshes on a background thread; mappings
+//! that appear after the first refresh are queued in `new_mappings` for the
+//! alert engine.
+
+use std::collections::HashSet;
+use std::io::{Read, Write};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, SocketAddrV4, TcpStream, ToSocketAddrs, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const SSDP_ADDR: &str = "239.255.255.250:1900";
+const SSDP_WAIT: Duration = Duration::from_millis(1500);
+const HTTP_TIMEOUT: Duration = Duration::from_secs(2);
+/// Max response size for description XML and SOAP replies.
+const MAX_HTTP_READ: usize = 64 * 1024;
+/// Safety cap on `GetGenericPortMappingEntry` indices.
+const MAX_MAPPINGS: u16 = 256;
+
+const WAN_SERVICES: &[&str] = &["WANIPConnection", "WANPPPConnection"];
+
+/// One entry of the gateway's port mapping table.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PortMapping {
+    /// Remote host restriction; empty = any.
+    pub remote_host: String,
+    pub external_port: u16,
+    /// "TCP" or "UDP".
+    pub protocol: String,
+    pub internal_port: u16,
+    pub internal_client: Ipv4Addr,
+    pub enabled: bool,
+    pub description: String,
+    /// 0 = permanent.
+    pub lease_secs: u32,
+}
+
+/// A WAN connection service found on the gateway.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Gateway {
+    /// Host:port of the control URL.
+    pub addr: SocketAddr,
+    pub control_path: String,
+    pub service_type: String,
+    /// Our LAN address as seen by the gateway (local end of the HTTP connection).
+    pub local_ip: Option<Ipv4Addr>,
+}
+
+struct Snapshot {
+    gateway: Option<Gateway>,
+    mappings: Vec<PortMapping>,
+}
+
+/// What makes a mapping "the same" across refreshes: lease countdowns and
+/// description edits don't count as a new mapping.
+type MappingKey = (String, u16, Ipv4Addr, u16);
+
+fn mapping_key(m: &PortMapping) -> MappingKey {
+    (m.protocol.clone(), m.external_port, m.internal_client, m.internal_port)
+}
+
+// ─── UpnpMonitor ─────────────────────────────────────────────────────────────
+
+pub struct UpnpMonitor {
+    /// Gateway found on the last refresh (None: no IGD answered).
+    pub gateway: Option<Gateway>,
+    /// Port mappings from the last refresh.
+    pub mappings: Vec<PortMapping>,
+    pub last_refresh: Option<Instant>,
+    /// Mappings that appeared since the previous refresh (drained by the app).
+    pub new_mappings: Vec<PortMapping>,
+    /// Mappings seen on the previous refresh; None until the gateway first
+    /// answered, so the table found at startup doesn't raise alerts.
+    known: Option<HashSet<MappingKey>>,
+    scanning: Arc<AtomicBool>,
+    pending: Arc<Mutex<Option<Snapshot>>>,
+    tick_count: u32,
+}
+
+impl UpnpMonitor {
+    pub fn new() -> Self {
+        Self {
+            gateway: None,
+            mappings: Vec::new(),
+            last_refresh: None,
+            new_mappings: Vec::new(),
+            known: None,
+            scanning: Arc::new(AtomicBool::new(false)),
+            pending: Arc::new(Mutex::new(None)),
+            tick_count: 0,
+        }
+    }
+
+    /// Refresh the mapping table every 60 ticks (~1 minute).
+    pub fn tick(&mut self) {
+        self.tick_count += 1;
+        if self.tick_count % 60 == 1 {
+            self.refresh();
+        }
+        let snap = self.pending.lock().ok().and_then(|mut p| p.take());
+        if let Some(snap) = snap {
+            if snap.gateway.is_some() {
+                let added = self.detect_new(&snap.mappings);
+                self.new_mappings.extend(added);
+            }
+            self.gateway = snap.gateway;
+            self.mappings = snap.mappings;
+            self.last_refresh = Some(Instant::now());
+        }
+    }
+
+    /// Query the gateway on a background thread.
+    pub fn refresh(&self) {
+        if self.scanning.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let scanning = Arc::clone(&self.scanning);
+        let pending = Arc::clone(&self.pending);
+        thread::spawn(move || {
+            let gateway = discover_gateway();
+            let mappings = gateway.as_ref().map(list_mappings).unwrap_or_default();
+            if let Ok(mut p) = pending.lock() {
+                *p = Some(Snapshot { gateway, mappings });
+            }
+            scanning.store(false, Ordering::SeqCst);
+        });
+    }
+
+    /// Mappings not present on the previous refresh. The first table seen
+    /// only seeds the known set.
+    fn detect_new(&mut self, mappings: &[PortMapping]) -> Vec<PortMapping> {
+        let current: HashSet<MappingKey> = mappings.iter().map(mapping_key).collect();
+        let added = match &self.known {
+            Some(known) => mappings.iter().filter(|m| !known.contains(&mapping_key(m))).cloned().collect(),
+            None => Vec::new(),
+        };
+        self.known = Some(current);
+        added
+    }
+
+    /// The mapping that forwards Internet traffic to a local listener, if any.
+    pub fn mapping_for(&self, bind: IpAddr, port: u16, protocol: &str) -> Option<&PortMapping> {
+        let local = self.gateway.as_ref()?.local_ip?;
+        let bind_ok = match bind {
+            IpAddr::V4(v4) => v4.is_unspecified() || v4 == local,
+            IpAddr::V6(v6) => v6.is_unspecified(),
+        };
+        if !bind_ok {
+            return None;
+        }
+        self.mappings.iter().find(|m| {
+            m.enabled
+                && m.internal_client == local
+                && m.internal_port == port
+                && m.protocol.eq_ignore_ascii_case(protocol)
+        })
+    }
+}
+
+// ─── Discovery ───────────────────────────────────────────────────────────────
+
+/// M-SEARCH for an IGD and resolve its WAN connection service.
+pub fn discover_gateway() -> Option<Gateway> {
+    let sock = UdpSocket::bind(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0)).ok()?;
+    sock.set_read_timeout(Some(SSDP_WAIT)).ok()?;
+
+    let msearch = format!(
+        "M-SEARCH * HTTP/1.1\r\nHOST: {}\r\nMAN: \"ssdp:discover\"\r\nMX: 1\r\nST: urn:schemas-upnp-org:device:InternetGatewayDevice:1\r\n\r\n",
+        SSDP_ADDR
+    );
+    sock.send_to(msearch.as_bytes(), SSDP_ADDR).ok()?;
+
+    let mut buf = [0u8; 2048];
+    let mut locations = Vec::new();
+    while let Ok((len, _)) = sock.recv_from(&mut buf) {
+        let resp = String::from_utf8_lossy(&buf[..len]);
+        if let Some(loc) = header_value(&resp, "location") {
+            if !locations.contains(&loc) {
+                locations.push(loc);
+            }
+        }
+    }
+
+    locations.iter().find_map(|loc| gateway_from_location(loc))
+}
+
+/// Fetch a device description and pick its WAN connection service.
+pub fn gateway_from_location(location: &str) -> Option<Gateway> {
+    let (addr, path) = parse_http_url(location)?;
+    let (xml, local_ip) = http_request(addr, &format!(
+        "GET {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
+        path, addr
+    ))?;
+    let (service_type, control_url) = find_wan_service(&xml)?;
+
+    // controlURL may be absolute or relative to URLBase / the description URL
+    let (addr, control_path) = if control_url.starts_with("http://") {
+        parse_http_url(&control_url)?
+    } else {
+        let base = xml_text(&xml, "URLBase").and_then(|b| parse_http_url(&b)).map(|(a, _)| a).unwrap_or(addr);
+        let path = if control_url.starts_with('/') { control_url } else { format!("/{}", control_url) };
+        (base, path)
+    };
+
+    Some(Gateway { addr, control_path, service_type, local_ip })
+}
+
+/// `(serviceType, controlURL)` of the first WANIPConnection / WANPPPConnection service.
+fn find_wan_service(xml: &str) -> Option<(String, String)> {
+    xml.split("<service>").skip(1).find_map(|block| {
+        let service_type = xml_text(block, "serviceType")?;
+        if !WAN_SERVICES.iter().any(|s| service_type.contains(s)) {
+            return None;
+        }
+        Some((service_type, xml_text(block, "controlURL")?))
+    })
+}
+
+// ─── Port mapping enumeration ────────────────────────────────────────────────
+
+/// Walk `GetGenericPortMappingEntry` from index 0 until the gateway reports
+/// SpecifiedArrayIndexInvalid (or any other error).
+pub fn list_mappings(gw: &Gateway) -> Vec<PortMapping> {
+    let mut mappings = Vec::new();
+    for index in 0..MAX_MAPPINGS {
+        let index = index.to_string();
+        let Some(body) = soap_call(gw, "GetGenericPortMappingEntry", &[("NewPortMappingIndex", &index)]) else {
+            break;
+        };
+        match parse_mapping(&body) {
+            Some(m) => mappings.push(m),
+            None => break,
+        }
+    }
+    mappings
+}
+
+/// Send a SOAP action; returns the response body on HTTP 200.
+fn soap_call(gw: &Gateway, action: &str, args: &[(&str, &str)]) -> Option<String> {
+    let args_xml: String = args.iter().map(|(k, v)| format!("<{k}>{v}</{k}>", k = k, v = v)).collect();
+    let body = format!(
+        "<?xml version=\"1.0\"?>\r\n<s:Envelope xmlns:s=\"http://schemas.xmlsoap.org/soap/envelope/\" s:encodingStyle=\"http://schemas.xmlsoap.org/soap/encoding/\"><s:Body><u:{action} xmlns:u=\"{service}\">{args}</u:{action}></s:Body></s:Envelope>",
+        action = action,
+        service = gw.service_type,
+        args = args_xml,
+    );
+    let request = format!(
+        "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: text/xml; charset=\"utf-8\"\r\nSOAPAction: \"{}#{}\"\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        gw.control_path, gw.addr, gw.service_type, action, body.len(), body
+    );
+    let (response, _) = http_request_raw(gw.addr, &request)?;
+    let status_ok = response.lines().next().map(|l| l.contains(" 200")).unwrap_or(false);
+    if !status_ok {
+        return None;
+    }
+    response.split_once("\r\n\r\n").map(|(_, b)| b.to_string())
+}
+
+fn parse_mapping(xml: &str) -> Option<PortMapping> {
+    let num = |tag: &str| xml_text(xml, tag).and_then(|v| v.trim().parse::<u32>().ok());
+    Some(PortMapping {
+        remote_host: xml_text(xml, "NewRemoteHost").unwrap_or_default(),
+        external_port: u16::try_from(num("NewExternalPort")?).ok()?,
+        protocol: xml_text(xml, "NewProtocol")?.trim().to_uppercase(),
+        internal_port: u16::try_from(num("NewInternalPort")?).ok()?,
+        internal_client: xml_text(xml, "NewInternalClient")?.trim().parse().ok()?,
+        enabled: num("NewEnabled").map(|v| v != 0).unwrap_or(true),
+        description: xml_text(xml, "NewPortMappingDescription").unwrap_or_default(),
+        lease_secs: num("NewLeaseDuration").unwrap_or(0),
+    })
+}
+
+// ─── HTTP / XML helpers ──────────────────────────────────────────────────────
+
+/// `http://host:port/path` -> (addr, path)
+fn parse_http_url(url: &str) -> Option<(SocketAddr, String)> {
+    let rest = url.trim().strip_prefix("http://")?;
+    let (authority, path) = match rest.find('/') {
+        Some(i) => (&rest[..i], rest[i..].to_string()),
+        None => (rest, "/".to_string()),
+    };
+    let authority = if authority.contains(':') { authority.to_string() } else { format!("{}:80", authority) };
+    let addr = authority.to_socket_addrs().ok()?.next()?;
+    Some((addr, path))
+}
+
+/// Send a request and return the response body plus our local IPv4 address.
+fn http_request(addr: SocketAddr, request: &str) -> Option<(String, Option<Ipv4Addr>)> {
+    let (response, local) = http_request_raw(addr, request)?;
+    let (_, body) = response.split_once("\r\n\r\n")?;
+    Some((body.to_string(), local))
+}
+
+fn http_request_raw(addr: SocketAddr, request: &str) -> Option<(String, Option<Ipv4Addr>)> {
+    let mut stream = TcpStream::connect_timeout(&addr, HTTP_TIMEOUT).ok()?;
+    stream.set_read_timeout(Some(HTTP_TIMEOUT)).ok();
+    stream.set_write_timeout(Some(HTTP_TIMEOUT)).ok();
+    let local = match stream.local_addr() {
+        Ok(SocketAddr::V4(v4)) => Some(*v4.ip()),
+        _ => None,
+    };
+    stream.write_all(request.as_bytes()).ok()?;
+
+    let mut response = Vec::new();
+    let mut chunk = [0u8; 4096];
+    loop {
+        match stream.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(n) => {
+                response.extend_from_slice(&chunk[..n]);
+                if response.len() >= MAX_HTTP_READ {
+                    break;
+                }
+            }
+            Err(_) => break,
+        }
+    }
+    if response.is_empty() {
+        return None;
+    }
+    Some((String::from_utf8_lossy(&response).into_owned(), local))
+}
+
+fn header_value(response: &str, name: &str) -> Option<String> {
+    response.lines().find_map(|line| {
+        let (k, v) = line.split_once(':')?;
+        k.trim().eq_ignore_ascii_case(name).then(|| v.trim().to_string())
+    })
+}
+
+/// Text of the first `<tag>` element, ignoring any namespace prefix.
+fn xml_text(xml: &str, tag: &str) -> Option<String> {
+    let mut from = 0;
+    while let Some(i) = xml[from..].find('<') {
+        let start = from + i + 1;
+        let end = start + xml[start..].find('>')?;
+        let open = &xml[start..end];
+        from = end + 1;
+        let name = open.split_whitespace().next().unwrap_or("");
+        let local = name.rsplit(':').next().unwrap_or(name);
+        if local != tag || open.ends_with('/') {
+            continue;
+        }
+        let close = xml[from..].find("</")?;
+        return Some(xml_unescape(xml[from..from + close].trim()));
+    }
+    None
+}
+
+fn xml_unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DESCRIPTION: &str = r#"<?xml version="1.0"?>
+<root xmlns="urn:schemas-upnp-org:device-1-0">
+  <device><serviceList>
+    <service>
+      <serviceType>urn:schemas-upnp-org:service:Layer3Forwarding:1</serviceType>
+      <controlURL>/ctl/L3F</controlURL>
+    </service>
+  </serviceList>
+  <deviceList><device><deviceList><device><serviceList>
+    <service>
+      <serviceType>urn:schemas-upnp-org:service:WANIPConnection:1</serviceType>
+      <serviceId>urn:upnp-org:serviceId:WANIPConn1</serviceId>
+      <controlURL>/ctl/IPConn</controlURL>
+    </service>
+  </serviceList></device></deviceList></device></deviceList></device>
+</root>"#;
+
+    const ENTRY: &str = r#"<?xml version="1.0"?>
+<s:Envelope xmlns:s="http://schemas.xmlsoap.org/soap/envelope/"><s:Body>
+<u:GetGenericPortMappingEntryResponse xmlns:u="urn:schemas-upnp-org:service:WANIPConnection:1">
+<NewRemoteHost></NewRemoteHost><NewExternalPort>8443</NewExternalPort><NewProtocol>TCP</NewProtocol>
+<NewInternalPort>443</NewInternalPort><NewInternalClient>192.168.1.20</NewInternalClient>
+<NewEnabled>1</NewEnabled><NewPortMappingDescription>NAS &amp; web</NewPortMappingDescription>
+<NewLeaseDuration>0</NewLeaseDuration>
+</u:GetGenericPortMappingEntryResponse></s:Body></s:Envelope>"#;
+
+    #[test]
+    fn test_find_wan_service() {
+        let (service, control) = find_wan_service(DESCRIPTION).unwrap();
+        assert_eq!(service, "urn:schemas-upnp-org:service:WANIPConnection:1");
+        assert_eq!(control, "/ctl/IPConn");
+    }
+
+    #[test]
+    fn test_parse_mapping_entry() {
+        let m = parse_mapping(ENTRY).unwrap();
+        assert_eq!(m.external_port, 8443);
+        assert_eq!(m.internal_port, 443);
+        assert_eq!(m.internal_client, Ipv4Addr::new(192, 168, 1, 20));
+        assert_eq!(m.protocol, "TCP");
+        assert!(m.enabled);
+        assert_eq!(m.description, "NAS & web");
+        assert!(m.remote_host.is_empty());
+    }
+
+    #[test]
+    fn test_parse_http_url() {
+        let (addr, path) = parse_http_url("http://192.168.1.1:5000/rootDesc.xml").unwrap();
+        assert_eq!(addr, "192.168.1.1:5000".parse().unwrap());
+        assert_eq!(path, "/rootDesc.xml");
+        let (addr, path) = parse_http_url("http://10.0.0.1").unwrap();
+        assert_eq!(addr.port(), 80);
+        assert_eq!(path, "/");
+    }
+
+    fn entry(external: u16, internal: u16, client: &str, desc: &str) -> String {
+        format!(
+            "<s:Envelope><s:Body><u:GetGenericPortMappingEntryResponse>\
+             <NewRemoteHost></NewRemoteHost><NewExternalPort>{}</NewExternalPort><NewProtocol>TCP</NewProtocol>\
+             <NewInternalPort>{}</NewInternalPort><NewInternalClient>{}</NewInternalClient>\
+             <NewEnabled>1</NewEnabled><NewPortMappingDescription>{}</NewPortMappingDescription>\
+             <NewLeaseDuration>3600</NewLeaseDuration>\
+             </u:GetGenericPortMappingEntryResponse></s:Body></s:Envelope>",
+            external, internal, client, desc
+        )
+    }
+
+    /// Minimal IGD stand-in: serves the description at `/rootDesc.xml` and
+    /// answers `GetGenericPortMappingEntry` from `entries`, with a SOAP 500
+    /// (SpecifiedArrayIndexInvalid) past the end of the table.
+    fn spawn_igd(entries: Vec<String>) -> SocketAddr {
+        use std::net::TcpListener;
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { break };
+                let mut request = Vec::new();
+                let mut chunk = [0u8; 4096];
+                while let Ok(n) = stream.read(&mut chunk) {
+                    if n == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&chunk[..n]);
+                    let text = String::from_utf8_lossy(&request);
+                    if (text.starts_with("GET") && text.contains("\r\n\r\n")) || text.contains("</s:Envelope>") {
+                        break;
+                    }
+                }
+                let request = String::from_utf8_lossy(&request).into_owned();
+                let (status, body) = if request.starts_with("GET /rootDesc.xml") {
+                    ("200 OK", DESCRIPTION.to_string())
+                } else if request.starts_with("POST /ctl/IPConn") && request.contains("GetGenericPortMappingEntry") {
+                    let index = xml_text(&request, "NewPortMappingIndex").and_then(|i| i.parse::<usize>().ok());
+                    match index.and_then(|i| entries.get(i)) {
+                        Some(e) => ("200 OK", e.clone()),
+                        None => ("500 Internal Server Error", "<s:Envelope><s:Body><s:Fault><detail><UPnPError><errorCode>713</errorCode><errorDescription>SpecifiedArrayIndexInvalid</errorDescription></UPnPError></detail></s:Fault></s:Body></s:Envelope>".to_string()),
+                    }
+                } else {
+                    ("404 Not Found", String::new())
+                };
+                let _ = write!(stream, "HTTP/1.1 {}\r\nContent-Type: text/xml\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", status, body.len(), body);
+            }
+        });
+        addr
+    }
+
+    #[test]
+    fn test_enumerate_mappings_from_stand_in() {
+        let addr = spawn_igd(vec![
+            entry(8443, 443, "127.0.0.1", "NAS &amp; web"),
+            entry(25565, 25565, "192.168.1.42", "Minecraft"),
+        ]);
+        let gw = gateway_from_location(&format!("http://{}/rootDesc.xml", addr)).unwrap();
+        assert_eq!(gw.addr, addr);
+        assert_eq!(gw.control_path, "/ctl/IPConn");
+        assert_eq!(gw.local_ip, Some(Ipv4Addr::LOCALHOST));
+
+        let mappings = list_mappings(&gw);
+        assert_eq!(mappings.len(), 2);
+        assert_eq!(mappings[0].description, "NAS & web");
+        assert_eq!(mappings[0].lease_secs, 3600);
+        assert_eq!(mappings[1].internal_client, Ipv4Addr::new(192, 168, 1, 42));
+        assert_eq!(mappings[1].external_port, 25565);
+
+        let mut mon = UpnpMonitor::new();
+        mon.gateway = Some(gw);
+        mon.mappings = mappings;
+        assert!(mon.mapping_for(Ipv4Addr::UNSPECIFIED.into(), 443, "TCP").is_some());
+    }
+
+    #[test]
+    fn test_detect_new_mappings() {
+        let a = parse_mapping(&entry(8443, 443, "192.168.1.20", "web")).unwrap();
+        let b = parse_mapping(&entry(3074, 3074, "192.168.1.30", "Xbox")).unwrap();
+        let mut mon = UpnpMonitor::new();
+        // The table found at startup only seeds the known set
+        assert!(mon.detect_new(std::slice::from_ref(&a)).is_empty());
+        // A description or lease change is not a new mapping
+        let mut renamed = a.clone();
+        renamed.description = "web server".into();
+        renamed.lease_secs = 10;
+        assert_eq!(mon.detect_new(&[renamed.clone(), b.clone()]), vec![b.clone()]);
+        assert!(mon.detect_new(&[renamed.clone(), b.clone()]).is_empty());
+        // Removed and re-added mappings alert again
+        assert!(mon.detect_new(std::slice::from_ref(&b)).is_empty());
+        assert_eq!(mon.detect_new(&[a.clone(), b]), vec![a]);
+    }
+
+    #[test]
+    fn test_mapping_for_listener() {
+        let mut mon = UpnpMonitor::new();
+        mon.gateway = Some(Gateway {
+            addr: "192.168.1.1:5000".parse().unwrap(),
+            control_path: "/ctl/IPConn".into(),
+            service_type: "urn:schemas-upnp-org:service:WANIPConnection:1".into(),
+            local_ip: Some(Ipv4Addr::new(192, 168, 1, 20)),
+        });
+        mon.mappings = vec![parse_mapping(ENTRY).unwrap()];
+        let any: IpAddr = Ipv4Addr::UNSPECIFIED.into();
+        assert!(mon.mapping_for(any, 443, "TCP").is_some());
+        assert!(mon.mapping_for(any, 443, "UDP").is_none());
+        assert!(mon.mapping_for(Ipv4Addr::LOCALHOST.into(), 443, "TCP").is_none());
+    }
+}
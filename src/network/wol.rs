@@ -0,0 +1,210 @@
+//! Wake-on-LAN: magic packets and watching for the woken device.
+//!
+//! A magic packet is six 0xFF bytes followed by the target MAC sixteen
+//! times, optionally followed by a 6-byte SecureOn password. It is sent as a
+//! UDP broadcast to ports 9 and 7 from the adapter that owns the LAN, both to
+//! the subnet's directed broadcast and to 255.255.255.255, since routers and
+//! NICs differ in which one they pass along.
+
+use std::io;
+use std::net::{Ipv4Addr, UdpSocket};
+
+use chrono::{DateTime, Local};
+
+use crate::types::LanDevice;
+
+/// Give up on a wake after this long without the device showing up.
+const WAKE_TIMEOUT_SECS: i64 = 300;
+
+/// Parse "AA:BB:CC:DD:EE:FF" or "AA-BB-CC-DD-EE-FF". SecureOn passwords use
+/// the same notation.
+pub fn parse_mac(s: &str) -> Option<[u8; 6]> {
+    let parts: Vec<&str> = s.trim().split([':', '-']).collect();
+    if parts.len() != 6 {
+        return None;
+    }
+    let mut mac = [0u8; 6];
+    for (byte, part) in mac.iter_mut().zip(parts) {
+        if part.len() != 2 {
+            return None;
+        }
+        *byte = u8::from_str_radix(part, 16).ok()?;
+    }
+    Some(mac)
+}
+
+pub fn magic_packet(mac: &[u8; 6], password: Option<&[u8; 6]>) -> Vec<u8> {
+    let mut packet = vec![0xFF; 6];
+    for _ in 0..16 {
+        packet.extend_from_slice(mac);
+    }
+    if let Some(pw) = password {
+        packet.extend_from_slice(pw);
+    }
+    packet
+}
+
+/// The subnet's directed broadcast address, e.g. 192.168.1.255 for a /24.
+pub fn directed_broadcast(ip: Ipv4Addr, mask: Ipv4Addr) -> Ipv4Addr {
+    Ipv4Addr::from(u32::from(ip) | !u32::from(mask))
+}
+
+/// Broadcast a magic packet from `local_ip`, so it leaves on the adapter of
+/// the device's network. Succeeds if at least one copy was sent.
+pub fn send_magic_packet(
+    mac: &[u8; 6],
+    password: Option<&[u8; 6]>,
+    local_ip: Ipv4Addr,
+    mask: Ipv4Addr,
+) -> io::Result<()> {
+    let packet = magic_packet(mac, password);
+    let sock = UdpSocket::bind((local_ip, 0))?;
+    sock.set_broadcast(true)?;
+
+    let mut last_err = None;
+    let mut sent = false;
+    for addr in [directed_broadcast(local_ip, mask), Ipv4Addr::BROADCAST] {
+        for port in [9, 7] {
+            match sock.send_to(&packet, (addr, port)) {
+                Ok(_) => sent = true,
+                Err(e) => last_err = Some(e),
+            }
+        }
+    }
+    match last_err {
+        Some(e) if !sent => Err(e),
+        _ => Ok(()),
+    }
+}
+
+// ─── Watching for the device ─────────────────────────────────────────────────
+
+pub struct PendingWake {
+    pub mac: String,
+    pub name: String,
+    pub sent: DateTime<Local>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum WakeOutcome {
+    Online { name: String, after_secs: i64 },
+    TimedOut { name: String },
+}
+
+/// Devices a magic packet was sent to, until a scan sees them or they time out.
+#[derive(Default)]
+pub struct WakeWatch {
+    pending: Vec<PendingWake>,
+}
+
+impl WakeWatch {
+    pub fn add(&mut self, mac: &str, name: &str, sent: DateTime<Local>) {
+        self.pending.retain(|w| w.mac != mac);
+        self.pending.push(PendingWake {
+            mac: mac.to_string(),
+            name: name.to_string(),
+            sent,
+        });
+    }
+
+    pub fn is_pending(&self, mac: &str) -> bool {
+        self.pending.iter().any(|w| w.mac == mac)
+    }
+
+    /// Whether any wake is still waiting for its device.
+    pub fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Resolve wakes whose device was seen online after the packet went out,
+    /// and those that have waited too long.
+    pub fn check(&mut self, devices: &[LanDevice], now: DateTime<Local>) -> Vec<WakeOutcome> {
+        let mut outcomes = Vec::new();
+        self.pending.retain(|w| {
+            let seen = devices
+                .iter()
+                .find(|d| d.mac == w.mac && d.is_online && d.last_seen > w.sent);
+            if let Some(d) = seen {
+                outcomes.push(WakeOutcome::Online {
+                    name: w.name.clone(),
+                    after_secs: (d.last_seen - w.sent).num_seconds(),
+                });
+                false
+            } else if (now - w.sent).num_seconds() > WAKE_TIMEOUT_SECS {
+                outcomes.push(WakeOutcome::TimedOut { name: w.name.clone() });
+                false
+            } else {
+                true
+            }
+        });
+        outcomes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::IpAddr;
+
+    use chrono::Duration;
+
+    fn device(mac: &str, online: bool, last_seen: DateTime<Local>) -> LanDevice {
+        LanDevice {
+            first_seen: last_seen,
+            last_seen,
+            is_online: online,
+            ..LanDevice::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 30)), mac.into())
+        }
+    }
+
+    #[test]
+    fn test_parse_mac() {
+        assert_eq!(parse_mac("00:1a:2B:3c:4D:5e"), Some([0x00, 0x1A, 0x2B, 0x3C, 0x4D, 0x5E]));
+        assert_eq!(parse_mac("00-1A-2B-3C-4D-5E"), Some([0x00, 0x1A, 0x2B, 0x3C, 0x4D, 0x5E]));
+        assert_eq!(parse_mac("00:1A:2B:3C:4D"), None);
+        assert_eq!(parse_mac("00:1A:2B:3C:4D:GG"), None);
+    }
+
+    #[test]
+    fn test_magic_packet_layout() {
+        let mac = [1, 2, 3, 4, 5, 6];
+        let packet = magic_packet(&mac, None);
+        assert_eq!(packet.len(), 102);
+        assert_eq!(&packet[..6], &[0xFF; 6]);
+        assert!(packet[6..].chunks(6).all(|c| c == mac));
+
+        let with_pw = magic_packet(&mac, Some(&[9, 9, 9, 9, 9, 9]));
+        assert_eq!(with_pw.len(), 108);
+        assert_eq!(&with_pw[102..], &[9; 6]);
+    }
+
+    #[test]
+    fn test_directed_broadcast() {
+        let b = directed_broadcast(Ipv4Addr::new(192, 168, 1, 20), Ipv4Addr::new(255, 255, 255, 0));
+        assert_eq!(b, Ipv4Addr::new(192, 168, 1, 255));
+        let b = directed_broadcast(Ipv4Addr::new(10, 0, 5, 1), Ipv4Addr::new(255, 255, 252, 0));
+        assert_eq!(b, Ipv4Addr::new(10, 0, 7, 255));
+    }
+
+    #[test]
+    fn test_watch_reports_online_and_timeout() {
+        let sent = Local::now();
+        let mut watch = WakeWatch::default();
+        watch.add("AA:AA:AA:AA:AA:AA", "nas", sent);
+        watch.add("BB:BB:BB:BB:BB:BB", "desktop", sent);
+
+        // Online before the packet went out does not count
+        let stale = [device("AA:AA:AA:AA:AA:AA", true, sent - Duration::seconds(60))];
+        assert!(watch.check(&stale, sent + Duration::seconds(5)).is_empty());
+
+        let woke = [device("AA:AA:AA:AA:AA:AA", true, sent + Duration::seconds(20))];
+        let outcomes = watch.check(&woke, sent + Duration::seconds(20));
+        assert_eq!(outcomes, vec![WakeOutcome::Online { name: "nas".into(), after_secs: 20 }]);
+        assert!(!watch.is_pending("AA:AA:AA:AA:AA:AA"));
+
+        let outcomes = watch.check(&[], sent + Duration::seconds(WAKE_TIMEOUT_SECS + 1));
+        assert_eq!(outcomes, vec![WakeOutcome::TimedOut { name: "desktop".into() }]);
+        assert!(!watch.is_pending("BB:BB:BB:BB:BB:BB"));
+        assert!(!watch.has_pending());
+    }
+}
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use std::net::IpAddr;
 
-use chrono::NaiveTime;
+use chrono::{DateTime, Local, NaiveTime};
 use serde::{Serialize, Deserialize};
 
 // ─── DNS cache ───────────────────────────────────────────────────────────────
@@ -82,15 +82,15 @@ impl TcpState {
     }
 
     pub fn color(&self) -> ratatui::style::Color {
-        use ratatui::style::Color;
+        let t = crate::ui::theme::theme();
         match self {
-            Self::Established => Color::Green,
-            Self::Listen => Color::Cyan,
-            Self::SynSent | Self::SynReceived => Color::Yellow,
-            Self::TimeWait | Self::FinWait1 | Self::FinWait2 => Color::Magenta,
-            Self::CloseWait | Self::Closing | Self::LastAck => Color::LightRed,
-            Self::Closed | Self::DeleteTcb => Color::DarkGray,
-            Self::Unknown(_) => Color::Gray,
+            Self::Established => t.ok,
+            Self::Listen => t.accent,
+            Self::SynSent | Self::SynReceived => t.warn,
+            Self::TimeWait | Self::FinWait1 | Self::FinWait2 => t.purple,
+            Self::CloseWait | Self::Closing | Self::LastAck => t.caution,
+            Self::Closed | Self::DeleteTcb => t.text_muted,
+            Self::Unknown(_) => t.text_dim,
         }
     }
 }
@@ -192,7 +192,7 @@ pub struct TrafficEntry {
 
 // ─── Bottom pane tab ─────────────────────────────────────────────────────────
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum BottomTab {
     Dashboard,
     Connections,
@@ -206,6 +206,18 @@ pub enum BottomTab {
 }
 
 impl BottomTab {
+    pub const ALL: [BottomTab; 9] = [
+        Self::Dashboard,
+        Self::Connections,
+        Self::Servers,
+        Self::Packets,
+        Self::Topology,
+        Self::Alerts,
+        Self::Firewall,
+        Self::Devices,
+        Self::Networks,
+    ];
+
     pub fn next(&self) -> Self {
         match self {
             Self::Dashboard => Self::Connections,
@@ -328,7 +340,9 @@ pub struct PacketSnippet {
     pub tcp_seq: u32,
     pub tcp_ack_num: u32,
     pub tcp_window: u16,
-    /// First 256 bytes of actual payload for hex dump
+    /// Raw TCP option bytes (MSS, window scale, SACK, timestamps, ...)
+    pub tcp_options: Vec<u8>,
+    /// First 256 bytes of actual payload for hex dump (all of it for DHCP)
     pub raw_payload: Vec<u8>,
 }
 
@@ -360,7 +374,7 @@ pub enum PacketDirection {
 
 // ─── Alert types (GlassWire-style) ──────────────────────────────────────────
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum AlertSeverity {
     Info,
     Warning,
@@ -377,11 +391,11 @@ impl AlertSeverity {
     }
 
     pub fn color(&self) -> ratatui::style::Color {
-        use ratatui::style::Color;
+        let t = crate::ui::theme::theme();
         match self {
-            Self::Info => Color::Rgb(80, 180, 255),
-            Self::Warning => Color::Rgb(255, 200, 60),
-            Self::Critical => Color::Rgb(255, 80, 80),
+            Self::Info => t.info,
+            Self::Warning => t.warn,
+            Self::Critical => t.error,
         }
     }
 }
@@ -398,8 +412,10 @@ pub enum AlertKind {
     RdpConnection { remote_addr: IpAddr, inbound: bool },
     /// Bandwidth spike detected
     BandwidthSpike { direction: String, speed_bps: f64, threshold_bps: f64 },
-    /// New device appeared on LAN
-    NewDevice { ip: IpAddr, mac: String, hostname: Option<String> },
+    /// A device not on the network's allowlist came online
+    UnknownDevice { ip: IpAddr, mac: String, hostname: Option<String> },
+    /// An allowlisted device came back online
+    TrustedDeviceReturned { ip: IpAddr, mac: String, name: String },
     /// Device left the LAN
     DeviceLeft { ip: IpAddr, mac: String },
     /// ARP spoofing: multiple IPs claiming same MAC, or IP changed MAC
@@ -420,6 +436,20 @@ pub enum AlertKind {
     InternetLost { detail: String },
     /// Internet connectivity restored
     InternetRestored,
+    /// A service started listening that is not in the listener baseline
+    NewListener { name: String, port: u16, proto: String, exe: String },
+    /// A baseline listener is no longer listening
+    ListenerGone { name: String, port: u16, proto: String },
+    /// A baseline listener's version or executable changed
+    ListenerChanged { name: String, port: u16, proto: String, what: String, old: String, new: String },
+    /// A user-defined health check started failing
+    HealthCheckFailed { name: String, port: u16, check: String, error: String },
+    /// A device seen this session has been offline longer than the threshold
+    DeviceOffline { name: String, ip: IpAddr, mac: String, minutes: i64 },
+    /// The router gained a UPnP port mapping forwarding an Internet port to a LAN host
+    NewPortMapping { external_port: u16, protocol: String, internal_client: IpAddr, internal_port: u16, description: String },
+    /// A DHCP server other than the gateway or a configured one answered on the LAN
+    RogueDhcpServer { server: IpAddr, mac: Option<String>, routers: Vec<IpAddr>, dns: Vec<IpAddr> },
 }
 
 impl AlertKind {
@@ -430,7 +460,8 @@ impl AlertKind {
             Self::SuspiciousHost { .. } => "Suspicious Host",
             Self::RdpConnection { .. } => "RDP Detected",
             Self::BandwidthSpike { .. } => "Bandwidth Spike",
-            Self::NewDevice { .. } => "New Device",
+            Self::UnknownDevice { .. } => "Unknown Device",
+            Self::TrustedDeviceReturned { .. } => "Device Returned",
             Self::DeviceLeft { .. } => "Device Left",
             Self::ArpAnomaly { .. } => "ARP Anomaly",
             Self::BandwidthOverage { .. } => "Data Overage",
@@ -441,6 +472,13 @@ impl AlertKind {
             Self::EvilTwinDetected { .. } => "Evil Twin",
             Self::InternetLost { .. } => "No Internet",
             Self::InternetRestored => "Internet OK",
+            Self::NewListener { .. } => "New Listener",
+            Self::ListenerGone { .. } => "Listener Gone",
+            Self::ListenerChanged { .. } => "Listener Changed",
+            Self::HealthCheckFailed { .. } => "Check Failed",
+            Self::DeviceOffline { .. } => "Device Offline",
+            Self::NewPortMapping { .. } => "Port Forward",
+            Self::RogueDhcpServer { .. } => "Rogue DHCP",
         }
     }
 
@@ -451,7 +489,8 @@ impl AlertKind {
             Self::SuspiciousHost { .. } => AlertSeverity::Critical,
             Self::RdpConnection { .. } => AlertSeverity::Warning,
             Self::BandwidthSpike { .. } => AlertSeverity::Info,
-            Self::NewDevice { .. } => AlertSeverity::Info,
+            Self::UnknownDevice { .. } => AlertSeverity::Critical,
+            Self::TrustedDeviceReturned { .. } => AlertSeverity::Info,
             Self::DeviceLeft { .. } => AlertSeverity::Info,
             Self::ArpAnomaly { .. } => AlertSeverity::Critical,
             Self::BandwidthOverage { .. } => AlertSeverity::Warning,
@@ -462,6 +501,13 @@ impl AlertKind {
             Self::EvilTwinDetected { .. } => AlertSeverity::Critical,
             Self::InternetLost { .. } => AlertSeverity::Critical,
             Self::InternetRestored => AlertSeverity::Info,
+            Self::NewListener { .. } => AlertSeverity::Warning,
+            Self::ListenerGone { .. } => AlertSeverity::Info,
+            Self::ListenerChanged { .. } => AlertSeverity::Warning,
+            Self::HealthCheckFailed { .. } => AlertSeverity::Warning,
+            Self::DeviceOffline { .. } => AlertSeverity::Warning,
+            Self::NewPortMapping { .. } => AlertSeverity::Warning,
+            Self::RogueDhcpServer { .. } => AlertSeverity::Critical,
         }
     }
 
@@ -483,9 +529,12 @@ impl AlertKind {
             Self::BandwidthSpike { direction, speed_bps, .. } => {
                 format!("{} spike: {}/s", direction, crate::utils::format_bytes(*speed_bps as u64))
             }
-            Self::NewDevice { ip, mac, hostname } => {
+            Self::UnknownDevice { ip, mac, hostname } => {
                 let name = hostname.as_deref().unwrap_or("unknown");
-                format!("New device: {} ({}) - {}", ip, mac, name)
+                format!("Unknown device joined: {} ({}) - {}", ip, mac, name)
+            }
+            Self::TrustedDeviceReturned { ip, name, .. } => {
+                format!("Trusted device returned: {} ({})", name, ip)
             }
             Self::DeviceLeft { ip, mac } => {
                 format!("Device left: {} ({})", ip, mac)
@@ -523,6 +572,31 @@ impl AlertKind {
             Self::InternetRestored => {
                 "Internet connectivity restored".to_string()
             }
+            Self::NewListener { name, port, proto, .. } => {
+                format!("New listener: {} on {}/{}", name, proto.to_lowercase(), port)
+            }
+            Self::ListenerGone { name, port, proto } => {
+                format!("Listener gone: {} on {}/{}", name, proto.to_lowercase(), port)
+            }
+            Self::ListenerChanged { name, port, proto, what, old, new } => {
+                format!("{} on {}/{}: {} changed {} → {}", name, proto.to_lowercase(), port, what, old, new)
+            }
+            Self::HealthCheckFailed { name, port, check, error } => {
+                format!("Health check failed: {} :{} ({}): {}", name, port, check, error)
+            }
+            Self::DeviceOffline { name, ip, minutes, .. } => {
+                let (n, unit) = if *minutes >= 120 { (minutes / 60, "h") } else { (*minutes, "min") };
+                format!("{} ({}) offline for more than {}{}", name, ip, n, unit)
+            }
+            Self::NewPortMapping { external_port, protocol, internal_client, internal_port, description } => {
+                let desc = if description.is_empty() { "no description" } else { description.as_str() };
+                format!("New UPnP port mapping: {}/{} → {}:{} ({})",
+                    protocol.to_lowercase(), external_port, internal_client, internal_port, desc)
+            }
+            Self::RogueDhcpServer { server, mac, routers, .. } => {
+                let router = routers.first().map(|r| format!(", router {}", r)).unwrap_or_default();
+                format!("Rogue DHCP server: {} ({}){}", server, mac.as_deref().unwrap_or("MAC unknown"), router)
+            }
         }
     }
 
@@ -533,7 +607,9 @@ impl AlertKind {
             Self::SuspiciousHost { .. }
             | Self::ArpAnomaly { .. }
             | Self::EvilTwinDetected { .. }
-            | Self::RdpConnection { .. } => AlertCategory::Security,
+            | Self::RdpConnection { .. }
+            | Self::NewPortMapping { .. }
+            | Self::RogueDhcpServer { .. } => AlertCategory::Security,
 
             // Network Access (apps connecting)
             Self::NewAppFirstConnection { .. }
@@ -543,11 +619,16 @@ impl AlertKind {
             // System Changes
             Self::DnsServerChanged { .. }
             | Self::HostsFileChanged { .. }
-            | Self::ProxyChanged { .. } => AlertCategory::SystemChanges,
+            | Self::ProxyChanged { .. }
+            | Self::NewListener { .. }
+            | Self::ListenerGone { .. }
+            | Self::ListenerChanged { .. } => AlertCategory::SystemChanges,
 
             // Device Activity
-            Self::NewDevice { .. }
-            | Self::DeviceLeft { .. } => AlertCategory::DeviceActivity,
+            Self::UnknownDevice { .. }
+            | Self::TrustedDeviceReturned { .. }
+            | Self::DeviceLeft { .. }
+            | Self::DeviceOffline { .. } => AlertCategory::DeviceActivity,
 
             // Bandwidth & Usage
             Self::BandwidthSpike { .. }
@@ -556,7 +637,8 @@ impl AlertKind {
 
             // Connectivity
             Self::InternetLost { .. }
-            | Self::InternetRestored => AlertCategory::Connectivity,
+            | Self::InternetRestored
+            | Self::HealthCheckFailed { .. } => AlertCategory::Connectivity,
         }
     }
 }
@@ -587,14 +669,14 @@ impl AlertCategory {
 
     /// Color for section headers.
     pub fn color(&self) -> ratatui::style::Color {
-        use ratatui::style::Color;
+        let t = crate::ui::theme::theme();
         match self {
-            Self::Security => Color::Rgb(255, 80, 80),
-            Self::NetworkAccess => Color::Rgb(100, 200, 255),
-            Self::SystemChanges => Color::Rgb(255, 200, 60),
-            Self::DeviceActivity => Color::Rgb(80, 220, 160),
-            Self::Bandwidth => Color::Rgb(180, 140, 255),
-            Self::Connectivity => Color::Rgb(255, 160, 60),
+            Self::Security => t.error,
+            Self::NetworkAccess => t.info,
+            Self::SystemChanges => t.warn,
+            Self::DeviceActivity => t.teal,
+            Self::Bandwidth => t.purple,
+            Self::Connectivity => t.caution,
         }
     }
 
@@ -714,8 +796,8 @@ pub struct LanDevice {
     pub mac: String,
     pub hostname: Option<String>,
     pub vendor: Option<String>,
-    pub first_seen: NaiveTime,
-    pub last_seen: NaiveTime,
+    pub first_seen: DateTime<Local>,
+    pub last_seen: DateTime<Local>,
     pub is_online: bool,
     /// User-assigned custom label for this device.
     pub custom_name: Option<String>,
@@ -733,14 +815,214 @@ pub struct LanDevice {
     /// Smoothed speed (bytes/s) from last few ticks.
     pub speed_sent: f64,
     pub speed_received: f64,
+    /// Classification evidence gathered during resolution: "mdns:_ipp._tcp",
+    /// "upnp:MediaRenderer", "model:...".
+    pub hints: Vec<String>,
+    /// Best-guess kind of device, see `network::device_type::classify`.
+    pub device_type: DeviceType,
+    /// How sure the classifier is about `device_type`, 0-99.
+    pub type_confidence: u8,
+    /// Services identified on the open ports, see `network::device_services`.
+    pub services: Vec<DeviceService>,
+    /// Options from the device's last captured DHCP request and the OS they
+    /// point to, see `network::dhcp_fingerprint`.
+    pub dhcp: Option<DhcpFingerprint>,
+}
+
+impl LanDevice {
+    /// An online device seen just now, with nothing resolved about it yet.
+    pub fn new(ip: IpAddr, mac: String) -> Self {
+        let now = Local::now();
+        Self {
+            ip,
+            mac,
+            hostname: None,
+            vendor: None,
+            first_seen: now,
+            last_seen: now,
+            is_online: true,
+            custom_name: None,
+            discovery_info: String::new(),
+            open_ports: String::new(),
+            bytes_sent: 0,
+            bytes_received: 0,
+            tick_sent: 0,
+            tick_received: 0,
+            speed_sent: 0.0,
+            speed_received: 0.0,
+            hints: Vec::new(),
+            device_type: DeviceType::Unknown,
+            type_confidence: 0,
+            services: Vec::new(),
+            dhcp: None,
+        }
+    }
+
+    /// Operating system inferred for the device, if any fingerprint named one.
+    pub fn os(&self) -> Option<&str> {
+        self.dhcp.as_ref().and_then(|d| d.os.as_deref())
+    }
+}
+
+/// What a device's DHCP requests reveal about it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DhcpFingerprint {
+    /// Option 55, the parameter request list in the client's order: "1,3,6,15".
+    pub params: String,
+    /// Option 60, e.g. "MSFT 5.0" or "android-dhcp-14".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vendor_class: Option<String>,
+    /// Option 57, maximum DHCP message size.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_size: Option<u16>,
+    /// Operating system from the fingerprint table: "Windows 10/11", "Android 14".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub os: Option<String>,
+    /// Device family: "Windows", "Apple iOS", "Android", "Embedded", ...
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub family: Option<String>,
+}
+
+/// A product identified behind an open port on a LAN device.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DeviceService {
+    pub port: u16,
+    /// "OpenSSH", "Synology DSM", "nginx", ...
+    pub product: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+impl DeviceService {
+    /// "OpenSSH 8.4 on 22".
+    pub fn label(&self) -> String {
+        match self.version {
+            Some(ref v) => format!("{} {} on {}", self.product, v, self.port),
+            None => format!("{} on {}", self.product, self.port),
+        }
+    }
+}
+
+/// What kind of device a LAN host appears to be.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DeviceType {
+    Phone,
+    Tablet,
+    Laptop,
+    Desktop,
+    Server,
+    VirtualMachine,
+    Printer,
+    Tv,
+    MediaPlayer,
+    Speaker,
+    Camera,
+    Nas,
+    Router,
+    GameConsole,
+    Iot,
+    Unknown,
+}
+
+impl DeviceType {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Phone => "Phone",
+            Self::Tablet => "Tablet",
+            Self::Laptop => "Laptop",
+            Self::Desktop => "Desktop",
+            Self::Server => "Server",
+            Self::VirtualMachine => "VM",
+            Self::Printer => "Printer",
+            Self::Tv => "TV",
+            Self::MediaPlayer => "Media",
+            Self::Speaker => "Speaker",
+            Self::Camera => "Camera",
+            Self::Nas => "NAS",
+            Self::Router => "Router",
+            Self::GameConsole => "Console",
+            Self::Iot => "IoT",
+            Self::Unknown => "Unknown",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Self::Phone => "\u{1F4F1}",
+            Self::Tablet => "\u{1F4D6}",
+            Self::Laptop => "\u{1F4BB}",
+            Self::Desktop => "\u{1F5A5}",
+            Self::Server => "\u{1F5C4}",
+            Self::VirtualMachine => "\u{1F4E6}",
+            Self::Printer => "\u{1F5A8}",
+            Self::Tv => "\u{1F4FA}",
+            Self::MediaPlayer => "\u{1F4C0}",
+            Self::Speaker => "\u{1F50A}",
+            Self::Camera => "\u{1F4F7}",
+            Self::Nas => "\u{1F4BE}",
+            Self::Router => "\u{1F4E1}",
+            Self::GameConsole => "\u{1F3AE}",
+            Self::Iot => "\u{1F4A1}",
+            Self::Unknown => "\u{2754}",
+        }
+    }
+
+    pub fn color(&self) -> ratatui::style::Color {
+        let t = crate::ui::theme::theme();
+        match self {
+            Self::Phone | Self::Tablet => t.info,
+            Self::Laptop | Self::Desktop => t.text,
+            Self::Server | Self::VirtualMachine | Self::Nas => t.purple,
+            Self::Printer => t.olive,
+            Self::Tv | Self::MediaPlayer | Self::Speaker | Self::GameConsole => t.pink,
+            Self::Camera => t.caution,
+            Self::Router => t.ok,
+            Self::Iot => t.teal,
+            Self::Unknown => t.text_muted,
+        }
+    }
+
+    /// All types in filter-cycle order.
+    pub fn all() -> &'static [DeviceType] {
+        &[
+            Self::Phone,
+            Self::Tablet,
+            Self::Laptop,
+            Self::Desktop,
+            Self::Server,
+            Self::VirtualMachine,
+            Self::Printer,
+            Self::Tv,
+            Self::MediaPlayer,
+            Self::Speaker,
+            Self::Camera,
+            Self::Nas,
+            Self::Router,
+            Self::GameConsole,
+            Self::Iot,
+            Self::Unknown,
+        ]
+    }
 }
 
 // ─── Firewall rule ──────────────────────────────────────────────────────────
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct FirewallRule {
     pub name: String,
     pub enabled: bool,
+    /// "In" or "Out".
+    pub direction: String,
+    /// "Allow", "Block" or "Bypass".
+    pub action: String,
+    /// Executable path, or "Any".
+    pub program: String,
+    /// `program` with environment variables expanded, for matching listeners.
+    pub program_path: String,
+    /// Port list as netsh prints it: "Any", "80,443", "5000-5010", "RPC", ...
+    pub local_ports: String,
+    /// "TCP", "UDP", "Any", "ICMPv4", ...
+    pub protocol: String,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -772,6 +1054,59 @@ pub enum FirewallAppAction {
     Drop,
 }
 
+/// Everything shown in the server detail popup.
+#[derive(Clone, Debug)]
+pub struct ServerDetail {
+    pub kind_label: String,
+    pub kind_icon: String,
+    pub category: String,
+    pub port: u16,
+    pub proto: String,
+    pub bind_addr: String,
+    pub pid: u32,
+    pub process_name: String,
+    pub exe_path: String,
+    pub cmdline: String,
+    pub product_name: String,
+    pub company_name: String,
+    pub version: String,
+    pub http_title: String,
+    pub banner: String,
+    pub response_headers: Vec<(String, String)>,
+    pub active_connections: u32,
+    pub first_seen: String,
+    pub is_responsive: bool,
+    pub tls_detected: bool,
+    pub category_color: (u8, u8, u8),
+    pub detected_techs: Vec<(String, String, String)>, // (name, category, version)
+    pub protocol_details: Vec<(String, String)>,
+    /// None until the listener's exposure has been assessed.
+    pub exposure: Option<ServerExposureDetail>,
+    /// Matching advisories: (severity, title, detail), most severe first.
+    pub advisories: Vec<(AlertSeverity, String, String)>,
+    /// None when no health check is configured for the port.
+    pub health: Option<ServerHealthDetail>,
+}
+
+/// Reachability of a listener for the server detail popup.
+#[derive(Clone, Debug)]
+pub struct ServerExposureDetail {
+    pub level: String,
+    pub color: (u8, u8, u8),
+    pub reasons: Vec<String>,
+    pub risks: Vec<(AlertSeverity, String)>,
+}
+
+/// A listener's health check and recent results for the server detail popup.
+#[derive(Clone, Debug)]
+pub struct ServerHealthDetail {
+    pub check: String,
+    pub interval_secs: u64,
+    /// Latency per run in ms, oldest first; None for a failed run.
+    pub history: Vec<Option<u64>>,
+    pub last_error: Option<String>,
+}
+
 /// Combined firewall + bandwidth detail for the popup overlay.
 #[derive(Clone, Debug)]
 pub struct FirewallAppDetail {
@@ -849,30 +1184,7 @@ pub struct ThreatInfo {
 pub enum DetailKind {
     Connection(Connection),
     Alert(Alert),
-    Device(LanDevice),
+    Device(Box<LanDevice>),
     FirewallApp(FirewallAppDetail),
-    Server {
-        kind_label: String,
-        kind_icon: String,
-        category: String,
-        port: u16,
-        proto: String,
-        bind_addr: String,
-        pid: u32,
-        process_name: String,
-        exe_path: String,
-        cmdline: String,
-        product_name: String,
-        company_name: String,
-        version: String,
-        http_title: String,
-        banner: String,
-        response_headers: Vec<(String, String)>,
-        active_connections: u32,
-        first_seen: String,
-        is_responsive: bool,
-        tls_detected: bool,
-        category_color: (u8, u8, u8),
-        detected_techs: Vec<(String, String, String)>, // (name, category, version)
-    },
+    Server(Box<ServerDetail>),
 }
@@ -4,7 +4,7 @@
 //! Mouse: click to focus pane, scroll wheel to scroll within.
 
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{
     Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap,
@@ -13,6 +13,7 @@ use ratatui::Frame;
 
 use crate::app::App;
 use crate::types::{Alert, AlertCategory};
+use crate::ui::theme::theme;
 
 /// Format bytes into human-readable string.
 fn fmt_bytes(b: u64) -> String {
@@ -67,39 +68,39 @@ pub fn draw_alerts(f: &mut Frame, area: Rect, app: &mut App) {
     let mut title_spans = vec![
         Span::styled(
             " Alerts ",
-            Style::default().fg(Color::Rgb(160, 180, 220)).add_modifier(Modifier::BOLD),
+            Style::default().fg(theme().text).add_modifier(Modifier::BOLD),
         ),
         Span::styled(
             format!(" {} total ", total_alerts),
-            Style::default().fg(Color::Rgb(100, 120, 150)),
+            Style::default().fg(theme().text_dim),
         ),
     ];
     if unread > 0 {
         title_spans.push(Span::styled(
             format!("({} new) ", unread),
-            Style::default().fg(Color::Rgb(255, 100, 80)).add_modifier(Modifier::BOLD),
+            Style::default().fg(theme().error).add_modifier(Modifier::BOLD),
         ));
     }
     if app.alert_engine.is_snoozed() {
         title_spans.push(Span::styled(
             " SNOOZED ",
             Style::default()
-                .fg(Color::Rgb(255, 200, 80))
-                .bg(Color::Rgb(60, 50, 20))
+                .fg(theme().highlight)
+                .bg(theme().warn_bg)
                 .add_modifier(Modifier::BOLD),
         ));
     }
     // Key hints in title
     title_spans.push(Span::styled(
         "  \u{2190}\u{2192}:Pane  \u{2191}\u{2193}:Scroll  ",
-        Style::default().fg(Color::Rgb(60, 80, 110)),
+        Style::default().fg(theme().text_muted),
     ));
 
     let outer_block = Block::default()
         .title(Line::from(title_spans))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Rgb(30, 50, 85)))
-        .style(Style::default().bg(Color::Rgb(12, 16, 28)));
+        .border_style(Style::default().fg(theme().border))
+        .style(Style::default().bg(theme().surface));
 
     let inner = outer_block.inner(remaining);
     f.render_widget(outer_block, remaining);
@@ -110,7 +111,7 @@ pub fn draw_alerts(f: &mut Frame, area: Rect, app: &mut App) {
     if active_cats.is_empty() {
         let empty = Paragraph::new(Line::from(Span::styled(
             "  No alerts yet. Monitoring...",
-            Style::default().fg(Color::Rgb(60, 80, 110)),
+            Style::default().fg(theme().text_muted),
         )));
         f.render_widget(empty, inner);
         app.alert_pane_rects.clear();
@@ -172,38 +173,38 @@ fn draw_banner(f: &mut Frame, area: Rect, app: &App) -> (u16, Rect) {
                 Span::styled(
                     " Since Your Last Visit ",
                     Style::default()
-                        .fg(Color::Rgb(100, 200, 255))
+                        .fg(theme().accent)
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::styled(
                     format!("(last seen: {}) ", summary.last_session_end),
-                    Style::default().fg(Color::Rgb(150, 170, 200)),
+                    Style::default().fg(theme().text),
                 ),
                 Span::styled(
                     "  Press any key to dismiss",
-                    Style::default().fg(Color::Rgb(80, 100, 130)),
+                    Style::default().fg(theme().text_muted),
                 ),
             ]),
             Line::from(vec![
                 Span::styled(
                     format!("  {} alerts", summary.alert_count),
-                    Style::default().fg(Color::Rgb(255, 160, 80)),
+                    Style::default().fg(theme().caution),
                 ),
-                Span::styled("  |  ", Style::default().fg(Color::Rgb(40, 55, 80))),
+                Span::styled("  |  ", Style::default().fg(theme().divider)),
                 Span::styled(
                     format!("{} connections", summary.connections),
-                    Style::default().fg(Color::Rgb(100, 200, 160)),
+                    Style::default().fg(theme().teal),
                 ),
-                Span::styled("  |  ", Style::default().fg(Color::Rgb(40, 55, 80))),
+                Span::styled("  |  ", Style::default().fg(theme().divider)),
                 Span::styled(
                     format!("{} devices", summary.device_count),
-                    Style::default().fg(Color::Rgb(180, 140, 255)),
+                    Style::default().fg(theme().purple),
                 ),
             ]),
             Line::from(vec![
                 Span::styled(
                     format!("  Down: {}  Up: {}", fmt_bytes(summary.bytes_down), fmt_bytes(summary.bytes_up)),
-                    Style::default().fg(Color::Rgb(130, 170, 220)),
+                    Style::default().fg(theme().text),
                 ),
             ]),
         ];
@@ -212,8 +213,8 @@ fn draw_banner(f: &mut Frame, area: Rect, app: &App) -> (u16, Rect) {
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Rgb(30, 60, 100)))
-                    .style(Style::default().bg(Color::Rgb(15, 22, 38))),
+                    .border_style(Style::default().fg(theme().border))
+                    .style(Style::default().bg(theme().surface_alt)),
             );
         f.render_widget(banner, layout[0]);
         (h, layout[1])
@@ -230,34 +231,34 @@ fn draw_banner(f: &mut Frame, area: Rect, app: &App) -> (u16, Rect) {
                 Span::styled(
                     " While You Were Away ",
                     Style::default()
-                        .fg(Color::Rgb(255, 220, 120))
+                        .fg(theme().highlight)
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::styled(
                     format!("({}m {}s) ", summary.duration_secs / 60, summary.duration_secs % 60),
-                    Style::default().fg(Color::Rgb(150, 170, 200)),
+                    Style::default().fg(theme().text),
                 ),
                 Span::styled(
                     "  Press any key to dismiss",
-                    Style::default().fg(Color::Rgb(80, 100, 130)),
+                    Style::default().fg(theme().text_muted),
                 ),
             ]),
             Line::from(vec![
                 Span::styled(
                     format!("  {} new connections", summary.new_connections),
-                    Style::default().fg(Color::Rgb(100, 200, 160)),
+                    Style::default().fg(theme().teal),
                 ),
-                Span::styled("  |  ", Style::default().fg(Color::Rgb(40, 55, 80))),
+                Span::styled("  |  ", Style::default().fg(theme().divider)),
                 Span::styled(
                     format!("Down: {}  Up: {}", fmt_bytes(summary.bytes_down), fmt_bytes(summary.bytes_up)),
-                    Style::default().fg(Color::Rgb(130, 170, 220)),
+                    Style::default().fg(theme().text),
                 ),
             ]),
         ];
         for evt in summary.events.iter().take(3) {
             lines.push(Line::from(Span::styled(
                 format!("  {}", evt),
-                Style::default().fg(Color::Rgb(180, 160, 120)),
+                Style::default().fg(theme().gold),
             )));
         }
 
@@ -265,8 +266,8 @@ fn draw_banner(f: &mut Frame, area: Rect, app: &App) -> (u16, Rect) {
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Rgb(80, 70, 30)))
-                    .style(Style::default().bg(Color::Rgb(30, 28, 15))),
+                    .border_style(Style::default().fg(theme().text_faint))
+                    .style(Style::default().bg(theme().warn_bg)),
             );
         f.render_widget(banner, layout[0]);
         (h, layout[1])
@@ -295,27 +296,27 @@ fn draw_category_pane(
         ),
         Span::styled(
             format!("({})", cat_alerts.len()),
-            Style::default().fg(Color::Rgb(100, 120, 150)),
+            Style::default().fg(theme().text_dim),
         ),
     ];
     if unread > 0 {
         title_parts.push(Span::styled(
             format!(" {} new", unread),
-            Style::default().fg(Color::Rgb(255, 100, 80)).add_modifier(Modifier::BOLD),
+            Style::default().fg(theme().error).add_modifier(Modifier::BOLD),
         ));
     }
 
     let border_color = if is_focused {
-        Color::Rgb(100, 200, 255) // bright blue for focused
+        theme().accent // bright blue for focused
     } else {
-        Color::Rgb(30, 45, 70) // dim for unfocused
+        theme().selected_bg // dim for unfocused
     };
 
     let block = Block::default()
         .title(Line::from(title_parts))
         .borders(Borders::ALL)
         .border_style(Style::default().fg(border_color))
-        .style(Style::default().bg(Color::Rgb(12, 16, 28)));
+        .style(Style::default().bg(theme().surface));
 
     let pane_inner = block.inner(area);
     f.render_widget(block, area);
@@ -340,7 +341,7 @@ fn draw_category_pane(
         // Highlight selected row in focused pane
         let is_selected = is_focused && i == scroll_offset;
         let bg = if is_selected {
-            Style::default().bg(Color::Rgb(25, 45, 85))
+            Style::default().bg(theme().selected_bg)
         } else {
             Style::default()
         };
@@ -349,14 +350,14 @@ fn draw_category_pane(
             Span::styled(
                 if is_selected { "\u{25b8} " } else { unread_marker },
                 if is_selected {
-                    Style::default().fg(Color::Rgb(100, 200, 255)).add_modifier(Modifier::BOLD)
+                    Style::default().fg(theme().accent).add_modifier(Modifier::BOLD)
                 } else {
-                    Style::default().fg(Color::Rgb(255, 100, 80))
+                    Style::default().fg(theme().error)
                 },
             ),
             Span::styled(
                 time_str,
-                bg.fg(Color::Rgb(100, 110, 130)).add_modifier(read_dim),
+                bg.fg(theme().text_dim).add_modifier(read_dim),
             ),
             Span::styled(
                 format!(" {} ", severity.label()),
@@ -364,11 +365,11 @@ fn draw_category_pane(
             ),
             Span::styled(
                 format!("[{}] ", type_label),
-                bg.fg(Color::Rgb(180, 190, 220)).add_modifier(read_dim),
+                bg.fg(theme().text).add_modifier(read_dim),
             ),
             Span::styled(
                 desc,
-                bg.fg(Color::Rgb(150, 160, 180)).add_modifier(read_dim),
+                bg.fg(theme().text_secondary).add_modifier(read_dim),
             ),
         ]));
     }
@@ -408,9 +409,9 @@ fn draw_category_pane(
         f.render_stateful_widget(
             Scrollbar::new(ScrollbarOrientation::VerticalRight)
                 .style(Style::default().fg(if is_focused {
-                    Color::Rgb(60, 120, 180)
+                    theme().text_dim
                 } else {
-                    Color::Rgb(30, 50, 80)
+                    theme().border
                 })),
             sb_area,
             &mut sb_state,
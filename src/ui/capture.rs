@@ -9,6 +9,7 @@ use ratatui::Frame;
 use crate::app::App;
 use crate::network::dns::port_service_name;
 use crate::types::TrafficEventKind;
+use crate::ui::theme::theme;
 
 pub fn draw_traffic(f: &mut Frame, area: Rect, app: &App) {
     let tracker = &app.traffic_tracker;
@@ -34,7 +35,7 @@ pub fn draw_traffic(f: &mut Frame, area: Rect, app: &App) {
     };
 
     let hdr_style = Style::default()
-        .fg(Color::Rgb(160, 180, 220))
+        .fg(theme().text)
         .add_modifier(Modifier::BOLD);
 
     let si = |col: usize| -> &str {
@@ -52,7 +53,7 @@ pub fn draw_traffic(f: &mut Frame, area: Rect, app: &App) {
         Cell::from(Span::styled(format!("State{}", si(5)), hdr_style)),
     ])
     .height(1)
-    .style(Style::default().bg(Color::Rgb(18, 25, 42)));
+    .style(Style::default().bg(theme().header_bg));
 
     // Sort by column if set
     let mut sorted_filtered: Vec<_> = filtered.into_iter().collect();
@@ -112,28 +113,28 @@ pub fn draw_traffic(f: &mut Frame, area: Rect, app: &App) {
             };
             let state_color = match &entry.event {
                 TrafficEventKind::StateChange { to, .. } => to.color(),
-                TrafficEventKind::NewConnection => Color::Green,
-                TrafficEventKind::ConnectionClosed => Color::Red,
-                TrafficEventKind::DataActivity { inbound: true, .. } => Color::Cyan,
-                TrafficEventKind::DataActivity { inbound: false, .. } => Color::Magenta,
+                TrafficEventKind::NewConnection => theme().ok,
+                TrafficEventKind::ConnectionClosed => theme().error,
+                TrafficEventKind::DataActivity { inbound: true, .. } => theme().accent,
+                TrafficEventKind::DataActivity { inbound: false, .. } => theme().purple,
             };
 
             // ── Process ──
-            let process_color = Color::Rgb(150, 200, 150);
+            let process_color = theme().ok_soft;
 
             // Row background tint
             let row_bg = match &entry.event {
-                TrafficEventKind::NewConnection => Color::Rgb(15, 25, 18),
-                TrafficEventKind::ConnectionClosed => Color::Rgb(25, 15, 15),
-                TrafficEventKind::StateChange { .. } => Color::Rgb(25, 25, 12),
-                TrafficEventKind::DataActivity { inbound: true, .. } => Color::Rgb(12, 20, 28),
-                TrafficEventKind::DataActivity { inbound: false, .. } => Color::Rgb(20, 14, 25),
+                TrafficEventKind::NewConnection => theme().ok_bg,
+                TrafficEventKind::ConnectionClosed => theme().error_bg,
+                TrafficEventKind::StateChange { .. } => theme().warn_bg,
+                TrafficEventKind::DataActivity { inbound: true, .. } => theme().surface,
+                TrafficEventKind::DataActivity { inbound: false, .. } => theme().surface,
             };
 
             Row::new(vec![
                 Cell::from(Span::styled(
                     time_str,
-                    Style::default().fg(Color::Rgb(100, 110, 130)),
+                    Style::default().fg(theme().text_dim),
                 )),
                 Cell::from(Span::styled(
                     entry.process_name.clone(),
@@ -145,7 +146,7 @@ pub fn draw_traffic(f: &mut Frame, area: Rect, app: &App) {
                 )),
                 Cell::from(Span::styled(
                     service_display,
-                    Style::default().fg(Color::Rgb(200, 180, 100)),
+                    Style::default().fg(theme().gold),
                 )),
                 Cell::from(Span::styled(
                     event_label,
@@ -172,20 +173,20 @@ pub fn draw_traffic(f: &mut Frame, area: Rect, app: &App) {
     let mut title_spans = vec![
         Span::styled(
             " Traffic ",
-            Style::default().fg(Color::Rgb(160, 180, 220)).add_modifier(Modifier::BOLD),
+            Style::default().fg(theme().text).add_modifier(Modifier::BOLD),
         ),
         Span::styled(
             format!(" {}/{} ", tracker.log.len(), tracker.max_log_size),
-            Style::default().fg(Color::Rgb(110, 130, 160)),
+            Style::default().fg(theme().text_dim),
         ),
     ];
     let usage_pct = (tracker.log.len() as f64 / tracker.max_log_size as f64 * 100.0) as u8;
     let usage_color = if usage_pct > 90 {
-        Color::Red
+        theme().error
     } else if usage_pct > 70 {
-        Color::Yellow
+        theme().warn
     } else {
-        Color::Rgb(80, 140, 80)
+        theme().text_dim
     };
     title_spans.push(Span::styled(
         format!("({}%)", usage_pct),
@@ -193,16 +194,16 @@ pub fn draw_traffic(f: &mut Frame, area: Rect, app: &App) {
     ));
     title_spans.push(Span::styled(
         localhost_info.to_string(),
-        Style::default().fg(Color::Rgb(100, 180, 220)),
+        Style::default().fg(theme().info),
     ));
     if !pause_info.is_empty() {
         title_spans.push(Span::styled(
             pause_info.to_string(),
-            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            Style::default().fg(theme().error).add_modifier(Modifier::BOLD),
         ));
     }
     if !filter_info.is_empty() {
-        title_spans.push(Span::styled(filter_info, Style::default().fg(Color::Yellow)));
+        title_spans.push(Span::styled(filter_info, Style::default().fg(theme().key)));
     }
 
     let table = Table::new(
@@ -221,8 +222,8 @@ pub fn draw_traffic(f: &mut Frame, area: Rect, app: &App) {
         Block::default()
             .title(Line::from(title_spans))
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Rgb(30, 50, 85)))
-            .style(Style::default().bg(Color::Rgb(12, 16, 28))),
+            .border_style(Style::default().fg(theme().border))
+            .style(Style::default().bg(theme().surface)),
     );
 
     f.render_widget(table, area);
@@ -239,7 +240,7 @@ pub fn draw_traffic(f: &mut Frame, area: Rect, app: &App) {
             ScrollbarState::new(total.saturating_sub(visible_height)).position(scroll);
         f.render_stateful_widget(
             Scrollbar::new(ScrollbarOrientation::VerticalRight)
-                .style(Style::default().fg(Color::Rgb(40, 70, 120))),
+                .style(Style::default().fg(theme().border_strong)),
             sb_area,
             &mut sb_state,
         );
@@ -258,23 +259,23 @@ fn build_host_display(entry: &TrafficEntry) -> (String, Color) {
             if let Some(ref dns) = entry.dns_name {
                 // DNS resolved — show domain name prominently
                 let display = format!("{} {}:{}", dir_arrow, dns, port);
-                (display, Color::Rgb(100, 220, 255)) // Bright cyan
+                (display, theme().accent) // Bright cyan
             } else if addr.is_loopback() {
                 let display = format!("{} localhost:{}", dir_arrow, port);
-                (display, Color::Rgb(100, 100, 120)) // Dim for localhost
+                (display, theme().text_dim) // Dim for localhost
             } else {
                 // No DNS — show IP:port
                 let display = format!("{} {}:{}", dir_arrow, addr, port);
-                (display, Color::Rgb(170, 185, 205)) // Normal
+                (display, theme().text) // Normal
             }
         }
         (Some(addr), None) => {
             if let Some(ref dns) = entry.dns_name {
                 let display = format!("{} {}", dir_arrow, dns);
-                (display, Color::Rgb(100, 220, 255))
+                (display, theme().accent)
             } else {
                 let display = format!("{} {}", dir_arrow, addr);
-                (display, Color::Rgb(170, 185, 205))
+                (display, theme().text)
             }
         }
         _ => {
@@ -282,7 +283,7 @@ fn build_host_display(entry: &TrafficEntry) -> (String, Color) {
             let local_svc = port_service_name(entry.local_port)
                 .map(|s| format!("{} [{}]", entry.local_port, s))
                 .unwrap_or_else(|| format!(":{}", entry.local_port));
-            (format!("{} {} {}", dir_arrow, entry.local_addr, local_svc), Color::Rgb(120, 120, 150))
+            (format!("{} {} {}", dir_arrow, entry.local_addr, local_svc), theme().text_dim)
         }
     }
 }
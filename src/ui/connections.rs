@@ -1,5 +1,5 @@
 use ratatui::layout::{Constraint, Rect};
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{
     Block, Borders, Cell, Row, Scrollbar, ScrollbarOrientation, ScrollbarState, Table,
@@ -9,6 +9,7 @@ use ratatui::Frame;
 use crate::app::App;
 use crate::network::dns::port_service_name;
 use crate::types::TcpState;
+use crate::ui::theme::theme;
 
 pub fn draw_connections(f: &mut Frame, area: Rect, app: &App) {
     let filtered = app.filtered_connections();
@@ -23,7 +24,7 @@ pub fn draw_connections(f: &mut Frame, area: Rect, app: &App) {
     };
 
     let hdr_style = Style::default()
-        .fg(Color::Rgb(160, 180, 220))
+        .fg(theme().text)
         .add_modifier(Modifier::BOLD);
 
     // ── Redesigned columns: Process | Remote Host | Country | Service | State | Local ──
@@ -36,7 +37,7 @@ pub fn draw_connections(f: &mut Frame, area: Rect, app: &App) {
         Cell::from(Span::styled(format!("Local{}", sort_ind(2)), hdr_style)),
     ])
     .height(1)
-    .style(Style::default().bg(Color::Rgb(18, 25, 42)));
+    .style(Style::default().bg(theme().header_bg));
 
     let visible_height = area.height.saturating_sub(5) as usize;
     let selected = if total > 0 { app.conn_scroll.min(total - 1) } else { 0 };
@@ -79,9 +80,9 @@ pub fn draw_connections(f: &mut Frame, area: Rect, app: &App) {
                 if is_selected { format!("\u{25B8} {}", base) } else { format!("  {}", base) }
             };
             let proc_color = if proc_name.starts_with("PID:") || proc_name.starts_with('[') {
-                Color::Rgb(90, 100, 125)
+                theme().text_muted
             } else {
-                Color::Rgb(130, 200, 140)
+                theme().ok_soft
             };
 
             // ── Remote Host (the star column) ──
@@ -89,18 +90,18 @@ pub fn draw_connections(f: &mut Frame, area: Rect, app: &App) {
                 (Some(dns), _) if dns != "localhost" => {
                     // Cap at 44 chars to prevent rows overflowing in embedded terminals
                     let host = if dns.len() > 44 { format!("{}…", &dns[..43]) } else { dns.clone() };
-                    (format!("\u{2192} {}", host), Color::Rgb(100, 220, 255))
+                    (format!("\u{2192} {}", host), theme().accent)
                 }
                 (Some(_), Some(ip)) if ip.is_loopback() => {
-                    ("\u{2192} localhost".to_string(), Color::Rgb(75, 85, 108))
+                    ("\u{2192} localhost".to_string(), theme().text_muted)
                 }
                 (None, Some(ip)) if ip.is_unspecified() => {
-                    ("*".to_string(), Color::Rgb(55, 65, 85))
+                    ("*".to_string(), theme().text_faint)
                 }
                 (None, Some(ip)) => {
-                    (format!("\u{2192} {}", ip), Color::Rgb(155, 170, 195))
+                    (format!("\u{2192} {}", ip), theme().text)
                 }
-                _ => ("*".to_string(), Color::Rgb(55, 65, 85)),
+                _ => ("*".to_string(), theme().text_faint),
             };
             let remote_bold = conn.dns_hostname.is_some()
                 && conn.dns_hostname.as_deref() != Some("localhost");
@@ -115,12 +116,12 @@ pub fn draw_connections(f: &mut Frame, area: Rect, app: &App) {
                 format!("{}/{}", port, proto)
             };
             let service_color = match svc_name {
-                Some("HTTPS") => Color::Rgb(80, 200, 120),
-                Some("HTTP") => Color::Rgb(220, 180, 60),
-                Some("DNS") => Color::Rgb(100, 180, 255),
-                Some("SSH") => Color::Rgb(180, 130, 255),
-                Some("RDP") => Color::Rgb(255, 150, 100),
-                _ => Color::Rgb(180, 170, 130),
+                Some("HTTPS") => theme().ok,
+                Some("HTTP") => theme().gold,
+                Some("DNS") => theme().info,
+                Some("SSH") => theme().purple,
+                Some("RDP") => theme().caution,
+                _ => theme().gold,
             };
 
             // ── State ──
@@ -133,7 +134,7 @@ pub fn draw_connections(f: &mut Frame, area: Rect, app: &App) {
                 .state
                 .as_ref()
                 .map(|s| s.color())
-                .unwrap_or(Color::Rgb(80, 100, 140));
+                .unwrap_or(theme().text_muted);
 
             // ── GeoIP country ──
             // Use plain ASCII code only — flag emoji (Regional Indicator pairs) have
@@ -144,12 +145,12 @@ pub fn draw_connections(f: &mut Frame, area: Rect, app: &App) {
                     match app.geoip.lookup(ip) {
                         Some(info) => (
                             info.code.to_string(),
-                            Color::Rgb(170, 200, 230),
+                            theme().text,
                         ),
-                        None => ("-".to_string(), Color::Rgb(55, 65, 85)),
+                        None => ("-".to_string(), theme().text_faint),
                     }
                 }
-                _ => ("-".to_string(), Color::Rgb(55, 65, 85)),
+                _ => ("-".to_string(), theme().text_faint),
             };
 
             // ── Local port ──
@@ -164,11 +165,11 @@ pub fn draw_connections(f: &mut Frame, area: Rect, app: &App) {
                     | Some(TcpState::DeleteTcb)
             );
             let row_bg = if is_selected {
-                Color::Rgb(25, 45, 85)
+                theme().selected_bg
             } else if dim {
-                Color::Rgb(8, 10, 18)
+                theme().bg
             } else {
-                Color::Rgb(12, 16, 28)
+                theme().surface
             };
 
             Row::new(vec![
@@ -185,12 +186,12 @@ pub fn draw_connections(f: &mut Frame, area: Rect, app: &App) {
                 )),
                 Cell::from(Span::styled(
                     geo_str,
-                    Style::default().fg(if dim { Color::Rgb(50, 55, 70) } else { geo_color }),
+                    Style::default().fg(if dim { theme().text_faint } else { geo_color }),
                 )),
                 Cell::from(Span::styled(
                     service_str,
                     Style::default().fg(if dim {
-                        Color::Rgb(70, 80, 100)
+                        theme().text_muted
                     } else {
                         service_color
                     }),
@@ -203,7 +204,7 @@ pub fn draw_connections(f: &mut Frame, area: Rect, app: &App) {
                 )),
                 Cell::from(Span::styled(
                     local_str,
-                    Style::default().fg(Color::Rgb(75, 85, 108)),
+                    Style::default().fg(theme().text_muted),
                 )),
             ])
             .style(Style::default().bg(row_bg))
@@ -225,21 +226,21 @@ pub fn draw_connections(f: &mut Frame, area: Rect, app: &App) {
     let mut title_spans = vec![
         Span::styled(
             " Connections ",
-            Style::default().fg(Color::Rgb(160, 180, 220)).add_modifier(Modifier::BOLD),
+            Style::default().fg(theme().text).add_modifier(Modifier::BOLD),
         ),
         Span::styled(
             format!(" {} total ", total),
-            Style::default().fg(Color::Rgb(100, 120, 150)),
+            Style::default().fg(theme().text_dim),
         ),
         Span::styled(
             localhost_info.to_string(),
-            Style::default().fg(Color::Rgb(80, 160, 200)),
+            Style::default().fg(theme().info),
         ),
     ];
     if !filter_info.is_empty() {
         title_spans.push(Span::styled(
             filter_info,
-            Style::default().fg(Color::Yellow),
+            Style::default().fg(theme().key),
         ));
     }
 
@@ -254,12 +255,12 @@ pub fn draw_connections(f: &mut Frame, area: Rect, app: &App) {
             .or_else(|| conn.remote_addr.map(|ip| ip.to_string()))
             .unwrap_or_else(|| "*".to_string());
         Line::from(vec![
-            Span::styled(" \u{25B8} ", Style::default().fg(Color::Rgb(100, 200, 255)).add_modifier(Modifier::BOLD)),
-            Span::styled(conn.process_name.clone(), Style::default().fg(Color::Rgb(130, 200, 140)).add_modifier(Modifier::BOLD)),
-            Span::styled(" \u{2192} ", Style::default().fg(Color::Rgb(60, 80, 110))),
-            Span::styled(remote_str, Style::default().fg(Color::Rgb(100, 220, 255))),
-            Span::styled(" \u{2502} ", Style::default().fg(Color::Rgb(40, 55, 80))),
-            Span::styled(geo_detail, Style::default().fg(Color::Rgb(170, 200, 230))),
+            Span::styled(" \u{25B8} ", Style::default().fg(theme().accent).add_modifier(Modifier::BOLD)),
+            Span::styled(conn.process_name.clone(), Style::default().fg(theme().ok_soft).add_modifier(Modifier::BOLD)),
+            Span::styled(" \u{2192} ", Style::default().fg(theme().text_muted)),
+            Span::styled(remote_str, Style::default().fg(theme().accent)),
+            Span::styled(" \u{2502} ", Style::default().fg(theme().divider)),
+            Span::styled(geo_detail, Style::default().fg(theme().text)),
         ])
     } else {
         Line::from("")
@@ -282,8 +283,8 @@ pub fn draw_connections(f: &mut Frame, area: Rect, app: &App) {
             .title(Line::from(title_spans))
             .title_bottom(detail_line)
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Rgb(30, 50, 85)))
-            .style(Style::default().bg(Color::Rgb(12, 16, 28))),
+            .border_style(Style::default().fg(theme().border))
+            .style(Style::default().bg(theme().surface)),
     );
 
     f.render_widget(table, area);
@@ -300,7 +301,7 @@ pub fn draw_connections(f: &mut Frame, area: Rect, app: &App) {
             ScrollbarState::new(total.saturating_sub(visible_height)).position(viewport_start);
         f.render_stateful_widget(
             Scrollbar::new(ScrollbarOrientation::VerticalRight)
-                .style(Style::default().fg(Color::Rgb(40, 70, 120))),
+                .style(Style::default().fg(theme().border_strong)),
             sb_area,
             &mut sb_state,
         );
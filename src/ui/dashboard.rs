@@ -16,17 +16,18 @@ use super::widgets::traffic_chart::draw_traffic_chart;
 use super::widgets::world_map::{
     draw_world_map_dots, fade_brightness, ip_to_seed, ConnectionDot,
 };
+use crate::ui::theme::theme;
 
 /// Palette of colors for top-app bar chart entries.
-const APP_COLORS: [ratatui::style::Color; 8] = [
-    ratatui::style::Color::Rgb(50, 160, 255),  // cyan
-    ratatui::style::Color::Rgb(180, 100, 255),  // purple
-    ratatui::style::Color::Rgb(80, 200, 120),   // green
-    ratatui::style::Color::Rgb(255, 200, 80),   // gold
-    ratatui::style::Color::Rgb(255, 130, 60),   // orange
-    ratatui::style::Color::Rgb(100, 220, 255),  // light cyan
-    ratatui::style::Color::Rgb(220, 130, 200),  // pink
-    ratatui::style::Color::Rgb(170, 200, 230),  // steel
+const APP_COLORS: [(u8, u8, u8); 8] = [
+    (50, 160, 255),  // cyan
+    (180, 100, 255),  // purple
+    (80, 200, 120),   // green
+    (255, 200, 80),   // gold
+    (255, 130, 60),   // orange
+    (100, 220, 255),  // light cyan
+    (220, 130, 200),  // pink
+    (170, 200, 230),  // steel
 ];
 
 pub fn draw_dashboard(f: &mut Frame, area: Rect, app: &App) {
@@ -45,19 +46,19 @@ pub fn draw_dashboard(f: &mut Frame, area: Rect, app: &App) {
         let hint = Line::from(vec![
             Span::styled(
                 " m:Exit Map  ",
-                Style::default().fg(Color::Yellow),
+                Style::default().fg(theme().key),
             ),
             Span::styled(
                 "1-4:Time Range  ",
-                Style::default().fg(Color::Rgb(60, 80, 110)),
+                Style::default().fg(theme().text_muted),
             ),
             Span::styled(
                 "Tab:Next tab",
-                Style::default().fg(Color::Rgb(60, 80, 110)),
+                Style::default().fg(theme().text_muted),
             ),
         ]);
         f.render_widget(
-            Paragraph::new(hint).style(Style::default().bg(Color::Rgb(12, 16, 30))),
+            Paragraph::new(hint).style(Style::default().bg(theme().surface)),
             main_split[1],
         );
         return;
@@ -118,10 +119,10 @@ fn draw_health_sparkline(f: &mut Frame, area: Rect, app: &App) {
     let health = compute_health_score(active, threats, alert_count, app.firewall_manager.enabled);
 
     let (health_color, health_label) = match health {
-        80..=100 => (Color::Rgb(80, 200, 120), "Excellent"),
-        60..=79 => (Color::Rgb(100, 200, 255), "Good"),
-        40..=59 => (Color::Rgb(255, 200, 80), "Fair"),
-        _ => (Color::Rgb(255, 80, 80), "Poor"),
+        80..=100 => (theme().ok, "Excellent"),
+        60..=79 => (theme().accent, "Good"),
+        40..=59 => (theme().highlight, "Fair"),
+        _ => (theme().error, "Poor"),
     };
 
     // Health gauge bar
@@ -129,7 +130,7 @@ fn draw_health_sparkline(f: &mut Frame, area: Rect, app: &App) {
     let filled = (health as usize * gauge_w) / 100;
     let gauge_bar = format!("{}{}", "█".repeat(filled), "░".repeat(gauge_w.saturating_sub(filled)));
 
-    let dim = Style::default().fg(Color::Rgb(70, 90, 120));
+    let dim = Style::default().fg(theme().text_muted);
 
     let line1 = Line::from(vec![
         Span::styled(
@@ -143,7 +144,7 @@ fn draw_health_sparkline(f: &mut Frame, area: Rect, app: &App) {
                 .filter_map(|c| c.remote_addr)
                 .filter(|ip| !ip.is_loopback() && !ip.is_unspecified())
                 .collect::<std::collections::HashSet<_>>().len()),
-            Style::default().fg(Color::Rgb(140, 170, 210)).add_modifier(Modifier::BOLD),
+            Style::default().fg(theme().text).add_modifier(Modifier::BOLD),
         ),
     ]);
 
@@ -168,17 +169,17 @@ fn draw_health_sparkline(f: &mut Frame, area: Rect, app: &App) {
 
     let line3 = Line::from(vec![
         Span::styled("  Conns ▏", dim),
-        Span::styled(sparkline, Style::default().fg(Color::Rgb(80, 160, 220))),
+        Span::styled(sparkline, Style::default().fg(theme().info)),
     ]);
 
     let block = Block::default()
         .title(Span::styled(
             " Network Health ",
-            Style::default().fg(Color::Rgb(160, 180, 220)).add_modifier(Modifier::BOLD),
+            Style::default().fg(theme().text).add_modifier(Modifier::BOLD),
         ))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Rgb(30, 50, 85)))
-        .style(Style::default().bg(Color::Rgb(8, 12, 24)));
+        .border_style(Style::default().fg(theme().border))
+        .style(Style::default().bg(theme().bg));
 
     f.render_widget(Paragraph::new(vec![line1, line2, line3]).block(block), area);
 }
@@ -210,15 +211,15 @@ fn draw_country_map(f: &mut Frame, area: Rect, app: &App) {
     // Color for a connection based on its TCP state
     fn conn_color(state: Option<&TcpState>, is_threat: bool) -> Color {
         if is_threat {
-            return Color::Rgb(255, 60, 60); // CLR_THREAT
+            return theme().error; // CLR_THREAT
         }
         match state {
-            Some(TcpState::Established) => Color::Rgb(80, 200, 120),   // green
-            Some(TcpState::SynSent) | Some(TcpState::SynReceived) => Color::Rgb(60, 180, 255), // cyan
-            Some(TcpState::TimeWait) | Some(TcpState::FinWait1) | Some(TcpState::FinWait2) => Color::Rgb(200, 120, 255), // purple
-            Some(TcpState::CloseWait) | Some(TcpState::Closing) | Some(TcpState::LastAck) => Color::Rgb(255, 100, 60), // orange
-            Some(TcpState::Listen) => Color::Rgb(60, 180, 255),
-            _ => Color::Rgb(120, 140, 170), // gray for unknown
+            Some(TcpState::Established) => theme().ok,   // green
+            Some(TcpState::SynSent) | Some(TcpState::SynReceived) => theme().info, // cyan
+            Some(TcpState::TimeWait) | Some(TcpState::FinWait1) | Some(TcpState::FinWait2) => theme().purple, // purple
+            Some(TcpState::CloseWait) | Some(TcpState::Closing) | Some(TcpState::LastAck) => theme().error, // orange
+            Some(TcpState::Listen) => theme().info,
+            _ => theme().text_secondary, // gray for unknown
         }
     }
 
@@ -247,7 +248,7 @@ fn draw_country_map(f: &mut Frame, area: Rect, app: &App) {
         if b > 0.0 {
             dots.push(ConnectionDot {
                 country_code: code,
-                color: Color::Rgb(255, 100, 60), // closing color
+                color: theme().error, // closing color
                 brightness: b,
                 jitter_seed: ip_to_seed(ip),
                 pulse: false,
@@ -282,12 +283,12 @@ fn draw_top_countries(f: &mut Frame, area: Rect, app: &App) {
         .title(Span::styled(
             " Top Countries ",
             Style::default()
-                .fg(Color::Rgb(160, 180, 220))
+                .fg(theme().text)
                 .add_modifier(Modifier::BOLD),
         ))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Rgb(30, 50, 85)))
-        .style(Style::default().bg(Color::Rgb(8, 12, 24)));
+        .border_style(Style::default().fg(theme().border))
+        .style(Style::default().bg(theme().bg));
 
     let inner = block.inner(area);
     f.render_widget(block, area);
@@ -309,18 +310,19 @@ fn draw_top_countries(f: &mut Frame, area: Rect, app: &App) {
             0
         };
 
-        let color = COUNTRY_COLORS[i % COUNTRY_COLORS.len()];
+        let (r, g, b) = COUNTRY_COLORS[i % COUNTRY_COLORS.len()];
+        let color = theme().rgb(r, g, b);
         let bar_str: String = "█".repeat(bar_len);
 
         let line = Line::from(vec![
             Span::styled(
                 format!(" {} ", code),
-                Style::default().fg(Color::Rgb(20, 20, 30)).bg(color),
+                Style::default().fg(theme().surface_alt).bg(color),
             ),
             Span::styled(" ", Style::default()),
             Span::styled(
                 format!("{:<12}", truncate_name(name, 12)),
-                Style::default().fg(Color::Rgb(160, 175, 200)),
+                Style::default().fg(theme().text),
             ),
             Span::styled(
                 format!("{:>3} ", count),
@@ -335,7 +337,7 @@ fn draw_top_countries(f: &mut Frame, area: Rect, app: &App) {
     if sorted.is_empty() {
         let msg = Line::from(Span::styled(
             "  No geo data yet",
-            Style::default().fg(Color::Rgb(60, 75, 100)),
+            Style::default().fg(theme().text_muted),
         ));
         f.render_widget(Paragraph::new(msg), inner);
     }
@@ -353,17 +355,17 @@ fn truncate_name(s: &str, max: usize) -> String {
     }
 }
 
-const COUNTRY_COLORS: [Color; 10] = [
-    Color::Rgb(80, 200, 255),   // cyan
-    Color::Rgb(120, 220, 140),  // green
-    Color::Rgb(255, 200, 80),   // gold
-    Color::Rgb(200, 140, 255),  // lavender
-    Color::Rgb(255, 140, 100),  // coral
-    Color::Rgb(100, 220, 200),  // teal
-    Color::Rgb(255, 160, 200),  // pink
-    Color::Rgb(180, 200, 130),  // lime
-    Color::Rgb(140, 180, 255),  // periwinkle
-    Color::Rgb(220, 180, 140),  // sand
+const COUNTRY_COLORS: [(u8, u8, u8); 10] = [
+    (80, 200, 255),   // cyan
+    (120, 220, 140),  // green
+    (255, 200, 80),   // gold
+    (200, 140, 255),  // lavender
+    (255, 140, 100),  // coral
+    (100, 220, 200),  // teal
+    (255, 160, 200),  // pink
+    (180, 200, 130),  // lime
+    (140, 180, 255),  // periwinkle
+    (220, 180, 140),  // sand
 ];
 
 /// Render the top apps by bandwidth as a bar chart.
@@ -382,10 +384,13 @@ fn draw_top_apps(f: &mut Frame, area: Rect, app: &App) {
         .iter()
         .take(8)
         .enumerate()
-        .map(|(i, (name, bw))| BarEntry {
-            label: name.to_string(),
-            value: bw.download_bytes + bw.upload_bytes,
-            color: APP_COLORS[i % APP_COLORS.len()],
+        .map(|(i, (name, bw))| {
+            let (r, g, b) = APP_COLORS[i % APP_COLORS.len()];
+            BarEntry {
+                label: name.to_string(),
+                value: bw.download_bytes + bw.upload_bytes,
+                color: theme().rgb(r, g, b),
+            }
         })
         .collect();
 
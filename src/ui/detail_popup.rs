@@ -8,8 +8,9 @@ use ratatui::Frame;
 
 use crate::app::App;
 use crate::network::dns::port_service_name;
-use crate::types::{DetailKind, FirewallAppAction};
+use crate::types::{DetailKind, FirewallAppAction, ServerDetail};
 use crate::utils::{format_bytes, format_speed};
+use crate::ui::theme::theme;
 
 /// Render the detail popup overlay if one is active.
 pub fn draw_detail_popup(f: &mut Frame, app: &App) {
@@ -36,10 +37,10 @@ pub fn draw_detail_popup(f: &mut Frame, app: &App) {
             f.render_widget(Clear, area);
             draw_firewall_app_detail(f, area, detail, app);
         }
-        DetailKind::Server { .. } => {
+        DetailKind::Server(server) => {
             let area = centered_rect(75, 85, f.area());
             f.render_widget(Clear, area);
-            draw_server_detail(f, area, detail);
+            draw_server_detail(f, area, server);
         }
     }
 }
@@ -61,23 +62,27 @@ fn draw_connection_detail(f: &mut Frame, area: Rect, conn: &crate::types::Connec
         .unwrap_or_else(|| "*".to_string());
 
     let state_str = conn.state.as_ref().map(|s| s.label().to_string()).unwrap_or_else(|| "\u{2014}".to_string());
-    let state_color = conn.state.as_ref().map(|s| s.color()).unwrap_or(Color::Gray);
+    let state_color = conn.state.as_ref().map(|s| s.color()).unwrap_or(theme().text_dim);
     let country_str = geo.map(|g| format!("{} {} ({})", g.flag, g.name, g.code)).unwrap_or_else(|| "Local / Private".to_string());
     let remote_addr_str = conn.remote_addr
         .map(|ip| format!("{}:{}", ip, conn.remote_port.unwrap_or(0)))
         .unwrap_or_else(|| "\u{2014}".to_string());
 
     let mut lines = header_lines(" Connection Detail ");
-    lines.push(row("Protocol",    conn.proto.label().to_string(),                   Color::Rgb(100, 220, 255)));
-    lines.push(row("Process",     conn.process_name.clone(),                        Color::Rgb(130, 200, 140)));
-    lines.push(row("PID",         conn.pid.to_string(),                             Color::Rgb(120, 130, 160)));
-    lines.push(row("Direction",   if conn.is_outbound() { "Outbound \u{2192}" } else { "Inbound \u{2190}" }.to_string(), Color::Rgb(200, 180, 100)));
-    lines.push(row("Local",       format!("{}:{}", conn.local_addr, conn.local_port), Color::Rgb(150, 160, 190)));
-    lines.push(row("Remote",      remote_addr_str,                                  Color::Rgb(170, 185, 210)));
-    lines.push(row("DNS Name",    remote_host,                                      Color::Rgb(100, 220, 255)));
-    lines.push(row("Service",     service,                                          Color::Rgb(200, 180, 80)));
+    lines.push(row("Protocol",    conn.proto.label().to_string(),                   theme().accent));
+    lines.push(row("Process",     conn.process_name.clone(),                        theme().ok_soft));
+    lines.push(row("PID",         conn.pid.to_string(),                             theme().text_dim));
+    lines.push(row("Direction",   if conn.is_outbound() { "Outbound \u{2192}" } else { "Inbound \u{2190}" }.to_string(), theme().gold));
+    lines.push(row("Local",       format!("{}:{}", conn.local_addr, conn.local_port), theme().text_secondary));
+    lines.push(row("Remote",      remote_addr_str,                                  theme().text));
+    lines.push(row("DNS Name",    remote_host,                                      theme().accent));
+    lines.push(row("Service",     service,                                          theme().gold));
     lines.push(row("State",       state_str,                                        state_color));
-    lines.push(row("Country",     country_str,                                      Color::Rgb(170, 200, 230)));
+    lines.push(row("Country",     country_str,                                      theme().text));
+    if let Some(guess) = conn.remote_addr.and_then(|ip| app.os_fingerprints.get(&ip)) {
+        lines.push(row("Remote OS",   guess.label(),                                    theme().purple));
+        lines.push(row("Distance",    format!("{} hops", guess.distance),               theme().text_secondary));
+    }
     lines.push(Line::from(""));
     lines.push(dismiss_line());
 
@@ -91,49 +96,95 @@ fn draw_alert_detail(f: &mut Frame, area: Rect, alert: &crate::types::Alert) {
     let sev_color = severity.color();
 
     let mut lines = header_lines(" Alert Detail ");
-    lines.push(row("Time",     alert.timestamp.format("%H:%M:%S").to_string(), Color::Rgb(120, 130, 160)));
+    lines.push(row("Time",     alert.timestamp.format("%H:%M:%S").to_string(), theme().text_dim));
     lines.push(row("Severity", severity.label().to_string(),                   sev_color));
-    lines.push(row("Type",     alert.kind.label().to_string(),                 Color::Rgb(180, 190, 220)));
+    lines.push(row("Type",     alert.kind.label().to_string(),                 theme().text));
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
         format!("  {}", alert.kind.description()),
-        Style::default().fg(Color::Rgb(200, 210, 230)).add_modifier(Modifier::BOLD),
+        Style::default().fg(theme().text_bright).add_modifier(Modifier::BOLD),
     )));
     lines.push(Line::from(""));
 
     match &alert.kind {
         crate::types::AlertKind::SuspiciousHost { process_name, ip, reason } => {
-            lines.push(row("Process", process_name.clone(), Color::Rgb(130, 200, 140)));
-            lines.push(row("IP",      ip.to_string(),       Color::Rgb(255, 120, 80)));
-            lines.push(row("Reason",  reason.clone(),       Color::Rgb(255, 180, 80)));
+            lines.push(row("Process", process_name.clone(), theme().ok_soft));
+            lines.push(row("IP",      ip.to_string(),       theme().caution));
+            lines.push(row("Reason",  reason.clone(),       theme().highlight));
         }
         crate::types::AlertKind::NewAppFirstConnection { process_name, remote } => {
-            lines.push(row("Process", process_name.clone(), Color::Rgb(130, 200, 140)));
-            lines.push(row("Remote",  remote.clone(),       Color::Rgb(100, 220, 255)));
+            lines.push(row("Process", process_name.clone(), theme().ok_soft));
+            lines.push(row("Remote",  remote.clone(),       theme().accent));
         }
         crate::types::AlertKind::BandwidthSpike { direction, speed_bps, threshold_bps } => {
-            lines.push(row("Direction", direction.clone(),           Color::Rgb(200, 180, 100)));
-            lines.push(row("Speed",     format_speed(*speed_bps),    Color::Rgb(255, 120, 80)));
-            lines.push(row("Threshold", format_speed(*threshold_bps), Color::Rgb(150, 160, 180)));
+            lines.push(row("Direction", direction.clone(),           theme().gold));
+            lines.push(row("Speed",     format_speed(*speed_bps),    theme().caution));
+            lines.push(row("Threshold", format_speed(*threshold_bps), theme().text_secondary));
+        }
+        crate::types::AlertKind::UnknownDevice { ip, mac, hostname } => {
+            lines.push(row("IP",       ip.to_string(),                                   theme().accent));
+            lines.push(row("MAC",      mac.clone(),                                      theme().text_secondary));
+            lines.push(row("Hostname", hostname.clone().unwrap_or_else(|| "unknown".to_string()), theme().olive));
+            lines.push(row("Allowlist", "not trusted on this network".to_string(),       theme().caution));
         }
-        crate::types::AlertKind::NewDevice { ip, mac, hostname } => {
-            lines.push(row("IP",       ip.to_string(),                                   Color::Rgb(100, 220, 255)));
-            lines.push(row("MAC",      mac.clone(),                                      Color::Rgb(150, 160, 180)));
-            lines.push(row("Hostname", hostname.clone().unwrap_or_else(|| "unknown".to_string()), Color::Rgb(180, 190, 140)));
+        crate::types::AlertKind::TrustedDeviceReturned { ip, mac, name } => {
+            lines.push(row("Device", name.clone(),   theme().ok_soft));
+            lines.push(row("IP",     ip.to_string(), theme().accent));
+            lines.push(row("MAC",    mac.clone(),    theme().text_secondary));
         }
         crate::types::AlertKind::ArpAnomaly { ip, expected_mac, actual_mac } => {
-            lines.push(row("IP",           ip.to_string(),        Color::Rgb(100, 220, 255)));
-            lines.push(row("Expected MAC", expected_mac.clone(),  Color::Rgb(150, 160, 180)));
-            lines.push(row("Actual MAC",   actual_mac.clone(),    Color::Rgb(255, 120, 80)));
+            lines.push(row("IP",           ip.to_string(),        theme().accent));
+            lines.push(row("Expected MAC", expected_mac.clone(),  theme().text_secondary));
+            lines.push(row("Actual MAC",   actual_mac.clone(),    theme().caution));
         }
         crate::types::AlertKind::BandwidthOverage { used_bytes, limit_bytes } => {
-            lines.push(row("Used",  format_bytes(*used_bytes),  Color::Rgb(255, 120, 80)));
-            lines.push(row("Limit", format_bytes(*limit_bytes), Color::Rgb(150, 160, 180)));
+            lines.push(row("Used",  format_bytes(*used_bytes),  theme().caution));
+            lines.push(row("Limit", format_bytes(*limit_bytes), theme().text_secondary));
         }
         crate::types::AlertKind::TrafficAnomaly { process_name, current_bytes, baseline_bytes } => {
-            lines.push(row("Process",  process_name.clone(),        Color::Rgb(130, 200, 140)));
-            lines.push(row("Current",  format_bytes(*current_bytes), Color::Rgb(255, 180, 80)));
-            lines.push(row("Baseline", format_bytes(*baseline_bytes), Color::Rgb(150, 160, 180)));
+            lines.push(row("Process",  process_name.clone(),        theme().ok_soft));
+            lines.push(row("Current",  format_bytes(*current_bytes), theme().highlight));
+            lines.push(row("Baseline", format_bytes(*baseline_bytes), theme().text_secondary));
+        }
+        crate::types::AlertKind::NewListener { name, port, proto, exe } => {
+            lines.push(row("Service",    name.clone(),                 theme().ok_soft));
+            lines.push(row("Port",       format!("{} {}", proto, port), theme().accent));
+            lines.push(row("Executable", exe.clone(),                  theme().text_secondary));
+        }
+        crate::types::AlertKind::ListenerChanged { name, port, proto, what, old, new } => {
+            lines.push(row("Service", name.clone(),                 theme().ok_soft));
+            lines.push(row("Port",    format!("{} {}", proto, port), theme().accent));
+            lines.push(row("Changed", what.clone(),                 theme().gold));
+            lines.push(row("Before",  old.clone(),                  theme().text_secondary));
+            lines.push(row("After",   new.clone(),                  theme().highlight));
+        }
+        crate::types::AlertKind::HealthCheckFailed { name, port, check, error } => {
+            lines.push(row("Service", name.clone(),          theme().ok_soft));
+            lines.push(row("Port",    format!("{}", port),   theme().accent));
+            lines.push(row("Check",   check.clone(),         theme().text_secondary));
+            lines.push(row("Error",   error.clone(),         theme().caution));
+        }
+        crate::types::AlertKind::DeviceOffline { name, ip, mac, minutes } => {
+            lines.push(row("Device",  name.clone(),                      theme().ok_soft));
+            lines.push(row("IP",      ip.to_string(),                    theme().accent));
+            lines.push(row("MAC",     mac.clone(),                       theme().text_secondary));
+            lines.push(row("Offline", format!("{}h {:02}m", minutes / 60, minutes % 60), theme().highlight));
+        }
+        crate::types::AlertKind::NewPortMapping { external_port, protocol, internal_client, internal_port, description } => {
+            lines.push(row("External",    format!("{} {}", protocol, external_port), theme().caution));
+            lines.push(row("Forwards to", format!("{}:{}", internal_client, internal_port), theme().accent));
+            lines.push(row("Description", if description.is_empty() { "(none)".to_string() } else { description.clone() }, theme().text_secondary));
+        }
+        crate::types::AlertKind::RogueDhcpServer { server, mac, routers, dns } => {
+            let list = |addrs: &[std::net::IpAddr]| if addrs.is_empty() {
+                "\u{2014}".to_string()
+            } else {
+                addrs.iter().map(|a| a.to_string()).collect::<Vec<_>>().join(", ")
+            };
+            lines.push(row("Server", server.to_string(),                              theme().caution));
+            lines.push(row("MAC",    mac.clone().unwrap_or_else(|| "unknown".to_string()), theme().text_secondary));
+            lines.push(row("Router", list(routers),                                   theme().accent));
+            lines.push(row("DNS",    list(dns),                                       theme().accent));
         }
         _ => {}
     }
@@ -150,7 +201,7 @@ fn draw_device_detail(f: &mut Frame, area: Rect, device: &crate::types::LanDevic
         .map(|gw| device.ip == std::net::IpAddr::V4(gw))
         .unwrap_or(false);
 
-    let status_color = if device.is_online { Color::Rgb(80, 200, 120) } else { Color::Rgb(100, 100, 120) };
+    let status_color = if device.is_online { theme().ok } else { theme().text_dim };
     let status_str = if device.is_online { "\u{25cf} Online" } else { "\u{25cb} Offline" };
 
     let mut lines = header_lines(" Device Detail ");
@@ -158,15 +209,28 @@ fn draw_device_detail(f: &mut Frame, area: Rect, device: &crate::types::LanDevic
     // ─── Identity ───
     lines.push(row("Status",     status_str.to_string(), status_color));
     lines.push(row("Role",       if is_gateway { "Gateway / Router" } else { "Host" }.to_string(),
-        if is_gateway { Color::Rgb(255, 200, 80) } else { Color::Rgb(150, 160, 190) }));
-    lines.push(row("IP Address", device.ip.to_string(), Color::Rgb(100, 180, 255)));
+        if is_gateway { theme().highlight } else { theme().text_secondary }));
+    let class = crate::network::device_type::classify(device, is_gateway);
+    if class.kind == crate::types::DeviceType::Unknown {
+        lines.push(row("Type", "Unknown (not enough evidence)".to_string(), theme().text_muted));
+    } else {
+        lines.push(row("Type", format!("{} {}  ({}% confidence)", class.kind.icon(), class.kind.label(), class.confidence),
+            class.kind.color()));
+        for reason in class.reasons.iter().take(4) {
+            lines.push(Line::from(vec![
+                Span::styled("               ", Style::default()),
+                Span::styled(format!("\u{2022} {}", reason), Style::default().fg(theme().text_dim)),
+            ]));
+        }
+    }
+    lines.push(row("IP Address", device.ip.to_string(), theme().info));
     // Show each hostname on its own line for readability
     if let Some(ref names) = device.hostname {
         let parts: Vec<&str> = names.split(", ").collect();
         if parts.len() == 1 {
-            lines.push(row("Hostname", parts[0].to_string(), Color::Rgb(130, 200, 140)));
+            lines.push(row("Hostname", parts[0].to_string(), theme().ok_soft));
         } else {
-            lines.push(row("Hostnames", format!("({} names found)", parts.len()), Color::Rgb(130, 200, 140)));
+            lines.push(row("Hostnames", format!("({} names found)", parts.len()), theme().ok_soft));
             for name in &parts {
                 lines.push(Line::from(vec![
                     Span::styled(
@@ -175,32 +239,42 @@ fn draw_device_detail(f: &mut Frame, area: Rect, device: &crate::types::LanDevic
                     ),
                     Span::styled(
                         format!("\u{2022} {}", name.trim()),
-                        Style::default().fg(Color::Rgb(130, 200, 140)),
+                        Style::default().fg(theme().ok_soft),
                     ),
                 ]));
             }
         }
     } else {
-        lines.push(row("Hostname", "\u{2014}".to_string(), Color::Rgb(90, 100, 120)));
+        lines.push(row("Hostname", "\u{2014}".to_string(), theme().text_muted));
     }
     if let Some(ref custom) = device.custom_name {
-        lines.push(row("Custom Name", custom.clone(), Color::Rgb(255, 220, 100)));
+        lines.push(row("Custom Name", custom.clone(), theme().highlight));
     }
 
     // ─── Hardware ───
     lines.push(section_divider("Hardware"));
-    lines.push(row("MAC Address", device.mac.clone(), Color::Rgb(150, 160, 180)));
-    lines.push(row("Vendor",     device.vendor.clone().unwrap_or_else(|| "Unknown".to_string()), Color::Rgb(180, 170, 140)));
+    lines.push(row("MAC Address", device.mac.clone(), theme().text_secondary));
+    lines.push(row("Vendor",     device.vendor.clone().unwrap_or_else(|| "Unknown".to_string()), theme().gold));
 
     // Detect locally administered / random MAC
     let first_octet = device.mac.split(':').next()
         .and_then(|s| u8::from_str_radix(s, 16).ok())
         .unwrap_or(0);
     if first_octet & 0x02 != 0 {
-        lines.push(row("MAC Type", "Locally Administered (Randomized)".to_string(), Color::Rgb(200, 160, 100)));
+        lines.push(row("MAC Type", "Locally Administered (Randomized)".to_string(), theme().gold));
     } else {
-        lines.push(row("MAC Type", "Globally Unique (Manufacturer)".to_string(), Color::Rgb(120, 160, 140)));
+        lines.push(row("MAC Type", "Globally Unique (Manufacturer)".to_string(), theme().text_secondary));
     }
+    let has_password = app.network_scanner.inventory.get(&device.mac)
+        .is_some_and(|r| r.wol_password.is_some());
+    let wol = if app.network_scanner.wake_watch.is_pending(&device.mac) {
+        "Magic packet sent, waiting for the device"
+    } else if has_password {
+        "Available (SecureOn password set)"
+    } else {
+        "Available (press w on the Devices tab)"
+    };
+    lines.push(row("Wake-on-LAN", wol.to_string(), theme().text_secondary));
 
     // ─── Open Ports ───
     if !device.open_ports.is_empty() {
@@ -213,17 +287,17 @@ fn draw_device_detail(f: &mut Frame, area: Rect, device: &crate::types::LanDevic
                 lines.push(Line::from(vec![
                     Span::styled(
                         format!("  {:<8}", port_num),
-                        Style::default().fg(Color::Rgb(180, 200, 120)).add_modifier(Modifier::BOLD),
+                        Style::default().fg(theme().olive).add_modifier(Modifier::BOLD),
                     ),
                     Span::styled(
                         svc_name.to_string(),
-                        Style::default().fg(Color::Rgb(120, 160, 180)),
+                        Style::default().fg(theme().text_secondary),
                     ),
                 ]));
             } else {
                 lines.push(Line::from(Span::styled(
                     format!("  {}", port_entry),
-                    Style::default().fg(Color::Rgb(180, 200, 120)).add_modifier(Modifier::BOLD),
+                    Style::default().fg(theme().olive).add_modifier(Modifier::BOLD),
                 )));
             }
         }
@@ -231,34 +305,75 @@ fn draw_device_detail(f: &mut Frame, area: Rect, device: &crate::types::LanDevic
         lines.push(section_divider("Open Ports"));
         lines.push(Line::from(Span::styled(
             "  No open ports detected",
-            Style::default().fg(Color::Rgb(70, 80, 100)),
+            Style::default().fg(theme().text_muted),
         )));
     }
 
+    // ─── Services ───
+    if !device.services.is_empty() {
+        lines.push(section_divider("Services"));
+        for svc in &device.services {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {:<8}", svc.port),
+                    Style::default().fg(theme().olive).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    svc.product.clone(),
+                    Style::default().fg(theme().accent),
+                ),
+                Span::styled(
+                    svc.version.as_ref().map(|v| format!(" {}", v)).unwrap_or_default(),
+                    Style::default().fg(theme().text_secondary),
+                ),
+            ]));
+        }
+    }
+
     // ─── Bandwidth ───
     lines.push(section_divider("Bandwidth"));
     lines.push(row("Received", if device.bytes_received > 0 {
         format!("{} ({}/s)", format_bytes(device.bytes_received), format_speed(device.speed_received))
     } else {
         "\u{2014}".to_string()
-    }, if device.speed_received > 0.0 { Color::Rgb(80, 200, 255) } else { Color::Rgb(90, 100, 120) }));
+    }, if device.speed_received > 0.0 { theme().info } else { theme().text_muted }));
 
     lines.push(row("Sent", if device.bytes_sent > 0 {
         format!("{} ({}/s)", format_bytes(device.bytes_sent), format_speed(device.speed_sent))
     } else {
         "\u{2014}".to_string()
-    }, if device.speed_sent > 0.0 { Color::Rgb(255, 180, 100) } else { Color::Rgb(90, 100, 120) }));
+    }, if device.speed_sent > 0.0 { theme().highlight } else { theme().text_muted }));
 
     lines.push(row("Total", if device.bytes_received + device.bytes_sent > 0 {
         format_bytes(device.bytes_received + device.bytes_sent)
     } else {
         "\u{2014}".to_string()
-    }, Color::Rgb(170, 185, 210)));
+    }, theme().text));
 
     // ─── Timing ───
     lines.push(section_divider("Timing"));
-    lines.push(row("First Seen", device.first_seen.format("%H:%M:%S").to_string(), Color::Rgb(120, 130, 160)));
-    lines.push(row("Last Seen",  device.last_seen.format("%H:%M:%S").to_string(),  Color::Rgb(120, 130, 160)));
+    lines.push(row("First Seen", device.first_seen.format("%Y-%m-%d %H:%M:%S").to_string(), theme().text_dim));
+    lines.push(row("Last Seen",  device.last_seen.format("%Y-%m-%d %H:%M:%S").to_string(),  theme().text_dim));
+
+    // ─── Inventory: what earlier sessions recorded for this MAC ───
+    if let Some(rec) = app.network_scanner.inventory.get(&device.mac) {
+        if rec.ips.len() > 1 {
+            let ips: Vec<String> = rec.ips.iter().rev().map(|ip| ip.to_string()).collect();
+            lines.push(row("Known IPs", ips.join(", "), theme().info));
+        }
+        if rec.hostnames.len() > 1 {
+            lines.push(row("Known Names", rec.hostnames.join(", "), theme().ok_soft));
+        }
+        if !rec.networks.is_empty() {
+            lines.push(row("Networks", rec.networks.join(", "), theme().text_secondary));
+        }
+
+        // ─── Presence ───
+        lines.push(section_divider("Presence"));
+        let now = chrono::Local::now();
+        lines.push(presence_bar("Last 24h", &rec.presence, now - chrono::Duration::hours(24), now, 48));
+        lines.push(presence_bar("Last 7 days", &rec.presence, now - chrono::Duration::days(7), now, 56));
+    }
 
     // ─── Discovery Details ───
     lines.push(section_divider("Discovery Methods"));
@@ -287,35 +402,144 @@ fn draw_device_detail(f: &mut Frame, area: Rect, device: &crate::types::LanDevic
                 lines.push(Line::from(vec![
                     Span::styled(
                         format!("  {:<22}", method_desc),
-                        Style::default().fg(Color::Rgb(100, 140, 180)).add_modifier(Modifier::BOLD),
+                        Style::default().fg(theme().text_dim).add_modifier(Modifier::BOLD),
                     ),
                     Span::styled(
                         value.to_string(),
-                        Style::default().fg(Color::Rgb(180, 190, 200)),
+                        Style::default().fg(theme().text),
                     ),
                 ]));
             } else {
                 lines.push(Line::from(Span::styled(
                     format!("  {}", entry),
-                    Style::default().fg(Color::Rgb(140, 160, 130)),
+                    Style::default().fg(theme().text_secondary),
                 )));
             }
         }
     } else {
         lines.push(Line::from(Span::styled(
             "  No discovery data yet (scan in progress or pending)",
-            Style::default().fg(Color::Rgb(70, 80, 100)),
+            Style::default().fg(theme().text_muted),
         )));
     }
 
     // ─── DHCP Info ───
-    if let std::net::IpAddr::V4(v4) = device.ip {
-        if let Ok(cache) = app.network_scanner.dhcp_hostnames.lock() {
-            if let Some(dhcp_name) = cache.get(&v4) {
-                lines.push(section_divider("DHCP"));
-                lines.push(row("DHCP Hostname", dhcp_name.clone(), Color::Rgb(180, 140, 255)));
+    let dhcp_name = match device.ip {
+        std::net::IpAddr::V4(v4) => app.network_scanner.dhcp_hostnames.lock().ok()
+            .and_then(|cache| cache.get(&v4).cloned()),
+        _ => None,
+    };
+    if dhcp_name.is_some() || device.dhcp.is_some() {
+        lines.push(section_divider("DHCP"));
+        if let Some(name) = dhcp_name {
+            lines.push(row("DHCP Hostname", name, theme().purple));
+        }
+        if let Some(ref fp) = device.dhcp {
+            if let Some(ref os) = fp.os {
+                lines.push(row("OS", os.clone(), theme().purple));
+            }
+            if let Some(ref family) = fp.family {
+                lines.push(row("Family", family.clone(), theme().purple));
+            }
+            if let Some(ref class) = fp.vendor_class {
+                lines.push(row("Vendor Class", class.clone(), theme().text_bright));
+            }
+            if !fp.params.is_empty() {
+                lines.push(row("Param List", fp.params.clone(), theme().text_secondary));
+            }
+            if let Some(size) = fp.max_size {
+                lines.push(row("Max Msg Size", size.to_string(), theme().text_secondary));
+            }
+        }
+    }
+
+    // ─── DNS-SD ───
+    let advertised = app.network_scanner.dnssd.lock().map(|c| c.services()).unwrap_or_default();
+    let advertised = crate::network::dnssd::services_for(&advertised, device.ip);
+    if !advertised.is_empty() {
+        lines.push(section_divider("Advertised Services (mDNS)"));
+        for svc in advertised {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {:<16}", svc.label()), Style::default().fg(theme().text_dim)),
+                Span::styled(
+                    format!("{} — {}:{}", svc.instance, svc.host, svc.port),
+                    Style::default().fg(theme().accent).add_modifier(Modifier::BOLD),
+                ),
+            ]));
+            if !svc.txt.is_empty() {
+                lines.push(Line::from(Span::styled(
+                    format!("    {}", svc.txt.join("  ")),
+                    Style::default().fg(theme().text_secondary),
+                )));
+            }
+        }
+    }
+
+    // ─── SNMP ───
+    if let (std::net::IpAddr::V4(v4), Ok(agents)) = (device.ip, app.network_scanner.snmp.lock()) {
+        if let Some(info) = agents.get(&v4) {
+            lines.push(section_divider("SNMP"));
+            lines.push(row("Agent", format!("{} community \"{}\"", info.version.label(), info.community),
+                theme().text_secondary));
+            if let Some(ref descr) = info.descr {
+                lines.push(row("Description", descr.lines().next().unwrap_or_default().to_string(), theme().text_bright));
+            }
+            if let Some(up) = info.uptime {
+                let secs = up.as_secs();
+                lines.push(row("Uptime", format!("{}d {:02}h {:02}m", secs / 86_400, secs % 86_400 / 3600, secs % 3600 / 60),
+                    theme().ok_soft));
+            }
+            if let Some(ref contact) = info.contact {
+                lines.push(row("Contact", contact.clone(), theme().text));
+            }
+            if let Some(ref location) = info.location {
+                lines.push(row("Location", location.clone(), theme().text));
+            }
+            for iface in info.active_interfaces().take(16) {
+                let status = if iface.up { "\u{25cf}" } else { "\u{25cb}" };
+                let speed = if iface.speed_mbps >= 1000 {
+                    format!("{}G", iface.speed_mbps / 1000)
+                } else {
+                    format!("{}M", iface.speed_mbps)
+                };
+                let traffic = match (iface.in_rate, iface.out_rate) {
+                    (Some(rx), Some(tx)) => format!("\u{2193}{} \u{2191}{}", format_speed(rx), format_speed(tx)),
+                    _ => format!("\u{2193}{} \u{2191}{}", format_bytes(iface.in_octets), format_bytes(iface.out_octets)),
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  {} ", status), Style::default().fg(
+                        if iface.up { theme().ok } else { theme().text_dim })),
+                    Span::styled(format!("{:<12}", iface.name), Style::default().fg(theme().text)),
+                    Span::styled(format!("{:>6}  ", speed), Style::default().fg(theme().text_dim)),
+                    Span::styled(traffic, Style::default().fg(theme().info)),
+                    Span::styled(iface.alias.as_deref().map(|a| format!("  {}", a)).unwrap_or_default(),
+                        Style::default().fg(theme().text_muted)),
+                ]));
+            }
+            for n in &info.neighbors {
+                let remote = n.mgmt_ip.map(|ip| format!(" ({})", ip)).unwrap_or_default();
+                lines.push(row("LLDP", format!("{} \u{2192} {} {}{}", n.local_port, n.label(), n.port_id, remote),
+                    theme().highlight));
             }
         }
+        if let Some((agent_ip, agent, n)) = crate::network::snmp::uplink_of(&agents, device.ip, &device.mac) {
+            if !agents.contains_key(&v4) {
+                lines.push(section_divider("SNMP"));
+            }
+            let switch = agent.name.clone().unwrap_or_else(|| agent_ip.to_string());
+            lines.push(row("Uplink", format!("{} port {}", switch, n.local_port), theme().highlight));
+        }
+    }
+
+    // ─── TCP/IP Fingerprint ───
+    if let Some(guess) = app.os_fingerprints.get(&device.ip) {
+        lines.push(section_divider("TCP/IP Fingerprint"));
+        lines.push(row("OS", guess.label(), theme().purple));
+        lines.push(row("Match", if guess.exact { "Signature" } else { "Initial TTL only" }.to_string(),
+            theme().text_secondary));
+        lines.push(row("Distance", format!("{} hops", guess.distance), theme().text_secondary));
+        lines.push(row("From", if guess.from_syn_ack { "SYN-ACK" } else { "SYN" }.to_string(), theme().text_secondary));
+        lines.push(row("Signature", guess.signature.clone(), theme().text_dim));
     }
 
     lines.push(Line::from(""));
@@ -336,11 +560,11 @@ fn draw_firewall_app_detail(f: &mut Frame, area: Rect, detail: &crate::types::Fi
         None => "ALLOWED",
     };
     let action_color = match &detail.current_action {
-        Some(FirewallAppAction::Allow) => Color::Rgb(80, 200, 255),
-        Some(FirewallAppAction::Deny) => Color::Rgb(255, 80, 80),
-        Some(FirewallAppAction::Drop) => Color::Rgb(255, 140, 40),
-        None if detail.is_blocked => Color::Rgb(255, 80, 80),
-        None => Color::Rgb(80, 200, 120),
+        Some(FirewallAppAction::Allow) => theme().info,
+        Some(FirewallAppAction::Deny) => theme().error,
+        Some(FirewallAppAction::Drop) => theme().caution,
+        None if detail.is_blocked => theme().error,
+        None => theme().ok,
     };
 
     let conn_str = if detail.conn_count > 0 {
@@ -348,10 +572,10 @@ fn draw_firewall_app_detail(f: &mut Frame, area: Rect, detail: &crate::types::Fi
     } else {
         "idle".to_string()
     };
-    let conn_color = if detail.conn_count > 0 { Color::Rgb(80, 200, 120) } else { Color::Rgb(100, 110, 130) };
+    let conn_color = if detail.conn_count > 0 { theme().ok } else { theme().text_dim };
 
     let mut lines = header_lines(" App Detail & Actions ");
-    lines.push(row("Application",  detail.app_name.clone(),           Color::Rgb(130, 200, 140)));
+    lines.push(row("Application",  detail.app_name.clone(),           theme().ok_soft));
     lines.push(row("Status",       action_str.to_string(),            action_color));
     lines.push(row("Connections",  conn_str,                          conn_color));
     lines.push(Line::from(""));
@@ -359,29 +583,29 @@ fn draw_firewall_app_detail(f: &mut Frame, area: Rect, detail: &crate::types::Fi
     // Bandwidth section
     lines.push(Line::from(Span::styled(
         "  \u{2500}\u{2500}\u{2500} Bandwidth \u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}",
-        Style::default().fg(Color::Rgb(40, 55, 80)),
+        Style::default().fg(theme().divider),
     )));
-    lines.push(row("Downloaded",   format_bytes(detail.download_bytes),  Color::Rgb(80, 180, 255)));
-    lines.push(row("Uploaded",     format_bytes(detail.upload_bytes),    Color::Rgb(180, 120, 255)));
-    lines.push(row("Total",        format_bytes(detail.download_bytes + detail.upload_bytes), Color::Rgb(170, 185, 210)));
-    lines.push(row("Speed \u{2193}",      format_speed(detail.current_down_speed),  Color::Rgb(80, 200, 160)));
-    lines.push(row("Speed \u{2191}",      format_speed(detail.current_up_speed),    Color::Rgb(200, 140, 255)));
-    lines.push(row("Peak \u{2193}",       format_speed(detail.peak_down_speed),     Color::Rgb(80, 180, 255)));
-    lines.push(row("Peak \u{2191}",       format_speed(detail.peak_up_speed),       Color::Rgb(180, 120, 255)));
-    lines.push(row("Last Seen",    detail.last_seen.clone(),             Color::Rgb(120, 130, 160)));
+    lines.push(row("Downloaded",   format_bytes(detail.download_bytes),  theme().info));
+    lines.push(row("Uploaded",     format_bytes(detail.upload_bytes),    theme().purple));
+    lines.push(row("Total",        format_bytes(detail.download_bytes + detail.upload_bytes), theme().text));
+    lines.push(row("Speed \u{2193}",      format_speed(detail.current_down_speed),  theme().teal));
+    lines.push(row("Speed \u{2191}",      format_speed(detail.current_up_speed),    theme().purple));
+    lines.push(row("Peak \u{2193}",       format_speed(detail.peak_down_speed),     theme().info));
+    lines.push(row("Peak \u{2191}",       format_speed(detail.peak_up_speed),       theme().purple));
+    lines.push(row("Last Seen",    detail.last_seen.clone(),             theme().text_dim));
     lines.push(Line::from(""));
 
     // Action section
     lines.push(Line::from(Span::styled(
         "  \u{2500}\u{2500}\u{2500} Actions \u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}",
-        Style::default().fg(Color::Rgb(40, 55, 80)),
+        Style::default().fg(theme().divider),
     )));
 
     let options: [(usize, &str, Color); 4] = [
-        (0, "Allow  - permit all traffic", Color::Rgb(80, 200, 120)),
-        (1, "Deny   - block and refuse connections", Color::Rgb(255, 80, 80)),
-        (2, "Drop   - silently drop all traffic", Color::Rgb(255, 140, 40)),
-        (3, "Back   - close without changes", Color::Rgb(140, 150, 170)),
+        (0, "Allow  - permit all traffic", theme().ok),
+        (1, "Deny   - block and refuse connections", theme().error),
+        (2, "Drop   - silently drop all traffic", theme().caution),
+        (3, "Back   - close without changes", theme().text_secondary),
     ];
 
     for (idx, label, color) in &options {
@@ -397,14 +621,14 @@ fn draw_firewall_app_detail(f: &mut Frame, area: Rect, detail: &crate::types::Fi
         let suffix = if is_current { " \u{25cf}" } else { "" };
 
         let mut spans = vec![
-            Span::styled(prefix, Style::default().fg(Color::Rgb(255, 200, 80))),
+            Span::styled(prefix, Style::default().fg(theme().highlight)),
             Span::styled(
                 label.to_string(),
                 Style::default().fg(*color).add_modifier(if is_sel { Modifier::BOLD } else { Modifier::empty() }),
             ),
         ];
         if !suffix.is_empty() {
-            spans.push(Span::styled(suffix, Style::default().fg(Color::Rgb(80, 200, 120))));
+            spans.push(Span::styled(suffix, Style::default().fg(theme().ok)));
         }
         lines.push(Line::from(spans));
     }
@@ -412,7 +636,7 @@ fn draw_firewall_app_detail(f: &mut Frame, area: Rect, detail: &crate::types::Fi
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
         "  [ \u{2191}\u{2193} select | Enter apply | Esc close ]",
-        Style::default().fg(Color::Rgb(65, 80, 110)).add_modifier(Modifier::ITALIC),
+        Style::default().fg(theme().text_muted).add_modifier(Modifier::ITALIC),
     )));
 
     render_popup(f, area, lines);
@@ -420,23 +644,24 @@ fn draw_firewall_app_detail(f: &mut Frame, area: Rect, detail: &crate::types::Fi
 
 // ─── Server detail ────────────────────────────────────────────────────────────
 
-fn draw_server_detail(f: &mut Frame, area: Rect, detail: &DetailKind) {
-    let DetailKind::Server {
+fn draw_server_detail(f: &mut Frame, area: Rect, detail: &ServerDetail) {
+    let ServerDetail {
         kind_label, kind_icon, category, port, proto, bind_addr,
         pid, process_name, exe_path, cmdline, product_name, company_name,
         version, http_title,
         banner, response_headers, active_connections, first_seen,
         is_responsive, tls_detected, category_color,
-        detected_techs,
-    } = detail else { return };
+        detected_techs, protocol_details,
+        exposure, advisories, health,
+    } = detail;
 
-    let cat_color = Color::Rgb(category_color.0, category_color.1, category_color.2);
+    let cat_color = theme().rgb(category_color.0, category_color.1, category_color.2);
 
     let mut lines = vec![
         Line::from(vec![
             Span::styled(
                 format!("  {} {} ", kind_icon, kind_label),
-                Style::default().fg(Color::Rgb(200, 220, 255)).add_modifier(Modifier::BOLD),
+                Style::default().fg(theme().text_bright).add_modifier(Modifier::BOLD),
             ),
             Span::styled(
                 format!(" {} ", category),
@@ -445,57 +670,115 @@ fn draw_server_detail(f: &mut Frame, area: Rect, detail: &DetailKind) {
         ]),
         Line::from(Span::styled(
             "  \u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}",
-            Style::default().fg(Color::Rgb(35, 50, 80)),
+            Style::default().fg(theme().border),
         )),
         Line::from(""),
     ];
 
     // ─── Network ───
     lines.push(section_divider("Network"));
-    lines.push(row("Port",         format!("{}", port),         Color::Rgb(100, 220, 255)));
-    lines.push(row("Protocol",     proto.clone(),               Color::Rgb(100, 220, 255)));
-    lines.push(row("Bind Address", bind_addr.clone(),           Color::Rgb(150, 160, 190)));
+    lines.push(row("Port",         format!("{}", port),         theme().accent));
+    lines.push(row("Protocol",     proto.clone(),               theme().accent));
+    lines.push(row("Bind Address", bind_addr.clone(),           theme().text_secondary));
 
     let (status_str, status_color) = if *is_responsive {
-        ("\u{25cf} UP".to_string(), Color::Rgb(80, 200, 120))
+        ("\u{25cf} UP".to_string(), theme().ok)
     } else {
-        ("\u{25cb} DOWN".to_string(), Color::Rgb(100, 100, 120))
+        ("\u{25cb} DOWN".to_string(), theme().text_dim)
     };
     lines.push(row("Status",      status_str,                  status_color));
 
     if *tls_detected {
-        lines.push(row("TLS",     "\u{1f512} Yes".to_string(), Color::Rgb(80, 200, 120)));
+        lines.push(row("TLS",     "\u{1f512} Yes".to_string(), theme().ok));
     } else {
-        lines.push(row("TLS",     "No".to_string(),            Color::Rgb(120, 130, 160)));
+        lines.push(row("TLS",     "No".to_string(),            theme().text_dim));
+    }
+
+    // ─── Exposure ───
+    if let Some(exp) = exposure {
+        lines.push(section_divider("Exposure"));
+        lines.push(row("Reachability", exp.level.clone(),
+            theme().rgb(exp.color.0, exp.color.1, exp.color.2)));
+        for reason in &exp.reasons {
+            lines.push(Line::from(vec![
+                Span::styled("  \u{00B7} ", Style::default().fg(theme().text_muted)),
+                Span::styled(reason.clone(), Style::default().fg(theme().text_secondary)),
+            ]));
+        }
+        for (severity, message) in &exp.risks {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  \u{26A0} {} ", severity.label()),
+                    Style::default().fg(severity.color()).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(message.clone(), Style::default().fg(theme().pink)),
+            ]));
+        }
+    }
+
+    // ─── Advisories ───
+    if !advisories.is_empty() {
+        lines.push(section_divider("Advisories"));
+        for (severity, title, detail) in advisories {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {} {:<16}", severity.label(), title),
+                    Style::default().fg(severity.color()).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(detail.clone(), Style::default().fg(theme().olive)),
+            ]));
+        }
+    }
+
+    // ─── Health ───
+    if let Some(h) = health {
+        lines.push(section_divider("Health"));
+        lines.push(row("Check", format!("{} every {}s", h.check, h.interval_secs), theme().text_secondary));
+        let runs = h.history.len();
+        let failed = h.history.iter().filter(|r| r.is_none()).count();
+        let latencies: Vec<u64> = h.history.iter().flatten().copied().collect();
+        if let Some(&last) = latencies.last() {
+            let avg = latencies.iter().sum::<u64>() / latencies.len() as u64;
+            let max = latencies.iter().copied().max().unwrap_or(0);
+            lines.push(row("Latency", format!("{} ms (avg {} ms, max {} ms)", last, avg, max), theme().accent));
+        }
+        let (ok_color, err_color) = (theme().ok, theme().error);
+        lines.push(row("Runs", format!("{} ({} failed)", runs, failed), if failed > 0 { err_color } else { ok_color }));
+        if let Some(ref e) = h.last_error {
+            lines.push(row("Last Error", e.clone(), err_color));
+        }
+        let mut spark = vec![Span::styled(format!("  {:<16}", "History"), Style::default().fg(theme().text_dim))];
+        spark.extend(super::servers::latency_sparkline(h.history.iter().copied(), 50));
+        lines.push(Line::from(spark));
     }
 
     // ─── Process ───
     lines.push(section_divider("Process"));
-    lines.push(row("PID",          format!("{}", pid),          Color::Rgb(120, 130, 160)));
-    lines.push(row("Process Name", process_name.clone(),        Color::Rgb(130, 200, 140)));
-    lines.push(row("Executable",   exe_path.clone(),            Color::Rgb(170, 185, 210)));
+    lines.push(row("PID",          format!("{}", pid),          theme().text_dim));
+    lines.push(row("Process Name", process_name.clone(),        theme().ok_soft));
+    lines.push(row("Executable",   exe_path.clone(),            theme().text));
 
     let cmd_display = if cmdline.len() > 100 {
         format!("{}...", &cmdline[..100])
     } else {
         cmdline.clone()
     };
-    lines.push(row("Command Line", cmd_display,                 Color::Rgb(150, 160, 190)));
+    lines.push(row("Command Line", cmd_display,                 theme().text_secondary));
 
     if !product_name.is_empty() {
-        lines.push(row("Product",      product_name.clone(),    Color::Rgb(180, 200, 140)));
+        lines.push(row("Product",      product_name.clone(),    theme().olive));
     }
     if !company_name.is_empty() {
-        lines.push(row("Company",      company_name.clone(),    Color::Rgb(160, 180, 140)));
+        lines.push(row("Company",      company_name.clone(),    theme().olive));
     }
 
     // ─── Detection ───
     lines.push(section_divider("Detection"));
     lines.push(row("Version",      if version.is_empty() { "\u{2014}".to_string() } else { version.clone() },
-        Color::Rgb(200, 180, 80)));
+        theme().gold));
 
     if !http_title.is_empty() {
-        lines.push(row("HTTP Title",  http_title.clone(),       Color::Rgb(180, 200, 120)));
+        lines.push(row("HTTP Title",  http_title.clone(),       theme().olive));
     }
 
     if !banner.is_empty() {
@@ -504,7 +787,26 @@ fn draw_server_detail(f: &mut Frame, area: Rect, detail: &DetailKind) {
         } else {
             banner.clone()
         };
-        lines.push(row("Banner",      banner_display,           Color::Rgb(180, 170, 140)));
+        lines.push(row("Banner",      banner_display,           theme().gold));
+    }
+
+    // ─── Protocol Handshake ───
+    if !protocol_details.is_empty() {
+        lines.push(section_divider("Protocol Handshake"));
+        for (key, value) in protocol_details {
+            let value_display = if value.chars().count() > 90 {
+                format!("{}...", value.chars().take(90).collect::<String>())
+            } else {
+                value.clone()
+            };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {:<16}", key),
+                    Style::default().fg(theme().text_dim),
+                ),
+                Span::styled(value_display, Style::default().fg(theme().text)),
+            ]));
+        }
     }
 
     // ─── HTTP Headers ───
@@ -514,11 +816,11 @@ fn draw_server_detail(f: &mut Frame, area: Rect, detail: &DetailKind) {
             lines.push(Line::from(vec![
                 Span::styled(
                     format!("  {:<22}", key),
-                    Style::default().fg(Color::Rgb(100, 140, 180)).add_modifier(Modifier::BOLD),
+                    Style::default().fg(theme().text_dim).add_modifier(Modifier::BOLD),
                 ),
                 Span::styled(
                     value.clone(),
-                    Style::default().fg(Color::Rgb(180, 190, 200)),
+                    Style::default().fg(theme().text),
                 ),
             ]));
         }
@@ -534,10 +836,10 @@ fn draw_server_detail(f: &mut Frame, area: Rect, detail: &DetailKind) {
                 format!("{} {} [{}]", name, ver, cat)
             };
             lines.push(Line::from(vec![
-                Span::styled("  \u{25B8} ", Style::default().fg(Color::Rgb(80, 200, 120))),
+                Span::styled("  \u{25B8} ", Style::default().fg(theme().ok)),
                 Span::styled(
                     display,
-                    Style::default().fg(Color::Rgb(180, 160, 240)),
+                    Style::default().fg(theme().purple),
                 ),
             ]));
         }
@@ -546,8 +848,8 @@ fn draw_server_detail(f: &mut Frame, area: Rect, detail: &DetailKind) {
     // ─── Stats ───
     lines.push(section_divider("Stats"));
     lines.push(row("Active Conns", format!("{}", active_connections),
-        if *active_connections > 0 { Color::Rgb(80, 200, 120) } else { Color::Rgb(100, 110, 130) }));
-    lines.push(row("First Seen",   first_seen.clone(),          Color::Rgb(120, 130, 160)));
+        if *active_connections > 0 { theme().ok } else { theme().text_dim }));
+    lines.push(row("First Seen",   first_seen.clone(),          theme().text_dim));
 
     lines.push(Line::from(""));
     lines.push(dismiss_line());
@@ -561,12 +863,12 @@ fn header_lines(title: &str) -> Vec<Line<'static>> {
         Line::from(Span::styled(
             format!("  {}", title),
             Style::default()
-                .fg(Color::Rgb(200, 220, 255))
+                .fg(theme().text_bright)
                 .add_modifier(Modifier::BOLD),
         )),
         Line::from(Span::styled(
             "  \u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}",
-            Style::default().fg(Color::Rgb(35, 50, 80)),
+            Style::default().fg(theme().border),
         )),
         Line::from(""),
     ]
@@ -577,37 +879,64 @@ fn row(label: &'static str, value: String, value_color: Color) -> Line<'static>
     Line::from(vec![
         Span::styled(
             format!("  {:<16}", label),
-            Style::default().fg(Color::Rgb(90, 105, 135)),
+            Style::default().fg(theme().text_dim),
         ),
         Span::styled(value, Style::default().fg(value_color).add_modifier(Modifier::BOLD)),
     ])
 }
 
+/// One row of shaded cells, oldest left, plus the share of time online.
+fn presence_bar(
+    label: &'static str,
+    spans: &[crate::network::presence::PresenceSpan],
+    from: chrono::DateTime<chrono::Local>,
+    to: chrono::DateTime<chrono::Local>,
+    buckets: usize,
+) -> Line<'static> {
+    let fractions = crate::network::presence::online_fraction(spans, from, to, buckets);
+    let bar: String = fractions
+        .iter()
+        .map(|&f| match f {
+            f if f <= 0.0 => '\u{00b7}',
+            f if f < 0.25 => '\u{2591}',
+            f if f < 0.5 => '\u{2592}',
+            f if f < 0.9 => '\u{2593}',
+            _ => '\u{2588}',
+        })
+        .collect();
+    let pct = fractions.iter().sum::<f32>() / buckets as f32 * 100.0;
+    Line::from(vec![
+        Span::styled(format!("  {:<16}", label), Style::default().fg(theme().text_dim)),
+        Span::styled(bar, Style::default().fg(theme().ok)),
+        Span::styled(format!("  {:.0}% online", pct), Style::default().fg(theme().text_dim)),
+    ])
+}
+
 fn section_divider(title: &str) -> Line<'static> {
     let bar = "\u{2500}".repeat(40usize.saturating_sub(title.len() + 6));
     Line::from(Span::styled(
         format!("  \u{2500}\u{2500}\u{2500} {} {}", title, bar),
-        Style::default().fg(Color::Rgb(40, 55, 80)),
+        Style::default().fg(theme().divider),
     ))
 }
 
 fn dismiss_line() -> Line<'static> {
     Line::from(Span::styled(
         "  [ Enter / Esc to close ]",
-        Style::default().fg(Color::Rgb(65, 80, 110)).add_modifier(Modifier::ITALIC),
+        Style::default().fg(theme().text_muted).add_modifier(Modifier::ITALIC),
     ))
 }
 
 fn render_popup(f: &mut Frame, area: Rect, lines: Vec<Line<'static>>) {
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Rgb(60, 100, 180)))
-        .style(Style::default().bg(Color::Rgb(10, 14, 28)));
+        .border_style(Style::default().fg(theme().text_dim))
+        .style(Style::default().bg(theme().surface));
 
     let inner = block.inner(area);
     f.render_widget(block, area);
     f.render_widget(
-        Paragraph::new(lines).style(Style::default().bg(Color::Rgb(10, 14, 28))),
+        Paragraph::new(lines).style(Style::default().bg(theme().surface)),
         inner,
     );
 }
@@ -1,15 +1,21 @@
 //! LAN Devices tab UI — network scanner results display.
 
 use ratatui::layout::{Constraint, Rect};
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{
     Block, Borders, Cell, Row, Scrollbar, ScrollbarOrientation, ScrollbarState, Table,
 };
 use ratatui::Frame;
 
+use chrono::{DateTime, Local};
+
 use crate::app::App;
+use crate::network::{device_services, range_scan};
+use crate::network::dnssd::DnsSdService;
+use crate::types::{DeviceType, LanDevice};
 use crate::utils::format_bytes;
+use crate::ui::theme::theme;
 
 fn format_speed(bps: f64) -> String {
     if bps < 1.0 {
@@ -19,10 +25,53 @@ fn format_speed(bps: f64) -> String {
     }
 }
 
+/// Time of day for today, otherwise the date — the inventory spans sessions.
+fn format_seen(t: &DateTime<Local>) -> String {
+    if t.date_naive() == Local::now().date_naive() {
+        t.format("%H:%M:%S").to_string()
+    } else {
+        t.format("%Y-%m-%d").to_string()
+    }
+}
+
+/// Icon, type and confidence, e.g. "💻 Laptop 72%". Unknown devices get a dash.
+fn type_line(device: &LanDevice) -> Line<'static> {
+    let t = device.device_type;
+    if t == DeviceType::Unknown {
+        return Line::from(Span::styled("—", Style::default().fg(theme().text_faint)));
+    }
+    Line::from(vec![
+        Span::styled(format!("{} ", t.icon()), Style::default().fg(t.color())),
+        Span::styled(t.label(), Style::default().fg(t.color())),
+        Span::styled(
+            format!(" {}%", device.type_confidence),
+            Style::default().fg(theme().text_muted),
+        ),
+    ])
+}
+
+/// OS from the DHCP fingerprint, else the passive TCP/IP guess.
+fn os_label(device: &LanDevice, app: &App) -> Option<String> {
+    device.os().map(str::to_string)
+        .or_else(|| app.os_fingerprints.get(&device.ip).map(|g| g.label()))
+}
+
 pub fn draw_devices(f: &mut Frame, area: Rect, app: &App) {
+    if app.dnssd_view {
+        draw_dnssd_services(f, area, app);
+        return;
+    }
+    if app.dhcp_view {
+        draw_dhcp_servers(f, area, app);
+        return;
+    }
+    if app.range_scan_view {
+        draw_range_scans(f, area, app);
+        return;
+    }
     let scanner = &app.network_scanner;
-    let mut devices: Vec<&crate::types::LanDevice> = scanner.devices.iter()
-        .filter(|d| !app.hide_offline_devices || d.is_online)
+    let mut devices: Vec<&LanDevice> = scanner.devices.iter()
+        .filter(|d| app.device_visible(d))
         .collect();
     // Sort by selected column
     let sort_col = app.device_sort_column;
@@ -30,16 +79,21 @@ pub fn draw_devices(f: &mut Frame, area: Rect, app: &App) {
     devices.sort_by(|a, b| {
         let ord = match sort_col {
             0 => b.is_online.cmp(&a.is_online),     // Status: online first
-            1 => a.ip.to_string().cmp(&b.ip.to_string()), // IP
-            2 => a.hostname.as_deref().unwrap_or("~").cmp(&b.hostname.as_deref().unwrap_or("~")),
-            3 => a.mac.cmp(&b.mac),
-            4 => a.vendor.as_deref().unwrap_or("~").cmp(&b.vendor.as_deref().unwrap_or("~")),
-            5 => a.open_ports.cmp(&b.open_ports),    // Ports
-            6 => a.first_seen.cmp(&b.first_seen),
-            7 => b.last_seen.cmp(&a.last_seen),      // Last seen: most recent first
-            8 => a.speed_received.partial_cmp(&b.speed_received).unwrap_or(std::cmp::Ordering::Equal),
-            9 => a.speed_sent.partial_cmp(&b.speed_sent).unwrap_or(std::cmp::Ordering::Equal),
-            10 => a.discovery_info.cmp(&b.discovery_info),
+            1 => a.device_type.label().cmp(b.device_type.label())   // Type, most certain first
+                .then(b.type_confidence.cmp(&a.type_confidence)),
+            2 => os_label(a, app).unwrap_or_else(|| "~".into()).cmp(&os_label(b, app).unwrap_or_else(|| "~".into())),
+            3 => a.ip.to_string().cmp(&b.ip.to_string()), // IP
+            4 => a.hostname.as_deref().unwrap_or("~").cmp(&b.hostname.as_deref().unwrap_or("~")),
+            5 => a.mac.cmp(&b.mac),
+            6 => a.vendor.as_deref().unwrap_or("~").cmp(&b.vendor.as_deref().unwrap_or("~")),
+            7 => a.open_ports.cmp(&b.open_ports),    // Ports
+            8 => b.services.len().cmp(&a.services.len()) // Services: most first
+                .then_with(|| a.services.first().map(|s| &s.product).cmp(&b.services.first().map(|s| &s.product))),
+            9 => a.first_seen.cmp(&b.first_seen),
+            10 => b.last_seen.cmp(&a.last_seen),      // Last seen: most recent first
+            11 => a.speed_received.partial_cmp(&b.speed_received).unwrap_or(std::cmp::Ordering::Equal),
+            12 => a.speed_sent.partial_cmp(&b.speed_sent).unwrap_or(std::cmp::Ordering::Equal),
+            13 => a.discovery_info.cmp(&b.discovery_info),
             _ => std::cmp::Ordering::Equal,
         };
         if sort_asc { ord.reverse() } else { ord }
@@ -65,7 +119,7 @@ pub fn draw_devices(f: &mut Frame, area: Rect, app: &App) {
     };
 
     let hdr_style = Style::default()
-        .fg(Color::Rgb(160, 180, 220))
+        .fg(theme().text)
         .add_modifier(Modifier::BOLD);
 
     let si = |col: usize| -> &str {
@@ -76,19 +130,22 @@ pub fn draw_devices(f: &mut Frame, area: Rect, app: &App) {
 
     let header = Row::new(vec![
         Cell::from(Span::styled(format!("Status{}", si(0)), hdr_style)),
-        Cell::from(Span::styled(format!("IP Address{}", si(1)), hdr_style)),
-        Cell::from(Span::styled(format!("Hostname{}", si(2)), hdr_style)),
-        Cell::from(Span::styled(format!("MAC{}", si(3)), hdr_style)),
-        Cell::from(Span::styled(format!("Vendor{}", si(4)), hdr_style)),
-        Cell::from(Span::styled(format!("Ports{}", si(5)), hdr_style)),
-        Cell::from(Span::styled(format!("First{}", si(6)), hdr_style)),
-        Cell::from(Span::styled(format!("Last{}", si(7)), hdr_style)),
-        Cell::from(Span::styled(format!("↓ Recv{}", si(8)), hdr_style)),
-        Cell::from(Span::styled(format!("↑ Sent{}", si(9)), hdr_style)),
-        Cell::from(Span::styled(format!("Details{}", si(10)), hdr_style)),
+        Cell::from(Span::styled(format!("Type{}", si(1)), hdr_style)),
+        Cell::from(Span::styled(format!("OS{}", si(2)), hdr_style)),
+        Cell::from(Span::styled(format!("IP Address{}", si(3)), hdr_style)),
+        Cell::from(Span::styled(format!("Hostname{}", si(4)), hdr_style)),
+        Cell::from(Span::styled(format!("MAC{}", si(5)), hdr_style)),
+        Cell::from(Span::styled(format!("Vendor{}", si(6)), hdr_style)),
+        Cell::from(Span::styled(format!("Ports{}", si(7)), hdr_style)),
+        Cell::from(Span::styled(format!("Services{}", si(8)), hdr_style)),
+        Cell::from(Span::styled(format!("First{}", si(9)), hdr_style)),
+        Cell::from(Span::styled(format!("Last{}", si(10)), hdr_style)),
+        Cell::from(Span::styled(format!("↓ Recv{}", si(11)), hdr_style)),
+        Cell::from(Span::styled(format!("↑ Sent{}", si(12)), hdr_style)),
+        Cell::from(Span::styled(format!("Details{}", si(13)), hdr_style)),
     ])
     .height(1)
-    .style(Style::default().bg(Color::Rgb(18, 25, 42)));
+    .style(Style::default().bg(theme().header_bg));
 
     let rows: Vec<Row> = devices
         .iter()
@@ -98,28 +155,30 @@ pub fn draw_devices(f: &mut Frame, area: Rect, app: &App) {
         .map(|(idx, device)| {
             let is_selected = idx == selected;
             let (status_icon, status_color) = if device.is_online {
-                ("● ONLINE", Color::Rgb(80, 200, 120))
+                ("● ONLINE", theme().ok)
+            } else if scanner.wake_watch.is_pending(&device.mac) {
+                ("◌ WAKING", theme().highlight)
             } else {
-                ("○ OFFLINE", Color::Rgb(100, 100, 120))
+                ("○ OFFLINE", theme().text_dim)
             };
 
             let hostname_display = device.hostname.as_deref().unwrap_or("—");
             let hostname_color = if device.hostname.is_some() {
-                Color::Rgb(130, 200, 180)
+                theme().ok_soft
             } else {
-                Color::Rgb(60, 65, 80)
+                theme().text_faint
             };
 
             let vendor = device.custom_name.as_deref()
                 .or(device.vendor.as_deref())
                 .unwrap_or("Unknown");
             let vendor_color = if device.custom_name.is_some() {
-                Color::Rgb(255, 220, 100)  // gold for custom labels
+                theme().highlight  // gold for custom labels
             } else {
-                Color::Rgb(180, 170, 140)  // default
+                theme().gold  // default
             };
-            let first_seen = device.first_seen.format("%H:%M:%S").to_string();
-            let last_seen = device.last_seen.format("%H:%M:%S").to_string();
+            let first_seen = format_seen(&device.first_seen);
+            let last_seen = format_seen(&device.last_seen);
 
             let is_gateway = scanner.gateway
                 .map(|gw| device.ip == std::net::IpAddr::V4(gw))
@@ -132,11 +191,11 @@ pub fn draw_devices(f: &mut Frame, area: Rect, app: &App) {
             };
 
             let row_bg = if is_selected {
-                Color::Rgb(25, 45, 85)
+                theme().selected_bg
             } else if device.is_online {
-                Color::Rgb(14, 18, 30)
+                theme().surface
             } else {
-                Color::Rgb(10, 12, 22)
+                theme().bg
             };
 
             Row::new(vec![
@@ -144,22 +203,34 @@ pub fn draw_devices(f: &mut Frame, area: Rect, app: &App) {
                     status_icon,
                     Style::default().fg(status_color).add_modifier(Modifier::BOLD),
                 )),
+                Cell::from(type_line(device)),
+                match os_label(device, app) {
+                    Some(os) => Cell::from(Span::styled(os, Style::default().fg(theme().purple))),
+                    None => Cell::from(Span::styled("—", Style::default().fg(theme().text_faint))),
+                },
                 Cell::from(Span::styled(
                     ip_display,
                     Style::default().fg(if is_gateway {
-                        Color::Rgb(255, 200, 80)
+                        theme().highlight
                     } else {
-                        Color::Rgb(100, 180, 255)
+                        theme().info
                     }),
                 )),
                 Cell::from(Span::styled(
                     hostname_display,
                     Style::default().fg(hostname_color),
                 )),
-                Cell::from(Span::styled(
-                    device.mac.clone(),
-                    Style::default().fg(Color::Rgb(150, 160, 180)),
-                )),
+                if scanner.is_trusted(&device.mac) {
+                    Cell::from(Line::from(vec![
+                        Span::styled("✓ ", Style::default().fg(theme().ok)),
+                        Span::styled(device.mac.clone(), Style::default().fg(theme().text_secondary)),
+                    ]))
+                } else {
+                    Cell::from(Span::styled(
+                        format!("  {}", device.mac),
+                        Style::default().fg(theme().text_secondary),
+                    ))
+                },
                 Cell::from(Span::styled(
                     vendor.to_string(),
                     Style::default().fg(vendor_color),
@@ -171,18 +242,30 @@ pub fn draw_devices(f: &mut Frame, area: Rect, app: &App) {
                         device.open_ports.clone()
                     },
                     Style::default().fg(if device.open_ports.is_empty() {
-                        Color::Rgb(60, 70, 90)
+                        theme().text_faint
                     } else {
-                        Color::Rgb(180, 200, 120)
+                        theme().olive
+                    }),
+                )),
+                Cell::from(Span::styled(
+                    if device.services.is_empty() {
+                        "—".to_string()
+                    } else {
+                        device_services::summary(&device.services)
+                    },
+                    Style::default().fg(if device.services.is_empty() {
+                        theme().text_faint
+                    } else {
+                        theme().accent
                     }),
                 )),
                 Cell::from(Span::styled(
                     first_seen,
-                    Style::default().fg(Color::Rgb(90, 100, 120)),
+                    Style::default().fg(theme().text_muted),
                 )),
                 Cell::from(Span::styled(
                     last_seen,
-                    Style::default().fg(Color::Rgb(90, 100, 120)),
+                    Style::default().fg(theme().text_muted),
                 )),
                 Cell::from(Span::styled(
                     if device.bytes_received > 0 {
@@ -191,9 +274,9 @@ pub fn draw_devices(f: &mut Frame, area: Rect, app: &App) {
                         "—".to_string()
                     },
                     Style::default().fg(if device.speed_received > 0.0 {
-                        Color::Rgb(80, 200, 255)
+                        theme().info
                     } else {
-                        Color::Rgb(60, 70, 90)
+                        theme().text_faint
                     }),
                 )),
                 Cell::from(Span::styled(
@@ -203,9 +286,9 @@ pub fn draw_devices(f: &mut Frame, area: Rect, app: &App) {
                         "—".to_string()
                     },
                     Style::default().fg(if device.speed_sent > 0.0 {
-                        Color::Rgb(255, 180, 100)
+                        theme().highlight
                     } else {
-                        Color::Rgb(60, 70, 90)
+                        theme().text_faint
                     }),
                 )),
                 Cell::from(Span::styled(
@@ -215,9 +298,9 @@ pub fn draw_devices(f: &mut Frame, area: Rect, app: &App) {
                         device.discovery_info.clone()
                     },
                     Style::default().fg(if device.discovery_info.is_empty() {
-                        Color::Rgb(60, 70, 90)
+                        theme().text_faint
                     } else {
-                        Color::Rgb(140, 160, 130)
+                        theme().text_secondary
                     }),
                 )),
             ])
@@ -258,38 +341,82 @@ pub fn draw_devices(f: &mut Frame, area: Rect, app: &App) {
     let mut title_spans = vec![
         Span::styled(
             " Devices ",
-            Style::default().fg(Color::Rgb(160, 180, 220)).add_modifier(Modifier::BOLD),
+            Style::default().fg(theme().text).add_modifier(Modifier::BOLD),
         ),
         Span::styled(
             format!(" {}/{} online ", online, all_total),
-            Style::default().fg(Color::Rgb(100, 120, 150)),
+            Style::default().fg(theme().text_dim),
         ),
     ];
+    if let Some(t) = app.device_type_filter {
+        title_spans.push(Span::styled(
+            format!("[{} {}] ", t.icon(), t.label()),
+            Style::default().fg(t.color()).add_modifier(Modifier::BOLD),
+        ));
+    }
+    if app.quarantine_view {
+        title_spans.push(Span::styled(
+            "[⚠ Untrusted only] ",
+            Style::default().fg(theme().error).add_modifier(Modifier::BOLD),
+        ));
+    }
+    title_spans.push(Span::styled(
+        format!("{} trusted ", scanner.allowlist.count(&scanner.network_id())),
+        Style::default().fg(theme().ok),
+    ));
     if hidden > 0 {
         title_spans.push(Span::styled(
             format!("({} hidden) ", hidden),
-            Style::default().fg(Color::Rgb(80, 80, 100)),
+            Style::default().fg(theme().text_muted),
         ));
     }
     if !scanning_str.is_empty() {
         title_spans.push(Span::styled(
             scanning_str,
-            Style::default().fg(Color::Rgb(80, 200, 255)).add_modifier(Modifier::BOLD),
+            Style::default().fg(theme().info).add_modifier(Modifier::BOLD),
         ));
     }
     if !local_ip_str.is_empty() {
         title_spans.push(Span::styled(
             local_ip_str,
-            Style::default().fg(Color::Rgb(80, 160, 200)),
+            Style::default().fg(theme().info),
         ));
     }
 
     let rename_hint = if app.renaming_device.is_some() {
         Line::from(vec![
-            Span::styled(" Rename: ", Style::default().fg(Color::Rgb(255, 200, 80)).add_modifier(ratatui::style::Modifier::BOLD)),
-            Span::styled(app.device_rename_text.clone(), Style::default().fg(Color::White)),
-            Span::styled("█", Style::default().fg(Color::Rgb(255, 200, 80))),
-            Span::styled("  Enter:confirm  Esc:cancel", Style::default().fg(Color::Rgb(55, 70, 100))),
+            Span::styled(" Rename: ", Style::default().fg(theme().highlight).add_modifier(ratatui::style::Modifier::BOLD)),
+            Span::styled(app.device_rename_text.clone(), Style::default().fg(theme().text_bright)),
+            Span::styled("█", Style::default().fg(theme().highlight)),
+            Span::styled("  Enter:confirm  Esc:cancel", Style::default().fg(theme().text_faint)),
+        ])
+    } else if let Some(ref path) = app.oui_import_path {
+        Line::from(vec![
+            Span::styled(" Import IEEE OUI CSV: ", Style::default().fg(theme().highlight).add_modifier(Modifier::BOLD)),
+            Span::styled(path.clone(), Style::default().fg(theme().text_bright)),
+            Span::styled("█", Style::default().fg(theme().highlight)),
+            Span::styled("  file or folder of oui/mam/oui36/cid.csv  Enter:import  Esc:cancel", Style::default().fg(theme().text_faint)),
+        ])
+    } else if let Some(ref path) = app.allowlist_export_path {
+        Line::from(vec![
+            Span::styled(" Export allowlist to: ", Style::default().fg(theme().highlight).add_modifier(Modifier::BOLD)),
+            Span::styled(path.clone(), Style::default().fg(theme().text_bright)),
+            Span::styled("█", Style::default().fg(theme().highlight)),
+            Span::styled("  .csv for CSV, otherwise JSON  Enter:export  Esc:cancel", Style::default().fg(theme().text_faint)),
+        ])
+    } else if let Some(ref path) = app.allowlist_import_path {
+        Line::from(vec![
+            Span::styled(" Import allowlist from: ", Style::default().fg(theme().highlight).add_modifier(Modifier::BOLD)),
+            Span::styled(path.clone(), Style::default().fg(theme().text_bright)),
+            Span::styled("█", Style::default().fg(theme().highlight)),
+            Span::styled("  JSON or CSV (mac,name,added)  Enter:import  Esc:cancel", Style::default().fg(theme().text_faint)),
+        ])
+    } else if app.wol_password_device.is_some() {
+        Line::from(vec![
+            Span::styled(" SecureOn password: ", Style::default().fg(theme().highlight).add_modifier(Modifier::BOLD)),
+            Span::styled("*".repeat(app.wol_password_text.chars().count()), Style::default().fg(theme().text_bright)),
+            Span::styled("█", Style::default().fg(theme().highlight)),
+            Span::styled("  AA:BB:CC:DD:EE:FF, empty to clear  Enter:save  Esc:cancel", Style::default().fg(theme().text_faint)),
         ])
     } else {
         let selected_name = if !app.network_scanner.devices.is_empty() {
@@ -306,20 +433,23 @@ pub fn draw_devices(f: &mut Frame, area: Rect, app: &App) {
         } else {
             " s:scan".to_string()
         };
-        Line::from(Span::styled(selected_name, Style::default().fg(Color::Rgb(55, 70, 100))))
+        Line::from(Span::styled(selected_name, Style::default().fg(theme().text_faint)))
     };
 
     let table = Table::new(
         rows,
         [
             Constraint::Length(10),  // Status
+            Constraint::Length(14),  // Type
+            Constraint::Length(16),  // OS
             Constraint::Length(22),  // IP Address
             Constraint::Length(14),  // Hostname
-            Constraint::Length(18),  // MAC Address
+            Constraint::Length(20),  // MAC Address (✓ when trusted)
             Constraint::Length(20),  // Vendor (wider to avoid truncation)
             Constraint::Length(22),  // Ports
-            Constraint::Length(9),   // First Seen
-            Constraint::Length(9),   // Last Seen
+            Constraint::Length(34),  // Services
+            Constraint::Length(11),  // First Seen
+            Constraint::Length(11),  // Last Seen
             Constraint::Length(18),  // Recv
             Constraint::Length(18),  // Sent
             Constraint::Min(20),    // Details
@@ -331,8 +461,8 @@ pub fn draw_devices(f: &mut Frame, area: Rect, app: &App) {
             .title(Line::from(title_spans))
             .title_bottom(rename_hint)
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Rgb(30, 50, 85)))
-            .style(Style::default().bg(Color::Rgb(12, 16, 28))),
+            .border_style(Style::default().fg(theme().border))
+            .style(Style::default().bg(theme().surface)),
     );
 
     f.render_widget(table, area);
@@ -348,9 +478,378 @@ pub fn draw_devices(f: &mut Frame, area: Rect, app: &App) {
         let mut sb_state = ScrollbarState::new(total.saturating_sub(visible_height)).position(viewport_start);
         f.render_stateful_widget(
             Scrollbar::new(ScrollbarOrientation::VerticalRight)
-                .style(Style::default().fg(Color::Rgb(40, 70, 120))),
+                .style(Style::default().fg(theme().border_strong)),
             sb_area,
             &mut sb_state,
         );
     }
 }
+
+/// Services sub-view: every DNS-SD service advertised on the LAN, grouped
+/// by the device that advertises it.
+fn draw_dnssd_services(f: &mut Frame, area: Rect, app: &App) {
+    let scanner = &app.network_scanner;
+    let services = scanner.dnssd.lock().map(|c| c.services()).unwrap_or_default();
+
+    // (device label, ip, service), devices in IP order
+    let mut rows_data: Vec<(String, String, &DnsSdService)> = services
+        .iter()
+        .map(|svc| {
+            let device = scanner.devices.iter().find(|d| svc.addrs.contains(&d.ip));
+            let label = device
+                .and_then(|d| d.custom_name.clone().or_else(|| d.hostname.clone()))
+                .unwrap_or_else(|| svc.host.trim_end_matches(".local").to_string());
+            let ip = device.map(|d| d.ip).or_else(|| svc.addrs.first().copied());
+            (label, ip.map(|ip| ip.to_string()).unwrap_or_default(), svc)
+        })
+        .collect();
+    rows_data.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.2.label().cmp(b.2.label())));
+
+    let total = rows_data.len();
+    let visible_height = area.height.saturating_sub(3) as usize;
+    let selected = if total > 0 { app.device_scroll.min(total - 1) } else { 0 };
+    let viewport_start = selected.saturating_sub(visible_height.saturating_sub(1));
+
+    let hdr_style = Style::default()
+        .fg(theme().text)
+        .add_modifier(Modifier::BOLD);
+    let header = Row::new(
+        ["Device", "IP Address", "Service", "Instance", "Port", "Host", "TXT"]
+            .map(|h| Cell::from(Span::styled(h, hdr_style))),
+    )
+    .height(1)
+    .style(Style::default().bg(theme().header_bg));
+
+    let mut prev_ip = None;
+    let rows: Vec<Row> = rows_data
+        .iter()
+        .enumerate()
+        .map(|(idx, (label, ip, svc))| {
+            // Name each device once, on its first service
+            let first = prev_ip != Some(ip);
+            prev_ip = Some(ip);
+            (idx, first, label, ip, svc)
+        })
+        .skip(viewport_start)
+        .take(visible_height)
+        .map(|(idx, first, label, ip, svc)| {
+            let row_bg = if idx == selected { theme().selected_bg } else { theme().surface };
+            let (device, ip) = if first { (label.clone(), ip.clone()) } else { (String::new(), String::new()) };
+            Row::new(vec![
+                Cell::from(Span::styled(device, Style::default().fg(theme().ok_soft))),
+                Cell::from(Span::styled(ip, Style::default().fg(theme().info))),
+                Cell::from(Span::styled(
+                    svc.label().to_string(),
+                    Style::default().fg(theme().accent).add_modifier(Modifier::BOLD),
+                )),
+                Cell::from(Span::styled(svc.instance.clone(), Style::default().fg(theme().highlight))),
+                Cell::from(Span::styled(svc.port.to_string(), Style::default().fg(theme().olive))),
+                Cell::from(Span::styled(svc.host.clone(), Style::default().fg(theme().text_secondary))),
+                Cell::from(Span::styled(
+                    if svc.txt.is_empty() { "—".to_string() } else { svc.txt.join("  ") },
+                    Style::default().fg(theme().text_secondary),
+                )),
+            ])
+            .style(Style::default().bg(row_bg))
+        })
+        .collect();
+
+    let device_count = {
+        let mut ips: Vec<&String> = rows_data.iter().map(|(_, ip, _)| ip).collect();
+        ips.dedup();
+        ips.len()
+    };
+    let title = Line::from(vec![
+        Span::styled(
+            " Devices › Services ",
+            Style::default().fg(theme().text).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(" {} services on {} devices (mDNS / DNS-SD) ", total, device_count),
+            Style::default().fg(theme().text_dim),
+        ),
+    ]);
+    let hint = Line::from(Span::styled(
+        if total == 0 { " s:scan to browse  m:back to devices" } else { " m:back to devices" },
+        Style::default().fg(theme().text_faint),
+    ));
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(22),  // Device
+            Constraint::Length(16),  // IP Address
+            Constraint::Length(18),  // Service
+            Constraint::Length(28),  // Instance
+            Constraint::Length(6),   // Port
+            Constraint::Length(26),  // Host
+            Constraint::Min(20),     // TXT
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .title(title)
+            .title_bottom(hint)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme().border))
+            .style(Style::default().bg(theme().surface)),
+    );
+    f.render_widget(table, area);
+}
+
+fn format_lease(secs: u32) -> String {
+    match secs {
+        u32::MAX => "infinite".to_string(),
+        s if s >= 86_400 && s % 3600 == 0 => format!("{}d {}h", s / 86_400, s % 86_400 / 3600),
+        s if s >= 3600 => format!("{}h {:02}m", s / 3600, s % 3600 / 60),
+        s => format!("{}m", s / 60),
+    }
+}
+
+fn draw_dhcp_servers(f: &mut Frame, area: Rect, app: &App) {
+    let scanner = &app.network_scanner;
+    let network = scanner.network_id();
+    let servers = scanner.dhcp_servers.servers(&network);
+
+    let total = servers.len();
+    let rogue = servers.iter().filter(|s| s.rogue).count();
+    let visible_height = area.height.saturating_sub(3) as usize;
+    let selected = if total > 0 { app.device_scroll.min(total - 1) } else { 0 };
+    let viewport_start = selected.saturating_sub(visible_height.saturating_sub(1));
+
+    let hdr_style = Style::default()
+        .fg(theme().text)
+        .add_modifier(Modifier::BOLD);
+    let header = Row::new(
+        ["Status", "Server", "MAC", "Offers", "ACKs", "Lease", "Router", "DNS Servers", "Domain", "First", "Last"]
+            .map(|h| Cell::from(Span::styled(h, hdr_style))),
+    )
+    .height(1)
+    .style(Style::default().bg(theme().header_bg));
+
+    let join = |addrs: &[std::net::Ipv4Addr]| if addrs.is_empty() {
+        "—".to_string()
+    } else {
+        addrs.iter().map(|a| a.to_string()).collect::<Vec<_>>().join(", ")
+    };
+
+    let rows: Vec<Row> = servers
+        .iter()
+        .enumerate()
+        .skip(viewport_start)
+        .take(visible_height)
+        .map(|(idx, s)| {
+            let row_bg = if idx == selected { theme().selected_bg } else { theme().surface };
+            let status = if s.rogue {
+                Span::styled("⚠ ROGUE", Style::default().fg(theme().error).add_modifier(Modifier::BOLD))
+            } else {
+                Span::styled("✓ expected", Style::default().fg(theme().ok))
+            };
+            let dim = Style::default().fg(theme().text_secondary);
+            Row::new(vec![
+                Cell::from(status),
+                Cell::from(Span::styled(
+                    s.server_id.to_string(),
+                    Style::default().fg(theme().info).add_modifier(Modifier::BOLD),
+                )),
+                Cell::from(Span::styled(s.mac.clone().unwrap_or_else(|| "—".to_string()), dim)),
+                Cell::from(Span::styled(s.offers.to_string(), Style::default().fg(theme().olive))),
+                Cell::from(Span::styled(s.acks.to_string(), Style::default().fg(theme().olive))),
+                Cell::from(Span::styled(
+                    s.lease_secs.map_or_else(|| "—".to_string(), format_lease),
+                    Style::default().fg(theme().highlight),
+                )),
+                Cell::from(Span::styled(join(&s.routers), Style::default().fg(theme().accent))),
+                Cell::from(Span::styled(join(&s.dns), Style::default().fg(theme().accent))),
+                Cell::from(Span::styled(s.domain.clone().unwrap_or_else(|| "—".to_string()), dim)),
+                Cell::from(Span::styled(format_seen(&s.first_seen), dim)),
+                Cell::from(Span::styled(format_seen(&s.last_seen), dim)),
+            ])
+            .style(Style::default().bg(row_bg))
+        })
+        .collect();
+
+    let mut title = vec![
+        Span::styled(
+            " Devices › DHCP ",
+            Style::default().fg(theme().text).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(" {} servers on {} ", total, network),
+            Style::default().fg(theme().text_dim),
+        ),
+    ];
+    if rogue > 0 {
+        title.push(Span::styled(
+            format!(" {} rogue ", rogue),
+            Style::default().fg(theme().header_bg).bg(theme().error).add_modifier(Modifier::BOLD),
+        ));
+    }
+    let hint = Line::from(Span::styled(
+        if total == 0 {
+            " no DHCP offers heard yet (needs packet capture)  d:back to devices"
+        } else {
+            " expected: gateway + dhcp_servers in devices.json  d:back to devices"
+        },
+        Style::default().fg(theme().text_faint),
+    ));
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(11),  // Status
+            Constraint::Length(16),  // Server
+            Constraint::Length(18),  // MAC
+            Constraint::Length(6),   // Offers
+            Constraint::Length(5),   // ACKs
+            Constraint::Length(9),   // Lease
+            Constraint::Length(16),  // Router
+            Constraint::Length(32),  // DNS Servers
+            Constraint::Length(16),  // Domain
+            Constraint::Length(10),  // First
+            Constraint::Min(10),     // Last
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .title(Line::from(title))
+            .title_bottom(hint)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme().border))
+            .style(Style::default().bg(theme().surface)),
+    );
+    f.render_widget(table, area);
+}
+
+fn draw_range_scans(f: &mut Frame, area: Rect, app: &App) {
+    let range_scans = &app.network_scanner.range_scans;
+    let scan = range_scans.scans.get(app.range_scan_selected);
+    let hosts = scan.map(|s| s.hosts.as_slice()).unwrap_or_default();
+
+    let total = hosts.len();
+    let visible_height = area.height.saturating_sub(3) as usize;
+    let selected = if total > 0 { app.device_scroll.min(total - 1) } else { 0 };
+    let viewport_start = selected.saturating_sub(visible_height.saturating_sub(1));
+
+    let hdr_style = Style::default()
+        .fg(theme().text)
+        .add_modifier(Modifier::BOLD);
+    let header = Row::new(
+        ["IP Address", "Hostname", "MAC", "Vendor", "Found By", "Open Ports", "Services"]
+            .map(|h| Cell::from(Span::styled(h, hdr_style))),
+    )
+    .height(1)
+    .style(Style::default().bg(theme().header_bg));
+
+    let dim = Style::default().fg(theme().text_secondary);
+    let dash = || Span::styled("—", Style::default().fg(theme().text_faint));
+    let rows: Vec<Row> = hosts
+        .iter()
+        .enumerate()
+        .skip(viewport_start)
+        .take(visible_height)
+        .map(|(idx, h)| {
+            let row_bg = if idx == selected { theme().selected_bg } else { theme().surface };
+            let text = |v: &Option<String>, style: Style| match v {
+                Some(v) => Span::styled(v.clone(), style),
+                None => dash(),
+            };
+            Row::new(vec![
+                Cell::from(Span::styled(
+                    h.ip.to_string(),
+                    Style::default().fg(theme().info).add_modifier(Modifier::BOLD),
+                )),
+                Cell::from(text(&h.hostname, Style::default().fg(theme().text_bright))),
+                Cell::from(text(&h.mac, dim)),
+                Cell::from(text(&h.vendor, Style::default().fg(theme().text))),
+                Cell::from(Span::styled(h.methods.join(","), Style::default().fg(theme().text_secondary))),
+                Cell::from(if h.open_ports.is_empty() {
+                    dash()
+                } else {
+                    Span::styled(h.open_ports.clone(), Style::default().fg(theme().highlight))
+                }),
+                Cell::from(if h.services.is_empty() {
+                    dash()
+                } else {
+                    Span::styled(device_services::summary(&h.services), Style::default().fg(theme().accent))
+                }),
+            ])
+            .style(Style::default().bg(row_bg))
+        })
+        .collect();
+
+    let mut title = vec![Span::styled(
+        " Devices › Range Scans ",
+        Style::default().fg(theme().text).add_modifier(Modifier::BOLD),
+    )];
+    if let Some(scan) = scan {
+        title.push(Span::styled(
+            format!(
+                " {} [{}/{}]  {} up of {}{}  {} ",
+                scan.name,
+                app.range_scan_selected + 1,
+                range_scans.scans.len(),
+                scan.hosts.len(),
+                scan.probed,
+                if scan.cancelled { " (stopped)" } else { "" },
+                format_seen(&scan.finished),
+            ),
+            Style::default().fg(theme().text_dim),
+        ));
+    }
+    if let Some((name, _)) = &range_scans.running {
+        let (phase, done, of) = range_scans.progress();
+        let progress = match phase {
+            range_scan::PHASE_PROBE => format!(" 🔍 {}: probing {}/{} IPs ", name, done, of),
+            range_scan::PHASE_RESOLVE => format!(" 🔍 {}: resolving {}/{} hosts ", name, done, of),
+            _ => format!(" 🔍 {}: starting... ", name),
+        };
+        title.push(Span::styled(
+            progress,
+            Style::default().fg(theme().info).add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    let hint = if let Some(ref input) = app.range_scan_input {
+        Line::from(vec![
+            Span::styled(" Scan targets: ", Style::default().fg(theme().highlight).add_modifier(Modifier::BOLD)),
+            Span::styled(input.clone(), Style::default().fg(theme().text_bright)),
+            Span::styled("█", Style::default().fg(theme().highlight)),
+            Span::styled("  [name:] 10.20.0.0/22, 192.168.5.10-50  Enter:scan  Esc:cancel", Style::default().fg(theme().text_faint)),
+        ])
+    } else {
+        Line::from(Span::styled(
+            match (scan.is_some(), range_scans.is_running()) {
+                (_, true) => " c:stop  [/]:saved scans  N:back to devices",
+                (true, false) => " n:new scan (prefilled)  [/]:saved scans  N:back to devices",
+                (false, false) => " no range scans yet  n:scan targets  N:back to devices",
+            },
+            Style::default().fg(theme().text_faint),
+        ))
+    };
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(16),  // IP Address
+            Constraint::Length(24),  // Hostname
+            Constraint::Length(18),  // MAC
+            Constraint::Length(16),  // Vendor
+            Constraint::Length(18),  // Found By
+            Constraint::Length(24),  // Open Ports
+            Constraint::Min(20),     // Services
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .title(Line::from(title))
+            .title_bottom(hint)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme().border))
+            .style(Style::default().bg(theme().surface)),
+    );
+    f.render_widget(table, area);
+}
@@ -4,7 +4,7 @@
 //! blocked), with firewall status, bandwidth columns, and Enter for detail/action popup.
 
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{
     Block, Borders, Cell, Paragraph, Row, Scrollbar, ScrollbarOrientation, ScrollbarState, Table,
@@ -13,6 +13,7 @@ use ratatui::Frame;
 
 use crate::app::App;
 use crate::utils::{format_bytes, format_speed};
+use crate::ui::theme::theme;
 
 pub fn draw_firewall(f: &mut Frame, area: Rect, app: &App) {
     let apps = app.firewall_app_list_filtered();
@@ -28,27 +29,27 @@ pub fn draw_firewall(f: &mut Frame, area: Rect, app: &App) {
     let mut title_spans = vec![
         Span::styled(
             " Firewall & Usage ",
-            Style::default().fg(Color::Rgb(160, 180, 220)).add_modifier(Modifier::BOLD),
+            Style::default().fg(theme().text).add_modifier(Modifier::BOLD),
         ),
         Span::styled(
             format!(" {} apps ", apps.len()),
-            Style::default().fg(Color::Rgb(100, 120, 150)),
+            Style::default().fg(theme().text_dim),
         ),
         Span::styled(
             format!(" {} blocked ", blocked_count),
             Style::default()
-                .fg(if blocked_count > 0 { Color::Rgb(255, 120, 80) } else { Color::Rgb(70, 90, 120) }),
+                .fg(if blocked_count > 0 { theme().caution } else { theme().text_muted }),
         ),
     ];
     if !filter_info.is_empty() {
-        title_spans.push(Span::styled(filter_info, Style::default().fg(Color::Yellow)));
+        title_spans.push(Span::styled(filter_info, Style::default().fg(theme().key)));
     }
 
     let outer_block = Block::default()
         .title(Line::from(title_spans))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Rgb(30, 50, 85)))
-        .style(Style::default().bg(Color::Rgb(12, 16, 28)));
+        .border_style(Style::default().fg(theme().border))
+        .style(Style::default().bg(theme().surface));
     let inner = outer_block.inner(area);
     f.render_widget(outer_block, area);
 
@@ -70,12 +71,12 @@ fn draw_firewall_status(f: &mut Frame, area: Rect, app: &App) {
     let fw = &app.firewall_manager;
 
     let status_str = if fw.enabled { "ACTIVE" } else { "DISABLED" };
-    let status_color = if fw.enabled { Color::Rgb(80, 200, 120) } else { Color::Rgb(255, 80, 80) };
+    let status_color = if fw.enabled { theme().ok } else { theme().error };
 
     let mode_color = match fw.mode {
-        crate::types::FirewallMode::Normal => Color::Rgb(80, 180, 255),
-        crate::types::FirewallMode::AskToConnect => Color::Rgb(255, 200, 60),
-        crate::types::FirewallMode::Lockdown => Color::Rgb(255, 80, 80),
+        crate::types::FirewallMode::Normal => theme().info,
+        crate::types::FirewallMode::AskToConnect => theme().warn,
+        crate::types::FirewallMode::Lockdown => theme().error,
     };
 
     let pending_str = if fw.mode == crate::types::FirewallMode::AskToConnect && !fw.pending_apps.is_empty() {
@@ -86,29 +87,29 @@ fn draw_firewall_status(f: &mut Frame, area: Rect, app: &App) {
     };
 
     let (policy_label, policy_color) = if fw.default_deny {
-        ("DENY-ALL", Color::Rgb(255, 100, 80))
+        ("DENY-ALL", theme().error)
     } else {
-        ("ALLOW-ALL", Color::Rgb(80, 200, 120))
+        ("ALLOW-ALL", theme().ok)
     };
 
     let line = Line::from(vec![
-        Span::styled("  Shield: ", Style::default().fg(Color::Rgb(120, 140, 170))),
+        Span::styled("  Shield: ", Style::default().fg(theme().text_secondary)),
         Span::styled(status_str, Style::default().fg(status_color).add_modifier(Modifier::BOLD)),
-        Span::styled("  |  Policy: ", Style::default().fg(Color::Rgb(80, 100, 130))),
+        Span::styled("  |  Policy: ", Style::default().fg(theme().text_muted)),
         Span::styled(policy_label, Style::default().fg(policy_color).add_modifier(Modifier::BOLD)),
-        Span::styled("  |  Mode: ", Style::default().fg(Color::Rgb(80, 100, 130))),
+        Span::styled("  |  Mode: ", Style::default().fg(theme().text_muted)),
         Span::styled(fw.mode.label(), Style::default().fg(mode_color).add_modifier(Modifier::BOLD)),
         Span::styled(
             format!("  |  {} blocked", fw.blocked_apps.len()),
-            Style::default().fg(Color::Rgb(90, 110, 140)),
+            Style::default().fg(theme().text_dim),
         ),
-        Span::styled(pending_str, Style::default().fg(Color::Rgb(255, 200, 60))),
+        Span::styled(pending_str, Style::default().fg(theme().warn)),
     ]);
 
     let block = Block::default()
         .borders(Borders::BOTTOM)
-        .border_style(Style::default().fg(Color::Rgb(30, 50, 85)))
-        .style(Style::default().bg(Color::Rgb(8, 12, 24)));
+        .border_style(Style::default().fg(theme().border))
+        .style(Style::default().bg(theme().bg));
 
     f.render_widget(Paragraph::new(line).block(block), area);
 }
@@ -122,47 +123,47 @@ fn draw_data_plan_summary(f: &mut Frame, area: Rect, app: &App) {
         let gauge_width = 20u16;
         let filled = ((pct as f64 / 100.0) * gauge_width as f64).round() as u16;
         let empty = gauge_width.saturating_sub(filled);
-        let gauge_color = if pct > 90 { Color::Red } else if pct > 70 { Color::Yellow } else { Color::Rgb(50, 200, 120) };
+        let gauge_color = if pct > 90 { theme().error } else if pct > 70 { theme().warn } else { theme().ok };
 
         vec![
             Line::from(vec![
-                Span::styled("  Data Plan: ", Style::default().fg(Color::Rgb(120, 140, 170))),
+                Span::styled("  Data Plan: ", Style::default().fg(theme().text_secondary)),
                 Span::styled(
                     format!("{} / {} ", format_bytes(used), format_bytes(limit)),
-                    Style::default().fg(Color::Rgb(180, 200, 230)).add_modifier(Modifier::BOLD),
+                    Style::default().fg(theme().text).add_modifier(Modifier::BOLD),
                 ),
                 Span::styled("\u{2588}".repeat(filled as usize), Style::default().fg(gauge_color)),
-                Span::styled("\u{2591}".repeat(empty as usize), Style::default().fg(Color::Rgb(30, 40, 55))),
+                Span::styled("\u{2591}".repeat(empty as usize), Style::default().fg(theme().selected_bg)),
                 Span::styled(format!(" {}%", pct), Style::default().fg(gauge_color)),
             ]),
             Line::from(vec![
-                Span::styled("  Today: ", Style::default().fg(Color::Rgb(80, 100, 130))),
+                Span::styled("  Today: ", Style::default().fg(theme().text_muted)),
                 Span::styled(
                     format!("\u{25bc}{} \u{25b2}{}", format_bytes(today_down), format_bytes(today_up)),
-                    Style::default().fg(Color::Rgb(130, 160, 200)),
+                    Style::default().fg(theme().text_secondary),
                 ),
-                Span::styled("  \u{2502}  Month: ", Style::default().fg(Color::Rgb(80, 100, 130))),
+                Span::styled("  \u{2502}  Month: ", Style::default().fg(theme().text_muted)),
                 Span::styled(
                     format!("\u{25bc}{} \u{25b2}{}", format_bytes(month_down), format_bytes(month_up)),
-                    Style::default().fg(Color::Rgb(130, 160, 200)),
+                    Style::default().fg(theme().text_secondary),
                 ),
             ]),
         ]
     } else {
         vec![
             Line::from(vec![
-                Span::styled("  No data plan configured", Style::default().fg(Color::Rgb(80, 100, 130))),
+                Span::styled("  No data plan configured", Style::default().fg(theme().text_muted)),
             ]),
             Line::from(vec![
-                Span::styled("  Today: ", Style::default().fg(Color::Rgb(80, 100, 130))),
+                Span::styled("  Today: ", Style::default().fg(theme().text_muted)),
                 Span::styled(
                     format!("\u{25bc}{} \u{25b2}{}", format_bytes(today_down), format_bytes(today_up)),
-                    Style::default().fg(Color::Rgb(130, 160, 200)),
+                    Style::default().fg(theme().text_secondary),
                 ),
-                Span::styled("  \u{2502}  Month: ", Style::default().fg(Color::Rgb(80, 100, 130))),
+                Span::styled("  \u{2502}  Month: ", Style::default().fg(theme().text_muted)),
                 Span::styled(
                     format!("\u{25bc}{} \u{25b2}{}", format_bytes(month_down), format_bytes(month_up)),
-                    Style::default().fg(Color::Rgb(130, 160, 200)),
+                    Style::default().fg(theme().text_secondary),
                 ),
             ]),
         ]
@@ -170,8 +171,8 @@ fn draw_data_plan_summary(f: &mut Frame, area: Rect, app: &App) {
 
     let block = Block::default()
         .borders(Borders::BOTTOM)
-        .border_style(Style::default().fg(Color::Rgb(30, 50, 85)))
-        .style(Style::default().bg(Color::Rgb(8, 12, 24)));
+        .border_style(Style::default().fg(theme().border))
+        .style(Style::default().bg(theme().bg));
 
     f.render_widget(Paragraph::new(plan_info).block(block), area);
 }
@@ -236,7 +237,7 @@ fn draw_firewall_apps(
     };
 
     let hdr_style = Style::default()
-        .fg(Color::Rgb(160, 180, 220))
+        .fg(theme().text)
         .add_modifier(Modifier::BOLD);
 
     let sort_col = app.bandwidth_tracker.sort_column;
@@ -255,14 +256,14 @@ fn draw_firewall_apps(
         Cell::from(Span::styled("Speed", hdr_style)),
     ])
     .height(1)
-    .style(Style::default().bg(Color::Rgb(18, 25, 42)));
+    .style(Style::default().bg(theme().header_bg));
 
     let rows: Vec<Row> = if display_total == 0 {
         vec![Row::new(vec![
             Cell::from(""),
             Cell::from(Span::styled(
                 "  No apps detected yet. Apps appear here when they make network connections.",
-                Style::default().fg(Color::Rgb(80, 100, 140)),
+                Style::default().fg(theme().text_muted),
             )),
             Cell::from(""),
             Cell::from(""),
@@ -270,7 +271,7 @@ fn draw_firewall_apps(
             Cell::from(""),
             Cell::from(""),
         ])
-        .style(Style::default().bg(Color::Rgb(12, 16, 28)))]
+        .style(Style::default().bg(theme().surface))]
     } else {
         display_items
             .iter()
@@ -282,7 +283,7 @@ fn draw_firewall_apps(
                     Cell::from(""),
                     Cell::from(Span::styled(
                         *label,
-                        Style::default().fg(Color::Rgb(60, 80, 110)),
+                        Style::default().fg(theme().text_muted),
                     )),
                     Cell::from(""),
                     Cell::from(""),
@@ -290,18 +291,18 @@ fn draw_firewall_apps(
                     Cell::from(""),
                     Cell::from(""),
                 ])
-                .style(Style::default().bg(Color::Rgb(8, 10, 20))),
+                .style(Style::default().bg(theme().bg)),
 
                 DisplayItem::App(idx, (name, _is_blocked, conn_count)) => {
                     let is_selected = *idx == selected;
 
                     let (status_str, eff_blocked) = app.firewall_manager.effective_status(name);
                     let status_color = match status_str {
-                        "DENY" => Color::Rgb(255, 80, 80),
-                        "DROP" => Color::Rgb(255, 140, 40),
-                        "ALLOW" => Color::Rgb(80, 200, 255),
-                        "BLOCKED" => Color::Rgb(255, 80, 80),
-                        _ => Color::Rgb(80, 200, 120), // ALLOWED
+                        "DENY" => theme().error,
+                        "DROP" => theme().caution,
+                        "ALLOW" => theme().info,
+                        "BLOCKED" => theme().error,
+                        _ => theme().ok, // ALLOWED
                     };
 
                     let prefix = if is_selected { "\u{25b6} " } else { "  " };
@@ -332,26 +333,26 @@ fn draw_firewall_apps(
                         let su = bw.smooth_up();
                         if sd + su > 0.5 {
                             let c = if sd + su > 100_000.0 {
-                                Color::Rgb(80, 220, 160)
+                                theme().teal
                             } else if sd + su > 1000.0 {
-                                Color::Rgb(100, 160, 200)
+                                theme().text_secondary
                             } else {
-                                Color::Rgb(70, 120, 100)
+                                theme().text_muted
                             };
                             (format!("\u{25bc}{} \u{25b2}{}", format_speed(sd), format_speed(su)), c)
                         } else {
-                            ("idle".to_string(), Color::Rgb(50, 60, 75))
+                            ("idle".to_string(), theme().text_faint)
                         }
                     } else {
-                        ("-".to_string(), Color::Rgb(50, 60, 75))
+                        ("-".to_string(), theme().text_faint)
                     };
 
                     let row_bg = if is_selected {
-                        Color::Rgb(25, 45, 85)
+                        theme().selected_bg
                     } else if eff_blocked {
-                        Color::Rgb(22, 10, 10)
+                        theme().error_bg
                     } else {
-                        Color::Rgb(12, 16, 28)
+                        theme().surface
                     };
 
                     Row::new(vec![
@@ -364,26 +365,26 @@ fn draw_firewall_apps(
                         Cell::from(Span::styled(
                             display_name,
                             Style::default().fg(if eff_blocked {
-                                Color::Rgb(200, 130, 130)
+                                theme().gold
                             } else {
-                                Color::Rgb(160, 200, 160)
+                                theme().olive
                             }),
                         )),
                         Cell::from(Span::styled(
                             conn_str,
-                            Style::default().fg(Color::Rgb(120, 150, 190)),
+                            Style::default().fg(theme().text_secondary),
                         )),
                         Cell::from(Span::styled(
                             dl_str,
-                            Style::default().fg(Color::Rgb(80, 180, 255)),
+                            Style::default().fg(theme().info),
                         )),
                         Cell::from(Span::styled(
                             ul_str,
-                            Style::default().fg(Color::Rgb(180, 120, 255)),
+                            Style::default().fg(theme().purple),
                         )),
                         Cell::from(Span::styled(
                             total_str,
-                            Style::default().fg(Color::Rgb(170, 185, 210)).add_modifier(Modifier::BOLD),
+                            Style::default().fg(theme().text).add_modifier(Modifier::BOLD),
                         )),
                         Cell::from(Span::styled(
                             speed_str,
@@ -402,20 +403,20 @@ fn draw_firewall_apps(
             Span::styled(
                 " Enter ",
                 Style::default()
-                    .fg(Color::Rgb(255, 200, 80))
+                    .fg(theme().highlight)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled("Detail/Action ", Style::default().fg(Color::Rgb(140, 170, 210))),
-            Span::styled(truncate_str(name, 20), Style::default().fg(Color::Rgb(160, 180, 220))),
+            Span::styled("Detail/Action ", Style::default().fg(theme().text)),
+            Span::styled(truncate_str(name, 20), Style::default().fg(theme().text)),
             Span::styled(
                 "  |  1-4:sort  f:filter  r:refresh  x:reset  e:export",
-                Style::default().fg(Color::Rgb(55, 70, 100)),
+                Style::default().fg(theme().text_faint),
             ),
         ])
     } else {
         Line::from(Span::styled(
             "  Apps will appear here when they make network connections",
-            Style::default().fg(Color::Rgb(60, 80, 110)),
+            Style::default().fg(theme().text_muted),
         ))
     };
 
@@ -436,8 +437,8 @@ fn draw_firewall_apps(
         Block::default()
             .title_bottom(hint_line)
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Rgb(30, 50, 85)))
-            .style(Style::default().bg(Color::Rgb(12, 16, 28))),
+            .border_style(Style::default().fg(theme().border))
+            .style(Style::default().bg(theme().surface)),
     );
 
     f.render_widget(table, area);
@@ -454,7 +455,7 @@ fn draw_firewall_apps(
             ScrollbarState::new(display_total.saturating_sub(visible_height)).position(viewport_start);
         f.render_stateful_widget(
             Scrollbar::new(ScrollbarOrientation::VerticalRight)
-                .style(Style::default().fg(Color::Rgb(40, 70, 120))),
+                .style(Style::default().fg(theme().border_strong)),
             sb_area,
             &mut sb_state,
         );
@@ -0,0 +1,91 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::app::App;
+use crate::keymap::Context;
+use crate::ui::theme::theme;
+
+/// Key bindings overlay (`?`): global, current tab and popup bindings from
+/// the active keymap.
+pub fn draw_help(f: &mut Frame, app: &mut App) {
+    if !app.show_help {
+        return;
+    }
+    let screen = f.area();
+    let width = 76.min(screen.width.saturating_sub(4));
+    let height = (screen.height * 4 / 5).max(10).min(screen.height);
+    let area = Rect::new(
+        screen.x + (screen.width - width) / 2,
+        screen.y + (screen.height - height) / 2,
+        width,
+        height,
+    );
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(
+                "  Key Bindings",
+                Style::default().fg(theme().text_bright).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("  ({} preset, keys.toml in the config folder)", app.keymap.preset.name()),
+                Style::default().fg(theme().text_muted),
+            ),
+        ]),
+        Line::from(""),
+    ];
+    let tab = Context::Tab(app.bottom_tab);
+    for (ctx, title) in [
+        (Context::Global, "Everywhere".to_string()),
+        (tab, app.bottom_tab.label().to_string()),
+        (Context::Popup, "Detail popup".to_string()),
+    ] {
+        let bar = "\u{2500}".repeat(40usize.saturating_sub(title.len() + 6));
+        lines.push(Line::from(Span::styled(
+            format!("  \u{2500}\u{2500}\u{2500} {} [{}] {}", title, ctx.section(), bar),
+            Style::default().fg(theme().divider),
+        )));
+        for action in app.keymap.actions(ctx) {
+            let keys = app.keymap.labels(ctx, *action);
+            let (keys, key_color) = if keys.is_empty() {
+                ("unbound".to_string(), theme().text_faint)
+            } else {
+                (keys, theme().highlight)
+            };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {:<22}", keys),
+                    Style::default().fg(key_color).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(action.describe(ctx).to_string(), Style::default().fg(theme().text)),
+                Span::styled(format!("  {}", action.name()), Style::default().fg(theme().text_faint)),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
+    // Clamp scrolling to the content so the last page stays full
+    let visible = height.saturating_sub(3) as usize;
+    app.help_scroll = app.help_scroll.min(lines.len().saturating_sub(visible));
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme().border_strong))
+        .title(Span::styled(" Help ", Style::default().fg(theme().accent).add_modifier(Modifier::BOLD)))
+        .title_bottom(Span::styled(
+            " \u{2191}\u{2193} scroll \u{2022} any other key closes ",
+            Style::default().fg(theme().text_muted),
+        ))
+        .style(Style::default().bg(theme().surface));
+
+    f.render_widget(Clear, area);
+    f.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .scroll((app.help_scroll as u16, 0)),
+        area,
+    );
+}
@@ -1,11 +1,13 @@
 pub mod connections;
 pub mod dashboard;
 pub mod detail_popup;
+pub mod help;
 pub mod packets;
 pub mod packets_tab;
 pub mod servers;
 pub mod speed;
 pub mod status;
+pub mod theme;
 pub mod title;
 pub mod topology;
 pub mod alerts;
@@ -15,13 +17,14 @@ pub mod networks;
 pub mod widgets;
 
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 use ratatui::Frame;
 
 use crate::app::App;
 use crate::types::BottomTab;
+use crate::ui::theme::theme;
 
 /// Master draw function — lays out all panes.
 pub fn draw(f: &mut Frame, app: &mut App) {
@@ -71,19 +74,20 @@ pub fn draw(f: &mut Frame, app: &mut App) {
             Span::styled(
                 format!(" {} ", msg),
                 Style::default()
-                    .fg(Color::Rgb(200, 255, 200))
+                    .fg(theme().text_bright)
                     .add_modifier(Modifier::BOLD),
             ),
         ]))
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Rgb(80, 200, 120)))
-                .style(Style::default().bg(Color::Rgb(15, 30, 20))),
+                .border_style(Style::default().fg(theme().ok))
+                .style(Style::default().bg(theme().ok_bg)),
         );
         f.render_widget(toast, toast_area);
     }
 
     // Detail popup overlay — drawn last so it appears on top of everything
     detail_popup::draw_detail_popup(f, app);
+    help::draw_help(f, app);
 }
@@ -12,6 +12,7 @@ use ratatui::Frame;
 
 use crate::app::App;
 use crate::types::NetworkCategory;
+use crate::ui::theme::theme;
 
 /// Represents a virtual row in the networks display.
 #[derive(Clone)]
@@ -120,7 +121,7 @@ pub fn draw_networks(f: &mut Frame, area: Rect, app: &App) {
     };
 
     let hdr_style = Style::default()
-        .fg(Color::Rgb(160, 180, 220))
+        .fg(theme().text)
         .add_modifier(Modifier::BOLD);
 
     let si = |col: usize| -> &str {
@@ -140,7 +141,7 @@ pub fn draw_networks(f: &mut Frame, area: Rect, app: &App) {
         Cell::from(Span::styled(format!("Ports{}", si(7)), hdr_style)),
     ])
     .height(1)
-    .style(Style::default().bg(Color::Rgb(18, 25, 42)));
+    .style(Style::default().bg(theme().header_bg));
 
     let rows: Vec<Row> = display_rows
         .iter()
@@ -154,15 +155,15 @@ pub fn draw_networks(f: &mut Frame, area: Rect, app: &App) {
                     let arrow = if *expanded { "\u{25bc}" } else { "\u{25b6}" };
                     let label = format!("{} Bluetooth ({} devices)", arrow, count);
                     let bg = if is_selected {
-                        Color::Rgb(25, 45, 85)
+                        theme().selected_bg
                     } else {
-                        Color::Rgb(16, 22, 38)
+                        theme().surface_alt
                     };
                     Row::new(vec![
                         Cell::from(Span::styled(
                             label,
                             Style::default()
-                                .fg(Color::Rgb(100, 200, 240))
+                                .fg(theme().accent)
                                 .add_modifier(Modifier::BOLD),
                         )),
                         Cell::from(""),
@@ -170,7 +171,7 @@ pub fn draw_networks(f: &mut Frame, area: Rect, app: &App) {
                         Cell::from(""),
                         Cell::from(Span::styled(
                             if *expanded { "b:collapse" } else { "b:expand / Enter" }.to_string(),
-                            Style::default().fg(Color::Rgb(60, 80, 110)),
+                            Style::default().fg(theme().text_muted),
                         )),
                         Cell::from(""),
                         Cell::from(""),
@@ -183,32 +184,32 @@ pub fn draw_networks(f: &mut Frame, area: Rect, app: &App) {
                     let cat_color = category_color(category);
 
                     let (status_icon, status_color) = if device.is_online {
-                        ("\u{25cf} ONLINE", Color::Rgb(80, 200, 120))
+                        ("\u{25cf} ONLINE", theme().ok)
                     } else {
-                        ("\u{25cb} OFFLINE", Color::Rgb(100, 100, 120))
+                        ("\u{25cb} OFFLINE", theme().text_dim)
                     };
 
                     let hostname_display = device.hostname.as_deref().unwrap_or("\u{2014}");
                     let hostname_color = if device.hostname.is_some() {
-                        Color::Rgb(130, 200, 180)
+                        theme().ok_soft
                     } else {
-                        Color::Rgb(60, 65, 80)
+                        theme().text_faint
                     };
 
                     let vendor = device.vendor.as_deref().unwrap_or("Unknown");
 
                     let row_bg = if is_selected {
-                        Color::Rgb(25, 45, 85)
+                        theme().selected_bg
                     } else if device.is_online {
-                        Color::Rgb(14, 18, 30)
+                        theme().surface
                     } else {
-                        Color::Rgb(10, 12, 22)
+                        theme().bg
                     };
 
                     Row::new(vec![
                         Cell::from(Span::styled(
                             (*net_name).to_string(),
-                            Style::default().fg(Color::Rgb(140, 160, 200)),
+                            Style::default().fg(theme().text_secondary),
                         )),
                         Cell::from(Span::styled(
                             cat_label.to_string(),
@@ -220,7 +221,7 @@ pub fn draw_networks(f: &mut Frame, area: Rect, app: &App) {
                         )),
                         Cell::from(Span::styled(
                             device.ip.to_string(),
-                            Style::default().fg(Color::Rgb(100, 180, 255)),
+                            Style::default().fg(theme().info),
                         )),
                         Cell::from(Span::styled(
                             hostname_display,
@@ -228,18 +229,18 @@ pub fn draw_networks(f: &mut Frame, area: Rect, app: &App) {
                         )),
                         Cell::from(Span::styled(
                             if device.mac.is_empty() { "\u{2014}".to_string() } else { device.mac.clone() },
-                            Style::default().fg(Color::Rgb(150, 160, 180)),
+                            Style::default().fg(theme().text_secondary),
                         )),
                         Cell::from(Span::styled(
                             vendor.to_string(),
-                            Style::default().fg(Color::Rgb(180, 170, 140)),
+                            Style::default().fg(theme().gold),
                         )),
                         Cell::from(Span::styled(
                             if device.open_ports.is_empty() { "\u{2014}".to_string() } else { device.open_ports.clone() },
                             Style::default().fg(if device.open_ports.is_empty() {
-                                Color::Rgb(60, 70, 90)
+                                theme().text_faint
                             } else {
-                                Color::Rgb(180, 200, 120)
+                                theme().olive
                             }),
                         )),
                     ])
@@ -261,23 +262,23 @@ pub fn draw_networks(f: &mut Frame, area: Rect, app: &App) {
     let mut title_spans = vec![
         Span::styled(
             " Networks ",
-            Style::default().fg(Color::Rgb(160, 180, 220)).add_modifier(Modifier::BOLD),
+            Style::default().fg(theme().text).add_modifier(Modifier::BOLD),
         ),
         Span::styled(
             format!(" {} networks, {} devices ", net_count, all_device_count),
-            Style::default().fg(Color::Rgb(100, 120, 150)),
+            Style::default().fg(theme().text_dim),
         ),
     ];
     if !scanning_str.is_empty() {
         title_spans.push(Span::styled(
             scanning_str,
-            Style::default().fg(Color::Rgb(80, 200, 255)).add_modifier(Modifier::BOLD),
+            Style::default().fg(theme().info).add_modifier(Modifier::BOLD),
         ));
     }
 
     let hint = Line::from(Span::styled(
         " s:scan  b:bluetooth  Enter:details",
-        Style::default().fg(Color::Rgb(55, 70, 100)),
+        Style::default().fg(theme().text_faint),
     ));
 
     let table = Table::new(
@@ -299,8 +300,8 @@ pub fn draw_networks(f: &mut Frame, area: Rect, app: &App) {
             .title(Line::from(title_spans))
             .title_bottom(hint)
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Rgb(30, 50, 85)))
-            .style(Style::default().bg(Color::Rgb(12, 16, 28))),
+            .border_style(Style::default().fg(theme().border))
+            .style(Style::default().bg(theme().surface)),
     );
 
     f.render_widget(table, area);
@@ -316,7 +317,7 @@ pub fn draw_networks(f: &mut Frame, area: Rect, app: &App) {
         let mut sb_state = ScrollbarState::new(total.saturating_sub(visible_height)).position(viewport_start);
         f.render_stateful_widget(
             Scrollbar::new(ScrollbarOrientation::VerticalRight)
-                .style(Style::default().fg(Color::Rgb(40, 70, 120))),
+                .style(Style::default().fg(theme().border_strong)),
             sb_area,
             &mut sb_state,
         );
@@ -340,15 +341,15 @@ fn category_label(cat: &NetworkCategory) -> &'static str {
 
 fn category_color(cat: &NetworkCategory) -> Color {
     match cat {
-        NetworkCategory::Vpn => Color::Rgb(100, 220, 180),
-        NetworkCategory::Docker => Color::Rgb(80, 160, 255),
-        NetworkCategory::Wsl => Color::Rgb(255, 160, 60),
-        NetworkCategory::HyperV => Color::Rgb(180, 130, 255),
-        NetworkCategory::Virtual => Color::Rgb(200, 180, 100),
-        NetworkCategory::Secondary => Color::Rgb(140, 180, 140),
-        NetworkCategory::Bluetooth => Color::Rgb(100, 200, 240),
-        NetworkCategory::MeshVpn => Color::Rgb(80, 220, 120),
-        NetworkCategory::Hotspot => Color::Rgb(255, 180, 60),
-        NetworkCategory::Tunnel => Color::Rgb(180, 120, 255),
+        NetworkCategory::Vpn => theme().teal,
+        NetworkCategory::Docker => theme().info,
+        NetworkCategory::Wsl => theme().caution,
+        NetworkCategory::HyperV => theme().purple,
+        NetworkCategory::Virtual => theme().gold,
+        NetworkCategory::Secondary => theme().ok_soft,
+        NetworkCategory::Bluetooth => theme().accent,
+        NetworkCategory::MeshVpn => theme().ok,
+        NetworkCategory::Hotspot => theme().warn,
+        NetworkCategory::Tunnel => theme().purple,
     }
 }
@@ -6,6 +6,7 @@ use ratatui::Frame;
 
 use crate::network::sniffer::PacketSniffer;
 use crate::types::PacketDirection;
+use crate::ui::theme::theme;
 
 pub fn draw_packet_preview(f: &mut Frame, area: Rect, sniffer: &PacketSniffer) {
     let visible_lines = area.height.saturating_sub(2) as usize; // borders
@@ -16,24 +17,24 @@ pub fn draw_packet_preview(f: &mut Frame, area: Rect, sniffer: &PacketSniffer) {
             Span::styled(
                 "  \u{26A0} ",
                 Style::default()
-                    .fg(Color::Rgb(200, 160, 60))
+                    .fg(theme().gold)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
                 err,
-                Style::default().fg(Color::Rgb(140, 120, 80)),
+                Style::default().fg(theme().text_dim),
             ),
             Span::styled(
                 "  \u{2502}  Run as Administrator to enable packet inspection",
-                Style::default().fg(Color::Rgb(80, 90, 110)),
+                Style::default().fg(theme().text_muted),
             ),
         ])];
 
         let block = Block::default()
             .title(wire_title(false))
             .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
-            .border_style(Style::default().fg(Color::Rgb(30, 50, 85)))
-            .style(Style::default().bg(Color::Rgb(8, 12, 24)));
+            .border_style(Style::default().fg(theme().border))
+            .style(Style::default().bg(theme().bg));
 
         f.render_widget(Paragraph::new(lines).block(block), area);
         return;
@@ -45,7 +46,7 @@ pub fn draw_packet_preview(f: &mut Frame, area: Rect, sniffer: &PacketSniffer) {
     let lines: Vec<Line> = if recent.is_empty() {
         vec![Line::from(Span::styled(
             "  Listening for readable packet data...",
-            Style::default().fg(Color::Rgb(60, 75, 100)),
+            Style::default().fg(theme().text_muted),
         ))]
     } else {
         recent
@@ -56,11 +57,11 @@ pub fn draw_packet_preview(f: &mut Frame, area: Rect, sniffer: &PacketSniffer) {
                 let (dir_icon, dir_color) = match pkt.direction {
                     PacketDirection::Inbound => (
                         "\u{25C0} IN ",
-                        Color::Rgb(80, 200, 120),
+                        theme().ok,
                     ),
                     PacketDirection::Outbound => (
                         "\u{25B6} OUT",
-                        Color::Rgb(100, 160, 255),
+                        theme().info,
                     ),
                 };
 
@@ -79,7 +80,7 @@ pub fn draw_packet_preview(f: &mut Frame, area: Rect, sniffer: &PacketSniffer) {
                 Line::from(vec![
                     Span::styled(
                         format!(" {} ", time),
-                        Style::default().fg(Color::Rgb(65, 75, 100)),
+                        Style::default().fg(theme().text_muted),
                     ),
                     Span::styled(
                         format!("{} ", dir_icon),
@@ -89,7 +90,7 @@ pub fn draw_packet_preview(f: &mut Frame, area: Rect, sniffer: &PacketSniffer) {
                     ),
                     Span::styled(
                         format!("{:>5} ", format_size_compact(pkt.payload_size)),
-                        Style::default().fg(Color::Rgb(100, 100, 130)),
+                        Style::default().fg(theme().text_dim),
                     ),
                     Span::styled(snippet_display, Style::default().fg(snippet_color)),
                 ])
@@ -100,8 +101,8 @@ pub fn draw_packet_preview(f: &mut Frame, area: Rect, sniffer: &PacketSniffer) {
     let block = Block::default()
         .title(wire_title(true))
         .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
-        .border_style(Style::default().fg(Color::Rgb(30, 50, 85)))
-        .style(Style::default().bg(Color::Rgb(6, 10, 20)));
+        .border_style(Style::default().fg(theme().border))
+        .style(Style::default().bg(theme().bg));
 
     f.render_widget(Paragraph::new(lines).block(block), area);
 }
@@ -113,12 +114,12 @@ fn wire_title(active: bool) -> Line<'static> {
             Span::styled(
                 " \u{26A1} Wire ",
                 Style::default()
-                    .fg(Color::Rgb(255, 200, 80))
+                    .fg(theme().highlight)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
                 "Live Packet Preview ",
-                Style::default().fg(Color::Rgb(120, 135, 165)),
+                Style::default().fg(theme().text_secondary),
             ),
         ])
     } else {
@@ -126,12 +127,12 @@ fn wire_title(active: bool) -> Line<'static> {
             Span::styled(
                 " \u{26A1} Wire ",
                 Style::default()
-                    .fg(Color::Rgb(120, 100, 60))
+                    .fg(theme().text_muted)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
                 "Packet Preview (Inactive) ",
-                Style::default().fg(Color::Rgb(80, 85, 100)),
+                Style::default().fg(theme().text_muted),
             ),
         ])
     }
@@ -145,19 +146,19 @@ fn classify_snippet_color(snippet: &str) -> Color {
         || upper.starts_with("HEAD ") || upper.starts_with("OPTIONS ")
         || upper.starts_with("PATCH ")
     {
-        Color::Rgb(80, 220, 160) // Green for HTTP
+        theme().teal // Green for HTTP
     } else if upper.starts_with("SSH-") || upper.starts_with("EHLO ") || upper.starts_with("HELO ") {
-        Color::Rgb(180, 140, 255) // Purple for protocols
+        theme().purple // Purple for protocols
     } else if snippet.starts_with('{') || snippet.starts_with('[') {
-        Color::Rgb(100, 200, 255) // Cyan for JSON
+        theme().accent // Cyan for JSON
     } else if upper.contains("<!DOCTYPE") || upper.contains("<HTML") || upper.contains("<?XML") {
-        Color::Rgb(220, 180, 80) // Yellow for HTML/XML
+        theme().gold // Yellow for HTML/XML
     } else if upper.contains("CONTENT-TYPE") || upper.contains("USER-AGENT")
         || upper.contains("ACCEPT") || upper.contains("HOST:")
     {
-        Color::Rgb(140, 200, 160) // Lighter green for HTTP headers
+        theme().ok_soft // Lighter green for HTTP headers
     } else {
-        Color::Rgb(130, 140, 165) // Default gray for mixed/unclear
+        theme().text_secondary // Default gray for mixed/unclear
     }
 }
 
@@ -11,28 +11,12 @@ use ratatui::widgets::{
 use ratatui::Frame;
 
 use crate::app::App;
-use crate::types::{ConnProto, PacketDirection, PacketSnippet};
+use crate::keymap::{Action, Context};
+use crate::types::{BottomTab, ConnProto, PacketDirection, PacketSnippet};
+use crate::ui::theme::theme;
 
 // ─── Theme constants ─────────────────────────────────────────────────────────
 
-const BG: Color = Color::Rgb(8, 12, 24);
-const BG_EVEN: Color = Color::Rgb(8, 12, 24);
-const BG_ODD: Color = Color::Rgb(10, 16, 28);
-const BG_SELECTED: Color = Color::Rgb(25, 40, 65);
-const BORDER: Color = Color::Rgb(30, 50, 85);
-const MUTED: Color = Color::Rgb(90, 110, 140);
-const HDR_FG: Color = Color::Rgb(160, 180, 220);
-const HDR_BG: Color = Color::Rgb(18, 25, 42);
-const DIR_IN: Color = Color::Rgb(80, 200, 120);
-const DIR_OUT: Color = Color::Rgb(100, 160, 255);
-const SCROLLBAR: Color = Color::Rgb(40, 70, 120);
-const DETAIL_BG: Color = Color::Rgb(10, 14, 26);
-const DETAIL_BORDER: Color = Color::Rgb(35, 55, 90);
-const DETAIL_LABEL: Color = Color::Rgb(120, 140, 180);
-const DETAIL_VALUE: Color = Color::Rgb(200, 210, 230);
-const HEX_OFFSET: Color = Color::Rgb(80, 100, 140);
-const HEX_BYTE: Color = Color::Rgb(140, 160, 200);
-const HEX_ASCII: Color = Color::Rgb(100, 180, 140);
 
 // ─── Expert severity ─────────────────────────────────────────────────────────
 
@@ -96,19 +80,19 @@ fn expert_indicator(level: ExpertLevel) -> (&'static str, Style) {
         ExpertLevel::Error => (
             "\u{25CF}",
             Style::default()
-                .fg(Color::Red)
+                .fg(theme().error)
                 .add_modifier(Modifier::BOLD),
         ),
-        ExpertLevel::Warn => ("\u{25B2}", Style::default().fg(Color::Yellow)),
-        ExpertLevel::Note => ("\u{00B7}", Style::default().fg(Color::Cyan)),
+        ExpertLevel::Warn => ("\u{25B2}", Style::default().fg(theme().warn)),
+        ExpertLevel::Note => ("\u{00B7}", Style::default().fg(theme().accent)),
         ExpertLevel::Chat => (" ", Style::default()),
     }
 }
 
 fn expert_row_tint(level: ExpertLevel) -> Option<Color> {
     match level {
-        ExpertLevel::Error => Some(Color::Rgb(40, 12, 12)),
-        ExpertLevel::Warn => Some(Color::Rgb(30, 26, 10)),
+        ExpertLevel::Error => Some(theme().error_bg),
+        ExpertLevel::Warn => Some(theme().warn_bg),
         _ => None,
     }
 }
@@ -156,24 +140,24 @@ fn protocol_style(proto: &ConnProto, port: u16) -> (Style, &'static str) {
     };
     let style = match app_proto {
         "TCP" => Style::default()
-            .fg(Color::Magenta)
+            .fg(theme().purple)
             .add_modifier(Modifier::BOLD),
         "UDP" => Style::default()
-            .fg(Color::Blue)
+            .fg(theme().info)
             .add_modifier(Modifier::BOLD),
         "DNS" => Style::default()
-            .fg(Color::Green)
+            .fg(theme().ok)
             .add_modifier(Modifier::BOLD),
         "HTTP" => Style::default()
-            .fg(Color::Cyan)
+            .fg(theme().accent)
             .add_modifier(Modifier::BOLD),
         "TLS" | "HTTPS" => Style::default()
-            .fg(Color::Rgb(100, 200, 255))
+            .fg(theme().accent)
             .add_modifier(Modifier::BOLD),
         "SSH" => Style::default()
-            .fg(Color::Yellow)
+            .fg(theme().gold)
             .add_modifier(Modifier::BOLD),
-        _ => Style::default().fg(Color::White),
+        _ => Style::default().fg(theme().text_bright),
     };
     (style, app_proto)
 }
@@ -191,24 +175,24 @@ fn snippet_content_color(snippet: &str) -> Color {
         || upper.starts_with("HEAD ")
         || upper.starts_with("OPTIONS ")
     {
-        return Color::Rgb(80, 220, 160); // green — HTTP
+        return theme().teal; // green — HTTP
     }
     if upper.starts_with("SSH-") || upper.starts_with("EHLO ") || upper.starts_with("STARTTLS") {
-        return Color::Rgb(180, 140, 255); // purple — protocol handshake
+        return theme().purple; // purple — protocol handshake
     }
     if snippet.starts_with('{') || snippet.starts_with('[') {
-        return Color::Rgb(100, 200, 255); // cyan — JSON
+        return theme().accent; // cyan — JSON
     }
     if upper.contains("<!DOCTYPE") || upper.contains("<HTML") || upper.contains("<?XML") {
-        return Color::Rgb(220, 180, 80); // yellow — markup
+        return theme().gold; // yellow — markup
     }
     if upper.contains("CONTENT-TYPE") || upper.contains("HOST:") || upper.contains("USER-AGENT") {
-        return Color::Rgb(140, 200, 160); // light green — HTTP headers
+        return theme().ok_soft; // light green — HTTP headers
     }
     if snippet.contains('\x16') || upper.contains("CERTIFICATE") {
-        return Color::Rgb(200, 160, 100); // amber — TLS
+        return theme().gold; // amber — TLS
     }
-    Color::Rgb(130, 140, 165) // default muted
+    theme().text_secondary // default muted
 }
 
 // ─── Compact size formatting ─────────────────────────────────────────────────
@@ -387,9 +371,9 @@ fn render_header(
     buffered_count: usize,
 ) {
     let (status_icon, status_color) = if app.packets_paused {
-        ("\u{25CB} PAUSED", Color::Rgb(255, 200, 80))
+        ("\u{25CB} PAUSED", theme().highlight)
     } else {
-        ("\u{25CF} CAPTURING", Color::Rgb(80, 255, 120))
+        ("\u{25CF} CAPTURING", theme().teal)
     };
 
     let now = chrono::Local::now().format("%H:%M:%S").to_string();
@@ -417,53 +401,53 @@ fn render_header(
         ),
         Span::styled(
             format!(" on {} ", app.interface_name),
-            Style::default().fg(MUTED),
+            Style::default().fg(theme().text_dim),
         ),
     ];
 
     if !app.packets_filter.is_empty() && filtered_count != total_count {
         line1.push(Span::styled(
             format!(" ({} / {} pkts, {} buffered) ", filtered_count, total_count, buffered_count),
-            Style::default().fg(Color::Rgb(160, 170, 190)),
+            Style::default().fg(theme().text),
         ));
     } else {
         line1.push(Span::styled(
             format!(" ({} pkts, {} buffered) ", total_count, buffered_count),
-            Style::default().fg(Color::Rgb(160, 170, 190)),
+            Style::default().fg(theme().text),
         ));
     }
 
     // Protocol breakdown stats
     line1.push(Span::styled(
         format!(" TCP:{}", tcp_count),
-        Style::default().fg(Color::Magenta),
+        Style::default().fg(theme().purple),
     ));
     line1.push(Span::styled(
         format!(" UDP:{}", udp_count),
-        Style::default().fg(Color::Blue),
+        Style::default().fg(theme().info),
     ));
     if syn_count > 0 {
         line1.push(Span::styled(
             format!(" SYN:{}", syn_count),
-            Style::default().fg(Color::Cyan),
+            Style::default().fg(theme().accent),
         ));
     }
     if fin_count > 0 {
         line1.push(Span::styled(
             format!(" FIN:{}", fin_count),
-            Style::default().fg(Color::Magenta),
+            Style::default().fg(theme().purple),
         ));
     }
     if rst_count > 0 {
         line1.push(Span::styled(
             format!(" RST:{}", rst_count),
-            Style::default().fg(Color::Red),
+            Style::default().fg(theme().error),
         ));
     }
 
     line1.push(Span::styled(
         format!(" {} ", now),
-        Style::default().fg(Color::Rgb(70, 85, 110)),
+        Style::default().fg(theme().text_muted),
     ));
 
     // Line 2: filter / error
@@ -472,38 +456,38 @@ fn render_header(
             Span::styled(
                 " \u{26A0} ",
                 Style::default()
-                    .fg(Color::Red)
+                    .fg(theme().error)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled(err, Style::default().fg(Color::Red)),
+            Span::styled(err, Style::default().fg(theme().error)),
         ])
     } else if !app.packets_filter.is_empty() {
         Line::from(vec![
             Span::styled(
                 " Filter: ",
                 Style::default()
-                    .fg(Color::Yellow)
+                    .fg(theme().key)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
                 app.packets_filter.clone(),
-                Style::default().fg(Color::White),
+                Style::default().fg(theme().text_bright),
             ),
             Span::styled(
                 "\u{2588}",
-                Style::default().fg(Color::White),
+                Style::default().fg(theme().text_bright),
             ),
         ])
     } else {
         Line::from(Span::styled(
             " Type to filter \u{2022} / clears with Esc",
-            Style::default().fg(Color::Rgb(50, 65, 90)),
+            Style::default().fg(theme().text_faint),
         ))
     };
 
     let header = Paragraph::new(vec![Line::from(line1), line2]).style(
         Style::default()
-            .bg(Color::Rgb(14, 20, 36)),
+            .bg(theme().surface_alt),
     );
     f.render_widget(header, area);
 }
@@ -518,7 +502,7 @@ fn render_packet_list(
     total: usize,
 ) {
     let hdr = Style::default()
-        .fg(HDR_FG)
+        .fg(theme().text)
         .add_modifier(Modifier::BOLD);
 
     let header = Row::new(vec![
@@ -533,7 +517,7 @@ fn render_packet_list(
         Cell::from(Span::styled("Info", hdr)),
     ])
     .height(1)
-    .style(Style::default().bg(HDR_BG));
+    .style(Style::default().bg(theme().header_bg));
 
     let visible_height = area.height.saturating_sub(4) as usize;
     let selected = if total > 0 {
@@ -577,8 +561,8 @@ fn render_packet_list(
 
             // Direction
             let (dir_icon, dir_color) = match pkt.direction {
-                PacketDirection::Inbound => ("\u{25C0}IN", DIR_IN),
-                PacketDirection::Outbound => ("\u{25B6}OT", DIR_OUT),
+                PacketDirection::Inbound => ("\u{25C0}IN", theme().ok),
+                PacketDirection::Outbound => ("\u{25B6}OT", theme().info),
             };
 
             // Source / Destination with DNS resolution
@@ -615,13 +599,13 @@ fn render_packet_list(
             // Color based on flags presence or snippet content
             let info_color = if !flags_str.is_empty() {
                 if pkt.tcp_flags & 0x04 != 0 {
-                    Color::Red        // RST
+                    theme().error        // RST
                 } else if pkt.tcp_flags & 0x02 != 0 {
-                    Color::Cyan       // SYN
+                    theme().accent       // SYN
                 } else if pkt.tcp_flags & 0x01 != 0 {
-                    Color::Magenta    // FIN
+                    theme().purple    // FIN
                 } else if pkt.snippet.is_empty() {
-                    Color::DarkGray   // ACK-only with no snippet
+                    theme().text_muted   // ACK-only with no snippet
                 } else {
                     snippet_content_color(&pkt.snippet)
                 }
@@ -631,19 +615,19 @@ fn render_packet_list(
 
             // Row background
             let row_bg = if is_selected {
-                BG_SELECTED
+                theme().selected_bg
             } else if let Some(tint) = expert_row_tint(expert) {
                 tint
             } else if idx % 2 == 0 {
-                BG_EVEN
+                theme().bg
             } else {
-                BG_ODD
+                theme().surface
             };
 
             let fg = if is_selected {
-                Color::White
+                theme().text_bright
             } else {
-                Color::Rgb(180, 190, 210)
+                theme().text
             };
 
             Row::new(vec![
@@ -651,17 +635,17 @@ fn render_packet_list(
                 Cell::from(Span::styled(
                     seq,
                     Style::default().fg(if is_selected {
-                        Color::Rgb(140, 155, 180)
+                        theme().text_secondary
                     } else {
-                        Color::Rgb(55, 65, 85)
+                        theme().text_faint
                     }),
                 )),
                 Cell::from(Span::styled(
                     time_str,
                     Style::default().fg(if is_selected {
-                        Color::Rgb(180, 195, 220)
+                        theme().text
                     } else {
-                        Color::Rgb(100, 110, 130)
+                        theme().text_dim
                     }),
                 )),
                 Cell::from(Span::styled(
@@ -672,29 +656,29 @@ fn render_packet_list(
                 )),
                 Cell::from(Span::styled(src_str, Style::default().fg(if is_selected { fg } else {
                     match pkt.direction {
-                        PacketDirection::Outbound => Color::Rgb(140, 180, 220),
-                        PacketDirection::Inbound => Color::Rgb(160, 170, 190),
+                        PacketDirection::Outbound => theme().text,
+                        PacketDirection::Inbound => theme().text,
                     }
                 }))),
                 Cell::from(Span::styled(dst_str, Style::default().fg(if is_selected { fg } else {
                     match pkt.direction {
-                        PacketDirection::Inbound => Color::Rgb(140, 180, 220),
-                        PacketDirection::Outbound => Color::Rgb(160, 170, 190),
+                        PacketDirection::Inbound => theme().text,
+                        PacketDirection::Outbound => theme().text,
                     }
                 }))),
                 Cell::from(Span::styled(proto_label, proto_style)),
                 Cell::from(Span::styled(
                     len_str,
                     Style::default().fg(if is_selected {
-                        Color::Rgb(180, 190, 210)
+                        theme().text
                     } else {
-                        Color::Rgb(100, 100, 130)
+                        theme().text_dim
                     }),
                 )),
                 Cell::from(Span::styled(
                     info_str,
                     Style::default().fg(if is_selected {
-                        Color::Rgb(220, 230, 245)
+                        theme().text_bright
                     } else {
                         info_color
                     }),
@@ -708,18 +692,18 @@ fn render_packet_list(
     let mut title_parts = vec![Span::styled(
         " \u{1F50D} Packets ",
         Style::default()
-            .fg(Color::Rgb(160, 180, 220))
+            .fg(theme().text)
             .add_modifier(Modifier::BOLD),
     )];
     if !app.packets_filter.is_empty() {
         title_parts.push(Span::styled(
             format!("({} / {}) ", total, packets.len()),
-            Style::default().fg(Color::Rgb(255, 220, 100)),
+            Style::default().fg(theme().highlight),
         ));
     } else {
         title_parts.push(Span::styled(
             format!("({}) ", total),
-            Style::default().fg(Color::Rgb(100, 120, 150)),
+            Style::default().fg(theme().text_dim),
         ));
     }
 
@@ -742,8 +726,8 @@ fn render_packet_list(
         Block::default()
             .title(Line::from(title_parts))
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(BORDER))
-            .style(Style::default().bg(BG)),
+            .border_style(Style::default().fg(theme().border))
+            .style(Style::default().bg(theme().bg)),
     );
 
     f.render_widget(table, area);
@@ -760,7 +744,7 @@ fn render_packet_list(
             ScrollbarState::new(total.saturating_sub(visible_height)).position(viewport_start);
         f.render_stateful_widget(
             Scrollbar::new(ScrollbarOrientation::VerticalRight)
-                .style(Style::default().fg(SCROLLBAR)),
+                .style(Style::default().fg(theme().border_strong)),
             sb_area,
             &mut sb_state,
         );
@@ -780,8 +764,8 @@ fn render_detail_pane(
     let mut lines: Vec<Line> = Vec::with_capacity(available);
 
     let (dir_label, dir_color) = match pkt.direction {
-        PacketDirection::Inbound => ("INBOUND", DIR_IN),
-        PacketDirection::Outbound => ("OUTBOUND", DIR_OUT),
+        PacketDirection::Inbound => ("INBOUND", theme().ok),
+        PacketDirection::Outbound => ("OUTBOUND", theme().info),
     };
 
     let relevant_port = if pkt.direction == PacketDirection::Outbound {
@@ -793,7 +777,7 @@ fn render_detail_pane(
 
     // ── Protocol layer 1: Frame ──
     lines.push(Line::from(vec![
-        Span::styled("  Frame: ", Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
+        Span::styled("  Frame: ", Style::default().fg(theme().text_bright).add_modifier(Modifier::BOLD)),
         Span::styled(
             format!(
                 "#{}, {} bytes captured at {}",
@@ -801,7 +785,7 @@ fn render_detail_pane(
                 pkt.payload_size,
                 pkt.timestamp.format("%H:%M:%S%.3f")
             ),
-            Style::default().fg(Color::White),
+            Style::default().fg(theme().text_bright),
         ),
         Span::styled(
             format!("  [{}]", dir_label),
@@ -838,20 +822,20 @@ fn render_detail_pane(
             Span::styled(
                 format!("  {}: ", ip_version),
                 Style::default()
-                    .fg(Color::Green)
+                    .fg(theme().ok)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled(src_display, Style::default().fg(Color::Rgb(140, 200, 255))),
+            Span::styled(src_display, Style::default().fg(theme().text)),
             Span::styled(
                 " \u{2192} ",
                 Style::default()
-                    .fg(Color::Rgb(80, 120, 170))
+                    .fg(theme().text_dim)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled(dst_display, Style::default().fg(Color::Rgb(255, 200, 140))),
+            Span::styled(dst_display, Style::default().fg(theme().highlight)),
             Span::styled(
                 format!("  TTL: {}, ID: 0x{:04X}, Total Length: {}", pkt.ttl, pkt.ip_id, pkt.ip_total_len),
-                Style::default().fg(DETAIL_LABEL),
+                Style::default().fg(theme().text_secondary),
             ),
         ]));
     }
@@ -872,8 +856,8 @@ fn render_detail_pane(
         };
 
         let transport_color = match pkt.protocol {
-            ConnProto::Tcp => Color::Magenta,
-            ConnProto::Udp => Color::Blue,
+            ConnProto::Tcp => theme().purple,
+            ConnProto::Udp => theme().info,
         };
 
         let mut transport_spans = vec![
@@ -885,28 +869,28 @@ fn render_detail_pane(
             ),
             Span::styled(
                 format!("{} \u{2192} {}", src_port_s, dst_port_s),
-                Style::default().fg(DETAIL_VALUE),
+                Style::default().fg(theme().text_bright),
             ),
             Span::styled(
                 format!(", Payload: {} bytes", pkt.payload_size),
-                Style::default().fg(DETAIL_LABEL),
+                Style::default().fg(theme().text_secondary),
             ),
         ];
 
         if pkt.protocol == ConnProto::Tcp {
             let flags_display = pkt.tcp_flags_str();
             let flags_color = if pkt.tcp_flags & 0x04 != 0 {
-                Color::Red
+                theme().error
             } else if pkt.tcp_flags & 0x02 != 0 {
-                Color::Cyan
+                theme().accent
             } else if pkt.tcp_flags & 0x01 != 0 {
-                Color::Magenta
+                theme().purple
             } else {
-                DETAIL_LABEL
+                theme().text_secondary
             };
             transport_spans.push(Span::styled(
                 format!("  Seq: {}, Ack: {}, Flags: ", pkt.tcp_seq, pkt.tcp_ack_num),
-                Style::default().fg(DETAIL_LABEL),
+                Style::default().fg(theme().text_secondary),
             ));
             transport_spans.push(Span::styled(
                 flags_display,
@@ -914,7 +898,7 @@ fn render_detail_pane(
             ));
             transport_spans.push(Span::styled(
                 format!(", Win: {}", pkt.tcp_window),
-                Style::default().fg(DETAIL_LABEL),
+                Style::default().fg(theme().text_secondary),
             ));
         }
 
@@ -931,7 +915,7 @@ fn render_detail_pane(
                 }
                 geo_spans.push(Span::styled(
                     format!("  Geo {}: {} {} ({})", label, geo.flag, geo.name, geo.code),
-                    Style::default().fg(Color::Rgb(100, 180, 255)),
+                    Style::default().fg(theme().info),
                 ));
             }
         }
@@ -944,7 +928,7 @@ fn render_detail_pane(
     if lines.len() < available {
         lines.push(Line::from(Span::styled(
             "  \u{2500}\u{2500}\u{2500} Payload \u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}",
-            Style::default().fg(Color::Rgb(40, 60, 90)),
+            Style::default().fg(theme().divider),
         )));
     }
 
@@ -968,7 +952,7 @@ fn render_detail_pane(
     if lines.len() < available {
         lines.push(Line::from(Span::styled(
             "  \u{2500}\u{2500}\u{2500} Hex Dump \u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}",
-            Style::default().fg(Color::Rgb(40, 60, 90)),
+            Style::default().fg(theme().divider),
         )));
     }
 
@@ -1014,11 +998,11 @@ fn render_detail_pane(
             .collect();
 
         lines.push(Line::from(vec![
-            Span::styled(offset_str, Style::default().fg(HEX_OFFSET)),
-            Span::styled(hex, Style::default().fg(HEX_BYTE)),
-            Span::styled(" \u{2502}", Style::default().fg(Color::Rgb(40, 55, 80))),
-            Span::styled(ascii, Style::default().fg(HEX_ASCII)),
-            Span::styled("\u{2502}", Style::default().fg(Color::Rgb(40, 55, 80))),
+            Span::styled(offset_str, Style::default().fg(theme().text_muted)),
+            Span::styled(hex, Style::default().fg(theme().text_secondary)),
+            Span::styled(" \u{2502}", Style::default().fg(theme().divider)),
+            Span::styled(ascii, Style::default().fg(theme().ok_soft)),
+            Span::styled("\u{2502}", Style::default().fg(theme().divider)),
         ]));
 
         offset += bytes_per_row;
@@ -1032,12 +1016,12 @@ fn render_detail_pane(
             Span::styled(
                 " Protocol Detail ",
                 Style::default()
-                    .fg(Color::Rgb(200, 180, 255))
+                    .fg(theme().text)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
                 format!(" #{} ", selected_idx + 1),
-                Style::default().fg(MUTED),
+                Style::default().fg(theme().text_dim),
             ),
             Span::styled(
                 format!("{} {} ", ei, proto_label),
@@ -1045,8 +1029,8 @@ fn render_detail_pane(
             ),
         ]))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(DETAIL_BORDER))
-        .style(Style::default().bg(DETAIL_BG));
+        .border_style(Style::default().fg(theme().border))
+        .style(Style::default().bg(theme().bg));
 
     let detail = Paragraph::new(lines).block(block);
     f.render_widget(detail, area);
@@ -1055,42 +1039,50 @@ fn render_detail_pane(
 // ─── Footer ──────────────────────────────────────────────────────────────────
 
 fn render_footer(f: &mut Frame, area: Rect, app: &App) {
-    let pause_label = if app.packets_paused { "Space:Resume" } else { "Space:Pause" };
-    let detail_label = if app.packets_detail_open { "d:Collapse" } else { "d:Expand" };
+    let packets = Context::Tab(BottomTab::Packets);
+    let key = |ctx: Context, action: Action| app.keymap.label(ctx, action).unwrap_or_else(|| "-".to_string());
+    let pause_label = format!("{}:{}", key(packets, Action::Pause), if app.packets_paused { "Resume" } else { "Pause" });
+    let detail_label = format!("{}:{}", key(packets, Action::ToggleDetail), if app.packets_detail_open { "Collapse" } else { "Expand" });
 
     let mut hints = vec![
         Span::styled(
             format!(" {}", pause_label),
-            Style::default().fg(Color::Yellow),
+            Style::default().fg(theme().key),
         ),
         Span::styled("  ", Style::default()),
-        Span::styled("Enter:Detail", Style::default().fg(Color::Yellow)),
+        Span::styled(format!("{}:Detail", key(Context::Global, Action::Detail)), Style::default().fg(theme().key)),
         Span::styled("  ", Style::default()),
-        Span::styled(detail_label, Style::default().fg(Color::Yellow)),
+        Span::styled(detail_label, Style::default().fg(theme().key)),
         Span::styled("  ", Style::default()),
-        Span::styled("c:Clear", Style::default().fg(Color::Yellow)),
+        Span::styled(format!("{}:Clear", key(packets, Action::Clear)), Style::default().fg(theme().key)),
         Span::styled("  ", Style::default()),
-        Span::styled("\u{2191}\u{2193}:Navigate", Style::default().fg(Color::Yellow)),
+        Span::styled(
+            format!("{}{}:Navigate", key(Context::Global, Action::ScrollUp), key(Context::Global, Action::ScrollDown)),
+            Style::default().fg(theme().key),
+        ),
         Span::styled("  ", Style::default()),
-        Span::styled("PgUp/Dn:Scroll", Style::default().fg(Color::Yellow)),
+        Span::styled(
+            format!("{}/{}:Scroll", key(Context::Global, Action::PageUp), key(Context::Global, Action::PageDown)),
+            Style::default().fg(theme().key),
+        ),
     ];
 
     if !app.packets_filter.is_empty() {
         hints.push(Span::styled("  ", Style::default()));
-        hints.push(Span::styled("Esc:Clear filter", Style::default().fg(Color::Yellow)));
+        hints.push(Span::styled(format!("{}:Clear filter", key(packets, Action::ClearFilter)), Style::default().fg(theme().key)));
         hints.push(Span::styled("  ", Style::default()));
         hints.push(Span::styled(
             format!("[FILTER: {}]", app.packets_filter),
             Style::default()
-                .fg(Color::Rgb(255, 220, 100))
+                .fg(theme().highlight)
                 .add_modifier(Modifier::BOLD),
         ));
     } else {
         hints.push(Span::styled("  ", Style::default()));
-        hints.push(Span::styled("Type:Filter", Style::default().fg(Color::Rgb(60, 80, 110))));
+        hints.push(Span::styled("Type:Filter", Style::default().fg(theme().text_muted)));
     }
 
     let footer = Paragraph::new(Line::from(hints))
-        .style(Style::default().bg(Color::Rgb(12, 16, 30)));
+        .style(Style::default().bg(theme().surface));
     f.render_widget(footer, area);
 }
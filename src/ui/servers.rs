@@ -11,31 +11,21 @@ use ratatui::widgets::{
 use ratatui::Frame;
 
 use crate::app::App;
+use crate::network::servers::baseline::ListenerChange;
+use crate::network::servers::exposure::ExposureLevel;
+use crate::network::servers::health::HealthMonitor;
 use crate::network::servers::types::{ListenProto, ListeningPort, ServerCategory};
+use crate::network::upnp::PortMapping;
+use crate::ui::theme::theme;
 
 // ─── Theme ──────────────────────────────────────────────────────────────────
 
-const BG: Color = Color::Rgb(12, 15, 26);
-const CARD_BG: Color = Color::Rgb(16, 20, 36);
-const CARD_ALT: Color = Color::Rgb(14, 18, 32);
-const SEL_BG: Color = Color::Rgb(20, 36, 68);
-const SEL_ACCENT: Color = Color::Rgb(60, 130, 240);
-const UNRESPONSIVE_BG: Color = Color::Rgb(10, 12, 22);
-const BORDER: Color = Color::Rgb(30, 42, 65);
-const DIM: Color = Color::Rgb(50, 60, 80);
-const LABEL: Color = Color::Rgb(70, 85, 110);
-const TEXT: Color = Color::Rgb(140, 158, 190);
-const BRIGHT: Color = Color::Rgb(200, 215, 240);
-const GREEN: Color = Color::Rgb(70, 195, 110);
-const YELLOW: Color = Color::Rgb(220, 185, 60);
-const TCP_COLOR: Color = Color::Rgb(80, 150, 240);
-const UDP_COLOR: Color = Color::Rgb(220, 170, 50);
 
 // ─── Helpers ────────────────────────────────────────────────────────────────
 
 fn cat_color(cat: &ServerCategory) -> Color {
     let (r, g, b) = cat.color();
-    Color::Rgb(r, g, b)
+    theme().rgb(r, g, b)
 }
 
 fn trunc(s: &str, max: usize) -> String {
@@ -57,7 +47,7 @@ enum Row<'a> {
     ProtoHeader { proto: ListenProto, count: usize },
 
     /// Server card line 1: icon + name + port + status badges
-    CardTop { server: &'a ListeningPort, idx: usize, conns: usize },
+    CardTop { server: &'a ListeningPort, idx: usize, conns: usize, health: Option<bool> },
     /// Server card line 2: category tag + process + description/path
     CardBot { server: &'a ListeningPort, idx: usize },
     /// Blank separator between cards
@@ -106,7 +96,8 @@ pub fn draw_servers(f: &mut Frame, area: Rect, app: &App) {
         for (i, s) in sorted.iter().enumerate() {
             if i > 0 { rows.push(Row::Spacer); }
             let conns = conn_counts.get(&s.port).copied().unwrap_or(0);
-            rows.push(Row::CardTop { server: s, idx: entry_count, conns });
+            let health = sc.health.for_port(s.port).and_then(|h| h.is_passing());
+            rows.push(Row::CardTop { server: s, idx: entry_count, conns, health });
             rows.push(Row::CardBot { server: s, idx: entry_count });
             entry_count += 1;
         }
@@ -124,6 +115,7 @@ pub fn draw_servers(f: &mut Frame, area: Rect, app: &App) {
         .filter(|s| matches!(s.proto, ListenProto::Tcp))
         .collect();
     let bind_stats = BindStats::from_servers(&tcp_all);
+    let exposure_stats = ExposureStats::from_servers(&tcp_all);
 
     let total_conns: usize = conn_counts.values().sum();
     let tls_count = all.iter().filter(|s| s.details.contains("TLS: yes")).count();
@@ -142,12 +134,25 @@ pub fn draw_servers(f: &mut Frame, area: Rect, app: &App) {
         .split(area);
 
     draw_dashboard(f, chunks[0], all.len(), tcp_total, udp_total, up,
-                   total_conns, tls_count, &bind_stats, sc.is_scanning());
+                   total_conns, tls_count, &bind_stats, &exposure_stats, sc.changes.len(), sc.is_scanning());
     if has_filter {
         draw_filter_bar(f, chunks[1], &sc.filter_text, entry_count);
     }
+    if sc.show_changes {
+        let selected = sc.scroll_offset.min(sc.changes.len().saturating_sub(1));
+        draw_changes(f, chunks[2], &sc.changes, &sc.baseline.recorded, selected);
+        draw_change_detail(f, chunks[3], sc.changes.get(selected), sc.baseline.entries.len());
+        return;
+    }
+    if sc.show_upnp {
+        let mappings = &app.upnp_monitor.mappings;
+        let selected = sc.scroll_offset.min(mappings.len().saturating_sub(1));
+        draw_upnp(f, chunks[2], app, selected);
+        draw_upnp_detail(f, chunks[3], app, mappings.get(selected));
+        return;
+    }
     draw_list(f, chunks[2], &rows, selected);
-    draw_detail(f, chunks[3], &filtered, selected, &conn_counts);
+    draw_detail(f, chunks[3], &filtered, selected, &conn_counts, &sc.health);
 }
 
 // ─── Bind address stats ─────────────────────────────────────────────────────
@@ -201,6 +206,37 @@ impl BindStats {
     }
 }
 
+/// Computed exposure levels (see `servers::exposure`) for TCP listeners.
+#[derive(Default)]
+struct ExposureStats {
+    /// Listeners with an exposure assessment.
+    assessed: usize,
+    local: usize,
+    firewalled: usize,
+    lan: usize,
+    internet: usize,
+    risky: usize,
+}
+
+impl ExposureStats {
+    fn from_servers(tcp: &[&ListeningPort]) -> Self {
+        let mut st = Self::default();
+        for e in tcp.iter().filter_map(|s| s.exposure.as_ref()) {
+            st.assessed += 1;
+            match e.level {
+                ExposureLevel::Loopback => st.local += 1,
+                ExposureLevel::Firewalled => st.firewalled += 1,
+                ExposureLevel::Lan => st.lan += 1,
+                ExposureLevel::Internet => st.internet += 1,
+            }
+            if !e.risks.is_empty() {
+                st.risky += 1;
+            }
+        }
+        st
+    }
+}
+
 // ─── Dashboard strip ────────────────────────────────────────────────────────
 
 #[allow(clippy::too_many_arguments)]
@@ -209,6 +245,8 @@ fn draw_dashboard(
     total: usize, tcp: usize, udp: usize, up: usize,
     total_conns: usize, tls_count: usize,
     bind_stats: &BindStats,
+    exposure_stats: &ExposureStats,
+    changes: usize,
     scanning: bool,
 ) {
     // Split into 3 panels: Bind Addresses | Exposure Summary | Quick Stats
@@ -222,16 +260,16 @@ fn draw_dashboard(
         .split(area);
 
     draw_bind_panel(f, panels[0], bind_stats, scanning);
-    draw_exposure_panel(f, panels[1], bind_stats);
-    draw_stats_panel(f, panels[2], total, tcp, udp, up, total_conns, tls_count);
+    draw_exposure_panel(f, panels[1], bind_stats, exposure_stats);
+    draw_stats_panel(f, panels[2], total, tcp, udp, up, total_conns, tls_count, changes);
 }
 
 /// Left panel: TCP bind address breakdown
 fn draw_bind_panel(f: &mut Frame, area: Rect, bs: &BindStats, scanning: bool) {
     let block = Block::default()
         .borders(Borders::RIGHT)
-        .border_style(Style::default().fg(Color::Rgb(20, 30, 50)))
-        .style(Style::default().bg(BG));
+        .border_style(Style::default().fg(theme().header_bg))
+        .style(Style::default().bg(theme().surface));
     let inner = block.inner(area);
     f.render_widget(block, area);
 
@@ -246,11 +284,11 @@ fn draw_bind_panel(f: &mut Frame, area: Rect, bs: &BindStats, scanning: bool) {
 
     // Title line
     let mut title_spans = vec![
-        Span::styled(" TCP BIND ", Style::default().fg(Color::Rgb(20, 30, 50)).bg(TCP_COLOR).add_modifier(Modifier::BOLD)),
-        Span::styled(format!("  {} listeners", bs.tcp_total), Style::default().fg(TEXT)),
+        Span::styled(" TCP BIND ", Style::default().fg(theme().header_bg).bg(theme().info).add_modifier(Modifier::BOLD)),
+        Span::styled(format!("  {} listeners", bs.tcp_total), Style::default().fg(theme().text_secondary)),
     ];
     if scanning {
-        title_spans.push(Span::styled("  \u{25CC} scanning\u{2026}", Style::default().fg(YELLOW)));
+        title_spans.push(Span::styled("  \u{25CC} scanning\u{2026}", Style::default().fg(theme().warn)));
     }
 
     let label_w = 14;
@@ -259,20 +297,20 @@ fn draw_bind_panel(f: &mut Frame, area: Rect, bs: &BindStats, scanning: bool) {
     // Build rows: show each bind type that has >0 count
     let mut entries: Vec<(String, usize, Color)> = Vec::new();
     if bs.all_interfaces > 0 {
-        entries.push(("0.0.0.0  *".into(), bs.all_interfaces, Color::Rgb(100, 200, 240)));
+        entries.push(("0.0.0.0  *".into(), bs.all_interfaces, theme().accent));
     }
     if bs.loopback_v4 > 0 {
-        entries.push(("127.0.0.1".into(), bs.loopback_v4, Color::Rgb(180, 140, 255)));
+        entries.push(("127.0.0.1".into(), bs.loopback_v4, theme().purple));
     }
     if bs.all_interfaces_v6 > 0 {
-        entries.push(("[::]  *".into(), bs.all_interfaces_v6, Color::Rgb(80, 180, 200)));
+        entries.push(("[::]  *".into(), bs.all_interfaces_v6, theme().info));
     }
     if bs.loopback_v6 > 0 {
-        entries.push(("[::1]".into(), bs.loopback_v6, Color::Rgb(150, 120, 220)));
+        entries.push(("[::1]".into(), bs.loopback_v6, theme().purple));
     }
     for (ip, count) in bs.specific.iter().take(2) {
         let ip_display = if ip.len() > 13 { &ip[..13] } else { ip.as_str() };
-        entries.push((ip_display.to_string(), *count, Color::Rgb(200, 180, 100)));
+        entries.push((ip_display.to_string(), *count, theme().gold));
     }
 
     let mut lines: Vec<Line> = vec![Line::from(title_spans)];
@@ -288,11 +326,11 @@ fn draw_bind_panel(f: &mut Frame, area: Rect, bs: &BindStats, scanning: bool) {
             ),
             Span::styled(
                 "\u{2500}".repeat(bar_w.saturating_sub(fill)),
-                Style::default().fg(Color::Rgb(18, 25, 40)),
+                Style::default().fg(theme().header_bg),
             ),
             Span::styled(
                 format!(" {:>2}", count),
-                Style::default().fg(BRIGHT),
+                Style::default().fg(theme().text_bright),
             ),
         ]));
     }
@@ -306,17 +344,25 @@ fn draw_bind_panel(f: &mut Frame, area: Rect, bs: &BindStats, scanning: bool) {
 }
 
 /// Center panel: TCP exposure summary — how exposed is this system?
-fn draw_exposure_panel(f: &mut Frame, area: Rect, bs: &BindStats) {
+fn draw_exposure_panel(f: &mut Frame, area: Rect, bs: &BindStats, es: &ExposureStats) {
     let block = Block::default()
         .borders(Borders::RIGHT)
-        .border_style(Style::default().fg(Color::Rgb(20, 30, 50)))
-        .style(Style::default().bg(BG));
+        .border_style(Style::default().fg(theme().header_bg))
+        .style(Style::default().bg(theme().surface));
     let inner = block.inner(area);
     f.render_widget(block, area);
 
-    let exposed = bs.all_interfaces + bs.all_interfaces_v6
-        + bs.specific.iter().map(|(_, c)| *c).sum::<usize>();
-    let local_only = bs.loopback_v4 + bs.loopback_v6;
+    // Prefer the computed levels (firewall + UPnP aware); fall back to bind
+    // addresses until the first assessment.
+    let assessed = es.assessed > 0;
+    let (exposed, local_only) = if assessed {
+        (es.lan + es.internet, es.local + es.firewalled)
+    } else {
+        (
+            bs.all_interfaces + bs.all_interfaces_v6 + bs.specific.iter().map(|(_, c)| *c).sum::<usize>(),
+            bs.loopback_v4 + bs.loopback_v6,
+        )
+    };
     let total = bs.tcp_total;
 
     // Exposure ratio bar
@@ -326,92 +372,108 @@ fn draw_exposure_panel(f: &mut Frame, area: Rect, bs: &BindStats) {
     let remainder = bar_w.saturating_sub(exposed_fill + local_fill);
 
     let exposure_color = if exposed == 0 {
-        Color::Rgb(70, 195, 110) // green — fully local
+        theme().ok // green — fully local
     } else if exposed <= local_only {
-        Color::Rgb(220, 185, 60) // yellow — moderate
+        theme().gold // yellow — moderate
     } else {
-        Color::Rgb(255, 100, 80) // red — mostly exposed
+        theme().error // red — mostly exposed
     };
 
     let (level_label, level_color) = if exposed == 0 {
-        ("Minimal", Color::Rgb(70, 195, 110))
+        ("Minimal", theme().ok)
     } else if exposed <= 3 {
-        ("Low", Color::Rgb(100, 200, 140))
+        ("Low", theme().ok)
     } else if exposed <= local_only {
-        ("Moderate", Color::Rgb(220, 185, 60))
+        ("Moderate", theme().gold)
     } else {
-        ("High", Color::Rgb(255, 100, 80))
+        ("High", theme().error)
     };
 
     let l0 = Line::from(vec![
-        Span::styled(" TCP EXPOSURE ", Style::default().fg(Color::Rgb(20, 30, 50)).bg(exposure_color).add_modifier(Modifier::BOLD)),
+        Span::styled(" TCP EXPOSURE ", Style::default().fg(theme().header_bg).bg(exposure_color).add_modifier(Modifier::BOLD)),
         Span::styled(format!("  {}", level_label), Style::default().fg(level_color).add_modifier(Modifier::BOLD)),
     ]);
 
     let l1 = Line::from(vec![
         Span::styled(" ", Style::default()),
-        Span::styled("\u{2588}".repeat(exposed_fill), Style::default().fg(Color::Rgb(255, 100, 80))),
-        Span::styled("\u{2588}".repeat(local_fill), Style::default().fg(Color::Rgb(80, 180, 230))),
-        Span::styled("\u{2500}".repeat(remainder), Style::default().fg(Color::Rgb(18, 25, 40))),
+        Span::styled("\u{2588}".repeat(exposed_fill), Style::default().fg(theme().error)),
+        Span::styled("\u{2588}".repeat(local_fill), Style::default().fg(theme().info)),
+        Span::styled("\u{2500}".repeat(remainder), Style::default().fg(theme().header_bg)),
     ]);
 
     let l2 = Line::from(vec![
-        Span::styled(" \u{25CF} ", Style::default().fg(Color::Rgb(255, 100, 80))),
-        Span::styled(format!("{}", exposed), Style::default().fg(BRIGHT).add_modifier(Modifier::BOLD)),
-        Span::styled(" network-facing", Style::default().fg(TEXT)),
-        Span::styled("   \u{25CF} ", Style::default().fg(Color::Rgb(80, 180, 230))),
-        Span::styled(format!("{}", local_only), Style::default().fg(BRIGHT).add_modifier(Modifier::BOLD)),
-        Span::styled(" localhost", Style::default().fg(TEXT)),
+        Span::styled(" \u{25CF} ", Style::default().fg(theme().error)),
+        Span::styled(format!("{}", exposed), Style::default().fg(theme().text_bright).add_modifier(Modifier::BOLD)),
+        Span::styled(" network-facing", Style::default().fg(theme().text_secondary)),
+        Span::styled("   \u{25CF} ", Style::default().fg(theme().info)),
+        Span::styled(format!("{}", local_only), Style::default().fg(theme().text_bright).add_modifier(Modifier::BOLD)),
+        Span::styled(if assessed { " local/firewalled" } else { " localhost" }, Style::default().fg(theme().text_secondary)),
     ]);
 
     let pct = if total > 0 { exposed * 100 / total } else { 0 };
     let l3 = Line::from(vec![
         Span::styled(format!(" {}% ", pct), Style::default().fg(exposure_color).add_modifier(Modifier::BOLD)),
-        Span::styled(format!("of {} TCP ports reachable from network", total), Style::default().fg(LABEL)),
+        Span::styled(format!("of {} TCP ports reachable from network", total), Style::default().fg(theme().text_muted)),
     ]);
 
-    f.render_widget(Paragraph::new(vec![l0, l1, l2, l3]), inner);
+    let mut lines = vec![l0, l1, l2, l3];
+    if assessed {
+        let risky_color = if es.risky > 0 { theme().error } else { theme().text_faint };
+        let wan_color = if es.internet > 0 { theme().caution } else { theme().text_faint };
+        lines.push(Line::from(vec![
+            Span::styled(format!(" \u{26A0} {} risky", es.risky), Style::default().fg(risky_color)),
+            Span::styled(format!("   \u{21E1} {} via UPnP", es.internet), Style::default().fg(wan_color)),
+            Span::styled(format!("   {} firewalled", es.firewalled), Style::default().fg(theme().text_muted)),
+        ]));
+    }
+
+    f.render_widget(Paragraph::new(lines), inner);
 }
 
 /// Right panel: Quick stats summary
+#[allow(clippy::too_many_arguments)]
 fn draw_stats_panel(
     f: &mut Frame, area: Rect,
     total: usize, tcp: usize, udp: usize, up: usize,
-    total_conns: usize, tls_count: usize,
+    total_conns: usize, tls_count: usize, changes: usize,
 ) {
     let block = Block::default()
         .borders(Borders::NONE)
-        .style(Style::default().bg(BG));
+        .style(Style::default().bg(theme().surface));
     let inner = block.inner(area);
     f.render_widget(block, area);
 
     let down = total.saturating_sub(up);
 
     let l1 = Line::from(vec![
-        Span::styled(format!(" {} ", total), Style::default().fg(BRIGHT).add_modifier(Modifier::BOLD)),
-        Span::styled("services  ", Style::default().fg(TEXT)),
-        Span::styled(" TCP ", Style::default().fg(Color::Rgb(20, 30, 50)).bg(TCP_COLOR).add_modifier(Modifier::BOLD)),
-        Span::styled(format!(" {} ", tcp), Style::default().fg(BRIGHT)),
+        Span::styled(format!(" {} ", total), Style::default().fg(theme().text_bright).add_modifier(Modifier::BOLD)),
+        Span::styled("services  ", Style::default().fg(theme().text_secondary)),
+        Span::styled(" TCP ", Style::default().fg(theme().header_bg).bg(theme().info).add_modifier(Modifier::BOLD)),
+        Span::styled(format!(" {} ", tcp), Style::default().fg(theme().text_bright)),
     ]);
 
     let l2 = Line::from(vec![
-        Span::styled(" \u{25CF} ", Style::default().fg(GREEN)),
-        Span::styled(format!("{} up", up), Style::default().fg(GREEN)),
-        Span::styled("  \u{25CB} ", Style::default().fg(DIM)),
-        Span::styled(format!("{} silent", down), Style::default().fg(DIM)),
+        Span::styled(" \u{25CF} ", Style::default().fg(theme().ok)),
+        Span::styled(format!("{} up", up), Style::default().fg(theme().ok)),
+        Span::styled("  \u{25CB} ", Style::default().fg(theme().text_faint)),
+        Span::styled(format!("{} silent", down), Style::default().fg(theme().text_faint)),
     ]);
 
     let l3 = Line::from(vec![
-        Span::styled(" \u{21C4} ", Style::default().fg(Color::Rgb(100, 160, 230))),
-        Span::styled(format!("{} conn", total_conns), Style::default().fg(TEXT)),
-        Span::styled("  \u{1F512} ", Style::default().fg(GREEN)),
-        Span::styled(format!("{} tls", tls_count), Style::default().fg(TEXT)),
+        Span::styled(" \u{21C4} ", Style::default().fg(theme().info)),
+        Span::styled(format!("{} conn", total_conns), Style::default().fg(theme().text_secondary)),
+        Span::styled("  \u{1F512} ", Style::default().fg(theme().ok)),
+        Span::styled(format!("{} tls", tls_count), Style::default().fg(theme().text_secondary)),
     ]);
 
     let l4 = Line::from(vec![
         Span::styled(" ", Style::default()),
-        Span::styled(" UDP ", Style::default().fg(Color::Rgb(30, 30, 10)).bg(UDP_COLOR).add_modifier(Modifier::BOLD)),
-        Span::styled(format!(" {} ", udp), Style::default().fg(BRIGHT)),
+        Span::styled(" UDP ", Style::default().fg(theme().warn_bg).bg(theme().gold).add_modifier(Modifier::BOLD)),
+        Span::styled(format!(" {} ", udp), Style::default().fg(theme().text_bright)),
+        Span::styled(
+            format!("  \u{0394} {} change{}", changes, if changes == 1 { "" } else { "s" }),
+            Style::default().fg(if changes > 0 { theme().warn } else { theme().text_faint }),
+        ),
     ]);
 
     f.render_widget(Paragraph::new(vec![l1, l2, l3, l4]), inner);
@@ -420,15 +482,15 @@ fn draw_stats_panel(
 /// Filter bar (shown only when filter is active)
 fn draw_filter_bar(f: &mut Frame, area: Rect, filter: &str, filtered: usize) {
     let line = Line::from(vec![
-        Span::styled(" \u{1F50D} ", Style::default().fg(YELLOW)),
-        Span::styled(filter.to_string(), Style::default().fg(YELLOW).add_modifier(Modifier::BOLD)),
+        Span::styled(" \u{1F50D} ", Style::default().fg(theme().warn)),
+        Span::styled(filter.to_string(), Style::default().fg(theme().warn).add_modifier(Modifier::BOLD)),
         Span::styled(
             format!("  {} match{}", filtered, if filtered == 1 { "" } else { "es" }),
-            Style::default().fg(DIM),
+            Style::default().fg(theme().text_faint),
         ),
     ]);
     f.render_widget(
-        Paragraph::new(line).style(Style::default().bg(BG)),
+        Paragraph::new(line).style(Style::default().bg(theme().surface)),
         area,
     );
 }
@@ -438,7 +500,7 @@ fn draw_filter_bar(f: &mut Frame, area: Rect, filter: &str, filtered: usize) {
 fn draw_list(f: &mut Frame, area: Rect, rows: &[Row], selected: usize) {
     let block = Block::default()
         .borders(Borders::NONE)
-        .style(Style::default().bg(BG));
+        .style(Style::default().bg(theme().surface));
     let inner = block.inner(area);
     f.render_widget(block, area);
 
@@ -447,9 +509,9 @@ fn draw_list(f: &mut Frame, area: Rect, rows: &[Row], selected: usize) {
             Line::from(""),
             Line::from(Span::styled(
                 "  No listening services detected. Press 's' to scan.",
-                Style::default().fg(DIM),
+                Style::default().fg(theme().text_faint),
             )),
-        ]).style(Style::default().bg(BG));
+        ]).style(Style::default().bg(theme().surface));
         f.render_widget(msg, inner);
         return;
     }
@@ -481,15 +543,15 @@ fn draw_list(f: &mut Frame, area: Rect, rows: &[Row], selected: usize) {
                 render_proto_header(f, row_area, *proto, *count, w);
             }
 
-            Row::CardTop { server, idx, conns } => {
-                render_card_top(f, row_area, server, *idx == selected, *conns, w);
+            Row::CardTop { server, idx, conns, health } => {
+                render_card_top(f, row_area, server, *idx == selected, *conns, *health, w);
             }
             Row::CardBot { server, idx } => {
                 render_card_bot(f, row_area, server, *idx == selected, w);
             }
             Row::Spacer => {
                 f.render_widget(
-                    Paragraph::new("").style(Style::default().bg(BG)),
+                    Paragraph::new("").style(Style::default().bg(theme().surface)),
                     row_area,
                 );
             }
@@ -505,18 +567,246 @@ fn draw_list(f: &mut Frame, area: Rect, rows: &[Row], selected: usize) {
         let mut state = ScrollbarState::new(total.saturating_sub(h)).position(start);
         f.render_stateful_widget(
             Scrollbar::new(ScrollbarOrientation::VerticalRight)
-                .style(Style::default().fg(Color::Rgb(30, 50, 90))),
+                .style(Style::default().fg(theme().border)),
             sb_area, &mut state,
         );
     }
 }
 
+// ─── Changes since baseline ─────────────────────────────────────────────────
+
+fn draw_changes(f: &mut Frame, area: Rect, changes: &[ListenerChange], recorded: &str, selected: usize) {
+    let block = Block::default()
+        .borders(Borders::NONE)
+        .style(Style::default().bg(theme().surface));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let w = inner.width as usize;
+    let h = inner.height as usize;
+
+    let prefix = " \u{0394} CHANGES SINCE BASELINE ".to_string();
+    let suffix = if recorded.is_empty() { " not recorded yet ".to_string() } else { format!(" recorded {} ", recorded) };
+    let fill = "\u{2500}".repeat(w.saturating_sub(prefix.chars().count() + suffix.len()));
+    let mut lines = vec![Line::from(vec![
+        Span::styled(prefix, Style::default().fg(theme().warn).add_modifier(Modifier::BOLD)),
+        Span::styled(fill, Style::default().fg(theme().selected_bg)),
+        Span::styled(suffix, Style::default().fg(theme().text_muted)),
+    ])];
+
+    if changes.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            if recorded.is_empty() {
+                "  The baseline is recorded after the first full scan."
+            } else {
+                "  No changes \u{2014} every listener matches the baseline."
+            },
+            Style::default().fg(theme().text_faint),
+        )));
+        f.render_widget(Paragraph::new(lines), inner);
+        return;
+    }
+
+    let rows = h.saturating_sub(1);
+    let start = if selected < rows { 0 } else { selected + 1 - rows };
+    for (i, c) in changes.iter().enumerate().skip(start).take(rows) {
+        let (r, g, b) = c.color();
+        let color = theme().rgb(r, g, b);
+        let sel = i == selected;
+        let bg = if sel { theme().selected_bg } else if i % 2 == 0 { theme().surface_alt } else { theme().surface };
+        let marker = if sel { "\u{258C}" } else { " " };
+        let line = Line::from(vec![
+            Span::styled(marker, Style::default().fg(theme().info)),
+            Span::styled(format!(" {:<7} ", c.label()), Style::default().fg(theme().header_bg).bg(color).add_modifier(Modifier::BOLD)),
+            Span::styled(format!(" {:>3} :{:<5} ", c.proto, c.port), Style::default().fg(theme().text_secondary)),
+            Span::styled(trunc(&c.description(), w.saturating_sub(22)), Style::default().fg(if sel { theme().text_bright } else { theme().text_secondary })),
+        ]);
+        lines.push(line.style(Style::default().bg(bg)));
+    }
+
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+fn draw_change_detail(f: &mut Frame, area: Rect, change: Option<&ListenerChange>, baseline_len: usize) {
+    let block = Block::default()
+        .borders(Borders::TOP)
+        .border_style(Style::default().fg(theme().border))
+        .style(Style::default().bg(theme().surface));
+
+    let hint = Line::from(Span::styled(
+        format!("  Baseline: {} listeners  \u{00B7}  B accept current listeners as baseline  \u{00B7}  c back to services", baseline_len),
+        Style::default().fg(theme().text_faint),
+    ));
+    let Some(c) = change else {
+        f.render_widget(Paragraph::new(hint).block(block), area);
+        return;
+    };
+
+    let (r, g, b) = c.color();
+    let exe = if c.exe.is_empty() { "\u{2014}".to_string() } else { trunc(&c.exe, 70) };
+    let lines = vec![
+        Line::from(vec![
+            Span::styled(format!(" {} ", c.name), Style::default().fg(theme().text_bright).add_modifier(Modifier::BOLD)),
+            Span::styled(format!(" {} :{}", c.proto, c.port), Style::default().fg(theme().text_secondary)),
+            Span::styled(format!("  {}", c.label()), Style::default().fg(theme().rgb(r, g, b)).add_modifier(Modifier::BOLD)),
+        ]),
+        Line::from(vec![
+            Span::styled("  ", Style::default()),
+            Span::styled(exe, Style::default().fg(theme().text_dim)),
+        ]),
+        hint,
+    ];
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+// ─── UPnP port mappings ─────────────────────────────────────────────────────
+
+/// Who a mapping forwards to — this PC or a named LAN device — and the
+/// listener or identified service on the internal port.
+fn mapping_target(app: &App, m: &PortMapping) -> (String, Option<String>) {
+    let local = app.upnp_monitor.gateway.as_ref().and_then(|g| g.local_ip);
+    if local == Some(m.internal_client) {
+        let listener = app.servers_scanner.servers.iter()
+            .find(|s| s.port == m.internal_port && s.proto.label().eq_ignore_ascii_case(&m.protocol))
+            .map(|s| s.display_name());
+        return ("this PC".to_string(), listener);
+    }
+    let ip = std::net::IpAddr::V4(m.internal_client);
+    match app.network_scanner.devices.iter().find(|d| d.ip == ip) {
+        Some(d) => {
+            let name = d.custom_name.as_deref()
+                .or(d.hostname.as_deref())
+                .or(d.vendor.as_deref())
+                .unwrap_or("LAN device");
+            let service = d.services.iter().find(|s| s.port == m.internal_port).map(|s| s.product.clone());
+            (name.to_string(), service)
+        }
+        None => ("unknown host".to_string(), None),
+    }
+}
+
+fn draw_upnp(f: &mut Frame, area: Rect, app: &App, selected: usize) {
+    let upnp = &app.upnp_monitor;
+    let block = Block::default()
+        .borders(Borders::NONE)
+        .style(Style::default().bg(theme().surface));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let w = inner.width as usize;
+    let h = inner.height as usize;
+
+    let prefix = " \u{21C4} UPnP PORT MAPPINGS ".to_string();
+    let suffix = match &upnp.gateway {
+        Some(gw) => format!(" {} on {} ", upnp.mappings.len(), gw.addr.ip()),
+        None => " no gateway ".to_string(),
+    };
+    let fill = "\u{2500}".repeat(w.saturating_sub(prefix.chars().count() + suffix.len()));
+    let mut lines = vec![Line::from(vec![
+        Span::styled(prefix, Style::default().fg(theme().warn).add_modifier(Modifier::BOLD)),
+        Span::styled(fill, Style::default().fg(theme().selected_bg)),
+        Span::styled(suffix, Style::default().fg(theme().text_muted)),
+    ])];
+
+    if upnp.mappings.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            if upnp.gateway.is_some() {
+                "  The router has no port mappings."
+            } else if upnp.last_refresh.is_none() {
+                "  Looking for a UPnP Internet Gateway Device\u{2026}"
+            } else {
+                "  No UPnP Internet Gateway Device answered (UPnP may be off on the router)."
+            },
+            Style::default().fg(theme().text_faint),
+        )));
+        f.render_widget(Paragraph::new(lines), inner);
+        return;
+    }
+
+    lines.push(Line::from(Span::styled(
+        format!("  {:<11} {:<16} {:<18} {:<6} {:<20} {}", "EXTERNAL", "INTERNAL", "HOST", "PORT", "LISTENER", "DESCRIPTION"),
+        Style::default().fg(theme().text_muted).add_modifier(Modifier::BOLD),
+    )));
+
+    let rows = h.saturating_sub(2);
+    let start = if selected < rows { 0 } else { selected + 1 - rows };
+    for (i, m) in upnp.mappings.iter().enumerate().skip(start).take(rows) {
+        let sel = i == selected;
+        let bg = if sel { theme().selected_bg } else if i % 2 == 0 { theme().surface_alt } else { theme().surface };
+        let marker = if sel { "\u{258C}" } else { " " };
+        let (host, listener) = mapping_target(app, m);
+        let proto_color = if m.protocol == "UDP" { theme().gold } else { theme().info };
+        let (listener_text, listener_color) = match listener {
+            Some(l) => (l, theme().ok),
+            None => ("\u{2014}".to_string(), theme().text_faint),
+        };
+        let text = if !m.enabled { theme().text_faint } else if sel { theme().text_bright } else { theme().text_secondary };
+        let line = Line::from(vec![
+            Span::styled(marker, Style::default().fg(theme().info)),
+            Span::styled(format!(" {:<4}", m.protocol), Style::default().fg(proto_color)),
+            Span::styled(format!("{:<7}", m.external_port), Style::default().fg(if m.enabled { theme().error } else { theme().text_faint })),
+            Span::styled(format!("{:<16} ", m.internal_client), Style::default().fg(text)),
+            Span::styled(format!("{:<18} ", trunc(&host, 18)), Style::default().fg(text)),
+            Span::styled(format!("{:<6} ", m.internal_port), Style::default().fg(text)),
+            Span::styled(format!("{:<20} ", trunc(&listener_text, 20)), Style::default().fg(listener_color)),
+            Span::styled(trunc(&m.description, w.saturating_sub(78)), Style::default().fg(text)),
+        ]);
+        lines.push(line.style(Style::default().bg(bg)));
+    }
+
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+fn draw_upnp_detail(f: &mut Frame, area: Rect, app: &App, mapping: Option<&PortMapping>) {
+    let block = Block::default()
+        .borders(Borders::TOP)
+        .border_style(Style::default().fg(theme().border))
+        .style(Style::default().bg(theme().surface));
+
+    let upnp = &app.upnp_monitor;
+    let refreshed = match upnp.last_refresh {
+        Some(t) => format!("refreshed {}s ago", t.elapsed().as_secs()),
+        None => "not refreshed yet".to_string(),
+    };
+    let service = upnp.gateway.as_ref()
+        .and_then(|g| g.service_type.rsplit(':').nth(1).map(str::to_string))
+        .unwrap_or_else(|| "\u{2014}".to_string());
+    let hint = Line::from(Span::styled(
+        format!("  {}  \u{00B7}  {}  \u{00B7}  new mappings raise alerts  \u{00B7}  u back to services", service, refreshed),
+        Style::default().fg(theme().text_faint),
+    ));
+    let Some(m) = mapping else {
+        f.render_widget(Paragraph::new(hint).block(block), area);
+        return;
+    };
+
+    let remote = if m.remote_host.is_empty() { "any remote host".to_string() } else { format!("only from {}", m.remote_host) };
+    let lease = if m.lease_secs == 0 { "permanent".to_string() } else { format!("lease {}s", m.lease_secs) };
+    let state = if m.enabled { "enabled" } else { "disabled" };
+    let (host, listener) = mapping_target(app, m);
+    let lines = vec![
+        Line::from(vec![
+            Span::styled(format!(" {} {} ", m.protocol, m.external_port), Style::default().fg(theme().text_bright).add_modifier(Modifier::BOLD)),
+            Span::styled(format!(" \u{2192} {}:{} ({})", m.internal_client, m.internal_port, host), Style::default().fg(theme().text_secondary)),
+            Span::styled(format!("  {}", listener.unwrap_or_default()), Style::default().fg(theme().ok)),
+        ]),
+        Line::from(Span::styled(
+            format!("  {}  \u{00B7}  {}  \u{00B7}  {}  \u{00B7}  {}", trunc(&m.description, 50), remote, lease, state),
+            Style::default().fg(theme().text_dim),
+        )),
+        hint,
+    ];
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
 // ─── Protocol section header ────────────────────────────────────────────────
 
 fn render_proto_header(f: &mut Frame, area: Rect, proto: ListenProto, count: usize, w: usize) {
     let (label, color, icon) = match proto {
-        ListenProto::Tcp => ("TCP SERVICES", TCP_COLOR, "\u{25C6}"),
-        ListenProto::Udp => ("UDP SERVICES", UDP_COLOR, "\u{25C7}"),
+        ListenProto::Tcp => ("TCP SERVICES", theme().info, "\u{25C6}"),
+        ListenProto::Udp => ("UDP SERVICES", theme().gold, "\u{25C7}"),
     };
 
     let prefix = format!(" {} {} ", icon, label);
@@ -526,32 +816,35 @@ fn render_proto_header(f: &mut Frame, area: Rect, proto: ListenProto, count: usi
 
     let line = Line::from(vec![
         Span::styled(prefix, Style::default().fg(color).add_modifier(Modifier::BOLD)),
-        Span::styled(fill, Style::default().fg(Color::Rgb(25, 38, 60))),
+        Span::styled(fill, Style::default().fg(theme().selected_bg)),
         Span::styled(suffix, Style::default().fg(color).add_modifier(Modifier::BOLD)),
     ]);
     f.render_widget(
-        Paragraph::new(line).style(Style::default().bg(Color::Rgb(12, 16, 30))),
+        Paragraph::new(line).style(Style::default().bg(theme().surface)),
         area,
     );
 }
 
 // ─── Card line 1: icon + name + port + status ───────────────────────────────
 
-fn render_card_top(f: &mut Frame, area: Rect, s: &ListeningPort, sel: bool, conns: usize, w: usize) {
+fn render_card_top(
+    f: &mut Frame, area: Rect, s: &ListeningPort, sel: bool, conns: usize,
+    health: Option<bool>, w: usize,
+) {
     let (kr, kg, kb) = s.server_kind.color();
-    let kc = Color::Rgb(kr, kg, kb);
+    let kc = theme().rgb(kr, kg, kb);
     let proto_color = match s.proto {
-        ListenProto::Tcp => TCP_COLOR,
-        ListenProto::Udp => UDP_COLOR,
+        ListenProto::Tcp => theme().info,
+        ListenProto::Udp => theme().gold,
     };
 
     let mut spans: Vec<Span> = Vec::new();
 
     // Left accent bar + selection
     if sel {
-        spans.push(Span::styled(" \u{2588}\u{25B8}", Style::default().fg(SEL_ACCENT)));
+        spans.push(Span::styled(" \u{2588}\u{25B8}", Style::default().fg(theme().info)));
     } else {
-        spans.push(Span::styled(" \u{2502} ", Style::default().fg(Color::Rgb(25, 35, 55))));
+        spans.push(Span::styled(" \u{2502} ", Style::default().fg(theme().header_bg)));
     }
 
     // Icon
@@ -575,15 +868,15 @@ fn render_card_top(f: &mut Frame, area: Rect, s: &ListeningPort, sel: bool, conn
     {
         use std::net::IpAddr;
         let (bind_label, bind_fg, bind_bg) = match s.bind_addr {
-            IpAddr::V4(v4) if v4.is_unspecified() => ("*", Color::Rgb(20, 10, 10), Color::Rgb(255, 100, 80)),
-            IpAddr::V6(v6) if v6.is_unspecified() => ("*", Color::Rgb(20, 10, 10), Color::Rgb(220, 140, 80)),
-            IpAddr::V4(v4) if v4.is_loopback() => ("127.0.0.1", Color::Rgb(10, 20, 30), Color::Rgb(80, 180, 230)),
-            IpAddr::V6(v6) if v6.is_loopback() => ("::1", Color::Rgb(10, 20, 30), Color::Rgb(100, 160, 210)),
+            IpAddr::V4(v4) if v4.is_unspecified() => ("*", theme().error_bg, theme().error),
+            IpAddr::V6(v6) if v6.is_unspecified() => ("*", theme().error_bg, theme().caution),
+            IpAddr::V4(v4) if v4.is_loopback() => ("127.0.0.1", theme().surface, theme().info),
+            IpAddr::V6(v6) if v6.is_loopback() => ("::1", theme().surface, theme().info),
             other => {
                 let s = other.to_string();
                 // Leak is fine: these are a small fixed set of system IPs
                 let label: &'static str = Box::leak(s.into_boxed_str());
-                (label, Color::Rgb(20, 20, 10), Color::Rgb(200, 180, 100))
+                (label, theme().warn_bg, theme().gold)
             }
         };
         spans.push(Span::styled(
@@ -592,36 +885,65 @@ fn render_card_top(f: &mut Frame, area: Rect, s: &ListeningPort, sel: bool, conn
         ));
     }
 
+    // Exposure level (bind + firewall + UPnP) and risk marker
+    if let Some(ref e) = s.exposure {
+        let (r, g, b) = e.level.color();
+        spans.push(Span::styled(
+            format!(" {}", e.level.short_label()),
+            Style::default().fg(theme().rgb(r, g, b)).add_modifier(Modifier::BOLD),
+        ));
+        if let Some(sev) = e.worst_risk() {
+            spans.push(Span::styled(" \u{26A0}", Style::default().fg(sev.color()).add_modifier(Modifier::BOLD)));
+        }
+    }
+
     // Status
     spans.push(Span::styled("  ", Style::default()));
     if s.is_responsive {
-        spans.push(Span::styled("\u{25CF} UP", Style::default().fg(GREEN).add_modifier(Modifier::BOLD)));
+        spans.push(Span::styled("\u{25CF} UP", Style::default().fg(theme().ok).add_modifier(Modifier::BOLD)));
     } else {
-        spans.push(Span::styled("\u{25CB} \u{2014}\u{2014}", Style::default().fg(DIM)));
+        spans.push(Span::styled("\u{25CB} \u{2014}\u{2014}", Style::default().fg(theme().text_faint)));
+    }
+
+    // Health check: last run passed / failed
+    if let Some(ok) = health {
+        spans.push(Span::styled(" \u{2665}", Style::default().fg(if ok { theme().ok } else { theme().error }).add_modifier(Modifier::BOLD)));
     }
 
     // TLS
     if s.details.contains("TLS: yes") {
-        spans.push(Span::styled("  \u{1F512}", Style::default().fg(GREEN)));
+        spans.push(Span::styled("  \u{1F512}", Style::default().fg(theme().ok)));
     }
 
     // Version badge
     if let Some(ref ver) = s.version {
-        spans.push(Span::styled(format!("  v{}", trunc(ver, 10)), Style::default().fg(YELLOW)));
+        spans.push(Span::styled(format!("  v{}", trunc(ver, 10)), Style::default().fg(theme().warn)));
+    }
+
+    // Advisory badges: known CVEs for this version, end-of-life release
+    let cves = s.advisories.iter().filter(|a| !a.is_eol()).count();
+    if cves > 0 {
+        let label = if cves == 1 { " CVE ".to_string() } else { format!(" {} CVE ", cves) };
+        spans.push(Span::styled(" ", Style::default()));
+        spans.push(Span::styled(label, Style::default().fg(theme().error_bg).bg(theme().error).add_modifier(Modifier::BOLD)));
+    }
+    if s.advisories.iter().any(|a| a.is_eol()) {
+        spans.push(Span::styled(" ", Style::default()));
+        spans.push(Span::styled(" EOL ", Style::default().fg(theme().warn_bg).bg(theme().warn).add_modifier(Modifier::BOLD)));
     }
 
     // Active connections
     if conns > 0 {
-        let cc = if conns > 10 { Color::Rgb(255, 140, 90) } else { GREEN };
+        let cc = if conns > 10 { theme().caution } else { theme().ok };
         spans.push(Span::styled(
             format!("  \u{2022}{}conn", conns),
             Style::default().fg(cc),
         ));
     }
 
-    let bg = if sel { SEL_BG }
-    else if !s.is_responsive { UNRESPONSIVE_BG }
-    else { CARD_BG };
+    let bg = if sel { theme().selected_bg }
+    else if !s.is_responsive { theme().bg }
+    else { theme().surface_alt };
 
     f.render_widget(
         Paragraph::new(Line::from(spans)).style(Style::default().bg(bg)),
@@ -639,9 +961,9 @@ fn render_card_bot(f: &mut Frame, area: Rect, s: &ListeningPort, sel: bool, w: u
 
     // Left accent continuation
     if sel {
-        spans.push(Span::styled(" \u{2588} ", Style::default().fg(SEL_ACCENT)));
+        spans.push(Span::styled(" \u{2588} ", Style::default().fg(theme().info)));
     } else {
-        spans.push(Span::styled(" \u{2502} ", Style::default().fg(Color::Rgb(25, 35, 55))));
+        spans.push(Span::styled(" \u{2502} ", Style::default().fg(theme().header_bg)));
     }
 
     // Category tag (colored pill)
@@ -659,11 +981,11 @@ fn render_card_bot(f: &mut Frame, area: Rect, s: &ListeningPort, sel: bool, w: u
             .unwrap_or(&s.process_name);
         spans.push(Span::styled(
             trunc(stem, 18),
-            Style::default().fg(Color::Rgb(100, 170, 110)),
+            Style::default().fg(theme().ok),
         ));
         spans.push(Span::styled(
             format!("({})", s.pid),
-            Style::default().fg(LABEL),
+            Style::default().fg(theme().text_muted),
         ));
     }
 
@@ -674,15 +996,15 @@ fn render_card_bot(f: &mut Frame, area: Rect, s: &ListeningPort, sel: bool, w: u
         spans.push(Span::styled("  ", Style::default()));
         let desc = s.display_description();
         if desc.len() > 3 && desc != s.server_kind.description() {
-            spans.push(Span::styled(trunc(&desc, remaining), Style::default().fg(LABEL)));
+            spans.push(Span::styled(trunc(&desc, remaining), Style::default().fg(theme().text_muted)));
         } else if !s.exe_path.is_empty() {
-            spans.push(Span::styled(trunc(&s.exe_path, remaining), Style::default().fg(DIM)));
+            spans.push(Span::styled(trunc(&s.exe_path, remaining), Style::default().fg(theme().text_faint)));
         }
     }
 
-    let bg = if sel { SEL_BG }
-    else if !s.is_responsive { UNRESPONSIVE_BG }
-    else { CARD_ALT };
+    let bg = if sel { theme().selected_bg }
+    else if !s.is_responsive { theme().bg }
+    else { theme().surface };
 
     f.render_widget(
         Paragraph::new(Line::from(spans)).style(Style::default().bg(bg)),
@@ -696,16 +1018,17 @@ fn draw_detail(
     f: &mut Frame, area: Rect,
     filtered: &[&ListeningPort], selected: usize,
     conn_counts: &HashMap<u16, usize>,
+    health: &HealthMonitor,
 ) {
     let block = Block::default()
         .borders(Borders::TOP)
-        .border_style(Style::default().fg(BORDER))
-        .style(Style::default().bg(BG));
+        .border_style(Style::default().fg(theme().border))
+        .style(Style::default().bg(theme().surface));
 
     if filtered.is_empty() || selected >= filtered.len() {
         let empty = Paragraph::new(Line::from(Span::styled(
             "  \u{2191}\u{2193} browse  Enter detail  s scan  o open folder  y copy path  \u{2190}\u{2192} collapse/expand",
-            Style::default().fg(DIM),
+            Style::default().fg(theme().text_faint),
         ))).block(block);
         f.render_widget(empty, area);
         return;
@@ -714,9 +1037,9 @@ fn draw_detail(
     let s = filtered[selected];
     let conns = conn_counts.get(&s.port).copied().unwrap_or(0);
     let (kr, kg, kb) = s.server_kind.color();
-    let kc = Color::Rgb(kr, kg, kb);
-    let pipe = || Span::styled(" \u{2502} ", Style::default().fg(Color::Rgb(25, 35, 55)));
-    let proto_color = match s.proto { ListenProto::Tcp => TCP_COLOR, ListenProto::Udp => UDP_COLOR };
+    let kc = theme().rgb(kr, kg, kb);
+    let pipe = || Span::styled(" \u{2502} ", Style::default().fg(theme().header_bg));
+    let proto_color = match s.proto { ListenProto::Tcp => theme().info, ListenProto::Udp => theme().gold };
 
     // Line 1: Identity + status
     let proto_tag = match s.proto { ListenProto::Tcp => "TCP", ListenProto::Udp => "UDP" };
@@ -724,59 +1047,104 @@ fn draw_detail(
         Span::styled(format!(" {} ", s.display_icon()), Style::default().fg(kc)),
         Span::styled(s.display_name(), Style::default().fg(kc).add_modifier(Modifier::BOLD)),
         pipe(),
-        Span::styled(format!(" {} ", proto_tag), Style::default().fg(Color::Rgb(20, 25, 40)).bg(proto_color).add_modifier(Modifier::BOLD)),
+        Span::styled(format!(" {} ", proto_tag), Style::default().fg(theme().header_bg).bg(proto_color).add_modifier(Modifier::BOLD)),
         Span::styled(format!(" :{}", s.port), Style::default().fg(proto_color).add_modifier(Modifier::BOLD)),
     ];
     if let Some(ref v) = s.version {
         l1.push(pipe());
-        l1.push(Span::styled(format!("v{}", v), Style::default().fg(YELLOW)));
+        l1.push(Span::styled(format!("v{}", v), Style::default().fg(theme().warn)));
     }
     l1.push(pipe());
     l1.push(Span::styled(
         if s.is_responsive { "\u{25CF} Responding" } else { "\u{25CB} Not responding" },
-        Style::default().fg(if s.is_responsive { GREEN } else { DIM }),
+        Style::default().fg(if s.is_responsive { theme().ok } else { theme().text_faint }),
     ));
     if conns > 0 {
         l1.push(pipe());
-        l1.push(Span::styled(format!("{} connection{}", conns, if conns == 1 { "" } else { "s" }), Style::default().fg(TEXT)));
+        l1.push(Span::styled(format!("{} connection{}", conns, if conns == 1 { "" } else { "s" }), Style::default().fg(theme().text_secondary)));
     }
     let has_tls = s.details.contains("TLS: yes");
     if has_tls {
         l1.push(pipe());
-        l1.push(Span::styled("\u{1F512} TLS", Style::default().fg(GREEN).add_modifier(Modifier::BOLD)));
+        l1.push(Span::styled("\u{1F512} TLS", Style::default().fg(theme().ok).add_modifier(Modifier::BOLD)));
     }
 
     // Line 2: Process + path
     let exe = if s.exe_path.is_empty() { "\u{2014}".to_string() } else { trunc(&s.exe_path, 70) };
     let l2 = Line::from(vec![
         Span::styled("  ", Style::default()),
-        Span::styled(&s.process_name, Style::default().fg(Color::Rgb(100, 170, 110))),
-        Span::styled(format!(" ({})", s.pid), Style::default().fg(LABEL)),
+        Span::styled(&s.process_name, Style::default().fg(theme().ok)),
+        Span::styled(format!(" ({})", s.pid), Style::default().fg(theme().text_muted)),
         pipe(),
-        Span::styled(exe, Style::default().fg(Color::Rgb(90, 105, 140))),
+        Span::styled(exe, Style::default().fg(theme().text_dim)),
     ]);
 
     // Line 3: Detected techs / banner / description
     let mut l3: Vec<Span> = vec![Span::styled("  ", Style::default())];
     if !s.detected_techs.is_empty() {
-        l3.push(Span::styled("Tech: ", Style::default().fg(LABEL)));
+        l3.push(Span::styled("Tech: ", Style::default().fg(theme().text_muted)));
         for (i, t) in s.detected_techs.iter().take(6).enumerate() {
-            if i > 0 { l3.push(Span::styled(" \u{00B7} ", Style::default().fg(DIM))); }
+            if i > 0 { l3.push(Span::styled(" \u{00B7} ", Style::default().fg(theme().text_faint))); }
             let label = if t.version.is_empty() { t.name.clone() } else { format!("{}/{}", t.name, t.version) };
-            l3.push(Span::styled(label, Style::default().fg(Color::Rgb(160, 145, 230))));
+            l3.push(Span::styled(label, Style::default().fg(theme().purple)));
         }
         if s.detected_techs.len() > 6 {
-            l3.push(Span::styled(format!("  +{}", s.detected_techs.len() - 6), Style::default().fg(DIM)));
+            l3.push(Span::styled(format!("  +{}", s.detected_techs.len() - 6), Style::default().fg(theme().text_faint)));
         }
     } else if let Some(ref title) = s.http_title {
-        l3.push(Span::styled(format!("Title: \u{201C}{}\u{201D}", trunc(title, 50)), Style::default().fg(TEXT)));
+        l3.push(Span::styled(format!("Title: \u{201C}{}\u{201D}", trunc(title, 50)), Style::default().fg(theme().text_secondary)));
     } else if let Some(ref banner) = s.banner {
         let clean = banner.replace(['\r', '\n'], " ");
-        l3.push(Span::styled(format!("Banner: {}", trunc(&clean, 55)), Style::default().fg(LABEL)));
+        l3.push(Span::styled(format!("Banner: {}", trunc(&clean, 55)), Style::default().fg(theme().text_muted)));
     } else {
-        l3.push(Span::styled(s.display_description(), Style::default().fg(LABEL)));
+        l3.push(Span::styled(s.display_description(), Style::default().fg(theme().text_muted)));
     }
 
-    let detail = Paragraph::new(vec![Line::from(l1), l2, Line::from(l3)]).block(block);
+    let mut lines = vec![Line::from(l1), l2, Line::from(l3)];
+
+    // Line 4: Health check latency history
+    if let Some(h) = health.for_port(s.port) {
+        let mut l4 = vec![
+            Span::styled("  \u{2665} ", Style::default().fg(theme().text_muted)),
+            Span::styled(h.check.describe(), Style::default().fg(theme().text_secondary)),
+            Span::styled(" \u{258F}", Style::default().fg(theme().text_faint)),
+        ];
+        l4.extend(latency_sparkline(h.history.iter().copied(), 40));
+        match (h.is_passing(), &h.last_error) {
+            (None, _) => l4.push(Span::styled("  pending", Style::default().fg(theme().text_faint))),
+            (Some(false), Some(e)) => {
+                l4.push(Span::styled(format!("  \u{2717} {}", trunc(e, 40)), Style::default().fg(theme().error)));
+            }
+            _ => {
+                let ms = h.last_latency().unwrap_or(0);
+                l4.push(Span::styled(format!("  {} ms", ms), Style::default().fg(theme().ok)));
+            }
+        }
+        lines.push(Line::from(l4));
+    }
+
+    let detail = Paragraph::new(lines).block(block);
     f.render_widget(detail, area);
 }
+
+/// Latency sparkline of the last `width` health check runs; failed runs
+/// show as a red cross. Shared with the server detail popup.
+pub fn latency_sparkline(history: impl DoubleEndedIterator<Item = Option<u64>>, width: usize) -> Vec<Span<'static>> {
+    let mut samples: Vec<Option<u64>> = history.rev().take(width).collect();
+    samples.reverse();
+    if samples.is_empty() {
+        return vec![Span::styled("\u{2581}".repeat(width), Style::default().fg(theme().text_faint))];
+    }
+    let max_val = samples.iter().flatten().copied().max().unwrap_or(1).max(1);
+    let chars = ['\u{2581}', '\u{2582}', '\u{2583}', '\u{2584}', '\u{2585}', '\u{2586}', '\u{2587}', '\u{2588}'];
+    samples
+        .iter()
+        .map(|sample| match sample {
+            Some(ms) => {
+                let idx = ((*ms as f64 / max_val as f64) * 7.0) as usize;
+                Span::styled(chars[idx.min(7)].to_string(), Style::default().fg(theme().info))
+            }
+            None => Span::styled("\u{2717}", Style::default().fg(theme().error)),
+        })
+        .collect()
+}
@@ -11,6 +11,7 @@ use super::widgets::conn_stats::{draw_conn_stats, ConnStats};
 use super::widgets::data_rates::draw_data_rates;
 use super::widgets::network_pulse::draw_network_pulse;
 use super::widgets::protocol_cloud::draw_protocol_cloud;
+use crate::ui::theme::theme;
 
 /// Header section: sparklines (left 40%) + 4 KPI widgets (right 60%).
 pub fn draw_speed_section(f: &mut Frame, area: Rect, app: &App) {
@@ -78,35 +79,35 @@ fn draw_sparklines(f: &mut Frame, area: Rect, app: &App) {
                     Span::styled(
                         " ▼ ",
                         Style::default()
-                            .fg(Color::Rgb(50, 160, 255))
+                            .fg(theme().download)
                             .add_modifier(Modifier::BOLD),
                     ),
                     Span::styled(
                         format_speed(app.current_down_speed),
                         Style::default()
-                            .fg(Color::Rgb(80, 210, 255))
+                            .fg(theme().accent)
                             .add_modifier(Modifier::BOLD),
                     ),
                     Span::styled(
                         "  pk:",
-                        Style::default().fg(Color::Rgb(55, 65, 85)),
+                        Style::default().fg(theme().text_faint),
                     ),
                     Span::styled(
                         format_speed(app.peak_down),
-                        Style::default().fg(Color::Rgb(100, 120, 160)),
+                        Style::default().fg(theme().text_dim),
                     ),
                     Span::styled(
                         "  tot:",
-                        Style::default().fg(Color::Rgb(55, 65, 85)),
+                        Style::default().fg(theme().text_faint),
                     ),
                     Span::styled(
                         format!("{} ", format_bytes(app.total_down)),
-                        Style::default().fg(Color::Rgb(100, 120, 160)),
+                        Style::default().fg(theme().text_dim),
                     ),
                 ]))
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Rgb(30, 50, 85)))
-                .style(Style::default().bg(Color::Rgb(8, 12, 24))),
+                .border_style(Style::default().fg(theme().border))
+                .style(Style::default().bg(theme().bg)),
         );
     f.render_widget(down_sparkline, chunks[0]);
 
@@ -137,35 +138,35 @@ fn draw_sparklines(f: &mut Frame, area: Rect, app: &App) {
                     Span::styled(
                         " ▲ ",
                         Style::default()
-                            .fg(Color::Rgb(180, 100, 255))
+                            .fg(theme().upload)
                             .add_modifier(Modifier::BOLD),
                     ),
                     Span::styled(
                         format_speed(app.current_up_speed),
                         Style::default()
-                            .fg(Color::Rgb(210, 160, 255))
+                            .fg(theme().purple)
                             .add_modifier(Modifier::BOLD),
                     ),
                     Span::styled(
                         "  pk:",
-                        Style::default().fg(Color::Rgb(55, 65, 85)),
+                        Style::default().fg(theme().text_faint),
                     ),
                     Span::styled(
                         format_speed(app.peak_up),
-                        Style::default().fg(Color::Rgb(100, 120, 160)),
+                        Style::default().fg(theme().text_dim),
                     ),
                     Span::styled(
                         "  tot:",
-                        Style::default().fg(Color::Rgb(55, 65, 85)),
+                        Style::default().fg(theme().text_faint),
                     ),
                     Span::styled(
                         format!("{} ", format_bytes(app.total_up)),
-                        Style::default().fg(Color::Rgb(100, 120, 160)),
+                        Style::default().fg(theme().text_dim),
                     ),
                 ]))
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Rgb(30, 50, 85)))
-                .style(Style::default().bg(Color::Rgb(8, 12, 24))),
+                .border_style(Style::default().fg(theme().border))
+                .style(Style::default().bg(theme().bg)),
         );
     f.render_widget(up_sparkline, chunks[1]);
 }
@@ -175,26 +176,28 @@ fn draw_sparklines(f: &mut Frame, area: Rect, app: &App) {
 
 /// Blue gradient based on download speed
 fn speed_color(speed: f64) -> Color {
+    let t = theme();
     if speed > 1_000_000.0 {
-        Color::Rgb(0, 255, 255) // Bright cyan for MB/s+
+        t.accent // Bright cyan for MB/s+
     } else if speed > 100_000.0 {
-        Color::Rgb(30, 190, 255)
+        t.download
     } else if speed > 10_000.0 {
-        Color::Rgb(50, 140, 230)
+        t.fade(t.download, 0.85)
     } else {
-        Color::Rgb(40, 110, 200) // Dim blue for idle
+        t.fade(t.download, 0.7) // Dim blue for idle
     }
 }
 
 /// Purple gradient based on upload speed
 fn speed_color_warm(speed: f64) -> Color {
+    let t = theme();
     if speed > 1_000_000.0 {
-        Color::Rgb(255, 130, 255) // Bright magenta
+        t.pink // Bright magenta
     } else if speed > 100_000.0 {
-        Color::Rgb(210, 120, 255)
+        t.upload
     } else if speed > 10_000.0 {
-        Color::Rgb(170, 100, 230)
+        t.fade(t.upload, 0.85)
     } else {
-        Color::Rgb(140, 80, 200) // Dim purple for idle
+        t.fade(t.upload, 0.7) // Dim purple for idle
     }
 }
@@ -1,48 +1,39 @@
 use ratatui::layout::Rect;
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
 use ratatui::Frame;
 
 use crate::app::App;
+use crate::keymap::{Action, Context};
 use crate::types::BottomTab;
+use crate::ui::theme::theme;
 
 /// Tab menu — rendered above the tab content area.
 pub fn draw_tab_menu(f: &mut Frame, area: Rect, app: &App) {
-    let tabs = [
-        BottomTab::Dashboard,
-        BottomTab::Connections,
-        BottomTab::Servers,
-        BottomTab::Packets,
-        BottomTab::Topology,
-        BottomTab::Alerts,
-        BottomTab::Firewall,
-        BottomTab::Devices,
-        BottomTab::Networks,
-    ];
     let mut tab_spans: Vec<Span<'static>> = Vec::new();
-    for (i, tab) in tabs.iter().enumerate() {
+    for (i, tab) in BottomTab::ALL.iter().enumerate() {
         if i > 0 {
-            tab_spans.push(Span::styled(" │ ", Style::default().fg(Color::Rgb(40, 55, 80))));
+            tab_spans.push(Span::styled(" │ ", Style::default().fg(theme().divider)));
         }
         let label = format!(" {} {} ", i + 1, tab.label());
         if *tab == app.bottom_tab {
             tab_spans.push(Span::styled(
                 label,
                 Style::default()
-                    .fg(Color::Rgb(255, 220, 120))
-                    .bg(Color::Rgb(30, 42, 70))
+                    .fg(theme().highlight)
+                    .bg(theme().selected_bg)
                     .add_modifier(Modifier::BOLD),
             ));
         } else {
             tab_spans.push(Span::styled(
                 label,
-                Style::default().fg(Color::Rgb(80, 100, 135)),
+                Style::default().fg(theme().text_muted),
             ));
         }
     }
     let tab_line = Paragraph::new(Line::from(tab_spans))
-        .style(Style::default().bg(Color::Rgb(18, 25, 45)));
+        .style(Style::default().bg(theme().header_bg));
     f.render_widget(tab_line, area);
 }
 
@@ -50,85 +41,115 @@ pub fn draw_tab_menu(f: &mut Frame, area: Rect, app: &App) {
 pub fn draw_key_hints(f: &mut Frame, area: Rect, app: &App) {
     // If popup is open, show dismiss hint only
     if app.detail_popup.is_some() {
+        let keys = [Action::Confirm, Action::Close].iter()
+            .filter_map(|a| app.keymap.label(Context::Popup, *a))
+            .collect::<Vec<_>>()
+            .join(" / ");
         let spans = vec![
             Span::styled(
-                " Enter / Esc ",
-                Style::default().fg(Color::Rgb(255, 200, 80)).add_modifier(Modifier::BOLD),
+                format!(" {} ", keys),
+                Style::default().fg(theme().highlight).add_modifier(Modifier::BOLD),
             ),
-            Span::styled("Close detail  ", Style::default().fg(Color::Rgb(95, 108, 135))),
+            Span::styled("Close detail  ", Style::default().fg(theme().text_dim)),
         ];
         let paragraph = Paragraph::new(Line::from(spans))
-            .style(Style::default().bg(Color::Rgb(20, 28, 50)));
+            .style(Style::default().bg(theme().header_bg));
         f.render_widget(paragraph, area);
         return;
     }
 
     let incognito_label = format!("Incognito:{}", if app.incognito { "ON" } else { "OFF" });
+    let global = |actions: &[Action], desc: &str| action_span(app, Context::Global, actions, desc);
     let common_keys = vec![
-        key_span("q", "Quit"),
-        key_span("Tab", "Switch"),
-        key_span("\u{2191}\u{2193}", "Select"),
-        key_span("Enter", "Detail"),
-        key_span("i", &incognito_label),
+        global(&[Action::Quit], "Quit"),
+        global(&[Action::NextTab], "Switch"),
+        global(&[Action::ScrollUp, Action::ScrollDown], "Select"),
+        global(&[Action::Detail], "Detail"),
+        global(&[Action::Incognito], &incognito_label),
+        global(&[Action::Help], "Help"),
     ];
 
+    let tab = |actions: &[Action], desc: &str| action_span(app, Context::Tab(app.bottom_tab), actions, desc);
+
     let tab_keys = match app.bottom_tab {
         BottomTab::Dashboard => vec![
-            key_span("1-4", "Time Range"),
+            tab(&[Action::Range5m, Action::Range15m, Action::Range1h, Action::Range24h], "Time Range"),
         ],
         BottomTab::Connections => vec![
-            key_span("1-5", "Sort"),
-            key_span("b", "Block"),
-            key_span("l", &format!("Listen:{}", if app.show_listen { "ON" } else { "OFF" })),
-            key_span("x", &format!("{}", if app.hide_localhost_conn { "Show Local" } else { "Hide Local" })),
+            tab(&[Action::Sort1, Action::Sort2, Action::Sort3, Action::Sort4, Action::Sort5], "Sort"),
+            tab(&[Action::Block], "Block"),
+            tab(&[Action::Trace], "Trace"),
+            tab(&[Action::ToggleListen], &format!("Listen:{}", if app.show_listen { "ON" } else { "OFF" })),
+            tab(&[Action::ToggleLocalhost], &format!("{}", if app.hide_localhost_conn { "Show Local" } else { "Hide Local" })),
             key_span("f", "Filter"),
-            key_span("Esc", "Clear"),
+            tab(&[Action::ClearFilter], "Clear"),
         ],
         BottomTab::Servers => vec![
-            key_span("s", "Scan"),
-            key_span("o", "Open"),
-            key_span("y", "Copy"),
-            key_span("p", "Folder"),
+            tab(&[Action::Scan], "Scan"),
+            tab(&[Action::OpenFolder], "Open"),
+            tab(&[Action::CopyPath], "Copy"),
+            tab(&[Action::CopyFolder], "Folder"),
             key_span("\u{2190}", "Collapse"),
             key_span("\u{2192}", "Expand"),
-            key_span("1-3", "Sort"),
+            tab(&[Action::Sort1, Action::Sort2, Action::Sort3], "Sort"),
+            tab(&[Action::ReloadDatabases], "Reload DB"),
+            tab(&[Action::ToggleChanges], if app.servers_scanner.show_changes { "Services" } else { "Changes" }),
+            tab(&[Action::ToggleUpnp], if app.servers_scanner.show_upnp { "Services" } else { "UPnP" }),
+            tab(&[Action::AcceptBaseline], "Accept Baseline"),
+            tab(&[Action::HealthCheck], "Health Check"),
         ],
         BottomTab::Alerts => vec![
-            key_span("\u{2190}\u{2192}", "Pane"),
-            key_span("\u{2191}\u{2193}", "Scroll"),
-            key_span("Enter", "Detail"),
-            key_span("r", "Mark Read"),
-            key_span("c", "Clear All"),
-            key_span("z", &format!("{}", if app.alert_engine.is_snoozed() { "Unsnooze" } else { "Snooze 5m" })),
+            tab(&[Action::PrevPane, Action::NextPane], "Pane"),
+            global(&[Action::ScrollUp, Action::ScrollDown], "Scroll"),
+            global(&[Action::Detail], "Detail"),
+            tab(&[Action::MarkRead], "Mark Read"),
+            tab(&[Action::Clear], "Clear All"),
+            tab(&[Action::Snooze], &format!("{}", if app.alert_engine.is_snoozed() { "Unsnooze" } else { "Snooze 5m" })),
         ],
         BottomTab::Firewall => vec![
-            key_span("Enter", "Detail"),
-            key_span("d", &format!("Policy:{}", if app.firewall_manager.default_deny { "Deny-All" } else { "Allow-All" })),
-            key_span("1-4", "Sort"),
-            key_span("r", "Refresh"),
-            key_span("e", "Export"),
+            global(&[Action::Detail], "Detail"),
+            tab(&[Action::DefaultPolicy], &format!("Policy:{}", if app.firewall_manager.default_deny { "Deny-All" } else { "Allow-All" })),
+            tab(&[Action::Sort1, Action::Sort2, Action::Sort3, Action::Sort4], "Sort"),
+            tab(&[Action::Refresh], "Refresh"),
+            tab(&[Action::Export], "Export"),
             key_span("f", "Filter"),
-            key_span("x", "Reset All"),
-            key_span("Esc", "Clear"),
+            tab(&[Action::ResetRules], "Reset All"),
+            tab(&[Action::ClearFilter], "Clear"),
         ],
         BottomTab::Devices => vec![
-            key_span("s", "Scan Now"),
-            key_span("r", "Rename"),
-            key_span("o", &format!("Offline:{}", if app.hide_offline_devices { "Hidden" } else { "Shown" })),
+            tab(&[Action::Scan], "Scan Now"),
+            tab(&[Action::Rename], "Rename"),
+            tab(&[Action::Wake], "Wake"),
+            tab(&[Action::SecureOn], "SecureOn"),
+            tab(&[Action::ToggleOffline], &format!("Offline:{}", if app.hide_offline_devices { "Hidden" } else { "Shown" })),
+            tab(&[Action::TypeFilter], &format!("Type:{}", app.device_type_filter.map_or("All", |t| t.label()))),
+            tab(&[Action::ImportOui], "OUI Import"),
+            tab(&[Action::Trust], "Trust"),
+            tab(&[Action::ToggleQuarantine], &format!("View:{}", if app.quarantine_view { "Untrusted" } else { "All" })),
+            tab(&[Action::Export, Action::Import], "Allowlist Export/Import"),
+            tab(&[Action::ToggleServices], if app.dnssd_view { "Devices" } else { "Services" }),
+            tab(&[Action::ToggleDhcp], if app.dhcp_view { "Devices" } else { "DHCP" }),
+            tab(&[Action::RangeScan], "Range Scan"),
+            tab(&[Action::ToggleRangeScans], if app.range_scan_view { "Devices" } else { "Saved Scans" }),
         ],
         BottomTab::Networks => vec![
-            key_span("s", "Scan Now"),
-            key_span("b", &format!("{}", if app.bluetooth_expanded { "Collapse BT" } else { "Expand BT" })),
+            tab(&[Action::Scan], "Scan Now"),
+            tab(&[Action::ToggleBluetooth], &format!("{}", if app.bluetooth_expanded { "Collapse BT" } else { "Expand BT" })),
         ],
         BottomTab::Packets => vec![
-            key_span("Space", &format!("{}", if app.packets_paused { "Resume" } else { "Pause" })),
-            key_span("d", "Detail"),
-            key_span("c", "Clear"),
+            tab(&[Action::Pause], &format!("{}", if app.packets_paused { "Resume" } else { "Pause" })),
+            tab(&[Action::ToggleDetail], "Detail"),
+            tab(&[Action::Clear], "Clear"),
             key_span("f", "Filter"),
-            key_span("Esc", "Clear"),
+            tab(&[Action::ClearFilter], "Clear"),
         ],
         BottomTab::Topology => vec![
-            key_span("\u{2191}\u{2193}", "Navigate"),
+            global(&[Action::ScrollUp, Action::ScrollDown], "Navigate"),
+            tab(&[Action::TypeFilter], &format!("Type:{}", app.device_type_filter.map_or("All", |t| t.label()))),
+            tab(&[Action::Trace], "Trace"),
+            tab(&[Action::TogglePaths], if app.path_view { "Diagram" } else { "Paths" }),
+            tab(&[Action::TraceMode], &format!("Mode:{}", app.trace_mode.label())),
+            tab(&[Action::Export], "Export"),
         ],
     };
 
@@ -136,27 +157,40 @@ pub fn draw_key_hints(f: &mut Frame, area: Rect, app: &App) {
     for s in common_keys {
         spans.extend(s);
     }
-    spans.push(Span::styled(" │ ", Style::default().fg(Color::Rgb(40, 55, 80))));
+    spans.push(Span::styled(" │ ", Style::default().fg(theme().divider)));
     for s in tab_keys {
         spans.extend(s);
     }
 
     let paragraph = Paragraph::new(Line::from(spans))
-        .style(Style::default().bg(Color::Rgb(20, 28, 50)));
+        .style(Style::default().bg(theme().header_bg));
     f.render_widget(paragraph, area);
 }
 
+/// Hint for one or more actions, labelled with their first bound keys.
+/// Actions with no key are left out of the hint.
+fn action_span(app: &App, ctx: Context, actions: &[Action], desc: &str) -> Vec<Span<'static>> {
+    let labels: Vec<String> = actions.iter().filter_map(|a| app.keymap.label(ctx, *a)).collect();
+    let key = match labels.as_slice() {
+        [] => return Vec::new(),
+        [first, .., last] if labels.len() > 2 => format!("{}-{}", first, last),
+        [a, b] if a.chars().count() == 1 && b.chars().count() == 1 && !a.chars().all(char::is_alphanumeric) => format!("{}{}", a, b),
+        _ => labels.join("/"),
+    };
+    key_span(&key, desc)
+}
+
 fn key_span(key: &str, desc: &str) -> Vec<Span<'static>> {
     vec![
         Span::styled(
             format!(" {} ", key),
             Style::default()
-                .fg(Color::Rgb(255, 200, 80))
+                .fg(theme().highlight)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::styled(
             format!("{} ", desc),
-            Style::default().fg(Color::Rgb(95, 108, 135)),
+            Style::default().fg(theme().text_dim),
         ),
     ]
 }
@@ -0,0 +1,489 @@
+//! Colour themes.
+//!
+//! Every widget takes its colours from the active [`Theme`], a set of
+//! semantic roles (surfaces, borders, text levels, status colours). The
+//! theme is read once at startup from `%APPDATA%\psnet\theme.toml`:
+//!
+//! ```toml
+//! preset = "light"        # dark (default), light, high-contrast, 16-color
+//!
+//! [colors]
+//! accent = "#0090c0"      # hex, a colour name, or a 256-colour index
+//! selected_bg = "blue"
+//! ```
+//!
+//! `[colors]` overrides single roles on top of the preset. The `16-color`
+//! preset only uses the terminal's named colours, for consoles without
+//! true-colour support.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use ratatui::style::Color;
+use serde::Deserialize;
+
+use crate::config;
+
+const CONFIG_FILE: &str = "theme.toml";
+
+static ACTIVE: OnceLock<Theme> = OnceLock::new();
+
+/// The active theme. Dark until [`init`] has loaded the config file.
+pub fn theme() -> &'static Theme {
+    ACTIVE.get_or_init(Theme::dark)
+}
+
+/// Load `theme.toml` and make it the active theme. Returns a message for the
+/// status line when the file has problems; the rest of the file still applies.
+pub fn init() -> Option<String> {
+    let (theme, message) = load(&config::path(CONFIG_FILE));
+    let _ = ACTIVE.set(theme);
+    message
+}
+
+// ─── Presets ────────────────────────────────────────────────────────────────
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Preset {
+    Dark,
+    Light,
+    HighContrast,
+    Ansi16,
+}
+
+impl Preset {
+    pub const ALL: [Preset; 4] = [Preset::Dark, Preset::Light, Preset::HighContrast, Preset::Ansi16];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Dark => "dark",
+            Self::Light => "light",
+            Self::HighContrast => "high-contrast",
+            Self::Ansi16 => "16-color",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().replace(['_', ' '], "-").as_str() {
+            "dark" => Some(Self::Dark),
+            "light" => Some(Self::Light),
+            "high-contrast" | "highcontrast" | "contrast" => Some(Self::HighContrast),
+            "16-color" | "16-colour" | "16" | "ansi" | "ansi16" => Some(Self::Ansi16),
+            _ => None,
+        }
+    }
+
+    pub fn theme(&self) -> Theme {
+        match self {
+            Self::Dark => Theme::dark(),
+            Self::Light => Theme::light(),
+            Self::HighContrast => Theme::high_contrast(),
+            Self::Ansi16 => Theme::ansi16(),
+        }
+    }
+}
+
+// ─── Roles ──────────────────────────────────────────────────────────────────
+
+macro_rules! roles {
+    ($($(#[$doc:meta])* $name:ident,)*) => {
+        #[derive(Clone, Debug, PartialEq)]
+        pub struct Theme {
+            /// Preset the roles started from; decides how [`Theme::rgb`]
+            /// adapts data-driven colours.
+            pub preset: Preset,
+            $($(#[$doc])* pub $name: Color,)*
+        }
+
+        impl Theme {
+            /// Role names as written in `theme.toml`.
+            #[cfg(test)]
+            pub const ROLES: &'static [&'static str] = &[$(stringify!($name),)*];
+
+            fn role_mut(&mut self, name: &str) -> Option<&mut Color> {
+                match name {
+                    $(stringify!($name) => Some(&mut self.$name),)*
+                    _ => None,
+                }
+            }
+
+            #[cfg(test)]
+            pub fn role(&self, name: &str) -> Option<Color> {
+                match name {
+                    $(stringify!($name) => Some(self.$name),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+roles! {
+    /// Pane background behind maps, diagrams and packet lists.
+    bg,
+    /// Background of bordered blocks and tables.
+    surface,
+    /// Alternate rows and cards.
+    surface_alt,
+    /// Table headers and popup title bars.
+    header_bg,
+    /// Selected row.
+    selected_bg,
+    /// Tinted backgrounds behind good / warning / error banners.
+    ok_bg,
+    warn_bg,
+    error_bg,
+    /// Block borders.
+    border,
+    /// Focused borders, scrollbars and gauges.
+    border_strong,
+    /// Separators and empty bar cells.
+    divider,
+    /// Text, from most to least prominent.
+    text_bright,
+    text,
+    text_secondary,
+    text_dim,
+    text_muted,
+    text_faint,
+    /// Headings and the main accent.
+    accent,
+    /// Informational values and links.
+    info,
+    /// Emphasis and the selection marker.
+    highlight,
+    /// Healthy / online / allowed.
+    ok,
+    ok_soft,
+    /// Needs attention.
+    warn,
+    /// Worse than a warning, not yet an error.
+    caution,
+    /// Errors, threats, blocked.
+    error,
+    /// Secondary hues for categories and protocols.
+    purple,
+    teal,
+    pink,
+    gold,
+    olive,
+    /// Key names in hint bars.
+    key,
+    /// Download / upload traffic.
+    download,
+    upload,
+}
+
+impl Theme {
+    pub fn dark() -> Self {
+        Theme {
+            preset: Preset::Dark,
+            bg: Color::Rgb(8, 12, 24),
+            surface: Color::Rgb(12, 16, 28),
+            surface_alt: Color::Rgb(16, 22, 38),
+            header_bg: Color::Rgb(18, 25, 42),
+            selected_bg: Color::Rgb(25, 45, 85),
+            ok_bg: Color::Rgb(15, 30, 20),
+            warn_bg: Color::Rgb(30, 28, 15),
+            error_bg: Color::Rgb(30, 10, 10),
+            border: Color::Rgb(30, 50, 85),
+            border_strong: Color::Rgb(40, 70, 120),
+            divider: Color::Rgb(40, 55, 80),
+            text_bright: Color::Rgb(220, 230, 250),
+            text: Color::Rgb(160, 180, 220),
+            text_secondary: Color::Rgb(140, 150, 175),
+            text_dim: Color::Rgb(100, 120, 150),
+            text_muted: Color::Rgb(70, 85, 110),
+            text_faint: Color::Rgb(50, 60, 80),
+            accent: Color::Rgb(100, 220, 255),
+            info: Color::Rgb(80, 180, 255),
+            highlight: Color::Rgb(255, 200, 80),
+            ok: Color::Rgb(80, 200, 120),
+            ok_soft: Color::Rgb(130, 200, 140),
+            warn: Color::Rgb(255, 200, 60),
+            caution: Color::Rgb(255, 130, 70),
+            error: Color::Rgb(255, 80, 80),
+            purple: Color::Rgb(180, 140, 255),
+            teal: Color::Rgb(80, 220, 160),
+            pink: Color::Rgb(255, 160, 200),
+            gold: Color::Rgb(200, 180, 100),
+            olive: Color::Rgb(180, 200, 130),
+            key: Color::Yellow,
+            download: Color::Rgb(50, 160, 255),
+            upload: Color::Rgb(180, 100, 255),
+        }
+    }
+
+    pub fn light() -> Self {
+        Theme {
+            preset: Preset::Light,
+            bg: Color::Rgb(250, 251, 253),
+            surface: Color::Rgb(244, 246, 250),
+            surface_alt: Color::Rgb(236, 240, 246),
+            header_bg: Color::Rgb(224, 230, 241),
+            selected_bg: Color::Rgb(198, 216, 245),
+            ok_bg: Color::Rgb(222, 243, 228),
+            warn_bg: Color::Rgb(250, 241, 212),
+            error_bg: Color::Rgb(250, 224, 224),
+            border: Color::Rgb(165, 180, 206),
+            border_strong: Color::Rgb(100, 130, 185),
+            divider: Color::Rgb(200, 208, 222),
+            text_bright: Color::Rgb(10, 15, 30),
+            text: Color::Rgb(35, 45, 70),
+            text_secondary: Color::Rgb(70, 80, 105),
+            text_dim: Color::Rgb(100, 110, 135),
+            text_muted: Color::Rgb(135, 145, 165),
+            text_faint: Color::Rgb(175, 182, 198),
+            accent: Color::Rgb(0, 125, 165),
+            info: Color::Rgb(25, 100, 200),
+            highlight: Color::Rgb(175, 105, 0),
+            ok: Color::Rgb(20, 135, 60),
+            ok_soft: Color::Rgb(60, 125, 80),
+            warn: Color::Rgb(170, 115, 0),
+            caution: Color::Rgb(200, 85, 20),
+            error: Color::Rgb(200, 30, 30),
+            purple: Color::Rgb(120, 70, 200),
+            teal: Color::Rgb(0, 135, 105),
+            pink: Color::Rgb(185, 55, 125),
+            gold: Color::Rgb(135, 105, 25),
+            olive: Color::Rgb(95, 115, 35),
+            key: Color::Rgb(160, 85, 0),
+            download: Color::Rgb(20, 105, 205),
+            upload: Color::Rgb(135, 55, 195),
+        }
+    }
+
+    pub fn high_contrast() -> Self {
+        Theme {
+            preset: Preset::HighContrast,
+            bg: Color::Rgb(0, 0, 0),
+            surface: Color::Rgb(0, 0, 0),
+            surface_alt: Color::Rgb(0, 0, 0),
+            header_bg: Color::Rgb(40, 40, 40),
+            selected_bg: Color::Rgb(0, 70, 160),
+            ok_bg: Color::Rgb(0, 55, 0),
+            warn_bg: Color::Rgb(70, 60, 0),
+            error_bg: Color::Rgb(90, 0, 0),
+            border: Color::Rgb(200, 200, 200),
+            border_strong: Color::Rgb(255, 255, 255),
+            divider: Color::Rgb(150, 150, 150),
+            text_bright: Color::Rgb(255, 255, 255),
+            text: Color::Rgb(245, 245, 245),
+            text_secondary: Color::Rgb(225, 225, 225),
+            text_dim: Color::Rgb(200, 200, 200),
+            text_muted: Color::Rgb(175, 175, 175),
+            text_faint: Color::Rgb(140, 140, 140),
+            accent: Color::Rgb(0, 255, 255),
+            info: Color::Rgb(100, 180, 255),
+            highlight: Color::Rgb(255, 255, 0),
+            ok: Color::Rgb(0, 255, 0),
+            ok_soft: Color::Rgb(130, 255, 130),
+            warn: Color::Rgb(255, 230, 0),
+            caution: Color::Rgb(255, 150, 0),
+            error: Color::Rgb(255, 60, 60),
+            purple: Color::Rgb(220, 150, 255),
+            teal: Color::Rgb(0, 255, 190),
+            pink: Color::Rgb(255, 130, 210),
+            gold: Color::Rgb(255, 215, 0),
+            olive: Color::Rgb(200, 255, 80),
+            key: Color::Rgb(255, 255, 0),
+            download: Color::Rgb(0, 200, 255),
+            upload: Color::Rgb(255, 110, 255),
+        }
+    }
+
+    pub fn ansi16() -> Self {
+        Theme {
+            preset: Preset::Ansi16,
+            bg: Color::Reset,
+            surface: Color::Reset,
+            surface_alt: Color::Reset,
+            header_bg: Color::Black,
+            selected_bg: Color::Blue,
+            ok_bg: Color::Reset,
+            warn_bg: Color::Reset,
+            error_bg: Color::Reset,
+            border: Color::DarkGray,
+            border_strong: Color::Gray,
+            divider: Color::DarkGray,
+            text_bright: Color::White,
+            text: Color::Gray,
+            text_secondary: Color::Gray,
+            text_dim: Color::DarkGray,
+            text_muted: Color::DarkGray,
+            text_faint: Color::DarkGray,
+            accent: Color::LightCyan,
+            info: Color::LightBlue,
+            highlight: Color::LightYellow,
+            ok: Color::LightGreen,
+            ok_soft: Color::Green,
+            warn: Color::Yellow,
+            caution: Color::LightRed,
+            error: Color::Red,
+            purple: Color::LightMagenta,
+            teal: Color::Cyan,
+            pink: Color::Magenta,
+            gold: Color::Yellow,
+            olive: Color::Green,
+            key: Color::Yellow,
+            download: Color::LightCyan,
+            upload: Color::LightMagenta,
+        }
+    }
+
+    /// Adapt a data-driven colour (category and fingerprint colours) to the
+    /// theme: darkened on light backgrounds, snapped to a named colour on
+    /// 16-colour terminals.
+    pub fn rgb(&self, r: u8, g: u8, b: u8) -> Color {
+        match self.preset {
+            Preset::Dark | Preset::HighContrast => Color::Rgb(r, g, b),
+            Preset::Light => Color::Rgb(
+                (r as f32 * 0.6) as u8,
+                (g as f32 * 0.6) as u8,
+                (b as f32 * 0.6) as u8,
+            ),
+            Preset::Ansi16 => nearest_named(r, g, b),
+        }
+    }
+
+    /// Blend `c` toward the pane background; 1.0 keeps `c`, 0.0 is the
+    /// background. Named colours cannot blend and fall back to `text_faint`
+    /// below half strength.
+    pub fn fade(&self, c: Color, amount: f32) -> Color {
+        let amount = amount.clamp(0.0, 1.0);
+        match (c, self.bg) {
+            (Color::Rgb(r, g, b), Color::Rgb(br, bgr, bb)) => {
+                let mix = |fg: u8, bg: u8| (bg as f32 + (fg as f32 - bg as f32) * amount) as u8;
+                Color::Rgb(mix(r, br), mix(g, bgr), mix(b, bb))
+            }
+            _ if amount < 0.5 => self.text_faint,
+            _ => c,
+        }
+    }
+}
+
+/// Nearest of the 16 terminal colours, using the common xterm values.
+fn nearest_named(r: u8, g: u8, b: u8) -> Color {
+    const NAMED: [(Color, (i32, i32, i32)); 16] = [
+        (Color::Black, (0, 0, 0)),
+        (Color::Red, (205, 0, 0)),
+        (Color::Green, (0, 205, 0)),
+        (Color::Yellow, (205, 205, 0)),
+        (Color::Blue, (0, 0, 238)),
+        (Color::Magenta, (205, 0, 205)),
+        (Color::Cyan, (0, 205, 205)),
+        (Color::Gray, (229, 229, 229)),
+        (Color::DarkGray, (127, 127, 127)),
+        (Color::LightRed, (255, 0, 0)),
+        (Color::LightGreen, (0, 255, 0)),
+        (Color::LightYellow, (255, 255, 0)),
+        (Color::LightBlue, (92, 92, 255)),
+        (Color::LightMagenta, (255, 0, 255)),
+        (Color::LightCyan, (0, 255, 255)),
+        (Color::White, (255, 255, 255)),
+    ];
+    let (r, g, b) = (r as i32, g as i32, b as i32);
+    NAMED
+        .iter()
+        .min_by_key(|(_, (nr, ng, nb))| (r - nr).pow(2) + (g - ng).pow(2) + (b - nb).pow(2))
+        .map(|(c, _)| *c)
+        .unwrap_or(Color::Reset)
+}
+
+// ─── Config file ────────────────────────────────────────────────────────────
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct ThemeFile {
+    preset: Option<String>,
+    #[serde(alias = "colours")]
+    colors: BTreeMap<String, String>,
+}
+
+/// Read the theme file. A missing file gives the dark theme silently; any
+/// other problem is reported alongside whatever could be applied.
+pub fn load(path: &Path) -> (Theme, Option<String>) {
+    match std::fs::read_to_string(path) {
+        Ok(content) => match parse(&content) {
+            Ok((theme, warnings)) if warnings.is_empty() => (theme, None),
+            Ok((theme, warnings)) => (theme, Some(format!("{}: {}", CONFIG_FILE, warnings.join("; ")))),
+            Err(e) => (Theme::dark(), Some(format!("{}: {}", CONFIG_FILE, e))),
+        },
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => (Theme::dark(), None),
+        Err(e) => (Theme::dark(), Some(format!("{}: {}", CONFIG_FILE, e))),
+    }
+}
+
+/// Parse a theme file into a theme plus warnings for unknown presets, roles
+/// and colours. Only invalid TOML is an error.
+pub fn parse(content: &str) -> Result<(Theme, Vec<String>), String> {
+    let file: ThemeFile = toml::from_str(content).map_err(|e| format!("invalid TOML ({})", e.message()))?;
+    let mut warnings = Vec::new();
+    let preset = match file.preset.as_deref() {
+        None => Preset::Dark,
+        Some(name) => Preset::parse(name).unwrap_or_else(|| {
+            let names: Vec<&str> = Preset::ALL.iter().map(|p| p.name()).collect();
+            warnings.push(format!("unknown preset '{}' (use {})", name, names.join(", ")));
+            Preset::Dark
+        }),
+    };
+    let mut theme = preset.theme();
+    for (role, value) in &file.colors {
+        let Some(slot) = theme.role_mut(role) else {
+            warnings.push(format!("unknown role '{}'", role));
+            continue;
+        };
+        match Color::from_str(value) {
+            Ok(c) => *slot = c,
+            Err(_) => warnings.push(format!("bad colour '{}' for {}", value, role)),
+        }
+    }
+    Ok((theme, warnings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_preset_and_overrides() {
+        let (t, warnings) = parse("preset = \"light\"\n[colors]\naccent = \"#102030\"\nerror = \"red\"\n").unwrap();
+        assert!(warnings.is_empty());
+        assert_eq!(t.preset, Preset::Light);
+        assert_eq!(t.accent, Color::Rgb(0x10, 0x20, 0x30));
+        assert_eq!(t.error, Color::Red);
+        assert_eq!(t.bg, Theme::light().bg);
+        assert_eq!(parse("").unwrap().0, Theme::dark());
+    }
+
+    #[test]
+    fn test_parse_warnings() {
+        let (t, warnings) = parse("preset = \"neon\"\n[colors]\nfoo = \"red\"\nok = \"nope\"\n").unwrap();
+        assert_eq!(t, Theme::dark());
+        assert_eq!(warnings.len(), 3);
+        assert!(parse("preset = ").is_err());
+    }
+
+    #[test]
+    fn test_presets() {
+        for p in Preset::ALL {
+            assert_eq!(Preset::parse(p.name()), Some(p));
+            assert_eq!(p.theme().preset, p);
+        }
+        let t = Theme::ansi16();
+        for role in Theme::ROLES {
+            assert!(!matches!(t.role(role), Some(Color::Rgb(..)) | None), "{}", role);
+        }
+        assert!(!matches!(t.rgb(255, 80, 80), Color::Rgb(..)));
+    }
+
+    #[test]
+    fn test_fade() {
+        let t = Theme::dark();
+        assert_eq!(t.fade(t.ok, 1.0), t.ok);
+        assert_eq!(t.fade(t.ok, 0.0), t.bg);
+        assert_eq!(Theme::ansi16().fade(Color::Green, 0.2), Color::DarkGray);
+    }
+}
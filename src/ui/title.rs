@@ -1,11 +1,12 @@
 use chrono::Local;
 use ratatui::layout::Rect;
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
 use ratatui::Frame;
 
 use crate::app::App;
+use crate::ui::theme::theme;
 
 pub fn draw_title_bar(f: &mut Frame, area: Rect, app: &App) {
     let now = Local::now().format("%H:%M:%S").to_string();
@@ -30,18 +31,18 @@ pub fn draw_title_bar(f: &mut Frame, area: Rect, app: &App) {
         Span::styled(
             if pulse { " \u{26A1} " } else { " \u{25CF} " },
             Style::default()
-                .fg(Color::Rgb(255, 220, 80))
+                .fg(theme().highlight)
                 .add_modifier(Modifier::BOLD),
         )
     } else if app.current_down_speed > 5_000.0 || app.current_up_speed > 5_000.0 {
         Span::styled(
             if pulse { " \u{25CF} " } else { " \u{25CB} " },
-            Style::default().fg(Color::Rgb(80, 200, 120)),
+            Style::default().fg(theme().ok),
         )
     } else {
         Span::styled(
             " \u{25CB} ",
-            Style::default().fg(Color::Rgb(55, 70, 95)),
+            Style::default().fg(theme().text_faint),
         )
     };
 
@@ -49,7 +50,7 @@ pub fn draw_title_bar(f: &mut Frame, area: Rect, app: &App) {
         Some(Span::styled(
             " [INCOGNITO] ",
             Style::default()
-                .fg(Color::Rgb(255, 220, 80))
+                .fg(theme().highlight)
                 .add_modifier(Modifier::BOLD),
         ))
     } else {
@@ -60,12 +61,12 @@ pub fn draw_title_bar(f: &mut Frame, area: Rect, app: &App) {
         Span::styled(
             " \u{25C8} PSNET ",
             Style::default()
-                .fg(Color::Rgb(80, 200, 255))
+                .fg(theme().info)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::styled(
             "Network Monitor",
-            Style::default().fg(Color::Rgb(130, 150, 190)),
+            Style::default().fg(theme().text_secondary),
         ),
         activity,
     ];
@@ -75,44 +76,44 @@ pub fn draw_title_bar(f: &mut Frame, area: Rect, app: &App) {
     title_parts.extend(vec![
         Span::styled(
             " \u{2502} ",
-            Style::default().fg(Color::Rgb(35, 50, 75)),
+            Style::default().fg(theme().border),
         ),
         Span::styled(
             format!("{} ", app.interface_name),
-            Style::default().fg(Color::Rgb(90, 150, 210)),
+            Style::default().fg(theme().info),
         ),
         Span::styled(
             " \u{2502} ",
-            Style::default().fg(Color::Rgb(35, 50, 75)),
+            Style::default().fg(theme().border),
         ),
         Span::styled(
             format!("{}", established),
             Style::default()
-                .fg(Color::Rgb(80, 200, 120))
+                .fg(theme().ok)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::styled(
             format!(" active / {} total ", conn_count),
-            Style::default().fg(Color::Rgb(85, 100, 130)),
+            Style::default().fg(theme().text_muted),
         ),
         Span::styled(
             " \u{2502} ",
-            Style::default().fg(Color::Rgb(35, 50, 75)),
+            Style::default().fg(theme().border),
         ),
         Span::styled(
             format!("\u{23F1} {} ", dur),
-            Style::default().fg(Color::Rgb(110, 120, 150)),
+            Style::default().fg(theme().text_dim),
         ),
         Span::styled(
             format!(" {} ", now),
-            Style::default().fg(Color::Rgb(85, 95, 120)),
+            Style::default().fg(theme().text_muted),
         ),
     ]);
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Rgb(30, 50, 85)))
-        .style(Style::default().bg(Color::Rgb(8, 12, 24)));
+        .border_style(Style::default().fg(theme().border))
+        .style(Style::default().bg(theme().bg));
 
     f.render_widget(Paragraph::new(Line::from(title_parts)).block(block), area);
 }
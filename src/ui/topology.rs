@@ -1,6 +1,8 @@
 //! Network Topology tab — hub-and-spoke diagram showing the local machine
 //! connected to infrastructure nodes (gateway, DNS), LAN devices discovered
 //! by the network scanner, and remote hosts from active connections.
+//! `p` swaps the diagram for the traced paths: a tree of the hops shared by
+//! every traceroute, over the hop table of the selected one.
 
 use std::collections::{HashMap, HashSet};
 use std::net::IpAddr;
@@ -9,29 +11,19 @@ use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{
-    Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState,
+    Block, Borders, Cell, Paragraph, Row, Scrollbar, ScrollbarOrientation, ScrollbarState, Table,
 };
 use ratatui::Frame;
 
 use crate::app::App;
+use crate::network::topology::{EdgeKind, GraphEdge, GraphNode, NodeKind, TopologyGraph, Traffic};
+use crate::network::traceroute::{self, Hop, Trace, TraceState};
 use crate::types::TcpState;
 use crate::utils::format_speed;
+use crate::ui::theme::theme;
 
 // ─── Theme palette ───────────────────────────────────────────────────────────
 
-const BG: Color = Color::Rgb(8, 12, 24);
-const BORDER: Color = Color::Rgb(30, 50, 85);
-const SELECTED: Color = Color::Rgb(255, 200, 80);
-const ACTIVE: Color = Color::Rgb(80, 200, 120);
-const INACTIVE: Color = Color::Rgb(60, 70, 90);
-const OFFLINE: Color = Color::Rgb(200, 60, 60);
-const TEXT: Color = Color::Rgb(140, 160, 190);
-const TEXT_MUTED: Color = Color::Rgb(90, 110, 140);
-const HUB_BORDER: Color = Color::Rgb(100, 180, 255);
-const GATEWAY_BORDER: Color = Color::Rgb(80, 200, 120);
-const DNS_BORDER: Color = Color::Rgb(80, 200, 255);
-const LAN_BORDER: Color = Color::Rgb(170, 140, 255);
-const LAN_HEADER: Color = Color::Rgb(200, 170, 255);
 
 // ─── Column widths ───────────────────────────────────────────────────────────
 
@@ -66,10 +58,14 @@ struct RemoteNodePos {
 pub fn draw_topology(f: &mut Frame, area: Rect, app: &App) {
     if area.height < 6 || area.width < 50 {
         let msg = Paragraph::new("Terminal too small for topology view")
-            .style(Style::default().fg(TEXT_MUTED).bg(BG));
+            .style(Style::default().fg(theme().text_dim).bg(theme().bg));
         f.render_widget(msg, area);
         return;
     }
+    if app.path_view {
+        draw_paths(f, area, app);
+        return;
+    }
 
     // ── Aggregate data ──
     let remote_nodes = aggregate_remote_nodes(app);
@@ -92,17 +88,17 @@ pub fn draw_topology(f: &mut Frame, area: Rect, app: &App) {
             Span::styled(
                 " \u{1F5A7} Topology ",
                 Style::default()
-                    .fg(Color::Rgb(160, 180, 220))
+                    .fg(theme().text)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
                 format!(" {} hosts, {} LAN ", total_remote, lan_count),
-                Style::default().fg(Color::Rgb(100, 120, 150)),
+                Style::default().fg(theme().text_dim),
             ),
         ]))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(BORDER))
-        .style(Style::default().bg(BG));
+        .border_style(Style::default().fg(theme().border))
+        .style(Style::default().bg(theme().bg));
 
     let inner = outer_block.inner(area);
     f.render_widget(outer_block, area);
@@ -163,7 +159,7 @@ pub fn draw_topology(f: &mut Frame, area: Rect, app: &App) {
         lan_count,
         app,
     );
-    draw_footer(f, footer_area);
+    draw_footer(f, footer_area, app);
 }
 
 // ─── Infrastructure + LAN devices (left column) ─────────────────────────────
@@ -202,12 +198,12 @@ fn draw_infrastructure_and_lan(
         let gw_lines = vec![
             Line::from(Span::styled(
                 truncate_str(&gateway_ip, area.width.saturating_sub(2) as usize),
-                Style::default().fg(TEXT),
+                Style::default().fg(theme().text_secondary),
             )),
             Line::from(Span::styled(
                 "\u{25CF} Online",
                 Style::default()
-                    .fg(ACTIVE)
+                    .fg(theme().ok)
                     .add_modifier(Modifier::BOLD),
             )),
         ];
@@ -216,23 +212,23 @@ fn draw_infrastructure_and_lan(
             .title(Span::styled(
                 " Gateway ",
                 Style::default()
-                    .fg(GATEWAY_BORDER)
+                    .fg(theme().ok)
                     .add_modifier(Modifier::BOLD),
             ))
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(GATEWAY_BORDER));
+            .border_style(Style::default().fg(theme().ok));
 
         f.render_widget(
             Paragraph::new(gw_lines)
                 .block(gw_block)
-                .style(Style::default().bg(BG)),
+                .style(Style::default().bg(theme().bg)),
             gw_area,
         );
 
         positions.push(LeftNodePos {
             y: y_cursor,
             height: gw_height,
-            color: GATEWAY_BORDER,
+            color: theme().ok,
         });
         y_cursor += gw_height + 1;
     }
@@ -260,17 +256,17 @@ fn draw_infrastructure_and_lan(
                         Span::styled(
                             "\u{25CF} ",
                             Style::default()
-                                .fg(ACTIVE)
+                                .fg(theme().ok)
                                 .add_modifier(Modifier::BOLD),
                         ),
                         Span::styled(
                             truncate_str(ip_str, max_w.saturating_sub(11)),
-                            Style::default().fg(TEXT),
+                            Style::default().fg(theme().text_secondary),
                         ),
                         Span::styled(
                             " (Active)",
                             Style::default()
-                                .fg(ACTIVE)
+                                .fg(theme().ok)
                                 .add_modifier(Modifier::BOLD),
                         ),
                     ])
@@ -278,11 +274,11 @@ fn draw_infrastructure_and_lan(
                     Line::from(vec![
                         Span::styled(
                             "\u{25CB} ",
-                            Style::default().fg(TEXT_MUTED),
+                            Style::default().fg(theme().text_dim),
                         ),
                         Span::styled(
                             truncate_str(ip_str, max_w.saturating_sub(2)),
-                            Style::default().fg(TEXT_MUTED),
+                            Style::default().fg(theme().text_dim),
                         ),
                     ])
                 }
@@ -299,29 +295,31 @@ fn draw_infrastructure_and_lan(
             .title(Span::styled(
                 title_label,
                 Style::default()
-                    .fg(DNS_BORDER)
+                    .fg(theme().info)
                     .add_modifier(Modifier::BOLD),
             ))
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(DNS_BORDER));
+            .border_style(Style::default().fg(theme().info));
 
         f.render_widget(
             Paragraph::new(dns_lines)
                 .block(dns_block)
-                .style(Style::default().bg(BG)),
+                .style(Style::default().bg(theme().bg)),
             dns_area,
         );
 
         positions.push(LeftNodePos {
             y: y_cursor,
             height: dns_height,
-            color: DNS_BORDER,
+            color: theme().info,
         });
         y_cursor += dns_height + 1;
     }
 
     // ── LAN Devices section ──
-    let devices = &app.network_scanner.devices;
+    let devices: Vec<&crate::types::LanDevice> = app.network_scanner.devices.iter()
+        .filter(|d| app.device_type_filter.is_none_or(|t| d.device_type == t))
+        .collect();
     if !devices.is_empty() && y_cursor + 2 <= area.y + area.height {
         // Section header
         let header_area = Rect {
@@ -330,22 +328,31 @@ fn draw_infrastructure_and_lan(
             width: area.width,
             height: 1,
         };
+        let filter_label = app.device_type_filter
+            .map(|t| format!(" {}", t.label()))
+            .unwrap_or_default();
         let dev_header = format!(
-            "\u{2500}\u{2500} LAN ({}) \u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}",
+            "\u{2500}\u{2500} LAN{} ({}) \u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}",
+            filter_label,
             devices.len()
         );
         f.render_widget(
             Paragraph::new(Line::from(Span::styled(
                 truncate_str(&dev_header, area.width as usize),
                 Style::default()
-                    .fg(LAN_HEADER)
+                    .fg(theme().purple)
                     .add_modifier(Modifier::BOLD),
             )))
-            .style(Style::default().bg(BG)),
+            .style(Style::default().bg(theme().bg)),
             header_area,
         );
         y_cursor += 1;
 
+        // LLDP links reported by SNMP-managed switches
+        let agents_guard = app.network_scanner.snmp.lock().ok();
+        let no_agents = std::collections::HashMap::new();
+        let agents = agents_guard.as_deref().unwrap_or(&no_agents);
+
         // Device boxes (3 lines each)
         let dev_height = 3u16;
         for device in devices.iter() {
@@ -371,33 +378,56 @@ fn draw_infrastructure_and_lan(
                 .unwrap_or(&device.mac);
 
             let (status_icon, status_color) = if device.is_online {
-                ("\u{25CF}", ACTIVE)
+                ("\u{25CF}", theme().ok)
             } else {
-                ("\u{25CB}", OFFLINE)
+                ("\u{25CB}", theme().error)
             };
 
             let ip_str = device.ip.to_string();
-            let dev_line = Line::from(vec![
+            // Switch port this device hangs off, or how many neighbours a switch has
+            let link = match crate::network::snmp::uplink_of(agents, device.ip, &device.mac) {
+                Some((agent_ip, agent, n)) => Some(format!(
+                    " \u{21C4} {} {}",
+                    agent.name.clone().unwrap_or_else(|| agent_ip.to_string()),
+                    n.local_port
+                )),
+                None => match device.ip {
+                    std::net::IpAddr::V4(v4) => agents
+                        .get(&v4)
+                        .filter(|a| !a.neighbors.is_empty())
+                        .map(|a| format!(" \u{21C4} {} links", a.neighbors.len())),
+                    _ => None,
+                },
+            };
+            let mut dev_spans = vec![
                 Span::styled(
                     format!("{} ", status_icon),
                     Style::default().fg(status_color),
                 ),
                 Span::styled(
                     truncate_str(&ip_str, max_w.saturating_sub(2)).to_string(),
-                    Style::default().fg(TEXT_MUTED),
+                    Style::default().fg(theme().text_dim),
                 ),
-            ]);
+            ];
+            if let Some(link) = link {
+                let room = max_w.saturating_sub(2 + ip_str.len());
+                dev_spans.push(Span::styled(
+                    truncate_str(&link, room).to_string(),
+                    Style::default().fg(theme().ok),
+                ));
+            }
+            let dev_line = Line::from(dev_spans);
 
-            let border_color = if device.is_online { LAN_BORDER } else { INACTIVE };
+            let border_color = if device.is_online { theme().purple } else { theme().text_faint };
 
             let dev_block = Block::default()
                 .title(Span::styled(
-                    format!(" {} ", truncate_str(display_name, max_w.saturating_sub(2))),
+                    format!(" {} {} ", device.device_type.icon(), truncate_str(display_name, max_w.saturating_sub(5))),
                     Style::default()
                         .fg(if device.is_online {
-                            Color::Rgb(180, 160, 255)
+                            theme().purple
                         } else {
-                            TEXT_MUTED
+                            theme().text_dim
                         })
                         .add_modifier(Modifier::BOLD),
                 ))
@@ -407,7 +437,7 @@ fn draw_infrastructure_and_lan(
             f.render_widget(
                 Paragraph::new(vec![dev_line])
                     .block(dev_block)
-                    .style(Style::default().bg(BG)),
+                    .style(Style::default().bg(theme().bg)),
                 dev_area,
             );
 
@@ -449,14 +479,14 @@ fn draw_hub(f: &mut Frame, area: Rect, app: &App) {
         Span::styled(
             "\u{25CF} FW ON",
             Style::default()
-                .fg(ACTIVE)
+                .fg(theme().ok)
                 .add_modifier(Modifier::BOLD),
         )
     } else {
         Span::styled(
             "\u{25CB} FW OFF",
             Style::default()
-                .fg(Color::Rgb(255, 80, 80))
+                .fg(theme().error)
                 .add_modifier(Modifier::BOLD),
         )
     };
@@ -465,21 +495,21 @@ fn draw_hub(f: &mut Frame, area: Rect, app: &App) {
         Line::from(Span::styled(
             truncate_str(&hostname, max_w),
             Style::default()
-                .fg(Color::Rgb(220, 230, 255))
+                .fg(theme().text_bright)
                 .add_modifier(Modifier::BOLD),
         )),
         Line::from(Span::styled(
             truncate_str(&local_ip, max_w),
-            Style::default().fg(Color::Rgb(100, 180, 255)),
+            Style::default().fg(theme().info),
         )),
-        Line::from(Span::styled(iface.to_string(), Style::default().fg(TEXT_MUTED))),
+        Line::from(Span::styled(iface.to_string(), Style::default().fg(theme().text_dim))),
         Line::from(Span::styled(
             truncate_str(&down_speed, max_w),
-            Style::default().fg(Color::Rgb(80, 200, 255)),
+            Style::default().fg(theme().info),
         )),
         Line::from(Span::styled(
             truncate_str(&up_speed, max_w),
-            Style::default().fg(Color::Rgb(255, 150, 80)),
+            Style::default().fg(theme().caution),
         )),
         Line::from(fw_status),
     ];
@@ -501,17 +531,17 @@ fn draw_hub(f: &mut Frame, area: Rect, app: &App) {
         .title(Span::styled(
             " THIS PC ",
             Style::default()
-                .fg(HUB_BORDER)
+                .fg(theme().info)
                 .add_modifier(Modifier::BOLD),
         ))
         .borders(Borders::ALL)
         .border_type(ratatui::widgets::BorderType::Double)
-        .border_style(Style::default().fg(HUB_BORDER));
+        .border_style(Style::default().fg(theme().info));
 
     f.render_widget(
         Paragraph::new(lines)
             .block(hub_block)
-            .style(Style::default().bg(Color::Rgb(12, 18, 35))),
+            .style(Style::default().bg(theme().surface)),
         hub_area,
     );
 }
@@ -527,9 +557,9 @@ fn draw_remote_nodes(
     if nodes.is_empty() || area.height < 4 {
         let empty = Paragraph::new(Line::from(Span::styled(
             "No remote connections",
-            Style::default().fg(TEXT_MUTED),
+            Style::default().fg(theme().text_dim),
         )))
-        .style(Style::default().bg(BG));
+        .style(Style::default().bg(theme().bg));
         f.render_widget(empty, area);
         return (0, Vec::new());
     }
@@ -598,7 +628,7 @@ fn draw_remote_nodes(
             ScrollbarState::new(total.saturating_sub(max_visible)).position(viewport_start);
         f.render_stateful_widget(
             Scrollbar::new(ScrollbarOrientation::VerticalRight)
-                .style(Style::default().fg(Color::Rgb(40, 70, 120))),
+                .style(Style::default().fg(theme().border_strong)),
             sb_area,
             &mut sb_state,
         );
@@ -613,11 +643,11 @@ fn draw_single_remote_node(f: &mut Frame, area: Rect, node: &RemoteNode, is_sele
     // Line 1: hostname or "(no DNS)"
     let host_display = node.hostname.as_deref().unwrap_or("").to_string();
     let host_line = if host_display.is_empty() {
-        Line::from(Span::styled("(no DNS)", Style::default().fg(TEXT_MUTED)))
+        Line::from(Span::styled("(no DNS)", Style::default().fg(theme().text_dim)))
     } else {
         Line::from(Span::styled(
             truncate_str(&host_display, max_w),
-            Style::default().fg(Color::Rgb(100, 220, 255)),
+            Style::default().fg(theme().accent),
         ))
     };
 
@@ -634,24 +664,24 @@ fn draw_single_remote_node(f: &mut Frame, area: Rect, node: &RemoteNode, is_sele
         max_w.saturating_sub(proto_str.len() + 3),
     );
     let conn_line = Line::from(vec![
-        Span::styled(proto_str, Style::default().fg(TEXT)),
-        Span::styled(" (", Style::default().fg(TEXT_MUTED)),
+        Span::styled(proto_str, Style::default().fg(theme().text_secondary)),
+        Span::styled(" (", Style::default().fg(theme().text_dim)),
         Span::styled(
             proc_display.to_string(),
-            Style::default().fg(Color::Rgb(130, 200, 140)),
+            Style::default().fg(theme().ok_soft),
         ),
-        Span::styled(")", Style::default().fg(TEXT_MUTED)),
+        Span::styled(")", Style::default().fg(theme().text_dim)),
     ]);
 
     let lines = vec![host_line, conn_line];
 
     // Border color
     let border_color = if is_selected {
-        SELECTED
+        theme().highlight
     } else if node.has_established {
-        ACTIVE
+        theme().ok
     } else {
-        INACTIVE
+        theme().text_faint
     };
 
     // Title: IP + flag + country
@@ -659,22 +689,22 @@ fn draw_single_remote_node(f: &mut Frame, area: Rect, node: &RemoteNode, is_sele
         format!(" {} ", node.ip),
         Style::default()
             .fg(if is_selected {
-                SELECTED
+                theme().highlight
             } else {
-                Color::Rgb(180, 195, 220)
+                theme().text
             })
             .add_modifier(Modifier::BOLD),
     )];
     if let Some(ref flag) = node.flag {
         title_parts.push(Span::styled(
             format!("{} ", flag),
-            Style::default().fg(Color::Rgb(170, 200, 230)),
+            Style::default().fg(theme().text),
         ));
     }
     if let Some(ref code) = node.country_code {
         title_parts.push(Span::styled(
             format!("{} ", code),
-            Style::default().fg(Color::Rgb(140, 160, 190)),
+            Style::default().fg(theme().text_secondary),
         ));
     }
 
@@ -683,9 +713,9 @@ fn draw_single_remote_node(f: &mut Frame, area: Rect, node: &RemoteNode, is_sele
         .borders(Borders::ALL)
         .border_style(Style::default().fg(border_color))
         .style(Style::default().bg(if is_selected {
-            Color::Rgb(16, 24, 45)
+            theme().header_bg
         } else {
-            BG
+            theme().bg
         }));
 
     f.render_widget(Paragraph::new(lines).block(block), area);
@@ -723,7 +753,7 @@ fn draw_left_edges(
             let seg = "\u{2500}".repeat(edge_area.width as usize);
             let line = Line::from(Span::styled(seg, Style::default().fg(*color)));
             f.render_widget(
-                Paragraph::new(vec![line]).style(Style::default().bg(BG)),
+                Paragraph::new(vec![line]).style(Style::default().bg(theme().bg)),
                 row_area,
             );
         } else {
@@ -741,17 +771,17 @@ fn draw_left_edges(
                 let pad = " ".repeat(edge_area.width.saturating_sub(1) as usize);
                 let line = Line::from(vec![
                     Span::styled(pad, Style::default()),
-                    Span::styled("\u{2502}", Style::default().fg(Color::Rgb(40, 60, 100))),
+                    Span::styled("\u{2502}", Style::default().fg(theme().divider)),
                 ]);
                 f.render_widget(
-                    Paragraph::new(vec![line]).style(Style::default().bg(BG)),
+                    Paragraph::new(vec![line]).style(Style::default().bg(theme().bg)),
                     row_area,
                 );
             } else {
                 let blank = " ".repeat(edge_area.width as usize);
                 f.render_widget(
                     Paragraph::new(vec![Line::from(Span::raw(blank))])
-                        .style(Style::default().bg(BG)),
+                        .style(Style::default().bg(theme().bg)),
                     row_area,
                 );
             }
@@ -789,27 +819,27 @@ fn draw_right_edges(
                 Line::from(vec![
                     Span::styled(
                         "\u{2500}".repeat(left_dashes),
-                        Style::default().fg(Color::Rgb(60, 100, 150)),
+                        Style::default().fg(theme().text_muted),
                     ),
                     Span::styled(
                         label,
                         Style::default()
-                            .fg(Color::Rgb(180, 200, 230))
+                            .fg(theme().text)
                             .add_modifier(Modifier::BOLD),
                     ),
                     Span::styled(
                         "\u{2500}".repeat(right_dashes),
-                        Style::default().fg(Color::Rgb(60, 100, 150)),
+                        Style::default().fg(theme().text_muted),
                     ),
                 ])
             } else {
                 Line::from(Span::styled(
                     "\u{2500}".repeat(w),
-                    Style::default().fg(Color::Rgb(60, 100, 150)),
+                    Style::default().fg(theme().text_muted),
                 ))
             };
             f.render_widget(
-                Paragraph::new(vec![line]).style(Style::default().bg(BG)),
+                Paragraph::new(vec![line]).style(Style::default().bg(theme().bg)),
                 row_area,
             );
         } else {
@@ -826,7 +856,7 @@ fn draw_right_edges(
                 let line = Line::from(vec![
                     Span::styled(
                         "\u{2502}",
-                        Style::default().fg(Color::Rgb(40, 60, 100)),
+                        Style::default().fg(theme().divider),
                     ),
                     Span::styled(
                         " ".repeat(edge_area.width.saturating_sub(1) as usize),
@@ -834,14 +864,14 @@ fn draw_right_edges(
                     ),
                 ]);
                 f.render_widget(
-                    Paragraph::new(vec![line]).style(Style::default().bg(BG)),
+                    Paragraph::new(vec![line]).style(Style::default().bg(theme().bg)),
                     row_area,
                 );
             } else {
                 let blank = " ".repeat(edge_area.width as usize);
                 f.render_widget(
                     Paragraph::new(vec![Line::from(Span::raw(blank))])
-                        .style(Style::default().bg(BG)),
+                        .style(Style::default().bg(theme().bg)),
                     row_area,
                 );
             }
@@ -867,57 +897,74 @@ fn draw_summary(
         .count();
 
     let summary = Line::from(vec![
-        Span::styled(" \u{25C8} ", Style::default().fg(HUB_BORDER)),
+        Span::styled(" \u{25C8} ", Style::default().fg(theme().info)),
         Span::styled(
             format!("{} remote", total_remote),
-            Style::default().fg(TEXT).add_modifier(Modifier::BOLD),
+            Style::default().fg(theme().text_secondary).add_modifier(Modifier::BOLD),
         ),
-        Span::styled("  \u{2502}  ", Style::default().fg(Color::Rgb(40, 55, 80))),
+        Span::styled("  \u{2502}  ", Style::default().fg(theme().divider)),
         Span::styled(
             format!("{} conn ({} est)", total_conns, established),
-            Style::default().fg(TEXT),
+            Style::default().fg(theme().text_secondary),
         ),
-        Span::styled("  \u{2502}  ", Style::default().fg(Color::Rgb(40, 55, 80))),
+        Span::styled("  \u{2502}  ", Style::default().fg(theme().divider)),
         Span::styled(
             format!("{} countries", unique_countries),
-            Style::default().fg(Color::Rgb(170, 200, 230)),
+            Style::default().fg(theme().text),
         ),
-        Span::styled("  \u{2502}  ", Style::default().fg(Color::Rgb(40, 55, 80))),
+        Span::styled("  \u{2502}  ", Style::default().fg(theme().divider)),
         Span::styled(
             format!(
                 "{} LAN devices",
                 lan_count,
             ),
-            Style::default().fg(LAN_HEADER),
+            Style::default().fg(theme().purple),
         ),
     ]);
 
     f.render_widget(
-        Paragraph::new(vec![summary]).style(Style::default().bg(BG)),
+        Paragraph::new(vec![summary]).style(Style::default().bg(theme().bg)),
         area,
     );
 }
 
 // ─── Footer ──────────────────────────────────────────────────────────────────
 
-fn draw_footer(f: &mut Frame, area: Rect) {
-    let hints = Line::from(vec![
+/// The export prompt while it is open.
+fn export_prompt(app: &App) -> Option<Line<'static>> {
+    let path = app.topology_export_path.as_ref()?;
+    Some(Line::from(vec![
+        Span::styled(" Export topology to: ", Style::default().fg(theme().highlight).add_modifier(Modifier::BOLD)),
+        Span::styled(path.clone(), Style::default().fg(theme().text_bright)),
+        Span::styled("\u{2588}", Style::default().fg(theme().highlight)),
+        Span::styled("  .dot/.gv DOT, .mmd/.md Mermaid, otherwise JSON  Enter:export  Esc:cancel", Style::default().fg(theme().text_muted)),
+    ]))
+}
+
+fn draw_footer(f: &mut Frame, area: Rect, app: &App) {
+    let hints = export_prompt(app).unwrap_or_else(|| Line::from(vec![
         Span::styled(
             " \u{2191}\u{2193}:Select",
-            Style::default().fg(Color::Yellow),
+            Style::default().fg(theme().key),
         ),
         Span::styled("  ", Style::default()),
-        Span::styled("Enter:Detail", Style::default().fg(Color::Yellow)),
+        Span::styled("Enter:Detail", Style::default().fg(theme().key)),
         Span::styled("  ", Style::default()),
-        Span::styled("PgUp/Dn:Scroll", Style::default().fg(Color::Yellow)),
+        Span::styled("PgUp/Dn:Scroll", Style::default().fg(theme().key)),
         Span::styled("  ", Style::default()),
-        Span::styled("s:Scan LAN", Style::default().fg(Color::Yellow)),
+        Span::styled("s:Scan LAN", Style::default().fg(theme().key)),
         Span::styled("  ", Style::default()),
-        Span::styled("Tab:Next tab", Style::default().fg(Color::Rgb(60, 80, 110))),
-    ]);
+        Span::styled("T:Trace", Style::default().fg(theme().key)),
+        Span::styled("  ", Style::default()),
+        Span::styled("p:Paths", Style::default().fg(theme().key)),
+        Span::styled("  ", Style::default()),
+        Span::styled("e:Export", Style::default().fg(theme().key)),
+        Span::styled("  ", Style::default()),
+        Span::styled("Tab:Next tab", Style::default().fg(theme().text_muted)),
+    ]));
 
     f.render_widget(
-        Paragraph::new(vec![hints]).style(Style::default().bg(Color::Rgb(12, 16, 30))),
+        Paragraph::new(vec![hints]).style(Style::default().bg(theme().surface)),
         area,
     );
 }
@@ -984,6 +1031,115 @@ fn aggregate_remote_nodes(app: &App) -> Vec<RemoteNode> {
     nodes
 }
 
+/// Address and display name of the remote node selected in the diagram.
+pub fn selected_remote(app: &App) -> Option<(IpAddr, String)> {
+    let nodes = aggregate_remote_nodes(app);
+    let node = nodes.get(app.topology_scroll.min(nodes.len().checked_sub(1)?))?;
+    Some((node.ip, node.hostname.clone().unwrap_or_else(|| node.ip.to_string())))
+}
+
+// ─── Graph model (export) ────────────────────────────────────────────────────
+
+/// The diagram as nodes and edges, with the device-type filter applied.
+pub fn graph(app: &App) -> TopologyGraph {
+    let mut g = TopologyGraph::default();
+    let speeds = Traffic {
+        send_bps: Some(app.current_up_speed),
+        receive_bps: Some(app.current_down_speed),
+        ..Traffic::default()
+    };
+
+    let mut host = GraphNode::new("host", NodeKind::Host, "This PC");
+    host.ip = app.network_scanner.local_ip.map(IpAddr::V4)
+        .or_else(|| detect_local_ip(app).parse().ok());
+    host.traffic = speeds.clone();
+    g.nodes.push(host);
+
+    if let Some(gw) = app.network_scanner.gateway {
+        let mut node = GraphNode::new("gateway", NodeKind::Gateway, "Gateway");
+        node.ip = Some(IpAddr::V4(gw));
+        node.vendor = app.network_scanner.devices.iter()
+            .find(|d| d.ip == IpAddr::V4(gw))
+            .and_then(|d| d.vendor.clone());
+        g.nodes.push(node);
+        let mut edge = GraphEdge::new("host", "gateway", EdgeKind::Uplink);
+        edge.active = true;
+        edge.traffic = speeds;
+        g.edges.push(edge);
+    }
+
+    for (ip_str, is_active) in detect_dns_servers(app) {
+        let Ok(ip) = ip_str.parse::<IpAddr>() else { continue };
+        let id = format!("dns_{}", ip);
+        let mut node = GraphNode::new(&id, NodeKind::Dns, "DNS");
+        node.ip = Some(ip);
+        g.nodes.push(node);
+        let mut edge = GraphEdge::new("host", &id, EdgeKind::Dns);
+        edge.active = is_active;
+        g.edges.push(edge);
+    }
+
+    let agents_guard = app.network_scanner.snmp.lock().ok();
+    let no_agents = HashMap::new();
+    let agents = agents_guard.as_deref().unwrap_or(&no_agents);
+    let devices: Vec<&crate::types::LanDevice> = app.network_scanner.devices.iter()
+        .filter(|d| app.device_type_filter.is_none_or(|t| d.device_type == t))
+        .collect();
+    let on_graph: HashSet<IpAddr> = devices.iter().map(|d| d.ip).collect();
+    for d in &devices {
+        let id = format!("lan_{}", d.ip);
+        let label = d.custom_name.as_deref()
+            .or(d.hostname.as_deref())
+            .or(d.vendor.as_deref())
+            .map_or_else(|| d.ip.to_string(), str::to_string);
+        let mut node = GraphNode::new(&id, NodeKind::Lan, label);
+        node.ip = Some(d.ip);
+        node.mac = Some(d.mac.clone()).filter(|m| !m.is_empty());
+        node.vendor = d.vendor.clone();
+        node.device_type = Some(d.device_type)
+            .filter(|t| *t != crate::types::DeviceType::Unknown)
+            .map(|t| t.label().to_string());
+        node.online = d.is_online;
+        node.traffic = Traffic {
+            bytes_sent: Some(d.bytes_sent),
+            bytes_received: Some(d.bytes_received),
+            send_bps: Some(d.speed_sent),
+            receive_bps: Some(d.speed_received),
+            connections: None,
+        };
+        let traffic = node.traffic.clone();
+        g.nodes.push(node);
+
+        // Hang the device off its switch port when a switch on the graph reports it
+        let mut edge = match crate::network::snmp::uplink_of(agents, d.ip, &d.mac) {
+            Some((agent_ip, _, n)) if on_graph.contains(&IpAddr::V4(agent_ip)) && IpAddr::V4(agent_ip) != d.ip => {
+                let mut e = GraphEdge::new(&format!("lan_{}", agent_ip), &id, EdgeKind::Switch);
+                e.port = Some(n.local_port.clone());
+                e
+            }
+            _ => GraphEdge::new("host", &id, EdgeKind::Lan),
+        };
+        edge.active = d.is_online;
+        edge.traffic = Traffic { send_bps: traffic.send_bps, receive_bps: traffic.receive_bps, ..Traffic::default() };
+        g.edges.push(edge);
+    }
+
+    for r in aggregate_remote_nodes(app) {
+        let id = format!("remote_{}", r.ip);
+        let mut node = GraphNode::new(&id, NodeKind::Remote, r.hostname.clone().unwrap_or_else(|| r.ip.to_string()));
+        node.ip = Some(r.ip);
+        node.country = r.country_code.clone();
+        node.process = Some(r.top_process.clone()).filter(|p| !p.is_empty());
+        node.traffic.connections = Some(r.conn_count);
+        g.nodes.push(node);
+        let mut edge = GraphEdge::new("host", &id, EdgeKind::Connection);
+        edge.active = r.has_established;
+        edge.traffic.connections = Some(r.conn_count);
+        g.edges.push(edge);
+    }
+    g
+}
+
 // ─── Helper: detect DNS servers ──────────────────────────────────────────────
 
 /// Returns all known DNS servers as (ip_string, is_active) pairs.
@@ -1046,3 +1202,229 @@ fn truncate_str(s: &str, max: usize) -> &str {
         ""
     }
 }
+
+// ─── Path view ───────────────────────────────────────────────────────────────
+
+fn rtt_str(rtt: Option<u32>) -> String {
+    match rtt {
+        Some(0) => "<1 ms".to_string(),
+        Some(ms) => format!("{} ms", ms),
+        None => "*".to_string(),
+    }
+}
+
+fn draw_paths(f: &mut Frame, area: Rect, app: &App) {
+    let traces = app.tracer.snapshot();
+    let selected = traces.get(app.trace_selected.min(traces.len().saturating_sub(1)));
+
+    let outer_block = Block::default()
+        .title(Line::from(vec![
+            Span::styled(
+                " \u{1F5A7} Topology \u{203A} Paths ",
+                Style::default()
+                    .fg(theme().text)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(" {} traces  mode {} ", traces.len(), app.trace_mode.label()),
+                Style::default().fg(theme().text_dim),
+            ),
+        ]))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme().border))
+        .style(Style::default().bg(theme().bg));
+    let inner = outer_block.inner(area);
+    f.render_widget(outer_block, area);
+    if inner.height < 4 {
+        return;
+    }
+
+    let hop_rows = selected.map_or(0, |t| t.hops.len() as u16);
+    let vsplit = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(3),                                              // shared-hop tree
+            Constraint::Length((hop_rows + 3).min(inner.height / 2).max(4)), // selected trace
+            Constraint::Length(1),                                           // footer
+        ])
+        .split(inner);
+
+    draw_path_tree(f, vsplit[0], app, &traces, selected);
+    draw_hop_table(f, vsplit[1], selected);
+
+    let hints = export_prompt(app).unwrap_or_else(|| Line::from(vec![
+        Span::styled(" \u{2191}\u{2193}:Scroll", Style::default().fg(theme().key)),
+        Span::styled("  ", Style::default()),
+        Span::styled("[/]:Select trace", Style::default().fg(theme().key)),
+        Span::styled("  ", Style::default()),
+        Span::styled(format!("m:Mode ({})", app.trace_mode.label()), Style::default().fg(theme().key)),
+        Span::styled("  ", Style::default()),
+        Span::styled("p:Diagram", Style::default().fg(theme().key)),
+        Span::styled("  ", Style::default()),
+        Span::styled("e:Export", Style::default().fg(theme().key)),
+        Span::styled("  ", Style::default()),
+        Span::styled("T on Connections traces a connection", Style::default().fg(theme().text_muted)),
+    ]));
+    f.render_widget(
+        Paragraph::new(vec![hints]).style(Style::default().bg(theme().surface)),
+        vsplit[2],
+    );
+}
+
+/// Hops shared by every trace as a tree under this machine.
+fn draw_path_tree(f: &mut Frame, area: Rect, app: &App, traces: &[Trace], selected: Option<&Trace>) {
+    if traces.is_empty() {
+        let empty = Paragraph::new(vec![
+            Line::from(Span::styled(
+                " No traces yet \u{2014} press T on a remote node in the diagram or on a connection",
+                Style::default().fg(theme().text_dim),
+            )),
+        ])
+        .style(Style::default().bg(theme().bg));
+        f.render_widget(empty, area);
+        return;
+    }
+
+    let on_selected = |hop: &Hop| {
+        selected.is_some_and(|t| t.hops.get(hop.ttl as usize - 1).is_some_and(|h| h.addr == hop.addr))
+    };
+    let mut lines = vec![Line::from(Span::styled(
+        format!(" \u{25C8} This PC ({})", detect_local_ip(app)),
+        Style::default().fg(theme().info).add_modifier(Modifier::BOLD),
+    ))];
+    for line in traceroute::path_tree(traces) {
+        let hop = &line.hop;
+        let mut spans = vec![Span::styled(format!(" {}", line.prefix), Style::default().fg(theme().divider))];
+        match hop.addr {
+            Some(addr) => spans.push(Span::styled(
+                addr.to_string(),
+                Style::default()
+                    .fg(if on_selected(hop) { theme().highlight } else { theme().text })
+                    .add_modifier(Modifier::BOLD),
+            )),
+            None => spans.push(Span::styled("*", Style::default().fg(theme().text_faint))),
+        }
+        if let Some(ref name) = hop.hostname {
+            spans.push(Span::styled(format!(" {}", name), Style::default().fg(theme().accent)));
+        }
+        if let Some(ref asn) = hop.asn {
+            spans.push(Span::styled(format!("  {}", asn.label()), Style::default().fg(theme().purple)));
+        }
+        if let Some(country) = hop.country {
+            spans.push(Span::styled(format!("  {} {}", country.flag, country.code), Style::default().fg(theme().text)));
+        }
+        if hop.addr.is_some() {
+            spans.push(Span::styled(format!("  {}", rtt_str(hop.avg_rtt())), Style::default().fg(theme().text_secondary)));
+        }
+        if line.traces > 1 {
+            spans.push(Span::styled(format!("  \u{00D7}{}", line.traces), Style::default().fg(theme().ok)));
+        }
+        if !line.ends.is_empty() {
+            spans.push(Span::styled(
+                format!("  \u{2192} {}", line.ends.join(", ")),
+                Style::default().fg(theme().ok_soft).add_modifier(Modifier::BOLD),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let total = lines.len();
+    let visible = area.height as usize;
+    let offset = app.topology_scroll.min(total.saturating_sub(visible));
+    f.render_widget(
+        Paragraph::new(lines).scroll((offset as u16, 0)).style(Style::default().bg(theme().bg)),
+        area,
+    );
+    if total > visible {
+        let mut state = ScrollbarState::new(total.saturating_sub(visible)).position(offset);
+        f.render_stateful_widget(Scrollbar::new(ScrollbarOrientation::VerticalRight), area, &mut state);
+    }
+}
+
+/// Per-hop RTT, loss, reverse DNS, ASN and country of one trace.
+fn draw_hop_table(f: &mut Frame, area: Rect, trace: Option<&Trace>) {
+    let hdr_style = Style::default()
+        .fg(theme().text)
+        .add_modifier(Modifier::BOLD);
+    let header = Row::new(
+        ["Hop", "Address", "Reverse DNS", "ASN", "Country", "Loss", "Last", "Avg", "Best", "Worst"]
+            .map(|h| Cell::from(Span::styled(h, hdr_style))),
+    )
+    .style(Style::default().bg(theme().header_bg));
+
+    let dim = Style::default().fg(theme().text_dim);
+    let rows: Vec<Row> = trace
+        .map(|t| t.hops.as_slice())
+        .unwrap_or_default()
+        .iter()
+        .map(|h| {
+            let loss = h.loss_pct();
+            let loss_color = match loss {
+                0 => theme().ok,
+                1..=49 => theme().highlight,
+                _ => theme().error,
+            };
+            Row::new(vec![
+                Cell::from(Span::styled(h.ttl.to_string(), dim)),
+                Cell::from(Span::styled(
+                    h.addr.map_or("*".to_string(), |a| a.to_string()),
+                    Style::default().fg(theme().text).add_modifier(Modifier::BOLD),
+                )),
+                Cell::from(Span::styled(h.hostname.clone().unwrap_or_default(), Style::default().fg(theme().accent))),
+                Cell::from(Span::styled(h.asn.as_ref().map(|a| a.label()).unwrap_or_default(), Style::default().fg(theme().purple))),
+                Cell::from(Span::styled(
+                    h.country.map(|c| format!("{} {}", c.flag, c.name)).unwrap_or_default(),
+                    Style::default().fg(theme().text),
+                )),
+                Cell::from(Span::styled(format!("{}%", loss), Style::default().fg(loss_color))),
+                Cell::from(Span::styled(rtt_str(h.last_rtt()), Style::default().fg(theme().text_secondary))),
+                Cell::from(Span::styled(rtt_str(h.avg_rtt()), Style::default().fg(theme().text_secondary))),
+                Cell::from(Span::styled(rtt_str(h.best_rtt()), dim)),
+                Cell::from(Span::styled(rtt_str(h.worst_rtt()), dim)),
+            ])
+        })
+        .collect();
+
+    let title = match trace {
+        Some(t) => {
+            let (state, color) = match &t.state {
+                TraceState::Running => (format!("tracing hop {}...", t.hops.len().max(1)), theme().info),
+                TraceState::Reached => (format!("reached in {} hops", t.hops.len()), theme().ok),
+                TraceState::Unreached => ("target did not answer".to_string(), theme().highlight),
+                TraceState::Failed(e) => (e.clone(), theme().error),
+            };
+            let port = if t.mode == traceroute::TraceMode::TcpSyn { format!(":{}", t.port) } else { String::new() };
+            Line::from(vec![
+                Span::styled(format!(" {} ", t.label), Style::default().fg(theme().highlight).add_modifier(Modifier::BOLD)),
+                Span::styled(format!("{}{} {} ", t.target, port, t.mode.label()), Style::default().fg(theme().text_dim)),
+                Span::styled(format!(" {} ", state), Style::default().fg(color)),
+            ])
+        }
+        None => Line::from(Span::styled(" No trace selected ", Style::default().fg(theme().text_dim))),
+    };
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(4),  // Hop
+            Constraint::Length(16), // Address
+            Constraint::Min(20),    // Reverse DNS
+            Constraint::Length(18), // ASN
+            Constraint::Length(18), // Country
+            Constraint::Length(5),  // Loss
+            Constraint::Length(7),  // Last
+            Constraint::Length(7),  // Avg
+            Constraint::Length(7),  // Best
+            Constraint::Length(7),  // Worst
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .title(title)
+            .borders(Borders::TOP)
+            .border_style(Style::default().fg(theme().border))
+            .style(Style::default().bg(theme().bg)),
+    );
+    f.render_widget(table, area);
+}
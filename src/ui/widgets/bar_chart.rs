@@ -3,6 +3,7 @@ use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
 use ratatui::Frame;
+use crate::ui::theme::theme;
 
 pub struct BarEntry {
     pub label: String,
@@ -32,21 +33,21 @@ pub fn draw_bar_chart(f: &mut Frame, area: Rect, title: &str, entries: &[BarEntr
         let val_str = fmt_bytes_short(entry.value);
 
         Line::from(vec![
-            Span::styled(name, Style::default().fg(Color::Rgb(130, 200, 140))),
+            Span::styled(name, Style::default().fg(theme().ok_soft)),
             Span::styled(" ", Style::default()),
             Span::styled(bar, Style::default().fg(entry.color)),
-            Span::styled(format!(" {}", val_str), Style::default().fg(Color::Rgb(100, 120, 150))),
+            Span::styled(format!(" {}", val_str), Style::default().fg(theme().text_dim)),
         ])
     }).collect();
 
     let block = Block::default()
         .title(Span::styled(
             format!(" {} ", title),
-            Style::default().fg(Color::Rgb(160, 180, 220)).add_modifier(Modifier::BOLD),
+            Style::default().fg(theme().text).add_modifier(Modifier::BOLD),
         ))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Rgb(30, 50, 85)))
-        .style(Style::default().bg(Color::Rgb(8, 12, 24)));
+        .border_style(Style::default().fg(theme().border))
+        .style(Style::default().bg(theme().bg));
 
     f.render_widget(Paragraph::new(lines).block(block), area);
 }
@@ -1,12 +1,13 @@
 //! Connection Stats KPI widget — live TCP state distribution, TCP/UDP split, connection count.
 
 use ratatui::layout::Rect;
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
 use ratatui::Frame;
 
 use crate::types::{ConnProto, TcpState};
+use crate::ui::theme::theme;
 
 /// Snapshot of connection statistics for rendering.
 pub struct ConnStats {
@@ -54,25 +55,25 @@ impl ConnStats {
 
 /// Draw a compact connection stats widget.
 pub fn draw_conn_stats(f: &mut Frame, area: Rect, stats: &ConnStats, tick: u64) {
-    let bg = Color::Rgb(8, 12, 24);
+    let bg = theme().bg;
 
     let block = Block::default()
         .title(Line::from(vec![
             Span::styled(
                 " Connections ",
                 Style::default()
-                    .fg(Color::Rgb(80, 200, 255))
+                    .fg(theme().info)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
                 format!("{} ", stats.total),
                 Style::default()
-                    .fg(Color::Rgb(255, 255, 255))
+                    .fg(theme().text_bright)
                     .add_modifier(Modifier::BOLD),
             ),
         ]))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Rgb(30, 50, 85)))
+        .border_style(Style::default().fg(theme().border))
         .style(Style::default().bg(bg));
 
     let inner = block.inner(area);
@@ -94,18 +95,18 @@ pub fn draw_conn_stats(f: &mut Frame, area: Rect, stats: &ConnStats, tick: u64)
     lines.push(Line::from(vec![
         Span::styled(
             format!(" {} ", pulse_char),
-            Style::default().fg(Color::Rgb(80, 220, 120)),
+            Style::default().fg(theme().ok),
         ),
-        Span::styled("EST  ", Style::default().fg(Color::Rgb(70, 90, 110))),
+        Span::styled("EST  ", Style::default().fg(theme().text_muted)),
         Span::styled(
             format!("{:>4} ", stats.established),
             Style::default()
-                .fg(Color::Rgb(80, 220, 120))
+                .fg(theme().ok)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::styled(
             "█".repeat(est_bar_len.min(bar_w)),
-            Style::default().fg(Color::Rgb(40, 140, 70)),
+            Style::default().fg(theme().text_muted),
         ),
     ]));
 
@@ -114,14 +115,14 @@ pub fn draw_conn_stats(f: &mut Frame, area: Rect, stats: &ConnStats, tick: u64)
         let tw_bar = ((stats.time_wait as f64 / max_state.max(1) as f64) * bar_w as f64).ceil() as usize;
         lines.push(Line::from(vec![
             Span::styled("   ", Style::default()),
-            Span::styled("TW   ", Style::default().fg(Color::Rgb(70, 90, 110))),
+            Span::styled("TW   ", Style::default().fg(theme().text_muted)),
             Span::styled(
                 format!("{:>4} ", stats.time_wait),
-                Style::default().fg(Color::Rgb(180, 100, 255)),
+                Style::default().fg(theme().purple),
             ),
             Span::styled(
                 "█".repeat(tw_bar.min(bar_w)),
-                Style::default().fg(Color::Rgb(120, 60, 180)),
+                Style::default().fg(theme().text_muted),
             ),
         ]));
     }
@@ -130,15 +131,15 @@ pub fn draw_conn_stats(f: &mut Frame, area: Rect, stats: &ConnStats, tick: u64)
     if stats.close_wait > 0 || stats.syn_sent > 0 || inner.height > 4 {
         lines.push(Line::from(vec![
             Span::styled("   ", Style::default()),
-            Span::styled("CW   ", Style::default().fg(Color::Rgb(70, 90, 110))),
+            Span::styled("CW   ", Style::default().fg(theme().text_muted)),
             Span::styled(
                 format!("{:>4} ", stats.close_wait),
-                Style::default().fg(Color::Rgb(255, 140, 80)),
+                Style::default().fg(theme().caution),
             ),
-            Span::styled("  SYN ", Style::default().fg(Color::Rgb(70, 90, 110))),
+            Span::styled("  SYN ", Style::default().fg(theme().text_muted)),
             Span::styled(
                 format!("{}", stats.syn_sent),
-                Style::default().fg(Color::Rgb(255, 220, 80)),
+                Style::default().fg(theme().highlight),
             ),
         ]));
     }
@@ -147,15 +148,15 @@ pub fn draw_conn_stats(f: &mut Frame, area: Rect, stats: &ConnStats, tick: u64)
     if stats.listen > 0 || inner.height > 5 {
         lines.push(Line::from(vec![
             Span::styled("   ", Style::default()),
-            Span::styled("LISN ", Style::default().fg(Color::Rgb(70, 90, 110))),
+            Span::styled("LISN ", Style::default().fg(theme().text_muted)),
             Span::styled(
                 format!("{:>4} ", stats.listen),
-                Style::default().fg(Color::Rgb(80, 180, 255)),
+                Style::default().fg(theme().info),
             ),
-            Span::styled("  OTH ", Style::default().fg(Color::Rgb(70, 90, 110))),
+            Span::styled("  OTH ", Style::default().fg(theme().text_muted)),
             Span::styled(
                 format!("{}", stats.other),
-                Style::default().fg(Color::Rgb(100, 110, 130)),
+                Style::default().fg(theme().text_dim),
             ),
         ]));
     }
@@ -174,21 +175,21 @@ pub fn draw_conn_stats(f: &mut Frame, area: Rect, stats: &ConnStats, tick: u64)
             Span::styled(" ", Style::default()),
             Span::styled(
                 format!("TCP:{}", stats.tcp_count),
-                Style::default().fg(Color::Rgb(80, 160, 255)).add_modifier(Modifier::BOLD),
+                Style::default().fg(theme().info).add_modifier(Modifier::BOLD),
             ),
             Span::styled(" ", Style::default()),
             Span::styled(
                 "▓".repeat(tcp_w.min(split_w)),
-                Style::default().fg(Color::Rgb(40, 100, 180)),
+                Style::default().fg(theme().border_strong),
             ),
             Span::styled(
                 "░".repeat(udp_w.min(split_w)),
-                Style::default().fg(Color::Rgb(80, 50, 120)),
+                Style::default().fg(theme().text_faint),
             ),
             Span::styled(" ", Style::default()),
             Span::styled(
                 format!("UDP:{}", stats.udp_count),
-                Style::default().fg(Color::Rgb(180, 100, 255)).add_modifier(Modifier::BOLD),
+                Style::default().fg(theme().purple).add_modifier(Modifier::BOLD),
             ),
         ]));
     }
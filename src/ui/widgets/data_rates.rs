@@ -1,29 +1,30 @@
 //! Data Rates KPI widget — per-app live speed mini-sparklines with rates.
 
 use ratatui::layout::Rect;
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
 use ratatui::Frame;
 
 use crate::app::App;
 use crate::utils::format_speed;
+use crate::ui::theme::theme;
 
 /// Draw top active apps with their live data rates.
 pub fn draw_data_rates(f: &mut Frame, area: Rect, app: &App) {
-    let bg = Color::Rgb(8, 12, 24);
+    let bg = theme().bg;
 
     let block = Block::default()
         .title(Line::from(vec![
             Span::styled(
                 " Live Rates ",
                 Style::default()
-                    .fg(Color::Rgb(100, 220, 200))
+                    .fg(theme().teal)
                     .add_modifier(Modifier::BOLD),
             ),
         ]))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Rgb(30, 50, 85)))
+        .border_style(Style::default().fg(theme().border))
         .style(Style::default().bg(bg));
 
     let inner = block.inner(area);
@@ -56,7 +57,7 @@ pub fn draw_data_rates(f: &mut Frame, area: Rect, app: &App) {
     if apps.is_empty() {
         lines.push(Line::from(Span::styled(
             "  No active apps",
-            Style::default().fg(Color::Rgb(50, 65, 90)),
+            Style::default().fg(theme().text_faint),
         )));
     }
 
@@ -83,27 +84,27 @@ pub fn draw_data_rates(f: &mut Frame, area: Rect, app: &App) {
         };
 
         let speed_color = if total > 100_000.0 {
-            Color::Rgb(80, 255, 160)
+            theme().teal
         } else if total > 10_000.0 {
-            Color::Rgb(80, 200, 120)
+            theme().ok
         } else {
-            Color::Rgb(60, 140, 100)
+            theme().text_dim
         };
 
         lines.push(Line::from(vec![
             Span::styled(
                 format!(" {}", display_name),
-                Style::default().fg(Color::Rgb(160, 175, 200)),
+                Style::default().fg(theme().text),
             ),
-            Span::styled(" ▼", Style::default().fg(Color::Rgb(60, 120, 180))),
+            Span::styled(" ▼", Style::default().fg(theme().text_dim)),
             Span::styled(
                 format!("{:>8}", format_speed(down)),
-                Style::default().fg(Color::Rgb(80, 170, 230)),
+                Style::default().fg(theme().info),
             ),
-            Span::styled(" ▲", Style::default().fg(Color::Rgb(120, 70, 180))),
+            Span::styled(" ▲", Style::default().fg(theme().text_dim)),
             Span::styled(
                 format!("{:>8} ", format_speed(up)),
-                Style::default().fg(Color::Rgb(170, 120, 240)),
+                Style::default().fg(theme().purple),
             ),
             Span::styled(
                 "█".repeat(bar_len.min(bar_budget)),
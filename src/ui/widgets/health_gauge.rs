@@ -1,16 +1,17 @@
 use ratatui::layout::Rect;
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
 use ratatui::Frame;
+use crate::ui::theme::theme;
 
 /// Compute and display a network health score.
 pub fn draw_health_gauge(f: &mut Frame, area: Rect, score: u8, active_conns: usize, countries: usize, alert_count: usize, threat_count: usize) {
     let (color, label) = match score {
-        80..=100 => (Color::Rgb(80, 200, 120), "Excellent"),
-        60..=79 => (Color::Rgb(100, 200, 255), "Good"),
-        40..=59 => (Color::Rgb(255, 200, 80), "Fair"),
-        _ => (Color::Rgb(255, 80, 80), "Poor"),
+        80..=100 => (theme().ok, "Excellent"),
+        60..=79 => (theme().accent, "Good"),
+        40..=59 => (theme().highlight, "Fair"),
+        _ => (theme().error, "Poor"),
     };
 
     // Build a simple gauge bar
@@ -34,16 +35,16 @@ pub fn draw_health_gauge(f: &mut Frame, area: Rect, score: u8, active_conns: usi
             Style::default().fg(color),
         )),
         Line::from(vec![
-            Span::styled(format!("  {} ", active_conns), Style::default().fg(Color::Rgb(100, 200, 255)).add_modifier(Modifier::BOLD)),
-            Span::styled("conns  ", Style::default().fg(Color::Rgb(80, 100, 130))),
-            Span::styled(format!("{} ", countries), Style::default().fg(Color::Rgb(170, 200, 230)).add_modifier(Modifier::BOLD)),
-            Span::styled("countries  ", Style::default().fg(Color::Rgb(80, 100, 130))),
-            Span::styled(format!("{} ", alert_count), Style::default().fg(Color::Rgb(255, 200, 80)).add_modifier(Modifier::BOLD)),
-            Span::styled("alerts  ", Style::default().fg(Color::Rgb(80, 100, 130))),
+            Span::styled(format!("  {} ", active_conns), Style::default().fg(theme().accent).add_modifier(Modifier::BOLD)),
+            Span::styled("conns  ", Style::default().fg(theme().text_muted)),
+            Span::styled(format!("{} ", countries), Style::default().fg(theme().text).add_modifier(Modifier::BOLD)),
+            Span::styled("countries  ", Style::default().fg(theme().text_muted)),
+            Span::styled(format!("{} ", alert_count), Style::default().fg(theme().highlight).add_modifier(Modifier::BOLD)),
+            Span::styled("alerts  ", Style::default().fg(theme().text_muted)),
             if threat_count > 0 {
-                Span::styled(format!("{} threats", threat_count), Style::default().fg(Color::Rgb(255, 80, 80)).add_modifier(Modifier::BOLD))
+                Span::styled(format!("{} threats", threat_count), Style::default().fg(theme().error).add_modifier(Modifier::BOLD))
             } else {
-                Span::styled("0 threats", Style::default().fg(Color::Rgb(60, 80, 100)))
+                Span::styled("0 threats", Style::default().fg(theme().text_muted))
             },
         ]),
     ];
@@ -51,11 +52,11 @@ pub fn draw_health_gauge(f: &mut Frame, area: Rect, score: u8, active_conns: usi
     let block = Block::default()
         .title(Span::styled(
             " Network Summary ",
-            Style::default().fg(Color::Rgb(160, 180, 220)).add_modifier(Modifier::BOLD),
+            Style::default().fg(theme().text).add_modifier(Modifier::BOLD),
         ))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Rgb(30, 50, 85)))
-        .style(Style::default().bg(Color::Rgb(8, 12, 24)));
+        .border_style(Style::default().fg(theme().border))
+        .style(Style::default().bg(theme().bg));
 
     f.render_widget(Paragraph::new(lines).block(block), area);
 }
@@ -1,17 +1,18 @@
 //! Network Pulse KPI widget — live packets/sec, unique IPs, data throughput mini-bar.
 
 use ratatui::layout::Rect;
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
 use ratatui::Frame;
 
 use crate::app::App;
 use crate::utils::format_bytes;
+use crate::ui::theme::theme;
 
 /// Draw a compact network pulse / KPI widget.
 pub fn draw_network_pulse(f: &mut Frame, area: Rect, app: &App) {
-    let bg = Color::Rgb(8, 12, 24);
+    let bg = theme().bg;
 
     // Compute live metrics
     let devices_online = app.network_scanner.devices.iter().filter(|d| d.is_online).count();
@@ -37,11 +38,11 @@ pub fn draw_network_pulse(f: &mut Frame, area: Rect, app: &App) {
         "◇"
     };
     let pulse_color = if app.current_down_speed > 100_000.0 || app.current_up_speed > 100_000.0 {
-        Color::Rgb(80, 255, 160)
+        theme().teal
     } else if app.current_down_speed > 1000.0 || app.current_up_speed > 1000.0 {
-        Color::Rgb(80, 200, 120)
+        theme().ok
     } else {
-        Color::Rgb(50, 70, 90)
+        theme().text_faint
     };
 
     let block = Block::default()
@@ -54,7 +55,7 @@ pub fn draw_network_pulse(f: &mut Frame, area: Rect, app: &App) {
             ),
         ]))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Rgb(30, 50, 85)))
+        .border_style(Style::default().fg(theme().border))
         .style(Style::default().bg(bg));
 
     let inner = block.inner(area);
@@ -71,45 +72,45 @@ pub fn draw_network_pulse(f: &mut Frame, area: Rect, app: &App) {
         Span::styled(" ", Style::default()),
         Span::styled(
             format!("{}", devices_online),
-            Style::default().fg(Color::Rgb(80, 220, 160)).add_modifier(Modifier::BOLD),
+            Style::default().fg(theme().teal).add_modifier(Modifier::BOLD),
         ),
         Span::styled(
             format!("/{} dev", total_devices),
-            Style::default().fg(Color::Rgb(60, 80, 110)),
+            Style::default().fg(theme().text_muted),
         ),
         Span::styled("  ", Style::default()),
         Span::styled(
             format!("{}", unique_countries),
-            Style::default().fg(Color::Rgb(255, 200, 80)).add_modifier(Modifier::BOLD),
+            Style::default().fg(theme().highlight).add_modifier(Modifier::BOLD),
         ),
         Span::styled(
             " countries",
-            Style::default().fg(Color::Rgb(60, 80, 110)),
+            Style::default().fg(theme().text_muted),
         ),
         Span::styled("  ", Style::default()),
         Span::styled(
             format!("{}", unique_apps),
-            Style::default().fg(Color::Rgb(180, 140, 255)).add_modifier(Modifier::BOLD),
+            Style::default().fg(theme().purple).add_modifier(Modifier::BOLD),
         ),
         Span::styled(
             " apps",
-            Style::default().fg(Color::Rgb(60, 80, 110)),
+            Style::default().fg(theme().text_muted),
         ),
     ]));
 
     // Row 2: Today's data
     if inner.height as usize > lines.len() {
         lines.push(Line::from(vec![
-            Span::styled(" Today ", Style::default().fg(Color::Rgb(50, 65, 90))),
-            Span::styled("▼", Style::default().fg(Color::Rgb(80, 160, 220))),
+            Span::styled(" Today ", Style::default().fg(theme().text_faint)),
+            Span::styled("▼", Style::default().fg(theme().info)),
             Span::styled(
                 format!("{} ", format_bytes(today_down)),
-                Style::default().fg(Color::Rgb(100, 170, 230)),
+                Style::default().fg(theme().info),
             ),
-            Span::styled("▲", Style::default().fg(Color::Rgb(160, 100, 220))),
+            Span::styled("▲", Style::default().fg(theme().purple)),
             Span::styled(
                 format!("{}", format_bytes(today_up)),
-                Style::default().fg(Color::Rgb(180, 140, 240)),
+                Style::default().fg(theme().purple),
             ),
         ]));
     }
@@ -119,22 +120,22 @@ pub fn draw_network_pulse(f: &mut Frame, area: Rect, app: &App) {
         let fw_on = app.firewall_manager.enabled;
         let unread = app.alert_engine.unread();
         lines.push(Line::from(vec![
-            Span::styled(" FW:", Style::default().fg(Color::Rgb(50, 65, 90))),
+            Span::styled(" FW:", Style::default().fg(theme().text_faint)),
             Span::styled(
                 if fw_on { "ON " } else { "OFF" },
                 Style::default()
-                    .fg(if fw_on { Color::Rgb(80, 200, 120) } else { Color::Rgb(255, 80, 80) })
+                    .fg(if fw_on { theme().ok } else { theme().error })
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled("  Alerts:", Style::default().fg(Color::Rgb(50, 65, 90))),
+            Span::styled("  Alerts:", Style::default().fg(theme().text_faint)),
             Span::styled(
                 format!("{}", app.alert_engine.alerts.len()),
-                Style::default().fg(Color::Rgb(100, 120, 150)),
+                Style::default().fg(theme().text_dim),
             ),
             if unread > 0 {
                 Span::styled(
                     format!(" ({} new)", unread),
-                    Style::default().fg(Color::Rgb(255, 180, 60)).add_modifier(Modifier::BOLD),
+                    Style::default().fg(theme().warn).add_modifier(Modifier::BOLD),
                 )
             } else {
                 Span::styled("", Style::default())
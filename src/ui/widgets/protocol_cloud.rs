@@ -5,6 +5,7 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
 
 use crate::network::protocols::ProtocolTracker;
+use crate::ui::theme::theme;
 
 /// Format a count compactly: 0-999 as-is, 1000+ as "1.2k", 1_000_000+ as "1.2M".
 fn compact_count(n: u64) -> String {
@@ -33,28 +34,14 @@ fn compact_count(n: u64) -> String {
 
 /// Interpolate a protocol color toward dim based on brightness (0.0..1.0).
 fn fade_color(base: Color, brightness: f64) -> Color {
-    match base {
-        Color::Rgb(r, g, b) => {
-            let dim_r: u8 = 40;
-            let dim_g: u8 = 45;
-            let dim_b: u8 = 55;
-            let br = brightness.clamp(0.0, 1.0);
-            let out_r = dim_r as f64 + (r as f64 - dim_r as f64) * br;
-            let out_g = dim_g as f64 + (g as f64 - dim_g as f64) * br;
-            let out_b = dim_b as f64 + (b as f64 - dim_b as f64) * br;
-            Color::Rgb(out_r as u8, out_g as u8, out_b as u8)
-        }
-        other => {
-            if brightness > 0.3 { other } else { Color::DarkGray }
-        }
-    }
+    theme().fade(base, (0.2 + 0.8 * brightness.clamp(0.0, 1.0)) as f32)
 }
 
 /// Draw the protocol tag cloud widget.
 pub fn draw_protocol_cloud(f: &mut Frame, area: Rect, tracker: &ProtocolTracker, tick: u64) {
-    let bg = Color::Rgb(8, 12, 24);
-    let border_color = Color::Rgb(30, 50, 85);
-    let title_color = Color::Rgb(100, 160, 255);
+    let bg = theme().bg;
+    let border_color = theme().border;
+    let title_color = theme().info;
 
     let block = Block::default()
         .title(Line::from(vec![
@@ -74,7 +61,7 @@ pub fn draw_protocol_cloud(f: &mut Frame, area: Rect, tracker: &ProtocolTracker,
     let protos = tracker.active_protocols(tick);
     if protos.is_empty() {
         let empty = Paragraph::new(Line::from(vec![
-            Span::styled("  Waiting for packets...", Style::default().fg(Color::Rgb(60, 70, 90))),
+            Span::styled("  Waiting for packets...", Style::default().fg(theme().text_faint)),
         ])).style(Style::default().bg(bg));
         f.render_widget(empty, inner);
         return;
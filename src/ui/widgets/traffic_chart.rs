@@ -1,9 +1,10 @@
 use ratatui::layout::Rect;
-use ratatui::style::{Color, Style};
+use ratatui::style::Style;
 use ratatui::text::Span;
 use ratatui::widgets::{Block, Borders};
 use ratatui::widgets::canvas::{Canvas, Line as CanvasLine};
 use ratatui::Frame;
+use crate::ui::theme::theme;
 
 /// Draw a time-series line graph of download/upload traffic.
 /// `data` is a slice of (down_bps, up_bps) samples, newest last.
@@ -12,10 +13,10 @@ pub fn draw_traffic_chart(f: &mut Frame, area: Rect, data: &[(f64, f64)], _time_
     if area.width < 10 || area.height < 5 || data.is_empty() {
         // Too small, render empty block
         let block = Block::default()
-            .title(Span::styled(" Traffic ", Style::default().fg(Color::Rgb(160, 180, 220))))
+            .title(Span::styled(" Traffic ", Style::default().fg(theme().text)))
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Rgb(30, 50, 85)))
-            .style(Style::default().bg(Color::Rgb(12, 16, 28)));
+            .border_style(Style::default().fg(theme().border))
+            .style(Style::default().bg(theme().surface));
         f.render_widget(block, area);
         return;
     }
@@ -32,8 +33,8 @@ pub fn draw_traffic_chart(f: &mut Frame, area: Rect, data: &[(f64, f64)], _time_
 
     // Build title with time range buttons
     let title_spans = vec![
-        Span::styled(" Traffic Graph ", Style::default().fg(Color::Rgb(160, 180, 220)).add_modifier(ratatui::style::Modifier::BOLD)),
-        Span::styled(" │ ", Style::default().fg(Color::Rgb(40, 55, 80))),
+        Span::styled(" Traffic Graph ", Style::default().fg(theme().text).add_modifier(ratatui::style::Modifier::BOLD)),
+        Span::styled(" │ ", Style::default().fg(theme().divider)),
         range_btn("5m", 300, selected_range),
         Span::styled(" ", Style::default()),
         range_btn("15m", 900, selected_range),
@@ -41,8 +42,8 @@ pub fn draw_traffic_chart(f: &mut Frame, area: Rect, data: &[(f64, f64)], _time_
         range_btn("1h", 3600, selected_range),
         Span::styled(" ", Style::default()),
         range_btn("24h", 86400, selected_range),
-        Span::styled(" │ ", Style::default().fg(Color::Rgb(40, 55, 80))),
-        Span::styled(format!("Peak: {}", fmt_speed(max_val)), Style::default().fg(Color::Rgb(100, 120, 150))),
+        Span::styled(" │ ", Style::default().fg(theme().divider)),
+        Span::styled(format!("Peak: {}", fmt_speed(max_val)), Style::default().fg(theme().text_dim)),
     ];
 
     let canvas = Canvas::default()
@@ -50,8 +51,8 @@ pub fn draw_traffic_chart(f: &mut Frame, area: Rect, data: &[(f64, f64)], _time_
             Block::default()
                 .title(ratatui::text::Line::from(title_spans))
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Rgb(30, 50, 85)))
-                .style(Style::default().bg(Color::Rgb(8, 12, 24))),
+                .border_style(Style::default().fg(theme().border))
+                .style(Style::default().bg(theme().bg)),
         )
         .x_bounds([0.0, chart_width])
         .y_bounds([0.0, max_val * 1.1])
@@ -64,7 +65,7 @@ pub fn draw_traffic_chart(f: &mut Frame, area: Rect, data: &[(f64, f64)], _time_
                 let y2 = data[i].0;
                 ctx.draw(&CanvasLine {
                     x1, y1, x2, y2,
-                    color: Color::Rgb(50, 160, 255),
+                    color: theme().download,
                 });
             }
             // Draw upload line (purple)
@@ -75,7 +76,7 @@ pub fn draw_traffic_chart(f: &mut Frame, area: Rect, data: &[(f64, f64)], _time_
                 let y2 = data[i].1;
                 ctx.draw(&CanvasLine {
                     x1, y1, x2, y2,
-                    color: Color::Rgb(180, 100, 255),
+                    color: theme().upload,
                 });
             }
         });
@@ -87,12 +88,12 @@ fn range_btn(label: &str, samples: usize, selected: usize) -> Span<'static> {
     if samples == selected {
         Span::styled(
             format!("[{}]", label),
-            Style::default().fg(Color::Rgb(255, 220, 120)).add_modifier(ratatui::style::Modifier::BOLD),
+            Style::default().fg(theme().highlight).add_modifier(ratatui::style::Modifier::BOLD),
         )
     } else {
         Span::styled(
             format!(" {} ", label),
-            Style::default().fg(Color::Rgb(80, 100, 130)),
+            Style::default().fg(theme().text_muted),
         )
     }
 }
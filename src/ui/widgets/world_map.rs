@@ -12,23 +12,12 @@ use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
 use ratatui::Frame;
+use crate::ui::theme::theme;
 
 // ── Color palette ──────────────────────────────────────────────────────────
 
-const CLR_OCEAN: Color = Color::Rgb(8, 12, 24);
-const CLR_BORDER: Color = Color::Rgb(30, 50, 85);
-const CLR_LAND: Color = Color::Rgb(30, 55, 85);
-const CLR_NORMAL: Color = Color::Rgb(80, 200, 120);
-const CLR_MODERATE: Color = Color::Rgb(255, 200, 80);
-const CLR_HIGH: Color = Color::Rgb(255, 130, 60);
-const CLR_THREAT: Color = Color::Rgb(255, 60, 60);
 
 // Activity-based colors
-const CLR_CONNECTING: Color = Color::Rgb(60, 180, 255);    // cyan-blue for new connections
-const CLR_RECEIVING: Color = Color::Rgb(50, 255, 130);     // bright green for inbound data
-const CLR_SENDING: Color = Color::Rgb(200, 120, 255);      // purple/magenta for outbound data
-const CLR_DISCONNECTING: Color = Color::Rgb(255, 100, 60); // red-orange for disconnections
-const CLR_MIXED: Color = Color::Rgb(220, 230, 255);        // bright white-blue for mixed activity
 
 /// Fade duration for closed connections (in ticks, 1 tick = 1s).
 const FADE_TICKS: u64 = 10;
@@ -402,13 +391,13 @@ impl BrailleCanvas {
                 let bits = self.cells[row][col];
                 let ch = char::from_u32(BRAILLE_BASE + bits as u32).unwrap_or(' ');
                 let fg = if bits == 0 {
-                    CLR_OCEAN
+                    theme().bg
                 } else {
-                    self.colors[row][col].unwrap_or(CLR_LAND)
+                    self.colors[row][col].unwrap_or(theme().divider)
                 };
                 spans.push(Span::styled(
                     ch.to_string(),
-                    Style::default().fg(fg).bg(CLR_OCEAN),
+                    Style::default().fg(fg).bg(theme().bg),
                 ));
             }
             lines.push(Line::from(spans));
@@ -421,7 +410,7 @@ impl BrailleCanvas {
 
 fn marker_color(count: usize, has_threat: bool, activity: &CountryActivity, tick: u64) -> Color {
     if has_threat {
-        return CLR_THREAT;
+        return theme().error;
     }
 
     // Activity-based colors take priority when active
@@ -430,52 +419,42 @@ fn marker_color(count: usize, has_threat: bool, activity: &CountryActivity, tick
     if has_activity {
         // Mixed send+receive = white pulse
         if activity.sending && activity.receiving {
-            return if tick % 2 == 0 { CLR_MIXED } else { Color::Rgb(180, 200, 230) };
+            return if tick % 2 == 0 { theme().text_bright } else { theme().text };
         }
         if activity.connecting {
             // Pulse cyan
-            return if tick % 2 == 0 { CLR_CONNECTING } else { Color::Rgb(40, 140, 200) };
+            return if tick % 2 == 0 { theme().info } else { theme().text_dim };
         }
         if activity.receiving {
-            return CLR_RECEIVING;
+            return theme().teal;
         }
         if activity.sending {
-            return CLR_SENDING;
+            return theme().purple;
         }
         if activity.disconnecting {
             // Fade effect - dimmer red
-            return if tick % 2 == 0 { CLR_DISCONNECTING } else { Color::Rgb(180, 70, 40) };
+            return if tick % 2 == 0 { theme().caution } else { theme().error };
         }
     }
 
     // Fall back to count-based coloring
     if count > 20 {
-        CLR_HIGH
+        theme().caution
     } else if count >= 6 {
-        CLR_MODERATE
+        theme().highlight
     } else {
-        CLR_NORMAL
+        theme().ok
     }
 }
 
 /// Produce a dimmed version of a color for the glow effect.
 fn dim_color(c: Color) -> Color {
-    match c {
-        Color::Rgb(r, g, b) => Color::Rgb(r / 3, g / 3, b / 3),
-        other => other,
-    }
+    theme().fade(c, 0.33)
 }
 
-/// Scale a color's brightness by a factor (0.0–1.0).
+/// Scale a color's brightness by a factor (0.0–1.0), toward the ocean.
 fn scale_color(c: Color, factor: f32) -> Color {
-    match c {
-        Color::Rgb(r, g, b) => Color::Rgb(
-            (r as f32 * factor) as u8,
-            (g as f32 * factor) as u8,
-            (b as f32 * factor) as u8,
-        ),
-        other => other,
-    }
+    theme().fade(c, factor)
 }
 
 /// Simple hash for IP-based jitter so dots spread within a country.
@@ -539,7 +518,7 @@ fn setup_map(inner_w: usize, map_h: usize) -> MapSetup {
             let py0 = (((my0 - y_top) / (y_bot - y_top)) * dot_h as f64) as i32;
             let px1 = (mx1 * dot_w as f64) as i32;
             let py1 = (((my1 - y_top) / (y_bot - y_top)) * dot_h as f64) as i32;
-            canvas.line(px0, py0, px1, py1, CLR_LAND);
+            canvas.line(px0, py0, px1, py1, theme().divider);
         }
     }
 
@@ -550,8 +529,8 @@ fn early_exit_block(f: &mut Frame, area: Rect) -> bool {
     if area.width < 6 || area.height < 5 {
         let block = Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(CLR_BORDER))
-            .style(Style::default().bg(CLR_OCEAN));
+            .border_style(Style::default().fg(theme().border))
+            .style(Style::default().bg(theme().bg));
         f.render_widget(block, area);
         return true;
     }
@@ -599,16 +578,16 @@ pub fn draw_world_map(f: &mut Frame, area: Rect, countries: &[CountryMarker], ti
     let mut lines = ms.canvas.render();
 
     let legend = Line::from(vec![
-        Span::styled(" \u{25CF}", Style::default().fg(CLR_CONNECTING).bg(CLR_OCEAN)),
-        Span::styled(" New ", Style::default().fg(Color::Rgb(100, 120, 150)).bg(CLR_OCEAN)),
-        Span::styled("\u{25CF}", Style::default().fg(CLR_RECEIVING).bg(CLR_OCEAN)),
-        Span::styled(" Recv ", Style::default().fg(Color::Rgb(100, 120, 150)).bg(CLR_OCEAN)),
-        Span::styled("\u{25CF}", Style::default().fg(CLR_SENDING).bg(CLR_OCEAN)),
-        Span::styled(" Send ", Style::default().fg(Color::Rgb(100, 120, 150)).bg(CLR_OCEAN)),
-        Span::styled("\u{25CF}", Style::default().fg(CLR_DISCONNECTING).bg(CLR_OCEAN)),
-        Span::styled(" Close ", Style::default().fg(Color::Rgb(100, 120, 150)).bg(CLR_OCEAN)),
-        Span::styled("\u{25CF}", Style::default().fg(CLR_THREAT).bg(CLR_OCEAN)),
-        Span::styled(" Threat", Style::default().fg(Color::Rgb(100, 120, 150)).bg(CLR_OCEAN)),
+        Span::styled(" \u{25CF}", Style::default().fg(theme().info).bg(theme().bg)),
+        Span::styled(" New ", Style::default().fg(theme().text_dim).bg(theme().bg)),
+        Span::styled("\u{25CF}", Style::default().fg(theme().teal).bg(theme().bg)),
+        Span::styled(" Recv ", Style::default().fg(theme().text_dim).bg(theme().bg)),
+        Span::styled("\u{25CF}", Style::default().fg(theme().purple).bg(theme().bg)),
+        Span::styled(" Send ", Style::default().fg(theme().text_dim).bg(theme().bg)),
+        Span::styled("\u{25CF}", Style::default().fg(theme().caution).bg(theme().bg)),
+        Span::styled(" Close ", Style::default().fg(theme().text_dim).bg(theme().bg)),
+        Span::styled("\u{25CF}", Style::default().fg(theme().error).bg(theme().bg)),
+        Span::styled(" Threat", Style::default().fg(theme().text_dim).bg(theme().bg)),
     ]);
     lines.push(legend);
 
@@ -618,25 +597,25 @@ pub fn draw_world_map(f: &mut Frame, area: Rect, countries: &[CountryMarker], ti
     let mut title_spans = vec![
         Span::styled(
             " Connection Map ",
-            Style::default().fg(Color::Rgb(160, 180, 220)).add_modifier(Modifier::BOLD),
+            Style::default().fg(theme().text).add_modifier(Modifier::BOLD),
         ),
         Span::styled(
             format!(" {} countries ", active),
-            Style::default().fg(Color::Rgb(100, 120, 150)),
+            Style::default().fg(theme().text_dim),
         ),
     ];
     if threat_count > 0 {
         title_spans.push(Span::styled(
             format!(" {} threats ", threat_count),
-            Style::default().fg(CLR_THREAT).add_modifier(Modifier::BOLD),
+            Style::default().fg(theme().error).add_modifier(Modifier::BOLD),
         ));
     }
 
     let block = Block::default()
         .title(Line::from(title_spans))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(CLR_BORDER))
-        .style(Style::default().bg(CLR_OCEAN));
+        .border_style(Style::default().fg(theme().border))
+        .style(Style::default().bg(theme().bg));
 
     let paragraph = Paragraph::new(lines).block(block);
     f.render_widget(paragraph, area);
@@ -709,7 +688,7 @@ pub fn draw_world_map_dots(
             ms.canvas.filled_circle(px, py, r, color);
         }
 
-        if dot.color == CLR_THREAT {
+        if dot.color == theme().error {
             threat_count += 1;
         }
     }
@@ -718,16 +697,16 @@ pub fn draw_world_map_dots(
 
     // Legend
     let legend = Line::from(vec![
-        Span::styled(" \u{25CF}", Style::default().fg(CLR_NORMAL).bg(CLR_OCEAN)),
-        Span::styled(" Est ", Style::default().fg(Color::Rgb(100, 120, 150)).bg(CLR_OCEAN)),
-        Span::styled("\u{25CF}", Style::default().fg(CLR_CONNECTING).bg(CLR_OCEAN)),
-        Span::styled(" Syn ", Style::default().fg(Color::Rgb(100, 120, 150)).bg(CLR_OCEAN)),
-        Span::styled("\u{25CF}", Style::default().fg(CLR_SENDING).bg(CLR_OCEAN)),
-        Span::styled(" Wait ", Style::default().fg(Color::Rgb(100, 120, 150)).bg(CLR_OCEAN)),
-        Span::styled("\u{25CF}", Style::default().fg(CLR_DISCONNECTING).bg(CLR_OCEAN)),
-        Span::styled(" Close ", Style::default().fg(Color::Rgb(100, 120, 150)).bg(CLR_OCEAN)),
-        Span::styled("\u{25CF}", Style::default().fg(CLR_THREAT).bg(CLR_OCEAN)),
-        Span::styled(" Threat", Style::default().fg(Color::Rgb(100, 120, 150)).bg(CLR_OCEAN)),
+        Span::styled(" \u{25CF}", Style::default().fg(theme().ok).bg(theme().bg)),
+        Span::styled(" Est ", Style::default().fg(theme().text_dim).bg(theme().bg)),
+        Span::styled("\u{25CF}", Style::default().fg(theme().info).bg(theme().bg)),
+        Span::styled(" Syn ", Style::default().fg(theme().text_dim).bg(theme().bg)),
+        Span::styled("\u{25CF}", Style::default().fg(theme().purple).bg(theme().bg)),
+        Span::styled(" Wait ", Style::default().fg(theme().text_dim).bg(theme().bg)),
+        Span::styled("\u{25CF}", Style::default().fg(theme().caution).bg(theme().bg)),
+        Span::styled(" Close ", Style::default().fg(theme().text_dim).bg(theme().bg)),
+        Span::styled("\u{25CF}", Style::default().fg(theme().error).bg(theme().bg)),
+        Span::styled(" Threat", Style::default().fg(theme().text_dim).bg(theme().bg)),
     ]);
     lines.push(legend);
 
@@ -735,31 +714,31 @@ pub fn draw_world_map_dots(
     let mut title_spans = vec![
         Span::styled(
             " Connection Map ",
-            Style::default().fg(Color::Rgb(160, 180, 220)).add_modifier(Modifier::BOLD),
+            Style::default().fg(theme().text).add_modifier(Modifier::BOLD),
         ),
         Span::styled(
             format!(" {} live", live_count),
-            Style::default().fg(CLR_NORMAL),
+            Style::default().fg(theme().ok),
         ),
     ];
     if fading_count > 0 {
         title_spans.push(Span::styled(
             format!(" {} fading", fading_count),
-            Style::default().fg(Color::Rgb(100, 120, 150)),
+            Style::default().fg(theme().text_dim),
         ));
     }
     if threat_count > 0 {
         title_spans.push(Span::styled(
             format!(" {} threats ", threat_count),
-            Style::default().fg(CLR_THREAT).add_modifier(Modifier::BOLD),
+            Style::default().fg(theme().error).add_modifier(Modifier::BOLD),
         ));
     }
 
     let block = Block::default()
         .title(Line::from(title_spans))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(CLR_BORDER))
-        .style(Style::default().bg(CLR_OCEAN));
+        .border_style(Style::default().fg(theme().border))
+        .style(Style::default().bg(theme().bg));
 
     let paragraph = Paragraph::new(lines).block(block);
     f.render_widget(paragraph, area);